@@ -17,10 +17,14 @@ use std::sync::Arc;
 use digest::Digest;
 use secrecy::SecretString;
 use sha1::Sha1;
-use snafu::{ensure, OptionExt};
+use snafu::{ensure, OptionExt, ResultExt};
+use subtle::ConstantTimeEq;
 
-use crate::error::{IllegalParamSnafu, InvalidConfigSnafu, Result, UserPasswordMismatchSnafu};
+use crate::error::{
+    IllegalParamSnafu, InvalidConfigSnafu, Result, UserPasswordMismatchSnafu, VerifyPasswordSnafu,
+};
 use crate::user_info::DefaultUserInfo;
+use crate::user_provider::htpasswd_user_provider::{HtpasswdUserProvider, HTPASSWD_USER_PROVIDER};
 use crate::user_provider::static_user_provider::{StaticUserProvider, STATIC_USER_PROVIDER};
 use crate::{UserInfoRef, UserProviderRef};
 
@@ -43,6 +47,11 @@ pub fn user_provider_from_option(opt: &String) -> Result<UserProviderRef> {
                 StaticUserProvider::try_from(content).map(|p| Arc::new(p) as UserProviderRef)?;
             Ok(provider)
         }
+        HTPASSWD_USER_PROVIDER => {
+            let provider =
+                HtpasswdUserProvider::try_from(content).map(|p| Arc::new(p) as UserProviderRef)?;
+            Ok(provider)
+        }
         _ => InvalidConfigSnafu {
             value: name.to_string(),
             msg: "Invalid UserProviderOption",
@@ -67,6 +76,8 @@ pub enum Password<'a> {
     PlainText(SecretString),
     MysqlNativePassword(HashedPassword<'a>, Salt<'a>),
     PgMD5(HashedPassword<'a>, Salt<'a>),
+    /// The `ClientProof` and the `AuthMessage` of a PostgreSQL SCRAM-SHA-256 authentication.
+    PgScramSha256(&'a [u8], &'a [u8]),
 }
 
 pub fn auth_mysql(
@@ -74,6 +85,17 @@ pub fn auth_mysql(
     salt: Salt,
     username: &str,
     save_pwd: &[u8],
+) -> Result<()> {
+    auth_mysql_with_hash_stage_2(auth_data, salt, username, &double_sha1(save_pwd))
+}
+
+/// Same as [`auth_mysql`], but takes the double sha1 of the saved password (the "hash stage 2"
+/// in MySQL) instead of the plain text one.
+pub(crate) fn auth_mysql_with_hash_stage_2(
+    auth_data: HashedPassword,
+    salt: Salt,
+    username: &str,
+    hash_stage_2: &[u8],
 ) -> Result<()> {
     ensure!(
         auth_data.len() == 20,
@@ -82,15 +104,14 @@ pub fn auth_mysql(
         }
     );
     // ref: https://github.com/mysql/mysql-server/blob/a246bad76b9271cb4333634e954040a970222e0a/sql/auth/password.cc#L62
-    let hash_stage_2 = double_sha1(save_pwd);
-    let tmp = sha1_two(salt, &hash_stage_2);
+    let tmp = sha1_two(salt, hash_stage_2);
     // xor auth_data and tmp
     let mut xor_result = [0u8; 20];
     for i in 0..20 {
         xor_result[i] = auth_data[i] ^ tmp[i];
     }
     let candidate_stage_2 = sha1_one(&xor_result);
-    if candidate_stage_2 == hash_stage_2 {
+    if constant_time_eq(&candidate_stage_2, hash_stage_2) {
         Ok(())
     } else {
         UserPasswordMismatchSnafu {
@@ -113,10 +134,25 @@ fn sha1_one(data: &[u8]) -> Vec<u8> {
     hasher.finalize().to_vec()
 }
 
-fn double_sha1(data: &[u8]) -> Vec<u8> {
+pub(crate) fn double_sha1(data: &[u8]) -> Vec<u8> {
     sha1_one(&sha1_one(data))
 }
 
+/// Compares hashed passwords in constant time, so that they can't be guessed by timing.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Verifies passwords in a blocking thread, as hashing them is slow on purpose and would
+/// otherwise block the async runtime.
+pub(crate) async fn verify_password_blocking(
+    verify: impl FnOnce() -> bool + Send + 'static,
+) -> Result<bool> {
+    tokio::task::spawn_blocking(verify)
+        .await
+        .context(VerifyPasswordSnafu)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
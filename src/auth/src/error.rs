@@ -34,6 +34,18 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to watch file, source: {}", source))]
+    FileWatch {
+        source: notify::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to verify password, source: {}", source))]
+    VerifyPassword {
+        source: tokio::task::JoinError,
+        location: Location,
+    },
+
     #[snafu(display("Auth failed, source: {}", source))]
     AuthBackend {
         location: Location,
@@ -63,6 +75,33 @@ pub enum Error {
 
     #[snafu(display("User is not authorized to perform this action"))]
     PermissionDenied { location: Location },
+
+    #[snafu(display(
+        "Failed to access users and privileges in metadata, source: {}",
+        source
+    ))]
+    Metadata {
+        location: Location,
+        source: common_meta::error::Error,
+    },
+
+    #[snafu(display("User already exists, username: {}", username))]
+    UserAlreadyExists {
+        username: String,
+        location: Location,
+    },
+
+    #[snafu(display("Role already exists, role: {}", role))]
+    RoleAlreadyExists { role: String, location: Location },
+
+    #[snafu(display("User or role not found, name: {}", name))]
+    GranteeNotFound { name: String, location: Location },
+
+    #[snafu(display("Role not found, role: {}", role))]
+    RoleNotFound { role: String, location: Location },
+
+    #[snafu(display("Invalid object name: {}", name))]
+    InvalidObjectName { name: String, location: Location },
 }
 
 impl ErrorExt for Error {
@@ -72,6 +111,8 @@ impl ErrorExt for Error {
             Error::IllegalParam { .. } => StatusCode::InvalidArguments,
             Error::InternalState { .. } => StatusCode::Unexpected,
             Error::Io { .. } => StatusCode::Internal,
+            Error::FileWatch { .. } => StatusCode::Internal,
+            Error::VerifyPassword { .. } => StatusCode::Internal,
             Error::AuthBackend { .. } => StatusCode::Internal,
 
             Error::UserNotFound { .. } => StatusCode::UserNotFound,
@@ -79,6 +120,13 @@ impl ErrorExt for Error {
             Error::UserPasswordMismatch { .. } => StatusCode::UserPasswordMismatch,
             Error::AccessDenied { .. } => StatusCode::AccessDenied,
             Error::PermissionDenied { .. } => StatusCode::PermissionDenied,
+            Error::Metadata { source, .. } => source.status_code(),
+
+            Error::UserAlreadyExists { .. }
+            | Error::RoleAlreadyExists { .. }
+            | Error::GranteeNotFound { .. }
+            | Error::RoleNotFound { .. }
+            | Error::InvalidObjectName { .. } => StatusCode::InvalidArguments,
         }
     }
 
@@ -15,6 +15,8 @@
 mod common;
 pub mod error;
 mod permission;
+mod rbac;
+mod scram;
 mod user_info;
 mod user_provider;
 
@@ -25,8 +27,10 @@ pub use common::{
     auth_mysql, user_provider_from_option, userinfo_by_name, HashedPassword, Identity, Password,
 };
 pub use permission::{PermissionChecker, PermissionReq, PermissionResp};
+pub use rbac::{RbacManager, RbacManagerRef, RbacOptions, RBAC_USER_PROVIDER};
+pub use scram::{ScramSha256Verifier, SCRAM_SHA_256};
 pub use user_info::UserInfo;
-pub use user_provider::UserProvider;
+pub use user_provider::{PgAuthMethod, UserProvider};
 
 /// pub type alias
 pub type UserInfoRef = std::sync::Arc<dyn UserInfo>;
@@ -30,6 +30,13 @@ pub enum PermissionReq<'a> {
     PromStoreWrite,
     PromStoreRead,
     Otlp,
+    /// Reads the metadata of a schema, or a table in the schema if the table name is given,
+    /// e.g. the rows about them in `information_schema`.
+    ReadMetadata {
+        catalog: &'a str,
+        schema: &'a str,
+        table: Option<&'a str>,
+    },
 }
 
 #[derive(Debug)]
@@ -44,6 +51,22 @@ pub trait PermissionChecker: Send + Sync {
         user_info: Option<UserInfoRef>,
         req: PermissionReq,
     ) -> Result<PermissionResp>;
+
+    /// Checks the permission of a request issued in the database `catalog.schema`.
+    ///
+    /// Unqualified table names in the request are resolved in this database. Checkers
+    /// that don't care about the database can leave it to [`check_permission`].
+    ///
+    /// [`check_permission`]: PermissionChecker::check_permission
+    fn check_database_permission(
+        &self,
+        user_info: Option<UserInfoRef>,
+        _catalog: &str,
+        _schema: &str,
+        req: PermissionReq,
+    ) -> Result<PermissionResp> {
+        self.check_permission(user_info, req)
+    }
 }
 
 impl PermissionChecker for Option<&PermissionCheckerRef> {
@@ -61,4 +84,23 @@ impl PermissionChecker for Option<&PermissionCheckerRef> {
             None => Ok(PermissionResp::Allow),
         }
     }
+
+    fn check_database_permission(
+        &self,
+        user_info: Option<UserInfoRef>,
+        catalog: &str,
+        schema: &str,
+        req: PermissionReq,
+    ) -> Result<PermissionResp> {
+        match self {
+            Some(checker) => {
+                match checker.check_database_permission(user_info, catalog, schema, req) {
+                    Ok(PermissionResp::Reject) => PermissionDeniedSnafu.fail(),
+                    Ok(PermissionResp::Allow) => Ok(PermissionResp::Allow),
+                    Err(e) => Err(e),
+                }
+            }
+            None => Ok(PermissionResp::Allow),
+        }
+    }
 }
@@ -0,0 +1,898 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod access;
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_meta::key::user::{
+    GrantObject, Privilege, PrivilegeGrant, RoleKey, RoleValue, UserKey, UserManager, UserValue,
+};
+use common_meta::kv_backend::KvBackendRef;
+use secrecy::ExposeSecret;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::ObjectName;
+use sql::statements::privilege::{
+    CreateRole, CreateUser, Grant, GrantObject as SqlGrantObject, Grantable,
+    Privilege as SqlPrivilege, Revoke,
+};
+
+use crate::common::{auth_mysql_with_hash_stage_2, double_sha1, verify_password_blocking};
+use crate::error::{
+    AccessDeniedSnafu, GranteeNotFoundSnafu, IllegalParamSnafu, InvalidObjectNameSnafu,
+    MetadataSnafu, Result, RoleAlreadyExistsSnafu, RoleNotFoundSnafu, UnsupportedPasswordTypeSnafu,
+    UserAlreadyExistsSnafu, UserNotFoundSnafu, UserPasswordMismatchSnafu,
+};
+use crate::rbac::access::{Access, AccessResolver, Target};
+use crate::scram::ScramSha256Verifier;
+use crate::user_info::DefaultUserInfo;
+use crate::user_provider::static_user_provider::StaticUserProvider;
+use crate::{
+    Identity, Password, PermissionChecker, PermissionReq, PermissionResp, PgAuthMethod,
+    UserInfoRef, UserProvider,
+};
+
+pub const RBAC_USER_PROVIDER: &str = "rbac_user_provider";
+
+/// Iterations of the PBKDF2 to salt passwords, the same as the default of PostgreSQL.
+const SCRAM_ITERATIONS: u32 = 4096;
+
+pub type RbacManagerRef = Arc<RbacManager>;
+
+/// Options to enable role-based access control.
+#[derive(Debug, Clone)]
+pub struct RbacOptions {
+    /// Superusers in the same format as the static user provider, e.g. `cmd:root=123456`.
+    /// Superusers have all privileges, and are the only ones that can manage other users.
+    pub superusers: String,
+}
+
+/// Manages users, roles and their privileges in the metadata, and checks requests against them.
+///
+/// Privileges can be granted on all catalogs, a catalog, a schema or a table, to users or roles.
+/// Roles can be granted to users. A user has the privileges granted to itself and to its roles.
+pub struct RbacManager {
+    superusers: StaticUserProvider,
+    user_manager: UserManager,
+    /// Privileges of authenticated users, including those granted to their roles.
+    ///
+    /// Permission checks are synchronous, so privileges are loaded when users authenticate, and
+    /// reloaded when users or roles are changed, either through this manager or by other
+    /// frontends, which the metasrv notifies through the cache invalidation instructions.
+    privileges: RwLock<HashMap<String, BTreeSet<PrivilegeGrant>>>,
+}
+
+impl RbacManager {
+    pub fn try_new(options: &RbacOptions, kv_backend: KvBackendRef) -> Result<Self> {
+        Ok(Self {
+            superusers: StaticUserProvider::try_from(options.superusers.as_str())?,
+            user_manager: UserManager::new(kv_backend),
+            privileges: RwLock::new(HashMap::new()),
+        })
+    }
+
+    fn is_superuser(&self, username: &str) -> bool {
+        self.superusers.contains_user(username)
+    }
+
+    pub async fn create_user(&self, stmt: &CreateUser) -> Result<()> {
+        let username = &stmt.name.value;
+        ensure!(
+            !stmt.password.is_empty(),
+            IllegalParamSnafu {
+                msg: "blank password"
+            }
+        );
+        ensure!(
+            !self.is_superuser(username),
+            UserAlreadyExistsSnafu { username }
+        );
+
+        let value = new_user_value(&stmt.password);
+        let created = self
+            .user_manager
+            .create_user(UserKey::new(username), &value)
+            .await
+            .context(MetadataSnafu)?;
+        ensure!(
+            created || stmt.if_not_exists,
+            UserAlreadyExistsSnafu { username }
+        );
+        Ok(())
+    }
+
+    pub async fn create_role(&self, stmt: &CreateRole) -> Result<()> {
+        let role = &stmt.name.value;
+        let created = self
+            .user_manager
+            .create_role(RoleKey::new(role), &RoleValue::default())
+            .await
+            .context(MetadataSnafu)?;
+        ensure!(
+            created || stmt.if_not_exists,
+            RoleAlreadyExistsSnafu { role }
+        );
+        Ok(())
+    }
+
+    /// Executes the `GRANT` statement. Unqualified names are resolved in `catalog.schema`.
+    pub async fn grant(&self, stmt: &Grant, catalog: &str, schema: &str) -> Result<()> {
+        let grantee = &stmt.grantee.value;
+        match &stmt.grantable {
+            Grantable::Role(role) => {
+                let role = &role.value;
+                let _ = self
+                    .get_role(role)
+                    .await?
+                    .context(RoleNotFoundSnafu { role })?;
+                let _ = self
+                    .update_user(grantee, |user| {
+                        let _ = user.roles.insert(role.clone());
+                    })
+                    .await?
+                    .context(GranteeNotFoundSnafu { name: grantee })?;
+            }
+            Grantable::Privileges { privileges, object } => {
+                let grants = to_privilege_grants(privileges, object, catalog, schema)?;
+                self.update_grants(grantee, |granted| granted.extend(grants.iter().cloned()))
+                    .await?;
+            }
+        }
+        self.reload_privileges().await
+    }
+
+    /// Executes the `REVOKE` statement. Unqualified names are resolved in `catalog.schema`.
+    ///
+    /// Only the privileges granted on exactly the same object are revoked, i.e. revoking a
+    /// privilege on a table doesn't affect the privilege granted on its schema.
+    pub async fn revoke(&self, stmt: &Revoke, catalog: &str, schema: &str) -> Result<()> {
+        let grantee = &stmt.grantee.value;
+        match &stmt.grantable {
+            Grantable::Role(role) => {
+                let _ = self
+                    .update_user(grantee, |user| {
+                        let _ = user.roles.remove(&role.value);
+                    })
+                    .await?
+                    .context(GranteeNotFoundSnafu { name: grantee })?;
+            }
+            Grantable::Privileges { privileges, object } => {
+                let grants = to_privilege_grants(privileges, object, catalog, schema)?;
+                self.update_grants(grantee, |granted| {
+                    granted.retain(|grant| !grants.contains(grant))
+                })
+                .await?;
+            }
+        }
+        self.reload_privileges().await
+    }
+
+    /// Updates privileges granted to the user or the role named `grantee`.
+    async fn update_grants(
+        &self,
+        grantee: &str,
+        mut update: impl FnMut(&mut BTreeSet<PrivilegeGrant>),
+    ) -> Result<()> {
+        if self
+            .update_user(grantee, |user| update(&mut user.grants))
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+        if self
+            .update_role(grantee, |role| update(&mut role.grants))
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+        GranteeNotFoundSnafu { name: grantee }.fail()
+    }
+
+    async fn get_user(&self, username: &str) -> Result<Option<UserValue>> {
+        self.user_manager
+            .get_user(UserKey::new(username))
+            .await
+            .context(MetadataSnafu)
+    }
+
+    async fn update_user(
+        &self,
+        username: &str,
+        update: impl FnMut(&mut UserValue),
+    ) -> Result<Option<UserValue>> {
+        self.user_manager
+            .update_user(UserKey::new(username), update)
+            .await
+            .context(MetadataSnafu)
+    }
+
+    async fn get_role(&self, role: &str) -> Result<Option<RoleValue>> {
+        self.user_manager
+            .get_role(RoleKey::new(role))
+            .await
+            .context(MetadataSnafu)
+    }
+
+    async fn update_role(
+        &self,
+        role: &str,
+        update: impl FnMut(&mut RoleValue),
+    ) -> Result<Option<RoleValue>> {
+        self.user_manager
+            .update_role(RoleKey::new(role), update)
+            .await
+            .context(MetadataSnafu)
+    }
+
+    /// Resolves all privileges of the user, including those granted to its roles.
+    async fn resolve_privileges(&self, user: &UserValue) -> Result<BTreeSet<PrivilegeGrant>> {
+        let mut privileges = user.grants.clone();
+        for role in &user.roles {
+            // The role may have been removed from the metadata by hand.
+            if let Some(role) = self.get_role(role).await? {
+                privileges.extend(role.grants);
+            }
+        }
+        Ok(privileges)
+    }
+
+    /// Loads privileges of the user into the cache.
+    async fn load_privileges(&self, username: &str, user: &UserValue) -> Result<()> {
+        let privileges = self.resolve_privileges(user).await?;
+        let _ = self
+            .privileges
+            .write()
+            .unwrap()
+            .insert(username.to_string(), privileges);
+        Ok(())
+    }
+
+    /// Reloads privileges of all cached users from the metadata. It must be called after
+    /// users or roles are changed, so that the changes take effect on existing sessions.
+    pub async fn reload_privileges(&self) -> Result<()> {
+        let usernames: Vec<_> = self.privileges.read().unwrap().keys().cloned().collect();
+        for username in usernames {
+            match self.get_user(&username).await? {
+                Some(user) => self.load_privileges(&username, &user).await?,
+                None => {
+                    let _ = self.privileges.write().unwrap().remove(&username);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check(&self, username: &str, access: Access) -> PermissionResp {
+        if self.is_superuser(username) {
+            return PermissionResp::Allow;
+        }
+        let required = match access {
+            Access::Superuser => return PermissionResp::Reject,
+            Access::Privileges(required) => Required::All(required),
+            Access::AnyPrivilege(target) => Required::Any(target),
+        };
+
+        let privileges = self.privileges.read().unwrap();
+        let Some(granted) = privileges.get(username) else {
+            return PermissionResp::Reject;
+        };
+        let allowed = match required {
+            Required::All(required) => required
+                .iter()
+                .all(|(privilege, target)| is_granted(granted, *privilege, target)),
+            Required::Any(target) => has_any_privilege(granted, &target),
+        };
+        if allowed {
+            PermissionResp::Allow
+        } else {
+            PermissionResp::Reject
+        }
+    }
+}
+
+/// Privileges that a request requires, besides being a superuser.
+enum Required {
+    All(Vec<(Privilege, Target)>),
+    Any(Target),
+}
+
+/// Returns true if any privilege is granted on the table, or in the schema if the table is
+/// absent. Everyone can see `information_schema` itself.
+fn has_any_privilege(granted: &BTreeSet<PrivilegeGrant>, target: &Target) -> bool {
+    target.schema == INFORMATION_SCHEMA_NAME
+        || granted.iter().any(|grant| match &target.table {
+            Some(table) => grant
+                .object
+                .covers(&target.catalog, &target.schema, Some(table)),
+            None => grant
+                .object
+                .intersects_schema(&target.catalog, &target.schema),
+        })
+}
+
+fn is_granted(granted: &BTreeSet<PrivilegeGrant>, privilege: Privilege, target: &Target) -> bool {
+    // Everyone can read tables in `information_schema`, whose rows are filtered by
+    // the privileges of the user, see `PermissionReq::ReadMetadata`.
+    if privilege == Privilege::Read && target.schema == INFORMATION_SCHEMA_NAME {
+        return true;
+    }
+    granted.iter().any(|grant| {
+        grant.privilege == privilege
+            && grant
+                .object
+                .covers(&target.catalog, &target.schema, target.table.as_deref())
+    })
+}
+
+/// Passwords are saved as salted SCRAM-SHA-256 verifiers, which verify plain text passwords
+/// and PostgreSQL SCRAM-SHA-256 authentications. The MySQL native password authentication
+/// requires the unsalted double sha1 of the password, which is only used by that protocol.
+fn new_user_value(password: &str) -> UserValue {
+    let salt: [u8; 16] = rand::random();
+    UserValue {
+        password: ScramSha256Verifier::new(password, salt.to_vec(), SCRAM_ITERATIONS).to_string(),
+        mysql_native_password: Some(hex::encode(double_sha1(password.as_bytes()))),
+        ..Default::default()
+    }
+}
+
+fn to_privilege_grants(
+    privileges: &[SqlPrivilege],
+    object: &SqlGrantObject,
+    catalog: &str,
+    schema: &str,
+) -> Result<Vec<PrivilegeGrant>> {
+    let object = to_grant_object(object, catalog, schema)?;
+    Ok(privileges
+        .iter()
+        .flat_map(|privilege| match privilege {
+            SqlPrivilege::Read => vec![Privilege::Read],
+            SqlPrivilege::Write => vec![Privilege::Write],
+            SqlPrivilege::All => vec![Privilege::Read, Privilege::Write],
+        })
+        .map(|privilege| PrivilegeGrant::new(object.clone(), privilege))
+        .collect())
+}
+
+fn to_grant_object(object: &SqlGrantObject, catalog: &str, schema: &str) -> Result<GrantObject> {
+    let idents = |name: &ObjectName| -> Vec<String> {
+        name.0.iter().map(|ident| ident.value.clone()).collect()
+    };
+    let object = match object {
+        SqlGrantObject::All => GrantObject::All,
+        SqlGrantObject::Catalog(catalog) => GrantObject::Catalog(catalog.value.clone()),
+        SqlGrantObject::Schema(name) => match &idents(name)[..] {
+            [c, s] => GrantObject::Schema {
+                catalog: c.clone(),
+                schema: s.clone(),
+            },
+            [s] => GrantObject::Schema {
+                catalog: catalog.to_string(),
+                schema: s.clone(),
+            },
+            _ => {
+                return InvalidObjectNameSnafu {
+                    name: name.to_string(),
+                }
+                .fail()
+            }
+        },
+        SqlGrantObject::Table(name) => {
+            let (c, s, t) = match &idents(name)[..] {
+                [c, s, t] => (c.clone(), s.clone(), t.clone()),
+                [s, t] => (catalog.to_string(), s.clone(), t.clone()),
+                [t] => (catalog.to_string(), schema.to_string(), t.clone()),
+                _ => {
+                    return InvalidObjectNameSnafu {
+                        name: name.to_string(),
+                    }
+                    .fail()
+                }
+            };
+            GrantObject::Table {
+                catalog: c,
+                schema: s,
+                table: t,
+            }
+        }
+    };
+    Ok(object)
+}
+
+#[async_trait]
+impl UserProvider for RbacManager {
+    fn name(&self) -> &str {
+        RBAC_USER_PROVIDER
+    }
+
+    async fn authenticate(&self, id: Identity<'_>, password: Password<'_>) -> Result<UserInfoRef> {
+        let Identity::UserId(username, _) = id;
+        if self.is_superuser(username) {
+            return self.superusers.authenticate(id, password).await;
+        }
+        ensure!(
+            !username.is_empty(),
+            IllegalParamSnafu {
+                msg: "blank username"
+            }
+        );
+
+        let user = self
+            .get_user(username)
+            .await?
+            .context(UserNotFoundSnafu { username })?;
+        let verifier = ScramSha256Verifier::parse(&user.password)
+            .context(UserPasswordMismatchSnafu { username })?;
+        match password {
+            Password::PlainText(pwd) => {
+                let pwd = pwd.expose_secret().clone();
+                ensure!(
+                    verify_password_blocking(move || verifier.verify_password(&pwd)).await?,
+                    UserPasswordMismatchSnafu { username }
+                );
+            }
+            Password::MysqlNativePassword(auth_data, salt) => {
+                let hash_stage_2 = user
+                    .mysql_native_password
+                    .as_deref()
+                    .and_then(|hash| hex::decode(hash).ok())
+                    .context(UnsupportedPasswordTypeSnafu {
+                        password_type: "mysql_native_password",
+                    })?;
+                auth_mysql_with_hash_stage_2(auth_data, salt, username, &hash_stage_2)?;
+            }
+            Password::PgMD5(_, _) => {
+                return UnsupportedPasswordTypeSnafu {
+                    password_type: "pg_md5",
+                }
+                .fail();
+            }
+            Password::PgScramSha256(client_proof, auth_message) => {
+                ensure!(
+                    verifier.verify_client_proof(auth_message, client_proof),
+                    UserPasswordMismatchSnafu { username }
+                );
+            }
+        }
+
+        self.load_privileges(username, &user).await?;
+        Ok(DefaultUserInfo::with_name(username))
+    }
+
+    async fn authorize(&self, catalog: &str, schema: &str, user_info: &UserInfoRef) -> Result<()> {
+        let username = user_info.username();
+        if self.is_superuser(username) || schema == INFORMATION_SCHEMA_NAME {
+            return Ok(());
+        }
+
+        if !self.privileges.read().unwrap().contains_key(username) {
+            if let Some(user) = self.get_user(username).await? {
+                self.load_privileges(username, &user).await?;
+            }
+        }
+        // Users can connect to a schema if they have any privilege in it.
+        let target = Target {
+            catalog: catalog.to_string(),
+            schema: schema.to_string(),
+            table: None,
+        };
+        let authorized = self
+            .privileges
+            .read()
+            .unwrap()
+            .get(username)
+            .map(|granted| has_any_privilege(granted, &target))
+            .unwrap_or(false);
+        ensure!(
+            authorized,
+            AccessDeniedSnafu {
+                catalog,
+                schema,
+                username,
+            }
+        );
+        Ok(())
+    }
+
+    async fn pg_auth_method(&self, username: &str) -> Result<PgAuthMethod> {
+        if self.is_superuser(username) {
+            return self.superusers.pg_auth_method(username).await;
+        }
+        // Unknown users go through the cleartext password flow, and fail in [`authenticate`].
+        let verifier = self
+            .get_user(username)
+            .await?
+            .and_then(|user| ScramSha256Verifier::parse(&user.password));
+        Ok(verifier
+            .map(PgAuthMethod::ScramSha256)
+            .unwrap_or(PgAuthMethod::CleartextPassword))
+    }
+}
+
+impl PermissionChecker for RbacManager {
+    fn check_permission(
+        &self,
+        user_info: Option<UserInfoRef>,
+        req: PermissionReq,
+    ) -> Result<PermissionResp> {
+        self.check_database_permission(
+            user_info,
+            common_catalog::consts::DEFAULT_CATALOG_NAME,
+            common_catalog::consts::DEFAULT_SCHEMA_NAME,
+            req,
+        )
+    }
+
+    fn check_database_permission(
+        &self,
+        user_info: Option<UserInfoRef>,
+        catalog: &str,
+        schema: &str,
+        req: PermissionReq,
+    ) -> Result<PermissionResp> {
+        // Requests without a user come from internal components, or from servers
+        // that authentication is not enabled.
+        let Some(user_info) = user_info else {
+            return Ok(PermissionResp::Allow);
+        };
+        let access = AccessResolver::new(catalog, schema).resolve(&req)?;
+        Ok(self.check(user_info.username(), access))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_meta::kv_backend::memory::MemoryKvBackend;
+    use sha1::{Digest, Sha1};
+    use sql::ast::Ident;
+    use sql::dialect::GreptimeDbDialect;
+    use sql::parser::ParserContext;
+    use sql::statements::statement::Statement;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Statement {
+        ParserContext::create_with_dialect(sql, &GreptimeDbDialect {})
+            .unwrap()
+            .remove(0)
+    }
+
+    async fn execute(manager: &RbacManager, sql: &str) -> Result<()> {
+        match parse(sql) {
+            Statement::CreateUser(stmt) => manager.create_user(&stmt).await,
+            Statement::CreateRole(stmt) => manager.create_role(&stmt).await,
+            Statement::Grant(stmt) => manager.grant(&stmt, "greptime", "public").await,
+            Statement::Revoke(stmt) => manager.revoke(&stmt, "greptime", "public").await,
+            _ => unreachable!(),
+        }
+    }
+
+    async fn login(manager: &RbacManager, username: &str, password: &str) -> Result<UserInfoRef> {
+        manager
+            .authenticate(
+                Identity::UserId(username, None),
+                Password::PlainText(password.to_string().into()),
+            )
+            .await
+    }
+
+    fn check(manager: &RbacManager, user_info: &UserInfoRef, sql: &str) -> PermissionResp {
+        let stmt = parse(sql);
+        manager
+            .check_database_permission(
+                Some(user_info.clone()),
+                "greptime",
+                "public",
+                PermissionReq::SqlStatement(&stmt),
+            )
+            .unwrap()
+    }
+
+    fn check_metadata(
+        manager: &RbacManager,
+        user_info: &UserInfoRef,
+        schema: &str,
+        table: Option<&str>,
+    ) -> PermissionResp {
+        manager
+            .check_permission(
+                Some(user_info.clone()),
+                PermissionReq::ReadMetadata {
+                    catalog: "greptime",
+                    schema,
+                    table,
+                },
+            )
+            .unwrap()
+    }
+
+    fn new_manager() -> RbacManager {
+        new_manager_with(Arc::new(MemoryKvBackend::default()))
+    }
+
+    fn new_manager_with(kv_backend: KvBackendRef) -> RbacManager {
+        let options = RbacOptions {
+            superusers: "cmd:root=123456".to_string(),
+        };
+        RbacManager::try_new(&options, kv_backend).unwrap()
+    }
+
+    #[test]
+    fn test_to_grant_object() {
+        let name = |parts: &[&str]| ObjectName(parts.iter().map(|p| Ident::new(*p)).collect());
+
+        assert_eq!(
+            GrantObject::Table {
+                catalog: "greptime".to_string(),
+                schema: "s".to_string(),
+                table: "t".to_string(),
+            },
+            to_grant_object(
+                &SqlGrantObject::Table(name(&["s", "t"])),
+                "greptime",
+                "public"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            GrantObject::Schema {
+                catalog: "c".to_string(),
+                schema: "s".to_string(),
+            },
+            to_grant_object(
+                &SqlGrantObject::Schema(name(&["c", "s"])),
+                "greptime",
+                "public"
+            )
+            .unwrap()
+        );
+
+        for object in [
+            SqlGrantObject::Table(name(&["x", "c", "s", "t"])),
+            SqlGrantObject::Table(name(&[])),
+            SqlGrantObject::Schema(name(&["x", "c", "s"])),
+            SqlGrantObject::Schema(name(&[])),
+        ] {
+            assert!(to_grant_object(&object, "greptime", "public").is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_user() {
+        let manager = new_manager();
+        execute(&manager, "CREATE USER alice IDENTIFIED BY 'pwd'")
+            .await
+            .unwrap();
+        assert!(matches!(
+            execute(&manager, "CREATE USER alice IDENTIFIED BY 'pwd'").await,
+            Err(crate::error::Error::UserAlreadyExists { .. })
+        ));
+        execute(
+            &manager,
+            "CREATE USER IF NOT EXISTS alice IDENTIFIED BY 'other'",
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            execute(&manager, "CREATE USER root IDENTIFIED BY 'pwd'").await,
+            Err(crate::error::Error::UserAlreadyExists { .. })
+        ));
+
+        let _ = login(&manager, "alice", "pwd").await.unwrap();
+        let _ = login(&manager, "root", "123456").await.unwrap();
+        assert!(matches!(
+            login(&manager, "alice", "other").await,
+            Err(crate::error::Error::UserPasswordMismatch { .. })
+        ));
+        assert!(matches!(
+            login(&manager, "bob", "pwd").await,
+            Err(crate::error::Error::UserNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_grant_and_revoke() {
+        let manager = new_manager();
+        execute(&manager, "CREATE USER alice IDENTIFIED BY 'pwd'")
+            .await
+            .unwrap();
+        let alice = login(&manager, "alice", "pwd").await.unwrap();
+        let root = login(&manager, "root", "123456").await.unwrap();
+
+        assert!(matches!(
+            check(&manager, &alice, "SELECT * FROM foo"),
+            PermissionResp::Reject
+        ));
+        assert!(matches!(
+            check(&manager, &alice, "SELECT * FROM information_schema.tables"),
+            PermissionResp::Allow
+        ));
+        assert!(manager
+            .authorize("greptime", "public", &alice)
+            .await
+            .is_err());
+
+        execute(&manager, "GRANT READ ON foo TO alice")
+            .await
+            .unwrap();
+        assert!(matches!(
+            check(&manager, &alice, "SELECT * FROM foo"),
+            PermissionResp::Allow
+        ));
+        assert!(matches!(
+            check(
+                &manager,
+                &alice,
+                "SELECT * FROM foo JOIN bar ON foo.a = bar.a"
+            ),
+            PermissionResp::Reject
+        ));
+        assert!(matches!(
+            check(&manager, &alice, "INSERT INTO foo VALUES (1)"),
+            PermissionResp::Reject
+        ));
+        manager
+            .authorize("greptime", "public", &alice)
+            .await
+            .unwrap();
+
+        execute(&manager, "CREATE ROLE writer").await.unwrap();
+        execute(&manager, "GRANT ALL ON public.* TO writer")
+            .await
+            .unwrap();
+        execute(&manager, "GRANT writer TO alice").await.unwrap();
+        assert!(matches!(
+            check(&manager, &alice, "INSERT INTO bar SELECT * FROM foo"),
+            PermissionResp::Allow
+        ));
+        assert!(matches!(
+            check(&manager, &alice, "DROP TABLE other.foo"),
+            PermissionResp::Reject
+        ));
+
+        execute(&manager, "REVOKE writer FROM alice").await.unwrap();
+        assert!(matches!(
+            check(&manager, &alice, "INSERT INTO bar SELECT * FROM foo"),
+            PermissionResp::Reject
+        ));
+        execute(&manager, "REVOKE READ ON greptime.public.foo FROM alice")
+            .await
+            .unwrap();
+        assert!(matches!(
+            check(&manager, &alice, "SELECT * FROM foo"),
+            PermissionResp::Reject
+        ));
+
+        assert!(matches!(
+            check(&manager, &alice, "GRANT READ ON *.* TO alice"),
+            PermissionResp::Reject
+        ));
+        assert!(matches!(
+            check(&manager, &root, "GRANT READ ON *.* TO alice"),
+            PermissionResp::Allow
+        ));
+        assert!(matches!(
+            execute(&manager, "GRANT READ ON *.* TO bob").await,
+            Err(crate::error::Error::GranteeNotFound { .. })
+        ));
+        assert!(matches!(
+            execute(&manager, "GRANT reader TO alice").await,
+            Err(crate::error::Error::RoleNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_salted_password() {
+        let manager = new_manager();
+        execute(&manager, "CREATE USER alice IDENTIFIED BY 'pwd'")
+            .await
+            .unwrap();
+        execute(&manager, "CREATE USER bob IDENTIFIED BY 'pwd'")
+            .await
+            .unwrap();
+        let alice = manager.get_user("alice").await.unwrap().unwrap();
+        let bob = manager.get_user("bob").await.unwrap().unwrap();
+        assert_ne!(alice.password, bob.password);
+        assert!(alice.password.starts_with("SCRAM-SHA-256$4096:"));
+
+        // MySQL native password
+        let salt = b"01234567890123456789";
+        let stage_1 = Sha1::digest("pwd");
+        let stage_2 = Sha1::digest(stage_1);
+        let mut auth_data = Sha1::digest([salt.as_slice(), stage_2.as_slice()].concat()).to_vec();
+        auth_data.iter_mut().zip(stage_1).for_each(|(a, b)| *a ^= b);
+        let _ = manager
+            .authenticate(
+                Identity::UserId("alice", None),
+                Password::MysqlNativePassword(&auth_data, salt),
+            )
+            .await
+            .unwrap();
+
+        // PostgreSQL SCRAM-SHA-256
+        let PgAuthMethod::ScramSha256(verifier) = manager.pg_auth_method("alice").await.unwrap()
+        else {
+            unreachable!()
+        };
+        assert!(verifier.verify_password("pwd"));
+        assert!(matches!(
+            manager.pg_auth_method("unknown").await.unwrap(),
+            PgAuthMethod::CleartextPassword
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_metadata() {
+        let manager = new_manager();
+        execute(&manager, "CREATE USER alice IDENTIFIED BY 'pwd'")
+            .await
+            .unwrap();
+        execute(&manager, "GRANT READ ON foo TO alice")
+            .await
+            .unwrap();
+        let alice = login(&manager, "alice", "pwd").await.unwrap();
+
+        for (schema, table, allowed) in [
+            ("public", None, true),
+            ("public", Some("foo"), true),
+            ("public", Some("bar"), false),
+            ("other", None, false),
+            ("other", Some("foo"), false),
+            (INFORMATION_SCHEMA_NAME, None, true),
+            (INFORMATION_SCHEMA_NAME, Some("tables"), true),
+        ] {
+            assert_eq!(
+                allowed,
+                matches!(
+                    check_metadata(&manager, &alice, schema, table),
+                    PermissionResp::Allow
+                ),
+                "{schema}.{table:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_privileges() {
+        let kv_backend: KvBackendRef = Arc::new(MemoryKvBackend::default());
+        let manager = new_manager_with(kv_backend.clone());
+        // Another frontend sharing the same metadata.
+        let other = new_manager_with(kv_backend);
+        execute(&manager, "CREATE USER alice IDENTIFIED BY 'pwd'")
+            .await
+            .unwrap();
+        execute(&manager, "GRANT READ ON foo TO alice")
+            .await
+            .unwrap();
+        let alice = login(&other, "alice", "pwd").await.unwrap();
+        assert!(matches!(
+            check(&other, &alice, "SELECT * FROM foo"),
+            PermissionResp::Allow
+        ));
+
+        execute(&manager, "REVOKE READ ON foo FROM alice")
+            .await
+            .unwrap();
+        other.reload_privileges().await.unwrap();
+        assert!(matches!(
+            check(&other, &alice, "SELECT * FROM foo"),
+            PermissionResp::Reject
+        ));
+    }
+}
@@ -0,0 +1,606 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Finds out which privileges a [PermissionReq] requires.
+
+use std::ops::ControlFlow;
+
+use api::v1::ddl_request::Expr as DdlExpr;
+use api::v1::greptime_request::Request;
+use api::v1::query_request::Query;
+use common_meta::key::user::Privilege;
+use sql::ast::{Expr, ObjectName, Query as SpQuery, Visit, Visitor};
+use sql::statements::copy::{Copy, CopyTable};
+use sql::statements::statement::Statement;
+
+use crate::error::{InvalidObjectNameSnafu, Result};
+use crate::PermissionReq;
+
+/// Functions that manage the cluster, e.g. moving regions between datanodes. Only superusers
+/// can call them.
+const ADMIN_FUNCTIONS: [&str; 1] = ["migrate_region"];
+
+/// A schema or a table that a request accesses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Target {
+    pub(crate) catalog: String,
+    pub(crate) schema: String,
+    /// `None` if the request accesses the whole schema.
+    pub(crate) table: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Access {
+    /// The request requires all these privileges. No privilege is required if it's empty.
+    Privileges(Vec<(Privilege, Target)>),
+    /// The request requires any privilege on the table, or in the schema if the table is
+    /// absent, e.g. reading the metadata of the schema or the table.
+    AnyPrivilege(Target),
+    /// Only superusers can issue the request, e.g. managing users and privileges.
+    Superuser,
+}
+
+/// Resolves the privileges that requests require in the database `catalog.schema`.
+pub(crate) struct AccessResolver<'a> {
+    catalog: &'a str,
+    schema: &'a str,
+}
+
+impl<'a> AccessResolver<'a> {
+    pub(crate) fn new(catalog: &'a str, schema: &'a str) -> Self {
+        Self { catalog, schema }
+    }
+
+    pub(crate) fn resolve(&self, req: &PermissionReq) -> Result<Access> {
+        let privileges = match req {
+            PermissionReq::SqlStatement(stmt) => return self.resolve_statement(stmt),
+            PermissionReq::GrpcRequest(request) => self.resolve_grpc_request(request),
+            PermissionReq::PromQuery | PermissionReq::PromStoreRead => {
+                vec![(Privilege::Read, self.current_schema())]
+            }
+            PermissionReq::Opentsdb
+            | PermissionReq::LineProtocol
+            | PermissionReq::PromStoreWrite
+            | PermissionReq::Otlp => vec![(Privilege::Write, self.current_schema())],
+            PermissionReq::ReadMetadata {
+                catalog,
+                schema,
+                table,
+            } => {
+                return Ok(Access::AnyPrivilege(Target {
+                    catalog: catalog.to_string(),
+                    schema: schema.to_string(),
+                    table: table.map(|t| t.to_string()),
+                }))
+            }
+        };
+        Ok(Access::Privileges(privileges))
+    }
+
+    fn resolve_statement(&self, stmt: &Statement) -> Result<Access> {
+        let privileges = match stmt {
+            Statement::Query(query) if calls_admin_function(&query.inner) => {
+                return Ok(Access::Superuser)
+            }
+            Statement::Insert(insert) if calls_admin_function(&insert.inner) => {
+                return Ok(Access::Superuser)
+            }
+            // `EXPLAIN ANALYZE` executes the query.
+            Statement::Explain(explain) if calls_admin_function(&explain.inner) => {
+                return Ok(Access::Superuser)
+            }
+            Statement::Delete(delete) if calls_admin_function(&delete.inner) => {
+                return Ok(Access::Superuser)
+            }
+            Statement::CreateView(create) if calls_admin_function(&create.query.inner) => {
+                return Ok(Access::Superuser)
+            }
+            Statement::CreateMaterializedView(create)
+                if calls_admin_function(&create.query.inner) =>
+            {
+                return Ok(Access::Superuser)
+            }
+            Statement::Query(query) => self.read_relations(&query.inner)?,
+            Statement::Explain(explain) => self.read_relations(&explain.inner)?,
+            Statement::Insert(insert) => {
+                let table_name = insert.table_name();
+                let mut privileges: Vec<_> = collect_relations(&insert.inner)
+                    .iter()
+                    .filter(|name| *name != table_name)
+                    .map(|name| Ok((Privilege::Read, self.table(name)?)))
+                    .collect::<Result<_>>()?;
+                privileges.push((Privilege::Write, self.table(table_name)?));
+                privileges
+            }
+            Statement::Delete(delete) => collect_relations(&delete.inner)
+                .iter()
+                .map(|name| Ok((Privilege::Write, self.table(name)?)))
+                .collect::<Result<_>>()?,
+            Statement::CreateTable(stmt) => vec![(Privilege::Write, self.table(&stmt.name)?)],
+            Statement::CreateExternalTable(stmt) => {
+                vec![(Privilege::Write, self.table(&stmt.name)?)]
+            }
+            Statement::CreateMaterializedView(stmt) => {
+                let mut privileges = self.read_relations(&stmt.query.inner)?;
+                privileges.push((Privilege::Write, self.table(&stmt.name)?));
+                privileges
+            }
+            Statement::CreateView(stmt) => {
+                let mut privileges = self.read_relations(&stmt.query.inner)?;
+                privileges.push((Privilege::Write, self.table(&stmt.name)?));
+                privileges
+            }
+            Statement::DropTable(stmt) => vec![(Privilege::Write, self.table(stmt.table_name())?)],
+            Statement::DropView(stmt) => vec![(Privilege::Write, self.table(stmt.view_name())?)],
+            Statement::Alter(stmt) => vec![(Privilege::Write, self.table(stmt.table_name())?)],
+            Statement::TruncateTable(stmt) => {
+                vec![(Privilege::Write, self.table(stmt.table_name())?)]
+            }
+            Statement::CreateDatabase(stmt) => vec![(Privilege::Write, self.schema(&stmt.name)?)],
+            Statement::ShowCreateTable(stmt) => {
+                vec![(Privilege::Read, self.table(&stmt.table_name)?)]
+            }
+            Statement::ShowCreateView(stmt) => {
+                vec![(Privilege::Read, self.table(&stmt.view_name)?)]
+            }
+            Statement::DescribeTable(stmt) => vec![(Privilege::Read, self.table(stmt.name())?)],
+            Statement::Copy(Copy::CopyTable(CopyTable::To(arg))) => {
+                vec![(Privilege::Read, self.table(&arg.table_name)?)]
+            }
+            Statement::Copy(Copy::CopyTable(CopyTable::From(arg))) => {
+                vec![(Privilege::Write, self.table(&arg.table_name)?)]
+            }
+            Statement::Copy(Copy::CopyDatabase(arg)) => {
+                vec![(Privilege::Read, self.schema(&arg.database_name)?)]
+            }
+            Statement::Tql(_) => vec![(Privilege::Read, self.current_schema())],
+            // Only names are listed, and users can't see anything more.
+            Statement::ShowDatabases(_)
+            | Statement::ShowTables(_)
+            | Statement::ShowMaterializedViews(_) => vec![],
+            Statement::CreateUser(_)
+            | Statement::CreateRole(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_) => return Ok(Access::Superuser),
+        };
+        Ok(Access::Privileges(privileges))
+    }
+
+    fn resolve_grpc_request(&self, request: &Request) -> Vec<(Privilege, Target)> {
+        match request {
+            Request::Inserts(requests) => requests
+                .inserts
+                .iter()
+                .map(|r| {
+                    (
+                        Privilege::Write,
+                        self.table_in_current_schema(&r.table_name),
+                    )
+                })
+                .collect(),
+            Request::RowInserts(requests) => requests
+                .inserts
+                .iter()
+                .map(|r| {
+                    (
+                        Privilege::Write,
+                        self.table_in_current_schema(&r.table_name),
+                    )
+                })
+                .collect(),
+            Request::Deletes(requests) => requests
+                .deletes
+                .iter()
+                .map(|r| {
+                    (
+                        Privilege::Write,
+                        self.table_in_current_schema(&r.table_name),
+                    )
+                })
+                .collect(),
+            Request::RowDeletes(requests) => requests
+                .deletes
+                .iter()
+                .map(|r| {
+                    (
+                        Privilege::Write,
+                        self.table_in_current_schema(&r.table_name),
+                    )
+                })
+                .collect(),
+            Request::Query(request) => match &request.query {
+                // SQL statements are checked one by one when they are executed.
+                Some(Query::Sql(_)) | None => vec![],
+                Some(Query::LogicalPlan(_)) | Some(Query::PromRangeQuery(_)) => {
+                    vec![(Privilege::Read, self.current_schema())]
+                }
+            },
+            Request::Ddl(request) => {
+                let target = match &request.expr {
+                    Some(DdlExpr::CreateDatabase(expr)) => Target {
+                        catalog: self.catalog.to_string(),
+                        schema: expr.database_name.clone(),
+                        table: None,
+                    },
+                    Some(DdlExpr::CreateTable(expr)) => self.qualified_table(
+                        &expr.catalog_name,
+                        &expr.schema_name,
+                        &expr.table_name,
+                    ),
+                    Some(DdlExpr::Alter(expr)) => self.qualified_table(
+                        &expr.catalog_name,
+                        &expr.schema_name,
+                        &expr.table_name,
+                    ),
+                    Some(DdlExpr::DropTable(expr)) => self.qualified_table(
+                        &expr.catalog_name,
+                        &expr.schema_name,
+                        &expr.table_name,
+                    ),
+                    Some(DdlExpr::FlushTable(expr)) => self.qualified_table(
+                        &expr.catalog_name,
+                        &expr.schema_name,
+                        &expr.table_name,
+                    ),
+                    Some(DdlExpr::CompactTable(expr)) => self.qualified_table(
+                        &expr.catalog_name,
+                        &expr.schema_name,
+                        &expr.table_name,
+                    ),
+                    Some(DdlExpr::TruncateTable(expr)) => self.qualified_table(
+                        &expr.catalog_name,
+                        &expr.schema_name,
+                        &expr.table_name,
+                    ),
+                    None => return vec![],
+                };
+                vec![(Privilege::Write, target)]
+            }
+        }
+    }
+
+    fn read_relations<V: Visit>(&self, node: &V) -> Result<Vec<(Privilege, Target)>> {
+        collect_relations(node)
+            .iter()
+            .map(|name| Ok((Privilege::Read, self.table(name)?)))
+            .collect()
+    }
+
+    fn current_schema(&self) -> Target {
+        Target {
+            catalog: self.catalog.to_string(),
+            schema: self.schema.to_string(),
+            table: None,
+        }
+    }
+
+    /// Resolves `[catalog.]schema`.
+    fn schema(&self, name: &ObjectName) -> Result<Target> {
+        let (catalog, schema) = match &name.0[..] {
+            [schema] => (self.catalog, schema.value.as_str()),
+            [catalog, schema] => (catalog.value.as_str(), schema.value.as_str()),
+            _ => {
+                return InvalidObjectNameSnafu {
+                    name: name.to_string(),
+                }
+                .fail()
+            }
+        };
+        Ok(Target {
+            catalog: catalog.to_string(),
+            schema: schema.to_string(),
+            table: None,
+        })
+    }
+
+    /// Resolves `[[catalog.]schema.]table`.
+    fn table(&self, name: &ObjectName) -> Result<Target> {
+        let (catalog, schema, table) = match &name.0[..] {
+            [table] => (self.catalog, self.schema, table.value.as_str()),
+            [schema, table] => (self.catalog, schema.value.as_str(), table.value.as_str()),
+            [catalog, schema, table] => (
+                catalog.value.as_str(),
+                schema.value.as_str(),
+                table.value.as_str(),
+            ),
+            _ => {
+                return InvalidObjectNameSnafu {
+                    name: name.to_string(),
+                }
+                .fail()
+            }
+        };
+        Ok(Target {
+            catalog: catalog.to_string(),
+            schema: schema.to_string(),
+            table: Some(table.to_string()),
+        })
+    }
+
+    fn table_in_current_schema(&self, table: &str) -> Target {
+        self.qualified_table("", "", table)
+    }
+
+    /// Empty catalog or schema in gRPC requests means the current one.
+    fn qualified_table(&self, catalog: &str, schema: &str, table: &str) -> Target {
+        Target {
+            catalog: if catalog.is_empty() {
+                self.catalog
+            } else {
+                catalog
+            }
+            .to_string(),
+            schema: if schema.is_empty() {
+                self.schema
+            } else {
+                schema
+            }
+            .to_string(),
+            table: Some(table.to_string()),
+        }
+    }
+}
+
+/// Collects tables referenced by a statement or query, except common table expressions.
+fn collect_relations<V: Visit>(node: &V) -> Vec<ObjectName> {
+    /// The common table expressions defined by a query.
+    struct CteScope {
+        /// Names of the CTEs and their queries. The queries are only compared by addresses to
+        /// find out whether a CTE is being visited.
+        ctes: Vec<(String, *const SpQuery)>,
+        recursive: bool,
+        /// The number of CTEs visible now. A CTE is visible in the body of the query and the
+        /// CTEs after it, or also in itself if they are recursive.
+        visible: usize,
+    }
+
+    impl CteScope {
+        fn position(&self, query: &SpQuery) -> Option<usize> {
+            self.ctes
+                .iter()
+                .position(|(_, cte)| std::ptr::eq(*cte, query))
+        }
+    }
+
+    #[derive(Default)]
+    struct RelationCollector {
+        /// Scopes of the queries being visited, from the outermost to the innermost.
+        scopes: Vec<CteScope>,
+        relations: Vec<ObjectName>,
+    }
+
+    impl RelationCollector {
+        fn is_cte(&self, name: &ObjectName) -> bool {
+            let [ident] = &name.0[..] else {
+                return false;
+            };
+            self.scopes.iter().any(|scope| {
+                scope.ctes[..scope.visible]
+                    .iter()
+                    .any(|(cte, _)| *cte == ident.value)
+            })
+        }
+    }
+
+    impl Visitor for RelationCollector {
+        type Break = ();
+
+        fn pre_visit_query(&mut self, query: &SpQuery) -> ControlFlow<Self::Break> {
+            if let Some(scope) = self.scopes.last_mut() {
+                if let Some(i) = scope.position(query) {
+                    scope.visible = if scope.recursive { i + 1 } else { i };
+                }
+            }
+
+            let (ctes, recursive) = match &query.with {
+                Some(with) => (
+                    with.cte_tables
+                        .iter()
+                        .map(|cte| (cte.alias.name.value.clone(), &*cte.query as *const SpQuery))
+                        .collect::<Vec<_>>(),
+                    with.recursive,
+                ),
+                None => (vec![], false),
+            };
+            self.scopes.push(CteScope {
+                visible: ctes.len(),
+                ctes,
+                recursive,
+            });
+            ControlFlow::Continue(())
+        }
+
+        fn post_visit_query(&mut self, query: &SpQuery) -> ControlFlow<Self::Break> {
+            let _ = self.scopes.pop();
+            if let Some(scope) = self.scopes.last_mut() {
+                if scope.position(query).is_some() {
+                    scope.visible = scope.ctes.len();
+                }
+            }
+            ControlFlow::Continue(())
+        }
+
+        fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+            if !self.is_cte(relation) {
+                self.relations.push(relation.clone());
+            }
+            ControlFlow::Continue(())
+        }
+    }
+
+    let mut collector = RelationCollector::default();
+    let _ = node.visit(&mut collector);
+
+    let mut relations = collector.relations;
+    relations.dedup();
+    relations
+}
+
+/// Returns true if a statement or query calls any of the [`ADMIN_FUNCTIONS`].
+fn calls_admin_function<V: Visit>(node: &V) -> bool {
+    struct AdminFunctionFinder;
+
+    impl Visitor for AdminFunctionFinder {
+        type Break = ();
+
+        fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+            if let Expr::Function(function) = expr {
+                if let Some(name) = function.name.0.last() {
+                    if ADMIN_FUNCTIONS
+                        .iter()
+                        .any(|f| f.eq_ignore_ascii_case(&name.value))
+                    {
+                        return ControlFlow::Break(());
+                    }
+                }
+            }
+            ControlFlow::Continue(())
+        }
+    }
+
+    node.visit(&mut AdminFunctionFinder).is_break()
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::{InsertRequest, InsertRequests};
+    use sql::dialect::GreptimeDbDialect;
+    use sql::parser::ParserContext;
+
+    use super::*;
+
+    fn resolve_sql(sql: &str) -> Access {
+        try_resolve_sql(sql).unwrap()
+    }
+
+    fn try_resolve_sql(sql: &str) -> Result<Access> {
+        let stmt = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {})
+            .unwrap()
+            .remove(0);
+        AccessResolver::new("greptime", "public").resolve(&PermissionReq::SqlStatement(&stmt))
+    }
+
+    fn table(catalog: &str, schema: &str, table: &str) -> Target {
+        Target {
+            catalog: catalog.to_string(),
+            schema: schema.to_string(),
+            table: Some(table.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_resolve_statement() {
+        assert_eq!(
+            Access::Privileges(vec![
+                (Privilege::Read, table("greptime", "public", "foo")),
+                (Privilege::Read, table("greptime", "other", "bar")),
+            ]),
+            resolve_sql(
+                "WITH t AS (SELECT * FROM foo) SELECT * FROM t JOIN other.bar ON t.a = other.bar.a"
+            )
+        );
+        assert_eq!(
+            Access::Privileges(vec![
+                (Privilege::Read, table("greptime", "public", "bar")),
+                (Privilege::Write, table("c", "s", "foo")),
+            ]),
+            resolve_sql("INSERT INTO c.s.foo SELECT * FROM bar")
+        );
+        assert_eq!(
+            Access::Privileges(vec![(
+                Privilege::Write,
+                Target {
+                    catalog: "greptime".to_string(),
+                    schema: "db".to_string(),
+                    table: None,
+                }
+            )]),
+            resolve_sql("CREATE DATABASE db")
+        );
+        assert_eq!(Access::Privileges(vec![]), resolve_sql("SHOW TABLES"));
+        assert_eq!(Access::Superuser, resolve_sql("GRANT READ ON *.* TO alice"));
+        assert_eq!(
+            Access::Superuser,
+            resolve_sql("SELECT MIGRATE_REGION(1024, 1, 1, 2)")
+        );
+        for sql in [
+            "DELETE FROM t WHERE migrate_region(1024, 1, 1, 2) = ''",
+            "CREATE VIEW v AS SELECT migrate_region(1024, 1, 1, 2)",
+            "CREATE MATERIALIZED VIEW v AS SELECT migrate_region(1024, 1, 1, 2)",
+        ] {
+            assert_eq!(Access::Superuser, resolve_sql(sql), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_invalid_object_name() {
+        for sql in [
+            "SELECT * FROM x.greptime.public.foo",
+            "INSERT INTO x.greptime.public.foo VALUES (1)",
+            "DROP TABLE x.greptime.public.foo",
+        ] {
+            assert!(try_resolve_sql(sql).is_err(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_cte_scopes() {
+        let read = |tables: &[&str]| {
+            Access::Privileges(
+                tables
+                    .iter()
+                    .map(|t| (Privilege::Read, table("greptime", "public", t)))
+                    .collect(),
+            )
+        };
+        // The CTE in the subquery doesn't hide the table in the outer query.
+        assert_eq!(
+            read(&["secret"]),
+            resolve_sql(
+                "SELECT * FROM secret WHERE EXISTS (WITH secret AS (SELECT 1) SELECT * FROM secret)"
+            )
+        );
+        // A CTE is visible in the subqueries of the query defining it.
+        assert_eq!(
+            read(&["foo"]),
+            resolve_sql(
+                "WITH t AS (SELECT * FROM foo) SELECT * FROM t WHERE EXISTS (SELECT * FROM t)"
+            )
+        );
+        // A CTE isn't visible in its own query, but is visible in the following ones.
+        assert_eq!(
+            read(&["secret", "t"]),
+            resolve_sql(
+                "WITH secret AS (SELECT * FROM secret), u AS (SELECT * FROM t), \
+                t AS (SELECT * FROM secret) SELECT * FROM t"
+            )
+        );
+    }
+
+    #[test]
+    fn test_resolve_grpc_request() {
+        let request = Request::Inserts(InsertRequests {
+            inserts: vec![InsertRequest {
+                table_name: "foo".to_string(),
+                ..Default::default()
+            }],
+        });
+        assert_eq!(
+            Access::Privileges(vec![(Privilege::Write, table("greptime", "public", "foo"))]),
+            AccessResolver::new("greptime", "public")
+                .resolve(&PermissionReq::GrpcRequest(&request))
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SCRAM-SHA-256 credentials, ref: <https://datatracker.ietf.org/doc/html/rfc5802>
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::common::constant_time_eq;
+
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+/// What the server saves to verify SCRAM-SHA-256 authentications, without knowing the password.
+///
+/// It's saved as `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`, the same as
+/// PostgreSQL does, where the salt and keys are base64 encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramSha256Verifier {
+    pub iterations: u32,
+    pub salt: Vec<u8>,
+    pub stored_key: Vec<u8>,
+    pub server_key: Vec<u8>,
+}
+
+impl ScramSha256Verifier {
+    pub fn new(password: &str, salt: Vec<u8>, iterations: u32) -> Self {
+        let salted_password = salted_password(password.as_bytes(), &salt, iterations);
+        let client_key = hmac_sha256(&salted_password, b"Client Key");
+        Self {
+            iterations,
+            salt,
+            stored_key: Sha256::digest(client_key).to_vec(),
+            server_key: hmac_sha256(&salted_password, b"Server Key"),
+        }
+    }
+
+    /// Parses the verifier from `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let rest = s.strip_prefix(SCRAM_SHA_256)?.strip_prefix('$')?;
+        let (params, keys) = rest.split_once('$')?;
+        let (iterations, salt) = params.split_once(':')?;
+        let (stored_key, server_key) = keys.split_once(':')?;
+        Some(Self {
+            iterations: iterations.parse().ok()?,
+            salt: base64::decode(salt).ok()?,
+            stored_key: base64::decode(stored_key).ok()?,
+            server_key: base64::decode(server_key).ok()?,
+        })
+    }
+
+    pub fn verify_password(&self, password: &str) -> bool {
+        let expected = Self::new(password, self.salt.clone(), self.iterations);
+        constant_time_eq(&expected.stored_key, &self.stored_key)
+            && constant_time_eq(&expected.server_key, &self.server_key)
+    }
+
+    /// Verifies the `ClientProof` sent by the client, signed on the `AuthMessage`.
+    pub fn verify_client_proof(&self, auth_message: &[u8], client_proof: &[u8]) -> bool {
+        let client_signature = hmac_sha256(&self.stored_key, auth_message);
+        if client_proof.len() != client_signature.len() {
+            return false;
+        }
+        let client_key: Vec<u8> = client_proof
+            .iter()
+            .zip(client_signature)
+            .map(|(a, b)| a ^ b)
+            .collect();
+        constant_time_eq(Sha256::digest(client_key).as_slice(), &self.stored_key)
+    }
+
+    /// Signs the `AuthMessage`, so that the client can verify the server.
+    pub fn server_signature(&self, auth_message: &[u8]) -> Vec<u8> {
+        hmac_sha256(&self.server_key, auth_message)
+    }
+}
+
+impl std::fmt::Display for ScramSha256Verifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}${}:{}${}:{}",
+            SCRAM_SHA_256,
+            self.iterations,
+            base64::encode(&self.salt),
+            base64::encode(&self.stored_key),
+            base64::encode(&self.server_key)
+        )
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    // HMAC accepts keys of any length.
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// PBKDF2 with HMAC-SHA-256. The output has only one block as its length equals to the digest.
+fn salted_password(password: &[u8], salt: &[u8], iterations: u32) -> Vec<u8> {
+    let mut u = hmac_sha256(password, &[salt, &1u32.to_be_bytes()].concat());
+    let mut result = u.clone();
+    for _ in 1..iterations {
+        u = hmac_sha256(password, &u);
+        result.iter_mut().zip(&u).for_each(|(r, u)| *r ^= u);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scram_sha256_verifier() {
+        let verifier = ScramSha256Verifier::new("pencil", b"salt".to_vec(), 4096);
+        let s = verifier.to_string();
+        assert!(s.starts_with("SCRAM-SHA-256$4096:c2FsdA==$"));
+        assert_eq!(Some(verifier.clone()), ScramSha256Verifier::parse(&s));
+        assert!(ScramSha256Verifier::parse("SCRAM-SHA-256$4096:c2FsdA==").is_none());
+
+        assert!(verifier.verify_password("pencil"));
+        assert!(!verifier.verify_password("pen"));
+    }
+
+    #[test]
+    fn test_verify_client_proof() {
+        // Test vector from https://datatracker.ietf.org/doc/html/rfc7677#section-3
+        let salt = base64::decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap();
+        let verifier = ScramSha256Verifier::new("pencil", salt, 4096);
+        let auth_message = "n=user,r=rOprNGfwEbeRWgbNEkqO,\
+            r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096,\
+            c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+        let client_proof = base64::decode("dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=").unwrap();
+        assert!(verifier.verify_client_proof(auth_message.as_bytes(), &client_proof));
+        assert!(!verifier.verify_client_proof(b"other message", &client_proof));
+        assert_eq!(
+            "6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=",
+            base64::encode(verifier.server_signature(auth_message.as_bytes()))
+        );
+    }
+}
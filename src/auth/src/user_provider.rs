@@ -12,12 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod htpasswd_user_provider;
 pub(crate) mod static_user_provider;
 
 use crate::common::{Identity, Password};
 use crate::error::Result;
+use crate::scram::ScramSha256Verifier;
 use crate::UserInfoRef;
 
+/// How PostgreSQL clients send the password to authenticate.
+#[derive(Debug, Clone)]
+pub enum PgAuthMethod {
+    CleartextPassword,
+    /// Clients send [`Password::PgMD5`].
+    Md5Password,
+    /// Clients send [`Password::PgScramSha256`] after the SCRAM exchange with the verifier.
+    ScramSha256(ScramSha256Verifier),
+}
+
 #[async_trait::async_trait]
 pub trait UserProvider: Send + Sync {
     fn name(&self) -> &str;
@@ -30,6 +42,13 @@ pub trait UserProvider: Send + Sync {
     /// This method should be called after [`authenticate`].
     async fn authorize(&self, catalog: &str, schema: &str, user_info: &UserInfoRef) -> Result<()>;
 
+    /// [`pg_auth_method`] returns how PostgreSQL clients should send the password of the user.
+    /// It depends on how the password is saved, e.g. a hashed password can't be verified against
+    /// an MD5 password sent by clients.
+    async fn pg_auth_method(&self, _username: &str) -> Result<PgAuthMethod> {
+        Ok(PgAuthMethod::CleartextPassword)
+    }
+
     /// [`auth`] is a combination of [`authenticate`] and [`authorize`].
     /// In most cases it's preferred for both convenience and performance.
     async fn auth(
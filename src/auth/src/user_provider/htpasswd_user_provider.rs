@@ -0,0 +1,487 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use common_telemetry::{info, warn};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use secrecy::ExposeSecret;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::common::{auth_mysql_with_hash_stage_2, constant_time_eq, verify_password_blocking};
+use crate::error::{
+    Error, FileWatchSnafu, IllegalParamSnafu, InvalidConfigSnafu, IoSnafu, Result,
+    UnsupportedPasswordTypeSnafu, UserNotFoundSnafu, UserPasswordMismatchSnafu,
+};
+use crate::scram::ScramSha256Verifier;
+use crate::user_info::DefaultUserInfo;
+use crate::user_provider::PgAuthMethod;
+use crate::{Identity, Password, UserInfoRef, UserProvider};
+
+pub(crate) const HTPASSWD_USER_PROVIDER: &str = "htpasswd_user_provider";
+
+/// A hashed credential of a user. Supported formats:
+/// - bcrypt: `$2a$`, `$2b$` or `$2y$` prefixed, as generated by `htpasswd -B`
+/// - SHA1: `{SHA}<base64>`, as generated by `htpasswd -s`
+/// - SHA256: `{SHA256}<base64>`
+/// - MySQL native password: `*<hex of sha1(sha1(password))>`
+/// - PostgreSQL MD5: `md5<hex of md5(password + username)>`
+/// - PostgreSQL SCRAM-SHA-256: `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`
+#[derive(Debug, Clone)]
+enum Credential {
+    Bcrypt(String),
+    Sha1(Vec<u8>),
+    Sha256(Vec<u8>),
+    MysqlNativePassword(Vec<u8>),
+    PgMd5(String),
+    PgScramSha256(ScramSha256Verifier),
+}
+
+impl Credential {
+    fn parse(hash: &str) -> Option<Self> {
+        if hash.starts_with("$2a$") || hash.starts_with("$2b$") || hash.starts_with("$2y$") {
+            Some(Credential::Bcrypt(hash.to_string()))
+        } else if let Some(hash) = hash.strip_prefix("{SHA}") {
+            base64::decode(hash).ok().map(Credential::Sha1)
+        } else if let Some(hash) = hash.strip_prefix("{SHA256}") {
+            base64::decode(hash).ok().map(Credential::Sha256)
+        } else if let Some(hash) = hash.strip_prefix('*') {
+            hex::decode(hash).ok().map(Credential::MysqlNativePassword)
+        } else if let Some(hash) = hash.strip_prefix("md5") {
+            (hash.len() == 32 && hex::decode(hash).is_ok())
+                .then(|| Credential::PgMd5(hash.to_lowercase()))
+        } else {
+            ScramSha256Verifier::parse(hash).map(Credential::PgScramSha256)
+        }
+    }
+
+    /// Verifies the plain text password. It's slow for bcrypt and SCRAM-SHA-256 credentials,
+    /// so it should run in a blocking thread.
+    fn verify_password(&self, username: &str, password: &str) -> bool {
+        match self {
+            Credential::Bcrypt(hash) => bcrypt::verify(password, hash).unwrap_or(false),
+            Credential::Sha1(hash) => constant_time_eq(Sha1::digest(password).as_slice(), hash),
+            Credential::Sha256(hash) => constant_time_eq(Sha256::digest(password).as_slice(), hash),
+            Credential::MysqlNativePassword(hash) => {
+                constant_time_eq(Sha1::digest(Sha1::digest(password)).as_slice(), hash)
+            }
+            Credential::PgMd5(hash) => {
+                let computed = format!("{:x}", md5::compute(format!("{password}{username}")));
+                constant_time_eq(computed.as_bytes(), hash.as_bytes())
+            }
+            Credential::PgScramSha256(verifier) => verifier.verify_password(password),
+        }
+    }
+}
+
+/// Users loaded from an htpasswd formatted file, one `<username>:<hashed password>` per line.
+///
+/// A user may have several lines with credentials in different formats, e.g. a bcrypt hash for
+/// HTTP and gRPC, and a `*` prefixed hash for the MySQL native password authentication. The file
+/// is watched and reloaded on changes, and the previous users are kept if it becomes invalid.
+pub(crate) struct HtpasswdUserProvider {
+    users: Arc<RwLock<HashMap<String, Vec<Credential>>>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl TryFrom<&str> for HtpasswdUserProvider {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let path = PathBuf::from(value);
+        ensure!(
+            path.is_file(),
+            InvalidConfigSnafu {
+                value: value.to_string(),
+                msg: "HtpasswdUserProviderOption must be a valid file path",
+            }
+        );
+        let users = Arc::new(RwLock::new(load_users(&path)?));
+        let watcher = watch_users(path, users.clone())?;
+        Ok(HtpasswdUserProvider {
+            users,
+            _watcher: watcher,
+        })
+    }
+}
+
+fn load_users(path: &Path) -> Result<HashMap<String, Vec<Credential>>> {
+    let content = std::fs::read_to_string(path).context(IoSnafu)?;
+    let mut users: HashMap<String, Vec<Credential>> = HashMap::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (username, hash) = line.split_once(':').context(InvalidConfigSnafu {
+            value: line.to_string(),
+            msg: "htpasswd lines must be in format `<username>:<hashed password>`",
+        })?;
+        let credential = Credential::parse(hash).context(InvalidConfigSnafu {
+            value: username.to_string(),
+            msg: "unsupported hashed password format",
+        })?;
+        users
+            .entry(username.to_string())
+            .or_default()
+            .push(credential);
+    }
+
+    ensure!(
+        !users.is_empty(),
+        InvalidConfigSnafu {
+            value: path.display().to_string(),
+            msg: "htpasswd file must contains at least one valid credential",
+        }
+    );
+    Ok(users)
+}
+
+/// Watches the directory of the file rather than the file itself, so that the reloading still
+/// works after the file is replaced by editors or config management tools.
+fn watch_users(
+    path: PathBuf,
+    users: Arc<RwLock<HashMap<String, Vec<Credential>>>>,
+) -> Result<RecommendedWatcher> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf();
+    let file_name = path.file_name().map(|name| name.to_os_string());
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                warn!("Failed to watch htpasswd file {}: {e}", path.display());
+                return;
+            }
+        };
+        if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            || !event
+                .paths
+                .iter()
+                .any(|p| p.file_name().map(|name| name.to_os_string()) == file_name)
+        {
+            return;
+        }
+
+        match load_users(&path) {
+            Ok(loaded) => {
+                info!("Reloaded {} users from {}", loaded.len(), path.display());
+                *users.write().unwrap() = loaded;
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to reload users from {}, keep the previous ones: {e}",
+                    path.display()
+                );
+            }
+        }
+    })
+    .context(FileWatchSnafu)?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .context(FileWatchSnafu)?;
+    Ok(watcher)
+}
+
+impl HtpasswdUserProvider {
+    fn credentials(&self, username: &str) -> Result<Vec<Credential>> {
+        ensure!(
+            !username.is_empty(),
+            IllegalParamSnafu {
+                msg: "blank username"
+            }
+        );
+        self.users
+            .read()
+            .unwrap()
+            .get(username)
+            .cloned()
+            .context(UserNotFoundSnafu {
+                username: username.to_string(),
+            })
+    }
+}
+
+#[async_trait]
+impl UserProvider for HtpasswdUserProvider {
+    fn name(&self) -> &str {
+        HTPASSWD_USER_PROVIDER
+    }
+
+    async fn authenticate(
+        &self,
+        input_id: Identity<'_>,
+        input_pwd: Password<'_>,
+    ) -> Result<UserInfoRef> {
+        let Identity::UserId(username, _) = input_id;
+        let credentials = self.credentials(username)?;
+
+        let matched = match input_pwd {
+            Password::PlainText(pwd) => {
+                let pwd = pwd.expose_secret().clone();
+                ensure!(
+                    !pwd.is_empty(),
+                    IllegalParamSnafu {
+                        msg: "blank password"
+                    }
+                );
+                let username = username.to_string();
+                verify_password_blocking(move || {
+                    credentials
+                        .iter()
+                        .any(|c| c.verify_password(&username, &pwd))
+                })
+                .await?
+            }
+            Password::MysqlNativePassword(auth_data, salt) => {
+                let hash_stage_2 = credentials
+                    .iter()
+                    .find_map(|c| match c {
+                        Credential::MysqlNativePassword(hash) => Some(hash),
+                        _ => None,
+                    })
+                    .context(UnsupportedPasswordTypeSnafu {
+                        password_type: "mysql_native_password",
+                    })?;
+                auth_mysql_with_hash_stage_2(auth_data, salt, username, hash_stage_2)?;
+                true
+            }
+            Password::PgMD5(hashed, salt) => {
+                let md5_hash = credentials
+                    .iter()
+                    .find_map(|c| match c {
+                        Credential::PgMd5(hash) => Some(hash),
+                        _ => None,
+                    })
+                    .context(UnsupportedPasswordTypeSnafu {
+                        password_type: "pg_md5",
+                    })?;
+                // ref: https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-START-UP
+                let expected = md5::compute([md5_hash.as_bytes(), salt].concat());
+                constant_time_eq(hashed, format!("md5{expected:x}").as_bytes())
+            }
+            Password::PgScramSha256(client_proof, auth_message) => credentials
+                .iter()
+                .find_map(|c| match c {
+                    Credential::PgScramSha256(verifier) => Some(verifier),
+                    _ => None,
+                })
+                .context(UnsupportedPasswordTypeSnafu {
+                    password_type: "pg_scram_sha256",
+                })?
+                .verify_client_proof(auth_message, client_proof),
+        };
+
+        ensure!(
+            matched,
+            UserPasswordMismatchSnafu {
+                username: username.to_string(),
+            }
+        );
+        Ok(DefaultUserInfo::with_name(username))
+    }
+
+    async fn authorize(
+        &self,
+        _catalog: &str,
+        _schema: &str,
+        _user_info: &UserInfoRef,
+    ) -> Result<()> {
+        // default allow all
+        Ok(())
+    }
+
+    async fn pg_auth_method(&self, username: &str) -> Result<PgAuthMethod> {
+        // Unknown users go through the cleartext password flow, and fail in [`authenticate`].
+        let Ok(credentials) = self.credentials(username) else {
+            return Ok(PgAuthMethod::CleartextPassword);
+        };
+        let mut method = PgAuthMethod::CleartextPassword;
+        for credential in credentials {
+            match credential {
+                Credential::PgScramSha256(verifier) => {
+                    return Ok(PgAuthMethod::ScramSha256(verifier))
+                }
+                Credential::PgMd5(_) => method = PgAuthMethod::Md5Password,
+                _ => {}
+            }
+        }
+        Ok(method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common_test_util::temp_dir::create_temp_dir;
+
+    use super::*;
+
+    async fn authenticate(
+        provider: &HtpasswdUserProvider,
+        username: &str,
+        password: &str,
+    ) -> Result<UserInfoRef> {
+        provider
+            .authenticate(
+                Identity::UserId(username, None),
+                Password::PlainText(password.to_string().into()),
+            )
+            .await
+    }
+
+    fn sha1_base64(password: &str) -> String {
+        base64::encode(Sha1::digest(password))
+    }
+
+    #[tokio::test]
+    async fn test_htpasswd_provider() {
+        let dir = create_temp_dir("test_htpasswd_provider");
+        let path = dir.path().join("htpasswd");
+        let scram = ScramSha256Verifier::new("scram_pwd", b"salt".to_vec(), 4096);
+        let content = format!(
+            "# comment\n\
+            bcrypt:{}\n\
+            sha:{{SHA}}{}\n\
+            sha256:{{SHA256}}{}\n\
+            mysql:*{}\n\
+            pg:md5{:x}\n\
+            scram:{}\n",
+            bcrypt::hash("bcrypt_pwd", 4).unwrap(),
+            sha1_base64("sha_pwd"),
+            base64::encode(Sha256::digest("sha256_pwd")),
+            hex::encode(Sha1::digest(Sha1::digest("mysql_pwd"))),
+            md5::compute("pg_pwdpg"),
+            scram
+        );
+        std::fs::write(&path, content).unwrap();
+        let provider = HtpasswdUserProvider::try_from(path.to_str().unwrap()).unwrap();
+
+        for (username, password) in [
+            ("bcrypt", "bcrypt_pwd"),
+            ("sha", "sha_pwd"),
+            ("sha256", "sha256_pwd"),
+            ("mysql", "mysql_pwd"),
+            ("pg", "pg_pwd"),
+            ("scram", "scram_pwd"),
+        ] {
+            let user_info = authenticate(&provider, username, password).await.unwrap();
+            assert_eq!(username, user_info.username());
+            assert!(authenticate(&provider, username, "wrong").await.is_err());
+        }
+        assert!(authenticate(&provider, "unknown", "pwd").await.is_err());
+
+        // MySQL native password
+        let salt = b"01234567890123456789";
+        let stage_1 = Sha1::digest("mysql_pwd");
+        let stage_2 = Sha1::digest(stage_1);
+        let mut auth_data = Sha1::digest([salt.as_slice(), stage_2.as_slice()].concat()).to_vec();
+        auth_data.iter_mut().zip(stage_1).for_each(|(a, b)| *a ^= b);
+        let _ = provider
+            .authenticate(
+                Identity::UserId("mysql", None),
+                Password::MysqlNativePassword(&auth_data, salt),
+            )
+            .await
+            .unwrap();
+        assert!(provider
+            .authenticate(
+                Identity::UserId("bcrypt", None),
+                Password::MysqlNativePassword(&auth_data, salt),
+            )
+            .await
+            .is_err());
+
+        // PostgreSQL MD5
+        assert!(matches!(
+            provider.pg_auth_method("pg").await.unwrap(),
+            PgAuthMethod::Md5Password
+        ));
+        let hashed = format!(
+            "md5{:x}",
+            md5::compute(format!("{:x}salt", md5::compute("pg_pwdpg")))
+        );
+        let _ = provider
+            .authenticate(
+                Identity::UserId("pg", None),
+                Password::PgMD5(hashed.as_bytes(), b"salt"),
+            )
+            .await
+            .unwrap();
+        assert!(provider
+            .authenticate(
+                Identity::UserId("pg", None),
+                Password::PgMD5(hashed.as_bytes(), b"tlas"),
+            )
+            .await
+            .is_err());
+
+        // PostgreSQL SCRAM-SHA-256
+        let PgAuthMethod::ScramSha256(verifier) = provider.pg_auth_method("scram").await.unwrap()
+        else {
+            unreachable!()
+        };
+        assert_eq!(scram, verifier);
+        assert!(matches!(
+            provider.pg_auth_method("bcrypt").await.unwrap(),
+            PgAuthMethod::CleartextPassword
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_htpasswd_provider_reload() {
+        let dir = create_temp_dir("test_htpasswd_provider_reload");
+        let path = dir.path().join("htpasswd");
+        std::fs::write(&path, format!("root:{{SHA}}{}\n", sha1_base64("old"))).unwrap();
+        let provider = HtpasswdUserProvider::try_from(path.to_str().unwrap()).unwrap();
+        let _ = authenticate(&provider, "root", "old").await.unwrap();
+
+        std::fs::write(&path, format!("root:{{SHA}}{}\n", sha1_base64("new"))).unwrap();
+        let mut reloaded = false;
+        for _ in 0..50 {
+            if authenticate(&provider, "root", "new").await.is_ok() {
+                reloaded = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(reloaded);
+        assert!(authenticate(&provider, "root", "old").await.is_err());
+
+        // Keeps the previous users if the file becomes invalid.
+        std::fs::write(&path, "root:plaintext\n").unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let _ = authenticate(&provider, "root", "new").await.unwrap();
+    }
+
+    #[test]
+    fn test_invalid_htpasswd() {
+        let dir = create_temp_dir("test_invalid_htpasswd");
+        let path = dir.path().join("htpasswd");
+        std::fs::write(&path, "root:plaintext\n").unwrap();
+        assert!(HtpasswdUserProvider::try_from(path.to_str().unwrap()).is_err());
+        std::fs::write(&path, "# no users\n").unwrap();
+        assert!(HtpasswdUserProvider::try_from(path.to_str().unwrap()).is_err());
+        assert!(HtpasswdUserProvider::try_from("/not/exist").is_err());
+    }
+}
@@ -92,6 +92,12 @@ pub(crate) struct StaticUserProvider {
     users: HashMap<String, Vec<u8>>,
 }
 
+impl StaticUserProvider {
+    pub(crate) fn contains_user(&self, username: &str) -> bool {
+        self.users.contains_key(username)
+    }
+}
+
 #[async_trait]
 impl UserProvider for StaticUserProvider {
     fn name(&self) -> &str {
@@ -140,6 +146,10 @@ impl UserProvider for StaticUserProvider {
                         password_type: "pg_md5",
                     }
                     .fail(),
+                    Password::PgScramSha256(_, _) => UnsupportedPasswordTypeSnafu {
+                        password_type: "pg_scram_sha256",
+                    }
+                    .fail(),
                 }
             }
         }
@@ -20,6 +20,7 @@ use common_error::status_code::StatusCode;
 use datafusion::error::DataFusionError;
 use datatypes::prelude::ConcreteDataType;
 use snafu::{Location, Snafu};
+use table::metadata::TableId;
 use tokio::task::JoinError;
 
 use crate::DeregisterTableRequest;
@@ -211,6 +212,24 @@ pub enum Error {
     ))]
     UpgradeWeakCatalogManagerRef { location: Location },
 
+    #[snafu(display("Failed to find partitions of table {}, source: {}", table_id, source))]
+    FindPartitions {
+        table_id: TableId,
+        location: Location,
+        source: partition::error::Error,
+    },
+
+    #[snafu(display(
+        "Failed to find region routes of table {}, source: {}",
+        table_id,
+        source
+    ))]
+    FindRegionRoutes {
+        table_id: TableId,
+        location: Location,
+        source: partition::error::Error,
+    },
+
     #[snafu(display("Failed to execute system catalog table scan, source: {}", source))]
     SystemCatalogTableScanExec {
         location: Location,
@@ -322,6 +341,9 @@ impl ErrorExt for Error {
             Error::QueryAccessDenied { .. } => StatusCode::AccessDenied,
             Error::Datafusion { .. } => StatusCode::EngineExecuteQuery,
             Error::TableMetadataManager { source, .. } => source.status_code(),
+            Error::FindPartitions { source, .. } | Error::FindRegionRoutes { source, .. } => {
+                source.status_code()
+            }
         }
     }
 
@@ -13,6 +13,11 @@
 // limitations under the License.
 
 mod columns;
+mod engines;
+mod key_column_usage;
+mod partitions;
+mod region_peers;
+mod schemata;
 mod tables;
 
 use std::collections::HashMap;
@@ -23,6 +28,7 @@ use common_error::ext::BoxedError;
 use common_recordbatch::{RecordBatchStreamAdaptor, SendableRecordBatchStream};
 use datatypes::schema::SchemaRef;
 use futures_util::StreamExt;
+use partition::manager::PartitionRuleManagerRef;
 use snafu::ResultExt;
 use store_api::data_source::DataSource;
 use store_api::storage::{ScanRequest, TableId};
@@ -34,16 +40,59 @@ use table::thin_table::{ThinTable, ThinTableAdapter};
 use table::TableRef;
 
 use self::columns::InformationSchemaColumns;
+use self::engines::InformationSchemaEngines;
+use self::key_column_usage::InformationSchemaKeyColumnUsage;
+use self::partitions::InformationSchemaPartitions;
+use self::region_peers::InformationSchemaRegionPeers;
+use self::schemata::InformationSchemaSchemata;
 use crate::error::Result;
 use crate::information_schema::tables::InformationSchemaTables;
 use crate::CatalogManager;
 
 pub const TABLES: &str = "tables";
 pub const COLUMNS: &str = "columns";
+pub const SCHEMATA: &str = "schemata";
+pub const ENGINES: &str = "engines";
+pub const KEY_COLUMN_USAGE: &str = "key_column_usage";
+pub const PARTITIONS: &str = "partitions";
+pub const REGION_PEERS: &str = "region_peers";
+
+/// Names of all tables in information schema.
+pub const INFORMATION_SCHEMA_TABLE_NAMES: [&str; 7] = [
+    TABLES,
+    COLUMNS,
+    SCHEMATA,
+    ENGINES,
+    KEY_COLUMN_USAGE,
+    PARTITIONS,
+    REGION_PEERS,
+];
+
+/// Decides whether a schema, or a table in the schema if the table name is given, is visible
+/// to the current user, with arguments `(catalog, schema, table)`.
+pub type MetadataFilterRef = Arc<dyn Fn(&str, &str, Option<&str>) -> bool + Send + Sync>;
+
+fn is_visible(
+    filter: &Option<MetadataFilterRef>,
+    catalog: &str,
+    schema: &str,
+    table: Option<&str>,
+) -> bool {
+    filter
+        .as_ref()
+        .map(|filter| filter(catalog, schema, table))
+        .unwrap_or(true)
+}
 
 pub struct InformationSchemaProvider {
     catalog_name: String,
     catalog_manager: Weak<dyn CatalogManager>,
+    /// Partition manager to query partitions and region routes of tables. Tables are
+    /// treated as unpartitioned and served by the local node if it is absent.
+    partition_manager: Option<PartitionRuleManagerRef>,
+    /// Rows of schemas and tables that are filtered out are omitted. All rows are
+    /// returned if it is absent.
+    filter: Option<MetadataFilterRef>,
 }
 
 impl InformationSchemaProvider {
@@ -51,11 +100,25 @@ impl InformationSchemaProvider {
         Self {
             catalog_name,
             catalog_manager,
+            partition_manager: None,
+            filter: None,
         }
     }
 
+    /// Sets the partition manager used by `partitions` and `region_peers`.
+    pub fn with_partition_manager(mut self, partition_manager: PartitionRuleManagerRef) -> Self {
+        self.partition_manager = Some(partition_manager);
+        self
+    }
+
+    /// Sets the filter of schemas and tables that are visible in information schema tables.
+    pub fn with_filter(mut self, filter: Option<MetadataFilterRef>) -> Self {
+        self.filter = filter;
+        self
+    }
+
     /// Build a map of [TableRef] in information schema.
-    /// Including `tables` and `columns`.
+    /// Including all tables in [INFORMATION_SCHEMA_TABLE_NAMES].
     pub fn build(
         catalog_name: String,
         catalog_manager: Weak<dyn CatalogManager>,
@@ -63,8 +126,9 @@ impl InformationSchemaProvider {
         let provider = Self::new(catalog_name, catalog_manager);
 
         let mut schema = HashMap::new();
-        schema.insert(TABLES.to_owned(), provider.table(TABLES).unwrap());
-        schema.insert(COLUMNS.to_owned(), provider.table(COLUMNS).unwrap());
+        for name in INFORMATION_SCHEMA_TABLE_NAMES {
+            schema.insert(name.to_owned(), provider.table(name).unwrap());
+        }
         schema
     }
 
@@ -86,10 +150,35 @@ impl InformationSchemaProvider {
             TABLES => Some(Arc::new(InformationSchemaTables::new(
                 self.catalog_name.clone(),
                 self.catalog_manager.clone(),
+                self.filter.clone(),
             )) as _),
             COLUMNS => Some(Arc::new(InformationSchemaColumns::new(
                 self.catalog_name.clone(),
                 self.catalog_manager.clone(),
+                self.filter.clone(),
+            )) as _),
+            SCHEMATA => Some(Arc::new(InformationSchemaSchemata::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+                self.filter.clone(),
+            )) as _),
+            ENGINES => Some(Arc::new(InformationSchemaEngines::new()) as _),
+            KEY_COLUMN_USAGE => Some(Arc::new(InformationSchemaKeyColumnUsage::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+                self.filter.clone(),
+            )) as _),
+            PARTITIONS => Some(Arc::new(InformationSchemaPartitions::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+                self.partition_manager.clone(),
+                self.filter.clone(),
+            )) as _),
+            REGION_PEERS => Some(Arc::new(InformationSchemaRegionPeers::new(
+                self.catalog_name.clone(),
+                self.catalog_manager.clone(),
+                self.partition_manager.clone(),
+                self.filter.clone(),
             )) as _),
             _ => None,
         }
@@ -34,7 +34,7 @@ use snafu::{OptionExt, ResultExt};
 use store_api::storage::TableId;
 
 use super::tables::InformationSchemaTables;
-use super::{InformationTable, COLUMNS, TABLES};
+use super::{is_visible, InformationTable, MetadataFilterRef, COLUMNS, TABLES};
 use crate::error::{
     CreateRecordBatchSnafu, InternalSnafu, Result, UpgradeWeakCatalogManagerRefSnafu,
 };
@@ -44,6 +44,7 @@ pub(super) struct InformationSchemaColumns {
     schema: SchemaRef,
     catalog_name: String,
     catalog_manager: Weak<dyn CatalogManager>,
+    filter: Option<MetadataFilterRef>,
 }
 
 const TABLE_CATALOG: &str = "table_catalog";
@@ -54,11 +55,16 @@ const DATA_TYPE: &str = "data_type";
 const SEMANTIC_TYPE: &str = "semantic_type";
 
 impl InformationSchemaColumns {
-    pub(super) fn new(catalog_name: String, catalog_manager: Weak<dyn CatalogManager>) -> Self {
+    pub(super) fn new(
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        filter: Option<MetadataFilterRef>,
+    ) -> Self {
         Self {
             schema: Self::schema(),
             catalog_name,
             catalog_manager,
+            filter,
         }
     }
 
@@ -78,6 +84,7 @@ impl InformationSchemaColumns {
             self.schema.clone(),
             self.catalog_name.clone(),
             self.catalog_manager.clone(),
+            self.filter.clone(),
         )
     }
 }
@@ -120,6 +127,7 @@ struct InformationSchemaColumnsBuilder {
     schema: SchemaRef,
     catalog_name: String,
     catalog_manager: Weak<dyn CatalogManager>,
+    filter: Option<MetadataFilterRef>,
 
     catalog_names: StringVectorBuilder,
     schema_names: StringVectorBuilder,
@@ -134,11 +142,13 @@ impl InformationSchemaColumnsBuilder {
         schema: SchemaRef,
         catalog_name: String,
         catalog_manager: Weak<dyn CatalogManager>,
+        filter: Option<MetadataFilterRef>,
     ) -> Self {
         Self {
             schema,
             catalog_name,
             catalog_manager,
+            filter,
             catalog_names: StringVectorBuilder::with_capacity(42),
             schema_names: StringVectorBuilder::with_capacity(42),
             table_names: StringVectorBuilder::with_capacity(42),
@@ -157,9 +167,10 @@ impl InformationSchemaColumnsBuilder {
             .context(UpgradeWeakCatalogManagerRefSnafu)?;
 
         for schema_name in catalog_manager.schema_names(&catalog_name).await? {
-            if !catalog_manager
-                .schema_exist(&catalog_name, &schema_name)
-                .await?
+            if !is_visible(&self.filter, &catalog_name, &schema_name, None)
+                || !catalog_manager
+                    .schema_exist(&catalog_name, &schema_name)
+                    .await?
             {
                 continue;
             }
@@ -167,6 +178,9 @@ impl InformationSchemaColumnsBuilder {
                 .table_names(&catalog_name, &schema_name)
                 .await?
             {
+                if !is_visible(&self.filter, &catalog_name, &schema_name, Some(&table_name)) {
+                    continue;
+                }
                 let (keys, schema) = if let Some(table) = catalog_manager
                     .table(&catalog_name, &schema_name, &table_name)
                     .await?
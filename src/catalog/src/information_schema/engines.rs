@@ -0,0 +1,182 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_catalog::consts::{
+    IMMUTABLE_FILE_ENGINE, INFORMATION_SCHEMA_ENGINES_TABLE_ID, MITO_ENGINE,
+};
+use common_error::ext::BoxedError;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::StringVectorBuilder;
+use snafu::ResultExt;
+use store_api::storage::TableId;
+
+use super::ENGINES;
+use crate::error::{CreateRecordBatchSnafu, InternalSnafu, Result};
+use crate::information_schema::InformationTable;
+
+/// The `information_schema.engines` table implementation.
+pub(super) struct InformationSchemaEngines {
+    schema: SchemaRef,
+}
+
+impl InformationSchemaEngines {
+    pub(super) fn new() -> Self {
+        Self {
+            schema: Self::schema(),
+        }
+    }
+
+    pub(crate) fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            ColumnSchema::new("engine", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("support", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("comment", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("transactions", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("xa", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("savepoints", ConcreteDataType::string_datatype(), false),
+        ]))
+    }
+
+    fn builder(&self) -> InformationSchemaEnginesBuilder {
+        InformationSchemaEnginesBuilder::new(self.schema.clone())
+    }
+}
+
+impl InformationTable for InformationSchemaEngines {
+    fn table_id(&self) -> TableId {
+        INFORMATION_SCHEMA_ENGINES_TABLE_ID
+    }
+
+    fn table_name(&self) -> &'static str {
+        ENGINES
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_engines()
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.engines` table row by row
+///
+/// Columns are based on <https://dev.mysql.com/doc/refman/8.0/en/information-schema-engines-table.html>
+struct InformationSchemaEnginesBuilder {
+    schema: SchemaRef,
+
+    engines: StringVectorBuilder,
+    supports: StringVectorBuilder,
+    comments: StringVectorBuilder,
+    transactions: StringVectorBuilder,
+    xa: StringVectorBuilder,
+    savepoints: StringVectorBuilder,
+}
+
+impl InformationSchemaEnginesBuilder {
+    fn new(schema: SchemaRef) -> Self {
+        Self {
+            schema,
+            engines: StringVectorBuilder::with_capacity(2),
+            supports: StringVectorBuilder::with_capacity(2),
+            comments: StringVectorBuilder::with_capacity(2),
+            transactions: StringVectorBuilder::with_capacity(2),
+            xa: StringVectorBuilder::with_capacity(2),
+            savepoints: StringVectorBuilder::with_capacity(2),
+        }
+    }
+
+    /// Construct the `information_schema.engines` virtual table
+    fn make_engines(&mut self) -> Result<RecordBatch> {
+        self.add_engine(
+            MITO_ENGINE,
+            "DEFAULT",
+            "Storage engine for time-series data",
+        );
+        self.add_engine(
+            IMMUTABLE_FILE_ENGINE,
+            "YES",
+            "Storage engine for external immutable files",
+        );
+
+        self.finish()
+    }
+
+    fn add_engine(&mut self, engine: &str, support: &str, comment: &str) {
+        self.engines.push(Some(engine));
+        self.supports.push(Some(support));
+        self.comments.push(Some(comment));
+        self.transactions.push(Some("NO"));
+        self.xa.push(Some("NO"));
+        self.savepoints.push(Some("NO"));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.engines.finish()),
+            Arc::new(self.supports.finish()),
+            Arc::new(self.comments.finish()),
+            Arc::new(self.transactions.finish()),
+            Arc::new(self.xa.finish()),
+            Arc::new(self.savepoints.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaEngines {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_engines()
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
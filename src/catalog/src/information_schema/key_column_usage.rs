@@ -0,0 +1,335 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_catalog::consts::INFORMATION_SCHEMA_KEY_COLUMN_USAGE_TABLE_ID;
+use common_error::ext::BoxedError;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVectorBuilder, UInt32VectorBuilder};
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::TableId;
+
+use super::KEY_COLUMN_USAGE;
+use crate::error::{
+    CreateRecordBatchSnafu, InternalSnafu, Result, UpgradeWeakCatalogManagerRefSnafu,
+};
+use crate::information_schema::{is_visible, InformationTable, MetadataFilterRef};
+use crate::CatalogManager;
+
+/// Catalog of constraints, always `def` as MySQL does.
+const CONSTRAINT_CATALOG: &str = "def";
+/// Constraint name of the time index.
+const TIME_INDEX_CONSTRAINT_NAME: &str = "TIME INDEX";
+/// Constraint name of the primary key.
+const PRIMARY_CONSTRAINT_NAME: &str = "PRIMARY";
+
+/// The `information_schema.key_column_usage` table implementation.
+pub(super) struct InformationSchemaKeyColumnUsage {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    filter: Option<MetadataFilterRef>,
+}
+
+impl InformationSchemaKeyColumnUsage {
+    pub(super) fn new(
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        filter: Option<MetadataFilterRef>,
+    ) -> Self {
+        Self {
+            schema: Self::schema(),
+            catalog_name,
+            catalog_manager,
+            filter,
+        }
+    }
+
+    pub(crate) fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            ColumnSchema::new(
+                "constraint_catalog",
+                ConcreteDataType::string_datatype(),
+                false,
+            ),
+            ColumnSchema::new(
+                "constraint_schema",
+                ConcreteDataType::string_datatype(),
+                false,
+            ),
+            ColumnSchema::new(
+                "constraint_name",
+                ConcreteDataType::string_datatype(),
+                false,
+            ),
+            ColumnSchema::new("table_catalog", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_schema", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_name", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("column_name", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "ordinal_position",
+                ConcreteDataType::uint32_datatype(),
+                false,
+            ),
+            ColumnSchema::new(
+                "position_in_unique_constraint",
+                ConcreteDataType::uint32_datatype(),
+                true,
+            ),
+            ColumnSchema::new(
+                "referenced_table_schema",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+            ColumnSchema::new(
+                "referenced_table_name",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+            ColumnSchema::new(
+                "referenced_column_name",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+        ]))
+    }
+
+    fn builder(&self) -> InformationSchemaKeyColumnUsageBuilder {
+        InformationSchemaKeyColumnUsageBuilder::new(
+            self.schema.clone(),
+            self.catalog_name.clone(),
+            self.catalog_manager.clone(),
+            self.filter.clone(),
+        )
+    }
+}
+
+impl InformationTable for InformationSchemaKeyColumnUsage {
+    fn table_id(&self) -> TableId {
+        INFORMATION_SCHEMA_KEY_COLUMN_USAGE_TABLE_ID
+    }
+
+    fn table_name(&self) -> &'static str {
+        KEY_COLUMN_USAGE
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_key_column_usage()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.key_column_usage` table row by row
+///
+/// The time index and primary key columns of a table are reported as key columns.
+///
+/// Columns are based on <https://dev.mysql.com/doc/refman/8.0/en/information-schema-key-column-usage-table.html>
+struct InformationSchemaKeyColumnUsageBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    filter: Option<MetadataFilterRef>,
+
+    constraint_catalogs: StringVectorBuilder,
+    constraint_schemas: StringVectorBuilder,
+    constraint_names: StringVectorBuilder,
+    table_catalogs: StringVectorBuilder,
+    table_schemas: StringVectorBuilder,
+    table_names: StringVectorBuilder,
+    column_names: StringVectorBuilder,
+    ordinal_positions: UInt32VectorBuilder,
+    positions_in_unique_constraint: UInt32VectorBuilder,
+    referenced_table_schemas: StringVectorBuilder,
+    referenced_table_names: StringVectorBuilder,
+    referenced_column_names: StringVectorBuilder,
+}
+
+impl InformationSchemaKeyColumnUsageBuilder {
+    fn new(
+        schema: SchemaRef,
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        filter: Option<MetadataFilterRef>,
+    ) -> Self {
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+            filter,
+            constraint_catalogs: StringVectorBuilder::with_capacity(42),
+            constraint_schemas: StringVectorBuilder::with_capacity(42),
+            constraint_names: StringVectorBuilder::with_capacity(42),
+            table_catalogs: StringVectorBuilder::with_capacity(42),
+            table_schemas: StringVectorBuilder::with_capacity(42),
+            table_names: StringVectorBuilder::with_capacity(42),
+            column_names: StringVectorBuilder::with_capacity(42),
+            ordinal_positions: UInt32VectorBuilder::with_capacity(42),
+            positions_in_unique_constraint: UInt32VectorBuilder::with_capacity(42),
+            referenced_table_schemas: StringVectorBuilder::with_capacity(42),
+            referenced_table_names: StringVectorBuilder::with_capacity(42),
+            referenced_column_names: StringVectorBuilder::with_capacity(42),
+        }
+    }
+
+    /// Construct the `information_schema.key_column_usage` virtual table
+    async fn make_key_column_usage(&mut self) -> Result<RecordBatch> {
+        let catalog_name = self.catalog_name.clone();
+        let catalog_manager = self
+            .catalog_manager
+            .upgrade()
+            .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+        for schema_name in catalog_manager.schema_names(&catalog_name).await? {
+            if !is_visible(&self.filter, &catalog_name, &schema_name, None)
+                || !catalog_manager
+                    .schema_exist(&catalog_name, &schema_name)
+                    .await?
+            {
+                continue;
+            }
+
+            for table_name in catalog_manager
+                .table_names(&catalog_name, &schema_name)
+                .await?
+            {
+                if !is_visible(&self.filter, &catalog_name, &schema_name, Some(&table_name)) {
+                    continue;
+                }
+                let Some(table) = catalog_manager
+                    .table(&catalog_name, &schema_name, &table_name)
+                    .await?
+                else {
+                    continue;
+                };
+
+                let table_info = table.table_info();
+                let schema = table.schema();
+                if let Some(time_index) = schema.timestamp_column() {
+                    self.add_key_column_usage(
+                        &catalog_name,
+                        &schema_name,
+                        &table_name,
+                        TIME_INDEX_CONSTRAINT_NAME,
+                        &time_index.name,
+                        1,
+                    );
+                }
+                for (position, idx) in table_info.meta.primary_key_indices.iter().enumerate() {
+                    let column = &schema.column_schemas()[*idx];
+                    self.add_key_column_usage(
+                        &catalog_name,
+                        &schema_name,
+                        &table_name,
+                        PRIMARY_CONSTRAINT_NAME,
+                        &column.name,
+                        position as u32 + 1,
+                    );
+                }
+            }
+        }
+
+        self.finish()
+    }
+
+    fn add_key_column_usage(
+        &mut self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        constraint_name: &str,
+        column_name: &str,
+        ordinal_position: u32,
+    ) {
+        self.constraint_catalogs.push(Some(CONSTRAINT_CATALOG));
+        self.constraint_schemas.push(Some(schema_name));
+        self.constraint_names.push(Some(constraint_name));
+        self.table_catalogs.push(Some(catalog_name));
+        self.table_schemas.push(Some(schema_name));
+        self.table_names.push(Some(table_name));
+        self.column_names.push(Some(column_name));
+        self.ordinal_positions.push(Some(ordinal_position));
+        // We don't support foreign keys.
+        self.positions_in_unique_constraint.push(None);
+        self.referenced_table_schemas.push(None);
+        self.referenced_table_names.push(None);
+        self.referenced_column_names.push(None);
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.constraint_catalogs.finish()),
+            Arc::new(self.constraint_schemas.finish()),
+            Arc::new(self.constraint_names.finish()),
+            Arc::new(self.table_catalogs.finish()),
+            Arc::new(self.table_schemas.finish()),
+            Arc::new(self.table_names.finish()),
+            Arc::new(self.column_names.finish()),
+            Arc::new(self.ordinal_positions.finish()),
+            Arc::new(self.positions_in_unique_constraint.finish()),
+            Arc::new(self.referenced_table_schemas.finish()),
+            Arc::new(self.referenced_table_names.finish()),
+            Arc::new(self.referenced_column_names.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaKeyColumnUsage {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_key_column_usage()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
@@ -0,0 +1,351 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_catalog::consts::INFORMATION_SCHEMA_PARTITIONS_TABLE_ID;
+use common_error::ext::BoxedError;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{Int64VectorBuilder, StringVectorBuilder, UInt64VectorBuilder};
+use partition::manager::PartitionRuleManagerRef;
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::{RegionId, TableId};
+use table::metadata::TableType;
+
+use super::PARTITIONS;
+use crate::error::{
+    CreateRecordBatchSnafu, FindPartitionsSnafu, InternalSnafu, Result,
+    UpgradeWeakCatalogManagerRefSnafu,
+};
+use crate::information_schema::{is_visible, InformationTable, MetadataFilterRef};
+use crate::CatalogManager;
+
+/// The `information_schema.partitions` table implementation.
+pub(super) struct InformationSchemaPartitions {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    partition_manager: Option<PartitionRuleManagerRef>,
+    filter: Option<MetadataFilterRef>,
+}
+
+impl InformationSchemaPartitions {
+    pub(super) fn new(
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        partition_manager: Option<PartitionRuleManagerRef>,
+        filter: Option<MetadataFilterRef>,
+    ) -> Self {
+        Self {
+            schema: Self::schema(),
+            catalog_name,
+            catalog_manager,
+            partition_manager,
+            filter,
+        }
+    }
+
+    pub(crate) fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            ColumnSchema::new("table_catalog", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_schema", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_name", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("partition_name", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "partition_ordinal_position",
+                ConcreteDataType::int64_datatype(),
+                false,
+            ),
+            ColumnSchema::new(
+                "partition_method",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+            ColumnSchema::new(
+                "partition_expression",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+            ColumnSchema::new(
+                "partition_description",
+                ConcreteDataType::string_datatype(),
+                true,
+            ),
+            ColumnSchema::new(
+                "greptime_partition_id",
+                ConcreteDataType::uint64_datatype(),
+                false,
+            ),
+        ]))
+    }
+
+    fn builder(&self) -> InformationSchemaPartitionsBuilder {
+        InformationSchemaPartitionsBuilder::new(
+            self.schema.clone(),
+            self.catalog_name.clone(),
+            self.catalog_manager.clone(),
+            self.partition_manager.clone(),
+            self.filter.clone(),
+        )
+    }
+}
+
+impl InformationTable for InformationSchemaPartitions {
+    fn table_id(&self) -> TableId {
+        INFORMATION_SCHEMA_PARTITIONS_TABLE_ID
+    }
+
+    fn table_name(&self) -> &'static str {
+        PARTITIONS
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_partitions()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.partitions` table row by row
+///
+/// Columns are based on <https://dev.mysql.com/doc/refman/8.0/en/information-schema-partitions-table.html>,
+/// with an extra `greptime_partition_id` column holding the region id of the partition.
+struct InformationSchemaPartitionsBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    partition_manager: Option<PartitionRuleManagerRef>,
+    filter: Option<MetadataFilterRef>,
+
+    catalog_names: StringVectorBuilder,
+    schema_names: StringVectorBuilder,
+    table_names: StringVectorBuilder,
+    partition_names: StringVectorBuilder,
+    partition_ordinal_positions: Int64VectorBuilder,
+    partition_methods: StringVectorBuilder,
+    partition_expressions: StringVectorBuilder,
+    partition_descriptions: StringVectorBuilder,
+    partition_ids: UInt64VectorBuilder,
+}
+
+impl InformationSchemaPartitionsBuilder {
+    fn new(
+        schema: SchemaRef,
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        partition_manager: Option<PartitionRuleManagerRef>,
+        filter: Option<MetadataFilterRef>,
+    ) -> Self {
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+            partition_manager,
+            filter,
+            catalog_names: StringVectorBuilder::with_capacity(42),
+            schema_names: StringVectorBuilder::with_capacity(42),
+            table_names: StringVectorBuilder::with_capacity(42),
+            partition_names: StringVectorBuilder::with_capacity(42),
+            partition_ordinal_positions: Int64VectorBuilder::with_capacity(42),
+            partition_methods: StringVectorBuilder::with_capacity(42),
+            partition_expressions: StringVectorBuilder::with_capacity(42),
+            partition_descriptions: StringVectorBuilder::with_capacity(42),
+            partition_ids: UInt64VectorBuilder::with_capacity(42),
+        }
+    }
+
+    /// Construct the `information_schema.partitions` virtual table
+    async fn make_partitions(&mut self) -> Result<RecordBatch> {
+        let catalog_name = self.catalog_name.clone();
+        let catalog_manager = self
+            .catalog_manager
+            .upgrade()
+            .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+        for schema_name in catalog_manager.schema_names(&catalog_name).await? {
+            if !is_visible(&self.filter, &catalog_name, &schema_name, None)
+                || !catalog_manager
+                    .schema_exist(&catalog_name, &schema_name)
+                    .await?
+            {
+                continue;
+            }
+
+            for table_name in catalog_manager
+                .table_names(&catalog_name, &schema_name)
+                .await?
+            {
+                if !is_visible(&self.filter, &catalog_name, &schema_name, Some(&table_name)) {
+                    continue;
+                }
+                let Some(table) = catalog_manager
+                    .table(&catalog_name, &schema_name, &table_name)
+                    .await?
+                else {
+                    continue;
+                };
+
+                let table_info = table.table_info();
+                if table_info.table_type != TableType::Base {
+                    continue;
+                }
+                let table_id = table_info.ident.table_id;
+
+                match &self.partition_manager {
+                    Some(partition_manager) => {
+                        let partitions = partition_manager
+                            .find_table_partitions(table_id)
+                            .await
+                            .context(FindPartitionsSnafu { table_id })?;
+                        for (index, partition) in partitions.iter().enumerate() {
+                            let columns = partition.partition.partition_columns();
+                            let method = if partition.partition.hash_modulus().is_some() {
+                                "HASH"
+                            } else if columns.len() > 1 {
+                                "RANGE COLUMNS"
+                            } else {
+                                "RANGE"
+                            };
+                            let expression = columns.join(", ");
+                            let description = partition
+                                .partition
+                                .partition_bounds()
+                                .iter()
+                                .map(|bound| bound.to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            self.add_partition(
+                                &catalog_name,
+                                &schema_name,
+                                &table_name,
+                                index,
+                                Some(method),
+                                Some(&expression),
+                                Some(&description),
+                                partition.id,
+                            );
+                        }
+                    }
+                    None => {
+                        // Without a partition manager, tables are not partitioned, so we
+                        // report each region of the table as a partition without a rule.
+                        for (index, region_number) in
+                            table_info.meta.region_numbers.iter().enumerate()
+                        {
+                            self.add_partition(
+                                &catalog_name,
+                                &schema_name,
+                                &table_name,
+                                index,
+                                None,
+                                None,
+                                None,
+                                RegionId::new(table_id, *region_number),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        self.finish()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_partition(
+        &mut self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        index: usize,
+        method: Option<&str>,
+        expression: Option<&str>,
+        description: Option<&str>,
+        region_id: RegionId,
+    ) {
+        self.catalog_names.push(Some(catalog_name));
+        self.schema_names.push(Some(schema_name));
+        self.table_names.push(Some(table_name));
+        self.partition_names
+            .push(Some(&format!("p{}", region_id.region_number())));
+        self.partition_ordinal_positions
+            .push(Some(index as i64 + 1));
+        self.partition_methods.push(method);
+        self.partition_expressions.push(expression);
+        self.partition_descriptions.push(description);
+        self.partition_ids.push(Some(region_id.as_u64()));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.catalog_names.finish()),
+            Arc::new(self.schema_names.finish()),
+            Arc::new(self.table_names.finish()),
+            Arc::new(self.partition_names.finish()),
+            Arc::new(self.partition_ordinal_positions.finish()),
+            Arc::new(self.partition_methods.finish()),
+            Arc::new(self.partition_expressions.finish()),
+            Arc::new(self.partition_descriptions.finish()),
+            Arc::new(self.partition_ids.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaPartitions {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_partitions()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
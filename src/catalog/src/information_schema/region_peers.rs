@@ -0,0 +1,311 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Weak};
+
+use arrow_schema::SchemaRef as ArrowSchemaRef;
+use common_catalog::consts::INFORMATION_SCHEMA_REGION_PEERS_TABLE_ID;
+use common_error::ext::BoxedError;
+use common_meta::peer::Peer;
+use common_query::physical_plan::TaskContext;
+use common_recordbatch::adapter::RecordBatchStreamAdapter;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter as DfRecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream as DfPartitionStream;
+use datafusion::physical_plan::SendableRecordBatchStream as DfSendableRecordBatchStream;
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder, VectorRef};
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVectorBuilder, UInt32VectorBuilder, UInt64VectorBuilder};
+use partition::manager::PartitionRuleManagerRef;
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::{RegionId, TableId};
+use table::metadata::TableType;
+
+use super::REGION_PEERS;
+use crate::error::{
+    CreateRecordBatchSnafu, FindRegionRoutesSnafu, InternalSnafu, Result,
+    UpgradeWeakCatalogManagerRefSnafu,
+};
+use crate::information_schema::{is_visible, InformationTable, MetadataFilterRef};
+use crate::CatalogManager;
+
+/// The `information_schema.region_peers` table implementation.
+pub(super) struct InformationSchemaRegionPeers {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    partition_manager: Option<PartitionRuleManagerRef>,
+    filter: Option<MetadataFilterRef>,
+}
+
+impl InformationSchemaRegionPeers {
+    pub(super) fn new(
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        partition_manager: Option<PartitionRuleManagerRef>,
+        filter: Option<MetadataFilterRef>,
+    ) -> Self {
+        Self {
+            schema: Self::schema(),
+            catalog_name,
+            catalog_manager,
+            partition_manager,
+            filter,
+        }
+    }
+
+    pub(crate) fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            ColumnSchema::new("table_catalog", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_schema", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("table_name", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("region_id", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("region_number", ConcreteDataType::uint32_datatype(), false),
+            ColumnSchema::new("peer_id", ConcreteDataType::uint64_datatype(), true),
+            ColumnSchema::new("peer_addr", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("is_leader", ConcreteDataType::string_datatype(), false),
+        ]))
+    }
+
+    fn builder(&self) -> InformationSchemaRegionPeersBuilder {
+        InformationSchemaRegionPeersBuilder::new(
+            self.schema.clone(),
+            self.catalog_name.clone(),
+            self.catalog_manager.clone(),
+            self.partition_manager.clone(),
+            self.filter.clone(),
+        )
+    }
+}
+
+impl InformationTable for InformationSchemaRegionPeers {
+    fn table_id(&self) -> TableId {
+        INFORMATION_SCHEMA_REGION_PEERS_TABLE_ID
+    }
+
+    fn table_name(&self) -> &'static str {
+        REGION_PEERS
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn to_stream(&self) -> Result<SendableRecordBatchStream> {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        let stream = Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_region_peers()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ));
+        Ok(Box::pin(
+            RecordBatchStreamAdapter::try_new(stream)
+                .map_err(BoxedError::new)
+                .context(InternalSnafu)?,
+        ))
+    }
+}
+
+/// Builds the `information_schema.region_peers` table row by row
+///
+/// Each region has one row for its leader peer and one row for every follower peer.
+struct InformationSchemaRegionPeersBuilder {
+    schema: SchemaRef,
+    catalog_name: String,
+    catalog_manager: Weak<dyn CatalogManager>,
+    partition_manager: Option<PartitionRuleManagerRef>,
+    filter: Option<MetadataFilterRef>,
+
+    table_catalogs: StringVectorBuilder,
+    table_schemas: StringVectorBuilder,
+    table_names: StringVectorBuilder,
+    region_ids: UInt64VectorBuilder,
+    region_numbers: UInt32VectorBuilder,
+    peer_ids: UInt64VectorBuilder,
+    peer_addrs: StringVectorBuilder,
+    is_leaders: StringVectorBuilder,
+}
+
+impl InformationSchemaRegionPeersBuilder {
+    fn new(
+        schema: SchemaRef,
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        partition_manager: Option<PartitionRuleManagerRef>,
+        filter: Option<MetadataFilterRef>,
+    ) -> Self {
+        Self {
+            schema,
+            catalog_name,
+            catalog_manager,
+            partition_manager,
+            filter,
+            table_catalogs: StringVectorBuilder::with_capacity(42),
+            table_schemas: StringVectorBuilder::with_capacity(42),
+            table_names: StringVectorBuilder::with_capacity(42),
+            region_ids: UInt64VectorBuilder::with_capacity(42),
+            region_numbers: UInt32VectorBuilder::with_capacity(42),
+            peer_ids: UInt64VectorBuilder::with_capacity(42),
+            peer_addrs: StringVectorBuilder::with_capacity(42),
+            is_leaders: StringVectorBuilder::with_capacity(42),
+        }
+    }
+
+    /// Construct the `information_schema.region_peers` virtual table
+    async fn make_region_peers(&mut self) -> Result<RecordBatch> {
+        let catalog_name = self.catalog_name.clone();
+        let catalog_manager = self
+            .catalog_manager
+            .upgrade()
+            .context(UpgradeWeakCatalogManagerRefSnafu)?;
+
+        for schema_name in catalog_manager.schema_names(&catalog_name).await? {
+            if !is_visible(&self.filter, &catalog_name, &schema_name, None)
+                || !catalog_manager
+                    .schema_exist(&catalog_name, &schema_name)
+                    .await?
+            {
+                continue;
+            }
+
+            for table_name in catalog_manager
+                .table_names(&catalog_name, &schema_name)
+                .await?
+            {
+                if !is_visible(&self.filter, &catalog_name, &schema_name, Some(&table_name)) {
+                    continue;
+                }
+                let Some(table) = catalog_manager
+                    .table(&catalog_name, &schema_name, &table_name)
+                    .await?
+                else {
+                    continue;
+                };
+
+                let table_info = table.table_info();
+                if table_info.table_type != TableType::Base {
+                    continue;
+                }
+                let table_id = table_info.ident.table_id;
+
+                match &self.partition_manager {
+                    Some(partition_manager) => {
+                        let table_route = partition_manager
+                            .find_table_route(table_id)
+                            .await
+                            .context(FindRegionRoutesSnafu { table_id })?;
+                        for route in &table_route.region_routes {
+                            let region_id = route.region.id;
+                            self.add_region_peer(
+                                &catalog_name,
+                                &schema_name,
+                                &table_name,
+                                region_id,
+                                route.leader_peer.as_ref(),
+                                true,
+                            );
+                            for follower in &route.follower_peers {
+                                self.add_region_peer(
+                                    &catalog_name,
+                                    &schema_name,
+                                    &table_name,
+                                    region_id,
+                                    Some(follower),
+                                    false,
+                                );
+                            }
+                        }
+                    }
+                    None => {
+                        // All regions are served by the local node in standalone mode.
+                        for region_number in &table_info.meta.region_numbers {
+                            self.add_region_peer(
+                                &catalog_name,
+                                &schema_name,
+                                &table_name,
+                                RegionId::new(table_id, *region_number),
+                                None,
+                                true,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        self.finish()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_region_peer(
+        &mut self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+        region_id: RegionId,
+        peer: Option<&Peer>,
+        is_leader: bool,
+    ) {
+        self.table_catalogs.push(Some(catalog_name));
+        self.table_schemas.push(Some(schema_name));
+        self.table_names.push(Some(table_name));
+        self.region_ids.push(Some(region_id.as_u64()));
+        self.region_numbers.push(Some(region_id.region_number()));
+        self.peer_ids.push(peer.map(|peer| peer.id));
+        self.peer_addrs.push(peer.map(|peer| peer.addr.as_str()));
+        self.is_leaders
+            .push(Some(if is_leader { "YES" } else { "NO" }));
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch> {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(self.table_catalogs.finish()),
+            Arc::new(self.table_schemas.finish()),
+            Arc::new(self.table_names.finish()),
+            Arc::new(self.region_ids.finish()),
+            Arc::new(self.region_numbers.finish()),
+            Arc::new(self.peer_ids.finish()),
+            Arc::new(self.peer_addrs.finish()),
+            Arc::new(self.is_leaders.finish()),
+        ];
+        RecordBatch::new(self.schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+impl DfPartitionStream for InformationSchemaRegionPeers {
+    fn schema(&self) -> &ArrowSchemaRef {
+        self.schema.arrow_schema()
+    }
+
+    fn execute(&self, _: Arc<TaskContext>) -> DfSendableRecordBatchStream {
+        let schema = self.schema.arrow_schema().clone();
+        let mut builder = self.builder();
+        Box::pin(DfRecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::once(async move {
+                builder
+                    .make_region_peers()
+                    .await
+                    .map(|x| x.into_df_record_batch())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
+}
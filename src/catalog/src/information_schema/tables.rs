@@ -37,21 +37,27 @@ use super::{COLUMNS, TABLES};
 use crate::error::{
     CreateRecordBatchSnafu, InternalSnafu, Result, UpgradeWeakCatalogManagerRefSnafu,
 };
-use crate::information_schema::InformationTable;
+use crate::information_schema::{is_visible, InformationTable, MetadataFilterRef};
 use crate::CatalogManager;
 
 pub(super) struct InformationSchemaTables {
     schema: SchemaRef,
     catalog_name: String,
     catalog_manager: Weak<dyn CatalogManager>,
+    filter: Option<MetadataFilterRef>,
 }
 
 impl InformationSchemaTables {
-    pub(super) fn new(catalog_name: String, catalog_manager: Weak<dyn CatalogManager>) -> Self {
+    pub(super) fn new(
+        catalog_name: String,
+        catalog_manager: Weak<dyn CatalogManager>,
+        filter: Option<MetadataFilterRef>,
+    ) -> Self {
         Self {
             schema: Self::schema(),
             catalog_name,
             catalog_manager,
+            filter,
         }
     }
 
@@ -71,6 +77,7 @@ impl InformationSchemaTables {
             self.schema.clone(),
             self.catalog_name.clone(),
             self.catalog_manager.clone(),
+            self.filter.clone(),
         )
     }
 }
@@ -116,6 +123,7 @@ struct InformationSchemaTablesBuilder {
     schema: SchemaRef,
     catalog_name: String,
     catalog_manager: Weak<dyn CatalogManager>,
+    filter: Option<MetadataFilterRef>,
 
     catalog_names: StringVectorBuilder,
     schema_names: StringVectorBuilder,
@@ -130,11 +138,13 @@ impl InformationSchemaTablesBuilder {
         schema: SchemaRef,
         catalog_name: String,
         catalog_manager: Weak<dyn CatalogManager>,
+        filter: Option<MetadataFilterRef>,
     ) -> Self {
         Self {
             schema,
             catalog_name,
             catalog_manager,
+            filter,
             catalog_names: StringVectorBuilder::with_capacity(42),
             schema_names: StringVectorBuilder::with_capacity(42),
             table_names: StringVectorBuilder::with_capacity(42),
@@ -153,9 +163,10 @@ impl InformationSchemaTablesBuilder {
             .context(UpgradeWeakCatalogManagerRefSnafu)?;
 
         for schema_name in catalog_manager.schema_names(&catalog_name).await? {
-            if !catalog_manager
-                .schema_exist(&catalog_name, &schema_name)
-                .await?
+            if !is_visible(&self.filter, &catalog_name, &schema_name, None)
+                || !catalog_manager
+                    .schema_exist(&catalog_name, &schema_name)
+                    .await?
             {
                 continue;
             }
@@ -164,6 +175,9 @@ impl InformationSchemaTablesBuilder {
                 .table_names(&catalog_name, &schema_name)
                 .await?
             {
+                if !is_visible(&self.filter, &catalog_name, &schema_name, Some(&table_name)) {
+                    continue;
+                }
                 if let Some(table) = catalog_manager
                     .table(&catalog_name, &schema_name, &table_name)
                     .await?
@@ -22,6 +22,7 @@ use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
 use api::v1::meta::{RegionStat, TableIdent, TableName};
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
 use common_telemetry::{info, warn};
 use snafu::ResultExt;
 use table::engine::{EngineContext, TableEngineRef};
@@ -30,6 +31,7 @@ use table::requests::CreateTableRequest;
 use table::TableRef;
 
 use crate::error::{CreateTableSnafu, Result};
+use crate::information_schema::MetadataFilterRef;
 
 pub mod error;
 pub mod information_schema;
@@ -98,6 +100,20 @@ pub trait CatalogManager: Send + Sync {
         schema: &str,
         table_name: &str,
     ) -> Result<Option<TableRef>>;
+
+    /// Returns the table in information schema, which only contains rows of the schemas and
+    /// tables that pass the `filter`.
+    ///
+    /// Catalog managers that build information schema tables ahead of time ignore the `filter`.
+    async fn information_schema_table(
+        &self,
+        catalog: &str,
+        table_name: &str,
+        _filter: Option<MetadataFilterRef>,
+    ) -> Result<Option<TableRef>> {
+        self.table(catalog, INFORMATION_SCHEMA_NAME, table_name)
+            .await
+    }
 }
 
 pub type CatalogManagerRef = Arc<dyn CatalogManager>;
@@ -25,6 +25,7 @@ use snafu::{ensure, OptionExt};
 use table::table::adapter::DfTableProviderAdapter;
 
 use crate::error::{QueryAccessDeniedSnafu, Result, TableNotExistSnafu};
+use crate::information_schema::MetadataFilterRef;
 use crate::CatalogManagerRef;
 
 pub struct DfTableSourceProvider {
@@ -33,6 +34,8 @@ pub struct DfTableSourceProvider {
     disallow_cross_schema_query: bool,
     default_catalog: String,
     default_schema: String,
+    /// Filters rows of information schema tables by the current user.
+    information_schema_filter: Option<MetadataFilterRef>,
 }
 
 impl DfTableSourceProvider {
@@ -47,9 +50,16 @@ impl DfTableSourceProvider {
             resolved_tables: HashMap::new(),
             default_catalog: query_ctx.current_catalog().to_owned(),
             default_schema: query_ctx.current_schema().to_owned(),
+            information_schema_filter: None,
         }
     }
 
+    /// Sets the filter of schemas and tables that are visible in information schema tables.
+    pub fn with_information_schema_filter(mut self, filter: Option<MetadataFilterRef>) -> Self {
+        self.information_schema_filter = filter;
+        self
+    }
+
     pub fn resolve_table_ref<'a>(
         &'a self,
         table_ref: TableReference<'a>,
@@ -101,13 +111,22 @@ impl DfTableSourceProvider {
         let schema_name = table_ref.schema.as_ref();
         let table_name = table_ref.table.as_ref();
 
-        let table = self
-            .catalog_manager
-            .table(catalog_name, schema_name, table_name)
-            .await?
-            .with_context(|| TableNotExistSnafu {
-                table: format_full_table_name(catalog_name, schema_name, table_name),
-            })?;
+        let table = if schema_name == INFORMATION_SCHEMA_NAME {
+            self.catalog_manager
+                .information_schema_table(
+                    catalog_name,
+                    table_name,
+                    self.information_schema_filter.clone(),
+                )
+                .await?
+        } else {
+            self.catalog_manager
+                .table(catalog_name, schema_name, table_name)
+                .await?
+        }
+        .with_context(|| TableNotExistSnafu {
+            table: format_full_table_name(catalog_name, schema_name, table_name),
+        })?;
 
         let provider = DfTableProviderAdapter::new(table);
         let source = provider_as_source(Arc::new(provider));
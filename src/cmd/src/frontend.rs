@@ -14,7 +14,7 @@
 
 use std::sync::Arc;
 
-use auth::UserProviderRef;
+use auth::{RbacOptions, UserProviderRef, RBAC_USER_PROVIDER};
 use clap::Parser;
 use common_base::Plugins;
 use common_telemetry::logging;
@@ -219,8 +219,20 @@ pub fn load_frontend_plugins(user_provider: &Option<String>) -> Result<Plugins>
     let plugins = Plugins::new();
 
     if let Some(provider) = user_provider {
-        let provider = auth::user_provider_from_option(provider).context(IllegalAuthConfigSnafu)?;
-        plugins.insert::<UserProviderRef>(provider);
+        // Users of the RBAC provider are stored in the metadata, so the provider
+        // is built along with the frontend instance.
+        if let Some(superusers) = provider
+            .strip_prefix(RBAC_USER_PROVIDER)
+            .and_then(|s| s.strip_prefix(':'))
+        {
+            plugins.insert(RbacOptions {
+                superusers: superusers.to_string(),
+            });
+        } else {
+            let provider =
+                auth::user_provider_from_option(provider).context(IllegalAuthConfigSnafu)?;
+            plugins.insert::<UserProviderRef>(provider);
+        }
     }
     Ok(plugins)
 }
@@ -357,6 +369,17 @@ mod tests {
         let _ = result.unwrap();
     }
 
+    #[test]
+    fn test_load_rbac_options() {
+        let plugins =
+            load_frontend_plugins(&Some("rbac_user_provider:cmd:root=123456".to_string())).unwrap();
+        assert!(plugins.get::<UserProviderRef>().is_none());
+        assert_eq!(
+            "cmd:root=123456",
+            plugins.get::<RbacOptions>().unwrap().superusers
+        );
+    }
+
     #[test]
     fn test_top_level_options() {
         let cmd = StartCommand {
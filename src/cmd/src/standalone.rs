@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use auth::RbacOptions;
 use clap::Parser;
 use common_base::Plugins;
 use common_telemetry::info;
@@ -24,12 +25,13 @@ use frontend::frontend::FrontendOptions;
 use frontend::instance::{FrontendInstance, Instance as FeInstance};
 use frontend::service_config::{
     GrpcOptions, InfluxdbOptions, MysqlOptions, OpentsdbOptions, PostgresOptions, PromStoreOptions,
+    RuleOptions,
 };
 use serde::{Deserialize, Serialize};
 use servers::http::HttpOptions;
 use servers::tls::{TlsMode, TlsOption};
 use servers::Mode;
-use snafu::ResultExt;
+use snafu::{ensure, ResultExt};
 
 use crate::error::{
     IllegalConfigSnafu, Result, ShutdownDatanodeSnafu, ShutdownFrontendSnafu, StartDatanodeSnafu,
@@ -90,6 +92,7 @@ pub struct StandaloneOptions {
     pub opentsdb_options: Option<OpentsdbOptions>,
     pub influxdb_options: Option<InfluxdbOptions>,
     pub prom_store_options: Option<PromStoreOptions>,
+    pub rule_options: Option<RuleOptions>,
     pub wal: WalConfig,
     pub storage: StorageConfig,
     pub procedure: ProcedureConfig,
@@ -109,6 +112,7 @@ impl Default for StandaloneOptions {
             opentsdb_options: Some(OpentsdbOptions::default()),
             influxdb_options: Some(InfluxdbOptions::default()),
             prom_store_options: Some(PromStoreOptions::default()),
+            rule_options: Some(RuleOptions::default()),
             wal: WalConfig::default(),
             storage: StorageConfig::default(),
             procedure: ProcedureConfig::default(),
@@ -128,6 +132,7 @@ impl StandaloneOptions {
             opentsdb_options: self.opentsdb_options,
             influxdb_options: self.influxdb_options,
             prom_store_options: self.prom_store_options,
+            rule_options: self.rule_options,
             meta_client_options: None,
             logging: self.logging,
             ..Default::default()
@@ -295,6 +300,12 @@ impl StartCommand {
 
     async fn build(self, fe_opts: FrontendOptions, dn_opts: DatanodeOptions) -> Result<Instance> {
         let plugins = Arc::new(load_frontend_plugins(&self.user_provider)?);
+        ensure!(
+            plugins.get::<RbacOptions>().is_none(),
+            IllegalConfigSnafu {
+                msg: "Role-based access control is not supported in standalone mode",
+            }
+        );
 
         info!("Standalone start command: {:#?}", self);
         info!(
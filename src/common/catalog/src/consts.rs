@@ -33,9 +33,20 @@ pub const NUMBERS_TABLE_ID: u32 = 2;
 pub const INFORMATION_SCHEMA_TABLES_TABLE_ID: u32 = 3;
 /// id for information_schema.columns
 pub const INFORMATION_SCHEMA_COLUMNS_TABLE_ID: u32 = 4;
+/// id for information_schema.schemata
+pub const INFORMATION_SCHEMA_SCHEMATA_TABLE_ID: u32 = 5;
+/// id for information_schema.engines
+pub const INFORMATION_SCHEMA_ENGINES_TABLE_ID: u32 = 6;
+/// id for information_schema.key_column_usage
+pub const INFORMATION_SCHEMA_KEY_COLUMN_USAGE_TABLE_ID: u32 = 7;
+/// id for information_schema.partitions
+pub const INFORMATION_SCHEMA_PARTITIONS_TABLE_ID: u32 = 8;
+/// id for information_schema.region_peers
+pub const INFORMATION_SCHEMA_REGION_PEERS_TABLE_ID: u32 = 9;
 
 pub const MITO_ENGINE: &str = "mito";
 pub const IMMUTABLE_FILE_ENGINE: &str = "file";
+pub const VIEW_ENGINE: &str = "view";
 
 pub const SEMANTIC_TYPE_PRIMARY_KEY: &str = "PRIMARY KEY";
 pub const SEMANTIC_TYPE_FIELD: &str = "FIELD";
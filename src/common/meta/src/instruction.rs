@@ -14,6 +14,7 @@
 
 use std::fmt::{Display, Formatter};
 
+use datatypes::value::Value;
 use serde::{Deserialize, Serialize};
 
 use crate::ident::TableIdent;
@@ -62,12 +63,58 @@ impl Display for SimpleReply {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct RegionSplitKeyReply {
+    pub result: bool,
+    /// The key to split the region at, `None` if the region has too few distinct keys to split.
+    pub split_key: Option<Vec<Value>>,
+    pub error: Option<String>,
+}
+
+impl Display for RegionSplitKeyReply {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "(result={}, split_key={:?}, error={:?})",
+            self.result, self.split_key, self.error
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Instruction {
     OpenRegion(RegionIdent),
     CloseRegion(RegionIdent),
+    /// Stops the writes to the region and flushes it, so that all its data can be read from
+    /// the shared storage.
+    DowngradeRegion(RegionIdent),
+    /// Makes the region catch up with the latest data in the shared storage and accept writes.
+    UpgradeRegion(RegionIdent),
+    /// Finds the key that splits the rows of the region into two halves by the partition columns.
+    GetRegionSplitKey {
+        region: RegionIdent,
+        partition_columns: Vec<String>,
+    },
+    /// Stops the writes to the region, creates the region `new_region_number` of the same table
+    /// and moves the rows not less than `split_key` to it.
+    SplitRegion {
+        region: RegionIdent,
+        new_region_number: u32,
+        partition_columns: Vec<String>,
+        split_key: Vec<Value>,
+    },
+    /// Stops the writes to both regions, moves all rows of the region `from_region_number` into
+    /// the region and drops the region `from_region_number`.
+    MergeRegions {
+        region: RegionIdent,
+        from_region_number: u32,
+    },
     InvalidateTableCache(TableIdent),
+    /// Invalidates the cached users and roles, whose raw keys in the metadata are given.
+    InvalidateUserCache {
+        keys: Vec<String>,
+    },
 }
 
 impl Display for Instruction {
@@ -75,7 +122,40 @@ impl Display for Instruction {
         match self {
             Self::OpenRegion(region) => write!(f, "Instruction::OpenRegion({})", region),
             Self::CloseRegion(region) => write!(f, "Instruction::CloseRegion({})", region),
+            Self::DowngradeRegion(region) => {
+                write!(f, "Instruction::DowngradeRegion({})", region)
+            }
+            Self::UpgradeRegion(region) => write!(f, "Instruction::UpgradeRegion({})", region),
+            Self::GetRegionSplitKey {
+                region,
+                partition_columns,
+            } => write!(
+                f,
+                "Instruction::GetRegionSplitKey({}, {:?})",
+                region, partition_columns
+            ),
+            Self::SplitRegion {
+                region,
+                new_region_number,
+                split_key,
+                ..
+            } => write!(
+                f,
+                "Instruction::SplitRegion({}, new_region_no='{}', split_key={:?})",
+                region, new_region_number, split_key
+            ),
+            Self::MergeRegions {
+                region,
+                from_region_number,
+            } => write!(
+                f,
+                "Instruction::MergeRegions({}, from_region_no='{}')",
+                region, from_region_number
+            ),
             Self::InvalidateTableCache(table) => write!(f, "Instruction::Invalidate({})", table),
+            Self::InvalidateUserCache { keys } => {
+                write!(f, "Instruction::InvalidateUserCache({:?})", keys)
+            }
         }
     }
 }
@@ -85,7 +165,13 @@ impl Display for Instruction {
 pub enum InstructionReply {
     OpenRegion(SimpleReply),
     CloseRegion(SimpleReply),
+    DowngradeRegion(SimpleReply),
+    UpgradeRegion(SimpleReply),
+    GetRegionSplitKey(RegionSplitKeyReply),
+    SplitRegion(SimpleReply),
+    MergeRegions(SimpleReply),
     InvalidateTableCache(SimpleReply),
+    InvalidateUserCache(SimpleReply),
 }
 
 impl Display for InstructionReply {
@@ -93,9 +179,23 @@ impl Display for InstructionReply {
         match self {
             Self::OpenRegion(reply) => write!(f, "InstructionReply::OpenRegion({})", reply),
             Self::CloseRegion(reply) => write!(f, "InstructionReply::CloseRegion({})", reply),
+            Self::DowngradeRegion(reply) => {
+                write!(f, "InstructionReply::DowngradeRegion({})", reply)
+            }
+            Self::UpgradeRegion(reply) => {
+                write!(f, "InstructionReply::UpgradeRegion({})", reply)
+            }
+            Self::GetRegionSplitKey(reply) => {
+                write!(f, "InstructionReply::GetRegionSplitKey({})", reply)
+            }
+            Self::SplitRegion(reply) => write!(f, "InstructionReply::SplitRegion({})", reply),
+            Self::MergeRegions(reply) => write!(f, "InstructionReply::MergeRegions({})", reply),
             Self::InvalidateTableCache(reply) => {
                 write!(f, "InstructionReply::Invalidate({})", reply)
             }
+            Self::InvalidateUserCache(reply) => {
+                write!(f, "InstructionReply::InvalidateUserCache({})", reply)
+            }
         }
     }
 }
@@ -145,5 +245,41 @@ mod tests {
             r#"{"type":"close_region","cluster_id":1,"datanode_id":2,"table_ident":{"catalog":"foo","schema":"bar","table":"hi","table_id":1024,"engine":"mito"},"region_number":1}"#,
             serialized
         );
+
+        let invalidate_user_cache = Instruction::InvalidateUserCache {
+            keys: vec!["__user/alice".to_string()],
+        };
+
+        let serialized = serde_json::to_string(&invalidate_user_cache).unwrap();
+
+        assert_eq!(
+            r#"{"type":"invalidate_user_cache","keys":["__user/alice"]}"#,
+            serialized
+        );
+
+        let split_region = Instruction::SplitRegion {
+            region: RegionIdent {
+                cluster_id: 1,
+                datanode_id: 2,
+                table_ident: TableIdent {
+                    catalog: "foo".to_string(),
+                    schema: "bar".to_string(),
+                    table: "hi".to_string(),
+                    table_id: 1024,
+                    engine: "mito".to_string(),
+                },
+                region_number: 1,
+            },
+            new_region_number: 2,
+            partition_columns: vec!["a".to_string()],
+            split_key: vec![Value::Int32(10)],
+        };
+
+        let serialized = serde_json::to_string(&split_region).unwrap();
+
+        assert_eq!(
+            r#"{"type":"split_region","region":{"cluster_id":1,"datanode_id":2,"table_ident":{"catalog":"foo","schema":"bar","table":"hi","table_id":1024,"engine":"mito"},"region_number":1},"new_region_number":2,"partition_columns":["a"],"split_key":[{"Int32":10}]}"#,
+            serialized
+        );
     }
 }
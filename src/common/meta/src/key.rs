@@ -36,6 +36,13 @@
 //!     - The value is a [TableNameValue] struct; it contains the table id.
 //!     - Used in the table name to table id lookup.
 //!
+//! 6. User key: `__user/{username}`
+//!     - The value is a [UserValue] struct; it contains the hashed password, roles and
+//!       privileges of the user.
+//!
+//! 7. Role key: `__role/{role}`
+//!     - The value is a [RoleValue] struct; it contains the privileges of the role.
+//!
 //! All keys have related managers. The managers take care of the serialization and deserialization
 //! of keys and values, and the interaction with the underlying KV store backend.
 //!
@@ -54,6 +61,7 @@ pub mod table_region;
 // TODO(weny): removes it.
 #[allow(deprecated)]
 pub mod table_route;
+pub mod user;
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
@@ -70,6 +78,7 @@ use table_name::{TableNameKey, TableNameManager, TableNameValue};
 use self::catalog_name::{CatalogManager, CatalogNameValue};
 use self::schema_name::{SchemaManager, SchemaNameValue};
 use self::table_route::{TableRouteManager, TableRouteValue};
+use self::user::{RoleValue, UserValue};
 use crate::error::{self, Result, SerdeJsonSnafu};
 #[allow(deprecated)]
 pub use crate::key::table_route::{TableRouteKey, TABLE_ROUTE_PREFIX};
@@ -88,6 +97,8 @@ const TABLE_NAME_KEY_PREFIX: &str = "__table_name";
 const TABLE_REGION_KEY_PREFIX: &str = "__table_region";
 const CATALOG_NAME_KEY_PREFIX: &str = "__catalog_name";
 const SCHEMA_NAME_KEY_PREFIX: &str = "__schema_name";
+const USER_KEY_PREFIX: &str = "__user";
+const ROLE_KEY_PREFIX: &str = "__role";
 
 pub type RegionDistribution = BTreeMap<DatanodeId, Vec<RegionNumber>>;
 
@@ -500,7 +511,9 @@ impl_table_meta_value! {
     TableNameValue,
     TableInfoValue,
     DatanodeTableValue,
-    TableRouteValue
+    TableRouteValue,
+    UserValue,
+    RoleValue
 }
 
 #[cfg(test)]
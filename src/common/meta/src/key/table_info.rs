@@ -280,6 +280,7 @@ mod tests {
             options: Default::default(),
             region_numbers: vec![1],
             partition_key_indices: vec![],
+            view_definition: None,
         };
 
         RawTableInfo {
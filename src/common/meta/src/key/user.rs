@@ -0,0 +1,445 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::key::{TableMetaKey, ROLE_KEY_PREFIX, USER_KEY_PREFIX};
+use crate::kv_backend::KvBackendRef;
+use crate::rpc::store::CompareAndPutRequest;
+
+/// A privilege that can be granted to users or roles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Privilege {
+    /// Reads data or metadata of tables.
+    Read,
+    /// Writes data to tables, or changes the definitions of tables and databases.
+    Write,
+}
+
+/// The object that a privilege is granted on.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GrantObject {
+    /// All catalogs.
+    All,
+    /// All schemas in a catalog.
+    Catalog(String),
+    /// All tables in a schema.
+    Schema { catalog: String, schema: String },
+    /// A single table.
+    Table {
+        catalog: String,
+        schema: String,
+        table: String,
+    },
+}
+
+impl GrantObject {
+    /// Returns true if this object covers the schema `catalog.schema` (when `table` is `None`),
+    /// or the table `catalog.schema.table`.
+    ///
+    /// A table object never covers a whole schema.
+    pub fn covers(&self, catalog: &str, schema: &str, table: Option<&str>) -> bool {
+        match self {
+            GrantObject::All => true,
+            GrantObject::Catalog(c) => c == catalog,
+            GrantObject::Schema {
+                catalog: c,
+                schema: s,
+            } => c == catalog && s == schema,
+            GrantObject::Table {
+                catalog: c,
+                schema: s,
+                table: t,
+            } => c == catalog && s == schema && table == Some(t.as_str()),
+        }
+    }
+
+    /// Returns true if this object is located inside the schema `catalog.schema`,
+    /// or covers the whole schema.
+    pub fn intersects_schema(&self, catalog: &str, schema: &str) -> bool {
+        match self {
+            GrantObject::All => true,
+            GrantObject::Catalog(c) => c == catalog,
+            GrantObject::Schema {
+                catalog: c,
+                schema: s,
+            }
+            | GrantObject::Table {
+                catalog: c,
+                schema: s,
+                ..
+            } => c == catalog && s == schema,
+        }
+    }
+}
+
+impl Display for GrantObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrantObject::All => write!(f, "*.*"),
+            GrantObject::Catalog(catalog) => write!(f, "{catalog}.*.*"),
+            GrantObject::Schema { catalog, schema } => write!(f, "{catalog}.{schema}.*"),
+            GrantObject::Table {
+                catalog,
+                schema,
+                table,
+            } => write!(f, "{catalog}.{schema}.{table}"),
+        }
+    }
+}
+
+/// A privilege on an object.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PrivilegeGrant {
+    pub object: GrantObject,
+    pub privilege: Privilege,
+}
+
+impl PrivilegeGrant {
+    pub fn new(object: GrantObject, privilege: Privilege) -> Self {
+        Self { object, privilege }
+    }
+}
+
+/// Returns true if the raw key is a [UserKey] or a [RoleKey].
+pub fn is_user_or_role_key(key: &[u8]) -> bool {
+    [USER_KEY_PREFIX, ROLE_KEY_PREFIX].iter().any(|prefix| {
+        key.strip_prefix(prefix.as_bytes())
+            .map(|rest| rest.starts_with(b"/"))
+            .unwrap_or(false)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UserKey<'a> {
+    pub username: &'a str,
+}
+
+impl<'a> UserKey<'a> {
+    pub fn new(username: &'a str) -> Self {
+        Self { username }
+    }
+}
+
+impl Display for UserKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", USER_KEY_PREFIX, self.username)
+    }
+}
+
+impl TableMetaKey for UserKey<'_> {
+    fn as_raw_key(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserValue {
+    /// The hashed password of the user. It's up to the caller to decide how
+    /// to hash the password.
+    pub password: String,
+    /// The hashed password for authentication protocols that require a specific hash,
+    /// e.g. the MySQL native password authentication. `None` if they are not supported.
+    #[serde(default)]
+    pub mysql_native_password: Option<String>,
+    /// Roles granted to the user.
+    pub roles: BTreeSet<String>,
+    /// Privileges granted to the user directly.
+    pub grants: BTreeSet<PrivilegeGrant>,
+}
+
+impl UserValue {
+    pub fn new(password: String) -> Self {
+        Self {
+            password,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoleKey<'a> {
+    pub role: &'a str,
+}
+
+impl<'a> RoleKey<'a> {
+    pub fn new(role: &'a str) -> Self {
+        Self { role }
+    }
+}
+
+impl Display for RoleKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", ROLE_KEY_PREFIX, self.role)
+    }
+}
+
+impl TableMetaKey for RoleKey<'_> {
+    fn as_raw_key(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoleValue {
+    /// Privileges granted to the role.
+    pub grants: BTreeSet<PrivilegeGrant>,
+}
+
+/// Manages users, roles and their privileges in the metadata store.
+pub struct UserManager {
+    kv_backend: KvBackendRef,
+}
+
+impl UserManager {
+    pub fn new(kv_backend: KvBackendRef) -> Self {
+        Self { kv_backend }
+    }
+
+    /// Creates the user. Returns false if the user already exists.
+    pub async fn create_user(&self, key: UserKey<'_>, value: &UserValue) -> Result<bool> {
+        let req = CompareAndPutRequest::new()
+            .with_key(key.as_raw_key())
+            .with_value(value.try_as_raw_value()?);
+
+        Ok(self.kv_backend.compare_and_put(req).await?.success)
+    }
+
+    pub async fn get_user(&self, key: UserKey<'_>) -> Result<Option<UserValue>> {
+        self.kv_backend
+            .get(&key.as_raw_key())
+            .await?
+            .map(|kv| UserValue::try_from_raw_value(&kv.value))
+            .transpose()
+    }
+
+    /// Updates the user by `update`. Returns the updated user, or `None` if the user doesn't
+    /// exist.
+    ///
+    /// `update` may be called more than once if the user is changed concurrently.
+    pub async fn update_user(
+        &self,
+        key: UserKey<'_>,
+        update: impl FnMut(&mut UserValue),
+    ) -> Result<Option<UserValue>> {
+        self.update_value(
+            key.as_raw_key(),
+            update,
+            UserValue::try_from_raw_value,
+            UserValue::try_as_raw_value,
+        )
+        .await
+    }
+
+    /// Creates the role. Returns false if the role already exists.
+    pub async fn create_role(&self, key: RoleKey<'_>, value: &RoleValue) -> Result<bool> {
+        let req = CompareAndPutRequest::new()
+            .with_key(key.as_raw_key())
+            .with_value(value.try_as_raw_value()?);
+
+        Ok(self.kv_backend.compare_and_put(req).await?.success)
+    }
+
+    pub async fn get_role(&self, key: RoleKey<'_>) -> Result<Option<RoleValue>> {
+        self.kv_backend
+            .get(&key.as_raw_key())
+            .await?
+            .map(|kv| RoleValue::try_from_raw_value(&kv.value))
+            .transpose()
+    }
+
+    /// Updates the role by `update`. Returns the updated role, or `None` if the role doesn't
+    /// exist.
+    ///
+    /// `update` may be called more than once if the role is changed concurrently.
+    pub async fn update_role(
+        &self,
+        key: RoleKey<'_>,
+        update: impl FnMut(&mut RoleValue),
+    ) -> Result<Option<RoleValue>> {
+        self.update_value(
+            key.as_raw_key(),
+            update,
+            RoleValue::try_from_raw_value,
+            RoleValue::try_as_raw_value,
+        )
+        .await
+    }
+
+    /// Reads, updates and writes back the value of `raw_key`, retrying if the value is changed
+    /// by others between reading and writing, so that concurrent updates are not lost.
+    async fn update_value<T>(
+        &self,
+        raw_key: Vec<u8>,
+        mut update: impl FnMut(&mut T),
+        decode: fn(&[u8]) -> Result<T>,
+        encode: fn(&T) -> Result<Vec<u8>>,
+    ) -> Result<Option<T>> {
+        loop {
+            let Some(kv) = self.kv_backend.get(&raw_key).await? else {
+                return Ok(None);
+            };
+            let mut value = decode(&kv.value)?;
+            update(&mut value);
+            let raw_value = encode(&value)?;
+            if raw_value == kv.value {
+                return Ok(Some(value));
+            }
+
+            let req = CompareAndPutRequest::new()
+                .with_key(raw_key.clone())
+                .with_expect(kv.value)
+                .with_value(raw_value);
+            if self.kv_backend.compare_and_put(req).await?.success {
+                return Ok(Some(value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::kv_backend::memory::MemoryKvBackend;
+
+    #[test]
+    fn test_key_serialization() {
+        assert_eq!("__user/alice", UserKey::new("alice").to_string());
+        assert_eq!("__role/reader", RoleKey::new("reader").to_string());
+        assert!(is_user_or_role_key(b"__user/alice"));
+        assert!(is_user_or_role_key(b"__role/reader"));
+        assert!(!is_user_or_role_key(b"__user_alice"));
+        assert!(!is_user_or_role_key(b"__table_info/1024"));
+    }
+
+    #[test]
+    fn test_grant_object_covers() {
+        let table = GrantObject::Table {
+            catalog: "greptime".to_string(),
+            schema: "public".to_string(),
+            table: "foo".to_string(),
+        };
+        assert!(table.covers("greptime", "public", Some("foo")));
+        assert!(!table.covers("greptime", "public", Some("bar")));
+        assert!(!table.covers("greptime", "public", None));
+        assert!(table.intersects_schema("greptime", "public"));
+
+        let schema = GrantObject::Schema {
+            catalog: "greptime".to_string(),
+            schema: "public".to_string(),
+        };
+        assert!(schema.covers("greptime", "public", Some("foo")));
+        assert!(schema.covers("greptime", "public", None));
+        assert!(!schema.covers("greptime", "other", None));
+
+        let catalog = GrantObject::Catalog("greptime".to_string());
+        assert!(catalog.covers("greptime", "other", Some("foo")));
+        assert!(!catalog.covers("other", "public", None));
+
+        assert!(GrantObject::All.covers("other", "other", None));
+    }
+
+    #[tokio::test]
+    async fn test_user_manager() {
+        let manager = UserManager::new(Arc::new(MemoryKvBackend::default()));
+
+        let key = UserKey::new("alice");
+        let mut user = UserValue::new("hashed".to_string());
+        assert!(manager.create_user(key, &user).await.unwrap());
+        assert!(!manager.create_user(key, &user).await.unwrap());
+
+        let _ = user.roles.insert("reader".to_string());
+        let _ = user.grants.insert(PrivilegeGrant::new(
+            GrantObject::Catalog("greptime".to_string()),
+            Privilege::Write,
+        ));
+        let updated = manager
+            .update_user(key, |value| {
+                let _ = value.roles.insert("reader".to_string());
+                let _ = value.grants.insert(PrivilegeGrant::new(
+                    GrantObject::Catalog("greptime".to_string()),
+                    Privilege::Write,
+                ));
+            })
+            .await
+            .unwrap();
+        assert_eq!(Some(&user), updated.as_ref());
+        assert_eq!(Some(user), manager.get_user(key).await.unwrap());
+        assert!(manager
+            .get_user(UserKey::new("bob"))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(manager
+            .update_user(UserKey::new("bob"), |_| {})
+            .await
+            .unwrap()
+            .is_none());
+
+        let key = RoleKey::new("reader");
+        let mut role = RoleValue::default();
+        assert!(manager.create_role(key, &role).await.unwrap());
+        assert!(!manager.create_role(key, &role).await.unwrap());
+
+        let _ = role
+            .grants
+            .insert(PrivilegeGrant::new(GrantObject::All, Privilege::Read));
+        let _ = manager
+            .update_role(key, |value| {
+                let _ = value
+                    .grants
+                    .insert(PrivilegeGrant::new(GrantObject::All, Privilege::Read));
+            })
+            .await
+            .unwrap();
+        assert_eq!(Some(role), manager.get_role(key).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_updates() {
+        let manager = Arc::new(UserManager::new(Arc::new(MemoryKvBackend::default())));
+        let key = RoleKey::new("reader");
+        assert!(manager
+            .create_role(key, &RoleValue::default())
+            .await
+            .unwrap());
+
+        let tasks = (0..10).map(|i| {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                let _ = manager
+                    .update_role(RoleKey::new("reader"), |value| {
+                        let _ = value.grants.insert(PrivilegeGrant::new(
+                            GrantObject::Catalog(format!("catalog_{i}")),
+                            Privilege::Read,
+                        ));
+                    })
+                    .await
+                    .unwrap();
+            })
+        });
+        for task in tasks.collect::<Vec<_>>() {
+            task.await.unwrap();
+        }
+
+        let role = manager.get_role(key).await.unwrap().unwrap();
+        assert_eq!(10, role.grants.len());
+    }
+}
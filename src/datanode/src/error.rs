@@ -87,6 +87,73 @@ pub enum Error {
         region_number: RegionNumber,
     },
 
+    #[snafu(display(
+        "Failed to set region {} in table: {} read-only, source: {}",
+        region_number,
+        table_name,
+        source
+    ))]
+    SetRegionReadOnly {
+        table_name: String,
+        location: Location,
+        source: TableError,
+        region_number: RegionNumber,
+    },
+
+    #[snafu(display(
+        "Failed to get split key of region {} in table: {}, source: {}",
+        region_number,
+        table_name,
+        source
+    ))]
+    GetRegionSplitKey {
+        table_name: String,
+        location: Location,
+        source: TableError,
+        region_number: RegionNumber,
+    },
+
+    #[snafu(display(
+        "Failed to create regions {:?} in table {}, source: {}",
+        region_numbers,
+        table_name,
+        source
+    ))]
+    CreateRegions {
+        table_name: String,
+        region_numbers: Vec<RegionNumber>,
+        location: Location,
+        source: TableError,
+    },
+
+    #[snafu(display(
+        "Failed to drop regions {:?} in table {}, source: {}",
+        region_numbers,
+        table_name,
+        source
+    ))]
+    DropRegions {
+        table_name: String,
+        region_numbers: Vec<RegionNumber>,
+        location: Location,
+        source: TableError,
+    },
+
+    #[snafu(display(
+        "Failed to move rows from region {} to region {} in table: {}, source: {}",
+        from,
+        to,
+        table_name,
+        source
+    ))]
+    MoveRegionRows {
+        table_name: String,
+        from: RegionNumber,
+        to: RegionNumber,
+        location: Location,
+        source: TableError,
+    },
+
     #[snafu(display("Failed to handle heartbeat response, source: {}", source))]
     HandleHeartbeatResponse {
         location: Location,
@@ -622,6 +689,11 @@ impl ErrorExt for Error {
             | RegisterTable { source, .. } => source.status_code(),
 
             CheckRegion { source, .. }
+            | SetRegionReadOnly { source, .. }
+            | GetRegionSplitKey { source, .. }
+            | CreateRegions { source, .. }
+            | DropRegions { source, .. }
+            | MoveRegionRows { source, .. }
             | OpenTable { source, .. }
             | CloseTable { source, .. }
             | GetTable { source, .. } => source.status_code(),
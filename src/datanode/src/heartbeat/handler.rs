@@ -13,4 +13,7 @@
 // limitations under the License.
 
 pub mod close_region;
+pub mod downgrade_region;
 pub mod open_region;
+pub mod region_balance;
+pub mod upgrade_region;
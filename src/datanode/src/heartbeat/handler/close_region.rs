@@ -137,7 +137,7 @@ impl CloseRegionHandler {
         Ok(true)
     }
 
-    async fn close_region_inner(&self, region_ident: RegionIdent) -> Result<bool> {
+    pub(crate) async fn close_region_inner(&self, region_ident: RegionIdent) -> Result<bool> {
         let table_ident = &region_ident.table_ident;
         let engine_name = &table_ident.engine;
         let engine = self
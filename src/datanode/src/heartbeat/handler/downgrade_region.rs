@@ -0,0 +1,146 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use catalog::CatalogManagerRef;
+use common_catalog::format_full_table_name;
+use common_meta::error::Result as MetaResult;
+use common_meta::heartbeat::handler::{
+    HandleControl, HeartbeatResponseHandler, HeartbeatResponseHandlerContext,
+};
+use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_meta::RegionIdent;
+use common_telemetry::{error, info, warn};
+use snafu::ResultExt;
+
+use crate::error::{self, Result};
+
+/// Makes the leader region read-only and flushes it before the region is migrated to another
+/// Datanode, so that the new leader can catch up with all its data from the shared storage.
+#[derive(Clone)]
+pub struct DowngradeRegionHandler {
+    catalog_manager: CatalogManagerRef,
+}
+
+#[async_trait]
+impl HeartbeatResponseHandler for DowngradeRegionHandler {
+    fn is_acceptable(&self, ctx: &HeartbeatResponseHandlerContext) -> bool {
+        matches!(
+            ctx.incoming_message.as_ref(),
+            Some((_, Instruction::DowngradeRegion { .. }))
+        )
+    }
+
+    async fn handle(&self, ctx: &mut HeartbeatResponseHandlerContext) -> MetaResult<HandleControl> {
+        let Some((meta, Instruction::DowngradeRegion(region_ident))) = ctx.incoming_message.take()
+        else {
+            unreachable!("DowngradeRegionHandler: should be guarded by 'is_acceptable'");
+        };
+
+        let mailbox = ctx.mailbox.clone();
+        let self_ref = Arc::new(self.clone());
+        let _handle = common_runtime::spawn_bg(async move {
+            let result = self_ref.downgrade_region_inner(&region_ident).await;
+
+            if let Err(e) = mailbox
+                .send((meta, DowngradeRegionHandler::map_result(result)))
+                .await
+            {
+                error!(e; "Failed to send reply to mailbox");
+            }
+        });
+
+        Ok(HandleControl::Done)
+    }
+}
+
+impl DowngradeRegionHandler {
+    pub fn new(catalog_manager: CatalogManagerRef) -> Self {
+        Self { catalog_manager }
+    }
+
+    fn map_result(result: Result<bool>) -> InstructionReply {
+        result.map_or_else(
+            |error| {
+                InstructionReply::DowngradeRegion(SimpleReply {
+                    result: false,
+                    error: Some(error.to_string()),
+                })
+            },
+            |result| {
+                InstructionReply::DowngradeRegion(SimpleReply {
+                    result,
+                    error: None,
+                })
+            },
+        )
+    }
+
+    /// Returns true if the region is read-only and flushed, false if the region doesn't exist.
+    async fn downgrade_region_inner(&self, region_ident: &RegionIdent) -> Result<bool> {
+        let table_ident = &region_ident.table_ident;
+        let region_number = region_ident.region_number;
+        let table_name = format_full_table_name(
+            &table_ident.catalog,
+            &table_ident.schema,
+            &table_ident.table,
+        );
+
+        let Some(table) = self
+            .catalog_manager
+            .table(
+                &table_ident.catalog,
+                &table_ident.schema,
+                &table_ident.table,
+            )
+            .await
+            .context(error::AccessCatalogSnafu)?
+        else {
+            warn!("Trying to downgrade a region of non-existing table: {table_name}");
+            return Ok(false);
+        };
+
+        let region_exist =
+            table
+                .contains_region(region_number)
+                .with_context(|_| error::CheckRegionSnafu {
+                    table_name: &table_name,
+                    region_number,
+                })?;
+        if !region_exist {
+            warn!("Trying to downgrade a non-existing region {region_number} of {table_name}");
+            return Ok(false);
+        }
+
+        // Rejects new writes first, so no data is left in the memtable after the flush.
+        table
+            .set_region_writable(region_number, false)
+            .await
+            .with_context(|_| error::SetRegionReadOnlySnafu {
+                table_name: &table_name,
+                region_number,
+            })?;
+        table
+            .flush(Some(region_number), Some(true))
+            .await
+            .with_context(|_| error::FlushTableSnafu {
+                table_name: &table_name,
+            })?;
+
+        info!("Region {region_number} of {table_name} is downgraded");
+        Ok(true)
+    }
+}
@@ -24,6 +24,7 @@ use common_meta::heartbeat::handler::{
     HandleControl, HeartbeatResponseHandler, HeartbeatResponseHandlerContext,
 };
 use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_meta::RegionIdent;
 use common_telemetry::{error, warn};
 use snafu::ResultExt;
 use store_api::storage::RegionNumber;
@@ -58,23 +59,8 @@ impl HeartbeatResponseHandler for OpenRegionHandler {
         let mailbox = ctx.mailbox.clone();
         let self_ref = Arc::new(self.clone());
 
-        let region_alive_keepers = self.region_alive_keepers.clone();
         let _handle = common_runtime::spawn_bg(async move {
-            let table_ident = &region_ident.table_ident;
-            let request = OpenTableRequest {
-                catalog_name: table_ident.catalog.clone(),
-                schema_name: table_ident.schema.clone(),
-                table_name: table_ident.table.clone(),
-                table_id: table_ident.table_id,
-                region_numbers: vec![region_ident.region_number],
-            };
-            let result = self_ref
-                .open_region_inner(table_ident.engine.clone(), request)
-                .await;
-
-            if matches!(result, Ok(true)) {
-                region_alive_keepers.register_region(&region_ident).await;
-            }
+            let result = self_ref.open_region(&region_ident).await;
 
             if let Err(e) = mailbox
                 .send((meta, OpenRegionHandler::map_result(result)))
@@ -117,6 +103,28 @@ impl OpenRegionHandler {
         )
     }
 
+    /// Opens the region and keeps it alive. Returns true if the region has been opened.
+    pub(crate) async fn open_region(&self, region_ident: &RegionIdent) -> Result<bool> {
+        let table_ident = &region_ident.table_ident;
+        let request = OpenTableRequest {
+            catalog_name: table_ident.catalog.clone(),
+            schema_name: table_ident.schema.clone(),
+            table_name: table_ident.table.clone(),
+            table_id: table_ident.table_id,
+            region_numbers: vec![region_ident.region_number],
+        };
+        let result = self
+            .open_region_inner(table_ident.engine.clone(), request)
+            .await;
+
+        if matches!(result, Ok(true)) {
+            self.region_alive_keepers
+                .register_region(region_ident)
+                .await;
+        }
+        result
+    }
+
     /// Returns true if a table or target regions have been opened.
     async fn regions_opened(
         &self,
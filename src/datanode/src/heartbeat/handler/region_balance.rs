@@ -0,0 +1,336 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use catalog::remote::region_alive_keeper::RegionAliveKeepers;
+use catalog::CatalogManagerRef;
+use common_catalog::format_full_table_name;
+use common_meta::error::Result as MetaResult;
+use common_meta::heartbeat::handler::{
+    HandleControl, HeartbeatResponseHandler, HeartbeatResponseHandlerContext,
+};
+use common_meta::ident::TableIdent;
+use common_meta::instruction::{Instruction, InstructionReply, RegionSplitKeyReply, SimpleReply};
+use common_meta::RegionIdent;
+use common_telemetry::{error, info, warn};
+use datatypes::value::Value;
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::RegionNumber;
+use table::engine::manager::TableEngineManagerRef;
+use table::engine::EngineContext;
+use table::requests::{MoveRegionRowsRequest, TableRegionsRequest};
+use table::TableRef;
+
+use crate::error::{self, Result};
+
+/// Splits and merges the regions of a range partitioned table, as instructed by the region
+/// balancer of Metasrv.
+///
+/// The regions involved are made read-only before any row is moved, and stay read-only until
+/// Metasrv upgrades them after the table route is updated.
+#[derive(Clone)]
+pub struct RegionBalanceHandler {
+    catalog_manager: CatalogManagerRef,
+    table_engine_manager: TableEngineManagerRef,
+    region_alive_keepers: Arc<RegionAliveKeepers>,
+}
+
+#[async_trait]
+impl HeartbeatResponseHandler for RegionBalanceHandler {
+    fn is_acceptable(&self, ctx: &HeartbeatResponseHandlerContext) -> bool {
+        matches!(
+            ctx.incoming_message.as_ref(),
+            Some((
+                _,
+                Instruction::GetRegionSplitKey { .. }
+                    | Instruction::SplitRegion { .. }
+                    | Instruction::MergeRegions { .. }
+            ))
+        )
+    }
+
+    async fn handle(&self, ctx: &mut HeartbeatResponseHandlerContext) -> MetaResult<HandleControl> {
+        let Some((meta, instruction)) = ctx.incoming_message.take() else {
+            unreachable!("RegionBalanceHandler: should be guarded by 'is_acceptable'");
+        };
+
+        let mailbox = ctx.mailbox.clone();
+        let self_ref = Arc::new(self.clone());
+        let _handle = common_runtime::spawn_bg(async move {
+            let reply = self_ref.handle_instruction(instruction).await;
+
+            if let Err(e) = mailbox.send((meta, reply)).await {
+                error!(e; "Failed to send reply to mailbox");
+            }
+        });
+
+        Ok(HandleControl::Done)
+    }
+}
+
+impl RegionBalanceHandler {
+    pub fn new(
+        catalog_manager: CatalogManagerRef,
+        table_engine_manager: TableEngineManagerRef,
+        region_alive_keepers: Arc<RegionAliveKeepers>,
+    ) -> Self {
+        Self {
+            catalog_manager,
+            table_engine_manager,
+            region_alive_keepers,
+        }
+    }
+
+    async fn handle_instruction(&self, instruction: Instruction) -> InstructionReply {
+        match instruction {
+            Instruction::GetRegionSplitKey {
+                region,
+                partition_columns,
+            } => {
+                let reply = match self.region_split_key(&region, &partition_columns).await {
+                    Ok(split_key) => RegionSplitKeyReply {
+                        result: true,
+                        split_key,
+                        error: None,
+                    },
+                    Err(e) => RegionSplitKeyReply {
+                        result: false,
+                        split_key: None,
+                        error: Some(e.to_string()),
+                    },
+                };
+                InstructionReply::GetRegionSplitKey(reply)
+            }
+            Instruction::SplitRegion {
+                region,
+                new_region_number,
+                partition_columns,
+                split_key,
+            } => InstructionReply::SplitRegion(Self::simple_reply(
+                self.split_region(&region, new_region_number, partition_columns, split_key)
+                    .await,
+            )),
+            Instruction::MergeRegions {
+                region,
+                from_region_number,
+            } => InstructionReply::MergeRegions(Self::simple_reply(
+                self.merge_regions(&region, from_region_number).await,
+            )),
+            _ => unreachable!("RegionBalanceHandler: should be guarded by 'is_acceptable'"),
+        }
+    }
+
+    fn simple_reply(result: Result<()>) -> SimpleReply {
+        match result {
+            Ok(()) => SimpleReply {
+                result: true,
+                error: None,
+            },
+            Err(e) => SimpleReply {
+                result: false,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    async fn table(&self, table_ident: &TableIdent) -> Result<TableRef> {
+        self.catalog_manager
+            .table(
+                &table_ident.catalog,
+                &table_ident.schema,
+                &table_ident.table,
+            )
+            .await
+            .context(error::AccessCatalogSnafu)?
+            .with_context(|| error::TableNotFoundSnafu {
+                table_name: full_table_name(table_ident),
+            })
+    }
+
+    async fn set_read_only(&self, table: &TableRef, region: &RegionIdent) -> Result<()> {
+        table
+            .set_region_writable(region.region_number, false)
+            .await
+            .with_context(|_| error::SetRegionReadOnlySnafu {
+                table_name: full_table_name(&region.table_ident),
+                region_number: region.region_number,
+            })
+    }
+
+    fn regions_request(
+        table_ident: &TableIdent,
+        region_numbers: Vec<RegionNumber>,
+    ) -> TableRegionsRequest {
+        TableRegionsRequest {
+            catalog_name: table_ident.catalog.clone(),
+            schema_name: table_ident.schema.clone(),
+            table_name: table_ident.table.clone(),
+            table_id: table_ident.table_id,
+            region_numbers,
+        }
+    }
+
+    async fn region_split_key(
+        &self,
+        region: &RegionIdent,
+        partition_columns: &[String],
+    ) -> Result<Option<Vec<Value>>> {
+        let table = self.table(&region.table_ident).await?;
+        table
+            .region_split_key(region.region_number, partition_columns)
+            .await
+            .with_context(|_| error::GetRegionSplitKeySnafu {
+                table_name: full_table_name(&region.table_ident),
+                region_number: region.region_number,
+            })
+    }
+
+    /// Creates the new region and moves the rows not less than `split_key` into it. Splitting
+    /// the region again resumes the moving.
+    async fn split_region(
+        &self,
+        region: &RegionIdent,
+        new_region_number: RegionNumber,
+        partition_columns: Vec<String>,
+        split_key: Vec<Value>,
+    ) -> Result<()> {
+        let table_ident = &region.table_ident;
+        let table_name = full_table_name(table_ident);
+        let table = self.table(table_ident).await?;
+        // Rows written to the region during the split might belong to the new region.
+        self.set_read_only(&table, region).await?;
+
+        let engine_name = &table_ident.engine;
+        let engine = self
+            .table_engine_manager
+            .engine(engine_name)
+            .context(error::TableEngineNotFoundSnafu { engine_name })?;
+        let region_numbers = vec![new_region_number];
+        let table = engine
+            .create_regions(
+                &EngineContext::default(),
+                Self::regions_request(table_ident, region_numbers.clone()),
+            )
+            .await
+            .with_context(|_| error::CreateRegionsSnafu {
+                table_name: &table_name,
+                region_numbers,
+            })?;
+        let new_region = RegionIdent {
+            region_number: new_region_number,
+            ..region.clone()
+        };
+        self.set_read_only(&table, &new_region).await?;
+
+        let rows = table
+            .move_region_rows(MoveRegionRowsRequest {
+                from: region.region_number,
+                to: new_region_number,
+                partition_columns,
+                lower_bound: Some(split_key),
+            })
+            .await
+            .with_context(|_| error::MoveRegionRowsSnafu {
+                table_name: &table_name,
+                from: region.region_number,
+                to: new_region_number,
+            })?;
+
+        info!(
+            "Region {} of {table_name} is split into region {new_region_number}, {rows} rows moved",
+            region.region_number
+        );
+        Ok(())
+    }
+
+    /// Moves all rows of the region `from_region_number` into the region and drops the region
+    /// `from_region_number`. It's a no-op if the region `from_region_number` has been dropped.
+    async fn merge_regions(
+        &self,
+        region: &RegionIdent,
+        from_region_number: RegionNumber,
+    ) -> Result<()> {
+        let table_ident = &region.table_ident;
+        let table_name = full_table_name(table_ident);
+        let table = self.table(table_ident).await?;
+
+        let from_region = RegionIdent {
+            region_number: from_region_number,
+            ..region.clone()
+        };
+        let region_exist = table
+            .contains_region(from_region_number)
+            .with_context(|_| error::CheckRegionSnafu {
+                table_name: &table_name,
+                region_number: from_region_number,
+            })?;
+        if !region_exist {
+            warn!("Region {from_region_number} of {table_name} is already merged");
+            return Ok(());
+        }
+
+        self.set_read_only(&table, region).await?;
+        self.set_read_only(&table, &from_region).await?;
+
+        let rows = table
+            .move_region_rows(MoveRegionRowsRequest {
+                from: from_region_number,
+                to: region.region_number,
+                partition_columns: vec![],
+                lower_bound: None,
+            })
+            .await
+            .with_context(|_| error::MoveRegionRowsSnafu {
+                table_name: &table_name,
+                from: from_region_number,
+                to: region.region_number,
+            })?;
+
+        let engine_name = &table_ident.engine;
+        let engine = self
+            .table_engine_manager
+            .engine(engine_name)
+            .context(error::TableEngineNotFoundSnafu { engine_name })?;
+        let region_numbers = vec![from_region_number];
+        engine
+            .drop_regions(
+                &EngineContext::default(),
+                Self::regions_request(table_ident, region_numbers.clone()),
+            )
+            .await
+            .with_context(|_| error::DropRegionsSnafu {
+                table_name: &table_name,
+                region_numbers,
+            })?;
+        self.region_alive_keepers
+            .deregister_region(&from_region)
+            .await;
+
+        info!(
+            "Region {from_region_number} of {table_name} is merged into region {}, {rows} rows moved",
+            region.region_number
+        );
+        Ok(())
+    }
+}
+
+fn full_table_name(table_ident: &TableIdent) -> String {
+    format_full_table_name(
+        &table_ident.catalog,
+        &table_ident.schema,
+        &table_ident.table,
+    )
+}
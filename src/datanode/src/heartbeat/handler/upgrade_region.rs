@@ -0,0 +1,130 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use catalog::remote::region_alive_keeper::RegionAliveKeepers;
+use catalog::CatalogManagerRef;
+use common_meta::error::Result as MetaResult;
+use common_meta::heartbeat::handler::{
+    HandleControl, HeartbeatResponseHandler, HeartbeatResponseHandlerContext,
+};
+use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_meta::RegionIdent;
+use common_telemetry::{error, info};
+use table::engine::manager::TableEngineManagerRef;
+
+use crate::error::Result;
+use crate::heartbeat::handler::close_region::CloseRegionHandler;
+use crate::heartbeat::handler::open_region::OpenRegionHandler;
+
+/// Makes a region catch up with the latest data in the shared storage and accept writes.
+///
+/// The region is reopened, so it reloads its manifest, including the files flushed by the
+/// downgraded leader on another Datanode, and becomes writable again.
+#[derive(Clone)]
+pub struct UpgradeRegionHandler {
+    open_region_handler: OpenRegionHandler,
+    close_region_handler: CloseRegionHandler,
+}
+
+#[async_trait]
+impl HeartbeatResponseHandler for UpgradeRegionHandler {
+    fn is_acceptable(&self, ctx: &HeartbeatResponseHandlerContext) -> bool {
+        matches!(
+            ctx.incoming_message.as_ref(),
+            Some((_, Instruction::UpgradeRegion { .. }))
+        )
+    }
+
+    async fn handle(&self, ctx: &mut HeartbeatResponseHandlerContext) -> MetaResult<HandleControl> {
+        let Some((meta, Instruction::UpgradeRegion(region_ident))) = ctx.incoming_message.take()
+        else {
+            unreachable!("UpgradeRegionHandler: should be guarded by 'is_acceptable'");
+        };
+
+        let mailbox = ctx.mailbox.clone();
+        let self_ref = Arc::new(self.clone());
+        let _handle = common_runtime::spawn_bg(async move {
+            let result = self_ref.upgrade_region_inner(region_ident).await;
+
+            if let Err(e) = mailbox
+                .send((meta, UpgradeRegionHandler::map_result(result)))
+                .await
+            {
+                error!(e; "Failed to send reply to mailbox");
+            }
+        });
+
+        Ok(HandleControl::Done)
+    }
+}
+
+impl UpgradeRegionHandler {
+    pub fn new(
+        catalog_manager: CatalogManagerRef,
+        table_engine_manager: TableEngineManagerRef,
+        region_alive_keepers: Arc<RegionAliveKeepers>,
+    ) -> Self {
+        Self {
+            open_region_handler: OpenRegionHandler::new(
+                catalog_manager.clone(),
+                table_engine_manager.clone(),
+                region_alive_keepers.clone(),
+            ),
+            close_region_handler: CloseRegionHandler::new(
+                catalog_manager,
+                table_engine_manager,
+                region_alive_keepers,
+            ),
+        }
+    }
+
+    fn map_result(result: Result<bool>) -> InstructionReply {
+        result.map_or_else(
+            |error| {
+                InstructionReply::UpgradeRegion(SimpleReply {
+                    result: false,
+                    error: Some(error.to_string()),
+                })
+            },
+            |result| {
+                InstructionReply::UpgradeRegion(SimpleReply {
+                    result,
+                    error: None,
+                })
+            },
+        )
+    }
+
+    /// Returns true if the region has been reopened.
+    async fn upgrade_region_inner(&self, region_ident: RegionIdent) -> Result<bool> {
+        // The region is either a candidate that doesn't receive writes, or a leader that has
+        // been downgraded and flushed, so closing it won't flush anything.
+        if !self
+            .close_region_handler
+            .close_region_inner(region_ident.clone())
+            .await?
+        {
+            return Ok(false);
+        }
+
+        let opened = self.open_region_handler.open_region(&region_ident).await?;
+        if opened {
+            info!("Region {region_ident} is upgraded");
+        }
+        Ok(opened)
+    }
+}
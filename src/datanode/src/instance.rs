@@ -35,6 +35,7 @@ use common_procedure::store::state_store::ObjectStateStore;
 use common_procedure::ProcedureManagerRef;
 use common_telemetry::logging::{debug, info};
 use file_table_engine::engine::immutable::ImmutableFileTableEngine;
+use file_table_engine::engine::view::ViewTableEngine;
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use log_store::LogConfig;
 use meta_client::client::{MetaClient, MetaClientBuilder};
@@ -65,7 +66,10 @@ use crate::error::{
 };
 use crate::greptimedb_telemetry::get_greptimedb_telemetry_task;
 use crate::heartbeat::handler::close_region::CloseRegionHandler;
+use crate::heartbeat::handler::downgrade_region::DowngradeRegionHandler;
 use crate::heartbeat::handler::open_region::OpenRegionHandler;
+use crate::heartbeat::handler::region_balance::RegionBalanceHandler;
+use crate::heartbeat::handler::upgrade_region::UpgradeRegionHandler;
 use crate::heartbeat::HeartbeatTask;
 use crate::row_inserter::RowInserter;
 use crate::sql::{SqlHandler, SqlRequest};
@@ -139,6 +143,17 @@ impl Instance {
                         region_alive_keepers.clone(),
                     )),
                     Arc::new(CloseRegionHandler::new(
+                        catalog_manager.clone(),
+                        engine_manager.clone(),
+                        region_alive_keepers.clone(),
+                    )),
+                    Arc::new(DowngradeRegionHandler::new(catalog_manager.clone())),
+                    Arc::new(UpgradeRegionHandler::new(
+                        catalog_manager.clone(),
+                        engine_manager.clone(),
+                        region_alive_keepers.clone(),
+                    )),
+                    Arc::new(RegionBalanceHandler::new(
                         catalog_manager.clone(),
                         engine_manager,
                         region_alive_keepers.clone(),
@@ -189,6 +204,7 @@ impl Instance {
             file_table_engine::config::EngineConfig::default(),
             object_store.clone(),
         ));
+        let view_engine = Arc::new(ViewTableEngine::new(object_store.clone()));
 
         let engine_procedures = HashMap::from([
             (
@@ -199,11 +215,16 @@ impl Instance {
                 immutable_file_engine.name().to_string(),
                 immutable_file_engine.clone() as TableEngineProcedureRef,
             ),
+            (
+                view_engine.name().to_string(),
+                view_engine.clone() as TableEngineProcedureRef,
+            ),
         ]);
         let engine_manager = Arc::new(
             MemoryTableEngineManager::with(vec![
                 mito_engine.clone(),
                 immutable_file_engine.clone(),
+                view_engine.clone(),
             ])
             .with_engine_procedures(engine_procedures),
         );
@@ -294,6 +315,8 @@ impl Instance {
         mito_engine.register_procedure_loaders(&*procedure_manager);
         // Register procedures of the file table engine.
         immutable_file_engine.register_procedure_loaders(&*procedure_manager);
+        // Register procedures of the view engine.
+        view_engine.register_procedure_loaders(&*procedure_manager);
         // Register procedures in table-procedure crate.
         table_procedure::register_procedure_loaders(
             catalog_manager.clone(),
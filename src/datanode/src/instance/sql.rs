@@ -27,6 +27,7 @@ use snafu::prelude::*;
 use sql::ast::ObjectName;
 use sql::statements::statement::Statement;
 use table::engine::TableReference;
+use table::metadata::TableType;
 use table::requests::{CreateDatabaseRequest, DropTableRequest, TruncateTableRequest};
 
 use crate::error::{
@@ -105,6 +106,24 @@ impl Instance {
                     .execute(SqlRequest::CreateTable(request), query_ctx)
                     .await
             }
+            Statement::CreateView(create_view) => {
+                let table_id = self
+                    .table_id_provider
+                    .as_ref()
+                    .context(TableIdProviderNotFoundSnafu)?
+                    .next_table_id()
+                    .await
+                    .context(BumpTableIdSnafu)?;
+                let name = create_view.name.clone();
+                let (catalog, schema, table) = table_idents_to_full_name(&name, query_ctx.clone())?;
+                let table_ref = TableReference::full(&catalog, &schema, &table);
+                let request =
+                    SqlHandler::create_view_to_request(table_id, create_view, &table_ref)?;
+                info!("Creating view: {table_ref}, table id = {table_id}",);
+                self.sql_handler
+                    .execute(SqlRequest::CreateTable(request), query_ctx)
+                    .await
+            }
             Statement::Alter(alter_table) => {
                 let name = alter_table.table_name().clone();
                 let (catalog, schema, table) = table_idents_to_full_name(&name, query_ctx.clone())?;
@@ -115,6 +134,7 @@ impl Instance {
                     alter_table,
                     table_ref,
                     table.table_info().ident.table_id,
+                    &table.schema(),
                 )?;
                 self.sql_handler
                     .execute(SqlRequest::Alter(req), query_ctx)
@@ -135,6 +155,40 @@ impl Instance {
                     .execute(SqlRequest::DropTable(req), query_ctx)
                     .await
             }
+            Statement::DropView(drop_view) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(drop_view.view_name(), query_ctx.clone())?;
+                let table_ref = TableReference::full(&catalog_name, &schema_name, &table_name);
+                let Some(table) = self
+                    .catalog_manager
+                    .table(&catalog_name, &schema_name, &table_name)
+                    .await
+                    .context(error::CatalogSnafu)?
+                else {
+                    ensure!(
+                        drop_view.drop_if_exists(),
+                        error::TableNotFoundSnafu {
+                            table_name: table_ref.to_string(),
+                        }
+                    );
+                    return Ok(Output::AffectedRows(0));
+                };
+                ensure!(
+                    table.table_type() == TableType::View,
+                    error::InvalidSqlSnafu {
+                        msg: format!("{table_ref} is not a view"),
+                    }
+                );
+                let req = DropTableRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                    table_id: table.table_info().ident.table_id,
+                };
+                self.sql_handler
+                    .execute(SqlRequest::DropTable(req), query_ctx)
+                    .await
+            }
             Statement::ShowCreateTable(show) => {
                 let (catalog, schema, table) =
                     table_idents_to_full_name(&show.table_name, query_ctx.clone())?;
@@ -143,6 +197,20 @@ impl Instance {
 
                 query::sql::show_create_table(table, None).context(ExecuteStatementSnafu)
             }
+            Statement::ShowCreateView(show) => {
+                let (catalog, schema, table) =
+                    table_idents_to_full_name(&show.view_name, query_ctx.clone())?;
+                let table_ref = TableReference::full(&catalog, &schema, &table);
+                let table = self.sql_handler.get_table(&table_ref).await?;
+                ensure!(
+                    table.table_type() == TableType::View,
+                    error::InvalidSqlSnafu {
+                        msg: format!("{table_ref} is not a view"),
+                    }
+                );
+
+                query::sql::show_create_view(table).context(ExecuteStatementSnafu)
+            }
             Statement::TruncateTable(truncate_table) => {
                 let (catalog_name, schema_name, table_name) =
                     table_idents_to_full_name(truncate_table.table_name(), query_ctx.clone())?;
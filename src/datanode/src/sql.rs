@@ -34,6 +34,7 @@ mod alter;
 mod compact_table;
 mod create;
 mod create_external;
+mod create_view;
 mod drop_table;
 mod flush_table;
 pub(crate) mod insert;
@@ -15,12 +15,15 @@
 use common_procedure::{watcher, ProcedureWithId};
 use common_query::Output;
 use common_telemetry::logging::info;
+use datatypes::schema::Schema;
 use snafu::prelude::*;
+use sql::ast::ColumnOption;
 use sql::statements::alter::{AlterTable, AlterTableOperation};
-use sql::statements::column_def_to_schema;
+use sql::statements::{column_def_to_schema, sql_default_to_constraint};
+use sql::util::to_lowercase_options_map;
 use table::engine::TableReference;
 use table::metadata::TableId;
-use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest};
+use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest, ModifyColumnRequest};
 use table_procedure::AlterTableProcedure;
 
 use crate::error::{self, Result, UnexpectedSnafu};
@@ -91,6 +94,7 @@ impl SqlHandler {
         alter_table: AlterTable,
         table_ref: TableReference,
         table_id: TableId,
+        table_schema: &Schema,
     ) -> Result<AlterTableRequest> {
         let alter_kind = match &alter_table.alter_operation() {
             AlterTableOperation::AddConstraint(table_constraint) => {
@@ -114,9 +118,49 @@ impl SqlHandler {
             AlterTableOperation::DropColumn { name } => AlterKind::DropColumns {
                 names: vec![name.value.clone()],
             },
+            AlterTableOperation::ModifyColumn { column_def } => {
+                let column_schema =
+                    column_def_to_schema(column_def, false).context(error::ParseSqlSnafu)?;
+                // Only changes the nullability if it is given explicitly.
+                let is_nullable = column_def.options.iter().find_map(|o| match o.option {
+                    ColumnOption::Null => Some(true),
+                    ColumnOption::NotNull => Some(false),
+                    _ => None,
+                });
+                AlterKind::ModifyColumns {
+                    columns: vec![ModifyColumnRequest {
+                        default_constraint: column_schema.default_constraint().cloned(),
+                        column_name: column_schema.name,
+                        data_type: Some(column_schema.data_type),
+                        is_nullable,
+                    }],
+                }
+            }
+            AlterTableOperation::SetColumnDefault { name, default } => {
+                let column_schema = table_schema
+                    .column_schema_by_name(&name.value)
+                    .with_context(|| error::ColumnNotFoundSnafu {
+                        column_name: &name.value,
+                        table_name: table_ref.table,
+                    })?;
+                let default_constraint =
+                    sql_default_to_constraint(&name.value, &column_schema.data_type, default)
+                        .context(error::ParseSqlSnafu)?;
+                AlterKind::ModifyColumns {
+                    columns: vec![ModifyColumnRequest {
+                        column_name: name.value.clone(),
+                        data_type: None,
+                        is_nullable: None,
+                        default_constraint: Some(default_constraint),
+                    }],
+                }
+            }
             AlterTableOperation::RenameTable { new_table_name } => AlterKind::RenameTable {
                 new_table_name: new_table_name.clone(),
             },
+            AlterTableOperation::SetTableOptions { options } => AlterKind::SetTableOptions {
+                options: to_lowercase_options_map(options),
+            },
         };
         Ok(AlterTableRequest {
             catalog_name: table_ref.catalog.to_string(),
@@ -134,6 +178,8 @@ mod tests {
     use std::assert_matches::assert_matches;
 
     use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema};
+    use datatypes::value::Value;
     use query::parser::{QueryLanguageParser, QueryStatement};
     use query::query_engine::SqlStatementExecutor;
     use session::context::QueryContext;
@@ -155,6 +201,13 @@ mod tests {
         }
     }
 
+    fn test_schema() -> Schema {
+        Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+        ])
+    }
+
     #[tokio::test]
     async fn test_alter_to_request_with_adding_column() {
         let alter_table = parse_sql("ALTER TABLE my_metric_1 ADD tagk_i STRING Null;");
@@ -162,6 +215,7 @@ mod tests {
             alter_table,
             TableReference::full("greptime", "public", "my_metric_1"),
             1,
+            &test_schema(),
         )
         .unwrap();
         assert_eq!(req.catalog_name, "greptime");
@@ -189,6 +243,7 @@ mod tests {
             alter_table,
             TableReference::full("greptime", "public", "test_table"),
             1,
+            &test_schema(),
         )
         .unwrap();
         assert_eq!(req.catalog_name, "greptime");
@@ -206,6 +261,110 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_alter_to_request_with_setting_table_options() {
+        let alter_table =
+            parse_sql("ALTER TABLE test_table SET TTL = '7d', write_buffer_size = '1MB';");
+        let req = SqlHandler::alter_to_request(
+            alter_table,
+            TableReference::full("greptime", "public", "test_table"),
+            1,
+            &test_schema(),
+        )
+        .unwrap();
+        assert_eq!(req.table_name, "test_table");
+
+        let alter_kind = req.alter_kind;
+        assert_matches!(alter_kind, AlterKind::SetTableOptions { .. });
+
+        match alter_kind {
+            AlterKind::SetTableOptions { options } => {
+                assert_eq!(2, options.len());
+                assert_eq!("7d", options["ttl"]);
+                assert_eq!("1MB", options["write_buffer_size"]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alter_to_request_with_modifying_column() {
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 MODIFY COLUMN cpu BIGINT NULL;");
+        let req = SqlHandler::alter_to_request(
+            alter_table,
+            TableReference::full("greptime", "public", "my_metric_1"),
+            1,
+            &test_schema(),
+        )
+        .unwrap();
+        assert_eq!(req.table_name, "my_metric_1");
+
+        let alter_kind = req.alter_kind;
+        assert_matches!(alter_kind, AlterKind::ModifyColumns { .. });
+
+        match alter_kind {
+            AlterKind::ModifyColumns { mut columns } => {
+                let request = columns.pop().unwrap();
+                assert_eq!("cpu", request.column_name);
+                assert_eq!(Some(ConcreteDataType::int64_datatype()), request.data_type);
+                assert_eq!(Some(true), request.is_nullable);
+                assert!(request.default_constraint.is_none());
+            }
+            _ => unreachable!(),
+        }
+
+        // Keeps the nullability if it isn't given.
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 MODIFY cpu BIGINT;");
+        let req = SqlHandler::alter_to_request(
+            alter_table,
+            TableReference::full("greptime", "public", "my_metric_1"),
+            1,
+            &test_schema(),
+        )
+        .unwrap();
+        match req.alter_kind {
+            AlterKind::ModifyColumns { columns } => {
+                assert_eq!(None, columns[0].is_nullable);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alter_to_request_with_setting_column_default() {
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 MODIFY cpu SET DEFAULT 1;");
+        let req = SqlHandler::alter_to_request(
+            alter_table,
+            TableReference::full("greptime", "public", "my_metric_1"),
+            1,
+            &test_schema(),
+        )
+        .unwrap();
+
+        match req.alter_kind {
+            AlterKind::ModifyColumns { mut columns } => {
+                let request = columns.pop().unwrap();
+                assert_eq!("cpu", request.column_name);
+                assert!(request.data_type.is_none());
+                assert!(request.is_nullable.is_none());
+                assert_eq!(
+                    Some(ColumnDefaultConstraint::Value(Value::Float64(1.0.into()))),
+                    request.default_constraint
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 MODIFY unknown SET DEFAULT 1;");
+        let result = SqlHandler::alter_to_request(
+            alter_table,
+            TableReference::full("greptime", "public", "my_metric_1"),
+            1,
+            &test_schema(),
+        );
+        assert_matches!(result, Err(error::Error::ColumnNotFound { .. }));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_alter_table_by_procedure() {
         let instance = MockInstance::new("alter_table_by_procedure").await;
@@ -0,0 +1,58 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_catalog::consts::VIEW_ENGINE;
+use datatypes::schema::RawSchema;
+use snafu::ResultExt;
+use sql::statements::column_def_to_schema;
+use sql::statements::create::CreateView;
+use table::engine::TableReference;
+use table::metadata::TableId;
+use table::requests::{CreateTableRequest, TableOptions, VIEW_DEFINITION_KEY};
+
+use crate::error::{self, Result};
+use crate::sql::SqlHandler;
+
+impl SqlHandler {
+    /// Converts [CreateView] to [SqlRequest::CreateTable].
+    pub(crate) fn create_view_to_request(
+        table_id: TableId,
+        stmt: CreateView,
+        table_ref: &TableReference<'_>,
+    ) -> Result<CreateTableRequest> {
+        let column_schemas = stmt
+            .columns
+            .iter()
+            .map(|column| column_def_to_schema(column, false).context(error::ParseSqlSnafu))
+            .collect::<Result<Vec<_>>>()?;
+        let options = HashMap::from([(VIEW_DEFINITION_KEY.to_string(), stmt.query.to_string())]);
+
+        Ok(CreateTableRequest {
+            id: table_id,
+            catalog_name: table_ref.catalog.to_string(),
+            schema_name: table_ref.schema.to_string(),
+            table_name: table_ref.table.to_string(),
+            desc: None,
+            schema: RawSchema::new(column_schemas),
+            region_numbers: vec![],
+            primary_key_indices: vec![],
+            create_if_not_exists: stmt.if_not_exists,
+            table_options: TableOptions::try_from(&options)
+                .context(error::UnrecognizedTableOptionSnafu)?,
+            engine: VIEW_ENGINE.to_string(),
+        })
+    }
+}
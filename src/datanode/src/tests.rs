@@ -27,9 +27,12 @@ use common_meta::heartbeat::handler::{
 };
 use common_meta::heartbeat::mailbox::{HeartbeatMailbox, MessageMeta};
 use common_meta::ident::TableIdent;
-use common_meta::instruction::{Instruction, InstructionReply, RegionIdent, SimpleReply};
+use common_meta::instruction::{
+    Instruction, InstructionReply, RegionIdent, RegionSplitKeyReply, SimpleReply,
+};
 use common_query::Output;
 use datatypes::prelude::ConcreteDataType;
+use datatypes::value::Value;
 use servers::query_handler::grpc::GrpcQueryHandler;
 use session::context::QueryContext;
 use table::engine::manager::TableEngineManagerRef;
@@ -39,7 +42,10 @@ use tokio::sync::mpsc::{self, Receiver};
 use tokio::time::Instant;
 
 use crate::heartbeat::handler::close_region::CloseRegionHandler;
+use crate::heartbeat::handler::downgrade_region::DowngradeRegionHandler;
 use crate::heartbeat::handler::open_region::OpenRegionHandler;
+use crate::heartbeat::handler::region_balance::RegionBalanceHandler;
+use crate::heartbeat::handler::upgrade_region::UpgradeRegionHandler;
 use crate::instance::Instance;
 
 pub(crate) mod test_util;
@@ -239,6 +245,174 @@ async fn test_open_region_handler() {
     assert_test_table_found(instance.inner()).await;
 }
 
+#[tokio::test]
+async fn test_downgrade_and_upgrade_region_handler() {
+    let HandlerTestGuard {
+        instance,
+        mailbox,
+        mut rx,
+        engine_manager_ref,
+        catalog_manager_ref,
+        ..
+    } = prepare_handler_test("test_downgrade_and_upgrade_region_handler").await;
+
+    let region_alive_keepers = Arc::new(RegionAliveKeepers::new(engine_manager_ref.clone(), 5000));
+    region_alive_keepers.start().await;
+
+    let executor = Arc::new(HandlerGroupExecutor::new(vec![
+        Arc::new(DowngradeRegionHandler::new(catalog_manager_ref.clone())),
+        Arc::new(UpgradeRegionHandler::new(
+            catalog_manager_ref.clone(),
+            engine_manager_ref.clone(),
+            region_alive_keepers.clone(),
+        )),
+    ]));
+
+    let Instruction::OpenRegion(region_ident) = open_region_instruction() else {
+        unreachable!()
+    };
+    let table = prepare_table(instance.inner()).await;
+    region_alive_keepers
+        .register_table(region_ident.table_ident.clone(), table)
+        .await
+        .unwrap();
+
+    // Downgrades the region, the region rejects writes.
+    handle_instruction(
+        executor.clone(),
+        mailbox.clone(),
+        Instruction::DowngradeRegion(region_ident.clone()),
+    )
+    .await;
+    let (_, reply) = rx.recv().await.unwrap();
+    assert_matches!(
+        reply,
+        InstructionReply::DowngradeRegion(SimpleReply { result: true, .. })
+    );
+    assert_test_table_read_only(instance.inner()).await;
+
+    // Upgrades the region, the region accepts writes again.
+    handle_instruction(
+        executor.clone(),
+        mailbox.clone(),
+        Instruction::UpgradeRegion(region_ident.clone()),
+    )
+    .await;
+    let (_, reply) = rx.recv().await.unwrap();
+    assert_matches!(
+        reply,
+        InstructionReply::UpgradeRegion(SimpleReply { result: true, .. })
+    );
+    assert_test_table_found(instance.inner()).await;
+
+    // Downgrades a region of non-exist table
+    handle_instruction(
+        executor.clone(),
+        mailbox.clone(),
+        Instruction::DowngradeRegion(RegionIdent {
+            table_ident: TableIdent {
+                table: "non-exist".to_string(),
+                table_id: 2024,
+                ..region_ident.table_ident
+            },
+            ..region_ident
+        }),
+    )
+    .await;
+    let (_, reply) = rx.recv().await.unwrap();
+    assert_matches!(
+        reply,
+        InstructionReply::DowngradeRegion(SimpleReply { result: false, .. })
+    );
+}
+
+#[tokio::test]
+async fn test_region_balance_handler() {
+    let HandlerTestGuard {
+        instance,
+        mailbox,
+        mut rx,
+        engine_manager_ref,
+        catalog_manager_ref,
+        ..
+    } = prepare_handler_test("test_region_balance_handler").await;
+
+    let executor = Arc::new(HandlerGroupExecutor::new(vec![Arc::new(
+        RegionBalanceHandler::new(
+            catalog_manager_ref.clone(),
+            engine_manager_ref.clone(),
+            Arc::new(RegionAliveKeepers::new(engine_manager_ref.clone(), 5000)),
+        ),
+    )]));
+
+    let Instruction::OpenRegion(region_ident) = open_region_instruction() else {
+        unreachable!()
+    };
+    let table = prepare_table(instance.inner()).await;
+    // Inserts rows of host1 and host2.
+    assert_test_table_found(instance.inner()).await;
+
+    let partition_columns = vec!["host".to_string()];
+    handle_instruction(
+        executor.clone(),
+        mailbox.clone(),
+        Instruction::GetRegionSplitKey {
+            region: region_ident.clone(),
+            partition_columns: partition_columns.clone(),
+        },
+    )
+    .await;
+    let (_, reply) = rx.recv().await.unwrap();
+    let InstructionReply::GetRegionSplitKey(RegionSplitKeyReply {
+        result: true,
+        split_key: Some(split_key),
+        ..
+    }) = reply
+    else {
+        panic!("Unexpected reply: {reply}");
+    };
+    assert_eq!(vec![Value::from("host2")], split_key);
+
+    // Splits the region, both regions reject writes until they are upgraded.
+    handle_instruction(
+        executor.clone(),
+        mailbox.clone(),
+        Instruction::SplitRegion {
+            region: region_ident.clone(),
+            new_region_number: 1,
+            partition_columns,
+            split_key,
+        },
+    )
+    .await;
+    let (_, reply) = rx.recv().await.unwrap();
+    assert_matches!(
+        reply,
+        InstructionReply::SplitRegion(SimpleReply { result: true, .. })
+    );
+    assert!(table.contains_region(1).unwrap());
+    assert_test_table_read_only(instance.inner()).await;
+
+    // Merges the region back, merging it again is a no-op.
+    for _ in 0..2 {
+        handle_instruction(
+            executor.clone(),
+            mailbox.clone(),
+            Instruction::MergeRegions {
+                region: region_ident.clone(),
+                from_region_number: 1,
+            },
+        )
+        .await;
+        let (_, reply) = rx.recv().await.unwrap();
+        assert_matches!(
+            reply,
+            InstructionReply::MergeRegions(SimpleReply { result: true, .. })
+        );
+    }
+    assert!(!table.contains_region(1).unwrap());
+}
+
 async fn prepare_handler_test(name: &str) -> HandlerTestGuard {
     let mock_instance = MockInstance::new(name).await;
     let instance = mock_instance.inner();
@@ -343,3 +517,23 @@ async fn assert_test_table_found(instance: &Instance) {
 
     assert!(matches!(output, Output::AffectedRows(2)));
 }
+
+async fn assert_test_table_read_only(instance: &Instance) {
+    let query = GrpcRequest::Query(QueryRequest {
+        query: Some(Query::Sql(
+            "INSERT INTO demo(host, cpu, memory, ts) VALUES \
+                        ('host1', 66.6, 1024, 1672201025000)"
+                .to_string(),
+        )),
+    });
+    let err = instance
+        .do_query(query, QueryContext::arc())
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("Try to write the read-only region"),
+        "{err}"
+    );
+}
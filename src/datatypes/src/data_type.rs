@@ -165,6 +165,36 @@ impl ConcreteDataType {
         ]
     }
 
+    /// Returns true if values of this type could be converted to `to` type without loss,
+    /// e.g. `Int32` to `Int64` or `Float32` to `Float64`.
+    pub fn can_widen_to(&self, to: &ConcreteDataType) -> bool {
+        use ConcreteDataType::*;
+
+        match self {
+            Int8(_) => matches!(to, Int16(_) | Int32(_) | Int64(_) | Float32(_) | Float64(_)),
+            Int16(_) => matches!(to, Int32(_) | Int64(_) | Float32(_) | Float64(_)),
+            Int32(_) => matches!(to, Int64(_) | Float64(_)),
+            UInt8(_) => matches!(
+                to,
+                UInt16(_)
+                    | UInt32(_)
+                    | UInt64(_)
+                    | Int16(_)
+                    | Int32(_)
+                    | Int64(_)
+                    | Float32(_)
+                    | Float64(_)
+            ),
+            UInt16(_) => matches!(
+                to,
+                UInt32(_) | UInt64(_) | Int32(_) | Int64(_) | Float32(_) | Float64(_)
+            ),
+            UInt32(_) => matches!(to, UInt64(_) | Int64(_) | Float64(_)),
+            Float32(_) => matches!(to, Float64(_)),
+            _ => false,
+        }
+    }
+
     /// Convert arrow data type to [ConcreteDataType].
     ///
     /// # Panics
@@ -545,6 +575,37 @@ mod tests {
         assert!(!ConcreteDataType::time_nanosecond_datatype().is_timestamp_compatible());
     }
 
+    #[test]
+    fn test_can_widen_to() {
+        assert!(
+            ConcreteDataType::int32_datatype().can_widen_to(&ConcreteDataType::int64_datatype())
+        );
+        assert!(
+            ConcreteDataType::uint32_datatype().can_widen_to(&ConcreteDataType::int64_datatype())
+        );
+        assert!(
+            ConcreteDataType::int16_datatype().can_widen_to(&ConcreteDataType::float32_datatype())
+        );
+        assert!(ConcreteDataType::float32_datatype()
+            .can_widen_to(&ConcreteDataType::float64_datatype()));
+
+        assert!(
+            !ConcreteDataType::int64_datatype().can_widen_to(&ConcreteDataType::int32_datatype())
+        );
+        assert!(
+            !ConcreteDataType::int32_datatype().can_widen_to(&ConcreteDataType::uint64_datatype())
+        );
+        assert!(
+            !ConcreteDataType::int64_datatype().can_widen_to(&ConcreteDataType::float64_datatype())
+        );
+        assert!(
+            !ConcreteDataType::int32_datatype().can_widen_to(&ConcreteDataType::int32_datatype())
+        );
+        assert!(
+            !ConcreteDataType::int32_datatype().can_widen_to(&ConcreteDataType::string_datatype())
+        );
+    }
+
     #[test]
     fn test_is_null() {
         assert!(ConcreteDataType::null_datatype().is_null());
@@ -16,6 +16,7 @@ pub mod immutable;
 mod procedure;
 #[cfg(test)]
 mod tests;
+pub mod view;
 
 use table::metadata::TableVersion;
 
@@ -12,16 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Procedures for immutable file table engine.
+//! Procedures for immutable file table engine and view engine.
 
 mod create;
+mod create_view;
 mod drop;
+mod drop_view;
 
 use common_procedure::ProcedureManager;
 
 use crate::engine::immutable::ImmutableFileTableEngine;
 pub(crate) use crate::engine::procedure::create::CreateImmutableFileTable;
+pub(crate) use crate::engine::procedure::create_view::CreateViewTable;
 pub(crate) use crate::engine::procedure::drop::DropImmutableFileTable;
+pub(crate) use crate::engine::procedure::drop_view::DropViewTable;
+use crate::engine::view::ViewTableEngine;
 
 /// Register all procedure loaders to the procedure manager.
 ///
@@ -35,3 +40,15 @@ pub(crate) fn register_procedure_loaders(
     CreateImmutableFileTable::register_loader(engine.clone(), procedure_manager);
     DropImmutableFileTable::register_loader(engine, procedure_manager);
 }
+
+/// Register all procedure loaders of the view engine to the procedure manager.
+///
+/// # Panics
+/// Panics on error.
+pub(crate) fn register_view_procedure_loaders(
+    engine: ViewTableEngine,
+    procedure_manager: &dyn ProcedureManager,
+) {
+    CreateViewTable::register_loader(engine.clone(), procedure_manager);
+    DropViewTable::register_loader(engine, procedure_manager);
+}
@@ -0,0 +1,350 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use common_catalog::consts::VIEW_ENGINE;
+use common_error::ext::BoxedError;
+use common_procedure::{BoxedProcedure, ProcedureManager};
+use common_telemetry::logging;
+use datatypes::schema::Schema;
+use object_store::ObjectStore;
+use snafu::ResultExt;
+use table::engine::{table_dir, EngineContext, TableEngine, TableEngineProcedure, TableReference};
+use table::metadata::{TableId, TableInfoBuilder, TableMetaBuilder, TableType};
+use table::requests::{
+    AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest,
+    TruncateTableRequest, VIEW_DEFINITION_KEY,
+};
+use table::{error as table_error, Result as TableResult, TableRef};
+use tokio::sync::Mutex;
+
+use crate::engine::procedure::{self, CreateViewTable, DropViewTable};
+use crate::engine::INIT_TABLE_VERSION;
+use crate::error::{
+    BuildTableInfoSnafu, BuildTableMetaSnafu, DropTableSnafu, InvalidRawSchemaSnafu, Result,
+    TableExistsSnafu,
+};
+use crate::manifest::immutable::delete_table_manifest;
+use crate::manifest::table_manifest_dir;
+use crate::table::view::{ViewTable, ViewTableRef};
+
+/// [TableEngine] for views, which only persists their definitions.
+#[derive(Clone)]
+pub struct ViewTableEngine {
+    inner: Arc<EngineInner>,
+}
+
+#[async_trait]
+impl TableEngine for ViewTableEngine {
+    fn name(&self) -> &str {
+        VIEW_ENGINE
+    }
+
+    async fn create_table(
+        &self,
+        _ctx: &EngineContext,
+        request: CreateTableRequest,
+    ) -> TableResult<TableRef> {
+        self.inner
+            .create_table(request)
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
+    async fn open_table(
+        &self,
+        _ctx: &EngineContext,
+        request: OpenTableRequest,
+    ) -> TableResult<Option<TableRef>> {
+        self.inner
+            .open_table(request)
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
+    async fn alter_table(
+        &self,
+        _ctx: &EngineContext,
+        _req: AlterTableRequest,
+    ) -> TableResult<TableRef> {
+        table_error::UnsupportedSnafu {
+            operation: "ALTER VIEW",
+        }
+        .fail()
+    }
+
+    fn get_table(&self, _ctx: &EngineContext, table_id: TableId) -> TableResult<Option<TableRef>> {
+        Ok(self.inner.get_table(table_id))
+    }
+
+    fn table_exists(&self, _ctx: &EngineContext, table_id: TableId) -> bool {
+        self.inner.get_table(table_id).is_some()
+    }
+
+    async fn drop_table(
+        &self,
+        _ctx: &EngineContext,
+        request: DropTableRequest,
+    ) -> TableResult<bool> {
+        self.inner
+            .drop_table(request)
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
+    async fn close(&self) -> TableResult<()> {
+        self.inner.close().await;
+        Ok(())
+    }
+
+    async fn truncate_table(
+        &self,
+        _ctx: &EngineContext,
+        _request: TruncateTableRequest,
+    ) -> TableResult<bool> {
+        table_error::UnsupportedSnafu {
+            operation: "TRUNCATE VIEW",
+        }
+        .fail()
+    }
+}
+
+#[async_trait]
+impl TableEngineProcedure for ViewTableEngine {
+    fn create_table_procedure(
+        &self,
+        _ctx: &EngineContext,
+        request: CreateTableRequest,
+    ) -> TableResult<BoxedProcedure> {
+        let procedure = Box::new(CreateViewTable::new(request, self.clone()));
+        Ok(procedure)
+    }
+
+    fn alter_table_procedure(
+        &self,
+        _ctx: &EngineContext,
+        _request: AlterTableRequest,
+    ) -> TableResult<BoxedProcedure> {
+        table_error::UnsupportedSnafu {
+            operation: "ALTER VIEW",
+        }
+        .fail()
+    }
+
+    fn drop_table_procedure(
+        &self,
+        _ctx: &EngineContext,
+        request: DropTableRequest,
+    ) -> TableResult<BoxedProcedure> {
+        let procedure = Box::new(DropViewTable::new(request, self.clone()));
+        Ok(procedure)
+    }
+
+    fn truncate_table_procedure(
+        &self,
+        _ctx: &EngineContext,
+        _request: TruncateTableRequest,
+    ) -> TableResult<BoxedProcedure> {
+        table_error::UnsupportedSnafu {
+            operation: "TRUNCATE VIEW",
+        }
+        .fail()
+    }
+}
+
+impl ViewTableEngine {
+    pub fn new(object_store: ObjectStore) -> Self {
+        ViewTableEngine {
+            inner: Arc::new(EngineInner::new(object_store)),
+        }
+    }
+
+    /// Register all procedure loaders to the procedure manager.
+    ///
+    /// # Panics
+    /// Panics on error.
+    pub fn register_procedure_loaders(&self, procedure_manager: &dyn ProcedureManager) {
+        procedure::register_view_procedure_loaders(self.clone(), procedure_manager);
+    }
+}
+
+struct EngineInner {
+    /// All views opened by the engine.
+    ///
+    /// Writing to `tables` should also hold the `table_mutex`.
+    tables: RwLock<HashMap<TableId, ViewTableRef>>,
+    object_store: ObjectStore,
+
+    /// Table mutex is used to protect the operations such as creating/opening/dropping
+    /// a view, to avoid things like opening the same view simultaneously.
+    table_mutex: Mutex<()>,
+}
+
+impl EngineInner {
+    fn new(object_store: ObjectStore) -> Self {
+        EngineInner {
+            tables: RwLock::new(HashMap::default()),
+            object_store,
+            table_mutex: Mutex::new(()),
+        }
+    }
+
+    async fn create_table(&self, request: CreateTableRequest) -> Result<TableRef> {
+        let CreateTableRequest {
+            id: table_id,
+            catalog_name,
+            schema_name,
+            table_name,
+            create_if_not_exists,
+            mut table_options,
+            ..
+        } = request;
+        let table_full_name = TableReference {
+            catalog: &catalog_name,
+            schema: &schema_name,
+            table: &table_name,
+        }
+        .to_string();
+
+        let _lock = self.table_mutex.lock().await;
+        if let Some(table) = self.get_table(table_id) {
+            return if create_if_not_exists {
+                Ok(table)
+            } else {
+                TableExistsSnafu { table_name }.fail()
+            };
+        }
+
+        let table_schema =
+            Arc::new(Schema::try_from(request.schema).context(InvalidRawSchemaSnafu)?);
+        let view_definition = table_options.extra_options.remove(VIEW_DEFINITION_KEY);
+        let table_meta = TableMetaBuilder::new_external_table()
+            .schema(table_schema)
+            .engine(VIEW_ENGINE)
+            .options(table_options)
+            .view_definition(view_definition)
+            .build()
+            .context(BuildTableMetaSnafu {
+                table_name: &table_full_name,
+            })?;
+        let table_info = TableInfoBuilder::new(&table_name, table_meta)
+            .ident(table_id)
+            .table_version(INIT_TABLE_VERSION)
+            .table_type(TableType::View)
+            .catalog_name(catalog_name.to_string())
+            .schema_name(schema_name.to_string())
+            .desc(request.desc)
+            .build()
+            .context(BuildTableInfoSnafu {
+                table_name: &table_full_name,
+            })?;
+
+        let table_dir = table_dir(&catalog_name, &schema_name, table_id);
+        let table = Arc::new(
+            ViewTable::create(&table_full_name, &table_dir, table_info, &self.object_store).await?,
+        );
+
+        logging::info!(
+            "View engine created view: {} in schema: {}, table_id: {}.",
+            table_name,
+            schema_name,
+            table_id
+        );
+
+        let _ = self.tables.write().unwrap().insert(table_id, table.clone());
+
+        Ok(table)
+    }
+
+    fn get_table(&self, table_id: TableId) -> Option<TableRef> {
+        self.tables
+            .read()
+            .unwrap()
+            .get(&table_id)
+            .cloned()
+            .map(|table| table as _)
+    }
+
+    async fn open_table(&self, request: OpenTableRequest) -> Result<Option<TableRef>> {
+        let OpenTableRequest {
+            catalog_name,
+            schema_name,
+            table_name,
+            table_id,
+            ..
+        } = request;
+
+        let _lock = self.table_mutex.lock().await;
+        if let Some(table) = self.get_table(table_id) {
+            return Ok(Some(table));
+        }
+
+        let table_full_name = TableReference {
+            catalog: &catalog_name,
+            schema: &schema_name,
+            table: &table_name,
+        }
+        .to_string();
+        let table_dir = table_dir(&catalog_name, &schema_name, table_id);
+        let table =
+            Arc::new(ViewTable::recover(&table_full_name, &table_dir, &self.object_store).await?);
+
+        logging::info!(
+            "View engine opened view: {} in schema: {}",
+            table_name,
+            schema_name
+        );
+
+        let _ = self.tables.write().unwrap().insert(table_id, table.clone());
+
+        Ok(Some(table))
+    }
+
+    async fn drop_table(&self, req: DropTableRequest) -> Result<bool> {
+        let table_full_name = req.table_ref().to_string();
+
+        let _lock = self.table_mutex.lock().await;
+        if self.get_table(req.table_id).is_none() {
+            return Ok(false);
+        }
+
+        let table_dir = table_dir(&req.catalog_name, &req.schema_name, req.table_id);
+        delete_table_manifest(
+            &table_full_name,
+            &table_manifest_dir(&table_dir),
+            &self.object_store,
+        )
+        .await
+        .map_err(BoxedError::new)
+        .context(DropTableSnafu {
+            table_name: &table_full_name,
+        })?;
+        let _ = self.tables.write().unwrap().remove(&req.table_id);
+
+        Ok(true)
+    }
+
+    async fn close(&self) {
+        let _lock = self.table_mutex.lock().await;
+
+        // Views hold no resources, so closing them only releases them from the engine.
+        self.tables.write().unwrap().clear();
+    }
+}
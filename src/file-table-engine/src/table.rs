@@ -14,3 +14,4 @@
 
 pub mod format;
 pub mod immutable;
+pub mod view;
@@ -21,7 +21,9 @@ use catalog::error::{
     self as catalog_err, InternalSnafu, InvalidSystemTableDefSnafu, ListCatalogsSnafu,
     ListSchemasSnafu, Result as CatalogResult, TableMetadataManagerSnafu, UnimplementedSnafu,
 };
-use catalog::information_schema::{InformationSchemaProvider, COLUMNS, TABLES};
+use catalog::information_schema::{
+    InformationSchemaProvider, MetadataFilterRef, INFORMATION_SCHEMA_TABLE_NAMES,
+};
 use catalog::remote::KvCacheInvalidatorRef;
 use catalog::{
     CatalogManager, DeregisterSchemaRequest, DeregisterTableRequest, RegisterSchemaRequest,
@@ -320,8 +322,11 @@ impl CatalogManager for FrontendCatalogManager {
             tables.push(NUMBERS_TABLE_NAME.to_string());
         }
         if schema == INFORMATION_SCHEMA_NAME {
-            tables.push(TABLES.to_string());
-            tables.push(COLUMNS.to_string());
+            tables.extend(
+                INFORMATION_SCHEMA_TABLE_NAMES
+                    .iter()
+                    .map(|name| name.to_string()),
+            );
         }
 
         Ok(tables)
@@ -347,7 +352,7 @@ impl CatalogManager for FrontendCatalogManager {
     }
 
     async fn table_exist(&self, catalog: &str, schema: &str, table: &str) -> CatalogResult<bool> {
-        if schema == INFORMATION_SCHEMA_NAME && (table == TABLES || table == COLUMNS) {
+        if schema == INFORMATION_SCHEMA_NAME && INFORMATION_SCHEMA_TABLE_NAMES.contains(&table) {
             return Ok(true);
         }
 
@@ -374,20 +379,9 @@ impl CatalogManager for FrontendCatalogManager {
         }
 
         if schema == INFORMATION_SCHEMA_NAME {
-            // hack: use existing cyclin reference to get Arc<Self>.
-            // This can be remove by refactoring the struct into something like Arc<Inner>
-            common_telemetry::info!("going to use dist instance");
-            let manager = if let Some(instance) = self.dist_instance.as_ref() {
-                common_telemetry::info!("dist instance exist");
-                instance.catalog_manager() as _
-            } else {
-                common_telemetry::info!("dist instance doesn't exist");
-                return Ok(None);
-            };
-
-            let provider =
-                InformationSchemaProvider::new(catalog.to_string(), Arc::downgrade(&manager));
-            return Ok(provider.table(table_name));
+            return self
+                .information_schema_table(catalog, table_name, None)
+                .await;
         }
 
         let key = TableNameKey::new(catalog, schema, table_name);
@@ -425,6 +419,30 @@ impl CatalogManager for FrontendCatalogManager {
         Ok(Some(table))
     }
 
+    async fn information_schema_table(
+        &self,
+        catalog: &str,
+        table_name: &str,
+        filter: Option<MetadataFilterRef>,
+    ) -> CatalogResult<Option<TableRef>> {
+        // hack: use existing cyclin reference to get Arc<Self>.
+        // This can be remove by refactoring the struct into something like Arc<Inner>
+        common_telemetry::info!("going to use dist instance");
+        let manager = if let Some(instance) = self.dist_instance.as_ref() {
+            common_telemetry::info!("dist instance exist");
+            instance.catalog_manager() as _
+        } else {
+            common_telemetry::info!("dist instance doesn't exist");
+            return Ok(None);
+        };
+
+        let provider =
+            InformationSchemaProvider::new(catalog.to_string(), Arc::downgrade(&manager))
+                .with_partition_manager(self.partition_manager.clone())
+                .with_filter(filter);
+        Ok(provider.table(table_name))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -598,6 +598,18 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to initialize role-based access control, source: {}", source))]
+    InitRbac {
+        source: auth::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to manage users and privileges, source: {}", source))]
+    ManagePrivilege {
+        source: auth::error::Error,
+        location: Location,
+    },
+
     #[snafu(display("Empty data: {}", msg))]
     EmptyData { msg: String, location: Location },
 
@@ -607,11 +619,99 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to create record batches, source: {}", source))]
+    CreateRecordBatches {
+        source: common_recordbatch::error::Error,
+        location: Location,
+    },
+
     #[snafu(display("Failed to build column vectors, source: {}", source))]
     BuildColumnVectors {
         source: common_recordbatch::error::Error,
         location: Location,
     },
+
+    #[snafu(display("Failed to read rule file {}, source: {}", path, source))]
+    ReadRuleFile {
+        path: String,
+        source: std::io::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to parse rule file {}, source: {}", path, source))]
+    ParseRuleFile {
+        path: String,
+        source: serde_yaml::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Invalid rule in group {}, reason: {}", group, reason))]
+    InvalidRule {
+        group: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to evaluate rule {}, source: {}", rule, source))]
+    EvaluateRule {
+        rule: String,
+        #[snafu(backtrace)]
+        source: servers::error::Error,
+    },
+
+    #[snafu(display("Invalid materialized view {}, reason: {}", view, reason))]
+    InvalidMaterializedView {
+        view: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to load materialized views of {}.{}, error: {}",
+        catalog,
+        schema,
+        err_msg
+    ))]
+    LoadMaterializedViews {
+        catalog: String,
+        schema: String,
+        err_msg: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to send alerts to {}, source: {}", url, source))]
+    SendAlerts {
+        url: String,
+        source: reqwest::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to submit region migration to {}, source: {}", url, source))]
+    SubmitRegionMigration {
+        url: String,
+        source: reqwest::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Region migration is rejected by Metasrv, reason: {}", reason))]
+    RegionMigrationRejected { reason: String, location: Location },
+
+    #[snafu(display(
+        "Invalid region migration reply from Metasrv: {}, source: {}",
+        body,
+        source
+    ))]
+    InvalidRegionMigrationReply {
+        body: String,
+        source: serde_json::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to build HTTP client, source: {}", source))]
+    BuildHttpClient {
+        source: reqwest::Error,
+        location: Location,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -634,11 +734,14 @@ impl ErrorExt for Error {
             | Error::BuildCsvConfig { .. }
             | Error::ProjectSchema { .. }
             | Error::UnsupportedFormat { .. }
-            | Error::EmptyData { .. } => StatusCode::InvalidArguments,
+            | Error::EmptyData { .. }
+            | Error::InvalidMaterializedView { .. } => StatusCode::InvalidArguments,
 
             Error::NotSupported { .. } => StatusCode::Unsupported,
 
-            Error::Permission { source, .. } => source.status_code(),
+            Error::Permission { source, .. }
+            | Error::InitRbac { source, .. }
+            | Error::ManagePrivilege { source, .. } => source.status_code(),
 
             Error::HandleHeartbeatResponse { source, .. }
             | Error::TableMetadataManager { source, .. } => source.status_code(),
@@ -744,9 +847,20 @@ impl ErrorExt for Error {
             Error::WriteParquet { source, .. } => source.status_code(),
             Error::InvalidCopyParameter { .. } => StatusCode::InvalidArguments,
 
-            Error::ReadRecordBatch { source, .. } | Error::BuildColumnVectors { source, .. } => {
-                source.status_code()
-            }
+            Error::ReadRecordBatch { source, .. }
+            | Error::CreateRecordBatches { source, .. }
+            | Error::BuildColumnVectors { source, .. } => source.status_code(),
+
+            Error::ReadRuleFile { .. }
+            | Error::ParseRuleFile { .. }
+            | Error::InvalidRule { .. } => StatusCode::InvalidArguments,
+            Error::EvaluateRule { source, .. } => source.status_code(),
+            Error::SendAlerts { .. }
+            | Error::SubmitRegionMigration { .. }
+            | Error::InvalidRegionMigrationReply { .. } => StatusCode::Unexpected,
+            Error::BuildHttpClient { .. } => StatusCode::Internal,
+            Error::RegionMigrationRejected { .. } => StatusCode::InvalidArguments,
+            Error::LoadMaterializedViews { .. } => StatusCode::Internal,
         }
     }
 
@@ -20,6 +20,7 @@ use api::v1::{
     AddColumn, AddColumns, AlterExpr, Column, ColumnDataType, CreateTableExpr, DropColumn,
     DropColumns, RenameTable,
 };
+use common_catalog::consts::VIEW_ENGINE;
 use common_error::ext::BoxedError;
 use common_grpc_expr::util::ColumnExpr;
 use datanode::instance::sql::table_idents_to_full_name;
@@ -30,11 +31,11 @@ use session::context::QueryContextRef;
 use snafu::{ensure, ResultExt};
 use sql::ast::{ColumnDef, ColumnOption, TableConstraint};
 use sql::statements::alter::{AlterTable, AlterTableOperation};
-use sql::statements::create::{CreateExternalTable, CreateTable, TIME_INDEX};
+use sql::statements::create::{CreateExternalTable, CreateTable, CreateView, TIME_INDEX};
 use sql::statements::{column_def_to_schema, sql_column_def_to_grpc_column_def};
 use sql::util::to_lowercase_options_map;
 use table::engine::TableReference;
-use table::requests::{TableOptions, IMMUTABLE_TABLE_META_KEY};
+use table::requests::{TableOptions, IMMUTABLE_TABLE_META_KEY, VIEW_DEFINITION_KEY};
 
 use crate::error::{
     BuildCreateExprOnInsertionSnafu, ColumnDataTypeSnafu, ConvertColumnDefaultConstraintSnafu,
@@ -152,6 +153,33 @@ pub fn create_to_expr(create: &CreateTable, query_ctx: QueryContextRef) -> Resul
     Ok(expr)
 }
 
+/// Convert `CreateView` statement to `CreateExpr` gRPC request of a table in the view engine.
+pub(crate) fn create_view_expr(
+    create: &CreateView,
+    query_ctx: QueryContextRef,
+) -> Result<CreateTableExpr> {
+    let (catalog_name, schema_name, table_name) =
+        table_idents_to_full_name(&create.name, query_ctx)
+            .map_err(BoxedError::new)
+            .context(ExternalSnafu)?;
+
+    let expr = CreateTableExpr {
+        catalog_name,
+        schema_name,
+        table_name,
+        desc: "".to_string(),
+        column_defs: columns_to_expr(&create.columns, "")?,
+        time_index: "".to_string(),
+        primary_keys: vec![],
+        create_if_not_exists: create.if_not_exists,
+        table_options: HashMap::from([(VIEW_DEFINITION_KEY.to_string(), create.query.to_string())]),
+        table_id: None,
+        region_numbers: vec![],
+        engine: VIEW_ENGINE.to_string(),
+    };
+    Ok(expr)
+}
+
 fn find_primary_keys(
     columns: &[ColumnDef],
     constraints: &[TableConstraint],
@@ -312,6 +340,20 @@ pub(crate) fn to_alter_expr(
         AlterTableOperation::RenameTable { new_table_name } => Kind::RenameTable(RenameTable {
             new_table_name: new_table_name.to_string(),
         }),
+        // The alter expr has no kind to carry modified columns yet.
+        AlterTableOperation::ModifyColumn { .. } | AlterTableOperation::SetColumnDefault { .. } => {
+            return NotSupportedSnafu {
+                feat: "ALTER TABLE MODIFY COLUMN in distributed mode",
+            }
+            .fail();
+        }
+        // The alter expr has no kind to carry table options yet.
+        AlterTableOperation::SetTableOptions { .. } => {
+            return NotSupportedSnafu {
+                feat: "ALTER TABLE SET in distributed mode",
+            }
+            .fail();
+        }
     };
 
     Ok(AlterExpr {
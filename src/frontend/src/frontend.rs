@@ -21,7 +21,7 @@ use servers::Mode;
 
 use crate::service_config::{
     DatanodeOptions, GrpcOptions, InfluxdbOptions, MysqlOptions, OpentsdbOptions, OtlpOptions,
-    PostgresOptions, PromStoreOptions,
+    PostgresOptions, PromStoreOptions, RuleOptions,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -38,6 +38,7 @@ pub struct FrontendOptions {
     pub influxdb_options: Option<InfluxdbOptions>,
     pub prom_store_options: Option<PromStoreOptions>,
     pub otlp_options: Option<OtlpOptions>,
+    pub rule_options: Option<RuleOptions>,
     pub meta_client_options: Option<MetaClientOptions>,
     pub logging: LoggingOptions,
     pub datanode: DatanodeOptions,
@@ -57,6 +58,7 @@ impl Default for FrontendOptions {
             influxdb_options: Some(InfluxdbOptions::default()),
             prom_store_options: Some(PromStoreOptions::default()),
             otlp_options: Some(OtlpOptions::default()),
+            rule_options: Some(RuleOptions::default()),
             meta_client_options: None,
             logging: LoggingOptions::default(),
             datanode: DatanodeOptions::default(),
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod invalidate_table_cache;
+pub mod invalidate_user_cache;
 
 #[cfg(test)]
 pub(crate) mod tests;
@@ -0,0 +1,98 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use auth::RbacManagerRef;
+use catalog::remote::KvCacheInvalidatorRef;
+use common_meta::error::Result as MetaResult;
+use common_meta::heartbeat::handler::{
+    HandleControl, HeartbeatResponseHandler, HeartbeatResponseHandlerContext,
+};
+use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_telemetry::error;
+
+/// Invalidates cached users and roles when they are changed, e.g. privileges are granted
+/// or revoked by other frontends.
+#[derive(Clone)]
+pub struct InvalidateUserCacheHandler {
+    backend_cache_invalidator: KvCacheInvalidatorRef,
+    rbac_manager: Option<RbacManagerRef>,
+}
+
+#[async_trait]
+impl HeartbeatResponseHandler for InvalidateUserCacheHandler {
+    fn is_acceptable(&self, ctx: &HeartbeatResponseHandlerContext) -> bool {
+        matches!(
+            ctx.incoming_message.as_ref(),
+            Some((_, Instruction::InvalidateUserCache { .. }))
+        )
+    }
+
+    async fn handle(&self, ctx: &mut HeartbeatResponseHandlerContext) -> MetaResult<HandleControl> {
+        let Some((meta, Instruction::InvalidateUserCache { keys })) = ctx.incoming_message.take()
+        else {
+            unreachable!("InvalidateUserCacheHandler: should be guarded by 'is_acceptable'");
+        };
+
+        let mailbox = ctx.mailbox.clone();
+        let self_ref = self.clone();
+        let _handle = common_runtime::spawn_bg(async move {
+            let error = self_ref
+                .invalidate_user_cache(keys)
+                .await
+                .err()
+                .map(|e| e.to_string());
+
+            if let Err(e) = mailbox
+                .send((
+                    meta,
+                    InstructionReply::InvalidateUserCache(SimpleReply {
+                        result: error.is_none(),
+                        error,
+                    }),
+                ))
+                .await
+            {
+                error!(e; "Failed to send reply to mailbox");
+            }
+        });
+
+        Ok(HandleControl::Done)
+    }
+}
+
+impl InvalidateUserCacheHandler {
+    pub fn new(
+        backend_cache_invalidator: KvCacheInvalidatorRef,
+        rbac_manager: Option<RbacManagerRef>,
+    ) -> Self {
+        Self {
+            backend_cache_invalidator,
+            rbac_manager,
+        }
+    }
+
+    async fn invalidate_user_cache(&self, keys: Vec<String>) -> auth::error::Result<()> {
+        for key in keys {
+            self.backend_cache_invalidator
+                .invalidate_key(key.as_bytes())
+                .await;
+        }
+
+        match &self.rbac_manager {
+            Some(rbac_manager) => rbac_manager.reload_privileges().await,
+            None => Ok(()),
+        }
+    }
+}
@@ -31,6 +31,7 @@ use table::metadata::TableId;
 use tokio::sync::mpsc;
 
 use super::invalidate_table_cache::InvalidateTableCacheHandler;
+use super::invalidate_user_cache::InvalidateUserCacheHandler;
 
 #[derive(Default)]
 pub struct MockKvCacheInvalidator {
@@ -124,6 +125,38 @@ async fn test_invalidate_table_cache_handler() {
     );
 }
 
+#[tokio::test]
+async fn test_invalidate_user_cache_handler() {
+    let user_key = b"__user/alice".to_vec();
+    let inner = HashMap::from([(user_key.clone(), 1)]);
+    let backend = Arc::new(MockKvCacheInvalidator {
+        inner: Mutex::new(inner),
+    });
+
+    let executor = Arc::new(HandlerGroupExecutor::new(vec![Arc::new(
+        InvalidateUserCacheHandler::new(backend.clone(), None),
+    )]));
+
+    let (tx, mut rx) = mpsc::channel(8);
+    let mailbox = Arc::new(HeartbeatMailbox::new(tx));
+
+    handle_instruction(
+        executor,
+        mailbox,
+        Instruction::InvalidateUserCache {
+            keys: vec!["__user/alice".to_string()],
+        },
+    )
+    .await;
+
+    let (_, reply) = rx.recv().await.unwrap();
+    assert_matches!(
+        reply,
+        InstructionReply::InvalidateUserCache(SimpleReply { result: true, .. })
+    );
+    assert!(!backend.inner.lock().unwrap().contains_key(&user_key));
+}
+
 pub fn test_message_meta(id: u64, subject: &str, to: &str, from: &str) -> MessageMeta {
     MessageMeta {
         id,
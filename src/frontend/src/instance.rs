@@ -30,10 +30,14 @@ use api::v1::ddl_request::Expr as DdlExpr;
 use api::v1::greptime_request::Request;
 use api::v1::meta::Role;
 use api::v1::{
-    AddColumns, AlterExpr, Column, DdlRequest, InsertRequest, InsertRequests, RowInsertRequests,
+    AddColumns, AlterExpr, Column, DdlRequest, DeleteRequests, InsertRequest, InsertRequests,
+    RowInsertRequests,
 };
 use async_trait::async_trait;
-use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
+use auth::{
+    PermissionChecker, PermissionCheckerRef, PermissionReq, RbacManager, RbacManagerRef,
+    RbacOptions, UserProviderRef,
+};
 use catalog::remote::CachedMetaKvBackend;
 use catalog::CatalogManagerRef;
 use client::client_manager::DatanodeClients;
@@ -44,12 +48,14 @@ use common_grpc::channel_manager::{ChannelConfig, ChannelManager};
 use common_meta::heartbeat::handler::parse_mailbox_message::ParseMailboxMessageHandler;
 use common_meta::heartbeat::handler::HandlerGroupExecutor;
 use common_meta::key::TableMetadataManager;
+use common_meta::kv_backend::KvBackendRef;
 use common_query::Output;
 use common_telemetry::logging::{debug, info};
 use common_telemetry::{error, timer};
 use datanode::instance::sql::table_idents_to_full_name;
 use datanode::instance::InstanceRef as DnInstanceRef;
 use datatypes::schema::Schema;
+use distributed::migrate_region::{MigrateRegion, RegionMigrator, RegionMigratorRef};
 use distributed::DistInstance;
 use meta_client::client::{MetaClient, MetaClientBuilder};
 use partition::manager::PartitionRuleManager;
@@ -59,23 +65,24 @@ use query::plan::LogicalPlan;
 use query::query_engine::options::{validate_catalog_and_schema, QueryOptions};
 use query::query_engine::DescribeResult;
 use query::{QueryEngineFactory, QueryEngineRef};
-use servers::error as server_error;
 use servers::error::{AuthSnafu, ExecuteQuerySnafu, ParsePromQLSnafu};
 use servers::interceptor::{
     PromQueryInterceptor, PromQueryInterceptorRef, SqlQueryInterceptor, SqlQueryInterceptorRef,
 };
-use servers::prometheus::PrometheusHandler;
+use servers::prometheus::{Alert, PrometheusHandler, RuleGroup};
 use servers::query_handler::grpc::{GrpcQueryHandler, GrpcQueryHandlerRef};
 use servers::query_handler::sql::SqlQueryHandler;
 use servers::query_handler::{
     InfluxdbLineProtocolHandler, OpenTelemetryProtocolHandler, OpentsdbProtocolHandler,
     PromStoreProtocolHandler, ScriptHandler,
 };
+use servers::{error as server_error, Mode};
 use session::context::QueryContextRef;
 use snafu::prelude::*;
 use sql::dialect::Dialect;
 use sql::parser::ParserContext;
 use sql::statements::copy::CopyTable;
+use sql::statements::show::{ShowMaterializedViews, ShowTables};
 use sql::statements::statement::Statement;
 use sqlparser::ast::ObjectName;
 use table::engine::TableReference;
@@ -89,12 +96,15 @@ use crate::error::{
 use crate::expr_factory::CreateExprFactory;
 use crate::frontend::FrontendOptions;
 use crate::heartbeat::handler::invalidate_table_cache::InvalidateTableCacheHandler;
+use crate::heartbeat::handler::invalidate_user_cache::InvalidateUserCacheHandler;
 use crate::heartbeat::HeartbeatTask;
 use crate::instance::standalone::StandaloneGrpcQueryHandler;
 use crate::metrics;
 use crate::row_inserter::RowInserter;
+use crate::rule::{RuleManager, RuleManagerRef};
 use crate::script::ScriptExecutor;
 use crate::server::{start_server, ServerHandlers, Services};
+use crate::statement::materialized_view::{columns_time_range, rows_time_range, Refresh};
 use crate::statement::StatementExecutor;
 
 #[async_trait]
@@ -131,6 +141,9 @@ pub struct Instance {
     servers: Arc<ServerHandlers>,
     heartbeat_task: Option<HeartbeatTask>,
     row_inserter: Arc<RowInserter>,
+    rule_manager: Option<RuleManagerRef>,
+    /// Submits region migrations, only available in distributed mode.
+    region_migrator: Option<RegionMigratorRef>,
 }
 
 impl Instance {
@@ -182,14 +195,18 @@ impl Instance {
             plugins.clone(),
         )
         .query_engine();
+        let region_migrator = Arc::new(RegionMigrator::try_new(meta_client.clone())?);
 
         let script_executor =
             Arc::new(ScriptExecutor::new(catalog_manager.clone(), query_engine.clone()).await?);
 
+        let rbac_manager = Self::build_rbac_manager(&plugins, meta_backend.clone())?;
+
         let statement_executor = Arc::new(StatementExecutor::new(
             catalog_manager.clone(),
             query_engine.clone(),
             dist_instance.clone(),
+            rbac_manager.clone(),
         ));
 
         plugins.insert::<StatementExecutorRef>(statement_executor.clone());
@@ -197,9 +214,10 @@ impl Instance {
         let handlers_executor = HandlerGroupExecutor::new(vec![
             Arc::new(ParseMailboxMessageHandler),
             Arc::new(InvalidateTableCacheHandler::new(
-                meta_backend,
+                meta_backend.clone(),
                 partition_manager,
             )),
+            Arc::new(InvalidateUserCacheHandler::new(meta_backend, rbac_manager)),
         ]);
 
         let heartbeat_task = Some(HeartbeatTask::new(
@@ -230,9 +248,28 @@ impl Instance {
             servers: Arc::new(HashMap::new()),
             heartbeat_task,
             row_inserter,
+            rule_manager: None,
+            region_migrator: Some(region_migrator),
         })
     }
 
+    /// Enables role-based access control if [RbacOptions] is provided. Users and their
+    /// privileges are stored in the metadata.
+    fn build_rbac_manager(
+        plugins: &Plugins,
+        kv_backend: KvBackendRef,
+    ) -> Result<Option<RbacManagerRef>> {
+        let Some(options) = plugins.get::<RbacOptions>() else {
+            return Ok(None);
+        };
+        let rbac_manager =
+            Arc::new(RbacManager::try_new(&options, kv_backend).context(error::InitRbacSnafu)?);
+        plugins.insert::<UserProviderRef>(rbac_manager.clone());
+        plugins.insert::<PermissionCheckerRef>(rbac_manager.clone());
+        plugins.insert::<RbacManagerRef>(rbac_manager.clone());
+        Ok(Some(rbac_manager))
+    }
+
     async fn create_meta_client(opts: &FrontendOptions) -> Result<Arc<MetaClient>> {
         let meta_client_options = opts
             .meta_client_options
@@ -280,6 +317,7 @@ impl Instance {
             catalog_manager.clone(),
             query_engine.clone(),
             dn_instance.clone(),
+            None,
         ));
 
         let create_expr_factory = CreateExprFactory;
@@ -303,10 +341,28 @@ impl Instance {
             servers: Arc::new(HashMap::new()),
             heartbeat_task: None,
             row_inserter,
+            rule_manager: None,
+            region_migrator: None,
         })
     }
 
     pub async fn build_servers(&mut self, opts: &FrontendOptions) -> Result<()> {
+        // Servers hold a clone of the instance, so rules must be loaded before them
+        // to be visible in the HTTP API.
+        if let Some(rule_options) = &opts.rule_options {
+            // Rules are only evaluated in standalone mode, as each frontend in a cluster
+            // would evaluate the same rules, duplicating the recorded series and alerts.
+            ensure!(
+                opts.mode == Mode::Standalone || rule_options.rule_files.is_empty(),
+                error::NotSupportedSnafu {
+                    feat: "rule evaluation in distributed mode"
+                }
+            );
+            if opts.mode == Mode::Standalone {
+                self.rule_manager = Some(Arc::new(RuleManager::try_new(rule_options)?));
+            }
+        }
+
         let servers = Services::build(opts, Arc::new(self.clone()), self.plugins.clone()).await?;
         self.servers = Arc::new(servers);
 
@@ -323,7 +379,22 @@ impl Instance {
         requests: RowInsertRequests,
         ctx: QueryContextRef,
     ) -> Result<Output> {
-        self.row_inserter.handle_inserts(requests, ctx).await
+        let mut refreshes = Vec::new();
+        for req in &requests.inserts {
+            if self.has_materialized_views(&ctx, &req.table_name) {
+                if let Some(time_range) = req.rows.as_ref().and_then(rows_time_range) {
+                    refreshes.push((req.table_name.clone(), Refresh::insert(time_range)));
+                }
+            }
+        }
+
+        let output = self
+            .row_inserter
+            .handle_inserts(requests, ctx.clone())
+            .await?;
+
+        self.refresh_materialized_views(&ctx, refreshes);
+        Ok(output)
     }
 
     /// Handle batch inserts
@@ -332,13 +403,68 @@ impl Instance {
         requests: InsertRequests,
         ctx: QueryContextRef,
     ) -> Result<Output> {
+        let mut refreshes = Vec::new();
         for req in requests.inserts.iter() {
             self.create_or_alter_table_on_demand(ctx.clone(), req)
                 .await?;
+
+            if self.has_materialized_views(&ctx, &req.table_name) {
+                if let Some(time_range) = columns_time_range(&req.columns) {
+                    refreshes.push((req.table_name.clone(), Refresh::insert(time_range)));
+                }
+            }
         }
 
         let query = Request::Inserts(requests);
-        GrpcQueryHandler::do_query(&*self.grpc_query_handler, query, ctx).await
+        let output =
+            GrpcQueryHandler::do_query(&*self.grpc_query_handler, query, ctx.clone()).await?;
+
+        self.refresh_materialized_views(&ctx, refreshes);
+        Ok(output)
+    }
+
+    /// Handle batch deletes
+    pub async fn handle_deletes(
+        &self,
+        requests: DeleteRequests,
+        ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let refreshes = requests
+            .deletes
+            .iter()
+            .filter(|req| self.has_materialized_views(&ctx, &req.table_name))
+            .filter_map(|req| {
+                let time_range = columns_time_range(&req.key_columns)?;
+                Some((req.table_name.clone(), Refresh::delete(time_range)))
+            })
+            .collect();
+
+        let query = Request::Deletes(requests);
+        let output =
+            GrpcQueryHandler::do_query(&*self.grpc_query_handler, query, ctx.clone()).await?;
+
+        self.refresh_materialized_views(&ctx, refreshes);
+        Ok(output)
+    }
+
+    fn has_materialized_views(&self, ctx: &QueryContextRef, table: &str) -> bool {
+        self.statement_executor.has_materialized_views(
+            ctx.current_catalog(),
+            ctx.current_schema(),
+            table,
+        )
+    }
+
+    /// Queues refreshes of the materialized views reading from the tables written into.
+    fn refresh_materialized_views(&self, ctx: &QueryContextRef, refreshes: Vec<(String, Refresh)>) {
+        for (table, refresh) in refreshes {
+            self.statement_executor.refresh_materialized_views(
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                &table,
+                refresh,
+            );
+        }
     }
 
     // check if table already exist:
@@ -467,6 +593,10 @@ impl Instance {
     }
 
     pub async fn shutdown(&self) -> Result<()> {
+        if let Some(rule_manager) = &self.rule_manager {
+            rule_manager.stop();
+        }
+
         futures::future::try_join_all(self.servers.values().map(|server| server.0.shutdown()))
             .await
             .context(error::ShutdownServerSnafu)
@@ -487,6 +617,10 @@ impl FrontendInstance for Instance {
             heartbeat_task.start().await?;
         }
 
+        if let Some(rule_manager) = &self.rule_manager {
+            rule_manager.start(Arc::new(self.clone()));
+        }
+
         futures::future::try_join_all(self.servers.values().map(start_server))
             .await
             .context(error::StartServerSnafu)
@@ -502,6 +636,18 @@ impl Instance {
     async fn query_statement(&self, stmt: Statement, query_ctx: QueryContextRef) -> Result<Output> {
         check_permission(self.plugins.clone(), &stmt, &query_ctx)?;
 
+        // Submits the migration here instead of in the query engine, which may evaluate
+        // functions more than once.
+        if let Some(call) = MigrateRegion::parse(&stmt)? {
+            let region_migrator =
+                self.region_migrator
+                    .as_ref()
+                    .with_context(|| error::NotSupportedSnafu {
+                        feat: "Region migration in standalone mode",
+                    })?;
+            return region_migrator.migrate_region(&call).await;
+        }
+
         let stmt = QueryStatement::Sql(stmt);
         self.statement_executor.execute_stmt(stmt, query_ctx).await
     }
@@ -538,8 +684,10 @@ impl SqlQueryHandler for Instance {
                     }
 
                     if let Err(e) = checker
-                        .check_permission(
+                        .check_database_permission(
                             query_ctx.current_user(),
+                            query_ctx.current_catalog(),
+                            query_ctx.current_schema(),
                             PermissionReq::SqlStatement(&stmt),
                         )
                         .context(PermissionSnafu)
@@ -607,7 +755,12 @@ impl SqlQueryHandler for Instance {
             self.plugins
                 .get::<PermissionCheckerRef>()
                 .as_ref()
-                .check_permission(query_ctx.current_user(), PermissionReq::SqlStatement(&stmt))
+                .check_database_permission(
+                    query_ctx.current_user(),
+                    query_ctx.current_catalog(),
+                    query_ctx.current_schema(),
+                    PermissionReq::SqlStatement(&stmt),
+                )
                 .context(PermissionSnafu)?;
 
             let plan = self
@@ -650,7 +803,12 @@ impl PrometheusHandler for Instance {
         self.plugins
             .get::<PermissionCheckerRef>()
             .as_ref()
-            .check_permission(query_ctx.current_user(), PermissionReq::PromQuery)
+            .check_database_permission(
+                query_ctx.current_user(),
+                query_ctx.current_catalog(),
+                query_ctx.current_schema(),
+                PermissionReq::PromQuery,
+            )
             .context(AuthSnafu)?;
 
         let stmt = QueryLanguageParser::parse_promql(query).with_context(|_| ParsePromQLSnafu {
@@ -672,6 +830,20 @@ impl PrometheusHandler for Instance {
     fn catalog_manager(&self) -> CatalogManagerRef {
         self.catalog_manager.clone()
     }
+
+    fn rule_groups(&self) -> Vec<RuleGroup> {
+        self.rule_manager
+            .as_ref()
+            .map(|rule_manager| rule_manager.rule_groups())
+            .unwrap_or_default()
+    }
+
+    fn alerts(&self) -> Vec<Alert> {
+        self.rule_manager
+            .as_ref()
+            .map(|rule_manager| rule_manager.alerts())
+            .unwrap_or_default()
+    }
 }
 
 pub fn check_permission(
@@ -693,9 +865,16 @@ pub fn check_permission(
         Statement::Query(_) | Statement::Explain(_) | Statement::Tql(_) | Statement::Delete(_) => {}
         // database ops won't be checked
         Statement::CreateDatabase(_) | Statement::ShowDatabases(_) => {}
+        // users and privileges are not bound to any schema
+        Statement::CreateUser(_)
+        | Statement::CreateRole(_)
+        | Statement::Grant(_)
+        | Statement::Revoke(_) => {}
         // show create table and alter are not supported yet
-        Statement::ShowCreateTable(_) | Statement::CreateExternalTable(_) | Statement::Alter(_) => {
-        }
+        Statement::ShowCreateTable(_)
+        | Statement::ShowCreateView(_)
+        | Statement::CreateExternalTable(_)
+        | Statement::Alter(_) => {}
 
         Statement::Insert(insert) => {
             validate_param(insert.table_name(), query_ctx)?;
@@ -703,11 +882,21 @@ pub fn check_permission(
         Statement::CreateTable(stmt) => {
             validate_param(&stmt.name, query_ctx)?;
         }
+        Statement::CreateMaterializedView(stmt) => {
+            validate_param(&stmt.name, query_ctx)?;
+        }
+        Statement::CreateView(stmt) => {
+            validate_param(&stmt.name, query_ctx)?;
+        }
         Statement::DropTable(drop_stmt) => {
             validate_param(drop_stmt.table_name(), query_ctx)?;
         }
-        Statement::ShowTables(stmt) => {
-            if let Some(database) = &stmt.database {
+        Statement::DropView(drop_stmt) => {
+            validate_param(drop_stmt.view_name(), query_ctx)?;
+        }
+        Statement::ShowTables(ShowTables { database, .. })
+        | Statement::ShowMaterializedViews(ShowMaterializedViews { database }) => {
+            if let Some(database) = database {
                 validate_catalog_and_schema(query_ctx.current_catalog(), database, query_ctx)
                     .map_err(BoxedError::new)
                     .context(SqlExecInterceptedSnafu)?;
@@ -14,6 +14,7 @@
 
 pub mod deleter;
 pub(crate) mod inserter;
+pub(crate) mod migrate_region;
 pub(crate) mod row_inserter;
 
 use std::collections::HashMap;
@@ -31,7 +32,7 @@ use catalog::{CatalogManager, DeregisterTableRequest, RegisterTableRequest};
 use chrono::DateTime;
 use client::client_manager::DatanodeClients;
 use client::Database;
-use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, VIEW_ENGINE};
 use common_catalog::format_full_table_name;
 use common_error::ext::BoxedError;
 use common_meta::key::schema_name::SchemaNameKey;
@@ -60,7 +61,7 @@ use sql::statements::{self, sql_value_to_value};
 use store_api::storage::RegionNumber;
 use table::error::TableOperationSnafu;
 use table::metadata::{RawTableInfo, RawTableMeta, TableId, TableIdent, TableInfo, TableType};
-use table::requests::{AlterTableRequest, TableOptions};
+use table::requests::{AlterTableRequest, TableOptions, VIEW_DEFINITION_KEY};
 use table::TableRef;
 
 use crate::catalog::FrontendCatalogManager;
@@ -110,7 +111,12 @@ impl DistInstance {
             &create_table.table_name,
         );
 
-        let (partitions, partition_cols) = parse_partitions(create_table, partitions)?;
+        // Views are only metadata, so they have neither partitions nor regions.
+        let (partitions, partition_cols) = if create_table.engine == VIEW_ENGINE {
+            (vec![], vec![])
+        } else {
+            parse_partitions(create_table, partitions)?
+        };
 
         let mut table_info = create_table_info(create_table, partition_cols)?;
 
@@ -357,6 +363,11 @@ impl DistInstance {
                 let _ = self.create_table(create_expr, None).await?;
                 Ok(Output::AffectedRows(0))
             }
+            Statement::CreateView(stmt) => {
+                let create_expr = &mut expr_factory::create_view_expr(&stmt, query_ctx)?;
+                let _ = self.create_table(create_expr, None).await?;
+                Ok(Output::AffectedRows(0))
+            }
             Statement::Alter(alter_table) => {
                 let expr = expr_factory::to_alter_expr(alter_table, query_ctx)?;
                 self.handle_alter_table(expr).await
@@ -369,6 +380,33 @@ impl DistInstance {
                 let table_name = TableName::new(catalog, schema, table);
                 self.drop_table(table_name).await
             }
+            Statement::DropView(stmt) => {
+                let (catalog, schema, view) =
+                    table_idents_to_full_name(stmt.view_name(), query_ctx)
+                        .map_err(BoxedError::new)
+                        .context(error::ExternalSnafu)?;
+                let Some(table) = self
+                    .catalog_manager
+                    .table(&catalog, &schema, &view)
+                    .await
+                    .context(CatalogSnafu)?
+                else {
+                    ensure!(
+                        stmt.drop_if_exists(),
+                        TableNotFoundSnafu {
+                            table_name: stmt.view_name().to_string(),
+                        }
+                    );
+                    return Ok(Output::AffectedRows(0));
+                };
+                ensure!(
+                    table.table_type() == TableType::View,
+                    error::InvalidSqlSnafu {
+                        err_msg: format!("{} is not a view", stmt.view_name()),
+                    }
+                );
+                self.drop_table(TableName::new(catalog, schema, view)).await
+            }
             Statement::Insert(insert) => {
                 let (catalog, schema, _) =
                     table_idents_to_full_name(insert.table_name(), query_ctx.clone())
@@ -405,6 +443,27 @@ impl DistInstance {
 
                 self.show_create_table(table_name, table_ref).await
             }
+            Statement::ShowCreateView(show) => {
+                let (catalog, schema, view) =
+                    table_idents_to_full_name(&show.view_name, query_ctx.clone())
+                        .map_err(BoxedError::new)
+                        .context(error::ExternalSnafu)?;
+
+                let table = self
+                    .catalog_manager
+                    .table(&catalog, &schema, &view)
+                    .await
+                    .context(CatalogSnafu)?
+                    .context(TableNotFoundSnafu { table_name: &view })?;
+                ensure!(
+                    table.table_type() == TableType::View,
+                    error::InvalidSqlSnafu {
+                        err_msg: format!("{} is not a view", show.view_name),
+                    }
+                );
+
+                query::sql::show_create_view(table).context(error::ExecuteStatementSnafu)
+            }
             Statement::TruncateTable(stmt) => {
                 let (catalog, schema, table) =
                     table_idents_to_full_name(stmt.table_name(), query_ctx)
@@ -738,6 +797,14 @@ fn create_partitions_stmt(partitions: Vec<PartitionInfo>) -> Result<Option<Parti
         .map(|name| name[..].into())
         .collect();
 
+    if let Some(modulus) = partitions[0].partition.hash_modulus() {
+        return Ok(Some(Partitions {
+            column_list,
+            entries: vec![],
+            hash_partitions: Some(modulus),
+        }));
+    }
+
     let entries = partitions
         .into_iter()
         .map(|info| {
@@ -750,6 +817,11 @@ fn create_partitions_stmt(partitions: Vec<PartitionInfo>) -> Result<Option<Parti
                     PartitionBound::Value(v) => statements::value_to_sql_value(v)
                         .with_context(|_| error::ConvertSqlValueSnafu { value: v.clone() }),
                     PartitionBound::MaxValue => Ok(SqlValue::Number(MAX_VALUE.to_string(), false)),
+                    // Unreachable, hash partitions are returned above.
+                    PartitionBound::Hash { .. } => error::UnexpectedSnafu {
+                        violated: "mixed hash and range partition bounds".to_string(),
+                    }
+                    .fail(),
                 })
                 .collect::<Result<Vec<_>>>()?;
 
@@ -763,6 +835,7 @@ fn create_partitions_stmt(partitions: Vec<PartitionInfo>) -> Result<Option<Parti
     Ok(Some(Partitions {
         column_list,
         entries,
+        hash_partitions: None,
     }))
 }
 
@@ -815,6 +888,10 @@ fn create_table_info(
         })
         .collect::<Result<Vec<_>>>()?;
 
+    let mut options = TableOptions::try_from(&create_table.table_options)
+        .context(UnrecognizedTableOptionSnafu)?;
+    let view_definition = options.extra_options.remove(VIEW_DEFINITION_KEY);
+
     let meta = RawTableMeta {
         schema: raw_schema,
         primary_key_indices,
@@ -823,10 +900,10 @@ fn create_table_info(
         next_column_id: column_schemas.len() as u32,
         region_numbers: vec![],
         engine_options: HashMap::new(),
-        options: TableOptions::try_from(&create_table.table_options)
-            .context(UnrecognizedTableOptionSnafu)?,
+        options,
         created_on: DateTime::default(),
         partition_key_indices,
+        view_definition,
     };
 
     let desc = if create_table.desc.is_empty() {
@@ -846,7 +923,11 @@ fn create_table_info(
         catalog_name: create_table.catalog_name.clone(),
         schema_name: create_table.schema_name.clone(),
         meta,
-        table_type: TableType::Base,
+        table_type: if create_table.engine == VIEW_ENGINE {
+            TableType::View
+        } else {
+            TableType::Base
+        },
     };
     Ok(table_info)
 }
@@ -876,6 +957,13 @@ fn find_partition_entries(
     partition_columns: &[String],
 ) -> Result<Vec<Vec<PartitionBound>>> {
     let entries = if let Some(partitions) = partitions {
+        if let Some(modulus) = partitions.hash_partitions {
+            // Each region owns the rows whose hash value modulo `modulus` equals its remainder.
+            return Ok((0..modulus)
+                .map(|remainder| vec![PartitionBound::Hash { remainder, modulus }])
+                .collect());
+        }
+
         let column_defs = partition_columns
             .iter()
             .map(|pc| {
@@ -967,6 +1055,13 @@ PARTITION BY RANGE COLUMNS (b, a) (
 ENGINE=mito",
                 r#"[{"column_list":["b","a"],"value_list":["{\"Value\":{\"String\":\"hz\"}}","{\"Value\":{\"Int32\":10}}"]},{"column_list":["b","a"],"value_list":["{\"Value\":{\"String\":\"sh\"}}","{\"Value\":{\"Int32\":20}}"]},{"column_list":["b","a"],"value_list":["\"MaxValue\"","\"MaxValue\""]}]"#,
             ),
+            (
+                r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (b) PARTITIONS 2
+ENGINE=mito",
+                r#"[{"column_list":["b"],"value_list":["{\"Hash\":{\"remainder\":0,\"modulus\":2}}"]},{"column_list":["b"],"value_list":["{\"Hash\":{\"remainder\":1,\"modulus\":2}}"]}]"#,
+            ),
         ];
         for (sql, expected) in cases {
             let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
@@ -0,0 +1,290 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_query::Output;
+use common_recordbatch::RecordBatches;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::StringVector;
+use meta_client::client::MetaClient;
+use serde::Deserialize;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::statements::statement::Statement;
+use sqlparser::ast::{
+    Expr, Function, FunctionArg, FunctionArgExpr, Query as SpQuery, SelectItem, SetExpr,
+    Value as SqlValue, Visit, Visitor,
+};
+
+use crate::error::{self, Result};
+
+const NAME: &str = "migrate_region";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The arguments of `migrate_region`, in order.
+const ARGS: [&str; 4] = ["table_id", "region_number", "from_peer", "to_peer"];
+
+/// A call to `migrate_region(table_id, region_number, from_peer, to_peer)`.
+///
+/// It's not a function evaluated by the query engine. The call must be the only item of a
+/// `SELECT` without `FROM`, with integer literals as arguments, so that a statement submits
+/// exactly one migration, e.g. `SELECT migrate_region(1024, 1, 1, 2)`. Calls in any other
+/// statements, like views, are rejected.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct MigrateRegion {
+    args: [u64; 4],
+    /// The name of the column of the result.
+    column_name: String,
+}
+
+impl MigrateRegion {
+    /// Returns the call if `stmt` is a `migrate_region` call, or an error if `stmt` calls
+    /// `migrate_region` in any other way.
+    pub(crate) fn parse(stmt: &Statement) -> Result<Option<Self>> {
+        let calls_migrate_region = match stmt {
+            Statement::Query(query) => calls_migrate_region(&query.inner),
+            Statement::Explain(explain) => calls_migrate_region(&explain.inner),
+            Statement::Insert(insert) => calls_migrate_region(&insert.inner),
+            Statement::Delete(delete) => calls_migrate_region(&delete.inner),
+            Statement::CreateView(create) => calls_migrate_region(&create.query.inner),
+            Statement::CreateMaterializedView(create) => calls_migrate_region(&create.query.inner),
+            _ => false,
+        };
+        if !calls_migrate_region {
+            return Ok(None);
+        }
+
+        let (function, column_name) = match stmt {
+            Statement::Query(query) => migrate_region_call(&query.inner),
+            _ => None,
+        }
+        .with_context(|| error::InvalidSqlSnafu {
+            err_msg: format!(
+                "`{NAME}` can only be called alone, as `SELECT {NAME}({})`",
+                ARGS.join(", ")
+            ),
+        })?;
+
+        ensure!(
+            function.args.len() == ARGS.len(),
+            error::InvalidSqlSnafu {
+                err_msg: format!(
+                    "`{NAME}` expects exactly {} arguments, have: {}",
+                    ARGS.len(),
+                    function.args.len()
+                ),
+            }
+        );
+        let mut args = [0u64; 4];
+        for ((arg, value), name) in args.iter_mut().zip(&function.args).zip(ARGS) {
+            *arg = match value {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(SqlValue::Number(
+                    n,
+                    _,
+                )))) => n.parse().ok(),
+                _ => None,
+            }
+            .with_context(|| error::InvalidSqlSnafu {
+                err_msg: format!("`{name}` must be a non-negative integer literal"),
+            })?;
+        }
+        Ok(Some(Self { args, column_name }))
+    }
+}
+
+/// Returns the function and the name of the result if `query` is exactly
+/// `SELECT migrate_region(...) [AS name]`.
+fn migrate_region_call(query: &SpQuery) -> Option<(&Function, String)> {
+    if query.with.is_some()
+        || !query.order_by.is_empty()
+        || query.limit.is_some()
+        || query.offset.is_some()
+        || query.fetch.is_some()
+    {
+        return None;
+    }
+    let SetExpr::Select(select) = &*query.body else {
+        return None;
+    };
+    if !select.from.is_empty()
+        || select.selection.is_some()
+        || !select.group_by.is_empty()
+        || select.having.is_some()
+    {
+        return None;
+    }
+
+    let (expr, column_name) = match &select.projection[..] {
+        [SelectItem::UnnamedExpr(expr)] => (expr, expr.to_string()),
+        [SelectItem::ExprWithAlias { expr, alias }] => (expr, alias.value.clone()),
+        _ => return None,
+    };
+    match expr {
+        Expr::Function(function)
+            if is_migrate_region(function) && function.over.is_none() && !function.distinct =>
+        {
+            Some((function, column_name))
+        }
+        _ => None,
+    }
+}
+
+fn is_migrate_region(function: &Function) -> bool {
+    function
+        .name
+        .0
+        .last()
+        .map_or(false, |name| name.value.eq_ignore_ascii_case(NAME))
+}
+
+fn calls_migrate_region<V: Visit>(node: &V) -> bool {
+    struct Finder;
+
+    impl Visitor for Finder {
+        type Break = ();
+
+        fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+            match expr {
+                Expr::Function(function) if is_migrate_region(function) => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }
+        }
+    }
+
+    node.visit(&mut Finder).is_break()
+}
+
+pub(crate) type RegionMigratorRef = Arc<RegionMigrator>;
+
+/// Submits region migration procedures to Metasrv through its admin HTTP endpoint.
+pub(crate) struct RegionMigrator {
+    meta_client: Arc<MetaClient>,
+    client: reqwest::Client,
+}
+
+/// The reply of Metasrv to a submitted region migration.
+#[derive(Deserialize)]
+struct SubmitReply {
+    procedure_id: String,
+}
+
+impl RegionMigrator {
+    pub(crate) fn try_new(meta_client: Arc<MetaClient>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context(error::BuildHttpClientSnafu)?;
+        Ok(Self {
+            meta_client,
+            client,
+        })
+    }
+
+    /// Submits the migration of the call, and returns the id of the procedure in a one-row
+    /// result.
+    pub(crate) async fn migrate_region(&self, call: &MigrateRegion) -> Result<Output> {
+        let procedure_id = self.submit(&call.args).await?;
+
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            &call.column_name,
+            ConcreteDataType::string_datatype(),
+            false,
+        )]));
+        let records = RecordBatches::try_from_columns(
+            schema,
+            vec![Arc::new(StringVector::from(vec![procedure_id])) as _],
+        )
+        .context(error::CreateRecordBatchesSnafu)?;
+        Ok(Output::RecordBatches(records))
+    }
+
+    async fn submit(&self, args: &[u64; 4]) -> Result<String> {
+        let leader = self
+            .meta_client
+            .ask_leader()
+            .await
+            .context(error::RequestMetaSnafu)?;
+        let query = ARGS
+            .iter()
+            .zip(args)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!("http://{leader}/admin/region-migration?{query}");
+
+        let response = self
+            .client
+            .post(&url)
+            .send()
+            .await
+            .context(error::SubmitRegionMigrationSnafu { url: &url })?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .context(error::SubmitRegionMigrationSnafu { url: &url })?;
+        ensure!(
+            status.is_success(),
+            error::RegionMigrationRejectedSnafu { reason: body }
+        );
+
+        let reply: SubmitReply = serde_json::from_str(&body)
+            .context(error::InvalidRegionMigrationReplySnafu { body })?;
+        Ok(reply.procedure_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sql::dialect::GreptimeDbDialect;
+    use sql::parser::ParserContext;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Result<Option<MigrateRegion>> {
+        let stmt = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {})
+            .unwrap()
+            .remove(0);
+        MigrateRegion::parse(&stmt)
+    }
+
+    #[test]
+    fn test_parse_migrate_region() {
+        assert_eq!(None, parse("SELECT * FROM t").unwrap());
+        assert_eq!(
+            Some(MigrateRegion {
+                args: [1024, 1, 1, 2],
+                column_name: "procedure_id".to_string(),
+            }),
+            parse("SELECT MIGRATE_REGION(1024, 1, 1, 2) AS procedure_id").unwrap()
+        );
+
+        for sql in [
+            "SELECT migrate_region(1024, 1, 1, 2) FROM t",
+            "SELECT migrate_region(1024, 1, 1, 2), 1",
+            "SELECT * FROM t WHERE migrate_region(1024, 1, 1, 2) = ''",
+            "SELECT migrate_region(a, 1, 1, 2)",
+            "SELECT migrate_region(1024, -1, 1, 2)",
+            "SELECT migrate_region(1024, 1, 1)",
+            "CREATE VIEW v AS SELECT migrate_region(1024, 1, 1, 2)",
+            "DELETE FROM t WHERE migrate_region(1024, 1, 1, 2) = ''",
+            "INSERT INTO t SELECT migrate_region(1024, 1, 1, 2)",
+        ] {
+            assert!(parse(sql).is_err(), "{sql}");
+        }
+    }
+}
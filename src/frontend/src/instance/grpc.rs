@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use api::v1::ddl_request::Expr as DdlExpr;
 use api::v1::greptime_request::Request;
 use api::v1::query_request::Query;
 use async_trait::async_trait;
@@ -26,6 +27,7 @@ use snafu::{ensure, OptionExt, ResultExt};
 
 use crate::error::{Error, IncompleteGrpcResultSnafu, NotSupportedSnafu, PermissionSnafu, Result};
 use crate::instance::Instance;
+use crate::statement::check_table_options;
 
 #[async_trait]
 impl GrpcQueryHandler for Instance {
@@ -39,7 +41,12 @@ impl GrpcQueryHandler for Instance {
         self.plugins
             .get::<PermissionCheckerRef>()
             .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::GrpcRequest(&request))
+            .check_database_permission(
+                ctx.current_user(),
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                PermissionReq::GrpcRequest(&request),
+            )
             .context(PermissionSnafu)?;
 
         let output = match request {
@@ -91,7 +98,12 @@ impl GrpcQueryHandler for Instance {
                     }
                 }
             }
-            Request::Ddl(_) | Request::Deletes(_) => {
+            Request::Deletes(requests) => self.handle_deletes(requests, ctx.clone()).await?,
+            Request::Ddl(ddl) => {
+                if let Some(DdlExpr::CreateTable(expr)) = &ddl.expr {
+                    check_table_options(expr.table_options.keys().map(String::as_str))?;
+                }
+                let request = Request::Ddl(ddl);
                 GrpcQueryHandler::do_query(self.grpc_query_handler.as_ref(), request, ctx.clone())
                     .await?
             }
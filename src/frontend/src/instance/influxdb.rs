@@ -33,7 +33,12 @@ impl InfluxdbLineProtocolHandler for Instance {
         self.plugins
             .get::<PermissionCheckerRef>()
             .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::LineProtocol)
+            .check_database_permission(
+                ctx.current_user(),
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                PermissionReq::LineProtocol,
+            )
             .context(AuthSnafu)?;
 
         let requests = request.try_into()?;
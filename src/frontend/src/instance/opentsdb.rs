@@ -31,7 +31,12 @@ impl OpentsdbProtocolHandler for Instance {
         self.plugins
             .get::<PermissionCheckerRef>()
             .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::Opentsdb)
+            .check_database_permission(
+                ctx.current_user(),
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                PermissionReq::Opentsdb,
+            )
             .context(AuthSnafu)?;
 
         let requests = InsertRequests {
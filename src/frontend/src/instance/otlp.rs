@@ -16,9 +16,15 @@ use async_trait::async_trait;
 use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq};
 use common_error::ext::BoxedError;
 use metrics::counter;
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse,
+};
 use opentelemetry_proto::tonic::collector::metrics::v1::{
     ExportMetricsServiceRequest, ExportMetricsServiceResponse,
 };
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
 use servers::error::{self, AuthSnafu, Result as ServerResult};
 use servers::otlp;
 use servers::query_handler::OpenTelemetryProtocolHandler;
@@ -26,7 +32,7 @@ use session::context::QueryContextRef;
 use snafu::ResultExt;
 
 use crate::instance::Instance;
-use crate::metrics::OTLP_METRICS_ROWS;
+use crate::metrics::{OTLP_LOGS_ROWS, OTLP_METRICS_ROWS, OTLP_TRACES_ROWS};
 
 #[async_trait]
 impl OpenTelemetryProtocolHandler for Instance {
@@ -38,7 +44,12 @@ impl OpenTelemetryProtocolHandler for Instance {
         self.plugins
             .get::<PermissionCheckerRef>()
             .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::Otlp)
+            .check_database_permission(
+                ctx.current_user(),
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                PermissionReq::Otlp,
+            )
             .context(AuthSnafu)?;
         let (requests, rows) = otlp::to_grpc_insert_requests(request)?;
         let _ = self
@@ -55,4 +66,64 @@ impl OpenTelemetryProtocolHandler for Instance {
         };
         Ok(resp)
     }
+
+    async fn traces(
+        &self,
+        request: ExportTraceServiceRequest,
+        table_name: String,
+        ctx: QueryContextRef,
+    ) -> ServerResult<ExportTraceServiceResponse> {
+        self.plugins
+            .get::<PermissionCheckerRef>()
+            .as_ref()
+            .check_database_permission(
+                ctx.current_user(),
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                PermissionReq::Otlp,
+            )
+            .context(AuthSnafu)?;
+        let (requests, rows) = otlp::trace::to_grpc_insert_requests(request, &table_name)?;
+        let _ = self
+            .handle_inserts(requests, ctx)
+            .await
+            .map_err(BoxedError::new)
+            .context(error::ExecuteGrpcQuerySnafu)?;
+
+        counter!(OTLP_TRACES_ROWS, rows as u64);
+
+        Ok(ExportTraceServiceResponse {
+            partial_success: None,
+        })
+    }
+
+    async fn logs(
+        &self,
+        request: ExportLogsServiceRequest,
+        table_name: String,
+        ctx: QueryContextRef,
+    ) -> ServerResult<ExportLogsServiceResponse> {
+        self.plugins
+            .get::<PermissionCheckerRef>()
+            .as_ref()
+            .check_database_permission(
+                ctx.current_user(),
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                PermissionReq::Otlp,
+            )
+            .context(AuthSnafu)?;
+        let (requests, rows) = otlp::logs::to_grpc_insert_requests(request, &table_name)?;
+        let _ = self
+            .handle_inserts(requests, ctx)
+            .await
+            .map_err(BoxedError::new)
+            .context(error::ExecuteGrpcQuerySnafu)?;
+
+        counter!(OTLP_LOGS_ROWS, rows as u64);
+
+        Ok(ExportLogsServiceResponse {
+            partial_success: None,
+        })
+    }
 }
@@ -152,7 +152,12 @@ impl PromStoreProtocolHandler for Instance {
         self.plugins
             .get::<PermissionCheckerRef>()
             .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::PromStoreWrite)
+            .check_database_permission(
+                ctx.current_user(),
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                PermissionReq::PromStoreWrite,
+            )
             .context(AuthSnafu)?;
         let (requests, samples) = prom_store::to_grpc_insert_requests(request)?;
         let _ = self
@@ -173,7 +178,12 @@ impl PromStoreProtocolHandler for Instance {
         self.plugins
             .get::<PermissionCheckerRef>()
             .as_ref()
-            .check_permission(ctx.current_user(), PermissionReq::PromStoreRead)
+            .check_database_permission(
+                ctx.current_user(),
+                ctx.current_catalog(),
+                ctx.current_schema(),
+                PermissionReq::PromStoreRead,
+            )
             .context(AuthSnafu)?;
 
         let response_type = negotiate_response_type(&request.accepted_response_types)?;
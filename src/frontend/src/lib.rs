@@ -23,6 +23,7 @@ pub mod heartbeat;
 pub mod instance;
 pub(crate) mod metrics;
 mod row_inserter;
+pub mod rule;
 mod script;
 mod server;
 pub mod service_config;
@@ -0,0 +1,655 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Evaluation of Prometheus recording and alerting rules.
+//!
+//! Rule groups are loaded from the files in [RuleOptions] and each group is evaluated
+//! periodically through the PromQL engine of the frontend. Results of recording rules
+//! are written back in the same way as Prometheus remote write, while alerts of
+//! alerting rules are kept in memory and posted to the configured webhook.
+
+mod config;
+mod notifier;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use api::prom_store::remote::{Label, Sample, TimeSeries, WriteRequest};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use common_catalog::parse_catalog_and_schema_from_db_string;
+use common_error::ext::ErrorExt;
+use common_error::status_code::StatusCode;
+use common_query::Output;
+use common_recordbatch::RecordBatches;
+use common_runtime::JoinHandle;
+use common_telemetry::logging::{error, info};
+use common_time::util::current_time_millis;
+use lazy_static::lazy_static;
+use promql_parser::parser::ValueType;
+use query::parser::PromQuery;
+use regex::{Captures, Regex};
+use servers::error as server_error;
+use servers::prom_store::METRIC_NAME_LABEL;
+use servers::prometheus::{
+    Alert, AlertingRuleStatus, PromSeries, PrometheusHandler, PrometheusJsonResponse,
+    PrometheusResponse, RecordingRuleStatus, RuleGroup, RuleStatus,
+};
+use servers::query_handler::PromStoreProtocolHandler;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::ResultExt;
+
+pub use self::config::{RuleConfig, RuleFile, RuleGroupConfig};
+use self::notifier::{AlertNotification, Notifier};
+use crate::error::{EvaluateRuleSnafu, Result};
+use crate::instance::FrontendInstanceRef;
+use crate::service_config::RuleOptions;
+
+const ALERT_NAME_LABEL: &str = "alertname";
+const STATE_INACTIVE: &str = "inactive";
+const STATE_PENDING: &str = "pending";
+const STATE_FIRING: &str = "firing";
+
+lazy_static! {
+    /// Matches `{{ $value }}` and `{{ $labels.<name> }}` in annotations.
+    static ref TEMPLATE_REGEX: Regex =
+        Regex::new(r"\{\{\s*\$(value|labels\.([a-zA-Z_][a-zA-Z0-9_]*))\s*\}\}").unwrap();
+}
+
+pub type RuleManagerRef = Arc<RuleManager>;
+
+pub struct RuleManager {
+    db: String,
+    groups: Vec<Arc<Group>>,
+    notifier: Option<Arc<Notifier>>,
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl RuleManager {
+    /// Loads the rule files in `options`, does nothing until [RuleManager::start].
+    pub fn try_new(options: &RuleOptions) -> Result<Self> {
+        let mut groups = Vec::new();
+        for path in &options.rule_files {
+            let file = RuleFile::load(path)?;
+            for group in file.groups {
+                let interval = group.interval.unwrap_or(options.evaluation_interval);
+                groups.push(Arc::new(Group::new(path.clone(), group, interval)));
+            }
+        }
+
+        let notifier = options
+            .webhook_url
+            .clone()
+            .map(|url| Arc::new(Notifier::new(url, options.webhook_timeout)));
+
+        Ok(Self {
+            db: options.db.clone(),
+            groups,
+            notifier,
+            tasks: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Spawns a background task evaluating each rule group with `instance`.
+    pub fn start(&self, instance: FrontendInstanceRef) {
+        let (catalog, schema) = parse_catalog_and_schema_from_db_string(&self.db);
+        let query_ctx = QueryContext::with(catalog, schema);
+
+        let mut tasks = self.tasks.lock().unwrap();
+        for group in &self.groups {
+            let group = group.clone();
+            let instance = instance.clone();
+            let query_ctx = query_ctx.clone();
+            let notifier = self.notifier.clone();
+
+            let handle = common_runtime::spawn_bg(async move {
+                let mut interval = tokio::time::interval(group.interval);
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                loop {
+                    let _ = interval.tick().await;
+                    group
+                        .evaluate(&instance, &query_ctx, notifier.as_deref())
+                        .await;
+                }
+            });
+            tasks.push(handle);
+        }
+
+        if !self.groups.is_empty() {
+            info!("Started evaluating {} rule groups", self.groups.len());
+        }
+    }
+
+    pub fn stop(&self) {
+        for task in self.tasks.lock().unwrap().drain(..) {
+            task.abort();
+        }
+    }
+
+    pub fn rule_groups(&self) -> Vec<RuleGroup> {
+        self.groups.iter().map(|group| group.status()).collect()
+    }
+
+    pub fn alerts(&self) -> Vec<Alert> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.rules.iter())
+            .flat_map(|rule| rule.state.read().unwrap().alerts())
+            .collect()
+    }
+}
+
+struct Group {
+    name: String,
+    file: String,
+    interval: Duration,
+    rules: Vec<Rule>,
+    /// Time of the last evaluation in milliseconds and the time it took.
+    last_evaluation: RwLock<Option<(i64, Duration)>>,
+}
+
+impl Group {
+    fn new(file: String, config: RuleGroupConfig, interval: Duration) -> Self {
+        Self {
+            name: config.name,
+            file,
+            interval,
+            rules: config.rules.into_iter().map(Rule::new).collect(),
+            last_evaluation: RwLock::new(None),
+        }
+    }
+
+    async fn evaluate(
+        &self,
+        instance: &FrontendInstanceRef,
+        query_ctx: &QueryContextRef,
+        notifier: Option<&Notifier>,
+    ) {
+        let start = Instant::now();
+        let now = current_time_millis();
+
+        let mut notifications = Vec::new();
+        for rule in &self.rules {
+            notifications.extend(rule.evaluate(now, self.interval, instance, query_ctx).await);
+        }
+
+        if let Some(notifier) = notifier {
+            if let Err(e) = notifier.send(&notifications).await {
+                error!(e; "Failed to send alerts of rule group {}", self.name);
+            }
+        }
+
+        *self.last_evaluation.write().unwrap() = Some((now, start.elapsed()));
+    }
+
+    fn status(&self) -> RuleGroup {
+        let (last_evaluation, evaluation_time) = self.last_evaluation.read().unwrap().unzip();
+        RuleGroup {
+            name: self.name.clone(),
+            file: self.file.clone(),
+            rules: self.rules.iter().map(|rule| rule.status()).collect(),
+            interval: self.interval.as_secs_f64(),
+            evaluation_time: evaluation_time.unwrap_or_default().as_secs_f64(),
+            last_evaluation: last_evaluation.map(millis_to_rfc3339).unwrap_or_default(),
+        }
+    }
+}
+
+struct Rule {
+    config: RuleConfig,
+    state: RwLock<RuleState>,
+}
+
+#[derive(Default)]
+struct RuleState {
+    /// `None` if the rule has never been evaluated.
+    last_result: Option<std::result::Result<(), String>>,
+    last_evaluation: Option<i64>,
+    evaluation_time: Duration,
+    /// Active alerts keyed by their labels, only used by alerting rules.
+    active: BTreeMap<Vec<(String, String)>, ActiveAlert>,
+}
+
+struct ActiveAlert {
+    labels: HashMap<String, String>,
+    annotations: HashMap<String, String>,
+    value: f64,
+    active_at: i64,
+    /// Time the alert started firing, `None` if it's still pending.
+    fired_at: Option<i64>,
+}
+
+impl Rule {
+    fn new(config: RuleConfig) -> Self {
+        Self {
+            config,
+            state: RwLock::new(RuleState::default()),
+        }
+    }
+
+    /// Evaluates the rule at `now` and returns the alerts to notify.
+    async fn evaluate(
+        &self,
+        now: i64,
+        interval: Duration,
+        instance: &FrontendInstanceRef,
+        query_ctx: &QueryContextRef,
+    ) -> Vec<AlertNotification> {
+        let start = Instant::now();
+
+        let result = match self.query(now, instance, query_ctx).await {
+            Ok(samples) if self.config.is_alerting() => {
+                Ok(self.update_alerts(now, interval, samples))
+            }
+            Ok(samples) => self
+                .record(now, samples, instance, query_ctx)
+                .await
+                .map(|_| vec![]),
+            Err(e) => Err(e),
+        };
+
+        let mut state = self.state.write().unwrap();
+        state.last_evaluation = Some(now);
+        state.evaluation_time = start.elapsed();
+        match result {
+            Ok(notifications) => {
+                state.last_result = Some(Ok(()));
+                notifications
+            }
+            Err(e) => {
+                error!(e; "Failed to evaluate rule {}", self.config.name());
+                state.last_result = Some(Err(e.to_string()));
+                vec![]
+            }
+        }
+    }
+
+    /// Runs the expression as an instant query and returns its output labels and values.
+    async fn query(
+        &self,
+        now: i64,
+        instance: &FrontendInstanceRef,
+        query_ctx: &QueryContextRef,
+    ) -> Result<Vec<(HashMap<String, String>, f64)>> {
+        let time = format!("{}", now as f64 / 1000.0);
+        let query = PromQuery {
+            query: self.config.expr.clone(),
+            start: time.clone(),
+            end: time,
+            step: "1s".to_string(),
+        };
+
+        let series =
+            match PrometheusHandler::do_query(instance.as_ref(), &query, query_ctx.clone()).await {
+                Ok(output) => output_to_series(output).await,
+                // Like Prometheus, metrics that don't exist yet result in an empty vector.
+                Err(e) if e.status_code() == StatusCode::TableNotFound => Ok(vec![]),
+                Err(e) => Err(e),
+            }
+            .context(EvaluateRuleSnafu {
+                rule: self.config.name(),
+            })?;
+
+        Ok(series
+            .into_iter()
+            .filter_map(|series| {
+                let value = series.value?.1.parse::<f64>().ok()?;
+                Some((self.output_labels(series.metric), value))
+            })
+            .collect())
+    }
+
+    /// Drops the metric name of the series and applies labels of the rule.
+    fn output_labels(&self, mut labels: HashMap<String, String>) -> HashMap<String, String> {
+        let _ = labels.remove(METRIC_NAME_LABEL);
+        labels.extend(self.config.labels.clone());
+        labels
+    }
+
+    async fn record(
+        &self,
+        now: i64,
+        samples: Vec<(HashMap<String, String>, f64)>,
+        instance: &FrontendInstanceRef,
+        query_ctx: &QueryContextRef,
+    ) -> Result<()> {
+        if samples.is_empty() {
+            return Ok(());
+        }
+
+        let timeseries = samples
+            .into_iter()
+            .map(|(labels, value)| {
+                let mut labels = labels
+                    .into_iter()
+                    .map(|(name, value)| Label { name, value })
+                    .collect::<Vec<_>>();
+                labels.push(Label {
+                    name: METRIC_NAME_LABEL.to_string(),
+                    value: self.config.name().to_string(),
+                });
+                TimeSeries {
+                    labels,
+                    samples: vec![Sample {
+                        value,
+                        timestamp: now,
+                    }],
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let request = WriteRequest {
+            timeseries,
+            ..Default::default()
+        };
+
+        PromStoreProtocolHandler::write(instance.as_ref(), request, query_ctx.clone())
+            .await
+            .context(EvaluateRuleSnafu {
+                rule: self.config.name(),
+            })
+    }
+
+    /// Updates active alerts with the result of an evaluation, like Prometheus an alert
+    /// becomes firing once it has been pending for the `for` duration of the rule.
+    fn update_alerts(
+        &self,
+        now: i64,
+        interval: Duration,
+        samples: Vec<(HashMap<String, String>, f64)>,
+    ) -> Vec<AlertNotification> {
+        let for_millis = self.config.for_duration.unwrap_or_default().as_millis() as i64;
+        let mut state = self.state.write().unwrap();
+
+        let mut seen = HashSet::with_capacity(samples.len());
+        for (mut labels, value) in samples {
+            let _ = labels.insert(ALERT_NAME_LABEL.to_string(), self.config.name().to_string());
+            let annotations = self
+                .config
+                .annotations
+                .iter()
+                .map(|(k, v)| (k.clone(), expand_template(v, &labels, value)))
+                .collect();
+
+            let mut key = labels
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<Vec<_>>();
+            key.sort_unstable();
+            let _ = seen.insert(key.clone());
+
+            let alert = state.active.entry(key).or_insert_with(|| ActiveAlert {
+                labels,
+                annotations: HashMap::new(),
+                value,
+                active_at: now,
+                fired_at: None,
+            });
+            alert.annotations = annotations;
+            alert.value = value;
+        }
+
+        let mut notifications = Vec::new();
+        state.active.retain(|key, alert| {
+            if seen.contains(key) {
+                return true;
+            }
+            // Resolved alerts are notified once more so the receiver can close them.
+            if let Some(fired_at) = alert.fired_at {
+                notifications.push(alert.notification(fired_at, now));
+            }
+            false
+        });
+
+        // Firing alerts are resent on every evaluation and expire if not refreshed.
+        let ends_at = now + 4 * interval.as_millis() as i64;
+        for alert in state.active.values_mut() {
+            if alert.fired_at.is_none() && now - alert.active_at >= for_millis {
+                alert.fired_at = Some(now);
+            }
+            if let Some(fired_at) = alert.fired_at {
+                notifications.push(alert.notification(fired_at, ends_at));
+            }
+        }
+
+        notifications
+    }
+
+    fn status(&self) -> RuleStatus {
+        let state = self.state.read().unwrap();
+        let (health, last_error) = match &state.last_result {
+            None => ("unknown", None),
+            Some(Ok(())) => ("ok", None),
+            Some(Err(e)) => ("err", Some(e.clone())),
+        };
+        let health = health.to_string();
+        let evaluation_time = state.evaluation_time.as_secs_f64();
+        let last_evaluation = state
+            .last_evaluation
+            .map(millis_to_rfc3339)
+            .unwrap_or_default();
+
+        if self.config.is_alerting() {
+            let alerts = state.alerts();
+            let rule_state = if alerts.iter().any(|alert| alert.state == STATE_FIRING) {
+                STATE_FIRING
+            } else if alerts.is_empty() {
+                STATE_INACTIVE
+            } else {
+                STATE_PENDING
+            };
+            RuleStatus::Alerting(AlertingRuleStatus {
+                state: rule_state.to_string(),
+                name: self.config.name().to_string(),
+                query: self.config.expr.clone(),
+                duration: self.config.for_duration.unwrap_or_default().as_secs_f64(),
+                labels: self.config.labels.clone(),
+                annotations: self.config.annotations.clone(),
+                alerts,
+                health,
+                last_error,
+                evaluation_time,
+                last_evaluation,
+            })
+        } else {
+            RuleStatus::Recording(RecordingRuleStatus {
+                name: self.config.name().to_string(),
+                query: self.config.expr.clone(),
+                labels: self.config.labels.clone(),
+                health,
+                last_error,
+                evaluation_time,
+                last_evaluation,
+            })
+        }
+    }
+}
+
+impl RuleState {
+    fn alerts(&self) -> Vec<Alert> {
+        self.active
+            .values()
+            .map(|alert| Alert {
+                labels: alert.labels.clone(),
+                annotations: alert.annotations.clone(),
+                state: if alert.fired_at.is_some() {
+                    STATE_FIRING
+                } else {
+                    STATE_PENDING
+                }
+                .to_string(),
+                active_at: millis_to_rfc3339(alert.active_at),
+                value: alert.value.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl ActiveAlert {
+    fn notification(&self, starts_at: i64, ends_at: i64) -> AlertNotification {
+        AlertNotification {
+            labels: self.labels.clone(),
+            annotations: self.annotations.clone(),
+            starts_at: millis_to_rfc3339(starts_at),
+            ends_at: millis_to_rfc3339(ends_at),
+            generator_url: String::new(),
+        }
+    }
+}
+
+async fn output_to_series(output: Output) -> server_error::Result<Vec<PromSeries>> {
+    let batches = match output {
+        Output::RecordBatches(batches) => batches,
+        Output::Stream(stream) => RecordBatches::try_collect(stream)
+            .await
+            .context(server_error::CollectRecordbatchSnafu)?,
+        Output::AffectedRows(_) => return Ok(vec![]),
+    };
+    let data =
+        PrometheusJsonResponse::record_batches_to_data(batches, String::new(), ValueType::Vector)?;
+    match data {
+        PrometheusResponse::PromData(data) => Ok(data.result),
+        _ => Ok(vec![]),
+    }
+}
+
+/// Expands `{{ $value }}` and `{{ $labels.<name> }}` in `template`, the full Go
+/// template language used by Prometheus is not supported.
+fn expand_template(template: &str, labels: &HashMap<String, String>, value: f64) -> String {
+    TEMPLATE_REGEX
+        .replace_all(template, |caps: &Captures| match caps.get(2) {
+            Some(name) => labels.get(name.as_str()).cloned().unwrap_or_default(),
+            None => value.to_string(),
+        })
+        .into_owned()
+}
+
+fn millis_to_rfc3339(millis: i64) -> String {
+    Utc.timestamp_millis_opt(millis)
+        .single()
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alerting_rule(for_duration: Option<Duration>) -> Rule {
+        Rule::new(RuleConfig {
+            alert: Some("HighLoad".to_string()),
+            expr: "load > 1".to_string(),
+            for_duration,
+            labels: HashMap::from([("severity".to_string(), "page".to_string())]),
+            annotations: HashMap::from([(
+                "summary".to_string(),
+                "load of {{ $labels.host }} is {{$value}}".to_string(),
+            )]),
+            ..Default::default()
+        })
+    }
+
+    fn sample(host: &str, value: f64) -> (HashMap<String, String>, f64) {
+        (
+            HashMap::from([("host".to_string(), host.to_string())]),
+            value,
+        )
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let labels = HashMap::from([("host".to_string(), "host1".to_string())]);
+        assert_eq!(
+            "host1 is 1.5, {{ $other }}",
+            expand_template(
+                "{{ $labels.host }} is {{$value}}, {{ $other }}",
+                &labels,
+                1.5
+            )
+        );
+        assert_eq!(
+            " is 2",
+            expand_template("{{ $labels.idc }} is {{ $value }}", &labels, 2.0)
+        );
+    }
+
+    #[test]
+    fn test_alert_lifecycle() {
+        let interval = Duration::from_secs(10);
+        let rule = alerting_rule(Some(Duration::from_secs(20)));
+
+        // pending
+        let notifications = rule.update_alerts(0, interval, vec![sample("host1", 2.0)]);
+        assert!(notifications.is_empty());
+        let alerts = rule.state.read().unwrap().alerts();
+        assert_eq!(1, alerts.len());
+        assert_eq!(STATE_PENDING, alerts[0].state);
+        assert_eq!("HighLoad", alerts[0].labels[ALERT_NAME_LABEL]);
+        assert_eq!("page", alerts[0].labels["severity"]);
+        assert_eq!("load of host1 is 2", alerts[0].annotations["summary"]);
+
+        // still pending before `for` elapses
+        let notifications = rule.update_alerts(10_000, interval, vec![sample("host1", 3.0)]);
+        assert!(notifications.is_empty());
+
+        // firing
+        let notifications = rule.update_alerts(20_000, interval, vec![sample("host1", 3.0)]);
+        assert_eq!(1, notifications.len());
+        assert_eq!("1970-01-01T00:00:20.000Z", notifications[0].starts_at);
+        assert_eq!("1970-01-01T00:01:00.000Z", notifications[0].ends_at);
+        let RuleStatus::Alerting(status) = rule.status() else {
+            unreachable!()
+        };
+        assert_eq!(STATE_FIRING, status.state);
+        assert_eq!("1970-01-01T00:00:00.000Z", status.alerts[0].active_at);
+
+        // resolved
+        let notifications = rule.update_alerts(30_000, interval, vec![]);
+        assert_eq!(1, notifications.len());
+        assert_eq!("1970-01-01T00:00:30.000Z", notifications[0].ends_at);
+        assert!(rule.state.read().unwrap().alerts().is_empty());
+        let RuleStatus::Alerting(status) = rule.status() else {
+            unreachable!()
+        };
+        assert_eq!(STATE_INACTIVE, status.state);
+    }
+
+    #[test]
+    fn test_pending_alert_resolved_silently() {
+        let interval = Duration::from_secs(10);
+        let rule = alerting_rule(Some(Duration::from_secs(60)));
+
+        let _ = rule.update_alerts(
+            0,
+            interval,
+            vec![sample("host1", 2.0), sample("host2", 2.0)],
+        );
+        assert_eq!(2, rule.state.read().unwrap().alerts().len());
+
+        let notifications = rule.update_alerts(10_000, interval, vec![sample("host2", 2.0)]);
+        assert!(notifications.is_empty());
+        let alerts = rule.state.read().unwrap().alerts();
+        assert_eq!(1, alerts.len());
+        assert_eq!("host2", alerts[0].labels["host"]);
+    }
+
+    #[test]
+    fn test_alert_fires_immediately_without_for() {
+        let rule = alerting_rule(None);
+        let notifications =
+            rule.update_alerts(0, Duration::from_secs(10), vec![sample("host1", 2.0)]);
+        assert_eq!(1, notifications.len());
+        assert_eq!(STATE_FIRING, rule.state.read().unwrap().alerts()[0].state);
+    }
+}
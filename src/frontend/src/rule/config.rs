@@ -0,0 +1,198 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus-format rule files, see
+//! https://prometheus.io/docs/prometheus/latest/configuration/recording_rules/
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, ResultExt};
+
+use crate::error::{InvalidRuleSnafu, ParseRuleFileSnafu, ReadRuleFileSnafu, Result};
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleFile {
+    pub groups: Vec<RuleGroupConfig>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleGroupConfig {
+    pub name: String,
+    /// Falls back to the evaluation interval in options when absent.
+    #[serde(default, with = "humantime_serde")]
+    pub interval: Option<Duration>,
+    pub rules: Vec<RuleConfig>,
+}
+
+/// Either a recording rule, which has `record`, or an alerting rule, which has `alert`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleConfig {
+    pub record: Option<String>,
+    pub alert: Option<String>,
+    pub expr: String,
+    #[serde(default, rename = "for", with = "humantime_serde")]
+    pub for_duration: Option<Duration>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub annotations: HashMap<String, String>,
+}
+
+impl RuleFile {
+    pub fn load(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path).context(ReadRuleFileSnafu { path })?;
+        Self::parse(path, &content)
+    }
+
+    fn parse(path: &str, content: &str) -> Result<Self> {
+        let file: RuleFile = serde_yaml::from_str(content).context(ParseRuleFileSnafu { path })?;
+        for group in &file.groups {
+            group.validate()?;
+        }
+        Ok(file)
+    }
+}
+
+impl RuleGroupConfig {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.name.is_empty(),
+            InvalidRuleSnafu {
+                group: &self.name,
+                reason: "group name must not be empty",
+            }
+        );
+        for rule in &self.rules {
+            rule.validate().map_err(|reason| {
+                InvalidRuleSnafu {
+                    group: &self.name,
+                    reason,
+                }
+                .build()
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl RuleConfig {
+    /// Name of the rule, which is the recorded metric or the alert name.
+    pub fn name(&self) -> &str {
+        self.record
+            .as_deref()
+            .or(self.alert.as_deref())
+            .unwrap_or_default()
+    }
+
+    pub fn is_alerting(&self) -> bool {
+        self.alert.is_some()
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        match (&self.record, &self.alert) {
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "rule {} must not specify both record and alert",
+                    self.name()
+                ))
+            }
+            (None, None) => return Err("one of record and alert must be specified".to_string()),
+            (Some(record), None) => {
+                if self.for_duration.is_some() || !self.annotations.is_empty() {
+                    return Err(format!(
+                        "recording rule {record} must not specify for or annotations"
+                    ));
+                }
+            }
+            (None, Some(_)) => {}
+        }
+        if self.name().is_empty() {
+            return Err("rule name must not be empty".to_string());
+        }
+        promql_parser::parser::parse(&self.expr)
+            .map(|_| ())
+            .map_err(|e| format!("invalid expression of rule {}: {e}", self.name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rule_file() {
+        let content = r#"
+groups:
+  - name: example
+    interval: 30s
+    rules:
+      - record: job:http_requests:rate5m
+        expr: sum by (job) (rate(http_requests_total[5m]))
+        labels:
+          team: infra
+      - alert: HighRequestLatency
+        expr: job:request_latency_seconds:mean5m > 0.5
+        for: 10m
+        labels:
+          severity: page
+        annotations:
+          summary: High request latency on {{ $labels.job }}
+"#;
+        let file = RuleFile::parse("rules.yml", content).unwrap();
+        assert_eq!(file.groups.len(), 1);
+        let group = &file.groups[0];
+        assert_eq!(group.name, "example");
+        assert_eq!(group.interval, Some(Duration::from_secs(30)));
+        assert_eq!(group.rules[0].name(), "job:http_requests:rate5m");
+        assert!(!group.rules[0].is_alerting());
+        assert_eq!(group.rules[0].labels["team"], "infra");
+        assert_eq!(group.rules[1].name(), "HighRequestLatency");
+        assert!(group.rules[1].is_alerting());
+        assert_eq!(group.rules[1].for_duration, Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn test_invalid_rule_file() {
+        let both = r#"
+groups:
+  - name: example
+    rules:
+      - record: foo
+        alert: Foo
+        expr: up
+"#;
+        assert!(RuleFile::parse("rules.yml", both).is_err());
+
+        let invalid_expr = r#"
+groups:
+  - name: example
+    rules:
+      - record: foo
+        expr: sum(up
+"#;
+        assert!(RuleFile::parse("rules.yml", invalid_expr).is_err());
+
+        let recording_with_for = r#"
+groups:
+  - name: example
+    rules:
+      - record: foo
+        expr: up
+        for: 1m
+"#;
+        assert!(RuleFile::parse("rules.yml", recording_with_for).is_err());
+    }
+}
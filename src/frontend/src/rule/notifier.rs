@@ -0,0 +1,67 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::Serialize;
+use snafu::ResultExt;
+
+use crate::error::{Result, SendAlertsSnafu};
+
+/// An alert in the format of Alertmanager's `POST /api/v2/alerts`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertNotification {
+    pub labels: HashMap<String, String>,
+    pub annotations: HashMap<String, String>,
+    /// RFC 3339 time when the alert started firing.
+    pub starts_at: String,
+    /// RFC 3339 time when the alert is considered resolved.
+    pub ends_at: String,
+    #[serde(rename = "generatorURL")]
+    pub generator_url: String,
+}
+
+/// Posts alerts to a webhook.
+pub struct Notifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl Notifier {
+    pub fn new(url: String, timeout: Duration) -> Self {
+        // Building the client only fails if the TLS backend can't be initialized.
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_default();
+        Self { client, url }
+    }
+
+    pub async fn send(&self, alerts: &[AlertNotification]) -> Result<()> {
+        if alerts.is_empty() {
+            return Ok(());
+        }
+        let _ = self
+            .client
+            .post(&self.url)
+            .json(alerts)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .context(SendAlertsSnafu { url: &self.url })?;
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@ pub mod opentsdb;
 pub mod otlp;
 pub mod postgres;
 pub mod prom_store;
+pub mod rule;
 
 pub use grpc::GrpcOptions;
 pub use influxdb::InfluxdbOptions;
@@ -28,5 +29,6 @@ pub use opentsdb::OpentsdbOptions;
 pub use otlp::OtlpOptions;
 pub use postgres::PostgresOptions;
 pub use prom_store::PromStoreOptions;
+pub use rule::RuleOptions;
 
 pub use self::datanode::DatanodeOptions;
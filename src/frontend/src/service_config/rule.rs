@@ -0,0 +1,75 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_catalog::consts::DEFAULT_SCHEMA_NAME;
+use serde::{Deserialize, Serialize};
+
+/// Options of Prometheus rule evaluation. Rules are only evaluated in standalone mode,
+/// since frontends of a cluster don't coordinate which of them evaluates a rule.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RuleOptions {
+    /// Paths of Prometheus-format rule files, glob patterns are not supported.
+    pub rule_files: Vec<String>,
+    /// Evaluation interval of groups that don't specify their own.
+    #[serde(with = "humantime_serde")]
+    pub evaluation_interval: Duration,
+    /// Database the rules are evaluated in and recording results are written to.
+    pub db: String,
+    /// Alerts are posted to this url in Alertmanager's webhook format when set.
+    pub webhook_url: Option<String>,
+    /// Timeout of a webhook request.
+    #[serde(with = "humantime_serde")]
+    pub webhook_timeout: Duration,
+}
+
+impl Default for RuleOptions {
+    fn default() -> Self {
+        Self {
+            rule_files: vec![],
+            evaluation_interval: Duration::from_secs(60),
+            db: DEFAULT_SCHEMA_NAME.to_string(),
+            webhook_url: None,
+            webhook_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_options() {
+        let default = RuleOptions::default();
+        assert!(default.rule_files.is_empty());
+        assert!(default.webhook_url.is_none());
+
+        let toml_string = r#"
+            rule_files = ["/etc/greptimedb/rules.yml"]
+            evaluation_interval = "30s"
+            webhook_url = "http://127.0.0.1:9093/api/v1/alerts"
+        "#;
+        let opts: RuleOptions = toml::from_str(toml_string).unwrap();
+        assert_eq!(opts.rule_files, vec!["/etc/greptimedb/rules.yml"]);
+        assert_eq!(opts.evaluation_interval, Duration::from_secs(30));
+        assert_eq!(opts.db, DEFAULT_SCHEMA_NAME);
+        assert_eq!(
+            opts.webhook_url.as_deref(),
+            Some("http://127.0.0.1:9093/api/v1/alerts")
+        );
+    }
+}
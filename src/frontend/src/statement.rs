@@ -17,13 +17,17 @@ mod copy_table_from;
 mod copy_table_to;
 mod describe;
 mod dml;
+pub(crate) mod materialized_view;
+mod privilege;
 mod show;
 mod tql;
+mod view;
 
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use auth::RbacManagerRef;
 use catalog::CatalogManagerRef;
 use common_error::ext::BoxedError;
 use common_query::Output;
@@ -35,13 +39,15 @@ use query::plan::LogicalPlan;
 use query::query_engine::SqlStatementExecutorRef;
 use query::QueryEngineRef;
 use session::context::QueryContextRef;
-use snafu::{OptionExt, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::statements::alter::AlterTableOperation;
 use sql::statements::copy::{CopyDatabaseArgument, CopyTable, CopyTableArgument};
 use sql::statements::statement::Statement;
 use table::engine::TableReference;
 use table::error::TableOperationSnafu;
 use table::requests::{
-    CopyDatabaseRequest, CopyDirection, CopyTableRequest, DeleteRequest, InsertRequest,
+    is_private_table_option, CopyDatabaseRequest, CopyDirection, CopyTableRequest, DeleteRequest,
+    InsertRequest,
 };
 use table::TableRef;
 
@@ -53,12 +59,15 @@ use crate::error::{
 use crate::instance::distributed::deleter::DistDeleter;
 use crate::instance::distributed::inserter::DistInserter;
 use crate::statement::backup::{COPY_DATABASE_TIME_END_KEY, COPY_DATABASE_TIME_START_KEY};
+use crate::statement::materialized_view::{MaterializedViewManager, MaterializedViewManagerRef};
 
 #[derive(Clone)]
 pub struct StatementExecutor {
     catalog_manager: CatalogManagerRef,
     query_engine: QueryEngineRef,
     sql_stmt_executor: SqlStatementExecutorRef,
+    rbac_manager: Option<RbacManagerRef>,
+    materialized_views: MaterializedViewManagerRef,
 }
 
 impl StatementExecutor {
@@ -66,11 +75,14 @@ impl StatementExecutor {
         catalog_manager: CatalogManagerRef,
         query_engine: QueryEngineRef,
         sql_stmt_executor: SqlStatementExecutorRef,
+        rbac_manager: Option<RbacManagerRef>,
     ) -> Self {
         Self {
             catalog_manager,
             query_engine,
             sql_stmt_executor,
+            rbac_manager,
+            materialized_views: Arc::new(MaterializedViewManager::new()),
         }
     }
 
@@ -86,6 +98,8 @@ impl StatementExecutor {
     }
 
     pub async fn execute_sql(&self, stmt: Statement, query_ctx: QueryContextRef) -> Result<Output> {
+        check_statement_table_options(&stmt)?;
+
         match stmt {
             Statement::Query(_) | Statement::Explain(_) => {
                 self.plan_exec(QueryStatement::Sql(stmt), query_ctx).await
@@ -103,6 +117,10 @@ impl StatementExecutor {
 
             Statement::ShowTables(stmt) => self.show_tables(stmt, query_ctx).await,
 
+            Statement::ShowMaterializedViews(stmt) => {
+                self.show_materialized_views(stmt, query_ctx).await
+            }
+
             Statement::Copy(sql::statements::copy::Copy::CopyTable(stmt)) => {
                 let req = to_copy_table_request(stmt, query_ctx.clone())?;
                 match req.direction {
@@ -121,13 +139,29 @@ impl StatementExecutor {
                     .await
             }
 
+            Statement::CreateUser(stmt) => self.create_user(stmt).await,
+
+            Statement::CreateRole(stmt) => self.create_role(stmt).await,
+
+            Statement::Grant(stmt) => self.grant(stmt, query_ctx).await,
+
+            Statement::Revoke(stmt) => self.revoke(stmt, query_ctx).await,
+
+            Statement::CreateMaterializedView(stmt) => {
+                self.create_materialized_view(stmt, query_ctx).await
+            }
+
+            Statement::CreateView(stmt) => self.create_view(stmt, query_ctx).await,
+
             Statement::CreateDatabase(_)
             | Statement::CreateTable(_)
             | Statement::CreateExternalTable(_)
             | Statement::Alter(_)
             | Statement::DropTable(_)
+            | Statement::DropView(_)
             | Statement::TruncateTable(_)
-            | Statement::ShowCreateTable(_) => self
+            | Statement::ShowCreateTable(_)
+            | Statement::ShowCreateView(_) => self
                 .sql_stmt_executor
                 .execute_sql(stmt, query_ctx)
                 .await
@@ -249,6 +283,39 @@ impl StatementExecutor {
     }
 }
 
+/// Checks that the table options in a statement from users don't contain the options reserved
+/// for internal use.
+fn check_statement_table_options(stmt: &Statement) -> Result<()> {
+    match stmt {
+        Statement::CreateTable(create) => {
+            check_table_options(create.options.iter().map(|o| o.name.value.as_str()))
+        }
+        Statement::CreateExternalTable(create) => {
+            check_table_options(create.options.keys().map(String::as_str))
+        }
+        Statement::Alter(alter) => match alter.alter_operation() {
+            AlterTableOperation::SetTableOptions { options } => {
+                check_table_options(options.iter().map(|o| o.name.value.as_str()))
+            }
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Checks that none of the table option `keys` is reserved for internal use.
+pub(crate) fn check_table_options<'a>(keys: impl IntoIterator<Item = &'a str>) -> Result<()> {
+    for key in keys {
+        ensure!(
+            !is_private_table_option(&key.to_lowercase()),
+            error::InvalidSqlSnafu {
+                err_msg: format!("table option `{key}` is reserved"),
+            }
+        );
+    }
+    Ok(())
+}
+
 fn to_copy_table_request(stmt: CopyTable, query_ctx: QueryContextRef) -> Result<CopyTableRequest> {
     let direction = match stmt {
         CopyTable::To(_) => CopyDirection::Export,
@@ -326,3 +393,34 @@ fn extract_timestamp(map: &HashMap<String, String>, key: &str) -> Result<Option<
         })
         .transpose()
 }
+
+#[cfg(test)]
+mod tests {
+    use sql::dialect::GreptimeDbDialect;
+    use sql::parser::ParserContext;
+
+    use super::*;
+
+    fn check(sql: &str) -> Result<()> {
+        let stmt = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {})
+            .unwrap()
+            .remove(0);
+        check_statement_table_options(&stmt)
+    }
+
+    #[test]
+    fn test_check_statement_table_options() {
+        check("CREATE TABLE t (ts TIMESTAMP TIME INDEX) WITH (ttl='7d')").unwrap();
+        check("ALTER TABLE t SET ttl='7d'").unwrap();
+
+        let err = check(
+            r#"CREATE TABLE t (ts TIMESTAMP TIME INDEX)
+            WITH ("__private.materialized_view_query"='SELECT * FROM secret')"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("is reserved"), "{err}");
+        let err = check("ALTER TABLE t SET __private.materialized_view_owner='root'").unwrap_err();
+        assert!(err.to_string().contains("is reserved"), "{err}");
+        assert!(check("ALTER TABLE t SET __PRIVATE.x='root'").is_err());
+    }
+}
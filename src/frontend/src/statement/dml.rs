@@ -14,9 +14,12 @@
 
 use std::collections::HashMap;
 
+use common_error::ext::BoxedError;
 use common_query::Output;
 use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
 use datafusion_expr::{DmlStatement, LogicalPlan as DfLogicalPlan, WriteOp};
+use datanode::instance::sql::table_idents_to_full_name;
+use datanode::sql::SqlHandler;
 use datatypes::schema::SchemaRef;
 use futures_util::StreamExt;
 use query::parser::QueryStatement;
@@ -31,20 +34,52 @@ use table::metadata::TableInfoRef;
 use table::requests::{DeleteRequest, InsertRequest};
 use table::TableRef;
 
+use super::materialized_view::{merge_time_ranges, vector_time_range, Refresh};
 use super::StatementExecutor;
 use crate::error::{
-    BuildColumnVectorsSnafu, ExecLogicalPlanSnafu, ExecuteStatementSnafu,
-    MissingTimeIndexColumnSnafu, ReadRecordBatchSnafu, Result, UnexpectedSnafu,
+    BuildColumnVectorsSnafu, ExecLogicalPlanSnafu, ExecuteStatementSnafu, ExternalSnafu,
+    InvokeDatanodeSnafu, MissingTimeIndexColumnSnafu, ReadRecordBatchSnafu, Result,
+    UnexpectedSnafu,
 };
 
 impl StatementExecutor {
     pub async fn insert(&self, insert: Box<Insert>, query_ctx: QueryContextRef) -> Result<Output> {
+        let (catalog, schema, table) =
+            table_idents_to_full_name(insert.table_name(), query_ctx.clone())
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?;
+        let has_views = self.has_materialized_views(&catalog, &schema, &table);
+
         if insert.can_extract_values() {
             // Fast path: plain insert ("insert with literal values") is executed directly
-            self.sql_stmt_executor
+            let time_range = if has_views {
+                let request = SqlHandler::insert_to_request(
+                    self.catalog_manager.clone(),
+                    &insert,
+                    query_ctx.clone(),
+                )
+                .await
+                .context(InvokeDatanodeSnafu)?;
+                self.insert_time_range(&request).await
+            } else {
+                None
+            };
+
+            let output = self
+                .sql_stmt_executor
                 .execute_sql(Statement::Insert(insert), query_ctx)
                 .await
-                .context(ExecuteStatementSnafu)
+                .context(ExecuteStatementSnafu)?;
+
+            if let Some(time_range) = time_range {
+                self.refresh_materialized_views(
+                    &catalog,
+                    &schema,
+                    &table,
+                    Refresh::insert(time_range),
+                );
+            }
+            Ok(output)
         } else {
             // Slow path: insert with subquery. Execute the subquery first, via query engine. Then
             // insert the results by sending insert requests.
@@ -68,15 +103,31 @@ impl StatementExecutor {
 
             // 3. Send insert requests.
             let mut affected_rows = 0;
+            let mut time_range = None;
             let table = self.get_table_from_dml(dml_statement, &query_ctx).await?;
             let table_info = table.table_info();
             while let Some(batch) = stream.next().await {
                 let record_batch = batch.context(ReadRecordBatchSnafu)?;
                 let insert_request =
                     build_insert_request(record_batch, table.schema(), &table_info)?;
+                if has_views {
+                    time_range = merge_time_ranges(
+                        time_range,
+                        self.insert_time_range(&insert_request).await,
+                    );
+                }
                 affected_rows += self.send_insert_request(insert_request).await?;
             }
 
+            // 4. Refresh the materialized views reading from the table.
+            if let Some(time_range) = time_range {
+                self.refresh_materialized_views(
+                    &catalog,
+                    &schema,
+                    &table,
+                    Refresh::insert(time_range),
+                );
+            }
             Ok(Output::AffectedRows(affected_rows))
         }
     }
@@ -101,14 +152,37 @@ impl StatementExecutor {
 
         // 3. Send delete requests.
         let mut affected_rows = 0;
+        let mut time_range = None;
         let table = self.get_table_from_dml(dml_statement, &query_ctx).await?;
         let table_info = table.table_info();
+        let has_views = self.has_materialized_views(
+            &table_info.catalog_name,
+            &table_info.schema_name,
+            &table_info.name,
+        );
+        let time_index = table.schema().timestamp_column().map(|c| c.name.clone());
         while let Some(batch) = stream.next().await {
             let record_batch = batch.context(ReadRecordBatchSnafu)?;
             let delete_request = build_delete_request(record_batch, table.schema(), &table_info)?;
+            if has_views {
+                let deleted_range = time_index
+                    .as_ref()
+                    .and_then(|time_index| delete_request.key_column_values.get(time_index))
+                    .and_then(vector_time_range);
+                time_range = merge_time_ranges(time_range, deleted_range);
+            }
             affected_rows += self.send_delete_request(delete_request).await?;
         }
 
+        // 4. Refresh the materialized views reading from the table.
+        if let Some(time_range) = time_range {
+            self.refresh_materialized_views(
+                &table_info.catalog_name,
+                &table_info.schema_name,
+                &table_info.name,
+                Refresh::delete(time_range),
+            );
+        }
         Ok(Output::AffectedRows(affected_rows))
     }
 
@@ -163,7 +237,7 @@ fn extract_dml_statement(logical_plan: LogicalPlan) -> Result<DmlStatement> {
     }
 }
 
-fn build_insert_request(
+pub(super) fn build_insert_request(
     record_batch: RecordBatch,
     table_schema: SchemaRef,
     table_info: &TableInfoRef,
@@ -181,7 +255,7 @@ fn build_insert_request(
     })
 }
 
-fn build_delete_request(
+pub(super) fn build_delete_request(
     record_batch: RecordBatch,
     table_schema: SchemaRef,
     table_info: &TableInfoRef,
@@ -0,0 +1,1124 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Materialized views: tables kept up to date with the results of a RANGE query.
+//!
+//! A materialized view is stored as a regular table, the sink, with the view query in its
+//! [MATERIALIZED_VIEW_QUERY_KEY] option. Writes into the source table of the query only queue
+//! the time range of the written rows. A background task then computes the align slots in the
+//! range again and upserts them into the sink, so that writes don't wait for their views.
+//!
+//! The view is refreshed with the privileges of the user who created it, which is stored in
+//! the [MATERIALIZED_VIEW_OWNER_KEY] option. Both options are reserved, users can't set them.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use api::helper::pb_value_to_value_ref;
+use api::v1::{Column, ColumnDataType, Rows, SemanticType};
+use auth::{userinfo_by_name, PermissionChecker, PermissionCheckerRef, PermissionReq};
+use common_catalog::consts::MITO_ENGINE;
+use common_error::ext::BoxedError;
+use common_query::Output;
+use common_recordbatch::RecordBatches;
+use common_telemetry::{info, warn};
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use datafusion::datasource::DefaultTableSource;
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{
+    Column as DfColumn, OwnedTableReference, ScalarValue, TableReference as DfTableReference,
+};
+use datafusion_expr::{Expr, LogicalPlan as DfLogicalPlan, LogicalPlanBuilder};
+use datanode::instance::sql::table_idents_to_full_name;
+use datatypes::arrow::datatypes::{DataType as ArrowDataType, TimeUnit as ArrowTimeUnit};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::value::{Value, ValueRef};
+use datatypes::vectors::{StringVector, VectorRef};
+use futures_util::StreamExt;
+use moka::future::{Cache, CacheBuilder};
+use query::parser::{QueryLanguageParser, QueryStatement};
+use query::plan::LogicalPlan;
+use query::RangeSelect;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::{
+    ColumnDef, ColumnOption, ColumnOptionDef, Ident, ObjectName, SqlOption, TableConstraint,
+    Value as SqlValue,
+};
+use sql::statements::create::{CreateMaterializedView, CreateTable, TIME_INDEX};
+use sql::statements::show::ShowMaterializedViews;
+use sql::statements::statement::Statement;
+use table::engine::TableReference;
+use table::requests::{InsertRequest, MATERIALIZED_VIEW_OWNER_KEY, MATERIALIZED_VIEW_QUERY_KEY};
+use table::table::adapter::DfTableProviderAdapter;
+
+use super::dml::{build_delete_request, build_insert_request};
+use crate::error::{
+    self, BuildDfLogicalPlanSnafu, CatalogSnafu, CreateRecordBatchesSnafu, ExecLogicalPlanSnafu,
+    ExecuteStatementSnafu, ExternalSnafu, InvalidMaterializedViewSnafu, LoadMaterializedViewsSnafu,
+    PermissionSnafu, ReadRecordBatchSnafu, Result, TableAlreadyExistSnafu,
+};
+use crate::statement::StatementExecutor;
+
+/// Materialized views of a schema, keyed by the name of their source table.
+type SchemaMaterializedViews = Arc<HashMap<String, Vec<Arc<MaterializedView>>>>;
+
+pub(crate) type MaterializedViewManagerRef = Arc<MaterializedViewManager>;
+
+/// Tracks the materialized views and queues their refreshes.
+pub(crate) struct MaterializedViewManager {
+    /// Materialized views of each schema, keyed by `catalog.schema`.
+    views: Cache<String, SchemaMaterializedViews>,
+    queue: Mutex<RefreshQueue>,
+    /// States of the views refreshed by this frontend, keyed by their full names.
+    states: RwLock<HashMap<String, MaterializedViewState>>,
+}
+
+impl MaterializedViewManager {
+    pub(crate) fn new() -> Self {
+        Self {
+            // Views created by other frontends are discovered once the cached entry expires.
+            views: CacheBuilder::new(1024)
+                .time_to_live(Duration::from_secs(30))
+                .build(),
+            queue: Mutex::default(),
+            states: RwLock::default(),
+        }
+    }
+
+    /// Returns whether writes into the table may refresh some materialized views.
+    ///
+    /// It doesn't wait for the views of the schema to be loaded. If they are not cached yet,
+    /// the refresh is queued anyway and the views are loaded by the background task.
+    fn may_have_views(&self, catalog: &str, schema: &str, table: &str) -> bool {
+        self.views
+            .get(&schema_key(catalog, schema))
+            .map_or(true, |views| views.contains_key(table))
+    }
+
+    /// Queues a refresh of the views reading from `source`, returns whether a task has to be
+    /// spawned to drain the queue.
+    fn enqueue(&self, source: SourceTable, refresh: Refresh) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+        let _ = queue
+            .pending
+            .entry(source)
+            .and_modify(|pending| *pending = pending.merge(refresh))
+            .or_insert(refresh);
+        !std::mem::replace(&mut queue.draining, true)
+    }
+
+    /// Takes the queued refreshes, or marks the queue as drained if there is none.
+    fn dequeue(&self) -> Option<HashMap<SourceTable, Refresh>> {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.pending.is_empty() {
+            queue.draining = false;
+            None
+        } else {
+            Some(std::mem::take(&mut queue.pending))
+        }
+    }
+
+    fn state(&self, view: &str) -> MaterializedViewState {
+        self.states
+            .read()
+            .unwrap()
+            .get(view)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn set_state(&self, view: String, state: MaterializedViewState) {
+        let _ = self.states.write().unwrap().insert(view, state);
+    }
+}
+
+fn schema_key(catalog: &str, schema: &str) -> String {
+    format!("{catalog}.{schema}")
+}
+
+#[derive(Debug, Default)]
+struct RefreshQueue {
+    pending: HashMap<SourceTable, Refresh>,
+    /// Whether a task is draining the queue, refreshes queued meanwhile are done by that task.
+    draining: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SourceTable {
+    catalog: String,
+    schema: String,
+    table: String,
+}
+
+/// A refresh of the align slots affected by writes into the source table of a view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Refresh {
+    /// The time range of the written rows, in milliseconds.
+    time_range: (i64, i64),
+    /// Whether rows were deleted. Align slots without any row left have no results, so the
+    /// old results of the affected slots are deleted before computing them again.
+    deleted: bool,
+}
+
+impl Refresh {
+    pub(crate) fn insert(time_range: (i64, i64)) -> Self {
+        Self {
+            time_range,
+            deleted: false,
+        }
+    }
+
+    pub(crate) fn delete(time_range: (i64, i64)) -> Self {
+        Self {
+            time_range,
+            deleted: true,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            time_range: (
+                self.time_range.0.min(other.time_range.0),
+                self.time_range.1.max(other.time_range.1),
+            ),
+            deleted: self.deleted || other.deleted,
+        }
+    }
+}
+
+/// The state of a materialized view, shown by `SHOW MATERIALIZED VIEWS`.
+#[derive(Debug, Clone, Default)]
+enum MaterializedViewState {
+    #[default]
+    Active,
+    /// The last refresh failed. The failed refresh is done again with the next one.
+    Failed { error: String, retry: Refresh },
+}
+
+/// A materialized view, whose results are stored in the table of the same name.
+#[derive(Debug)]
+pub(crate) struct MaterializedView {
+    catalog: String,
+    schema: String,
+    name: String,
+    query: String,
+    /// The user who created the view, `None` if it was created without authentication.
+    owner: Option<String>,
+    /// The parsed query, to check the privileges of the owner before refreshes.
+    stmt: Statement,
+    /// The plan of the query, compiled once when the view is loaded.
+    plan: DfLogicalPlan,
+    range_query: RangeQuery,
+}
+
+impl MaterializedView {
+    fn full_name(&self) -> String {
+        TableReference::full(&self.catalog, &self.schema, &self.name).to_string()
+    }
+
+    fn query_ctx(&self) -> QueryContextRef {
+        owner_query_ctx(&self.catalog, &self.schema, self.owner.as_deref())
+    }
+}
+
+/// Returns the context to run the query of a view in `catalog.schema` as its `owner`.
+fn owner_query_ctx(catalog: &str, schema: &str, owner: Option<&str>) -> QueryContextRef {
+    let query_ctx = QueryContext::with(catalog, schema);
+    query_ctx.set_current_user(owner.map(|owner| userinfo_by_name(Some(owner.to_string()))));
+    query_ctx
+}
+
+/// The parts of a materialized view query needed to maintain it incrementally.
+///
+/// The query must be a RANGE query over a single table in the schema of the view.
+#[derive(Debug)]
+struct RangeQuery {
+    source: String,
+    time_index: String,
+    time_index_type: ArrowDataType,
+    /// The align of the query, in milliseconds.
+    align: i64,
+    /// The largest range of the range expressions, in milliseconds.
+    range: i64,
+    by: Vec<String>,
+}
+
+impl RangeQuery {
+    fn try_new(view: &str, catalog: &str, schema: &str, plan: &DfLogicalPlan) -> Result<Self> {
+        let mut range_selects = Vec::new();
+        let mut sources = Vec::new();
+        collect_range_selects_and_sources(plan, &mut range_selects, &mut sources);
+
+        let invalid = |reason: &str| InvalidMaterializedViewSnafu {
+            view,
+            reason: reason.to_string(),
+        };
+        ensure!(
+            range_selects.len() == 1,
+            invalid("the query must contain exactly one RANGE query")
+        );
+        ensure!(
+            sources.len() == 1,
+            invalid("the query must read from exactly one table")
+        );
+        let range_select = range_selects[0];
+        ensure!(
+            range_select.range_expr.iter().all(|r| r.fill.is_none()),
+            invalid("FILL is not supported in materialized views")
+        );
+
+        let source = sources[0].clone().resolve(catalog, schema);
+        ensure!(
+            source.catalog == catalog && source.schema == schema,
+            invalid("the source table must be in the same database as the view")
+        );
+
+        let time_index = range_select.time_index.clone();
+        let time_index_type = plan
+            .schema()
+            .field_with_unqualified_name(&time_index)
+            .ok()
+            .with_context(|| {
+                invalid(&format!(
+                    "the query must select the time index `{time_index}`"
+                ))
+            })?
+            .data_type()
+            .clone();
+        let range = range_select
+            .range_expr
+            .iter()
+            .map(|r| r.range)
+            .max()
+            .unwrap_or_default();
+
+        Ok(Self {
+            source: source.table.to_string(),
+            time_index,
+            time_index_type,
+            align: range_select.align.as_millis() as i64,
+            range: range.as_millis() as i64,
+            by: range_select
+                .by_schema
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect(),
+        })
+    }
+
+    /// Returns the first and the last align slots affected by rows written in `[min, max]`
+    /// (in milliseconds).
+    fn slots(&self, min: i64, max: i64) -> (i64, i64) {
+        // A row at `ts` is aggregated into the align slot `t` iff `t - range < ts <= t`.
+        (
+            align_ceil(min, self.align),
+            align_floor(max + self.range - 1, self.align),
+        )
+    }
+
+    /// Restricts `plan` to the align slots affected by rows written in `[min, max]`
+    /// (in milliseconds).
+    fn restrict(&self, plan: DfLogicalPlan, min: i64, max: i64) -> Result<DfLogicalPlan> {
+        let (first_slot, last_slot) = self.slots(min, max);
+        let source_filter = self
+            .time_index_col()
+            .gt(self.time_index_lit(first_slot - self.range, false)?)
+            .and(
+                self.time_index_col()
+                    .lt(self.time_index_lit(last_slot + 1, true)?),
+            );
+        let plan = plan
+            .transform_up(&|plan| match plan {
+                DfLogicalPlan::TableScan(_) => Ok(Transformed::Yes(
+                    LogicalPlanBuilder::from(plan)
+                        .filter(source_filter.clone())?
+                        .build()?,
+                )),
+                _ => Ok(Transformed::No(plan)),
+            })
+            .context(BuildDfLogicalPlanSnafu)?;
+
+        LogicalPlanBuilder::from(plan)
+            .filter(self.slot_filter(first_slot, last_slot)?)
+            .and_then(|builder| builder.build())
+            .context(BuildDfLogicalPlanSnafu)
+    }
+
+    /// Returns the filter of the results in the align slots `[first_slot, last_slot]`.
+    fn slot_filter(&self, first_slot: i64, last_slot: i64) -> Result<Expr> {
+        Ok(self
+            .time_index_col()
+            .gt_eq(self.time_index_lit(first_slot, true)?)
+            .and(
+                self.time_index_col()
+                    .lt(self.time_index_lit(last_slot + 1, true)?),
+            ))
+    }
+
+    fn time_index_col(&self) -> Expr {
+        Expr::Column(DfColumn::from_name(&self.time_index))
+    }
+
+    /// Converts `millis` to a literal of the time index type, rounding up or down.
+    fn time_index_lit(&self, millis: i64, ceil: bool) -> Result<Expr> {
+        let ArrowDataType::Timestamp(unit, tz) = &self.time_index_type else {
+            return error::UnexpectedSnafu {
+                violated: format!("time index of type {:?}", self.time_index_type),
+            }
+            .fail();
+        };
+        let millis = Timestamp::new_millisecond(millis);
+        let ts = if ceil {
+            millis.convert_to_ceil(unit.into())
+        } else {
+            millis.convert_to(unit.into())
+        }
+        .map(|ts| ts.value());
+        let tz = tz.clone();
+        let value = match unit {
+            ArrowTimeUnit::Second => ScalarValue::TimestampSecond(ts, tz),
+            ArrowTimeUnit::Millisecond => ScalarValue::TimestampMillisecond(ts, tz),
+            ArrowTimeUnit::Microsecond => ScalarValue::TimestampMicrosecond(ts, tz),
+            ArrowTimeUnit::Nanosecond => ScalarValue::TimestampNanosecond(ts, tz),
+        };
+        Ok(Expr::Literal(value))
+    }
+}
+
+fn collect_range_selects_and_sources<'a>(
+    plan: &'a DfLogicalPlan,
+    range_selects: &mut Vec<&'a RangeSelect>,
+    sources: &mut Vec<&'a OwnedTableReference>,
+) {
+    match plan {
+        DfLogicalPlan::Extension(extension) => {
+            if let Some(range_select) = extension.node.as_any().downcast_ref::<RangeSelect>() {
+                range_selects.push(range_select);
+            }
+        }
+        DfLogicalPlan::TableScan(scan) => sources.push(&scan.table_name),
+        _ => {}
+    }
+    for input in plan.inputs() {
+        collect_range_selects_and_sources(input, range_selects, sources);
+    }
+}
+
+fn align_floor(ts: i64, align: i64) -> i64 {
+    ts.div_euclid(align) * align
+}
+
+fn align_ceil(ts: i64, align: i64) -> i64 {
+    align_floor(ts + align - 1, align)
+}
+
+impl StatementExecutor {
+    pub(super) async fn create_materialized_view(
+        &self,
+        stmt: CreateMaterializedView,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let (catalog, schema, name) = table_idents_to_full_name(&stmt.name, query_ctx.clone())
+            .map_err(BoxedError::new)
+            .context(ExternalSnafu)?;
+        let exists = self
+            .catalog_manager
+            .table_exist(&catalog, &schema, &name)
+            .await
+            .context(CatalogSnafu)?;
+        if exists {
+            ensure!(
+                stmt.if_not_exists,
+                TableAlreadyExistSnafu {
+                    table: TableReference::full(&catalog, &schema, &name).to_string(),
+                }
+            );
+            return Ok(Output::AffectedRows(0));
+        }
+
+        let owner = query_ctx
+            .current_user()
+            .map(|user| user.username().to_string());
+        let view = self
+            .plan_materialized_view(catalog, schema, name, stmt.query.to_string(), owner)
+            .await?;
+        let create_table = sink_table_stmt(&view)?;
+        let _ = self
+            .sql_stmt_executor
+            .execute_sql(Statement::CreateTable(create_table), query_ctx)
+            .await
+            .context(ExecuteStatementSnafu)?;
+        self.materialized_views
+            .views
+            .invalidate(&schema_key(&view.catalog, &view.schema))
+            .await;
+        info!(
+            "Created materialized view {} on {}",
+            view.full_name(),
+            view.range_query.source
+        );
+
+        // Backfills the view with the data already in the source table.
+        let affected_rows = self
+            .write_materialized_view(&view, view.plan.clone())
+            .await?;
+        Ok(Output::AffectedRows(affected_rows))
+    }
+
+    pub(super) async fn show_materialized_views(
+        &self,
+        stmt: ShowMaterializedViews,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let catalog = query_ctx.current_catalog();
+        let schema = stmt
+            .database
+            .as_deref()
+            .unwrap_or_else(|| query_ctx.current_schema());
+        let mut views = self
+            .schema_materialized_views(catalog, schema)
+            .await?
+            .values()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        views.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        let states = views
+            .iter()
+            .map(|view| self.materialized_views.state(&view.full_name()))
+            .collect::<Vec<_>>();
+        let column_schemas = vec![
+            ColumnSchema::new(
+                "Materialized_views",
+                ConcreteDataType::string_datatype(),
+                false,
+            ),
+            ColumnSchema::new("Source_table", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("State", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new("Error", ConcreteDataType::string_datatype(), true),
+        ];
+        let columns = vec![
+            Arc::new(StringVector::from(
+                views
+                    .iter()
+                    .map(|view| view.name.as_str())
+                    .collect::<Vec<_>>(),
+            )) as VectorRef,
+            Arc::new(StringVector::from(
+                views
+                    .iter()
+                    .map(|view| view.range_query.source.as_str())
+                    .collect::<Vec<_>>(),
+            )),
+            Arc::new(StringVector::from(
+                states
+                    .iter()
+                    .map(|state| match state {
+                        MaterializedViewState::Active => "active",
+                        MaterializedViewState::Failed { .. } => "failed",
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            Arc::new(StringVector::from(
+                states
+                    .iter()
+                    .map(|state| match state {
+                        MaterializedViewState::Active => None,
+                        MaterializedViewState::Failed { error, .. } => Some(error.as_str()),
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+        ];
+        let records =
+            RecordBatches::try_from_columns(Arc::new(Schema::new(column_schemas)), columns)
+                .context(CreateRecordBatchesSnafu)?;
+        Ok(Output::RecordBatches(records))
+    }
+
+    /// Returns whether writes into the table may refresh some materialized views, in which
+    /// case the time range of the written rows has to be passed to
+    /// [StatementExecutor::refresh_materialized_views].
+    pub(crate) fn has_materialized_views(&self, catalog: &str, schema: &str, table: &str) -> bool {
+        self.materialized_views
+            .may_have_views(catalog, schema, table)
+    }
+
+    /// Queues a refresh of the materialized views reading from the table, after rows were
+    /// written into it. The views are refreshed in the background.
+    pub(crate) fn refresh_materialized_views(
+        &self,
+        catalog: &str,
+        schema: &str,
+        table: &str,
+        refresh: Refresh,
+    ) {
+        let source = SourceTable {
+            catalog: catalog.to_string(),
+            schema: schema.to_string(),
+            table: table.to_string(),
+        };
+        if self.materialized_views.enqueue(source, refresh) {
+            let executor = self.clone();
+            let _handle = common_runtime::spawn_bg(async move {
+                executor.drain_refresh_queue().await;
+            });
+        }
+    }
+
+    /// Returns the time range of the rows in `request`, if the table it writes into is the
+    /// source of some materialized views.
+    pub(crate) async fn insert_time_range(&self, request: &InsertRequest) -> Option<(i64, i64)> {
+        let table_ref = TableReference::full(
+            &request.catalog_name,
+            &request.schema_name,
+            &request.table_name,
+        );
+        let table = self.get_table(&table_ref).await.ok()?;
+        let time_index = table.schema().timestamp_column()?.name.clone();
+        vector_time_range(request.columns_values.get(&time_index)?)
+    }
+
+    async fn drain_refresh_queue(&self) {
+        while let Some(pending) = self.materialized_views.dequeue() {
+            for (source, refresh) in pending {
+                let views = match self
+                    .schema_materialized_views(&source.catalog, &source.schema)
+                    .await
+                {
+                    Ok(views) => views,
+                    Err(e) => {
+                        warn!("Skip refreshing materialized views, error: {e}");
+                        continue;
+                    }
+                };
+                for view in views.get(&source.table).into_iter().flatten() {
+                    self.refresh_materialized_view(view, refresh).await;
+                }
+            }
+        }
+    }
+
+    async fn refresh_materialized_view(&self, view: &MaterializedView, refresh: Refresh) {
+        let full_name = view.full_name();
+        let refresh = match self.materialized_views.state(&full_name) {
+            MaterializedViewState::Active => refresh,
+            MaterializedViewState::Failed { retry, .. } => refresh.merge(retry),
+        };
+        let (min, max) = refresh.time_range;
+        let result: Result<usize> = async {
+            // The privileges of the owner may have been revoked since the view was loaded.
+            self.check_owner_permission(
+                &view.catalog,
+                &view.schema,
+                view.owner.as_deref(),
+                &view.stmt,
+            )?;
+            let plan = view.range_query.restrict(view.plan.clone(), min, max)?;
+            if refresh.deleted {
+                let _ = self.clear_materialized_view(view, min, max).await?;
+            }
+            self.write_materialized_view(view, plan).await
+        }
+        .await;
+
+        let state = match result {
+            Ok(_) => MaterializedViewState::Active,
+            Err(e) => {
+                warn!("Failed to refresh materialized view {full_name}, error: {e}");
+                // Plans the query again in case the source table was altered.
+                self.materialized_views
+                    .views
+                    .invalidate(&schema_key(&view.catalog, &view.schema))
+                    .await;
+                MaterializedViewState::Failed {
+                    error: e.to_string(),
+                    retry: refresh,
+                }
+            }
+        };
+        self.materialized_views.set_state(full_name, state);
+    }
+
+    async fn schema_materialized_views(
+        &self,
+        catalog: &str,
+        schema: &str,
+    ) -> Result<SchemaMaterializedViews> {
+        self.materialized_views
+            .views
+            .try_get_with(
+                schema_key(catalog, schema),
+                self.load_materialized_views(catalog, schema),
+            )
+            .await
+            .map_err(|e| {
+                LoadMaterializedViewsSnafu {
+                    catalog,
+                    schema,
+                    err_msg: e.to_string(),
+                }
+                .build()
+            })
+    }
+
+    async fn load_materialized_views(
+        &self,
+        catalog: &str,
+        schema: &str,
+    ) -> Result<SchemaMaterializedViews> {
+        let mut views = HashMap::<_, Vec<_>>::new();
+        let table_names = self
+            .catalog_manager
+            .table_names(catalog, schema)
+            .await
+            .context(CatalogSnafu)?;
+        for name in table_names {
+            let Some(table) = self
+                .catalog_manager
+                .table(catalog, schema, &name)
+                .await
+                .context(CatalogSnafu)?
+            else {
+                continue;
+            };
+            let table_info = table.table_info();
+            let options = &table_info.meta.options.extra_options;
+            let Some(query) = options.get(MATERIALIZED_VIEW_QUERY_KEY) else {
+                continue;
+            };
+
+            let view = self
+                .plan_materialized_view(
+                    catalog.to_string(),
+                    schema.to_string(),
+                    name.clone(),
+                    query.clone(),
+                    options.get(MATERIALIZED_VIEW_OWNER_KEY).cloned(),
+                )
+                .await;
+            match view {
+                Ok(view) => views
+                    .entry(view.range_query.source.clone())
+                    .or_default()
+                    .push(Arc::new(view)),
+                Err(e) => warn!("Skip materialized view {catalog}.{schema}.{name}, error: {e}"),
+            }
+        }
+        Ok(Arc::new(views))
+    }
+
+    async fn plan_materialized_view(
+        &self,
+        catalog: String,
+        schema: String,
+        name: String,
+        query: String,
+        owner: Option<String>,
+    ) -> Result<MaterializedView> {
+        let QueryStatement::Sql(stmt) =
+            QueryLanguageParser::parse_sql(&query).context(error::ParseQuerySnafu)?
+        else {
+            return InvalidMaterializedViewSnafu {
+                view: name,
+                reason: "the query must be a SQL query",
+            }
+            .fail();
+        };
+        self.check_owner_permission(&catalog, &schema, owner.as_deref(), &stmt)?;
+
+        let query_ctx = owner_query_ctx(&catalog, &schema, owner.as_deref());
+        let LogicalPlan::DfPlan(plan) = self
+            .plan(QueryStatement::Sql(stmt.clone()), query_ctx)
+            .await?;
+        let range_query = RangeQuery::try_new(&name, &catalog, &schema, &plan)?;
+        Ok(MaterializedView {
+            catalog,
+            schema,
+            name,
+            query,
+            owner,
+            stmt,
+            plan,
+            range_query,
+        })
+    }
+
+    /// Checks that the `owner` of a view in `catalog.schema` can read everything the query
+    /// `stmt` of the view reads.
+    fn check_owner_permission(
+        &self,
+        catalog: &str,
+        schema: &str,
+        owner: Option<&str>,
+        stmt: &Statement,
+    ) -> Result<()> {
+        let checker = self
+            .rbac_manager
+            .clone()
+            .map(|manager| manager as PermissionCheckerRef);
+        let _ = checker
+            .as_ref()
+            .check_database_permission(
+                owner.map(|owner| userinfo_by_name(Some(owner.to_string()))),
+                catalog,
+                schema,
+                PermissionReq::SqlStatement(stmt),
+            )
+            .context(PermissionSnafu)?;
+        Ok(())
+    }
+
+    /// Executes `plan` and upserts the results into the table of `view`.
+    async fn write_materialized_view(
+        &self,
+        view: &MaterializedView,
+        plan: DfLogicalPlan,
+    ) -> Result<usize> {
+        let output = self
+            .query_engine
+            .execute(LogicalPlan::DfPlan(plan), view.query_ctx())
+            .await
+            .context(ExecLogicalPlanSnafu)?;
+        let mut stream = match output {
+            Output::Stream(stream) => stream,
+            Output::RecordBatches(record_batches) => record_batches.as_stream(),
+            Output::AffectedRows(_) => {
+                return error::UnexpectedSnafu {
+                    violated: "expected a stream",
+                }
+                .fail()
+            }
+        };
+
+        let table = self
+            .get_table(&TableReference::full(
+                &view.catalog,
+                &view.schema,
+                &view.name,
+            ))
+            .await?;
+        let table_info = table.table_info();
+        let mut affected_rows = 0;
+        while let Some(batch) = stream.next().await {
+            let record_batch = batch.context(ReadRecordBatchSnafu)?;
+            if record_batch.num_rows() == 0 {
+                continue;
+            }
+            let insert_request = build_insert_request(record_batch, table.schema(), &table_info)?;
+            affected_rows += self.send_insert_request(insert_request).await?;
+        }
+        Ok(affected_rows)
+    }
+
+    /// Deletes the results of `view` in the align slots affected by rows in `[min, max]`
+    /// (in milliseconds).
+    async fn clear_materialized_view(
+        &self,
+        view: &MaterializedView,
+        min: i64,
+        max: i64,
+    ) -> Result<usize> {
+        let table_ref = TableReference::full(&view.catalog, &view.schema, &view.name);
+        let table = self.get_table(&table_ref).await?;
+        let table_info = table.table_info();
+        let table_schema = table.schema();
+
+        let (first_slot, last_slot) = view.range_query.slots(min, max);
+        let slot_filter = view.range_query.slot_filter(first_slot, last_slot)?;
+        let table_source = Arc::new(DefaultTableSource::new(Arc::new(
+            DfTableProviderAdapter::new(table),
+        )));
+        let plan = LogicalPlanBuilder::scan(
+            DfTableReference::from(table_ref).to_owned_reference(),
+            table_source,
+            None,
+        )
+        .and_then(|builder| builder.filter(slot_filter)?.build())
+        .context(BuildDfLogicalPlanSnafu)?;
+
+        let output = self
+            .query_engine
+            .execute(LogicalPlan::DfPlan(plan), view.query_ctx())
+            .await
+            .context(ExecLogicalPlanSnafu)?;
+        let mut stream = match output {
+            Output::Stream(stream) => stream,
+            Output::RecordBatches(record_batches) => record_batches.as_stream(),
+            Output::AffectedRows(_) => {
+                return error::UnexpectedSnafu {
+                    violated: "expected a stream",
+                }
+                .fail()
+            }
+        };
+
+        let mut affected_rows = 0;
+        while let Some(batch) = stream.next().await {
+            let record_batch = batch.context(ReadRecordBatchSnafu)?;
+            if record_batch.num_rows() == 0 {
+                continue;
+            }
+            let delete_request =
+                build_delete_request(record_batch, table_schema.clone(), &table_info)?;
+            affected_rows += self.send_delete_request(delete_request).await?;
+        }
+        Ok(affected_rows)
+    }
+}
+
+/// Builds the `CREATE TABLE` statement of the table storing the results of `view`.
+///
+/// The time index of the query becomes the time index of the table and the `BY` columns
+/// become its primary key, so that refreshed align slots overwrite the old results.
+fn sink_table_stmt(view: &MaterializedView) -> Result<CreateTable> {
+    let plan = &view.plan;
+    let range_query = &view.range_query;
+    let mut columns = Vec::with_capacity(plan.schema().fields().len());
+    let mut primary_keys = Vec::with_capacity(range_query.by.len());
+    for field in plan.schema().fields() {
+        let name = field.name();
+        let data_type = ConcreteDataType::from_arrow_type(field.data_type());
+        let option = if *name == range_query.time_index {
+            ColumnOption::NotNull
+        } else {
+            ColumnOption::Null
+        };
+        if range_query.by.contains(name) {
+            primary_keys.push(Ident::new(name));
+        }
+        columns.push(ColumnDef {
+            name: Ident::new(name),
+            data_type: sql::statements::concrete_data_type_to_sql_data_type(&data_type)
+                .context(error::ParseSqlSnafu)?,
+            collation: None,
+            options: vec![ColumnOptionDef { name: None, option }],
+        });
+    }
+
+    let mut constraints = vec![TableConstraint::Unique {
+        name: Some(TIME_INDEX.into()),
+        columns: vec![Ident::new(&range_query.time_index)],
+        is_primary: false,
+    }];
+    if !primary_keys.is_empty() {
+        constraints.push(TableConstraint::Unique {
+            name: None,
+            columns: primary_keys,
+            is_primary: true,
+        });
+    }
+
+    Ok(CreateTable {
+        if_not_exists: false,
+        table_id: 0,
+        name: ObjectName(vec![
+            Ident::new(&view.catalog),
+            Ident::new(&view.schema),
+            Ident::new(&view.name),
+        ]),
+        columns,
+        engine: MITO_ENGINE.to_string(),
+        constraints,
+        options: [
+            (MATERIALIZED_VIEW_QUERY_KEY, Some(&view.query)),
+            (MATERIALIZED_VIEW_OWNER_KEY, view.owner.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| {
+            Some(SqlOption {
+                name: name.into(),
+                value: SqlValue::SingleQuotedString(value?.clone()),
+            })
+        })
+        .collect(),
+        partitions: None,
+    })
+}
+
+/// Returns the smallest time range covering both `a` and `b`.
+pub(crate) fn merge_time_ranges(
+    a: Option<(i64, i64)>,
+    b: Option<(i64, i64)>,
+) -> Option<(i64, i64)> {
+    match (a, b) {
+        (Some((min_a, max_a)), Some((min_b, max_b))) => Some((min_a.min(min_b), max_a.max(max_b))),
+        (a, b) => a.or(b),
+    }
+}
+
+fn millis_range(timestamps: impl Iterator<Item = Timestamp>) -> Option<(i64, i64)> {
+    timestamps
+        .filter_map(|ts| ts.convert_to(TimeUnit::Millisecond))
+        .map(|ts| ts.value())
+        .fold(None, |range, ts| merge_time_ranges(range, Some((ts, ts))))
+}
+
+/// Returns the time range, in milliseconds, of the rows in a vector of timestamps.
+pub(crate) fn vector_time_range(vector: &VectorRef) -> Option<(i64, i64)> {
+    millis_range((0..vector.len()).filter_map(|i| match vector.get(i) {
+        Value::Timestamp(ts) => Some(ts),
+        _ => None,
+    }))
+}
+
+/// Returns the time range, in milliseconds, of the rows in column-format insert requests.
+pub(crate) fn columns_time_range(columns: &[Column]) -> Option<(i64, i64)> {
+    let column = columns
+        .iter()
+        .find(|c| c.semantic_type == SemanticType::Timestamp as i32)?;
+    let values = column.values.as_ref()?;
+    let (unit, values) = match ColumnDataType::from_i32(column.datatype)? {
+        ColumnDataType::TimestampSecond => (TimeUnit::Second, &values.ts_second_values),
+        ColumnDataType::TimestampMillisecond => {
+            (TimeUnit::Millisecond, &values.ts_millisecond_values)
+        }
+        ColumnDataType::TimestampMicrosecond => {
+            (TimeUnit::Microsecond, &values.ts_microsecond_values)
+        }
+        ColumnDataType::TimestampNanosecond => (TimeUnit::Nanosecond, &values.ts_nanosecond_values),
+        _ => return None,
+    };
+    millis_range(values.iter().map(|v| Timestamp::new(*v, unit)))
+}
+
+/// Returns the time range, in milliseconds, of the rows in row-format insert requests.
+pub(crate) fn rows_time_range(rows: &Rows) -> Option<(i64, i64)> {
+    let index = rows
+        .schema
+        .iter()
+        .position(|c| c.semantic_type == SemanticType::Timestamp as i32)?;
+    millis_range(rows.rows.iter().filter_map(|row| {
+        match pb_value_to_value_ref(row.values.get(index)?) {
+            ValueRef::Timestamp(ts) => Some(ts),
+            _ => None,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::value::ValueData;
+    use api::v1::{ColumnSchema, Row, Values};
+
+    use super::*;
+
+    #[test]
+    fn test_align() {
+        assert_eq!(0, align_floor(4999, 5000));
+        assert_eq!(5000, align_floor(5000, 5000));
+        assert_eq!(-5000, align_floor(-1, 5000));
+        assert_eq!(5000, align_ceil(1, 5000));
+        assert_eq!(5000, align_ceil(5000, 5000));
+        assert_eq!(0, align_ceil(-1, 5000));
+    }
+
+    #[test]
+    fn test_columns_time_range() {
+        let columns = vec![
+            Column {
+                column_name: "host".to_string(),
+                semantic_type: SemanticType::Tag as i32,
+                datatype: ColumnDataType::String as i32,
+                values: Some(Values {
+                    string_values: vec!["a".to_string(), "b".to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            Column {
+                column_name: "ts".to_string(),
+                semantic_type: SemanticType::Timestamp as i32,
+                datatype: ColumnDataType::TimestampSecond as i32,
+                values: Some(Values {
+                    ts_second_values: vec![3, 1, 2],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(Some((1000, 3000)), columns_time_range(&columns));
+        assert_eq!(None, columns_time_range(&columns[..1]));
+    }
+
+    #[test]
+    fn test_rows_time_range() {
+        let row = |ts| Row {
+            values: vec![
+                api::v1::Value {
+                    value_data: Some(ValueData::StringValue("a".to_string())),
+                },
+                api::v1::Value {
+                    value_data: Some(ValueData::TsNanosecondValue(ts)),
+                },
+            ],
+        };
+        let rows = Rows {
+            schema: vec![
+                ColumnSchema {
+                    column_name: "host".to_string(),
+                    datatype: ColumnDataType::String as i32,
+                    semantic_type: SemanticType::Tag as i32,
+                },
+                ColumnSchema {
+                    column_name: "ts".to_string(),
+                    datatype: ColumnDataType::TimestampNanosecond as i32,
+                    semantic_type: SemanticType::Timestamp as i32,
+                },
+            ],
+            rows: vec![row(5_000_000), row(-1_000_000), row(2_500_000)],
+        };
+        assert_eq!(Some((-1, 5)), rows_time_range(&rows));
+    }
+
+    #[test]
+    fn test_merge_time_ranges() {
+        assert_eq!(None, merge_time_ranges(None, None));
+        assert_eq!(Some((1, 2)), merge_time_ranges(Some((1, 2)), None));
+        assert_eq!(Some((1, 2)), merge_time_ranges(None, Some((1, 2))));
+        assert_eq!(Some((0, 5)), merge_time_ranges(Some((1, 5)), Some((0, 2))));
+    }
+
+    #[test]
+    fn test_refresh_queue() {
+        let manager = MaterializedViewManager::new();
+        assert!(manager.may_have_views("greptime", "public", "host"));
+
+        let source = SourceTable {
+            catalog: "greptime".to_string(),
+            schema: "public".to_string(),
+            table: "host".to_string(),
+        };
+        assert!(manager.enqueue(source.clone(), Refresh::insert((5, 10))));
+        // Refreshes queued before the queue is drained are merged.
+        assert!(!manager.enqueue(source.clone(), Refresh::delete((0, 3))));
+        let pending = manager.dequeue().unwrap();
+        assert_eq!(
+            Refresh {
+                time_range: (0, 10),
+                deleted: true,
+            },
+            pending[&source]
+        );
+        assert!(manager.dequeue().is_none());
+
+        // The queue is drained, so the next refresh needs a new task.
+        assert!(manager.enqueue(source, Refresh::insert((1, 2))));
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use auth::RbacManagerRef;
+use common_query::Output;
+use session::context::QueryContextRef;
+use snafu::{OptionExt, ResultExt};
+use sql::statements::privilege::{CreateRole, CreateUser, Grant, Revoke};
+
+use crate::error::{ManagePrivilegeSnafu, NotSupportedSnafu, Result};
+use crate::statement::StatementExecutor;
+
+impl StatementExecutor {
+    fn rbac_manager(&self) -> Result<&RbacManagerRef> {
+        self.rbac_manager.as_ref().context(NotSupportedSnafu {
+            feat: "managing users and privileges without role-based access control enabled",
+        })
+    }
+
+    pub(super) async fn create_user(&self, stmt: CreateUser) -> Result<Output> {
+        self.rbac_manager()?
+            .create_user(&stmt)
+            .await
+            .context(ManagePrivilegeSnafu)?;
+        Ok(Output::AffectedRows(0))
+    }
+
+    pub(super) async fn create_role(&self, stmt: CreateRole) -> Result<Output> {
+        self.rbac_manager()?
+            .create_role(&stmt)
+            .await
+            .context(ManagePrivilegeSnafu)?;
+        Ok(Output::AffectedRows(0))
+    }
+
+    pub(super) async fn grant(&self, stmt: Grant, query_ctx: QueryContextRef) -> Result<Output> {
+        self.rbac_manager()?
+            .grant(
+                &stmt,
+                query_ctx.current_catalog(),
+                query_ctx.current_schema(),
+            )
+            .await
+            .context(ManagePrivilegeSnafu)?;
+        Ok(Output::AffectedRows(0))
+    }
+
+    pub(super) async fn revoke(&self, stmt: Revoke, query_ctx: QueryContextRef) -> Result<Output> {
+        self.rbac_manager()?
+            .revoke(
+                &stmt,
+                query_ctx.current_catalog(),
+                query_ctx.current_schema(),
+            )
+            .await
+            .context(ManagePrivilegeSnafu)?;
+        Ok(Output::AffectedRows(0))
+    }
+}
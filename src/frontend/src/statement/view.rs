@@ -0,0 +1,76 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_error::ext::BoxedError;
+use common_query::Output;
+use datanode::instance::sql::table_idents_to_full_name;
+use datatypes::prelude::ConcreteDataType;
+use query::parser::QueryStatement;
+use query::plan::LogicalPlan;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::ResultExt;
+use sql::ast::{ColumnDef, ColumnOption, ColumnOptionDef, Ident};
+use sql::statements::create::CreateView;
+use sql::statements::statement::Statement;
+
+use crate::error::{self, ExecuteStatementSnafu, ExternalSnafu, Result};
+use crate::statement::StatementExecutor;
+
+impl StatementExecutor {
+    pub(super) async fn create_view(
+        &self,
+        mut stmt: CreateView,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let (catalog, schema, _) = table_idents_to_full_name(&stmt.name, query_ctx.clone())
+            .map_err(BoxedError::new)
+            .context(ExternalSnafu)?;
+
+        // Plans the query in the schema of the view, where it will be expanded, to validate it
+        // and to find out the columns of the view.
+        let query = QueryStatement::Sql(Statement::Query(stmt.query.clone()));
+        let plan = self
+            .plan(query, QueryContext::with(&catalog, &schema))
+            .await?;
+        stmt.columns = view_columns(&plan)?;
+
+        self.sql_stmt_executor
+            .execute_sql(Statement::CreateView(stmt), query_ctx)
+            .await
+            .context(ExecuteStatementSnafu)
+    }
+}
+
+fn view_columns(plan: &LogicalPlan) -> Result<Vec<ColumnDef>> {
+    let LogicalPlan::DfPlan(plan) = plan;
+    plan.schema()
+        .fields()
+        .iter()
+        .map(|field| {
+            let data_type = ConcreteDataType::from_arrow_type(field.data_type());
+            let option = if field.is_nullable() {
+                ColumnOption::Null
+            } else {
+                ColumnOption::NotNull
+            };
+            Ok(ColumnDef {
+                name: Ident::new(field.name()),
+                data_type: sql::statements::concrete_data_type_to_sql_data_type(&data_type)
+                    .context(error::ParseSqlSnafu)?,
+                collation: None,
+                options: vec![ColumnOptionDef { name: None, option }],
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,515 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::readable_size::ReadableSize;
+use common_meta::key::TableMetadataManagerRef;
+use common_meta::rpc::router::RegionRoute;
+use common_meta::ClusterId;
+use common_telemetry::{error, info};
+use partition::partition::PartitionDef;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use store_api::storage::{RegionId, RegionNumber};
+use table::metadata::TableId;
+
+use crate::cluster::MetaPeerClientRef;
+use crate::error::{self, Result};
+use crate::handler::node_stat::RegionStat;
+use crate::keys::{StatKey, StatValue};
+use crate::metasrv::ElectionRef;
+use crate::procedure::region_balance::RegionBalanceManagerRef;
+
+/// Options of the region balancer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegionBalancerOptions {
+    /// Whether to evaluate the balance plans periodically, false by default.
+    pub enable: bool,
+    /// The interval of evaluations in seconds.
+    pub interval_secs: u64,
+    /// A region is planned to split if its size exceeds this threshold.
+    pub split_region_size: ReadableSize,
+    /// A region is planned to split if its write capacity units exceed this threshold, 0 means
+    /// the write load is not considered.
+    pub split_region_wcus: i64,
+    /// Two adjacent regions are planned to merge if their total size is below this threshold.
+    pub merge_region_size: ReadableSize,
+}
+
+impl Default for RegionBalancerOptions {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            interval_secs: 300,
+            split_region_size: ReadableSize::gb(4),
+            split_region_wcus: 0,
+            merge_region_size: ReadableSize::mb(256),
+        }
+    }
+}
+
+/// An action to rebalance the regions of a table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BalancePlan {
+    /// Splits a region that is too large or too hot into two.
+    Split {
+        cluster_id: ClusterId,
+        table_id: TableId,
+        region_number: RegionNumber,
+        approximate_bytes: i64,
+        wcus: i64,
+    },
+    /// Merges two adjacent regions that are both small and cold, the latter region is merged
+    /// into the former one.
+    Merge {
+        cluster_id: ClusterId,
+        table_id: TableId,
+        region_numbers: [RegionNumber; 2],
+        approximate_bytes: i64,
+    },
+}
+
+pub type RegionBalancerRef = Arc<RegionBalancer>;
+
+/// Evaluates the region stats collected from the Datanode heartbeats, and plans to split or
+/// merge regions.
+///
+/// If enabled, the plans are evaluated periodically and executed by the region balance
+/// procedures. The admin API only reports the plans.
+pub struct RegionBalancer {
+    options: RegionBalancerOptions,
+    meta_peer_client: MetaPeerClientRef,
+    table_metadata_manager: TableMetadataManagerRef,
+    region_balance_manager: RegionBalanceManagerRef,
+    election: Option<ElectionRef>,
+    started: AtomicBool,
+}
+
+impl RegionBalancer {
+    pub fn new(
+        options: RegionBalancerOptions,
+        meta_peer_client: MetaPeerClientRef,
+        table_metadata_manager: TableMetadataManagerRef,
+        region_balance_manager: RegionBalanceManagerRef,
+        election: Option<ElectionRef>,
+    ) -> Self {
+        Self {
+            options,
+            meta_peer_client,
+            table_metadata_manager,
+            region_balance_manager,
+            election,
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// Starts evaluating and executing the balance plans periodically on the leader, if enabled.
+    pub fn start(self: &Arc<Self>) {
+        if !self.options.enable || self.started.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        let balancer = self.clone();
+        let interval = Duration::from_secs(self.options.interval_secs.max(1));
+        let _handle = common_runtime::spawn_bg(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                let _ = ticker.tick().await;
+                if !balancer.is_leader() {
+                    continue;
+                }
+
+                match balancer.plan().await {
+                    Ok(plans) => balancer.execute(plans).await,
+                    Err(e) => error!(e; "Failed to evaluate region balance plans"),
+                }
+            }
+        });
+        info!("Region balancer started, interval: {interval:?}");
+    }
+
+    fn is_leader(&self) -> bool {
+        self.election.as_ref().map_or(true, |e| e.is_leader())
+    }
+
+    /// Submits the procedures to execute the plans. Plans of the tables that are being balanced
+    /// are skipped, they are evaluated again from the new stats in the next round.
+    async fn execute(&self, plans: Vec<BalancePlan>) {
+        for plan in plans {
+            match self.region_balance_manager.submit_balance_plan(&plan).await {
+                Ok(Some(procedure_id)) => {
+                    info!("Region balancer submits procedure {procedure_id} for plan {plan:?}")
+                }
+                Ok(None) => {}
+                Err(e) => error!(e; "Failed to submit region balance plan {plan:?}"),
+            }
+        }
+    }
+
+    /// Evaluates the balance plans from the latest region stats.
+    pub async fn plan(&self) -> Result<Vec<BalancePlan>> {
+        let stats = self.meta_peer_client.get_all_dn_stat_kvs().await?;
+        let tables = latest_region_stats(&stats);
+
+        let mut plans = Vec::new();
+        for ((cluster_id, table_id), regions) in tables {
+            let Some(table_route) = self
+                .table_metadata_manager
+                .table_route_manager()
+                .get(table_id)
+                .await
+                .context(error::TableMetadataManagerSnafu)?
+            else {
+                // The table could be dropped after the stats are reported.
+                continue;
+            };
+            plans.extend(plan_table(
+                &self.options,
+                cluster_id,
+                table_id,
+                &regions,
+                &table_route.region_routes,
+            ));
+        }
+        Ok(plans)
+    }
+}
+
+type TableRegionStats<'a> = BTreeMap<(ClusterId, TableId), BTreeMap<RegionNumber, &'a RegionStat>>;
+
+/// Collects the latest stat of each region, grouped by tables.
+fn latest_region_stats(stats: &HashMap<StatKey, StatValue>) -> TableRegionStats<'_> {
+    let mut tables = TableRegionStats::new();
+    for (key, stat) in stats.iter().filter_map(|(k, v)| Some((k, v.stats.last()?))) {
+        for region_stat in &stat.region_stats {
+            let region_id = RegionId::from(region_stat.id);
+            let _ = tables
+                .entry((key.cluster_id, region_id.table_id()))
+                .or_default()
+                .insert(region_id.region_number(), region_stat);
+        }
+    }
+    tables
+}
+
+fn plan_table(
+    options: &RegionBalancerOptions,
+    cluster_id: ClusterId,
+    table_id: TableId,
+    regions: &BTreeMap<RegionNumber, &RegionStat>,
+    region_routes: &[RegionRoute],
+) -> Vec<BalancePlan> {
+    // Only the regions of RANGE partitions can be split at a key, or merged with the adjacent one.
+    let Some(partitions) = sort_range_partitions(region_routes) else {
+        return vec![];
+    };
+
+    let is_hot =
+        |stat: &RegionStat| options.split_region_wcus > 0 && stat.wcus >= options.split_region_wcus;
+
+    let mut plans = regions
+        .iter()
+        .filter(|(_, stat)| {
+            stat.approximate_bytes as u64 >= options.split_region_size.0 || is_hot(stat)
+        })
+        .map(|(region_number, stat)| BalancePlan::Split {
+            cluster_id,
+            table_id,
+            region_number: *region_number,
+            approximate_bytes: stat.approximate_bytes,
+            wcus: stat.wcus,
+        })
+        .collect::<Vec<_>>();
+
+    let mut i = 0;
+    while i + 1 < partitions.len() {
+        let (left, right) = (partitions[i].0, partitions[i + 1].0);
+        let (left_number, right_number) = (
+            left.region.id.region_number(),
+            right.region.id.region_number(),
+        );
+        let (Some(left_stat), Some(right_stat)) =
+            (regions.get(&left_number), regions.get(&right_number))
+        else {
+            i += 1;
+            continue;
+        };
+        // The rows are moved between the regions on the Datanode, so they have to be on the
+        // same one.
+        let same_peer = left.leader_peer.is_some() && left.leader_peer == right.leader_peer;
+        let approximate_bytes = left_stat.approximate_bytes + right_stat.approximate_bytes;
+        if same_peer
+            && (approximate_bytes as u64) < options.merge_region_size.0
+            && !is_hot(left_stat)
+            && !is_hot(right_stat)
+        {
+            plans.push(BalancePlan::Merge {
+                cluster_id,
+                table_id,
+                region_numbers: [left_number, right_number],
+                approximate_bytes,
+            });
+            // A region is merged at most once in a round.
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+    plans
+}
+
+/// Returns the region routes and their partitions in the order of the partition bounds, if the
+/// table is partitioned by RANGE. Then the range of a region starts from the bound of the
+/// previous one, and ends at its own bound (exclusive).
+///
+/// Returns `None` if the table is partitioned by HASH, or not partitioned by any column.
+pub(crate) fn sort_range_partitions(
+    region_routes: &[RegionRoute],
+) -> Option<Vec<(&RegionRoute, PartitionDef)>> {
+    let mut partitions = Vec::with_capacity(region_routes.len());
+    for route in region_routes {
+        let partition = PartitionDef::try_from(route.region.partition.clone()?).ok()?;
+        if partition.partition_columns().is_empty() || partition.hash_modulus().is_some() {
+            return None;
+        }
+        partitions.push((route, partition));
+    }
+    partitions.sort_by(|a, b| a.1.partition_bounds().cmp(b.1.partition_bounds()));
+    Some(partitions)
+}
+
+#[cfg(test)]
+mod tests {
+    use common_meta::peer::Peer;
+    use common_meta::rpc::router::{Partition, Region};
+
+    use super::*;
+    use crate::handler::node_stat::Stat;
+
+    fn region_stat(table_id: TableId, region_number: RegionNumber, bytes: i64) -> RegionStat {
+        RegionStat {
+            id: RegionId::new(table_id, region_number).as_u64(),
+            approximate_bytes: bytes,
+            ..Default::default()
+        }
+    }
+
+    /// Builds the routes of regions, each is given by its number, partition bound and the id of
+    /// its leader Datanode.
+    fn region_routes(regions: &[(RegionNumber, &str, u64)]) -> Vec<RegionRoute> {
+        regions
+            .iter()
+            .map(|(n, bound, peer)| RegionRoute {
+                region: Region {
+                    id: (*n as u64).into(),
+                    partition: Some(Partition {
+                        column_list: vec![b"a".to_vec()],
+                        value_list: vec![bound.as_bytes().to_vec()],
+                    }),
+                    ..Default::default()
+                },
+                leader_peer: Some(Peer::new(*peer, "")),
+                follower_peers: vec![],
+            })
+            .collect()
+    }
+
+    fn options() -> RegionBalancerOptions {
+        RegionBalancerOptions {
+            split_region_size: ReadableSize(1000),
+            split_region_wcus: 50,
+            merge_region_size: ReadableSize(100),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_latest_region_stats() {
+        let mut stats = HashMap::new();
+        let _ = stats.insert(
+            StatKey {
+                cluster_id: 0,
+                node_id: 1,
+            },
+            StatValue {
+                stats: vec![
+                    Stat {
+                        region_stats: vec![region_stat(1, 0, 1)],
+                        ..Default::default()
+                    },
+                    Stat {
+                        region_stats: vec![region_stat(1, 0, 2), region_stat(2, 1, 3)],
+                        ..Default::default()
+                    },
+                ],
+            },
+        );
+
+        let tables = latest_region_stats(&stats);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[&(0, 1)][&0].approximate_bytes, 2);
+        assert_eq!(tables[&(0, 2)][&1].approximate_bytes, 3);
+    }
+
+    #[test]
+    fn test_sort_range_partitions() {
+        let routes = region_routes(&[
+            (0, r#"{"Value":{"Int32":20}}"#, 1),
+            (1, r#""MaxValue""#, 1),
+            (2, r#"{"Value":{"Int32":10}}"#, 1),
+        ]);
+        let sorted = sort_range_partitions(&routes)
+            .unwrap()
+            .into_iter()
+            .map(|(route, _)| route.region.id.region_number())
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![2, 0, 1]);
+
+        let routes = region_routes(&[
+            (0, r#"{"Hash":{"remainder":0,"modulus":2}}"#, 1),
+            (1, r#"{"Hash":{"remainder":1,"modulus":2}}"#, 1),
+        ]);
+        assert!(sort_range_partitions(&routes).is_none());
+    }
+
+    #[test]
+    fn test_plan_split() {
+        let stats = [
+            region_stat(1, 0, 2000),
+            RegionStat {
+                wcus: 60,
+                ..region_stat(1, 1, 500)
+            },
+            region_stat(1, 2, 500),
+        ];
+        let regions = stats
+            .iter()
+            .map(|s| (RegionId::from(s.id).region_number(), s))
+            .collect();
+        let plans = plan_table(
+            &options(),
+            0,
+            1,
+            &regions,
+            &region_routes(&[
+                (0, r#"{"Value":{"Int32":10}}"#, 1),
+                (1, r#"{"Value":{"Int32":20}}"#, 1),
+                (2, r#""MaxValue""#, 1),
+            ]),
+        );
+        assert_eq!(
+            plans,
+            vec![
+                BalancePlan::Split {
+                    cluster_id: 0,
+                    table_id: 1,
+                    region_number: 0,
+                    approximate_bytes: 2000,
+                    wcus: 0,
+                },
+                BalancePlan::Split {
+                    cluster_id: 0,
+                    table_id: 1,
+                    region_number: 1,
+                    approximate_bytes: 500,
+                    wcus: 60,
+                },
+            ]
+        );
+
+        // Regions of hash partitions can't be split at a key.
+        let plans = plan_table(
+            &options(),
+            0,
+            1,
+            &regions,
+            &region_routes(&[
+                (0, r#"{"Hash":{"remainder":0,"modulus":3}}"#, 1),
+                (1, r#"{"Hash":{"remainder":1,"modulus":3}}"#, 1),
+                (2, r#"{"Hash":{"remainder":2,"modulus":3}}"#, 1),
+            ]),
+        );
+        assert!(plans.is_empty());
+    }
+
+    #[test]
+    fn test_plan_merge() {
+        let stats = [
+            region_stat(1, 0, 10),
+            region_stat(1, 1, 20),
+            region_stat(1, 2, 30),
+            region_stat(1, 3, 40),
+            region_stat(1, 4, 50),
+        ];
+        let regions = stats
+            .iter()
+            .map(|s| (RegionId::from(s.id).region_number(), s))
+            .collect();
+
+        // In the order of bounds, the regions are 1, 0, 3, 4, 2. Region 3 is on another
+        // Datanode, so it's not merged with its neighbours.
+        let plans = plan_table(
+            &options(),
+            0,
+            1,
+            &regions,
+            &region_routes(&[
+                (0, r#"{"Value":{"Int32":20}}"#, 1),
+                (1, r#"{"Value":{"Int32":10}}"#, 1),
+                (2, r#""MaxValue""#, 1),
+                (3, r#"{"Value":{"Int32":30}}"#, 2),
+                (4, r#"{"Value":{"Int32":40}}"#, 1),
+            ]),
+        );
+        assert_eq!(
+            plans,
+            vec![
+                BalancePlan::Merge {
+                    cluster_id: 0,
+                    table_id: 1,
+                    region_numbers: [1, 0],
+                    approximate_bytes: 30,
+                },
+                BalancePlan::Merge {
+                    cluster_id: 0,
+                    table_id: 1,
+                    region_numbers: [4, 2],
+                    approximate_bytes: 80,
+                },
+            ]
+        );
+
+        // Regions of hash partitions are never merged.
+        let plans = plan_table(
+            &options(),
+            0,
+            1,
+            &regions,
+            &region_routes(&[
+                (0, r#"{"Hash":{"remainder":0,"modulus":5}}"#, 1),
+                (1, r#"{"Hash":{"remainder":1,"modulus":5}}"#, 1),
+                (2, r#"{"Hash":{"remainder":2,"modulus":5}}"#, 1),
+                (3, r#"{"Hash":{"remainder":3,"modulus":5}}"#, 1),
+                (4, r#"{"Hash":{"remainder":4,"modulus":5}}"#, 1),
+            ]),
+        );
+        assert!(plans.is_empty());
+    }
+}
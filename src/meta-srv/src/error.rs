@@ -438,6 +438,31 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to migrate region: {}, reason: {}", region, reason))]
+    MigrateRegion {
+        region: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to balance region: {}, reason: {}", region, reason))]
+    BalanceRegion {
+        region: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to convert the partitions of table {}, source: {}",
+        table_id,
+        source
+    ))]
+    ConvertPartition {
+        table_id: u32,
+        location: Location,
+        source: partition::error::Error,
+    },
+
     #[snafu(display(
         "Received unexpected instruction reply, mailbox message: {}, reason: {}",
         mailbox_message,
@@ -607,12 +632,15 @@ impl ErrorExt for Error {
 
             Error::RegionFailoverCandidatesNotFound { .. } => StatusCode::RuntimeResourcesExhausted,
 
+            Error::MigrateRegion { .. } | Error::BalanceRegion { .. } => StatusCode::Internal,
+
             Error::RegisterProcedureLoader { source, .. } => source.status_code(),
 
             Error::TableRouteConversion { source, .. }
             | Error::ConvertProtoData { source, .. }
             | Error::TableMetadataManager { source, .. }
             | Error::UpdateTableRoute { source, .. }
+            | Error::ConvertPartition { source, .. }
             | Error::ConvertEtcdTxnObject { source, .. }
             | Error::GetFullTableInfo { source, .. } => source.status_code(),
 
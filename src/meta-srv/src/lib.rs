@@ -15,6 +15,7 @@
 #![feature(async_closure)]
 #![feature(result_flattening)]
 
+pub mod balancer;
 pub mod bootstrap;
 pub mod cluster;
 pub mod ddl;
@@ -31,6 +31,7 @@ use servers::http::HttpOptions;
 use snafu::ResultExt;
 use tokio::sync::broadcast::error::RecvError;
 
+use crate::balancer::{RegionBalancerOptions, RegionBalancerRef};
 use crate::cluster::MetaPeerClientRef;
 use crate::ddl::DdlManagerRef;
 use crate::election::{Election, LeaderChangeMessage};
@@ -38,6 +39,7 @@ use crate::error::{RecoverProcedureSnafu, Result};
 use crate::handler::HeartbeatHandlerGroup;
 use crate::lock::DistLockRef;
 use crate::metadata_service::MetadataServiceRef;
+use crate::procedure::region_migration::RegionMigrationManagerRef;
 use crate::pubsub::{PublishRef, SubscribeManagerRef};
 use crate::selector::{Selector, SelectorType};
 use crate::sequence::SequenceRef;
@@ -62,6 +64,7 @@ pub struct MetaSrvOptions {
     pub datanode: DatanodeOptions,
     pub enable_telemetry: bool,
     pub data_home: String,
+    pub region_balancer: RegionBalancerOptions,
 }
 
 impl Default for MetaSrvOptions {
@@ -83,6 +86,7 @@ impl Default for MetaSrvOptions {
             datanode: DatanodeOptions::default(),
             enable_telemetry: true,
             data_home: METASRV_HOME.to_string(),
+            region_balancer: RegionBalancerOptions::default(),
         }
     }
 }
@@ -185,6 +189,8 @@ pub struct MetaSrv {
     mailbox: MailboxRef,
     ddl_manager: DdlManagerRef,
     table_metadata_manager: TableMetadataManagerRef,
+    region_migration_manager: RegionMigrationManagerRef,
+    region_balancer: RegionBalancerRef,
     greptimedb_telemetry_task: Arc<GreptimeDBTelemetryTask>,
     pubsub: Option<(PublishRef, SubscribeManagerRef)>,
 }
@@ -275,6 +281,8 @@ impl MetaSrv {
                 .context(RecoverProcedureSnafu)?;
         }
 
+        self.region_balancer.start();
+
         info!("MetaSrv started");
         Ok(())
     }
@@ -346,6 +354,14 @@ impl MetaSrv {
         &self.table_metadata_manager
     }
 
+    pub fn region_migration_manager(&self) -> &RegionMigrationManagerRef {
+        &self.region_migration_manager
+    }
+
+    pub fn region_balancer(&self) -> &RegionBalancerRef {
+        &self.region_balancer
+    }
+
     pub fn publish(&self) -> Option<&PublishRef> {
         self.pubsub.as_ref().map(|suite| &suite.0)
     }
@@ -22,6 +22,7 @@ use common_meta::key::{TableMetadataManager, TableMetadataManagerRef};
 use common_procedure::local::{LocalManager, ManagerConfig};
 use common_procedure::ProcedureManagerRef;
 
+use crate::balancer::RegionBalancer;
 use crate::cluster::{MetaPeerClientBuilder, MetaPeerClientRef};
 use crate::ddl::{DdlManager, DdlManagerRef};
 use crate::error::Result;
@@ -40,7 +41,9 @@ use crate::metadata_service::{DefaultMetadataService, MetadataServiceRef};
 use crate::metasrv::{
     ElectionRef, MetaSrv, MetaSrvOptions, SelectorContext, SelectorRef, TABLE_ID_SEQ,
 };
+use crate::procedure::region_balance::RegionBalanceManager;
 use crate::procedure::region_failover::RegionFailoverManager;
+use crate::procedure::region_migration::RegionMigrationManager;
 use crate::procedure::state_store::MetaStateStore;
 use crate::pubsub::{PublishRef, SubscribeManagerRef};
 use crate::selector::lease_based::LeaseBasedSelector;
@@ -182,6 +185,41 @@ impl MetaSrvBuilder {
         );
         let _ = ddl_manager.try_start();
 
+        let region_migration_manager = Arc::new(RegionMigrationManager::new(
+            in_memory.clone(),
+            mailbox.clone(),
+            procedure_manager.clone(),
+            SelectorContext {
+                server_addr: options.server_addr.clone(),
+                datanode_lease_secs: options.datanode_lease_secs,
+                kv_store: kv_store.clone(),
+                meta_peer_client: meta_peer_client.clone(),
+                catalog: None,
+                schema: None,
+                table: None,
+            },
+            lock.clone(),
+            table_metadata_manager.clone(),
+        ));
+        region_migration_manager.try_start()?;
+
+        let region_balance_manager = Arc::new(RegionBalanceManager::new(
+            mailbox.clone(),
+            procedure_manager.clone(),
+            options.server_addr.clone(),
+            lock.clone(),
+            table_metadata_manager.clone(),
+        ));
+        region_balance_manager.try_start()?;
+
+        let region_balancer = Arc::new(RegionBalancer::new(
+            options.region_balancer.clone(),
+            meta_peer_client.clone(),
+            table_metadata_manager.clone(),
+            region_balance_manager,
+            election.clone(),
+        ));
+
         let handler_group = match handler_group {
             Some(handler_group) => handler_group,
             None => {
@@ -256,6 +294,8 @@ impl MetaSrvBuilder {
             mailbox,
             ddl_manager,
             table_metadata_manager,
+            region_migration_manager,
+            region_balancer,
             greptimedb_telemetry_task: get_greptimedb_telemetry_task(
                 Some(metasrv_home),
                 meta_peer_client,
@@ -15,6 +15,8 @@
 pub mod alter_table;
 pub mod create_table;
 pub mod drop_table;
+pub mod region_balance;
 pub mod region_failover;
+pub mod region_migration;
 pub(crate) mod state_store;
 mod utils;
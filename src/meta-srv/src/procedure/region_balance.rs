@@ -0,0 +1,901 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod balance_end;
+mod balance_start;
+mod invalidate_cache;
+mod merge_regions;
+mod pick_split_key;
+mod rollback_split;
+mod split_region;
+mod update_metadata;
+mod upgrade_regions;
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use api::v1::meta::MailboxMessage;
+use async_trait::async_trait;
+use balance_start::RegionBalanceStart;
+use common_meta::ident::TableIdent;
+use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_meta::key::table_route::TableRouteValue;
+use common_meta::key::TableMetadataManagerRef;
+use common_meta::{DatanodeId, RegionIdent};
+use common_procedure::error::{
+    Error as ProcedureError, FromJsonSnafu, Result as ProcedureResult, ToJsonSnafu,
+};
+use common_procedure::{
+    watcher, Context as ProcedureContext, LockKey, Procedure, ProcedureId, ProcedureManagerRef,
+    ProcedureWithId, Status,
+};
+use common_telemetry::{debug, error, info};
+use datatypes::value::Value;
+use serde::{Deserialize, Serialize};
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::RegionNumber;
+use table::metadata::TableId;
+
+use crate::balancer::{sort_range_partitions, BalancePlan};
+use crate::error::{
+    self, Error, RegisterProcedureLoaderSnafu, Result, RetryLaterSnafu, SerializeToJsonSnafu,
+    UnexpectedInstructionReplySnafu,
+};
+use crate::handler::HeartbeatMailbox;
+use crate::lock::DistLockRef;
+use crate::service::mailbox::{Channel, MailboxReceiver, MailboxRef};
+
+// Finding the split key scans the partition columns of the region.
+const REGION_SPLIT_KEY_MESSAGE_TIMEOUT: Duration = Duration::from_secs(60);
+// Splitting or merging regions rewrites the rows moved between them.
+const MOVE_REGION_ROWS_MESSAGE_TIMEOUT: Duration = Duration::from_secs(300);
+// Upgrading the regions reopens them.
+const UPGRADE_REGION_MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub type RegionBalanceManagerRef = Arc<RegionBalanceManager>;
+
+/// Submits the procedures that execute the plans of the region balancer.
+pub struct RegionBalanceManager {
+    procedure_manager: ProcedureManagerRef,
+    context: RegionBalanceContext,
+    /// The tables that have a running region balance procedure.
+    running_tables: Arc<Mutex<HashSet<TableId>>>,
+}
+
+impl RegionBalanceManager {
+    pub(crate) fn new(
+        mailbox: MailboxRef,
+        procedure_manager: ProcedureManagerRef,
+        server_addr: String,
+        dist_lock: DistLockRef,
+        table_metadata_manager: TableMetadataManagerRef,
+    ) -> Self {
+        Self {
+            procedure_manager,
+            context: RegionBalanceContext {
+                mailbox,
+                server_addr,
+                dist_lock,
+                table_metadata_manager,
+            },
+            running_tables: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    pub(crate) fn try_start(&self) -> Result<()> {
+        let context = self.context.clone();
+        self.procedure_manager
+            .register_loader(
+                RegionBalanceProcedure::TYPE_NAME,
+                Box::new(move |json| {
+                    let context = context.clone();
+                    RegionBalanceProcedure::from_json(json, context).map(|p| Box::new(p) as _)
+                }),
+            )
+            .context(RegisterProcedureLoaderSnafu {
+                type_name: RegionBalanceProcedure::TYPE_NAME,
+            })
+    }
+
+    /// Submits a region balance procedure in background to execute the plan. Returns the id of
+    /// the submitted procedure, or `None` if the regions of the table are being balanced, then
+    /// the plan is likely outdated.
+    pub async fn submit_balance_plan(&self, plan: &BalancePlan) -> Result<Option<ProcedureId>> {
+        let table_id = match plan {
+            BalancePlan::Split { table_id, .. } | BalancePlan::Merge { table_id, .. } => *table_id,
+        };
+        if !self.running_tables.lock().unwrap().insert(table_id) {
+            return Ok(None);
+        }
+
+        let result = self.submit_task(plan).await;
+        if result.is_err() {
+            let _ = self.running_tables.lock().unwrap().remove(&table_id);
+        }
+        result.map(Some)
+    }
+
+    async fn submit_task(&self, plan: &BalancePlan) -> Result<ProcedureId> {
+        let task = self.build_task(plan).await?;
+        let table_id = task.region.table_ident.table_id;
+
+        let procedure = RegionBalanceProcedure::new(task.clone(), self.context.clone());
+        let procedure_with_id = ProcedureWithId::with_random_id(Box::new(procedure));
+        let procedure_id = procedure_with_id.id;
+        info!("Starting region balance procedure {procedure_id} for {task}");
+
+        let mut watcher = self
+            .procedure_manager
+            .submit(procedure_with_id)
+            .await
+            .context(error::SubmitProcedureSnafu)?;
+
+        let running_tables = self.running_tables.clone();
+        let _handle = common_runtime::spawn_bg(async move {
+            let result = watcher::wait(&mut watcher).await;
+            let _ = running_tables.lock().unwrap().remove(&table_id);
+            if let Err(e) = result {
+                error!(e; "Failed to wait region balance procedure {procedure_id} for {task}");
+                return;
+            }
+
+            info!("Region balance procedure {procedure_id} for {task} is finished successfully!");
+        });
+        Ok(procedure_id)
+    }
+
+    async fn build_task(&self, plan: &BalancePlan) -> Result<RegionBalanceTask> {
+        let (cluster_id, table_id, region_number, from_region_number) = match plan {
+            BalancePlan::Split {
+                cluster_id,
+                table_id,
+                region_number,
+                ..
+            } => (*cluster_id, *table_id, *region_number, None),
+            BalancePlan::Merge {
+                cluster_id,
+                table_id,
+                region_numbers: [into, from],
+                ..
+            } => (*cluster_id, *table_id, *into, Some(*from)),
+        };
+
+        let table_info = self
+            .context
+            .table_metadata_manager
+            .table_info_manager()
+            .get(table_id)
+            .await
+            .context(error::TableMetadataManagerSnafu)?
+            .with_context(|| error::TableInfoNotFoundSnafu {
+                table_name: table_id.to_string(),
+            })?
+            .table_info;
+        let table_ident = TableIdent {
+            catalog: table_info.catalog_name,
+            schema: table_info.schema_name,
+            table: table_info.name,
+            table_id,
+            engine: table_info.meta.engine,
+        };
+
+        let table_route = self.context.table_route(&table_ident).await?;
+        let plan_error = |reason: &str| {
+            error::BalanceRegionSnafu {
+                region: format!("{region_number} of table {table_ident}"),
+                reason,
+            }
+            .build()
+        };
+        let partitions = sort_range_partitions(&table_route.region_routes)
+            .ok_or_else(|| plan_error("the table is not partitioned by RANGE"))?;
+        let (region_route, partition) = partitions
+            .iter()
+            .find(|(r, _)| r.region.id.region_number() == region_number)
+            .ok_or_else(|| plan_error("the region is not found in the table route"))?;
+        let datanode_id = region_route
+            .leader_peer
+            .as_ref()
+            .ok_or_else(|| plan_error("the region has no leader"))?
+            .id;
+
+        let kind = match from_region_number {
+            None => BalanceKind::Split {
+                partition_columns: partition.partition_columns().clone(),
+            },
+            Some(from_region_number) => BalanceKind::Merge { from_region_number },
+        };
+        Ok(RegionBalanceTask {
+            region: RegionIdent {
+                cluster_id,
+                datanode_id,
+                table_ident,
+                region_number,
+            },
+            kind,
+        })
+    }
+}
+
+/// How the regions are balanced.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+enum BalanceKind {
+    /// Splits the region into two by the partition columns.
+    Split { partition_columns: Vec<String> },
+    /// Merges the region `from_region_number`, whose range follows the range of the region,
+    /// into the region.
+    Merge { from_region_number: RegionNumber },
+}
+
+/// The region to split or to merge into. The `datanode_id` of `region` is the Datanode of the
+/// leader of the region.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct RegionBalanceTask {
+    region: RegionIdent,
+    kind: BalanceKind,
+}
+
+impl RegionBalanceTask {
+    /// Returns the partition columns to split the region by, or empty for merging.
+    fn partition_columns(&self) -> &[String] {
+        match &self.kind {
+            BalanceKind::Split { partition_columns } => partition_columns,
+            BalanceKind::Merge { .. } => &[],
+        }
+    }
+
+    /// Returns the [RegionIdent] of another region of the table on the same Datanode.
+    fn sibling_region(&self, region_number: RegionNumber) -> RegionIdent {
+        RegionIdent {
+            region_number,
+            ..self.region.clone()
+        }
+    }
+}
+
+impl std::fmt::Display for RegionBalanceTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            BalanceKind::Split { .. } => write!(f, "splitting region {}", self.region),
+            BalanceKind::Merge { from_region_number } => write!(
+                f,
+                "merging region {} into region {}",
+                from_region_number, self.region
+            ),
+        }
+    }
+}
+
+/// The new region split from the region of the task, it holds the rows not less than
+/// `split_key`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct RegionSplit {
+    new_region_number: RegionNumber,
+    split_key: Vec<Value>,
+}
+
+/// A "Node" in the state machine of region balance procedure.
+/// Contains the current state and the data.
+#[derive(Serialize, Deserialize, Debug)]
+struct Node {
+    task: RegionBalanceTask,
+    state: Box<dyn State>,
+}
+
+/// The "Context" of region balance procedure state machine.
+#[derive(Clone)]
+pub struct RegionBalanceContext {
+    pub mailbox: MailboxRef,
+    pub server_addr: String,
+    pub dist_lock: DistLockRef,
+    pub table_metadata_manager: TableMetadataManagerRef,
+}
+
+impl RegionBalanceContext {
+    async fn table_route(&self, table_ident: &TableIdent) -> Result<TableRouteValue> {
+        self.table_metadata_manager
+            .table_route_manager()
+            .get(table_ident.table_id)
+            .await
+            .context(error::TableMetadataManagerSnafu)?
+            .with_context(|| error::TableRouteNotFoundSnafu {
+                table_name: table_ident.table_ref().to_string(),
+            })
+    }
+
+    async fn send_instruction(
+        &self,
+        subject: &str,
+        datanode_id: DatanodeId,
+        instruction: &Instruction,
+        timeout: Duration,
+    ) -> Result<MailboxReceiver> {
+        let msg = MailboxMessage::json_message(
+            subject,
+            &format!("Metasrv@{}", self.server_addr),
+            &format!("Datanode-{datanode_id}"),
+            common_time::util::current_time_millis(),
+            instruction,
+        )
+        .with_context(|_| SerializeToJsonSnafu {
+            input: instruction.to_string(),
+        })?;
+
+        let ch = Channel::Datanode(datanode_id);
+        self.mailbox.send(&ch, msg, timeout).await
+    }
+
+    /// Sends the `instruction` to the Datanode and waits for its [SimpleReply]. Retries later if
+    /// the Datanode doesn't reply in time, all the instructions of region balance can be
+    /// executed again.
+    async fn simple_request(
+        &self,
+        subject: &str,
+        datanode_id: DatanodeId,
+        instruction: &Instruction,
+        timeout: Duration,
+    ) -> Result<SimpleReply> {
+        let mailbox_receiver = self
+            .send_instruction(subject, datanode_id, instruction, timeout)
+            .await?;
+
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received {subject} reply: {msg:?}");
+                simple_reply(&msg, instruction)
+            }
+            Err(Error::MailboxTimeout { .. }) => {
+                let reason =
+                    format!("Mailbox received timeout for {instruction} on Datanode {datanode_id}");
+                RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Extracts the [SimpleReply] of the region `instruction` from the mailbox message.
+fn simple_reply(msg: &MailboxMessage, instruction: &Instruction) -> Result<SimpleReply> {
+    let reply = HeartbeatMailbox::json_reply(msg)?;
+    match (instruction, reply) {
+        (Instruction::SplitRegion { .. }, InstructionReply::SplitRegion(reply))
+        | (Instruction::MergeRegions { .. }, InstructionReply::MergeRegions(reply))
+        | (Instruction::UpgradeRegion(_), InstructionReply::UpgradeRegion(reply)) => Ok(reply),
+        _ => UnexpectedInstructionReplySnafu {
+            mailbox_message: msg.to_string(),
+            reason: format!("expect the reply of {instruction}"),
+        }
+        .fail(),
+    }
+}
+
+/// The state machine of region balance procedure. Driven by the call to `next`.
+///
+/// The current state is kept if `next` fails, so that it's executed again when the procedure
+/// is retried.
+#[async_trait]
+#[typetag::serde(tag = "region_balance_state")]
+trait State: Sync + Send + Debug {
+    async fn next(
+        &mut self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>>;
+
+    fn status(&self) -> Status {
+        Status::executing(true)
+    }
+}
+
+/// The states transition of region balance procedure.
+///
+/// The rows are moved between the regions on the Datanode of the leader, and the regions reject
+/// writes from moving the rows until they are upgraded after the table route is updated. A
+/// split is rolled back by merging the new region back, while a merge is always retried until
+/// it's done, for the rows of the merged region may have been moved partially:
+///
+/// ```text
+///                       ┌──────────────────┐
+///                       │RegionBalanceStart│
+///                       └─────────┬────────┘
+///                                 │ Checks the leader of the region
+///                                 │ (and the region to merge)
+///                  Split          │           Merge
+///                ┌────────────────┴──────────────────┐
+///        ┌───────▼──────┐                     ┌──────▼─────┐
+///        │ PickSplitKey │                     │MergeRegions│
+///        └───────┬──────┘                     └──────┬─────┘
+///                │ Finds the median key              │ Moves the rows of the
+///                │ of the region                     │ merged region into the
+///                │                                   │ region, and drops it
+///         ┌──────▼────┐ Failed ┌─────────────┐       │
+///         │SplitRegion├───────►│RollbackSplit│       │
+///         └──────┬────┘        └─────────────┘       │
+///                │ Creates the  Merges the new       │
+///                │ new region,  region back, and     │
+///                │ and moves    fails the procedure  │
+///                │ rows into it                      │
+///                └────────────────┬──────────────────┘
+///                       ┌─────────▼────────┐
+///                       │UpdateRegionRoutes│
+///                       └─────────┬────────┘
+///                                 │ Rewrites the regions and their
+///                                 │ partition bounds in the table route
+///                                 │
+///                         ┌───────▼───────┐
+///                         │InvalidateCache│
+///                         └───────┬───────┘
+///                                 │ Broadcast Invalidate Table Cache
+///                                 │
+///                          ┌──────▼───────┐
+///                          │UpgradeRegions│
+///                          └──────┬───────┘
+///                                 │ Makes the regions accept writes
+///                                 │
+///                        ┌────────▼───────┐
+///                        │RegionBalanceEnd│
+///                        └────────────────┘
+/// ```
+pub struct RegionBalanceProcedure {
+    node: Node,
+    context: RegionBalanceContext,
+}
+
+impl RegionBalanceProcedure {
+    const TYPE_NAME: &'static str = "metasrv-procedure::RegionBalance";
+
+    fn new(task: RegionBalanceTask, context: RegionBalanceContext) -> Self {
+        let node = Node {
+            task,
+            state: Box::new(RegionBalanceStart),
+        };
+        Self { node, context }
+    }
+
+    fn from_json(json: &str, context: RegionBalanceContext) -> ProcedureResult<Self> {
+        let node: Node = serde_json::from_str(json).context(FromJsonSnafu)?;
+        Ok(Self { node, context })
+    }
+}
+
+#[async_trait]
+impl Procedure for RegionBalanceProcedure {
+    fn type_name(&self) -> &str {
+        Self::TYPE_NAME
+    }
+
+    async fn execute(&mut self, _ctx: &ProcedureContext) -> ProcedureResult<Status> {
+        let Node { task, state } = &mut self.node;
+        let next_state = state.next(&self.context, task).await.map_err(|e| {
+            if matches!(e, Error::RetryLater { .. }) {
+                ProcedureError::retry_later(e)
+            } else {
+                ProcedureError::external(e)
+            }
+        })?;
+        self.node.state = next_state;
+        Ok(self.node.state.status())
+    }
+
+    fn dump(&self) -> ProcedureResult<String> {
+        serde_json::to_string(&self.node).context(ToJsonSnafu)
+    }
+
+    fn lock_key(&self) -> LockKey {
+        // Locks the table, so that the regions of the table are never balanced, migrated or
+        // failed over at the same time.
+        let table_ident = &self.node.task.region.table_ident;
+        let table_key = common_catalog::format_full_table_name(
+            &table_ident.catalog,
+            &table_ident.schema,
+            &table_ident.table,
+        );
+        LockKey::single(table_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::v1::meta::mailbox_message::Payload;
+    use api::v1::meta::HeartbeatResponse;
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, MITO_ENGINE};
+    use common_meta::instruction::RegionSplitKeyReply;
+    use common_meta::rpc::router::{Partition as MetaPartition, RegionRoute};
+    use common_procedure::local::{LocalManager, ManagerConfig};
+    use common_procedure::BoxedProcedure;
+    use partition::partition::{PartitionBound, PartitionDef};
+    use tokio::sync::mpsc::Receiver;
+
+    use super::*;
+    use crate::procedure::region_failover::tests::TestingEnvBuilder;
+    use crate::procedure::state_store::MetaStateStore;
+    use crate::service::store::memory::MemStore;
+
+    pub struct TestingEnv {
+        pub context: RegionBalanceContext,
+        pub heartbeat_receivers: HashMap<DatanodeId, Receiver<tonic::Result<HeartbeatResponse>>>,
+    }
+
+    impl TestingEnv {
+        /// Creates a testing env with table "my_table" (id 1) and 3 Datanodes. See
+        /// [TestingEnvBuilder] of region failover for the region distribution. The table is
+        /// partitioned by RANGE on column "a", the bounds of region 1, 2, 3 and 4 are "b", "d",
+        /// "f" and MAXVALUE.
+        pub async fn new() -> Self {
+            let env = TestingEnvBuilder::new().build().await;
+            let context = env.context;
+
+            let table_route_value = context
+                .table_metadata_manager
+                .table_route_manager()
+                .get(1)
+                .await
+                .unwrap()
+                .unwrap();
+            let mut region_routes = table_route_value.region_routes.clone();
+            for route in region_routes.iter_mut() {
+                let bound = match route.region.id.region_number() {
+                    1 => PartitionBound::Value(Value::from("b")),
+                    2 => PartitionBound::Value(Value::from("d")),
+                    3 => PartitionBound::Value(Value::from("f")),
+                    _ => PartitionBound::MaxValue,
+                };
+                route.region.partition = Some(partition(bound));
+            }
+            context
+                .table_metadata_manager
+                .update_table_route(1, table_route_value, region_routes)
+                .await
+                .unwrap();
+
+            Self {
+                context: RegionBalanceContext {
+                    mailbox: context.mailbox,
+                    server_addr: context.selector_ctx.server_addr,
+                    dist_lock: context.dist_lock,
+                    table_metadata_manager: context.table_metadata_manager,
+                },
+                heartbeat_receivers: env.heartbeat_receivers,
+            }
+        }
+
+        /// Returns the routes of the regions of "my_table", ordered by region numbers.
+        pub async fn region_routes(&self) -> Vec<RegionRoute> {
+            let mut region_routes = self
+                .context
+                .table_metadata_manager
+                .table_route_manager()
+                .get(1)
+                .await
+                .unwrap()
+                .unwrap()
+                .region_routes;
+            region_routes.sort_by_key(|r| r.region.id.region_number());
+            region_routes
+        }
+    }
+
+    fn partition(bound: PartitionBound) -> MetaPartition {
+        MetaPartition::try_from(PartitionDef::new(vec!["a".to_string()], vec![bound])).unwrap()
+    }
+
+    /// Returns the region numbers, bounds and leaders of the region routes.
+    fn route_summary(region_routes: &[RegionRoute]) -> Vec<(RegionNumber, MetaPartition, u64)> {
+        region_routes
+            .iter()
+            .map(|r| {
+                (
+                    r.region.id.region_number(),
+                    r.region.partition.clone().unwrap(),
+                    r.leader_peer.as_ref().unwrap().id,
+                )
+            })
+            .collect()
+    }
+
+    /// Creates a task to split the region if `from_region_number` is `None`, or merge the region
+    /// `from_region_number` into the region.
+    pub fn new_task(
+        region_number: RegionNumber,
+        datanode_id: DatanodeId,
+        from_region_number: Option<RegionNumber>,
+    ) -> RegionBalanceTask {
+        let kind = match from_region_number {
+            None => BalanceKind::Split {
+                partition_columns: vec!["a".to_string()],
+            },
+            Some(from_region_number) => BalanceKind::Merge { from_region_number },
+        };
+        RegionBalanceTask {
+            region: RegionIdent {
+                cluster_id: 0,
+                datanode_id,
+                table_ident: TableIdent {
+                    catalog: DEFAULT_CATALOG_NAME.to_string(),
+                    schema: DEFAULT_SCHEMA_NAME.to_string(),
+                    table: "my_table".to_string(),
+                    table_id: 1,
+                    engine: MITO_ENGINE.to_string(),
+                },
+                region_number,
+            },
+            kind,
+        }
+    }
+
+    fn reply_message(id: u64, reply: InstructionReply) -> MailboxMessage {
+        MailboxMessage {
+            id,
+            subject: "Reply".to_string(),
+            from: "Datanode".to_string(),
+            to: "Metasrv".to_string(),
+            timestamp_millis: common_time::util::current_time_millis(),
+            payload: Some(Payload::Json(serde_json::to_string(&reply).unwrap())),
+        }
+    }
+
+    /// Replies the instructions received by the Datanode in order, each instruction is
+    /// expected to be the first of the pair.
+    fn mock_datanode_replies(
+        env: &mut TestingEnv,
+        datanode_id: DatanodeId,
+        replies: Vec<(Instruction, InstructionReply)>,
+    ) {
+        let mut rx = env.heartbeat_receivers.remove(&datanode_id).unwrap();
+        let mailbox = env.context.mailbox.clone();
+        let _handle = common_runtime::spawn_bg(async move {
+            for (expected, reply) in replies {
+                let resp = rx.recv().await.unwrap().unwrap();
+                let received = resp.mailbox_message.unwrap();
+                assert_eq!(
+                    received.payload,
+                    Some(Payload::Json(serde_json::to_string(&expected).unwrap()))
+                );
+                mailbox
+                    .on_recv(received.id, Ok(reply_message(received.id, reply)))
+                    .await
+                    .unwrap();
+            }
+        });
+    }
+
+    fn reply(result: bool) -> SimpleReply {
+        SimpleReply {
+            result,
+            error: (!result).then(|| "mocked".to_string()),
+        }
+    }
+
+    fn split_replies(
+        task: &RegionBalanceTask,
+        split_key: &str,
+        split_result: bool,
+    ) -> Vec<(Instruction, InstructionReply)> {
+        let split_key = vec![Value::from(split_key)];
+        vec![
+            (
+                Instruction::GetRegionSplitKey {
+                    region: task.region.clone(),
+                    partition_columns: vec!["a".to_string()],
+                },
+                InstructionReply::GetRegionSplitKey(RegionSplitKeyReply {
+                    result: true,
+                    split_key: Some(split_key.clone()),
+                    error: None,
+                }),
+            ),
+            (
+                Instruction::SplitRegion {
+                    region: task.region.clone(),
+                    new_region_number: 5,
+                    partition_columns: vec!["a".to_string()],
+                    split_key,
+                },
+                InstructionReply::SplitRegion(reply(split_result)),
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_split_region_procedure() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnv::new().await;
+        let task = new_task(1, 1, None);
+        // The region is split at "a5" into the new region 5, then both regions are upgraded.
+        let mut replies = split_replies(&task, "a5", true);
+        replies.extend([
+            (
+                Instruction::UpgradeRegion(task.region.clone()),
+                InstructionReply::UpgradeRegion(reply(true)),
+            ),
+            (
+                Instruction::UpgradeRegion(task.sibling_region(5)),
+                InstructionReply::UpgradeRegion(reply(true)),
+            ),
+        ]);
+        mock_datanode_replies(&mut env, 1, replies);
+
+        let mut procedure =
+            Box::new(RegionBalanceProcedure::new(task, env.context.clone())) as BoxedProcedure;
+        common_procedure_test::execute_procedure_until_done(&mut procedure).await;
+        assert!(procedure
+            .dump()
+            .unwrap()
+            .ends_with(r#""state":{"region_balance_state":"RegionBalanceEnd"}}"#));
+
+        // Region 1 holds the range ["a5", "b") now.
+        let region_routes = env.region_routes().await;
+        assert_eq!(
+            route_summary(&region_routes),
+            vec![
+                (1, partition(PartitionBound::Value(Value::from("a5"))), 1),
+                (2, partition(PartitionBound::Value(Value::from("d"))), 1),
+                (3, partition(PartitionBound::Value(Value::from("f"))), 2),
+                (4, partition(PartitionBound::MaxValue), 3),
+                (5, partition(PartitionBound::Value(Value::from("b"))), 1),
+            ]
+        );
+        let region_distribution = env
+            .context
+            .table_metadata_manager
+            .table_route_manager()
+            .get_region_distribution(1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(region_distribution.get(&1).unwrap(), &vec![1, 2, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_split_region_rollback() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnv::new().await;
+        let task = new_task(1, 1, None);
+        // The region fails to split, so the new region is merged back.
+        let mut replies = split_replies(&task, "a5", false);
+        replies.extend([
+            (
+                Instruction::MergeRegions {
+                    region: task.region.clone(),
+                    from_region_number: 5,
+                },
+                InstructionReply::MergeRegions(reply(true)),
+            ),
+            (
+                Instruction::UpgradeRegion(task.region.clone()),
+                InstructionReply::UpgradeRegion(reply(true)),
+            ),
+        ]);
+        mock_datanode_replies(&mut env, 1, replies);
+
+        let region_routes = env.region_routes().await;
+        let mut procedure = RegionBalanceProcedure::new(task, env.context.clone());
+        let ctx = ProcedureContext {
+            procedure_id: ProcedureId::random(),
+            provider: Arc::new(common_procedure_test::MockContextProvider::default()),
+        };
+
+        // RegionBalanceStart -> PickSplitKey -> SplitRegion -> RollbackSplit
+        for _ in 0..3 {
+            let _ = procedure.execute(&ctx).await.unwrap();
+        }
+        assert!(format!("{:?}", procedure.node.state).starts_with("RollbackSplit"));
+
+        // The procedure fails, and the table route is untouched.
+        let err = procedure.execute(&ctx).await.unwrap_err();
+        assert!(err.to_string().contains("mocked"), "{err}");
+        assert_eq!(env.region_routes().await, region_routes);
+    }
+
+    #[tokio::test]
+    async fn test_merge_regions_procedure() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnv::new().await;
+        let task = new_task(1, 1, Some(2));
+        mock_datanode_replies(
+            &mut env,
+            1,
+            vec![
+                (
+                    Instruction::MergeRegions {
+                        region: task.region.clone(),
+                        from_region_number: 2,
+                    },
+                    InstructionReply::MergeRegions(reply(true)),
+                ),
+                (
+                    Instruction::UpgradeRegion(task.region.clone()),
+                    InstructionReply::UpgradeRegion(reply(true)),
+                ),
+            ],
+        );
+
+        let mut procedure =
+            Box::new(RegionBalanceProcedure::new(task, env.context.clone())) as BoxedProcedure;
+        common_procedure_test::execute_procedure_until_done(&mut procedure).await;
+
+        // Region 1 holds the range of region 2 now.
+        let region_routes = env.region_routes().await;
+        assert_eq!(
+            route_summary(&region_routes),
+            vec![
+                (1, partition(PartitionBound::Value(Value::from("d"))), 1),
+                (3, partition(PartitionBound::Value(Value::from("f"))), 2),
+                (4, partition(PartitionBound::MaxValue), 3),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_balance_plan() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnv::new().await;
+        let state_store = Arc::new(MetaStateStore::new(Arc::new(MemStore::new())));
+        let procedure_manager: ProcedureManagerRef =
+            Arc::new(LocalManager::new(ManagerConfig::default(), state_store));
+        let manager = RegionBalanceManager::new(
+            env.context.mailbox.clone(),
+            procedure_manager.clone(),
+            env.context.server_addr.clone(),
+            env.context.dist_lock.clone(),
+            env.context.table_metadata_manager.clone(),
+        );
+        manager.try_start().unwrap();
+
+        let plan = BalancePlan::Split {
+            cluster_id: 0,
+            table_id: 1,
+            region_number: 3,
+            approximate_bytes: 0,
+            wcus: 0,
+        };
+        let procedure_id = manager.submit_balance_plan(&plan).await.unwrap().unwrap();
+        // The table is being balanced, so the plan is skipped.
+        assert!(manager.submit_balance_plan(&plan).await.unwrap().is_none());
+
+        let task = new_task(3, 2, None);
+        let mut replies = split_replies(&task, "e", true);
+        replies.extend([
+            (
+                Instruction::UpgradeRegion(task.region.clone()),
+                InstructionReply::UpgradeRegion(reply(true)),
+            ),
+            (
+                Instruction::UpgradeRegion(task.sibling_region(5)),
+                InstructionReply::UpgradeRegion(reply(true)),
+            ),
+        ]);
+        mock_datanode_replies(&mut env, 2, replies);
+
+        let mut watcher = procedure_manager.procedure_watcher(procedure_id).unwrap();
+        watcher::wait(&mut watcher).await.unwrap();
+        let region_routes = env.region_routes().await;
+        assert_eq!(
+            route_summary(&region_routes)[4],
+            (5, partition(PartitionBound::Value(Value::from("f"))), 2),
+        );
+        assert_eq!(
+            route_summary(&region_routes)[2],
+            (3, partition(PartitionBound::Value(Value::from("e"))), 2),
+        );
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt};
+use store_api::storage::MAX_REGION_SEQ;
+
+use super::merge_regions::MergeRegions;
+use super::pick_split_key::PickSplitKey;
+use super::{BalanceKind, RegionBalanceContext, RegionBalanceTask, State};
+use crate::balancer::sort_range_partitions;
+use crate::error::{self, Result};
+
+/// Checks the plan against the latest table route, which could be changed by other procedures
+/// after the plan is made.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct RegionBalanceStart;
+
+#[async_trait]
+#[typetag::serde]
+impl State for RegionBalanceStart {
+    async fn next(
+        &mut self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>> {
+        let region = &task.region;
+        let table_route = ctx.table_route(&region.table_ident).await?;
+        let partitions = sort_range_partitions(&table_route.region_routes).context(
+            error::BalanceRegionSnafu {
+                region: region.to_string(),
+                reason: "the table is not partitioned by RANGE",
+            },
+        )?;
+        let position = partitions
+            .iter()
+            .position(|(r, _)| r.region.id.region_number() == region.region_number)
+            .context(error::BalanceRegionSnafu {
+                region: region.to_string(),
+                reason: "the region is not found in the table route",
+            })?;
+
+        // The region could be moved by a region migration or failover procedure.
+        let leader = partitions[position].0.leader_peer.as_ref().map(|p| p.id);
+        ensure!(
+            leader == Some(region.datanode_id),
+            error::BalanceRegionSnafu {
+                region: region.to_string(),
+                reason: format!("the leader of the region has been changed to {leader:?}"),
+            }
+        );
+
+        match &task.kind {
+            BalanceKind::Split { .. } => {
+                let new_region_number = table_route
+                    .region_routes
+                    .iter()
+                    .map(|r| r.region.id.region_number())
+                    .max()
+                    .unwrap_or_default()
+                    + 1;
+                ensure!(
+                    new_region_number <= MAX_REGION_SEQ,
+                    error::BalanceRegionSnafu {
+                        region: region.to_string(),
+                        reason: "too many regions in the table",
+                    }
+                );
+                Ok(Box::new(PickSplitKey::new(new_region_number)))
+            }
+            BalanceKind::Merge { from_region_number } => {
+                // The range of the merged region has to follow the range of the region, so that
+                // the merged range is still contiguous.
+                let next = partitions.get(position + 1).map(|(r, _)| *r);
+                ensure!(
+                    next.map(|r| r.region.id.region_number()) == Some(*from_region_number),
+                    error::BalanceRegionSnafu {
+                        region: region.to_string(),
+                        reason: format!(
+                            "the range of region {from_region_number} doesn't follow the region"
+                        ),
+                    }
+                );
+                let from_leader = next.and_then(|r| r.leader_peer.as_ref()).map(|p| p.id);
+                ensure!(
+                    from_leader == leader,
+                    error::BalanceRegionSnafu {
+                        region: region.to_string(),
+                        reason: format!(
+                            "the leader of region {from_region_number} is {from_leader:?}"
+                        ),
+                    }
+                );
+                Ok(Box::new(MergeRegions::new(*from_region_number)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{new_task, TestingEnv};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_next_state() {
+        let env = TestingEnv::new().await;
+
+        // Regions 1 to 4 exist, so the new region is 5.
+        let task = new_task(1, 1, None);
+        let next_state = RegionBalanceStart.next(&env.context, &task).await.unwrap();
+        assert_eq!(
+            format!("{next_state:?}"),
+            "PickSplitKey { new_region_number: 5 }"
+        );
+
+        // Region 2 follows region 1 on Datanode 1.
+        let task = new_task(1, 1, Some(2));
+        let next_state = RegionBalanceStart.next(&env.context, &task).await.unwrap();
+        assert_eq!(
+            format!("{next_state:?}"),
+            "MergeRegions { from_region_number: 2 }"
+        );
+
+        // Region 3 is on Datanode 2.
+        let task = new_task(3, 1, None);
+        let result = RegionBalanceStart.next(&env.context, &task).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            error::Error::BalanceRegion { .. }
+        ));
+
+        // Region 3 follows region 2, but it's on another Datanode.
+        let task = new_task(2, 1, Some(3));
+        let result = RegionBalanceStart.next(&env.context, &task).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            error::Error::BalanceRegion { .. }
+        ));
+
+        // Region 2 doesn't follow region 3.
+        let task = new_task(3, 2, Some(2));
+        let result = RegionBalanceStart.next(&env.context, &task).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            error::Error::BalanceRegion { .. }
+        ));
+    }
+}
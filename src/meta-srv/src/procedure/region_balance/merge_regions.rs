@@ -0,0 +1,74 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, SimpleReply};
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+use store_api::storage::RegionNumber;
+
+use super::update_metadata::UpdateRegionRoutes;
+use super::{RegionBalanceContext, RegionBalanceTask, State, MOVE_REGION_ROWS_MESSAGE_TIMEOUT};
+use crate::error::{Result, RetryLaterSnafu};
+
+/// Moves the rows of the region `from_region_number` into the region, and drops the region
+/// `from_region_number` on the Datanode of the leader.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct MergeRegions {
+    from_region_number: RegionNumber,
+}
+
+impl MergeRegions {
+    pub(super) fn new(from_region_number: RegionNumber) -> Self {
+        Self { from_region_number }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for MergeRegions {
+    async fn next(
+        &mut self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>> {
+        let datanode_id = task.region.datanode_id;
+        let instruction = Instruction::MergeRegions {
+            region: task.region.clone(),
+            from_region_number: self.from_region_number,
+        };
+        let SimpleReply { result, error } = ctx
+            .simple_request(
+                "Merge Regions",
+                datanode_id,
+                &instruction,
+                MOVE_REGION_ROWS_MESSAGE_TIMEOUT,
+            )
+            .await?;
+
+        // Some rows of the merged region may have been moved, so the merge can't be rolled back
+        // but retried until it's done.
+        ensure!(
+            result,
+            RetryLaterSnafu {
+                reason: format!(
+                    "Region {} is not merged into region {} by Datanode {datanode_id}, \
+                    error: {error:?}",
+                    self.from_region_number, task.region,
+                ),
+            }
+        );
+        Ok(Box::new(UpdateRegionRoutes::new(None)))
+    }
+}
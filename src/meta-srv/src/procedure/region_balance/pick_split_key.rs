@@ -0,0 +1,224 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, InstructionReply, RegionSplitKeyReply};
+use common_telemetry::{debug, info};
+use serde::{Deserialize, Serialize};
+use store_api::storage::RegionNumber;
+
+use super::balance_end::RegionBalanceEnd;
+use super::split_region::SplitRegion;
+use super::{
+    RegionBalanceContext, RegionBalanceTask, RegionSplit, State, REGION_SPLIT_KEY_MESSAGE_TIMEOUT,
+};
+use crate::error::{
+    BalanceRegionSnafu, Error, Result, RetryLaterSnafu, UnexpectedInstructionReplySnafu,
+};
+use crate::handler::HeartbeatMailbox;
+use crate::service::mailbox::MailboxReceiver;
+
+/// Finds the key to split the region at, which is about the median of the partition columns of
+/// the rows in the region.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct PickSplitKey {
+    new_region_number: RegionNumber,
+}
+
+impl PickSplitKey {
+    pub(super) fn new(new_region_number: RegionNumber) -> Self {
+        Self { new_region_number }
+    }
+
+    fn instruction(task: &RegionBalanceTask) -> Instruction {
+        Instruction::GetRegionSplitKey {
+            region: task.region.clone(),
+            partition_columns: task.partition_columns().to_vec(),
+        }
+    }
+
+    async fn send_get_split_key_message(
+        &self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+        timeout: Duration,
+    ) -> Result<MailboxReceiver> {
+        ctx.send_instruction(
+            "Get Region Split Key",
+            task.region.datanode_id,
+            &Self::instruction(task),
+            timeout,
+        )
+        .await
+    }
+
+    async fn handle_response(
+        &self,
+        mailbox_receiver: MailboxReceiver,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>> {
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received region split key reply: {msg:?}");
+
+                let InstructionReply::GetRegionSplitKey(RegionSplitKeyReply {
+                    result,
+                    split_key,
+                    error,
+                }) = HeartbeatMailbox::json_reply(&msg)?
+                else {
+                    return UnexpectedInstructionReplySnafu {
+                        mailbox_message: msg.to_string(),
+                        reason: format!("expect the reply of {}", Self::instruction(task)),
+                    }
+                    .fail();
+                };
+
+                match split_key {
+                    Some(split_key) if result => Ok(Box::new(SplitRegion::new(RegionSplit {
+                        new_region_number: self.new_region_number,
+                        split_key,
+                    }))),
+                    None if result => {
+                        info!(
+                            "Region {} has too few distinct keys to split, skip splitting",
+                            task.region
+                        );
+                        Ok(Box::new(RegionBalanceEnd))
+                    }
+                    _ => BalanceRegionSnafu {
+                        region: task.region.to_string(),
+                        reason: format!("failed to get the split key, error: {error:?}"),
+                    }
+                    .fail(),
+                }
+            }
+            Err(Error::MailboxTimeout { .. }) => {
+                let reason = format!(
+                    "Mailbox received timeout for getting the split key of region {} on \
+                    Datanode {}",
+                    task.region, task.region.datanode_id,
+                );
+                RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for PickSplitKey {
+    async fn next(
+        &mut self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>> {
+        let mailbox_receiver = self
+            .send_get_split_key_message(ctx, task, REGION_SPLIT_KEY_MESSAGE_TIMEOUT)
+            .await?;
+
+        self.handle_response(mailbox_receiver, task).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::meta::mailbox_message::Payload;
+    use api::v1::meta::MailboxMessage;
+    use datatypes::value::Value;
+
+    use super::super::tests::{new_task, TestingEnv};
+    use super::*;
+
+    async fn pick_split_key(reply: RegionSplitKeyReply) -> Result<Box<dyn State>> {
+        let mut env = TestingEnv::new().await;
+        let task = new_task(1, 1, None);
+
+        let state = PickSplitKey::new(5);
+        let mailbox_receiver = state
+            .send_get_split_key_message(&env.context, &task, Duration::from_millis(100))
+            .await
+            .unwrap();
+        let message_id = mailbox_receiver.message_id();
+
+        // verify that the message is sent to the Datanode of the leader
+        let rx = env.heartbeat_receivers.get_mut(&1).unwrap();
+        let resp = rx.recv().await.unwrap().unwrap();
+        let received = &resp.mailbox_message.unwrap();
+        assert_eq!(received.id, message_id);
+        assert_eq!(received.subject, "Get Region Split Key");
+        assert_eq!(received.to, "Datanode-1");
+        assert_eq!(
+            received.payload,
+            Some(Payload::Json(
+                serde_json::to_string(&PickSplitKey::instruction(&task)).unwrap(),
+            ))
+        );
+
+        // simulating response from Datanode
+        env.context
+            .mailbox
+            .on_recv(
+                message_id,
+                Ok(MailboxMessage {
+                    id: message_id,
+                    subject: "Get Region Split Key".to_string(),
+                    from: "Datanode-1".to_string(),
+                    to: "Metasrv".to_string(),
+                    timestamp_millis: common_time::util::current_time_millis(),
+                    payload: Some(Payload::Json(
+                        serde_json::to_string(&InstructionReply::GetRegionSplitKey(reply)).unwrap(),
+                    )),
+                }),
+            )
+            .await
+            .unwrap();
+
+        state.handle_response(mailbox_receiver, &task).await
+    }
+
+    #[tokio::test]
+    async fn test_pick_split_key() {
+        common_telemetry::init_default_ut_logging();
+
+        let next_state = pick_split_key(RegionSplitKeyReply {
+            result: true,
+            split_key: Some(vec![Value::from("a5")]),
+            error: None,
+        })
+        .await
+        .unwrap();
+        assert!(format!("{next_state:?}").starts_with("SplitRegion"));
+
+        let next_state = pick_split_key(RegionSplitKeyReply {
+            result: true,
+            split_key: None,
+            error: None,
+        })
+        .await
+        .unwrap();
+        assert_eq!(format!("{next_state:?}"), "RegionBalanceEnd");
+
+        let result = pick_split_key(RegionSplitKeyReply {
+            result: false,
+            split_key: None,
+            error: Some("mocked".to_string()),
+        })
+        .await;
+        assert!(matches!(result.unwrap_err(), Error::BalanceRegion { .. }));
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, SimpleReply};
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+use store_api::storage::RegionNumber;
+
+use super::{
+    RegionBalanceContext, RegionBalanceTask, State, MOVE_REGION_ROWS_MESSAGE_TIMEOUT,
+    UPGRADE_REGION_MESSAGE_TIMEOUT,
+};
+use crate::error::{BalanceRegionSnafu, Result, RetryLaterSnafu};
+
+/// Merges the new region back into the region, makes the region writable again and fails the
+/// procedure. The table route is not updated yet, so the region still owns all its rows.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct RollbackSplit {
+    new_region_number: RegionNumber,
+    reason: String,
+}
+
+impl RollbackSplit {
+    pub(super) fn new(new_region_number: RegionNumber, reason: String) -> Self {
+        Self {
+            new_region_number,
+            reason,
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for RollbackSplit {
+    async fn next(
+        &mut self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>> {
+        let datanode_id = task.region.datanode_id;
+
+        // The new region may have been created and hold some rows of the region. Merging it is
+        // a no-op if it doesn't exist.
+        let instruction = Instruction::MergeRegions {
+            region: task.region.clone(),
+            from_region_number: self.new_region_number,
+        };
+        let SimpleReply { result, error } = ctx
+            .simple_request(
+                "Rollback Split Region",
+                datanode_id,
+                &instruction,
+                MOVE_REGION_ROWS_MESSAGE_TIMEOUT,
+            )
+            .await?;
+        ensure!(
+            result,
+            RetryLaterSnafu {
+                reason: format!(
+                    "Region {} is not merged back into region {} by Datanode {datanode_id}, \
+                    error: {error:?}",
+                    self.new_region_number, task.region,
+                ),
+            }
+        );
+
+        // The region stays read-only until it's upgraded, so retry until it is.
+        let instruction = Instruction::UpgradeRegion(task.region.clone());
+        let SimpleReply { result, error } = ctx
+            .simple_request(
+                "Rollback Region",
+                datanode_id,
+                &instruction,
+                UPGRADE_REGION_MESSAGE_TIMEOUT,
+            )
+            .await?;
+        ensure!(
+            result,
+            RetryLaterSnafu {
+                reason: format!(
+                    "Region {} is not upgraded by Datanode {datanode_id}, error: {error:?}",
+                    task.region,
+                ),
+            }
+        );
+
+        BalanceRegionSnafu {
+            region: task.region.to_string(),
+            reason: self.reason.clone(),
+        }
+        .fail()
+    }
+}
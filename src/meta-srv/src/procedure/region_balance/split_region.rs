@@ -0,0 +1,77 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, SimpleReply};
+use common_telemetry::warn;
+use serde::{Deserialize, Serialize};
+
+use super::rollback_split::RollbackSplit;
+use super::update_metadata::UpdateRegionRoutes;
+use super::{
+    RegionBalanceContext, RegionBalanceTask, RegionSplit, State, MOVE_REGION_ROWS_MESSAGE_TIMEOUT,
+};
+use crate::error::Result;
+
+/// Creates the new region on the Datanode of the leader, and moves the rows not less than the
+/// split key into it.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct SplitRegion {
+    split: RegionSplit,
+}
+
+impl SplitRegion {
+    pub(super) fn new(split: RegionSplit) -> Self {
+        Self { split }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for SplitRegion {
+    async fn next(
+        &mut self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>> {
+        let instruction = Instruction::SplitRegion {
+            region: task.region.clone(),
+            new_region_number: self.split.new_region_number,
+            partition_columns: task.partition_columns().to_vec(),
+            split_key: self.split.split_key.clone(),
+        };
+        let SimpleReply { result, error } = ctx
+            .simple_request(
+                "Split Region",
+                task.region.datanode_id,
+                &instruction,
+                MOVE_REGION_ROWS_MESSAGE_TIMEOUT,
+            )
+            .await?;
+
+        if result {
+            Ok(Box::new(UpdateRegionRoutes::new(Some(self.split.clone()))))
+        } else {
+            let reason = format!(
+                "Region {} is not split by Datanode {}, error: {error:?}",
+                task.region, task.region.datanode_id,
+            );
+            warn!("{reason}, rolling back");
+            Ok(Box::new(RollbackSplit::new(
+                self.split.new_region_number,
+                reason,
+            )))
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_meta::key::table_route::NextTableRouteKey;
+use common_meta::rpc::router::{Partition as MetaPartition, RegionRoute};
+use common_telemetry::info;
+use partition::partition::{PartitionBound, PartitionDef};
+use serde::{Deserialize, Serialize};
+use snafu::{OptionExt, ResultExt};
+use store_api::storage::{RegionId, RegionNumber};
+
+use super::invalidate_cache::InvalidateCache;
+use super::{BalanceKind, RegionBalanceContext, RegionBalanceTask, RegionSplit, State};
+use crate::error::{self, Result, RetryLaterSnafu};
+use crate::lock::keys::table_metadata_lock_key;
+use crate::lock::Opts;
+
+/// Rewrites the regions and their partition bounds in the table route.
+///
+/// For a split, the bound of the region becomes the split key, and the new region takes the
+/// original bound of the region. For a merge, the merged region is removed, and the region takes
+/// the bound of the merged region.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct UpdateRegionRoutes {
+    /// The new region if the region is split.
+    split: Option<RegionSplit>,
+}
+
+impl UpdateRegionRoutes {
+    pub(super) fn new(split: Option<RegionSplit>) -> Self {
+        Self { split }
+    }
+
+    fn new_region_number(&self) -> Option<RegionNumber> {
+        self.split.as_ref().map(|s| s.new_region_number)
+    }
+
+    async fn update_metadata(
+        &self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<()> {
+        let key = table_metadata_lock_key(&task.region);
+        let key = ctx.dist_lock.lock(key, Opts::default()).await?;
+
+        self.update_table_route(ctx, task).await?;
+
+        ctx.dist_lock.unlock(key).await?;
+        Ok(())
+    }
+
+    async fn update_table_route(
+        &self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<()> {
+        let table_id = task.region.table_ident.table_id;
+        let table_route_value = ctx.table_route(&task.region.table_ident).await?;
+
+        let Some(new_region_routes) =
+            self.new_region_routes(task, &table_route_value.region_routes)?
+        else {
+            info!("Region routes of {task} have been updated");
+            return Ok(());
+        };
+
+        info!(
+            "Updating region routes in table route value (key = '{}') for {task}",
+            NextTableRouteKey::new(table_id),
+        );
+
+        ctx.table_metadata_manager
+            .update_table_route(table_id, table_route_value, new_region_routes)
+            .await
+            .context(error::UpdateTableRouteSnafu)?;
+
+        Ok(())
+    }
+
+    /// Returns the region routes after the regions are split or merged, or `None` if the table
+    /// route has been updated.
+    fn new_region_routes(
+        &self,
+        task: &RegionBalanceTask,
+        region_routes: &[RegionRoute],
+    ) -> Result<Option<Vec<RegionRoute>>> {
+        let region = &task.region;
+        let position = |routes: &[RegionRoute], region_number| {
+            routes
+                .iter()
+                .position(|r| r.region.id.region_number() == region_number)
+        };
+
+        let mut new_region_routes = region_routes.to_vec();
+        match &task.kind {
+            BalanceKind::Split { partition_columns } => {
+                let split = self.split.as_ref().context(error::BalanceRegionSnafu {
+                    region: region.to_string(),
+                    reason: "the split key is missing",
+                })?;
+                if position(region_routes, split.new_region_number).is_some() {
+                    return Ok(None);
+                }
+                let i = position(region_routes, region.region_number).context(
+                    error::BalanceRegionSnafu {
+                        region: region.to_string(),
+                        reason: "the region is not found in the table route",
+                    },
+                )?;
+
+                let bounds = split
+                    .split_key
+                    .iter()
+                    .cloned()
+                    .map(PartitionBound::Value)
+                    .collect();
+                let partition =
+                    MetaPartition::try_from(PartitionDef::new(partition_columns.clone(), bounds))
+                        .context(error::ConvertPartitionSnafu {
+                        table_id: region.table_ident.table_id,
+                    })?;
+
+                let mut new_region_route = new_region_routes[i].clone();
+                new_region_route.region.id = RegionId::from_u64(split.new_region_number as u64);
+                new_region_routes[i].region.partition = Some(partition);
+                new_region_routes.push(new_region_route);
+            }
+            BalanceKind::Merge { from_region_number } => {
+                let Some(j) = position(region_routes, *from_region_number) else {
+                    return Ok(None);
+                };
+                let merged = new_region_routes.remove(j);
+                let i = position(&new_region_routes, region.region_number).context(
+                    error::BalanceRegionSnafu {
+                        region: region.to_string(),
+                        reason: "the region is not found in the table route",
+                    },
+                )?;
+                new_region_routes[i].region.partition = merged.region.partition;
+            }
+        }
+        Ok(Some(new_region_routes))
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for UpdateRegionRoutes {
+    async fn next(
+        &mut self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>> {
+        self.update_metadata(ctx, task).await.map_err(|e| {
+            RetryLaterSnafu {
+                reason: format!("Failed to update metadata for {task}, error: {e}"),
+            }
+            .build()
+        })?;
+        Ok(Box::new(InvalidateCache::new(self.new_region_number())))
+    }
+}
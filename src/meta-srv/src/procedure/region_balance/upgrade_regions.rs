@@ -0,0 +1,71 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, SimpleReply};
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+use store_api::storage::RegionNumber;
+
+use super::balance_end::RegionBalanceEnd;
+use super::{RegionBalanceContext, RegionBalanceTask, State, UPGRADE_REGION_MESSAGE_TIMEOUT};
+use crate::error::{Result, RetryLaterSnafu};
+
+/// Upgrades the region, and the new region if the region is split, so that they accept writes
+/// again. The table route has been updated, so retry until they are upgraded.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct UpgradeRegions {
+    new_region_number: Option<RegionNumber>,
+}
+
+impl UpgradeRegions {
+    pub(super) fn new(new_region_number: Option<RegionNumber>) -> Self {
+        Self { new_region_number }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for UpgradeRegions {
+    async fn next(
+        &mut self,
+        ctx: &RegionBalanceContext,
+        task: &RegionBalanceTask,
+    ) -> Result<Box<dyn State>> {
+        let region_numbers =
+            std::iter::once(task.region.region_number).chain(self.new_region_number);
+        for region_number in region_numbers {
+            let region = task.sibling_region(region_number);
+            let instruction = Instruction::UpgradeRegion(region.clone());
+            let SimpleReply { result, error } = ctx
+                .simple_request(
+                    "Upgrade Region",
+                    region.datanode_id,
+                    &instruction,
+                    UPGRADE_REGION_MESSAGE_TIMEOUT,
+                )
+                .await?;
+            ensure!(
+                result,
+                RetryLaterSnafu {
+                    reason: format!(
+                        "Region {region} is not upgraded by Datanode {}, error: {error:?}",
+                        region.datanode_id,
+                    ),
+                }
+            );
+        }
+        Ok(Box::new(RegionBalanceEnd))
+    }
+}
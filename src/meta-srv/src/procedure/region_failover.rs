@@ -360,7 +360,7 @@ impl Procedure for RegionFailoverProcedure {
 }
 
 #[cfg(test)]
-mod tests {
+pub(super) mod tests {
     use std::collections::HashMap;
 
     use api::v1::meta::mailbox_message::Payload;
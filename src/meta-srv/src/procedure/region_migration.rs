@@ -0,0 +1,704 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod close_source_region;
+mod downgrade_leader_region;
+mod invalidate_cache;
+mod migration_end;
+mod migration_start;
+mod open_candidate_region;
+mod rollback_candidate_region;
+mod update_metadata;
+mod upgrade_candidate_region;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::v1::meta::MailboxMessage;
+use async_trait::async_trait;
+use common_meta::ident::TableIdent;
+use common_meta::instruction::{Instruction, InstructionReply, SimpleReply};
+use common_meta::key::TableMetadataManagerRef;
+use common_meta::peer::Peer;
+use common_meta::{ClusterId, DatanodeId, RegionIdent};
+use common_procedure::error::{
+    Error as ProcedureError, FromJsonSnafu, Result as ProcedureResult, ToJsonSnafu,
+};
+use common_procedure::{
+    watcher, Context as ProcedureContext, LockKey, Procedure, ProcedureId, ProcedureManagerRef,
+    ProcedureWithId, Status,
+};
+use common_telemetry::{error, info};
+use migration_start::RegionMigrationStart;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt, ResultExt};
+use store_api::storage::RegionNumber;
+use table::metadata::TableId;
+
+use crate::error::{
+    self, Error, RegisterProcedureLoaderSnafu, Result, SerializeToJsonSnafu,
+    UnexpectedInstructionReplySnafu,
+};
+use crate::handler::HeartbeatMailbox;
+use crate::lease;
+use crate::lock::DistLockRef;
+use crate::metasrv::SelectorContext;
+use crate::service::mailbox::{Channel, MailboxReceiver, MailboxRef};
+use crate::service::store::kv::ResettableKvStoreRef;
+
+const OPEN_REGION_MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+const CLOSE_REGION_MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+// Downgrading the leader region flushes its memtables, so it may take a while.
+const DOWNGRADE_REGION_MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+// Upgrading the candidate region reopens it.
+const UPGRADE_REGION_MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A request to move a region from one Datanode to another.
+#[derive(Debug, Clone)]
+pub struct RegionMigrationRequest {
+    pub cluster_id: ClusterId,
+    pub table_id: TableId,
+    pub region_number: RegionNumber,
+    pub from_peer_id: DatanodeId,
+    pub to_peer_id: DatanodeId,
+}
+
+pub type RegionMigrationManagerRef = Arc<RegionMigrationManager>;
+
+/// Submits operator-triggered region migration procedures.
+pub struct RegionMigrationManager {
+    procedure_manager: ProcedureManagerRef,
+    context: RegionMigrationContext,
+}
+
+impl RegionMigrationManager {
+    pub(crate) fn new(
+        in_memory: ResettableKvStoreRef,
+        mailbox: MailboxRef,
+        procedure_manager: ProcedureManagerRef,
+        selector_ctx: SelectorContext,
+        dist_lock: DistLockRef,
+        table_metadata_manager: TableMetadataManagerRef,
+    ) -> Self {
+        Self {
+            procedure_manager,
+            context: RegionMigrationContext {
+                in_memory,
+                mailbox,
+                selector_ctx,
+                dist_lock,
+                table_metadata_manager,
+            },
+        }
+    }
+
+    pub(crate) fn try_start(&self) -> Result<()> {
+        let context = self.context.clone();
+        self.procedure_manager
+            .register_loader(
+                RegionMigrationProcedure::TYPE_NAME,
+                Box::new(move |json| {
+                    let context = context.clone();
+                    RegionMigrationProcedure::from_json(json, context).map(|p| Box::new(p) as _)
+                }),
+            )
+            .context(RegisterProcedureLoaderSnafu {
+                type_name: RegionMigrationProcedure::TYPE_NAME,
+            })
+    }
+
+    /// Validates the request and submits a region migration procedure in background.
+    /// Returns the id of the submitted procedure.
+    pub async fn submit_region_migration(
+        &self,
+        request: RegionMigrationRequest,
+    ) -> Result<ProcedureId> {
+        let task = self.build_task(&request).await?;
+
+        let procedure = RegionMigrationProcedure::new(task.clone(), self.context.clone());
+        let procedure_with_id = ProcedureWithId::with_random_id(Box::new(procedure));
+        let procedure_id = procedure_with_id.id;
+        info!("Starting region migration procedure {procedure_id} for {task}");
+
+        let mut watcher = self
+            .procedure_manager
+            .submit(procedure_with_id)
+            .await
+            .context(error::SubmitProcedureSnafu)?;
+
+        let _handle = common_runtime::spawn_bg(async move {
+            if let Err(e) = watcher::wait(&mut watcher).await {
+                error!(e; "Failed to wait region migration procedure {procedure_id} for {task}");
+                return;
+            }
+
+            info!("Region migration procedure {procedure_id} for {task} is finished successfully!");
+        });
+        Ok(procedure_id)
+    }
+
+    async fn build_task(&self, request: &RegionMigrationRequest) -> Result<RegionMigrationTask> {
+        let RegionMigrationRequest {
+            cluster_id,
+            table_id,
+            region_number,
+            from_peer_id,
+            to_peer_id,
+        } = *request;
+        ensure!(
+            from_peer_id != to_peer_id,
+            error::InvalidArgumentsSnafu {
+                err_msg: format!("region {region_number} is already on Datanode {to_peer_id}"),
+            }
+        );
+
+        let table_info = self
+            .context
+            .table_metadata_manager
+            .table_info_manager()
+            .get(table_id)
+            .await
+            .context(error::TableMetadataManagerSnafu)?
+            .with_context(|| error::TableInfoNotFoundSnafu {
+                table_name: table_id.to_string(),
+            })?
+            .table_info;
+
+        let region = RegionIdent {
+            cluster_id,
+            datanode_id: from_peer_id,
+            table_ident: TableIdent {
+                catalog: table_info.catalog_name,
+                schema: table_info.schema_name,
+                table: table_info.name,
+                table_id,
+                engine: table_info.meta.engine,
+            },
+            region_number,
+        };
+        let leader = self.context.region_leader(&region).await?;
+        ensure!(
+            leader == Some(from_peer_id),
+            error::InvalidArgumentsSnafu {
+                err_msg: format!(
+                    "the leader of region {region_number} is {leader:?}, \
+                    not Datanode {from_peer_id}"
+                ),
+            }
+        );
+
+        let to_peer = lease::alive_datanodes(
+            cluster_id,
+            &self.context.selector_ctx.meta_peer_client,
+            self.context.selector_ctx.datanode_lease_secs,
+        )
+        .await?
+        .into_iter()
+        .find_map(|(k, v)| (k.node_id == to_peer_id).then(|| Peer::new(k.node_id, v.node_addr)))
+        .with_context(|| error::InvalidArgumentsSnafu {
+            err_msg: format!("Datanode {to_peer_id} is not alive"),
+        })?;
+
+        Ok(RegionMigrationTask { region, to_peer })
+    }
+}
+
+/// The region to migrate and where it goes. The `datanode_id` of `region` is the source Datanode.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct RegionMigrationTask {
+    region: RegionIdent,
+    to_peer: Peer,
+}
+
+impl RegionMigrationTask {
+    /// Returns the [RegionIdent] of the region on the target Datanode.
+    fn candidate_region(&self) -> RegionIdent {
+        RegionIdent {
+            datanode_id: self.to_peer.id,
+            ..self.region.clone()
+        }
+    }
+}
+
+impl std::fmt::Display for RegionMigrationTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "region {} (from Datanode {} to Datanode {})",
+            self.region, self.region.datanode_id, self.to_peer.id
+        )
+    }
+}
+
+/// A "Node" in the state machine of region migration procedure.
+/// Contains the current state and the data.
+#[derive(Serialize, Deserialize, Debug)]
+struct Node {
+    task: RegionMigrationTask,
+    state: Box<dyn State>,
+}
+
+/// The "Context" of region migration procedure state machine.
+#[derive(Clone)]
+pub struct RegionMigrationContext {
+    pub in_memory: ResettableKvStoreRef,
+    pub mailbox: MailboxRef,
+    pub selector_ctx: SelectorContext,
+    pub dist_lock: DistLockRef,
+    pub table_metadata_manager: TableMetadataManagerRef,
+}
+
+impl RegionMigrationContext {
+    /// Returns the id of the Datanode where the leader of `region` resides, according to the
+    /// table route.
+    async fn region_leader(&self, region: &RegionIdent) -> Result<Option<DatanodeId>> {
+        let table_route_value = self
+            .table_metadata_manager
+            .table_route_manager()
+            .get(region.table_ident.table_id)
+            .await
+            .context(error::TableMetadataManagerSnafu)?
+            .with_context(|| error::TableRouteNotFoundSnafu {
+                table_name: region.table_ident.table_ref().to_string(),
+            })?;
+
+        let leader = table_route_value
+            .region_routes
+            .iter()
+            .find(|x| x.region.id == region.region_number as u64)
+            .with_context(|| error::InvalidArgumentsSnafu {
+                err_msg: format!("region {} not found in table route", region.region_number),
+            })?
+            .leader_peer
+            .as_ref()
+            .map(|p| p.id);
+        Ok(leader)
+    }
+
+    async fn send_instruction(
+        &self,
+        subject: &str,
+        datanode_id: DatanodeId,
+        instruction: &Instruction,
+        timeout: Duration,
+    ) -> Result<MailboxReceiver> {
+        let msg = MailboxMessage::json_message(
+            subject,
+            &format!("Metasrv@{}", self.selector_ctx.server_addr),
+            &format!("Datanode-{datanode_id}"),
+            common_time::util::current_time_millis(),
+            instruction,
+        )
+        .with_context(|_| SerializeToJsonSnafu {
+            input: instruction.to_string(),
+        })?;
+
+        let ch = Channel::Datanode(datanode_id);
+        self.mailbox.send(&ch, msg, timeout).await
+    }
+}
+
+/// Extracts the [SimpleReply] of the region `instruction` from the mailbox message.
+fn simple_reply(msg: &MailboxMessage, instruction: &Instruction) -> Result<SimpleReply> {
+    let reply = HeartbeatMailbox::json_reply(msg)?;
+    match (instruction, reply) {
+        (Instruction::OpenRegion(_), InstructionReply::OpenRegion(reply))
+        | (Instruction::CloseRegion(_), InstructionReply::CloseRegion(reply))
+        | (Instruction::DowngradeRegion(_), InstructionReply::DowngradeRegion(reply))
+        | (Instruction::UpgradeRegion(_), InstructionReply::UpgradeRegion(reply)) => Ok(reply),
+        _ => UnexpectedInstructionReplySnafu {
+            mailbox_message: msg.to_string(),
+            reason: format!("expect the reply of {instruction}"),
+        }
+        .fail(),
+    }
+}
+
+/// The state machine of region migration procedure. Driven by the call to `next`.
+///
+/// The current state is kept if `next` fails, so that it's executed again when the procedure
+/// is retried.
+#[async_trait]
+#[typetag::serde(tag = "region_migration_state")]
+trait State: Sync + Send + Debug {
+    async fn next(
+        &mut self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>>;
+
+    fn status(&self) -> Status {
+        Status::executing(true)
+    }
+}
+
+/// The states transition of region migration procedure.
+///
+/// The region keeps serving reads and writes on the source Datanode while the candidate region
+/// is opened on the target Datanode. Datanodes write WAL to their local disks, so the leader is
+/// only downgraded (made read-only and flushed to the shared storage) once the candidate is
+/// ready, and the candidate then catches up with the flushed data. Writes are rejected only
+/// between downgrading the leader and flipping the route. Before the route is flipped, a rollback
+/// just drops the candidate (and makes the leader writable again if it has been downgraded):
+///
+/// ```text
+///                 ┌────────────────────┐
+///                 │RegionMigrationStart│
+///                 └─────────┬──────────┘
+///                           │ Checks the region leader is
+///                           │ still the source Datanode
+///                           │
+///                 ┌─────────▼─────────┐     Failed     ┌───────────────────────┐
+///                 │OpenCandidateRegion├───────────────►│RollbackCandidateRegion│
+///                 └─────────┬─────────┘                └───────────▲───────────┘
+///                           │ Sends "Open Region" request          │ Closes the candidate
+///                           │ to the target Datanode, which        │ region, reopens the
+///                           │ replays the region from the          │ downgraded leader and
+///                           │ shared storage                       │ fails the procedure
+///                           │                                      │
+///                ┌──────────▼──────────┐        Failed             │
+///                │DowngradeLeaderRegion├───────────────────────────┤
+///                └──────────┬──────────┘                           │
+///                           │ Sends "Downgrade Region" request     │
+///                           │ to the source Datanode, which        │
+///                           │ rejects writes and flushes           │
+///                           │                                      │
+///                ┌──────────▼───────────┐       Failed             │
+///                │UpgradeCandidateRegion├──────────────────────────┘
+///                └──────────┬───────────┘
+///                           │ Sends "Upgrade Region" request to the
+///                           │ target Datanode, which catches up with
+///                           │ the flushed data and accepts writes
+///                           │
+///                  ┌────────▼────────┐
+///                  │UpdateRegionRoute│
+///                  └────────┬────────┘
+///                           │ Flips the region leader to
+///                           │ the target Datanode
+///                           │
+///                   ┌───────▼───────┐
+///                   │InvalidateCache│
+///                   └───────┬───────┘
+///                           │ Broadcast Invalidate Table
+///                           │ Cache
+///                           │
+///                 ┌─────────▼───────┐
+///                 │CloseSourceRegion│
+///                 └─────────┬───────┘
+///                           │ Sends "Close Region" request
+///                           │ to the source Datanode
+///                           │
+///                 ┌─────────▼────────┐
+///                 │RegionMigrationEnd│
+///                 └──────────────────┘
+/// ```
+pub struct RegionMigrationProcedure {
+    node: Node,
+    context: RegionMigrationContext,
+}
+
+impl RegionMigrationProcedure {
+    const TYPE_NAME: &'static str = "metasrv-procedure::RegionMigration";
+
+    fn new(task: RegionMigrationTask, context: RegionMigrationContext) -> Self {
+        let node = Node {
+            task,
+            state: Box::new(RegionMigrationStart),
+        };
+        Self { node, context }
+    }
+
+    fn from_json(json: &str, context: RegionMigrationContext) -> ProcedureResult<Self> {
+        let node: Node = serde_json::from_str(json).context(FromJsonSnafu)?;
+        Ok(Self { node, context })
+    }
+}
+
+#[async_trait]
+impl Procedure for RegionMigrationProcedure {
+    fn type_name(&self) -> &str {
+        Self::TYPE_NAME
+    }
+
+    async fn execute(&mut self, _ctx: &ProcedureContext) -> ProcedureResult<Status> {
+        let Node { task, state } = &mut self.node;
+        let next_state = state.next(&self.context, task).await.map_err(|e| {
+            if matches!(e, Error::RetryLater { .. }) {
+                ProcedureError::retry_later(e)
+            } else {
+                ProcedureError::external(e)
+            }
+        })?;
+        self.node.state = next_state;
+        Ok(self.node.state.status())
+    }
+
+    fn dump(&self) -> ProcedureResult<String> {
+        serde_json::to_string(&self.node).context(ToJsonSnafu)
+    }
+
+    fn lock_key(&self) -> LockKey {
+        // Same keys as the region failover procedure, so that a region is never failed over and
+        // migrated at the same time.
+        let region_ident = &self.node.task.region;
+        let table_key = common_catalog::format_full_table_name(
+            &region_ident.table_ident.catalog,
+            &region_ident.table_ident.schema,
+            &region_ident.table_ident.table,
+        );
+        let region_key = format!("{}/region-{}", table_key, region_ident.region_number);
+        LockKey::new(vec![table_key, region_key])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::v1::meta::mailbox_message::Payload;
+    use api::v1::meta::HeartbeatResponse;
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, MITO_ENGINE};
+    use common_procedure::BoxedProcedure;
+    use tokio::sync::mpsc::Receiver;
+
+    use super::*;
+    use crate::inactive_node_manager::InactiveNodeManager;
+    use crate::procedure::region_failover::tests::TestingEnvBuilder;
+
+    pub struct TestingEnv {
+        pub context: RegionMigrationContext,
+        pub heartbeat_receivers: HashMap<DatanodeId, Receiver<tonic::Result<HeartbeatResponse>>>,
+    }
+
+    impl TestingEnv {
+        /// Creates a testing env with table "my_table" (id 1) and 3 Datanodes. See
+        /// [TestingEnvBuilder] of region failover for the region distribution.
+        pub async fn new() -> Self {
+            let env = TestingEnvBuilder::new().build().await;
+            let context = env.context;
+            Self {
+                context: RegionMigrationContext {
+                    in_memory: context.in_memory,
+                    mailbox: context.mailbox,
+                    selector_ctx: context.selector_ctx,
+                    dist_lock: context.dist_lock,
+                    table_metadata_manager: context.table_metadata_manager,
+                },
+                heartbeat_receivers: env.heartbeat_receivers,
+            }
+        }
+    }
+
+    pub fn new_task(
+        region_number: u32,
+        from_peer_id: DatanodeId,
+        to_peer: Peer,
+    ) -> RegionMigrationTask {
+        RegionMigrationTask {
+            region: RegionIdent {
+                cluster_id: 0,
+                datanode_id: from_peer_id,
+                table_ident: TableIdent {
+                    catalog: DEFAULT_CATALOG_NAME.to_string(),
+                    schema: DEFAULT_SCHEMA_NAME.to_string(),
+                    table: "my_table".to_string(),
+                    table_id: 1,
+                    engine: MITO_ENGINE.to_string(),
+                },
+                region_number,
+            },
+            to_peer,
+        }
+    }
+
+    fn reply_message(id: u64, reply: InstructionReply) -> MailboxMessage {
+        MailboxMessage {
+            id,
+            subject: "Reply".to_string(),
+            from: "Datanode".to_string(),
+            to: "Metasrv".to_string(),
+            timestamp_millis: common_time::util::current_time_millis(),
+            payload: Some(Payload::Json(serde_json::to_string(&reply).unwrap())),
+        }
+    }
+
+    /// Replies the instructions received by the Datanode in order, each instruction is
+    /// expected to be the first of the pair.
+    fn mock_datanode_replies(
+        env: &mut TestingEnv,
+        datanode_id: DatanodeId,
+        replies: Vec<(Instruction, InstructionReply)>,
+    ) {
+        let mut rx = env.heartbeat_receivers.remove(&datanode_id).unwrap();
+        let mailbox = env.context.mailbox.clone();
+        let _handle = common_runtime::spawn_bg(async move {
+            for (expected, reply) in replies {
+                let resp = rx.recv().await.unwrap().unwrap();
+                let received = resp.mailbox_message.unwrap();
+                assert_eq!(
+                    received.payload,
+                    Some(Payload::Json(serde_json::to_string(&expected).unwrap()))
+                );
+                mailbox
+                    .on_recv(received.id, Ok(reply_message(received.id, reply)))
+                    .await
+                    .unwrap();
+            }
+        });
+    }
+
+    fn reply(result: bool) -> SimpleReply {
+        SimpleReply {
+            result,
+            error: (!result).then(|| "mocked".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_region_migration_procedure() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnv::new().await;
+        let task = new_task(1, 1, Peer::new(2, ""));
+        // The candidate is opened and upgraded on the target Datanode, and the leader is
+        // downgraded and then closed on the source Datanode.
+        mock_datanode_replies(
+            &mut env,
+            2,
+            vec![
+                (
+                    Instruction::OpenRegion(task.candidate_region()),
+                    InstructionReply::OpenRegion(reply(true)),
+                ),
+                (
+                    Instruction::UpgradeRegion(task.candidate_region()),
+                    InstructionReply::UpgradeRegion(reply(true)),
+                ),
+            ],
+        );
+        mock_datanode_replies(
+            &mut env,
+            1,
+            vec![
+                (
+                    Instruction::DowngradeRegion(task.region.clone()),
+                    InstructionReply::DowngradeRegion(reply(true)),
+                ),
+                (
+                    Instruction::CloseRegion(task.region.clone()),
+                    InstructionReply::CloseRegion(reply(true)),
+                ),
+            ],
+        );
+
+        let mut procedure = Box::new(RegionMigrationProcedure::new(
+            task.clone(),
+            env.context.clone(),
+        )) as BoxedProcedure;
+        common_procedure_test::execute_procedure_until_done(&mut procedure).await;
+
+        assert_eq!(
+            procedure.dump().unwrap(),
+            r#"{"task":{"region":{"cluster_id":0,"datanode_id":1,"table_ident":{"catalog":"greptime","schema":"public","table":"my_table","table_id":1,"engine":"mito"},"region_number":1},"to_peer":{"id":2,"addr":""}},"state":{"region_migration_state":"RegionMigrationEnd"}}"#
+        );
+
+        // Verifies that region 1 is moved from Datanode 1 to Datanode 2.
+        let region_distribution = env
+            .context
+            .table_metadata_manager
+            .table_route_manager()
+            .get_region_distribution(1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(region_distribution.get(&1).unwrap(), &vec![2]);
+        assert_eq!(region_distribution.get(&2).unwrap(), &vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_region_migration_rollback() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnv::new().await;
+        let task = new_task(1, 1, Peer::new(2, ""));
+        // The candidate fails to catch up, so it's closed.
+        mock_datanode_replies(
+            &mut env,
+            2,
+            vec![
+                (
+                    Instruction::OpenRegion(task.candidate_region()),
+                    InstructionReply::OpenRegion(reply(true)),
+                ),
+                (
+                    Instruction::UpgradeRegion(task.candidate_region()),
+                    InstructionReply::UpgradeRegion(reply(false)),
+                ),
+                (
+                    Instruction::CloseRegion(task.candidate_region()),
+                    InstructionReply::CloseRegion(reply(true)),
+                ),
+            ],
+        );
+        // The downgraded leader is upgraded again.
+        mock_datanode_replies(
+            &mut env,
+            1,
+            vec![
+                (
+                    Instruction::DowngradeRegion(task.region.clone()),
+                    InstructionReply::DowngradeRegion(reply(true)),
+                ),
+                (
+                    Instruction::UpgradeRegion(task.region.clone()),
+                    InstructionReply::UpgradeRegion(reply(true)),
+                ),
+            ],
+        );
+
+        let mut procedure = RegionMigrationProcedure::new(task.clone(), env.context.clone());
+        let ctx = ProcedureContext {
+            procedure_id: ProcedureId::random(),
+            provider: Arc::new(common_procedure_test::MockContextProvider::default()),
+        };
+
+        // RegionMigrationStart -> OpenCandidateRegion -> DowngradeLeaderRegion ->
+        // UpgradeCandidateRegion -> RollbackCandidateRegion
+        for _ in 0..4 {
+            let _ = procedure.execute(&ctx).await.unwrap();
+        }
+        assert!(format!("{:?}", procedure.node.state).starts_with("RollbackCandidateRegion"));
+
+        // The candidate is dropped, and the procedure fails.
+        let err = procedure.execute(&ctx).await.unwrap_err();
+        assert!(err.to_string().contains("mocked"), "{err}");
+        let region_distribution = env
+            .context
+            .table_metadata_manager
+            .table_route_manager()
+            .get_region_distribution(1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(region_distribution.get(&1).unwrap(), &vec![1, 2]);
+        // The lease of the candidate region is not renewed anymore.
+        let mut regions = vec![1];
+        InactiveNodeManager::new(&env.context.in_memory)
+            .retain_active_regions(0, 2, 1, &mut regions)
+            .await
+            .unwrap();
+        assert!(regions.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, SimpleReply};
+use common_telemetry::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use super::migration_end::RegionMigrationEnd;
+use super::{
+    simple_reply, RegionMigrationContext, RegionMigrationTask, State, CLOSE_REGION_MESSAGE_TIMEOUT,
+};
+use crate::error::{Error, Result};
+use crate::inactive_node_manager::InactiveNodeManager;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct CloseSourceRegion;
+
+#[async_trait]
+#[typetag::serde]
+impl State for CloseSourceRegion {
+    async fn next(
+        &mut self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        // Stops renewing the lease of the region on the source Datanode, so that the region will
+        // be closed by its region alive keeper eventually, even if the "Close Region" request
+        // is lost.
+        let inactive_node_manager = InactiveNodeManager::new(&ctx.in_memory);
+        inactive_node_manager
+            .register_inactive_region(&task.region)
+            .await?;
+
+        let instruction = Instruction::CloseRegion(task.region.clone());
+        let mailbox_receiver = ctx
+            .send_instruction(
+                "Close Source Region",
+                task.region.datanode_id,
+                &instruction,
+                CLOSE_REGION_MESSAGE_TIMEOUT,
+            )
+            .await?;
+
+        // The route has been flipped, so the migration is done even if the source region is
+        // not closed now.
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received close source region reply: {msg:?}");
+
+                let SimpleReply { result, error } = simple_reply(&msg, &instruction)?;
+                if result {
+                    inactive_node_manager
+                        .deregister_inactive_region(&task.region)
+                        .await?;
+                } else {
+                    warn!(
+                        "Region {} is not closed by Datanode {}, error: {error:?}, \
+                        wait for its lease to expire",
+                        task.region, task.region.datanode_id,
+                    );
+                }
+            }
+            Err(Error::MailboxTimeout { .. }) => {
+                warn!(
+                    "Mailbox received timeout for closing region {} on Datanode {}, \
+                    wait for its lease to expire",
+                    task.region, task.region.datanode_id,
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(Box::new(RegionMigrationEnd))
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, SimpleReply};
+use common_telemetry::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use super::rollback_candidate_region::RollbackCandidateRegion;
+use super::upgrade_candidate_region::UpgradeCandidateRegion;
+use super::{
+    simple_reply, RegionMigrationContext, RegionMigrationTask, State,
+    DOWNGRADE_REGION_MESSAGE_TIMEOUT,
+};
+use crate::error::{Error, Result, RetryLaterSnafu};
+use crate::service::mailbox::MailboxReceiver;
+
+/// Closes the region on the source Datanode. Closing flushes the region, so that all the data
+/// written before is persisted in the shared storage.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct DowngradeLeaderRegion;
+
+impl DowngradeLeaderRegion {
+    async fn send_downgrade_region_message(
+        &self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+        timeout: Duration,
+    ) -> Result<MailboxReceiver> {
+        // The region is still open on the source Datanode after it's downgraded, so it keeps
+        // serving reads until the route is flipped.
+        let instruction = Instruction::DowngradeRegion(task.region.clone());
+        ctx.send_instruction(
+            "Downgrade Leader Region",
+            task.region.datanode_id,
+            &instruction,
+            timeout,
+        )
+        .await
+    }
+
+    async fn handle_response(
+        &self,
+        mailbox_receiver: MailboxReceiver,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received downgrade leader region reply: {msg:?}");
+
+                let instruction = Instruction::DowngradeRegion(task.region.clone());
+                let SimpleReply { result, error } = simple_reply(&msg, &instruction)?;
+                if result {
+                    Ok(Box::new(UpgradeCandidateRegion))
+                } else {
+                    // The leader may have rejected writes before it failed to flush, so it's
+                    // reopened by the rollback.
+                    let reason = format!(
+                        "Region {} is not downgraded by Datanode {}, error: {error:?}",
+                        task.region, task.region.datanode_id,
+                    );
+                    warn!("{reason}, rolling back");
+                    Ok(Box::new(RollbackCandidateRegion::new(reason, true)))
+                }
+            }
+            Err(Error::MailboxTimeout { .. }) => {
+                // Unlike region failover, the source Datanode is supposed to be alive, so we
+                // can't assume the region is downgraded. Retry until it replies.
+                let reason = format!(
+                    "Mailbox received timeout for downgrading region {} on Datanode {}",
+                    task.region, task.region.datanode_id,
+                );
+                RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for DowngradeLeaderRegion {
+    async fn next(
+        &mut self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        let mailbox_receiver = self
+            .send_downgrade_region_message(ctx, task, DOWNGRADE_REGION_MESSAGE_TIMEOUT)
+            .await?;
+
+        self.handle_response(mailbox_receiver, task).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::meta::mailbox_message::Payload;
+    use api::v1::meta::MailboxMessage;
+    use common_meta::instruction::InstructionReply;
+    use common_meta::peer::Peer;
+
+    use super::super::tests::{new_task, TestingEnv};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_downgrade_leader_region() {
+        common_telemetry::init_default_ut_logging();
+
+        let mut env = TestingEnv::new().await;
+        let task = new_task(1, 1, Peer::new(2, ""));
+
+        let state = DowngradeLeaderRegion;
+        let mailbox_receiver = state
+            .send_downgrade_region_message(&env.context, &task, Duration::from_millis(100))
+            .await
+            .unwrap();
+        let message_id = mailbox_receiver.message_id();
+
+        // verify that the downgrade region message is sent to the source Datanode
+        let rx = env.heartbeat_receivers.get_mut(&1).unwrap();
+        let resp = rx.recv().await.unwrap().unwrap();
+        let received = &resp.mailbox_message.unwrap();
+        assert_eq!(received.id, message_id);
+        assert_eq!(received.subject, "Downgrade Leader Region");
+        assert_eq!(received.to, "Datanode-1");
+        assert_eq!(
+            received.payload,
+            Some(Payload::Json(
+                serde_json::to_string(&Instruction::DowngradeRegion(task.region.clone())).unwrap(),
+            ))
+        );
+
+        // simulating response from Datanode
+        env.context
+            .mailbox
+            .on_recv(
+                message_id,
+                Ok(MailboxMessage {
+                    id: message_id,
+                    subject: "Downgrade Leader Region".to_string(),
+                    from: "Datanode-1".to_string(),
+                    to: "Metasrv".to_string(),
+                    timestamp_millis: common_time::util::current_time_millis(),
+                    payload: Some(Payload::Json(
+                        serde_json::to_string(&InstructionReply::DowngradeRegion(SimpleReply {
+                            result: true,
+                            error: None,
+                        }))
+                        .unwrap(),
+                    )),
+                }),
+            )
+            .await
+            .unwrap();
+
+        let next_state = state
+            .handle_response(mailbox_receiver, &task)
+            .await
+            .unwrap();
+        assert_eq!(format!("{next_state:?}"), "UpgradeCandidateRegion");
+    }
+
+    #[tokio::test]
+    async fn test_downgrade_leader_region_timeout() {
+        common_telemetry::init_default_ut_logging();
+
+        let env = TestingEnv::new().await;
+        let task = new_task(1, 1, Peer::new(2, ""));
+
+        let state = DowngradeLeaderRegion;
+        let mailbox_receiver = state
+            .send_downgrade_region_message(&env.context, &task, Duration::from_millis(100))
+            .await
+            .unwrap();
+
+        let result = state.handle_response(mailbox_receiver, &task).await;
+        assert!(matches!(result.unwrap_err(), Error::RetryLater { .. }));
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api::v1::meta::MailboxMessage;
+use async_trait::async_trait;
+use common_meta::ident::TableIdent;
+use common_meta::instruction::Instruction;
+use common_telemetry::info;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use super::close_source_region::CloseSourceRegion;
+use super::{RegionMigrationContext, RegionMigrationTask, State};
+use crate::error::{self, Result};
+use crate::service::mailbox::BroadcastChannel;
+
+/// Broadcasts the "Invalidate Table Cache" message to Frontends, so that they route the requests
+/// of the region to the target Datanode.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct InvalidateCache;
+
+#[async_trait]
+#[typetag::serde]
+impl State for InvalidateCache {
+    async fn next(
+        &mut self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        let table_ident = TableIdent::from(task.region.clone());
+        info!(
+            "Broadcast invalidate table({}) cache message to frontend",
+            table_ident
+        );
+
+        let instruction = Instruction::InvalidateTableCache(table_ident);
+        let msg = &MailboxMessage::json_message(
+            "Invalidate Table Cache",
+            &format!("Metasrv@{}", ctx.selector_ctx.server_addr),
+            "Frontend broadcast",
+            common_time::util::current_time_millis(),
+            &instruction,
+        )
+        .with_context(|_| error::SerializeToJsonSnafu {
+            input: instruction.to_string(),
+        })?;
+        ctx.mailbox
+            .broadcast(&BroadcastChannel::Frontend, msg)
+            .await?;
+
+        Ok(Box::new(CloseSourceRegion))
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_telemetry::info;
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use super::migration_end::RegionMigrationEnd;
+use super::open_candidate_region::OpenCandidateRegion;
+use super::{RegionMigrationContext, RegionMigrationTask, State};
+use crate::error::{self, Result};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct RegionMigrationStart;
+
+#[async_trait]
+#[typetag::serde]
+impl State for RegionMigrationStart {
+    async fn next(
+        &mut self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        let leader = ctx.region_leader(&task.region).await?;
+        if leader == Some(task.to_peer.id) {
+            info!("The leader of {task} is already on the target Datanode, skip migration");
+            return Ok(Box::new(RegionMigrationEnd));
+        }
+
+        // The region could be moved by a region failover procedure after the migration is
+        // submitted.
+        ensure!(
+            leader == Some(task.region.datanode_id),
+            error::MigrateRegionSnafu {
+                region: task.region.to_string(),
+                reason: format!("the leader of the region has been changed to {leader:?}"),
+            }
+        );
+        Ok(Box::new(OpenCandidateRegion))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_meta::peer::Peer;
+
+    use super::super::tests::{new_task, TestingEnv};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_next_state() {
+        let env = TestingEnv::new().await;
+
+        // Region 1 is on Datanode 1.
+        let task = new_task(1, 1, Peer::new(2, ""));
+        let next_state = RegionMigrationStart
+            .next(&env.context, &task)
+            .await
+            .unwrap();
+        assert_eq!(format!("{next_state:?}"), "OpenCandidateRegion");
+
+        // Region 3 is already on Datanode 2.
+        let task = new_task(3, 1, Peer::new(2, ""));
+        let next_state = RegionMigrationStart
+            .next(&env.context, &task)
+            .await
+            .unwrap();
+        assert_eq!(format!("{next_state:?}"), "RegionMigrationEnd");
+
+        // Region 4 is on Datanode 3, not Datanode 1.
+        let task = new_task(4, 1, Peer::new(2, ""));
+        let result = RegionMigrationStart.next(&env.context, &task).await;
+        assert!(matches!(
+            result.unwrap_err(),
+            error::Error::MigrateRegion { .. }
+        ));
+    }
+}
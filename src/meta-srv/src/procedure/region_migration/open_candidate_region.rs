@@ -0,0 +1,187 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, SimpleReply};
+use common_telemetry::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use super::downgrade_leader_region::DowngradeLeaderRegion;
+use super::rollback_candidate_region::RollbackCandidateRegion;
+use super::{
+    simple_reply, RegionMigrationContext, RegionMigrationTask, State, OPEN_REGION_MESSAGE_TIMEOUT,
+};
+use crate::error::{Error, Result, RetryLaterSnafu};
+use crate::inactive_node_manager::InactiveNodeManager;
+use crate::service::mailbox::MailboxReceiver;
+
+/// Opens the region on the target Datanode, which replays the region from the shared storage.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct OpenCandidateRegion;
+
+impl OpenCandidateRegion {
+    async fn send_open_region_message(
+        &self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+        timeout: Duration,
+    ) -> Result<MailboxReceiver> {
+        // The target Datanode could once hold this region and be marked as inactive for it,
+        // clear that state so that the lease of the region can be renewed there.
+        let candidate = task.candidate_region();
+        InactiveNodeManager::new(&ctx.in_memory)
+            .deregister_inactive_region(&candidate)
+            .await?;
+
+        let instruction = Instruction::OpenRegion(candidate);
+        ctx.send_instruction(
+            "Open Candidate Region",
+            task.to_peer.id,
+            &instruction,
+            timeout,
+        )
+        .await
+    }
+
+    async fn handle_response(
+        &self,
+        mailbox_receiver: MailboxReceiver,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received open candidate region reply: {msg:?}");
+
+                let instruction = Instruction::OpenRegion(task.candidate_region());
+                let SimpleReply { result, error } = simple_reply(&msg, &instruction)?;
+                if result {
+                    Ok(Box::new(DowngradeLeaderRegion))
+                } else {
+                    // The leader is untouched, so only the candidate needs to be dropped.
+                    let reason = format!(
+                        "Region {} is not opened by Datanode {:?}, error: {error:?}",
+                        task.region, task.to_peer,
+                    );
+                    warn!("{reason}, rolling back");
+                    Ok(Box::new(RollbackCandidateRegion::new(reason, false)))
+                }
+            }
+            Err(Error::MailboxTimeout { .. }) => {
+                let reason = format!(
+                    "Mailbox received timeout for opening region {} on Datanode {:?}",
+                    task.region, task.to_peer,
+                );
+                RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for OpenCandidateRegion {
+    async fn next(
+        &mut self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        let mailbox_receiver = self
+            .send_open_region_message(ctx, task, OPEN_REGION_MESSAGE_TIMEOUT)
+            .await?;
+
+        self.handle_response(mailbox_receiver, task).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::meta::mailbox_message::Payload;
+    use api::v1::meta::MailboxMessage;
+    use common_meta::instruction::InstructionReply;
+    use common_meta::peer::Peer;
+
+    use super::super::tests::{new_task, TestingEnv};
+    use super::*;
+
+    async fn open_candidate_region(result: bool) -> Box<dyn State> {
+        let mut env = TestingEnv::new().await;
+        let task = new_task(1, 1, Peer::new(2, ""));
+
+        let state = OpenCandidateRegion;
+        let mailbox_receiver = state
+            .send_open_region_message(&env.context, &task, Duration::from_millis(100))
+            .await
+            .unwrap();
+        let message_id = mailbox_receiver.message_id();
+
+        // verify that the open region message is sent to the target Datanode
+        let rx = env.heartbeat_receivers.get_mut(&2).unwrap();
+        let resp = rx.recv().await.unwrap().unwrap();
+        let received = &resp.mailbox_message.unwrap();
+        assert_eq!(received.id, message_id);
+        assert_eq!(received.subject, "Open Candidate Region");
+        assert_eq!(received.to, "Datanode-2");
+        assert_eq!(
+            received.payload,
+            Some(Payload::Json(
+                serde_json::to_string(&Instruction::OpenRegion(task.candidate_region())).unwrap(),
+            ))
+        );
+
+        // simulating response from Datanode
+        env.context
+            .mailbox
+            .on_recv(
+                message_id,
+                Ok(MailboxMessage {
+                    id: message_id,
+                    subject: "Open Candidate Region".to_string(),
+                    from: "Datanode-2".to_string(),
+                    to: "Metasrv".to_string(),
+                    timestamp_millis: common_time::util::current_time_millis(),
+                    payload: Some(Payload::Json(
+                        serde_json::to_string(&InstructionReply::OpenRegion(SimpleReply {
+                            result,
+                            error: None,
+                        }))
+                        .unwrap(),
+                    )),
+                }),
+            )
+            .await
+            .unwrap();
+
+        state
+            .handle_response(mailbox_receiver, &task)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_open_candidate_region() {
+        common_telemetry::init_default_ut_logging();
+
+        let next_state = open_candidate_region(true).await;
+        assert_eq!(format!("{next_state:?}"), "DowngradeLeaderRegion");
+
+        let next_state = open_candidate_region(false).await;
+        assert_eq!(
+            format!("{next_state:?}"),
+            r#"RollbackCandidateRegion { reason: "Region RegionIdent(datanode_id='0.1', table_id='1', table_name='greptime.public.my_table', table_engine='mito', region_no='1') is not opened by Datanode Peer { id: 2, addr: \"\" }, error: None", leader_downgraded: false }"#
+        );
+    }
+}
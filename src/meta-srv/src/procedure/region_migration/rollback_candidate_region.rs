@@ -0,0 +1,156 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_meta::instruction::{Instruction, SimpleReply};
+use common_telemetry::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    simple_reply, RegionMigrationContext, RegionMigrationTask, State, CLOSE_REGION_MESSAGE_TIMEOUT,
+    UPGRADE_REGION_MESSAGE_TIMEOUT,
+};
+use crate::error::{Error, MigrateRegionSnafu, Result, RetryLaterSnafu};
+use crate::inactive_node_manager::InactiveNodeManager;
+
+/// Drops the candidate region and fails the procedure. The route is not flipped yet, so the
+/// source Datanode is still the leader of the region.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct RollbackCandidateRegion {
+    reason: String,
+    /// Whether the leader region has been (or may have been) downgraded, then it has to be
+    /// reopened to accept writes again.
+    leader_downgraded: bool,
+}
+
+impl RollbackCandidateRegion {
+    pub(super) fn new(reason: String, leader_downgraded: bool) -> Self {
+        Self {
+            reason,
+            leader_downgraded,
+        }
+    }
+
+    async fn close_candidate_region(
+        &self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<()> {
+        // The lease of the candidate region is not renewed anymore, so it's closed by the region
+        // alive keeper of the target Datanode eventually, even if the target Datanode can't be
+        // reached now.
+        let candidate = task.candidate_region();
+        InactiveNodeManager::new(&ctx.in_memory)
+            .register_inactive_region(&candidate)
+            .await?;
+
+        let instruction = Instruction::CloseRegion(candidate);
+        let mailbox_receiver = ctx
+            .send_instruction(
+                "Rollback Candidate Region",
+                task.to_peer.id,
+                &instruction,
+                CLOSE_REGION_MESSAGE_TIMEOUT,
+            )
+            .await?;
+
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received rollback candidate region reply: {msg:?}");
+
+                let SimpleReply { result, error } = simple_reply(&msg, &instruction)?;
+                if !result {
+                    warn!(
+                        "Region {} is not closed by Datanode {:?}, error: {error:?}, \
+                        wait for its lease to expire",
+                        task.region, task.to_peer,
+                    );
+                }
+            }
+            Err(Error::MailboxTimeout { .. }) => {
+                warn!(
+                    "Mailbox received timeout for closing region {} on Datanode {:?}, \
+                    wait for its lease to expire",
+                    task.region, task.to_peer,
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    async fn upgrade_leader_region(
+        &self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<()> {
+        let instruction = Instruction::UpgradeRegion(task.region.clone());
+        let mailbox_receiver = ctx
+            .send_instruction(
+                "Rollback Leader Region",
+                task.region.datanode_id,
+                &instruction,
+                UPGRADE_REGION_MESSAGE_TIMEOUT,
+            )
+            .await?;
+
+        // The region stays read-only until the leader is upgraded, so retry until it is.
+        match mailbox_receiver.await? {
+            Ok(msg) => {
+                debug!("Received rollback leader region reply: {msg:?}");
+
+                let SimpleReply { result, error } = simple_reply(&msg, &instruction)?;
+                if result {
+                    Ok(())
+                } else {
+                    let reason = format!(
+                        "Region {} is not upgraded by Datanode {}, error: {error:?}",
+                        task.region, task.region.datanode_id,
+                    );
+                    RetryLaterSnafu { reason }.fail()
+                }
+            }
+            Err(Error::MailboxTimeout { .. }) => {
+                let reason = format!(
+                    "Mailbox received timeout for upgrading region {} on Datanode {}",
+                    task.region, task.region.datanode_id,
+                );
+                RetryLaterSnafu { reason }.fail()
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for RollbackCandidateRegion {
+    async fn next(
+        &mut self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        self.close_candidate_region(ctx, task).await?;
+
+        if self.leader_downgraded {
+            self.upgrade_leader_region(ctx, task).await?;
+        }
+
+        MigrateRegionSnafu {
+            region: task.region.to_string(),
+            reason: self.reason.clone(),
+        }
+        .fail()
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use common_meta::key::table_route::NextTableRouteKey;
+use common_telemetry::info;
+use serde::{Deserialize, Serialize};
+use snafu::{OptionExt, ResultExt};
+
+use super::invalidate_cache::InvalidateCache;
+use super::{RegionMigrationContext, RegionMigrationTask, State};
+use crate::error::{self, Result, RetryLaterSnafu};
+use crate::lock::keys::table_metadata_lock_key;
+use crate::lock::Opts;
+
+/// Flips the leader of the region to the target Datanode in the table route.
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct UpdateRegionRoute;
+
+impl UpdateRegionRoute {
+    async fn update_metadata(
+        &self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<()> {
+        let key = table_metadata_lock_key(&task.region);
+        let key = ctx.dist_lock.lock(key, Opts::default()).await?;
+
+        self.update_table_route(ctx, task).await?;
+
+        ctx.dist_lock.unlock(key).await?;
+        Ok(())
+    }
+
+    async fn update_table_route(
+        &self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<()> {
+        let region = &task.region;
+        let table_id = region.table_ident.table_id;
+
+        let table_route_value = ctx
+            .table_metadata_manager
+            .table_route_manager()
+            .get(table_id)
+            .await
+            .context(error::TableMetadataManagerSnafu)?
+            .with_context(|| error::TableRouteNotFoundSnafu {
+                table_name: region.table_ident.table_ref().to_string(),
+            })?;
+
+        let mut new_region_routes = table_route_value.region_routes.clone();
+        let region_route = new_region_routes
+            .iter_mut()
+            .find(|x| x.region.id == region.region_number as u64)
+            .with_context(|| error::MigrateRegionSnafu {
+                region: region.to_string(),
+                reason: "region not found in table route",
+            })?;
+        region_route.leader_peer = Some(task.to_peer.clone());
+
+        info!(
+            "Updating region routes in table route value (key = '{}'), \
+            the leader of region {} is moved from Datanode {} to Datanode {}.",
+            NextTableRouteKey::new(table_id),
+            region.region_number,
+            region.datanode_id,
+            task.to_peer.id,
+        );
+
+        ctx.table_metadata_manager
+            .update_table_route(table_id, table_route_value, new_region_routes)
+            .await
+            .context(error::UpdateTableRouteSnafu)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+#[typetag::serde]
+impl State for UpdateRegionRoute {
+    async fn next(
+        &mut self,
+        ctx: &RegionMigrationContext,
+        task: &RegionMigrationTask,
+    ) -> Result<Box<dyn State>> {
+        self.update_metadata(ctx, task).await.map_err(|e| {
+            RetryLaterSnafu {
+                reason: format!("Failed to update metadata for migrating {task}, error: {e}"),
+            }
+            .build()
+        })?;
+        Ok(Box::new(InvalidateCache))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_meta::peer::Peer;
+
+    use super::super::tests::{new_task, TestingEnv};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_update_table_route() {
+        common_telemetry::init_default_ut_logging();
+
+        let env = TestingEnv::new().await;
+        let task = new_task(1, 1, Peer::new(3, ""));
+
+        let mut state = UpdateRegionRoute;
+        let next_state = state.next(&env.context, &task).await.unwrap();
+        assert_eq!(format!("{next_state:?}"), "InvalidateCache");
+
+        let region_distribution = env
+            .context
+            .table_metadata_manager
+            .table_route_manager()
+            .get_region_distribution(1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(region_distribution.get(&1).unwrap(), &vec![2]);
+        assert_eq!(region_distribution.get(&2).unwrap(), &vec![3]);
+        assert_eq!(region_distribution.get(&3).unwrap(), &vec![1, 4]);
+    }
+}
@@ -17,6 +17,8 @@ mod heartbeat;
 mod leader;
 mod meta;
 mod node_lease;
+mod region_balance;
+mod region_migration;
 mod route;
 
 use std::collections::HashMap;
@@ -89,6 +91,20 @@ pub fn make_admin_service(meta_srv: MetaSrv) -> Admin {
         },
     );
 
+    let router = router.route(
+        "/region-migration",
+        region_migration::RegionMigrationHandler {
+            region_migration_manager: meta_srv.region_migration_manager().clone(),
+        },
+    );
+
+    let router = router.route(
+        "/region-balance",
+        region_balance::RegionBalanceHandler {
+            region_balancer: meta_srv.region_balancer().clone(),
+        },
+    );
+
     let router = Router::nest("/admin", router);
 
     Admin::new(router)
@@ -99,6 +115,7 @@ pub trait HttpHandler: Send + Sync {
     async fn handle(
         &self,
         path: &str,
+        method: http::Method,
         params: &HashMap<String, String>,
     ) -> crate::Result<http::Response<String>>;
 }
@@ -147,7 +164,8 @@ where
             })
             .unwrap_or_default();
         let path = req.uri().path().to_owned();
-        Box::pin(async move { router.call(&path, query_params).await })
+        let method = req.method().clone();
+        Box::pin(async move { router.call(&path, method, query_params).await })
     }
 }
 
@@ -186,6 +204,7 @@ impl Router {
     pub async fn call(
         &self,
         path: &str,
+        method: http::Method,
         params: HashMap<String, String>,
     ) -> Result<http::Response<BoxBody>, Infallible> {
         let handler = match self.handlers.get(path) {
@@ -198,7 +217,7 @@ impl Router {
             }
         };
 
-        let res = match handler.handle(path, &params).await {
+        let res = match handler.handle(path, method, &params).await {
             Ok(res) => res.map(boxed),
             Err(e) => http::Response::builder()
                 .status(http::StatusCode::INTERNAL_SERVER_ERROR)
@@ -234,6 +253,7 @@ mod tests {
         async fn handle(
             &self,
             _: &str,
+            _: http::Method,
             _: &HashMap<String, String>,
         ) -> crate::Result<http::Response<String>> {
             Ok(http::Response::builder()
@@ -249,6 +269,7 @@ mod tests {
         async fn handle(
             &self,
             _: &str,
+            _: http::Method,
             _: &HashMap<String, String>,
         ) -> crate::Result<http::Response<String>> {
             error::EmptyKeySnafu {}.fail()
@@ -284,7 +305,11 @@ mod tests {
         let router = Router::nest("/test_root", router);
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                "/test_root/test_node",
+                http::Method::GET,
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
@@ -296,7 +321,11 @@ mod tests {
         let router = Router::new();
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                "/test_root/test_node",
+                http::Method::GET,
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
@@ -310,7 +339,11 @@ mod tests {
         let router = Router::nest("/test_root", router);
 
         let res = router
-            .call("/test_root/test_node", HashMap::default())
+            .call(
+                "/test_root/test_node",
+                http::Method::GET,
+                HashMap::default(),
+            )
             .await
             .unwrap();
 
@@ -25,7 +25,12 @@ pub struct HealthHandler;
 
 #[async_trait::async_trait]
 impl HttpHandler for HealthHandler {
-    async fn handle(&self, _: &str, _: &HashMap<String, String>) -> Result<http::Response<String>> {
+    async fn handle(
+        &self,
+        _: &str,
+        _: http::Method,
+        _: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
         Ok(http::Response::builder()
             .status(http::StatusCode::OK)
             .body(HTTP_OK.to_owned())
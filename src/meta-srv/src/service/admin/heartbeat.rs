@@ -32,6 +32,7 @@ impl HttpHandler for HeartBeatHandler {
     async fn handle(
         &self,
         _: &str,
+        _: http::Method,
         params: &HashMap<String, String>,
     ) -> Result<http::Response<String>> {
         let stat_kvs = self.meta_peer_client.get_all_dn_stat_kvs().await?;
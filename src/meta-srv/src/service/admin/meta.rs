@@ -43,7 +43,12 @@ pub struct TableHandler {
 
 #[async_trait::async_trait]
 impl HttpHandler for CatalogsHandler {
-    async fn handle(&self, _: &str, _: &HashMap<String, String>) -> Result<http::Response<String>> {
+    async fn handle(
+        &self,
+        _: &str,
+        _: http::Method,
+        _: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
         let stream = self
             .table_metadata_manager
             .catalog_manager()
@@ -65,6 +70,7 @@ impl HttpHandler for SchemasHandler {
     async fn handle(
         &self,
         _: &str,
+        _: http::Method,
         params: &HashMap<String, String>,
     ) -> Result<http::Response<String>> {
         let catalog = params
@@ -93,6 +99,7 @@ impl HttpHandler for TablesHandler {
     async fn handle(
         &self,
         _: &str,
+        _: http::Method,
         params: &HashMap<String, String>,
     ) -> Result<http::Response<String>> {
         let catalog = params
@@ -126,6 +133,7 @@ impl HttpHandler for TableHandler {
     async fn handle(
         &self,
         _: &str,
+        _: http::Method,
         params: &HashMap<String, String>,
     ) -> Result<http::Response<String>> {
         let table_name =
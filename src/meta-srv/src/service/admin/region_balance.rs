@@ -0,0 +1,49 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use snafu::ResultExt;
+use tonic::codegen::http;
+
+use super::HttpHandler;
+use crate::balancer::RegionBalancerRef;
+use crate::error::{self, Result};
+
+/// Evaluates the split and merge plans of regions from the latest region stats, e.g.:
+/// `/admin/region-balance`. The plans are returned only, they are executed periodically by the
+/// region balancer if it's enabled.
+pub struct RegionBalanceHandler {
+    pub region_balancer: RegionBalancerRef,
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for RegionBalanceHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        _: http::Method,
+        _: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        let plans = self.region_balancer.plan().await?;
+        let body = serde_json::to_string(&plans).context(error::SerializeToJsonSnafu {
+            input: format!("{plans:?}"),
+        })?;
+
+        http::Response::builder()
+            .status(http::StatusCode::OK)
+            .body(body)
+            .context(error::InvalidHttpBodySnafu)
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use snafu::{OptionExt, ResultExt};
+use tonic::codegen::http;
+
+use super::HttpHandler;
+use crate::error::{self, Result};
+use crate::procedure::region_migration::{RegionMigrationManagerRef, RegionMigrationRequest};
+
+/// Submits a region migration procedure and returns its id, e.g.:
+/// `POST /admin/region-migration?table_id=1024&region_number=0&from_peer=1&to_peer=2`.
+pub struct RegionMigrationHandler {
+    pub region_migration_manager: RegionMigrationManagerRef,
+}
+
+#[async_trait::async_trait]
+impl HttpHandler for RegionMigrationHandler {
+    async fn handle(
+        &self,
+        _: &str,
+        method: http::Method,
+        params: &HashMap<String, String>,
+    ) -> Result<http::Response<String>> {
+        // Submitting a migration changes the cluster, so it must not be triggered by a GET.
+        if method != http::Method::POST {
+            return http::Response::builder()
+                .status(http::StatusCode::METHOD_NOT_ALLOWED)
+                .header(http::header::ALLOW, "POST")
+                .body(format!("Method {method} is not allowed, use POST instead"))
+                .context(error::InvalidHttpBodySnafu);
+        }
+
+        let cluster_id = params
+            .get("cluster_id")
+            .map(|id| id.parse::<u64>())
+            .transpose()
+            .context(error::ParseNumSnafu {
+                err_msg: "`cluster_id` is not a valid number",
+            })?
+            .unwrap_or_default();
+        let request = RegionMigrationRequest {
+            cluster_id,
+            table_id: parse_param(params, "table_id")?,
+            region_number: parse_param(params, "region_number")?,
+            from_peer_id: parse_param(params, "from_peer")?,
+            to_peer_id: parse_param(params, "to_peer")?,
+        };
+
+        let procedure_id = self
+            .region_migration_manager
+            .submit_region_migration(request)
+            .await?;
+
+        http::Response::builder()
+            .status(http::StatusCode::OK)
+            .body(format!(r#"{{"procedure_id":"{procedure_id}"}}"#))
+            .context(error::InvalidHttpBodySnafu)
+    }
+}
+
+fn parse_param<T>(params: &HashMap<String, String>, name: &str) -> Result<T>
+where
+    T: FromStr<Err = std::num::ParseIntError>,
+{
+    params
+        .get(name)
+        .context(error::MissingRequiredParameterSnafu { param: name })?
+        .parse::<T>()
+        .context(error::ParseNumSnafu {
+            err_msg: format!("`{name}` is not a valid number"),
+        })
+}
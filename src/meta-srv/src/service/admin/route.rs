@@ -36,6 +36,7 @@ impl HttpHandler for RouteHandler {
     async fn handle(
         &self,
         _path: &str,
+        _: http::Method,
         params: &HashMap<String, String>,
     ) -> Result<http::Response<String>> {
         let full_table_name = params
@@ -24,22 +24,25 @@ use api::v1::meta::{
     BatchGetResponse as PbBatchGetResponse, BatchPutRequest as PbBatchPutRequest,
     BatchPutResponse as PbBatchPutResponse, CompareAndPutRequest as PbCompareAndPutRequest,
     CompareAndPutResponse as PbCompareAndPutResponse, DeleteRangeRequest as PbDeleteRangeRequest,
-    DeleteRangeResponse as PbDeleteRangeResponse, MoveValueRequest as PbMoveValueRequest,
-    MoveValueResponse as PbMoveValueResponse, PutRequest as PbPutRequest,
-    PutResponse as PbPutResponse, RangeRequest as PbRangeRequest, RangeResponse as PbRangeResponse,
-    ResponseHeader,
+    DeleteRangeResponse as PbDeleteRangeResponse, MailboxMessage,
+    MoveValueRequest as PbMoveValueRequest, MoveValueResponse as PbMoveValueResponse,
+    PutRequest as PbPutRequest, PutResponse as PbPutResponse, RangeRequest as PbRangeRequest,
+    RangeResponse as PbRangeResponse, ResponseHeader,
 };
+use common_meta::instruction::Instruction;
+use common_meta::key::user::is_user_or_role_key;
 use common_meta::rpc::store::{
     BatchDeleteRequest, BatchGetRequest, BatchPutRequest, CompareAndPutRequest, DeleteRangeRequest,
     MoveValueRequest, PutRequest, RangeRequest,
 };
-use common_telemetry::timer;
+use common_telemetry::{error, timer};
 use snafu::OptionExt;
 use tonic::{Request, Response};
 
 use crate::error::MissingRequestHeaderSnafu;
 use crate::metasrv::MetaSrv;
 use crate::metrics::METRIC_META_KV_REQUEST;
+use crate::service::mailbox::BroadcastChannel;
 use crate::service::GrpcResult;
 
 #[async_trait::async_trait]
@@ -89,8 +92,10 @@ impl store_server::Store for MetaSrv {
         );
 
         let req: PutRequest = req.into();
+        let user_keys = user_cache_keys([req.key.as_slice()]);
 
         let res = self.kv_store().put(req).await?;
+        self.invalidate_user_cache(user_keys).await;
 
         let res = res.to_proto_resp(ResponseHeader::success(cluster_id));
         Ok(Response::new(res))
@@ -141,8 +146,10 @@ impl store_server::Store for MetaSrv {
         );
 
         let req: BatchPutRequest = req.into();
+        let user_keys = user_cache_keys(req.kvs.iter().map(|kv| kv.key.as_slice()));
 
         let res = self.kv_store().batch_put(req).await?;
+        self.invalidate_user_cache(user_keys).await;
 
         let res = res.to_proto_resp(ResponseHeader::success(cluster_id));
         Ok(Response::new(res))
@@ -170,8 +177,10 @@ impl store_server::Store for MetaSrv {
         );
 
         let req: BatchDeleteRequest = req.into();
+        let user_keys = user_cache_keys(req.keys.iter().map(Vec::as_slice));
 
         let res = self.kv_store().batch_delete(req).await?;
+        self.invalidate_user_cache(user_keys).await;
 
         let res = res.to_proto_resp(ResponseHeader::success(cluster_id));
         Ok(Response::new(res))
@@ -199,8 +208,12 @@ impl store_server::Store for MetaSrv {
         );
 
         let req: CompareAndPutRequest = req.into();
+        let user_keys = user_cache_keys([req.key.as_slice()]);
 
         let res = self.kv_store().compare_and_put(req).await?;
+        if res.success {
+            self.invalidate_user_cache(user_keys).await;
+        }
 
         let res = res.to_proto_resp(ResponseHeader::success(cluster_id));
         Ok(Response::new(res))
@@ -265,6 +278,47 @@ impl store_server::Store for MetaSrv {
     }
 }
 
+/// Returns the keys of users and roles in `keys`, whose caches must be invalidated after
+/// they are written.
+fn user_cache_keys<'a>(keys: impl IntoIterator<Item = &'a [u8]>) -> Vec<String> {
+    keys.into_iter()
+        .filter(|key| is_user_or_role_key(key))
+        .map(|key| String::from_utf8_lossy(key).to_string())
+        .collect()
+}
+
+impl MetaSrv {
+    /// Broadcasts to frontends to invalidate their cached users and roles, so that changed
+    /// privileges take effect on all frontends.
+    async fn invalidate_user_cache(&self, keys: Vec<String>) {
+        if keys.is_empty() {
+            return;
+        }
+
+        let instruction = Instruction::InvalidateUserCache { keys };
+        let msg = match MailboxMessage::json_message(
+            "Invalidate user cache",
+            &format!("Metasrv@{}", self.options().server_addr),
+            "Frontend broadcast",
+            common_time::util::current_time_millis(),
+            &instruction,
+        ) {
+            Ok(msg) => msg,
+            Err(e) => {
+                error!("Failed to serialize {instruction}: {e}");
+                return;
+            }
+        };
+        if let Err(e) = self
+            .mailbox()
+            .broadcast(&BroadcastChannel::Frontend, &msg)
+            .await
+        {
+            error!(e; "Failed to broadcast {instruction}");
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
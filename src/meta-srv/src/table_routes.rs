@@ -109,6 +109,7 @@ pub(crate) mod tests {
                 options: TableOptions::default(),
                 created_on: DateTime::default(),
                 partition_key_indices: vec![],
+                view_definition: None,
             },
             table_type: TableType::Base,
         };
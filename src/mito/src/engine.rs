@@ -33,8 +33,9 @@ use snafu::{ensure, OptionExt, ResultExt};
 use storage::manifest::manifest_compress_type;
 use store_api::storage::{
     CloseOptions, ColumnDescriptorBuilder, ColumnFamilyDescriptor, ColumnFamilyDescriptorBuilder,
-    ColumnId, CompactionStrategy, EngineContext as StorageEngineContext, OpenOptions, RegionNumber,
-    RowKeyDescriptor, RowKeyDescriptorBuilder, StorageEngine,
+    ColumnId, CompactionStrategy, CreateOptions, EngineContext as StorageEngineContext,
+    OpenOptions, RegionDescriptorBuilder, RegionId, RegionNumber, RowKeyDescriptor,
+    RowKeyDescriptorBuilder, StorageEngine,
 };
 use table::engine::{
     region_name, table_dir, CloseTableResult, EngineContext, TableEngine, TableEngineProcedure,
@@ -43,7 +44,7 @@ use table::engine::{
 use table::metadata::{TableId, TableInfo, TableVersion};
 use table::requests::{
     AlterTableRequest, CloseTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest,
-    TruncateTableRequest,
+    TableRegionsRequest, TruncateTableRequest,
 };
 use table::{error as table_error, Result as TableResult, Table, TableRef};
 
@@ -51,9 +52,9 @@ use self::procedure::TruncateMitoTable;
 use crate::config::EngineConfig;
 use crate::engine::procedure::{AlterMitoTable, CreateMitoTable, DropMitoTable, TableCreator};
 use crate::error::{
-    BuildColumnDescriptorSnafu, BuildColumnFamilyDescriptorSnafu, BuildRowKeyDescriptorSnafu,
-    InvalidPrimaryKeySnafu, MissingTimestampIndexSnafu, RegionNotFoundSnafu, Result,
-    TableExistsSnafu,
+    BuildColumnDescriptorSnafu, BuildColumnFamilyDescriptorSnafu, BuildRegionDescriptorSnafu,
+    BuildRowKeyDescriptorSnafu, InvalidPrimaryKeySnafu, MissingTimestampIndexSnafu,
+    RegionNotFoundSnafu, Result, TableExistsSnafu, TableNotFoundSnafu,
 };
 use crate::manifest::TableManifest;
 use crate::metrics;
@@ -183,6 +184,22 @@ impl<S: StorageEngine> TableEngine for MitoEngine<S> {
         self.inner.close_table(request).await
     }
 
+    async fn create_regions(
+        &self,
+        _ctx: &EngineContext,
+        request: TableRegionsRequest,
+    ) -> TableResult<TableRef> {
+        self.inner.create_regions(request).await
+    }
+
+    async fn drop_regions(
+        &self,
+        _ctx: &EngineContext,
+        request: TableRegionsRequest,
+    ) -> TableResult<()> {
+        self.inner.drop_regions(request).await
+    }
+
     async fn close(&self) -> TableResult<()> {
         self.inner.close().await
     }
@@ -735,6 +752,114 @@ impl<S: StorageEngine> MitoEngineInner<S> {
         Ok(CloseTableResult::PartialClosed(removed_regions))
     }
 
+    async fn create_regions(&self, request: TableRegionsRequest) -> TableResult<TableRef> {
+        let table_id = request.table_id;
+        let _lock = self.table_mutex.lock(table_id).await;
+        let table = self
+            .get_mito_table(table_id)
+            .with_context(|| TableNotFoundSnafu {
+                table_name: request.table_ref().to_string(),
+            })
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+
+        let table_info = table.table_info();
+        let table_dir = table_dir(&request.catalog_name, &request.schema_name, table_id);
+        let table_options = &table_info.meta.options;
+        let write_buffer_size = table_options.write_buffer_size.map(|s| s.0 as usize);
+        let compaction_strategy = CompactionStrategy::from(&table_options.extra_options);
+        let open_opts = OpenOptions {
+            parent_dir: table_dir.clone(),
+            write_buffer_size,
+            ttl: table_options.ttl,
+            compaction_strategy: compaction_strategy.clone(),
+        };
+        let create_opts = CreateOptions {
+            parent_dir: table_dir,
+            write_buffer_size,
+            ttl: table_options.ttl,
+            compaction_strategy,
+        };
+
+        // New regions are created from the latest schema of the table, just like the regions
+        // created with the table.
+        let table_name = &request.table_name;
+        let schema = &table_info.meta.schema;
+        let primary_key_indices = &table_info.meta.primary_key_indices;
+        let (next_column_id, default_cf) =
+            build_column_family(INIT_COLUMN_ID, table_name, schema, primary_key_indices)
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+        let (_, row_key) =
+            build_row_key_desc(next_column_id, table_name, schema, primary_key_indices)
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+
+        let engine_ctx = StorageEngineContext::default();
+        for region_number in &request.region_numbers {
+            if table.contains_region(*region_number)? {
+                continue;
+            }
+
+            let region_name = region_name(table_id, *region_number);
+            let region = match self
+                .storage_engine
+                .open_region(&engine_ctx, &region_name, &open_opts)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?
+            {
+                Some(region) => region,
+                None => {
+                    let region_desc = RegionDescriptorBuilder::default()
+                        .id(RegionId::new(table_id, *region_number))
+                        .name(region_name.clone())
+                        .row_key(row_key.clone())
+                        .default_cf(default_cf.clone())
+                        .build()
+                        .context(BuildRegionDescriptorSnafu {
+                            table_name,
+                            region_name,
+                        })
+                        .map_err(BoxedError::new)
+                        .context(table_error::TableOperationSnafu)?;
+                    self.storage_engine
+                        .create_region(&engine_ctx, region_desc, &create_opts)
+                        .await
+                        .map_err(BoxedError::new)
+                        .context(table_error::TableOperationSnafu)?
+                }
+            };
+            table.load_region(*region_number, region).await?;
+        }
+
+        Ok(table)
+    }
+
+    async fn drop_regions(&self, request: TableRegionsRequest) -> TableResult<()> {
+        let _lock = self.table_mutex.lock(request.table_id).await;
+        let Some(table) = self.get_mito_table(request.table_id) else {
+            return Ok(());
+        };
+
+        let removed = table.remove_regions(&request.region_numbers).await?;
+        let ctx = StorageEngineContext::default();
+        for (region_number, region) in removed {
+            self.storage_engine
+                .drop_region(&ctx, region)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            logging::info!(
+                "Mito engine dropped region {} of table {}",
+                region_number,
+                request.table_ref()
+            );
+        }
+
+        Ok(())
+    }
+
     async fn truncate_table(&self, request: TruncateTableRequest) -> TableResult<bool> {
         let _lock = self.table_mutex.lock(request.table_id).await;
 
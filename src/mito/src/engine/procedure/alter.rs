@@ -201,11 +201,21 @@ impl<S: StorageEngine> AlterMitoTable<S> {
 
     /// Alter regions.
     async fn alter_regions(&mut self) -> Result<()> {
+        let table_name = &self.data.request.table_name;
+        if let AlterKind::SetTableOptions { .. } = &self.data.request.alter_kind {
+            // Safety: We init new info in engine_alter_table()
+            let new_info = self.new_info.as_ref().unwrap();
+            return self
+                .table
+                .alter_region_options(table_name, &new_info.meta.options)
+                .await
+                .map_err(Error::from_error_ext);
+        }
+
         let Some(alter_op) = &self.alter_op else {
             return Ok(());
         };
 
-        let table_name = &self.data.request.table_name;
         let table_version = self.data.table_version;
         self.table
             .alter_regions(table_name, table_version, alter_op)
@@ -287,10 +297,13 @@ impl AlterTableData {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
     use datatypes::prelude::ConcreteDataType;
     use datatypes::schema::ColumnSchema;
     use table::engine::{EngineContext, TableEngine, TableEngineProcedure};
-    use table::requests::AddColumnRequest;
+    use table::requests::{AddColumnRequest, ModifyColumnRequest};
 
     use super::*;
     use crate::engine::procedure::procedure_test_util::{self, TestEnv};
@@ -467,6 +480,59 @@ mod tests {
         assert_eq!(new_meta.region_numbers, old_meta.region_numbers);
     }
 
+    #[tokio::test]
+    async fn test_procedure_modify_column() {
+        common_telemetry::init_default_ut_logging();
+
+        let TestEnv {
+            table_engine,
+            dir: _dir,
+        } = procedure_test_util::setup_test_engine("modify_column").await;
+        let schema = Arc::new(test_util::schema_for_test());
+        let request = test_util::new_create_request(schema.clone());
+
+        let engine_ctx = EngineContext::default();
+        // Create table first.
+        let mut procedure = table_engine
+            .create_table_procedure(&engine_ctx, request.clone())
+            .unwrap();
+        procedure_test_util::execute_procedure_until_done(&mut procedure).await;
+
+        let table = table_engine
+            .get_table(&engine_ctx, request.id)
+            .unwrap()
+            .unwrap();
+        let old_info = table.table_info();
+        let old_meta = &old_info.meta;
+
+        // Makes the memory column nullable.
+        let alter_kind = AlterKind::ModifyColumns {
+            columns: vec![ModifyColumnRequest {
+                column_name: "memory".to_string(),
+                data_type: Some(ConcreteDataType::float64_datatype()),
+                is_nullable: Some(true),
+                default_constraint: None,
+            }],
+        };
+        let request = test_util::new_alter_request(alter_kind);
+        let mut procedure = table_engine
+            .alter_table_procedure(&engine_ctx, request.clone())
+            .unwrap();
+        procedure_test_util::execute_procedure_until_done(&mut procedure).await;
+
+        // Validate.
+        let new_info = table.table_info();
+        let new_meta = &new_info.meta;
+        let new_schema = &new_meta.schema;
+        assert!(new_schema
+            .column_schema_by_name("memory")
+            .unwrap()
+            .is_nullable());
+        assert_eq!(old_meta.primary_key_indices, new_meta.primary_key_indices);
+        assert_eq!(old_meta.value_indices, new_meta.value_indices);
+        assert_eq!(new_schema.version(), old_meta.schema.version() + 1);
+    }
+
     #[tokio::test]
     async fn test_procedure_rename_table() {
         common_telemetry::init_default_ut_logging();
@@ -510,4 +576,55 @@ mod tests {
             .unwrap()
             .is_some());
     }
+
+    #[tokio::test]
+    async fn test_procedure_set_table_options() {
+        common_telemetry::init_default_ut_logging();
+
+        let TestEnv {
+            table_engine,
+            dir: _dir,
+        } = procedure_test_util::setup_test_engine("set_table_options").await;
+        let schema = Arc::new(test_util::schema_for_test());
+        let create_request = test_util::new_create_request(schema.clone());
+
+        let engine_ctx = EngineContext::default();
+        // Create table first.
+        let mut procedure = table_engine
+            .create_table_procedure(&engine_ctx, create_request.clone())
+            .unwrap();
+        procedure_test_util::execute_procedure_until_done(&mut procedure).await;
+
+        let table = table_engine
+            .get_table(&engine_ctx, create_request.id)
+            .unwrap()
+            .unwrap();
+        let old_info = table.table_info();
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([
+                ("ttl".to_string(), "7d".to_string()),
+                ("write_buffer_size".to_string(), "1MB".to_string()),
+            ]),
+        };
+        let alter_request = test_util::new_alter_request(alter_kind);
+        let mut procedure = table_engine
+            .alter_table_procedure(&engine_ctx, alter_request.clone())
+            .unwrap();
+        procedure_test_util::execute_procedure_until_done(&mut procedure).await;
+
+        // Validate.
+        let new_info = table.table_info();
+        let new_meta = &new_info.meta;
+        assert_eq!(
+            Some(Duration::from_secs(7 * 24 * 3600)),
+            new_meta.options.ttl
+        );
+        assert_eq!(
+            Some(1024 * 1024),
+            new_meta.options.write_buffer_size.map(|size| size.0)
+        );
+        assert_eq!(old_info.meta.schema, new_meta.schema);
+        assert_eq!(old_info.ident.version + 1, new_info.ident.version);
+    }
 }
@@ -32,13 +32,15 @@ use store_api::manifest::Manifest;
 use store_api::storage::{ReadContext, ScanRequest};
 use table::metadata::TableType;
 use table::requests::{
-    AddColumnRequest, AlterKind, DeleteRequest, FlushTableRequest, TableOptions,
+    AddColumnRequest, AlterKind, DeleteRequest, FlushTableRequest, MoveRegionRowsRequest,
+    TableOptions,
 };
 use table::Table;
 
 use super::*;
 use crate::table::test_util::{
-    self, new_insert_request, new_truncate_request, setup_table, TestEngineComponents, TABLE_NAME,
+    self, new_insert_request, new_truncate_request, setup_table, TestEngineComponents, TABLE_ID,
+    TABLE_NAME,
 };
 
 pub fn has_parquet_file(sst_dir: &str) -> bool {
@@ -866,6 +868,109 @@ async fn test_table_delete_rows() {
     );
 }
 
+async fn scan_hosts(table: &TableRef) -> Vec<String> {
+    let stream = table.scan_to_stream(ScanRequest::default()).await.unwrap();
+    let batches = util::collect_batches(stream).await.unwrap();
+    let mut hosts = batches
+        .iter()
+        .flat_map(|batch| {
+            let column = batch.column(0).clone();
+            (0..column.len()).map(move |i| column.get(i).to_string())
+        })
+        .collect::<Vec<_>>();
+    hosts.sort();
+    hosts
+}
+
+#[tokio::test]
+async fn test_split_and_merge_regions() {
+    let TestEngineComponents {
+        table_engine,
+        table_ref: table,
+        dir: _dir,
+        ..
+    } = test_util::setup_test_engine_and_table().await;
+
+    let hosts: VectorRef = Arc::new(StringVector::from(vec!["host1", "host2", "host3", "host4"]));
+    let cpus: VectorRef = Arc::new(Float64Vector::from_vec(vec![1.0, 2.0, 3.0, 4.0]));
+    let memories: VectorRef = Arc::new(Float64Vector::from_vec(vec![1.0, 2.0, 3.0, 4.0]));
+    let tss: VectorRef = Arc::new(TimestampMillisecondVector::from_vec(vec![1, 2, 2, 1]));
+    let columns_values = HashMap::from([
+        ("host".to_string(), hosts),
+        ("cpu".to_string(), cpus),
+        ("memory".to_string(), memories),
+        ("ts".to_string(), tss),
+    ]);
+    let insert_req = new_insert_request("demo".to_string(), columns_values);
+    assert_eq!(4, table.insert(insert_req).await.unwrap());
+
+    let partition_columns = vec!["host".to_string()];
+    let split_key = table
+        .region_split_key(0, &partition_columns)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(vec![Value::from("host3")], split_key);
+
+    let request = TableRegionsRequest {
+        catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+        schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        table_name: TABLE_NAME.to_string(),
+        table_id: TABLE_ID,
+        region_numbers: vec![1],
+    };
+    let ctx = EngineContext::default();
+    let table = table_engine
+        .create_regions(&ctx, request.clone())
+        .await
+        .unwrap();
+    assert!(table.contains_region(1).unwrap());
+
+    // Moving the rows again moves nothing.
+    for expect in [2, 0] {
+        let moved = table
+            .move_region_rows(MoveRegionRowsRequest {
+                from: 0,
+                to: 1,
+                partition_columns: partition_columns.clone(),
+                lower_bound: Some(split_key.clone()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(expect, moved);
+    }
+    assert_eq!(
+        Some(vec![Value::from("host2")]),
+        table.region_split_key(0, &partition_columns).await.unwrap()
+    );
+    assert_eq!(
+        Some(vec![Value::from("host4")]),
+        table.region_split_key(1, &partition_columns).await.unwrap()
+    );
+    assert_eq!(
+        vec!["host1", "host2", "host3", "host4"],
+        scan_hosts(&table).await
+    );
+
+    // Merges region 1 back into region 0.
+    let moved = table
+        .move_region_rows(MoveRegionRowsRequest {
+            from: 1,
+            to: 0,
+            partition_columns,
+            lower_bound: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(2, moved);
+    table_engine.drop_regions(&ctx, request).await.unwrap();
+    assert!(!table.contains_region(1).unwrap());
+    assert_eq!(
+        vec!["host1", "host2", "host3", "host4"],
+        scan_hosts(&table).await
+    );
+}
+
 #[tokio::test]
 async fn test_flush_table_all_regions() {
     let TestEngineComponents {
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod split;
 #[cfg(any(test, feature = "test"))]
 pub mod test_util;
 
@@ -28,14 +29,15 @@ use common_recordbatch::error::ExternalSnafu;
 use common_recordbatch::{RecordBatch, RecordBatchStreamAdaptor, SendableRecordBatchStream};
 use common_telemetry::{info, logging};
 use datatypes::schema::Schema;
+use datatypes::value::Value;
 use metrics::histogram;
 use object_store::ObjectStore;
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AddColumn, AlterOperation, AlterRequest, ChunkReader, CompactContext, FlushContext,
-    FlushReason, ReadContext, Region, RegionMeta, RegionNumber, ScanRequest, SchemaRef, Snapshot,
-    WriteContext, WriteRequest,
+    AddColumn, AlterOperation, AlterOptions, AlterRequest, ChunkReader, ColumnDescriptorBuilder,
+    CompactContext, CompactionStrategy, FlushContext, FlushReason, ReadContext, Region, RegionMeta,
+    RegionNumber, ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
 };
 use table::error::{
     InvalidTableSnafu, RegionSchemaMismatchSnafu, Result as TableResult, TableOperationSnafu,
@@ -45,6 +47,7 @@ use table::metadata::{
 };
 use table::requests::{
     AddColumnRequest, AlterKind, AlterTableRequest, DeleteRequest, InsertRequest,
+    ModifyColumnRequest, MoveRegionRowsRequest, TableOptions,
 };
 use table::table::{AlterContext, Table};
 use table::{error as table_error, RegionStat};
@@ -58,6 +61,7 @@ use crate::error::{
 use crate::manifest::action::*;
 use crate::manifest::TableManifest;
 use crate::metrics::{MITO_INSERT_BATCH_SIZE, MITO_INSERT_ELAPSED};
+use crate::table::split::{KeySampler, MAX_SPLIT_KEY_SAMPLES};
 
 #[inline]
 fn table_manifest_dir(table_dir: &str) -> String {
@@ -180,8 +184,10 @@ impl<R: Region> Table for MitoTable<R> {
             if let Some(first_schema) = &first_schema {
                 // TODO(hl): we assume all regions' schemas are the same, but undergoing table altering
                 // may make these schemas inconsistent.
+                // Regions created by splitting have a different version from the older regions,
+                // so the columns are compared instead of the versions.
                 ensure!(
-                    first_schema.version() == schema.version(),
+                    first_schema.column_schemas() == schema.column_schemas(),
                     RegionSchemaMismatchSnafu {
                         table: common_catalog::format_full_table_name(
                             &table_info.catalog_name,
@@ -368,6 +374,145 @@ impl<R: Region> Table for MitoTable<R> {
         Ok(())
     }
 
+    async fn set_region_writable(
+        &self,
+        region_number: RegionNumber,
+        writable: bool,
+    ) -> TableResult<()> {
+        let regions = self.regions.load();
+        let region = regions
+            .get(&region_number)
+            .with_context(|| RegionNotFoundSnafu {
+                table: self.table_info().name.clone(),
+                region: region_number,
+            })
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+        region.set_writable(writable).await;
+        Ok(())
+    }
+
+    async fn region_split_key(
+        &self,
+        region_number: RegionNumber,
+        partition_columns: &[String],
+    ) -> TableResult<Option<Vec<Value>>> {
+        let regions = self.regions.load();
+        let region = self.find_region(&regions, region_number)?;
+        let region_schema = region.in_memory_metadata().schema().clone();
+        let projection = self.column_indices(region, &region_schema, partition_columns)?;
+        let mut reader = scan_region(region, Some(projection)).await?;
+        let indices = self.column_indices(region, reader.user_schema(), partition_columns)?;
+
+        let mut sampler = KeySampler::new(MAX_SPLIT_KEY_SAMPLES);
+        while let Some(chunk) = reader
+            .next_chunk()
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?
+        {
+            let columns = reader.project_chunk(chunk).columns;
+            let rows = columns.first().map_or(0, |c| c.len());
+            for row in 0..rows {
+                sampler.push(indices.iter().map(|i| columns[*i].get(row)).collect());
+            }
+        }
+        Ok(sampler.split_key())
+    }
+
+    async fn move_region_rows(&self, request: MoveRegionRowsRequest) -> TableResult<usize> {
+        let regions = self.regions.load();
+        let from = self.find_region(&regions, request.from)?;
+        let to = self.find_region(&regions, request.to)?;
+
+        let table_info = self.table_info();
+        let mut key_columns = table_info
+            .meta
+            .row_key_column_names()
+            .cloned()
+            .collect::<Vec<_>>();
+        if let Some(timestamp) = table_info.meta.schema.timestamp_column() {
+            key_columns.push(timestamp.name.clone());
+        }
+
+        let mut reader = scan_region(from, None).await?;
+        let schema = reader.user_schema().clone();
+        let partition_indices = self.column_indices(from, &schema, &request.partition_columns)?;
+
+        // Rows are written to the target region before they are deleted from the source region,
+        // so no row is lost if moving fails halfway.
+        let ctx = WriteContext {
+            ignore_read_only: true,
+        };
+        let mut rows_moved = 0;
+        while let Some(chunk) = reader
+            .next_chunk()
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?
+        {
+            let mut columns = reader.project_chunk(chunk).columns;
+            if let Some(lower_bound) = &request.lower_bound {
+                let partition_values = partition_indices
+                    .iter()
+                    .map(|i| columns[*i].clone())
+                    .collect::<Vec<_>>();
+                let mask = split::rows_not_less_than(&partition_values, lower_bound);
+                columns = columns
+                    .iter()
+                    .map(|c| c.filter(&mask))
+                    .collect::<datatypes::Result<Vec<_>>>()
+                    .map_err(BoxedError::new)
+                    .context(table_error::TableOperationSnafu)?;
+            }
+            let rows = columns.first().map_or(0, |c| c.len());
+            if rows == 0 {
+                continue;
+            }
+
+            let columns_values = schema
+                .column_schemas()
+                .iter()
+                .map(|c| c.name.clone())
+                .zip(columns)
+                .collect::<HashMap<_, _>>();
+            let keys = key_columns
+                .iter()
+                .filter_map(|name| Some((name.clone(), columns_values.get(name)?.clone())))
+                .collect();
+
+            let mut put_request = to.write_request();
+            put_request
+                .put(columns_values)
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            let _ = to
+                .write(&ctx, put_request)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+
+            let mut delete_request = from.write_request();
+            delete_request
+                .delete(keys)
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            let _ = from
+                .write(&ctx, delete_request)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+
+            rows_moved += rows;
+        }
+
+        info!(
+            "Moved {} rows from region {} to region {} of table {}",
+            rows_moved, request.from, request.to, table_info.name
+        );
+        Ok(rows_moved)
+    }
+
     fn region_stats(&self) -> TableResult<Vec<RegionStat>> {
         let regions = self.regions.load();
 
@@ -392,7 +537,69 @@ fn column_qualified_name(table_name: &str, region_name: &str, column_name: &str)
     format!("{table_name}.{region_name}.{column_name}")
 }
 
+/// Scans the latest rows of the region.
+async fn scan_region<R: Region>(
+    region: &R,
+    projection: Option<Vec<usize>>,
+) -> TableResult<<R::Snapshot as Snapshot>::Reader> {
+    let read_ctx = ReadContext::default();
+    let snapshot = region
+        .snapshot(&read_ctx)
+        .map_err(BoxedError::new)
+        .context(table_error::TableOperationSnafu)?;
+    let scan_request = ScanRequest {
+        projection,
+        ..Default::default()
+    };
+    let response = snapshot
+        .scan(&read_ctx, scan_request)
+        .await
+        .map_err(BoxedError::new)
+        .context(table_error::TableOperationSnafu)?;
+    Ok(response.reader)
+}
+
 impl<R: Region> MitoTable<R> {
+    fn find_region<'a>(
+        &self,
+        regions: &'a HashMap<RegionNumber, R>,
+        region_number: RegionNumber,
+    ) -> TableResult<&'a R> {
+        regions
+            .get(&region_number)
+            .with_context(|| RegionNotFoundSnafu {
+                table: self.table_info().name.clone(),
+                region: region_number,
+            })
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
+    /// Returns the indices of `columns` in the `schema` of the region.
+    fn column_indices(
+        &self,
+        region: &R,
+        schema: &Schema,
+        columns: &[String],
+    ) -> TableResult<Vec<usize>> {
+        columns
+            .iter()
+            .map(|name| {
+                schema
+                    .column_index_by_name(name)
+                    .with_context(|| ProjectedColumnNotFoundSnafu {
+                        column_qualified_name: column_qualified_name(
+                            &self.table_info().name,
+                            region.name(),
+                            name,
+                        ),
+                    })
+            })
+            .collect::<Result<Vec<_>>>()
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
     pub(crate) fn new(
         table_info: TableInfo,
         regions: HashMap<RegionNumber, R>,
@@ -632,6 +839,35 @@ impl<R: Region> MitoTable<R> {
         Ok(())
     }
 
+    /// Alters options of all regions according to the table options.
+    pub(crate) async fn alter_region_options(
+        &self,
+        table_name: &str,
+        table_options: &TableOptions,
+    ) -> TableResult<()> {
+        let options = AlterOptions {
+            write_buffer_size: table_options.write_buffer_size.map(|s| s.0 as usize),
+            ttl: table_options.ttl,
+            compaction_strategy: CompactionStrategy::from(&table_options.extra_options),
+        };
+        let regions = self.regions.load();
+        for region in regions.values() {
+            logging::debug!(
+                "start altering options of region {} of table {}, with options {:?}",
+                region.name(),
+                table_name,
+                options,
+            );
+            region
+                .alter_options(&options)
+                .await
+                .map_err(BoxedError::new)
+                .context(TableOperationSnafu)?;
+        }
+
+        Ok(())
+    }
+
     // Loads a region if the slot of the corresponding region number was not occupied.
     // Assuming the regions with the same region_number are the same.
     pub async fn load_region(&self, region_number: RegionNumber, region: R) -> TableResult<()> {
@@ -666,7 +902,10 @@ impl<R: Region> MitoTable<R> {
             AlterKind::RenameTable { new_table_name } => {
                 new_info.name = new_table_name.clone();
             }
-            AlterKind::AddColumns { .. } | AlterKind::DropColumns { .. } => {
+            AlterKind::AddColumns { .. }
+            | AlterKind::DropColumns { .. }
+            | AlterKind::ModifyColumns { .. }
+            | AlterKind::SetTableOptions { .. } => {
                 let table_meta = &current_info.meta;
                 let new_meta = table_meta
                     .builder_with_alter_kind(table_name, alter_kind)?
@@ -700,8 +939,13 @@ pub(crate) fn create_alter_operation(
         AlterKind::DropColumns { names } => Ok(Some(AlterOperation::DropColumns {
             names: names.clone(),
         })),
+        AlterKind::ModifyColumns { columns } => {
+            create_modify_columns_operation(table_name, columns, table_meta)
+        }
         // No need to build alter operation when reaming tables.
         AlterKind::RenameTable { .. } => Ok(None),
+        // Options of regions are altered separately as they don't change the region metadata.
+        AlterKind::SetTableOptions { .. } => Ok(None),
     }
 }
 
@@ -726,6 +970,38 @@ fn create_add_columns_operation(
     Ok(Some(AlterOperation::AddColumns { columns }))
 }
 
+fn create_modify_columns_operation(
+    table_name: &str,
+    requests: &[ModifyColumnRequest],
+    table_meta: &TableMeta,
+) -> TableResult<Option<AlterOperation>> {
+    let columns = requests
+        .iter()
+        .map(|request| {
+            // The new meta already holds the modified column, with the attributes that
+            // are not given in the request kept from the old column.
+            let column_schema = table_meta
+                .schema
+                .column_schema_by_name(&request.column_name)
+                .context(table_error::ColumnNotExistsSnafu {
+                    column_name: &request.column_name,
+                    table_name,
+                })?;
+            // Regions keep ids of modified columns, so we don't need to allocate an id here.
+            ColumnDescriptorBuilder::new(0, &column_schema.name, column_schema.data_type.clone())
+                .is_nullable(column_schema.is_nullable())
+                .default_constraint(column_schema.default_constraint().cloned())
+                .build()
+                .context(table_error::BuildColumnDescriptorSnafu {
+                    table_name,
+                    column_name: &column_schema.name,
+                })
+        })
+        .collect::<TableResult<Vec<_>>>()?;
+
+    Ok(Some(AlterOperation::ModifyColumns { columns }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,133 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Helpers to split the rows of a region by the values of partition columns.
+
+use datatypes::value::Value;
+use datatypes::vectors::{BooleanVector, VectorRef};
+
+/// Max number of keys sampled to find the split key of a region.
+pub(crate) const MAX_SPLIT_KEY_SAMPLES: usize = 4096;
+
+/// Samples the keys of rows evenly with bounded memory, no matter how many rows there are.
+///
+/// Every `stride`-th key is sampled. Once the samples are twice the capacity, every other
+/// sample is dropped and the stride is doubled.
+pub(crate) struct KeySampler {
+    capacity: usize,
+    stride: usize,
+    seen: usize,
+    samples: Vec<Vec<Value>>,
+}
+
+impl KeySampler {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            stride: 1,
+            seen: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, key: Vec<Value>) {
+        if self.seen % self.stride == 0 {
+            self.samples.push(key);
+            if self.samples.len() >= 2 * self.capacity {
+                let mut index = 0;
+                self.samples.retain(|_| {
+                    index += 1;
+                    index % 2 == 1
+                });
+                self.stride *= 2;
+            }
+        }
+        self.seen += 1;
+    }
+
+    /// Returns the median of sampled keys, or the smallest key after the median that is
+    /// greater than the minimum key, so that neither half is empty. Returns `None` if all keys
+    /// are the same.
+    pub(crate) fn split_key(mut self) -> Option<Vec<Value>> {
+        self.samples.sort_unstable();
+        let min = self.samples.first()?;
+        self.samples[self.samples.len() / 2..]
+            .iter()
+            .find(|key| *key > min)
+            .cloned()
+    }
+}
+
+/// Returns the mask of rows whose values of `columns` are not less than `bound`, compared in
+/// lexicographic order.
+pub(crate) fn rows_not_less_than(columns: &[VectorRef], bound: &[Value]) -> BooleanVector {
+    let rows = columns.first().map_or(0, |c| c.len());
+    (0..rows)
+        .map(|row| {
+            let key = columns.iter().map(|c| c.get(row)).collect::<Vec<_>>();
+            key.as_slice() >= bound
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use datatypes::vectors::{Int32Vector, StringVector, Vector};
+
+    use super::*;
+
+    #[test]
+    fn test_split_key() {
+        let mut sampler = KeySampler::new(4);
+        for i in (0..100).rev() {
+            sampler.push(vec![Value::Int32(i)]);
+        }
+        // Only 4 to 8 keys are kept.
+        assert!(sampler.samples.len() >= 4 && sampler.samples.len() < 8);
+        let key = sampler.split_key().unwrap();
+        assert!(matches!(key[0], Value::Int32(v) if v > 25 && v < 75));
+
+        let mut sampler = KeySampler::new(4);
+        for _ in 0..10 {
+            sampler.push(vec![Value::Int32(1)]);
+        }
+        assert_eq!(None, sampler.split_key());
+
+        // The split key is greater than the minimum key even if most keys are the same.
+        let mut sampler = KeySampler::new(16);
+        for i in 0..10 {
+            sampler.push(vec![Value::Int32(if i < 9 { 1 } else { 2 })]);
+        }
+        assert_eq!(Some(vec![Value::Int32(2)]), sampler.split_key());
+
+        assert_eq!(None, KeySampler::new(4).split_key());
+    }
+
+    #[test]
+    fn test_rows_not_less_than() {
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec!["a", "b", "b", "c"])),
+            Arc::new(Int32Vector::from_slice([3, 1, 2, 0])),
+        ];
+        let mask = rows_not_less_than(&columns, &[Value::from("b"), Value::Int32(2)]);
+        assert_eq!(
+            vec![false, false, true, true],
+            (0..mask.len())
+                .map(|i| mask.get(i) == Value::Boolean(true))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -26,10 +26,10 @@ use datatypes::schema::{ColumnSchema, Schema};
 use storage::metadata::{RegionMetaImpl, RegionMetadata};
 use storage::write_batch::WriteBatch;
 use store_api::storage::{
-    AlterRequest, Chunk, ChunkReader, CloseOptions, CompactContext, CreateOptions, EngineContext,
-    FlushContext, GetRequest, GetResponse, OpenOptions, ReadContext, Region, RegionDescriptor,
-    RegionId, ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext,
-    WriteResponse,
+    AlterOptions, AlterRequest, Chunk, ChunkReader, CloseOptions, CompactContext, CreateOptions,
+    EngineContext, FlushContext, GetRequest, GetResponse, OpenOptions, ReadContext, Region,
+    RegionDescriptor, RegionId, ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine,
+    WriteContext, WriteResponse,
 };
 
 pub type Result<T> = std::result::Result<T, MockError>;
@@ -194,6 +194,10 @@ impl Region for MockRegion {
         Ok(())
     }
 
+    async fn alter_options(&self, _options: &AlterOptions) -> Result<()> {
+        Ok(())
+    }
+
     async fn drop_region(&self) -> Result<()> {
         Ok(())
     }
@@ -213,6 +217,10 @@ impl Region for MockRegion {
     async fn truncate(&self) -> Result<()> {
         unimplemented!()
     }
+
+    async fn set_writable(&self, _writable: bool) {
+        unimplemented!()
+    }
 }
 
 impl MockRegionInner {
@@ -0,0 +1,273 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod output;
+mod picker;
+#[cfg(test)]
+pub(crate) mod test_util;
+mod twcs;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_telemetry::{debug, error};
+pub use picker::CompactionPickerRef;
+use snafu::ResultExt;
+use store_api::storage::{CompactionStrategy, RegionId, TwcsOptions};
+use tokio::sync::mpsc;
+use tokio::sync::oneshot::Sender;
+
+use crate::compaction::twcs::TwcsPicker;
+use crate::error::{CompactRegionSnafu, Error, RegionClosedSnafu, Result};
+use crate::region::version::VersionRef;
+use crate::region::MitoRegionRef;
+use crate::request::WorkerRequest;
+use crate::schedule::scheduler::{Scheduler, SchedulerRef};
+
+/// Region compaction request.
+pub struct CompactionRequest {
+    pub(crate) current_version: VersionRef,
+    /// Region to compact.
+    pub(crate) region: MitoRegionRef,
+    pub(crate) ttl: Option<Duration>,
+    pub(crate) compaction_time_window: Option<i64>,
+    pub(crate) request_sender: mpsc::Sender<WorkerRequest>,
+    pub(crate) waiters: Vec<Sender<Result<()>>>,
+}
+
+impl CompactionRequest {
+    pub(crate) fn region_id(&self) -> RegionId {
+        self.current_version.metadata.region_id
+    }
+}
+
+/// Builds compaction picker according to [CompactionStrategy].
+pub fn compaction_strategy_to_picker(strategy: &CompactionStrategy) -> CompactionPickerRef {
+    match strategy {
+        CompactionStrategy::Twcs(twcs_opts) => Arc::new(TwcsPicker::new(
+            twcs_opts.max_active_window_files,
+            twcs_opts.max_inactive_window_files,
+            twcs_opts.time_window_seconds,
+        )) as Arc<_>,
+        // TODO(hl): Support leveled time window compaction in mito2.
+        CompactionStrategy::LeveledTimeWindow => {
+            Arc::new(TwcsPicker::new(usize::MAX, 1, None)) as Arc<_>
+        }
+    }
+}
+
+/// Compaction scheduler tracks and manages compaction tasks.
+pub(crate) struct CompactionScheduler {
+    scheduler: SchedulerRef,
+    /// Compacting regions.
+    region_status: HashMap<RegionId, CompactionStatus>,
+    /// Request sender of the worker that this scheduler belongs to.
+    request_sender: mpsc::Sender<WorkerRequest>,
+}
+
+impl CompactionScheduler {
+    pub(crate) fn new(
+        scheduler: SchedulerRef,
+        request_sender: mpsc::Sender<WorkerRequest>,
+    ) -> CompactionScheduler {
+        CompactionScheduler {
+            scheduler,
+            region_status: HashMap::new(),
+            request_sender,
+        }
+    }
+
+    /// Schedules a compaction for the region.
+    pub(crate) fn schedule_compaction(
+        &mut self,
+        region: &MitoRegionRef,
+        waiter: Option<Sender<Result<()>>>,
+    ) -> Result<()> {
+        if let Some(status) = self.region_status.get_mut(&region.region_id) {
+            // Region is compacting. Add the waiter to pending list.
+            status.merge_waiter(waiter);
+            return Ok(());
+        }
+
+        // The region can compact directly.
+        let mut status = CompactionStatus::new(region.clone());
+        let request = status.new_compaction_request(self.request_sender.clone(), waiter);
+        self.region_status.insert(region.region_id, status);
+        self.schedule_compaction_request(request)
+    }
+
+    /// Notifies the scheduler that the compaction job is finished successfully.
+    pub(crate) fn on_compaction_finished(&mut self, region_id: RegionId) {
+        let Some(status) = self.region_status.get_mut(&region_id) else {
+            return;
+        };
+
+        // Try to schedule next compaction task for this region.
+        let request = status.new_compaction_request(self.request_sender.clone(), None);
+        if let Err(e) = self.schedule_compaction_request(request) {
+            error!(e; "Failed to schedule next compaction for region {}", region_id);
+        }
+    }
+
+    /// Notifies the scheduler that the compaction job is failed.
+    pub(crate) fn on_compaction_failed(&mut self, region_id: RegionId, err: Arc<Error>) {
+        error!(
+            "Region {} failed to compact, cancel all pending tasks",
+            region_id
+        );
+        // Remove this region.
+        let Some(status) = self.region_status.remove(&region_id) else {
+            return;
+        };
+
+        // Fast fail: cancels all pending tasks and sends error to their waiters.
+        status.on_failure(err);
+    }
+
+    /// Notifies the scheduler that the region is closed.
+    pub(crate) fn on_region_closed(&mut self, region_id: RegionId) {
+        self.remove_region_on_failure(region_id, Arc::new(RegionClosedSnafu { region_id }.build()));
+    }
+
+    /// Schedules a compaction request.
+    ///
+    /// If the region has nothing to compact, it removes the region from the status map.
+    fn schedule_compaction_request(&mut self, request: CompactionRequest) -> Result<()> {
+        // TODO(hl): build picker according to region options.
+        let picker =
+            compaction_strategy_to_picker(&CompactionStrategy::Twcs(TwcsOptions::default()));
+        let region_id = request.region_id();
+        debug!(
+            "Pick compaction strategy {:?} for region: {}",
+            picker, region_id
+        );
+        let Some(mut task) = picker.pick(request) else {
+            // Nothing to compact, remove it from the region status map.
+            self.region_status.remove(&region_id);
+            return Ok(());
+        };
+
+        // Submit the compaction task.
+        self.scheduler
+            .schedule(Box::pin(async move {
+                task.run().await;
+            }))
+            .map_err(|e| {
+                error!(e; "Failed to submit compaction request for region {}", region_id);
+
+                // If failed to submit the job, we need to remove the region from the scheduler.
+                self.region_status.remove(&region_id);
+
+                e
+            })
+    }
+
+    fn remove_region_on_failure(&mut self, region_id: RegionId, err: Arc<Error>) {
+        // Remove this region.
+        let Some(status) = self.region_status.remove(&region_id) else {
+            return;
+        };
+
+        // Notifies all pending tasks.
+        status.on_failure(err);
+    }
+}
+
+/// Pending compaction tasks.
+struct PendingCompaction {
+    waiters: Vec<Sender<Result<()>>>,
+}
+
+impl PendingCompaction {
+    /// Push waiter to the request.
+    fn push_waiter(&mut self, mut waiter: Option<Sender<Result<()>>>) {
+        if let Some(waiter) = waiter.take() {
+            self.waiters.push(waiter);
+        }
+    }
+
+    /// Send flush error to waiter.
+    fn on_failure(&mut self, region_id: RegionId, err: Arc<Error>) {
+        for waiter in self.waiters.drain(..) {
+            // Ignore send result.
+            let _ = waiter.send(Err(err.clone()).context(CompactRegionSnafu { region_id }));
+        }
+    }
+}
+
+/// Status of running and pending region compaction tasks.
+struct CompactionStatus {
+    /// Compacting region.
+    region: MitoRegionRef,
+    /// Compaction pending to schedule.
+    ///
+    /// For simplicity, we merge all pending compaction requests into one.
+    pending_compaction: Option<PendingCompaction>,
+}
+
+impl CompactionStatus {
+    /// Creates a new [CompactionStatus]
+    fn new(region: MitoRegionRef) -> CompactionStatus {
+        CompactionStatus {
+            region,
+            pending_compaction: None,
+        }
+    }
+
+    /// Merge the waiter to the pending compaction.
+    fn merge_waiter(&mut self, waiter: Option<Sender<Result<()>>>) {
+        let pending = self
+            .pending_compaction
+            .get_or_insert_with(|| PendingCompaction {
+                waiters: Vec::new(),
+            });
+        pending.push_waiter(waiter);
+    }
+
+    fn on_failure(self, err: Arc<Error>) {
+        if let Some(mut pending) = self.pending_compaction {
+            pending.on_failure(self.region.region_id, err.clone());
+        }
+    }
+
+    /// Creates a new compaction request for compaction picker.
+    ///
+    /// It consumes all pending compaction waiters.
+    fn new_compaction_request(
+        &mut self,
+        request_sender: mpsc::Sender<WorkerRequest>,
+        waiter: Option<Sender<Result<()>>>,
+    ) -> CompactionRequest {
+        let current_version = self.region.version_control.current().version;
+        let mut req = CompactionRequest {
+            current_version,
+            region: self.region.clone(),
+            // TODO(hl): Get TTL from region options.
+            ttl: None,
+            compaction_time_window: None,
+            request_sender,
+            waiters: Vec::new(),
+        };
+
+        if let Some(pending) = self.pending_compaction.take() {
+            req.waiters = pending.waiters;
+        }
+        if let Some(waiter) = waiter {
+            req.waiters.push(waiter);
+        }
+
+        req
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_telemetry::debug;
+use store_api::metadata::RegionMetadataRef;
+use store_api::storage::RegionId;
+
+use crate::access_layer::AccessLayerRef;
+use crate::error::Result;
+use crate::read::merge::MergeReaderBuilder;
+use crate::read::Source;
+use crate::sst::file::{FileHandle, FileId, FileMeta, Level};
+use crate::sst::parquet::WriteOptions;
+
+/// Output of a compaction: files in `inputs` are merged into a new file at `output_level`.
+#[derive(Debug, Clone)]
+pub(crate) struct CompactionOutput {
+    pub output_file_id: FileId,
+    /// Compaction output file level.
+    pub output_level: Level,
+    /// The left bound of time window.
+    pub time_window_bound: i64,
+    /// Time window size in seconds.
+    pub time_window_sec: i64,
+    /// Compaction input files.
+    pub inputs: Vec<FileHandle>,
+    /// Whether to remove deleted rows from the output.
+    ///
+    /// Delete markers must be kept if files other than `inputs` may contain rows they delete,
+    /// otherwise these rows would become visible again.
+    pub filter_deleted: bool,
+}
+
+impl CompactionOutput {
+    /// Merges input files and writes the output SST.
+    ///
+    /// Returns `None` if the output file has no rows.
+    pub(crate) async fn build(
+        &self,
+        region_id: RegionId,
+        metadata: RegionMetadataRef,
+        access_layer: AccessLayerRef,
+        write_opts: &WriteOptions,
+    ) -> Result<Option<FileMeta>> {
+        let mut builder = MergeReaderBuilder::new();
+        for file in &self.inputs {
+            let reader = access_layer.read_sst(file.clone()).build().await?;
+            builder.push_batch_reader(Box::new(reader));
+        }
+        let reader = builder.filter_deleted(self.filter_deleted).build().await?;
+        let source = Source::Reader(Box::new(reader));
+
+        let mut writer = access_layer.write_sst(self.output_file_id, metadata, source);
+        let Some(sst_info) = writer.write_all(write_opts).await? else {
+            debug!(
+                "No rows written to compaction output {}, region: {}",
+                self.output_file_id, region_id
+            );
+            return Ok(None);
+        };
+
+        Ok(Some(FileMeta {
+            region_id,
+            file_id: self.output_file_id,
+            time_range: sst_info.time_range,
+            level: self.output_level,
+            file_size: sst_info.file_size,
+            index_file_size: sst_info.index_file_size,
+        }))
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_time::Timestamp;
+use snafu::ResultExt;
+
+use crate::compaction::CompactionRequest;
+use crate::error::{Result, TtlCalculationSnafu};
+use crate::sst::file::FileHandle;
+use crate::sst::version::LevelMeta;
+
+pub type CompactionPickerRef = Arc<dyn Picker + Send + Sync>;
+
+#[async_trait::async_trait]
+pub trait CompactionTask: Debug + Send + Sync + 'static {
+    async fn run(&mut self);
+}
+
+/// Picker picks input SST files and builds the compaction task.
+/// Different compaction strategy may implement different pickers.
+pub trait Picker: Debug + Send + 'static {
+    fn pick(&self, req: CompactionRequest) -> Option<Box<dyn CompactionTask>>;
+}
+
+/// Returns SSTs whose max timestamp is older than `now - ttl`.
+pub(crate) fn get_expired_ssts(
+    levels: &[LevelMeta],
+    ttl: Option<Duration>,
+    now: Timestamp,
+) -> Result<Vec<FileHandle>> {
+    let Some(ttl) = ttl else {
+        return Ok(vec![]);
+    };
+
+    let expire_time = now.sub_duration(ttl).context(TtlCalculationSnafu)?;
+
+    let expired_ssts = levels
+        .iter()
+        .flat_map(|l| l.get_expired_files(&expire_time).into_iter())
+        .collect();
+    Ok(expired_ssts)
+}
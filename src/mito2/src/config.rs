@@ -20,6 +20,8 @@ use common_telemetry::warn;
 
 /// Default region worker num.
 const DEFAULT_NUM_WORKERS: usize = 1;
+/// Default max running background job.
+const DEFAULT_MAX_BG_JOB: usize = 4;
 /// Default region write buffer size.
 pub(crate) const DEFAULT_WRITE_BUFFER_SIZE: ReadableSize = ReadableSize::mb(32);
 
@@ -40,6 +42,14 @@ pub struct MitoConfig {
     pub manifest_checkpoint_distance: u64,
     /// Manifest compression type (default uncompressed).
     pub manifest_compress_type: CompressionType,
+
+    // Background job configs:
+    /// Max number of running background jobs (default 4).
+    pub max_background_jobs: usize,
+
+    // Flush configs:
+    /// Size of the mutable memtable of a region to trigger a flush (default 32MB).
+    pub region_write_buffer_size: ReadableSize,
 }
 
 impl Default for MitoConfig {
@@ -50,6 +60,8 @@ impl Default for MitoConfig {
             worker_request_batch_size: 64,
             manifest_checkpoint_distance: 10,
             manifest_compress_type: CompressionType::Uncompressed,
+            max_background_jobs: DEFAULT_MAX_BG_JOB,
+            region_write_buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
         }
     }
 }
@@ -75,5 +87,10 @@ impl MitoConfig {
             warn!("Sanitize channel size 0 to 1");
             self.worker_channel_size = 1;
         }
+
+        if self.max_background_jobs == 0 {
+            warn!("Sanitize max background jobs 0 to {}", DEFAULT_MAX_BG_JOB);
+            self.max_background_jobs = DEFAULT_MAX_BG_JOB;
+        }
     }
 }
@@ -19,18 +19,27 @@ mod tests;
 
 use std::sync::Arc;
 
+use async_trait::async_trait;
+use common_error::ext::BoxedError;
 use common_query::Output;
+use common_recordbatch::SendableRecordBatchStream;
 use object_store::ObjectStore;
-use snafu::ResultExt;
+use snafu::{OptionExt, ResultExt};
 use store_api::logstore::LogStore;
+use store_api::metadata::RegionMetadataRef;
+use store_api::region_engine::RegionEngine;
 use store_api::region_request::RegionRequest;
-use store_api::storage::RegionId;
+use store_api::storage::{RegionId, ScanRequest};
 
 use crate::config::MitoConfig;
-use crate::error::{RecvSnafu, Result};
+use crate::error::{RecvSnafu, RegionNotFoundSnafu, Result};
+use crate::read::scan_region::{ScanRegion, Scanner};
 use crate::request::{RegionTask, RequestBody};
 use crate::worker::WorkerGroup;
 
+/// Name of the mito engine.
+pub const MITO_ENGINE_NAME: &str = "mito";
+
 /// Region engine implementation for timeseries data.
 #[derive(Clone)]
 pub struct MitoEngine {
@@ -69,10 +78,26 @@ impl MitoEngine {
         Ok(Output::AffectedRows(0))
     }
 
+    /// Handles the scan `request` and returns a [SendableRecordBatchStream].
+    pub async fn handle_query(
+        &self,
+        region_id: RegionId,
+        request: ScanRequest,
+    ) -> Result<SendableRecordBatchStream> {
+        let scanner = self.inner.handle_query(region_id, request)?;
+        scanner.scan().await
+    }
+
     /// Returns true if the specific region exists.
     pub fn is_region_exists(&self, region_id: RegionId) -> bool {
         self.inner.workers.is_region_exists(region_id)
     }
+
+    /// Returns the region of specific `region_id`.
+    #[cfg(test)]
+    pub(crate) fn get_region(&self, region_id: RegionId) -> Option<crate::region::MitoRegionRef> {
+        self.inner.workers.get_region(region_id)
+    }
 }
 
 /// Inner struct of [MitoEngine].
@@ -107,4 +132,60 @@ impl EngineInner {
 
         receiver.await.context(RecvSnafu)?
     }
+
+    /// Handles the scan `request` and returns a [Scanner] for the `request`.
+    fn handle_query(&self, region_id: RegionId, request: ScanRequest) -> Result<Scanner> {
+        // Reading a region doesn't need to go through the region worker thread.
+        let region = self
+            .workers
+            .get_region(region_id)
+            .context(RegionNotFoundSnafu { region_id })?;
+        let version = region.version();
+        let scan_region = ScanRegion::new(version, region.access_layer.clone(), request);
+
+        scan_region.scanner()
+    }
+
+    /// Returns the metadata of the region.
+    fn get_metadata(&self, region_id: RegionId) -> Result<RegionMetadataRef> {
+        let region = self
+            .workers
+            .get_region(region_id)
+            .context(RegionNotFoundSnafu { region_id })?;
+        Ok(region.metadata())
+    }
+}
+
+#[async_trait]
+impl RegionEngine for MitoEngine {
+    fn name(&self) -> &str {
+        MITO_ENGINE_NAME
+    }
+
+    async fn handle_request(
+        &self,
+        region_id: RegionId,
+        request: RegionRequest,
+    ) -> std::result::Result<Output, BoxedError> {
+        MitoEngine::handle_request(self, region_id, request)
+            .await
+            .map_err(BoxedError::new)
+    }
+
+    async fn handle_query(
+        &self,
+        region_id: RegionId,
+        request: ScanRequest,
+    ) -> std::result::Result<SendableRecordBatchStream, BoxedError> {
+        MitoEngine::handle_query(self, region_id, request)
+            .await
+            .map_err(BoxedError::new)
+    }
+
+    async fn get_metadata(
+        &self,
+        region_id: RegionId,
+    ) -> std::result::Result<RegionMetadataRef, BoxedError> {
+        self.inner.get_metadata(region_id).map_err(BoxedError::new)
+    }
 }
@@ -16,12 +16,18 @@
 
 use std::collections::HashMap;
 
-use store_api::region_request::{RegionCloseRequest, RegionOpenRequest};
+use api::v1::{ColumnSchema, Rows};
+use common_base::readable_size::ReadableSize;
+use common_recordbatch::RecordBatches;
+use store_api::region_request::{
+    RegionCloseRequest, RegionCompactRequest, RegionDeleteRequest, RegionFlushRequest,
+    RegionOpenRequest, RegionPutRequest,
+};
 use store_api::storage::RegionId;
 
 use super::*;
 use crate::error::Error;
-use crate::test_util::{CreateRequestBuilder, TestEnv};
+use crate::test_util::{build_rows, rows_schema, CreateRequestBuilder, TestEnv};
 
 #[tokio::test]
 async fn test_engine_new_stop() {
@@ -220,3 +226,334 @@ async fn test_engine_reopen_region() {
         .unwrap();
     assert!(engine.is_region_exists(region_id));
 }
+
+/// Puts rows `[start, end)` into the region.
+async fn put_rows(
+    engine: &MitoEngine,
+    region_id: RegionId,
+    column_schemas: &[ColumnSchema],
+    start: usize,
+    end: usize,
+) {
+    let rows = Rows {
+        schema: column_schemas.to_vec(),
+        rows: build_rows(start, end),
+    };
+    engine
+        .handle_request(region_id, RegionRequest::Put(RegionPutRequest { rows }))
+        .await
+        .unwrap();
+}
+
+/// Flushes the region.
+async fn flush_region(engine: &MitoEngine, region_id: RegionId) {
+    engine
+        .handle_request(region_id, RegionRequest::Flush(RegionFlushRequest {}))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_flush_engine() {
+    let env = TestEnv::with_prefix("flush");
+    let engine = env.create_engine(MitoConfig::default()).await;
+
+    let region_id = RegionId::new(1, 1);
+    let request = CreateRequestBuilder::new().build();
+    let column_schemas = rows_schema(&request);
+    engine
+        .handle_request(region_id, RegionRequest::Create(request))
+        .await
+        .unwrap();
+
+    put_rows(&engine, region_id, &column_schemas, 0, 3).await;
+    flush_region(&engine, region_id).await;
+
+    let region = engine.get_region(region_id).unwrap();
+    let version_data = region.version_control.current();
+    let version = version_data.version;
+    assert_eq!(1, version.ssts.num_files());
+    assert!(version.memtables.is_empty());
+    assert_eq!(1, version.flushed_entry_id);
+    assert_eq!(3, version.flushed_sequence);
+    assert_eq!(3, version_data.committed_sequence);
+
+    // Flush an empty region.
+    flush_region(&engine, region_id).await;
+    let version = region.version_control.current().version;
+    assert_eq!(1, version.ssts.num_files());
+}
+
+#[tokio::test]
+async fn test_flush_reopen_region() {
+    let env = TestEnv::with_prefix("flush-reopen");
+    let engine = env.create_engine(MitoConfig::default()).await;
+
+    let region_id = RegionId::new(1, 1);
+    let request = CreateRequestBuilder::new().build();
+    let region_dir = request.region_dir.clone();
+    let column_schemas = rows_schema(&request);
+    engine
+        .handle_request(region_id, RegionRequest::Create(request))
+        .await
+        .unwrap();
+
+    put_rows(&engine, region_id, &column_schemas, 0, 3).await;
+    flush_region(&engine, region_id).await;
+    // Rows not flushed.
+    put_rows(&engine, region_id, &column_schemas, 3, 5).await;
+
+    engine
+        .handle_request(region_id, RegionRequest::Close(RegionCloseRequest {}))
+        .await
+        .unwrap();
+    engine
+        .handle_request(
+            region_id,
+            RegionRequest::Open(RegionOpenRequest {
+                engine: String::new(),
+                region_dir,
+                options: HashMap::default(),
+            }),
+        )
+        .await
+        .unwrap();
+
+    let region = engine.get_region(region_id).unwrap();
+    let version_data = region.version_control.current();
+    let version = version_data.version;
+    assert_eq!(1, version.ssts.num_files());
+    assert_eq!(1, version.flushed_entry_id);
+    assert_eq!(3, version.flushed_sequence);
+    // Unflushed rows are replayed from the WAL.
+    assert!(!version.memtables.mutable().is_empty());
+    assert_eq!(2, version_data.last_entry_id);
+    assert_eq!(5, version_data.committed_sequence);
+}
+
+#[tokio::test]
+async fn test_flush_when_memtable_full() {
+    let env = TestEnv::with_prefix("flush-full");
+    let engine = env
+        .create_engine(MitoConfig {
+            region_write_buffer_size: ReadableSize(1),
+            ..Default::default()
+        })
+        .await;
+
+    let region_id = RegionId::new(1, 1);
+    let request = CreateRequestBuilder::new().build();
+    let column_schemas = rows_schema(&request);
+    engine
+        .handle_request(region_id, RegionRequest::Create(request))
+        .await
+        .unwrap();
+
+    put_rows(&engine, region_id, &column_schemas, 0, 3).await;
+    // Waits until the background flush finished.
+    flush_region(&engine, region_id).await;
+
+    let region = engine.get_region(region_id).unwrap();
+    let version = region.version_control.current().version;
+    assert_eq!(1, version.ssts.num_files());
+    assert!(version.memtables.is_empty());
+}
+
+#[tokio::test]
+async fn test_compaction_region() {
+    let env = TestEnv::with_prefix("compaction");
+    let engine = env.create_engine(MitoConfig::default()).await;
+
+    let region_id = RegionId::new(1, 1);
+    let request = CreateRequestBuilder::new().build();
+    let column_schemas = rows_schema(&request);
+    engine
+        .handle_request(region_id, RegionRequest::Create(request))
+        .await
+        .unwrap();
+
+    // Creates 5 SSTs in the same time window. The flush of the 5th SST
+    // triggers a compaction.
+    for i in 0..5 {
+        put_rows(&engine, region_id, &column_schemas, i * 10, (i + 1) * 10).await;
+        flush_region(&engine, region_id).await;
+    }
+    // Waits until the compaction finished.
+    engine
+        .handle_request(region_id, RegionRequest::Compact(RegionCompactRequest {}))
+        .await
+        .unwrap();
+
+    let region = engine.get_region(region_id).unwrap();
+    let version = region.version_control.current().version;
+    assert_eq!(1, version.ssts.num_files());
+    assert_eq!(1, version.ssts.levels()[1].files().count());
+}
+
+#[tokio::test]
+async fn test_compaction_keep_deleted_rows() {
+    let env = TestEnv::with_prefix("compaction-delete");
+    let engine = env.create_engine(MitoConfig::default()).await;
+
+    let region_id = RegionId::new(1, 1);
+    let request = CreateRequestBuilder::new().build();
+    let column_schemas = rows_schema(&request);
+    engine
+        .handle_request(region_id, RegionRequest::Create(request))
+        .await
+        .unwrap();
+
+    // The first SST spans two one-hour windows and belongs to the latter one.
+    put_rows(&engine, region_id, &column_schemas, 1, 2).await;
+    put_rows(&engine, region_id, &column_schemas, 3601, 3602).await;
+    flush_region(&engine, region_id).await;
+    // Deletes a row of the first SST in the former window.
+    delete_rows(&engine, region_id, &column_schemas, 1, 2).await;
+    flush_region(&engine, region_id).await;
+    // The second SST in the former window triggers a compaction of the window, which
+    // doesn't contain the first SST.
+    put_rows(&engine, region_id, &column_schemas, 2, 3).await;
+    flush_region(&engine, region_id).await;
+    engine
+        .handle_request(region_id, RegionRequest::Compact(RegionCompactRequest {}))
+        .await
+        .unwrap();
+
+    let region = engine.get_region(region_id).unwrap();
+    let version = region.version_control.current().version;
+    assert_eq!(2, version.ssts.num_files());
+    assert_eq!(1, version.ssts.levels()[1].files().count());
+
+    // The deleted row is still invisible.
+    let expected = "\
++-------+---------+---------------------+
+| tag_0 | field_0 | ts                  |
++-------+---------+---------------------+
+| 2     | 2.0     | 1970-01-01T00:00:02 |
+| 3601  | 3601.0  | 1970-01-01T01:00:01 |
++-------+---------+---------------------+";
+    assert_eq!(
+        expected,
+        scan_region(&engine, region_id, ScanRequest::default()).await
+    );
+}
+
+/// Deletes rows `[start, end)` from the region.
+async fn delete_rows(
+    engine: &MitoEngine,
+    region_id: RegionId,
+    column_schemas: &[ColumnSchema],
+    start: usize,
+    end: usize,
+) {
+    let rows = Rows {
+        schema: column_schemas.to_vec(),
+        rows: build_rows(start, end),
+    };
+    engine
+        .handle_request(
+            region_id,
+            RegionRequest::Delete(RegionDeleteRequest { rows }),
+        )
+        .await
+        .unwrap();
+}
+
+/// Scans the region and returns the pretty printed result.
+async fn scan_region(engine: &MitoEngine, region_id: RegionId, request: ScanRequest) -> String {
+    let stream = engine.handle_query(region_id, request).await.unwrap();
+    let batches = RecordBatches::try_collect(stream).await.unwrap();
+    batches.pretty_print().unwrap()
+}
+
+#[tokio::test]
+async fn test_write_query_region() {
+    let env = TestEnv::new();
+    let engine = env.create_engine(MitoConfig::default()).await;
+
+    let region_id = RegionId::new(1, 1);
+    let request = CreateRequestBuilder::new().build();
+    let column_schemas = rows_schema(&request);
+    engine
+        .handle_request(region_id, RegionRequest::Create(request))
+        .await
+        .unwrap();
+
+    put_rows(&engine, region_id, &column_schemas, 0, 3).await;
+
+    let expected = "\
++-------+---------+---------------------+
+| tag_0 | field_0 | ts                  |
++-------+---------+---------------------+
+| 0     | 0.0     | 1970-01-01T00:00:00 |
+| 1     | 1.0     | 1970-01-01T00:00:01 |
+| 2     | 2.0     | 1970-01-01T00:00:02 |
++-------+---------+---------------------+";
+    assert_eq!(
+        expected,
+        scan_region(&engine, region_id, ScanRequest::default()).await
+    );
+}
+
+#[tokio::test]
+async fn test_scan_memtable_and_sst() {
+    let env = TestEnv::new();
+    let engine = env.create_engine(MitoConfig::default()).await;
+
+    let region_id = RegionId::new(1, 1);
+    let request = CreateRequestBuilder::new().build();
+    let column_schemas = rows_schema(&request);
+    engine
+        .handle_request(region_id, RegionRequest::Create(request))
+        .await
+        .unwrap();
+
+    put_rows(&engine, region_id, &column_schemas, 0, 3).await;
+    flush_region(&engine, region_id).await;
+    // Overwrites rows in the SST.
+    put_rows(&engine, region_id, &column_schemas, 1, 5).await;
+    // Deletes a row in the SST and a row in the memtable.
+    delete_rows(&engine, region_id, &column_schemas, 0, 1).await;
+    delete_rows(&engine, region_id, &column_schemas, 3, 4).await;
+
+    let expected = "\
++-------+---------+---------------------+
+| tag_0 | field_0 | ts                  |
++-------+---------+---------------------+
+| 1     | 1.0     | 1970-01-01T00:00:01 |
+| 2     | 2.0     | 1970-01-01T00:00:02 |
+| 4     | 4.0     | 1970-01-01T00:00:04 |
++-------+---------+---------------------+";
+    assert_eq!(
+        expected,
+        scan_region(&engine, region_id, ScanRequest::default()).await
+    );
+
+    // Reads the region with projection.
+    let request = ScanRequest {
+        projection: Some(vec![2, 1]),
+        ..Default::default()
+    };
+    let expected = "\
++---------------------+---------+
+| ts                  | field_0 |
++---------------------+---------+
+| 1970-01-01T00:00:01 | 1.0     |
+| 1970-01-01T00:00:02 | 2.0     |
+| 1970-01-01T00:00:04 | 4.0     |
++---------------------+---------+";
+    assert_eq!(expected, scan_region(&engine, region_id, request).await);
+}
+
+#[tokio::test]
+async fn test_scan_region_not_found() {
+    let env = TestEnv::new();
+    let engine = env.create_engine(MitoConfig::default()).await;
+
+    let err = engine
+        .handle_query(RegionId::new(1, 1), ScanRequest::default())
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::RegionNotFound { .. }));
+}
@@ -26,6 +26,7 @@ use snafu::{Location, Snafu};
 use store_api::manifest::ManifestVersion;
 use store_api::storage::RegionId;
 
+use crate::sst::file::FileId;
 use crate::worker::WorkerId;
 
 #[derive(Debug, Snafu)]
@@ -383,6 +384,79 @@ pub enum Error {
         source: JoinError,
         location: Location,
     },
+
+    #[snafu(display(
+        "Failed to delete SST file, file id: {}, source: {}, location: {}",
+        file_id,
+        source,
+        location
+    ))]
+    DeleteSst {
+        file_id: FileId,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to delete index file of SST, file id: {}, source: {}, location: {}",
+        file_id,
+        source,
+        location
+    ))]
+    DeleteIndex {
+        file_id: FileId,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to flush region {}, source: {}", region_id, source))]
+    FlushRegion {
+        region_id: RegionId,
+        source: Arc<Error>,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to compact region {}, source: {}", region_id, source))]
+    CompactRegion {
+        region_id: RegionId,
+        source: Arc<Error>,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to calculate SST expire time, source: {}", source))]
+    TtlCalculation {
+        source: common_time::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Region {} is closed, location: {}", region_id, location))]
+    RegionClosed {
+        region_id: RegionId,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to build predicate, location: {}, source: {}",
+        location,
+        source
+    ))]
+    BuildPredicate {
+        source: table::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to create record batch from vectors, location: {}, source: {}",
+        location,
+        source
+    ))]
+    CreateRecordBatch {
+        source: common_recordbatch::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Invalid SST index, {}, location: {}", reason, location))]
+    InvalidSstIndex { reason: String, location: Location },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -414,7 +488,8 @@ impl ErrorExt for Error {
             | RegionNotFound { .. }
             | RegionCorrupted { .. }
             | CreateDefault { .. }
-            | InvalidParquet { .. } => StatusCode::Unexpected,
+            | InvalidParquet { .. }
+            | InvalidSstIndex { .. } => StatusCode::Unexpected,
             InvalidScanIndex { .. }
             | InvalidMeta { .. }
             | InvalidSchema { .. }
@@ -444,6 +519,12 @@ impl ErrorExt for Error {
             InvalidFlumeSender { .. } => StatusCode::InvalidArguments,
             InvalidSchedulerState { .. } => StatusCode::InvalidArguments,
             StopScheduler { .. } => StatusCode::Internal,
+            DeleteSst { .. } | DeleteIndex { .. } => StatusCode::StorageUnavailable,
+            FlushRegion { source, .. } | CompactRegion { source, .. } => source.status_code(),
+            TtlCalculation { source, .. } => source.status_code(),
+            RegionClosed { .. } => StatusCode::Cancelled,
+            BuildPredicate { source, .. } => source.status_code(),
+            CreateRecordBatch { source, .. } => source.status_code(),
         }
     }
 
@@ -0,0 +1,366 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Flush related utilities and structs.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_telemetry::{error, info};
+use snafu::ResultExt;
+use store_api::storage::RegionId;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot::Sender;
+
+use crate::access_layer::AccessLayerRef;
+use crate::error::{Error, FlushRegionSnafu, RegionClosedSnafu, Result};
+use crate::manifest::action::{RegionEdit, RegionMetaAction, RegionMetaActionList};
+use crate::memtable::MemtableBuilderRef;
+use crate::read::Source;
+use crate::region::version::{VersionControlData, VersionRef};
+use crate::region::MitoRegionRef;
+use crate::request::{BackgroundNotify, FlushFailed, FlushFinished, WorkerRequest};
+use crate::schedule::scheduler::{Job, Scheduler, SchedulerRef};
+use crate::sst::file::{FileId, FileMeta};
+use crate::sst::parquet::WriteOptions;
+
+/// Reason of a flush task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FlushReason {
+    /// Other reasons.
+    Others,
+    /// Memtable is full.
+    MemtableFull,
+    /// Flush is triggered manually by users.
+    Manual,
+    /// Flush the region before closing it.
+    Close,
+}
+
+/// Task to flush a region.
+pub(crate) struct RegionFlushTask {
+    /// Region to flush.
+    pub(crate) region_id: RegionId,
+    /// Reason to flush.
+    pub(crate) reason: FlushReason,
+    /// Flush result senders.
+    pub(crate) senders: Vec<Sender<Result<()>>>,
+    /// Request sender to notify the worker.
+    pub(crate) request_sender: mpsc::Sender<WorkerRequest>,
+    /// Access layer of the region.
+    pub(crate) access_layer: AccessLayerRef,
+    /// Builder to build a new mutable memtable.
+    pub(crate) memtable_builder: MemtableBuilderRef,
+}
+
+impl RegionFlushTask {
+    /// Consumes the task and notify the sender the job is success.
+    fn on_success(self) {
+        for sender in self.senders {
+            let _ = sender.send(Ok(()));
+        }
+    }
+
+    /// Send flush error to waiters.
+    fn on_failure(&mut self, err: Arc<Error>) {
+        for sender in self.senders.drain(..) {
+            // Ignore send result.
+            let _ = sender.send(Err(err.clone()).context(FlushRegionSnafu {
+                region_id: self.region_id,
+            }));
+        }
+    }
+
+    /// Merges another task into this task.
+    fn merge(&mut self, mut other: RegionFlushTask) {
+        assert_eq!(self.region_id, other.region_id);
+        // Now we only merge senders. They share the same flush reason.
+        self.senders.append(&mut other.senders);
+    }
+
+    /// Converts the flush task into a background job.
+    ///
+    /// We must call this in the region worker.
+    fn into_flush_job(mut self, region: &MitoRegionRef) -> Job {
+        // Get a version of this region before creating a job to get current
+        // wal entry id, sequence and immutable memtables.
+        let version_data = region.version_control.current();
+        let region = region.clone();
+
+        Box::pin(async move {
+            self.do_flush(region, version_data).await;
+        })
+    }
+
+    /// Runs the flush task.
+    async fn do_flush(&mut self, region: MitoRegionRef, version_data: VersionControlData) {
+        let notify = match self.flush_memtables(&region, &version_data).await {
+            Ok(file_metas) => {
+                let memtables_to_remove = version_data
+                    .version
+                    .memtables
+                    .immutables()
+                    .iter()
+                    .map(|m| m.id())
+                    .collect();
+                let flush_finished = FlushFinished {
+                    file_metas,
+                    flushed_entry_id: version_data.last_entry_id,
+                    flushed_sequence: version_data.committed_sequence,
+                    memtables_to_remove,
+                    senders: std::mem::take(&mut self.senders),
+                };
+                BackgroundNotify::FlushFinished(flush_finished)
+            }
+            Err(e) => {
+                error!(e; "Failed to flush region {}", self.region_id);
+                let err = Arc::new(e);
+                self.on_failure(err.clone());
+                BackgroundNotify::FlushFailed(FlushFailed { err })
+            }
+        };
+
+        self.send_worker_request(WorkerRequest::Background {
+            region_id: self.region_id,
+            notify,
+        })
+        .await;
+    }
+
+    /// Flushes immutable memtables to level 0 SSTs and updates the manifest.
+    async fn flush_memtables(
+        &self,
+        region: &MitoRegionRef,
+        version_data: &VersionControlData,
+    ) -> Result<Vec<FileMeta>> {
+        let version = &version_data.version;
+        let file_metas = self.write_memtables(version).await?;
+
+        info!(
+            "Successfully flush memtables, region: {}, reason: {:?}, files: {:?}",
+            self.region_id,
+            self.reason,
+            file_metas
+                .iter()
+                .map(|meta| meta.file_id)
+                .collect::<Vec<_>>()
+        );
+
+        let edit = RegionEdit {
+            files_to_add: file_metas.clone(),
+            files_to_remove: Vec::new(),
+            compaction_time_window: None,
+            flushed_entry_id: Some(version_data.last_entry_id),
+            flushed_sequence: Some(version_data.committed_sequence),
+        };
+        let action_list = RegionMetaActionList::with_action(RegionMetaAction::Edit(edit));
+        region.manifest_manager.update(action_list).await?;
+
+        Ok(file_metas)
+    }
+
+    /// Writes each immutable memtable into a SST.
+    async fn write_memtables(&self, version: &VersionRef) -> Result<Vec<FileMeta>> {
+        let write_opts = WriteOptions {
+            bloom_filter_columns: version.bloom_filter_columns(),
+            ..Default::default()
+        };
+        let mut file_metas = Vec::with_capacity(version.memtables.immutables().len());
+
+        for memtable in version.memtables.immutables() {
+            if memtable.is_empty() {
+                continue;
+            }
+
+            let file_id = FileId::random();
+            let iter = memtable.iter(None);
+            let source = Source::Iter(iter);
+            let mut writer = self
+                .access_layer
+                .write_sst(file_id, version.metadata.clone(), source);
+            let Some(sst_info) = writer.write_all(&write_opts).await? else {
+                // No data written.
+                continue;
+            };
+
+            file_metas.push(FileMeta {
+                region_id: version.metadata.region_id,
+                file_id,
+                time_range: sst_info.time_range,
+                level: 0,
+                file_size: sst_info.file_size,
+                index_file_size: sst_info.index_file_size,
+            });
+        }
+
+        Ok(file_metas)
+    }
+
+    /// Notify flush job status.
+    async fn send_worker_request(&self, request: WorkerRequest) {
+        if self.request_sender.send(request).await.is_err() {
+            error!(
+                "Failed to notify flush job status for region {}, worker is stopped",
+                self.region_id
+            );
+        }
+    }
+}
+
+/// Manages background flushes of a worker.
+pub(crate) struct FlushScheduler {
+    /// Tracks regions need to flush.
+    region_status: HashMap<RegionId, FlushStatus>,
+    /// Background job scheduler.
+    scheduler: SchedulerRef,
+}
+
+impl FlushScheduler {
+    /// Creates a new flush scheduler.
+    pub(crate) fn new(scheduler: SchedulerRef) -> FlushScheduler {
+        FlushScheduler {
+            region_status: HashMap::new(),
+            scheduler,
+        }
+    }
+
+    /// Returns true if the region is stalling.
+    pub(crate) fn is_flush_requested(&self, region_id: RegionId) -> bool {
+        self.region_status.contains_key(&region_id)
+    }
+
+    /// Schedules a flush `task` for specific `region`.
+    pub(crate) fn schedule_flush(
+        &mut self,
+        region: &MitoRegionRef,
+        task: RegionFlushTask,
+    ) -> Result<()> {
+        debug_assert_eq!(region.region_id, task.region_id);
+
+        // Add this region to status map.
+        let flush_status = self.region_status.entry(region.region_id).or_default();
+        // Checks whether we can flush the region now.
+        if flush_status.flushing {
+            // There is already a flush job running.
+            flush_status.merge_task(task);
+            return Ok(());
+        }
+
+        // Now we can flush the region directly.
+        region
+            .version_control
+            .freeze_mutable(&task.memtable_builder);
+        if region
+            .version_control
+            .current()
+            .version
+            .memtables
+            .immutables()
+            .is_empty()
+        {
+            // Nothing to flush.
+            self.region_status.remove(&region.region_id);
+            task.on_success();
+            return Ok(());
+        }
+
+        let job = task.into_flush_job(region);
+        if let Err(e) = self.scheduler.schedule(job) {
+            // If scheduler returns error, senders in the job will be dropped and waiters
+            // can get recv errors.
+            error!(e; "Failed to schedule flush job for region {}", region.region_id);
+
+            // Remove from region status if we can't submit the task.
+            self.region_status.remove(&region.region_id);
+            return Err(e);
+        }
+        // Safety: The region status exists.
+        self.region_status
+            .get_mut(&region.region_id)
+            .unwrap()
+            .flushing = true;
+
+        Ok(())
+    }
+
+    /// Notifies the scheduler that the flush job is finished.
+    ///
+    /// Returns the pending task of the region if there is one.
+    pub(crate) fn on_flush_success(&mut self, region_id: RegionId) -> Option<RegionFlushTask> {
+        let Some(flush_status) = self.region_status.get_mut(&region_id) else {
+            return None;
+        };
+
+        // This region doesn't have running flush job.
+        flush_status.flushing = false;
+
+        let pending_task = flush_status.pending_task.take();
+        if pending_task.is_none() {
+            // The region doesn't have any pending flush task.
+            self.region_status.remove(&region_id);
+        }
+
+        pending_task
+    }
+
+    /// Notifies the scheduler that the flush job is failed.
+    pub(crate) fn on_flush_failed(&mut self, region_id: RegionId, err: Arc<Error>) {
+        // Remove this region.
+        let Some(flush_status) = self.region_status.remove(&region_id) else {
+            return;
+        };
+
+        // Fast fail: cancels the pending task.
+        flush_status.on_failure(err);
+    }
+
+    /// Notifies the scheduler that the region is closed.
+    pub(crate) fn on_region_closed(&mut self, region_id: RegionId) {
+        // Remove this region.
+        let Some(flush_status) = self.region_status.remove(&region_id) else {
+            return;
+        };
+
+        // Notifies all pending tasks.
+        flush_status.on_failure(Arc::new(RegionClosedSnafu { region_id }.build()));
+    }
+}
+
+/// Flush status of a region scheduled by the [FlushScheduler].
+///
+/// Tracks running and pending flush tasks.
+#[derive(Default)]
+struct FlushStatus {
+    /// There is a flush task running.
+    flushing: bool,
+    /// Task waiting for next flush.
+    pending_task: Option<RegionFlushTask>,
+}
+
+impl FlushStatus {
+    /// Merges the task to pending task.
+    fn merge_task(&mut self, task: RegionFlushTask) {
+        if let Some(pending) = &mut self.pending_task {
+            pending.merge(task);
+        } else {
+            self.pending_task = Some(task);
+        }
+    }
+
+    fn on_failure(self, err: Arc<Error>) {
+        if let Some(mut task) = self.pending_task {
+            task.on_failure(err);
+        }
+    }
+}
@@ -20,11 +20,16 @@
 pub mod test_util;
 
 // TODO(yingwen): Remove all `allow(dead_code)` after finish refactoring mito.
+mod access_layer;
+#[allow(dead_code)]
+mod compaction;
 pub mod config;
 #[allow(dead_code)]
 pub mod engine;
 pub mod error;
 #[allow(dead_code)]
+mod flush;
+#[allow(dead_code)]
 #[allow(unused_variables)]
 pub mod manifest;
 #[allow(dead_code)]
@@ -120,6 +125,7 @@ mod worker;
 ///     -MemtableVersionRef memtables
 ///     -SstVersionRef ssts
 ///     -SequenceNumber flushed_sequence
+///     -EntryId flushed_entry_id
 ///     -ManifestVersion manifest_version
 /// }
 /// class MemtableVersion {
@@ -152,6 +158,7 @@ mod worker;
 ///     +Option&lt;Timestamp, Timestamp&gt; time_range
 ///     +Level level
 ///     +u64 file_size
+///     +u64 index_file_size
 /// }
 /// VersionControl o-- Version
 /// Version o-- RegionMetadata
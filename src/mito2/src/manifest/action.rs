@@ -25,6 +25,7 @@ use store_api::storage::{RegionId, SequenceNumber};
 
 use crate::error::{RegionMetadataNotFoundSnafu, Result, SerdeJsonSnafu, Utf8Snafu};
 use crate::sst::file::{FileId, FileMeta};
+use crate::wal::EntryId;
 
 /// Actions that can be applied to region manifest.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -50,6 +51,7 @@ pub struct RegionEdit {
     pub files_to_add: Vec<FileMeta>,
     pub files_to_remove: Vec<FileMeta>,
     pub compaction_time_window: Option<i64>,
+    pub flushed_entry_id: Option<EntryId>,
     pub flushed_sequence: Option<SequenceNumber>,
 }
 
@@ -65,6 +67,12 @@ pub struct RegionManifest {
     pub metadata: RegionMetadataRef,
     /// SST files.
     pub files: HashMap<FileId, FileMeta>,
+    /// Last WAL entry id of flushed data.
+    #[serde(default)]
+    pub flushed_entry_id: EntryId,
+    /// Last sequence of flushed data.
+    #[serde(default)]
+    pub flushed_sequence: SequenceNumber,
     /// Current manifest version.
     pub manifest_version: ManifestVersion,
 }
@@ -73,6 +81,8 @@ pub struct RegionManifest {
 pub struct RegionManifestBuilder {
     metadata: Option<RegionMetadataRef>,
     files: HashMap<FileId, FileMeta>,
+    flushed_entry_id: EntryId,
+    flushed_sequence: SequenceNumber,
     manifest_version: ManifestVersion,
 }
 
@@ -83,6 +93,8 @@ impl RegionManifestBuilder {
             Self {
                 metadata: Some(s.metadata),
                 files: s.files,
+                flushed_entry_id: s.flushed_entry_id,
+                flushed_sequence: s.flushed_sequence,
                 manifest_version: s.manifest_version,
             }
         } else {
@@ -103,6 +115,12 @@ impl RegionManifestBuilder {
         for file in edit.files_to_remove {
             self.files.remove(&file.file_id);
         }
+        if let Some(flushed_entry_id) = edit.flushed_entry_id {
+            self.flushed_entry_id = self.flushed_entry_id.max(flushed_entry_id);
+        }
+        if let Some(flushed_sequence) = edit.flushed_sequence {
+            self.flushed_sequence = self.flushed_sequence.max(flushed_sequence);
+        }
     }
 
     /// Check if the builder keeps a [RegionMetadata](crate::metadata::RegionMetadata).
@@ -115,6 +133,8 @@ impl RegionManifestBuilder {
         Ok(RegionManifest {
             metadata,
             files: self.files,
+            flushed_entry_id: self.flushed_entry_id,
+            flushed_sequence: self.flushed_sequence,
             manifest_version: self.manifest_version,
         })
     }
@@ -237,6 +257,7 @@ mod tests {
             time_range: (0.into(), 10000.into()),
             level: 0,
             file_size: 1024,
+            index_file_size: 0,
         }
     }
 
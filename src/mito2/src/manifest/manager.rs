@@ -71,6 +71,7 @@ pub struct RegionManifestOptions {
 ///     -VersionNumber regoin_version
 ///     -Vec~FileMeta~ files_to_add
 ///     -Vec~FileMeta~ files_to_remove
+///     -EntryId flushed_entry_id
 ///     -SequenceNumber flushed_sequence
 /// }
 /// class RegionRemove {
@@ -93,6 +94,8 @@ pub struct RegionManifestOptions {
 /// class RegionManifest {
 ///     -RegionMetadataRef metadata
 ///     -HashMap&lt;FileId, FileMeta&gt; files
+///     -EntryId flushed_entry_id
+///     -SequenceNumber flushed_sequence
 ///     -ManifestVersion manifest_version
 /// }
 /// class RegionMetadata
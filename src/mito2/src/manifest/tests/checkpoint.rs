@@ -57,6 +57,7 @@ fn nop_action() -> RegionMetaActionList {
         files_to_add: vec![],
         files_to_remove: vec![],
         compaction_time_window: None,
+        flushed_entry_id: None,
         flushed_sequence: None,
     })])
 }
@@ -170,11 +171,13 @@ async fn checkpoint_with_different_compression_types() {
             time_range: (0.into(), 10000000.into()),
             level: 0,
             file_size: 1024000,
+            index_file_size: 0,
         };
         let action = RegionMetaActionList::new(vec![RegionMetaAction::Edit(RegionEdit {
             files_to_add: vec![file_meta],
             files_to_remove: vec![],
             compaction_time_window: None,
+            flushed_entry_id: None,
             flushed_sequence: None,
         })]);
         actions.push(action);
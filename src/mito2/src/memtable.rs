@@ -24,7 +24,7 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
 use store_api::metadata::RegionMetadataRef;
-use store_api::storage::ScanRequest;
+use store_api::storage::ColumnId;
 
 use crate::error::Result;
 pub use crate::memtable::key_values::KeyValues;
@@ -45,11 +45,27 @@ pub trait Memtable: Send + Sync + fmt::Debug {
     /// Write key values into the memtable.
     fn write(&self, kvs: &KeyValues) -> Result<()>;
 
-    fn iter(&self, req: ScanRequest) -> BoxedBatchIterator;
+    /// Scans the memtable for `projection` columns.
+    ///
+    /// The projection only applies to fields and `None` reads all fields.
+    fn iter(&self, projection: Option<&[ColumnId]>) -> BoxedBatchIterator;
+
+    /// Returns true if the memtable is empty.
+    fn is_empty(&self) -> bool;
+
+    /// Returns the statistics of the memtable.
+    fn stats(&self) -> MemtableStats;
 }
 
 pub type MemtableRef = Arc<dyn Memtable>;
 
+/// Statistics of a memtable.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MemtableStats {
+    /// The estimated bytes allocated by this memtable from heap.
+    pub estimated_bytes: usize,
+}
+
 /// Builder to build a new [Memtable].
 pub trait MemtableBuilder: Send + Sync + fmt::Debug {
     /// Builds a new memtable instance.
@@ -82,9 +98,17 @@ impl Memtable for EmptyMemtable {
         Ok(())
     }
 
-    fn iter(&self, _req: ScanRequest) -> BoxedBatchIterator {
+    fn iter(&self, _projection: Option<&[ColumnId]>) -> BoxedBatchIterator {
         Box::new(std::iter::empty())
     }
+
+    fn is_empty(&self) -> bool {
+        true
+    }
+
+    fn stats(&self) -> MemtableStats {
+        MemtableStats::default()
+    }
 }
 
 /// Default memtable builder.
@@ -13,8 +13,9 @@
 // limitations under the License.
 
 use std::collections::btree_map::Entry;
-use std::collections::{BTreeMap, Bound};
+use std::collections::{BTreeMap, Bound, HashSet};
 use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 
 use api::v1::OpType;
@@ -24,26 +25,45 @@ use datatypes::value::ValueRef;
 use datatypes::vectors::{UInt64Vector, UInt64VectorBuilder, UInt8Vector, UInt8VectorBuilder};
 use snafu::{ensure, ResultExt};
 use store_api::metadata::RegionMetadataRef;
-use store_api::storage::ScanRequest;
+use store_api::storage::ColumnId;
 
 use crate::error::{CompactValuesSnafu, PrimaryKeyLengthMismatchSnafu, Result};
-use crate::memtable::{BoxedBatchIterator, KeyValues, Memtable, MemtableId};
+use crate::memtable::{
+    BoxedBatchIterator, KeyValues, Memtable, MemtableBuilder, MemtableId, MemtableRef,
+    MemtableStats,
+};
 use crate::read::{Batch, BatchBuilder, BatchColumn};
 use crate::row_converter::{McmpRowCodec, RowCodec, SortField};
 
 /// Initial vector builder capacity.
 const INITIAL_BUILDER_CAPACITY: usize = 32;
 
+/// Builder to build [TimeSeriesMemtable].
+#[derive(Debug, Default)]
+pub struct TimeSeriesMemtableBuilder {
+    /// Next memtable id.
+    next_id: AtomicU32,
+}
+
+impl MemtableBuilder for TimeSeriesMemtableBuilder {
+    fn build(&self, metadata: &RegionMetadataRef) -> MemtableRef {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        Arc::new(TimeSeriesMemtable::new(metadata.clone(), id))
+    }
+}
+
 /// Memtable implementation that groups rows by their primary key.
 pub struct TimeSeriesMemtable {
     id: MemtableId,
     region_metadata: RegionMetadataRef,
     row_codec: McmpRowCodec,
     series_set: SeriesSet,
+    /// Estimated bytes written to this memtable.
+    estimated_bytes: AtomicUsize,
 }
 
 impl TimeSeriesMemtable {
-    pub fn new(region_metadata: RegionMetadataRef, id: MemtableId) -> Result<Self> {
+    pub fn new(region_metadata: RegionMetadataRef, id: MemtableId) -> Self {
         let row_codec = McmpRowCodec::new(
             region_metadata
                 .primary_key_columns()
@@ -51,12 +71,13 @@ impl TimeSeriesMemtable {
                 .collect(),
         );
         let series_set = SeriesSet::new(region_metadata.clone());
-        Ok(Self {
+        Self {
             id,
             region_metadata,
             series_set,
             row_codec,
-        })
+            estimated_bytes: AtomicUsize::new(0),
+        }
     }
 }
 
@@ -72,6 +93,7 @@ impl Memtable for TimeSeriesMemtable {
     }
 
     fn write(&self, kvs: &KeyValues) -> Result<()> {
+        let mut written_bytes = 0;
         for kv in kvs.iter() {
             ensure!(
                 kv.num_primary_keys() == self.row_codec.num_fields(),
@@ -81,22 +103,53 @@ impl Memtable for TimeSeriesMemtable {
                 }
             );
             let primary_key_encoded = self.row_codec.encode(kv.primary_keys())?;
-            let fields = kv.fields().collect();
+            let fields: Vec<_> = kv.fields().collect();
+            written_bytes += primary_key_encoded.len()
+                + ROW_INTERNAL_COLUMNS_SIZE
+                + fields.iter().map(estimated_value_size).sum::<usize>();
             let series = self.series_set.get_or_add_series(primary_key_encoded);
             let mut guard = series.write().unwrap();
             guard.push(kv.timestamp(), kv.sequence(), kv.op_type(), fields);
         }
+        self.estimated_bytes
+            .fetch_add(written_bytes, Ordering::Relaxed);
         Ok(())
     }
 
-    fn iter(&self, req: ScanRequest) -> BoxedBatchIterator {
-        let _projection = req.projection.map(|p| {
-            p.iter()
-                .map(|idx| self.region_metadata.column_metadatas[*idx].column_id)
-                .collect::<Vec<_>>()
-        });
+    fn iter(&self, projection: Option<&[ColumnId]>) -> BoxedBatchIterator {
+        let projection = if let Some(projection) = projection {
+            projection.iter().copied().collect()
+        } else {
+            self.region_metadata
+                .field_columns()
+                .map(|c| c.column_id)
+                .collect()
+        };
+
+        Box::new(self.series_set.iter_series(projection))
+    }
 
-        Box::new(self.series_set.iter_series())
+    fn is_empty(&self) -> bool {
+        self.series_set.series.read().unwrap().is_empty()
+    }
+
+    fn stats(&self) -> MemtableStats {
+        MemtableStats {
+            estimated_bytes: self.estimated_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Size of timestamp, sequence and op type of a row.
+const ROW_INTERNAL_COLUMNS_SIZE: usize = 8 + 8 + 1;
+
+/// Returns the estimated size of the value.
+fn estimated_value_size(value: &ValueRef) -> usize {
+    match value {
+        ValueRef::Null => 0,
+        ValueRef::String(s) => s.len(),
+        ValueRef::Binary(b) => b.len(),
+        _ => std::mem::size_of::<i64>(),
     }
 }
 
@@ -135,10 +188,11 @@ impl SeriesSet {
     }
 
     /// Iterates all series in [SeriesSet].
-    fn iter_series(&self) -> Iter {
+    fn iter_series(&self, projection: HashSet<ColumnId>) -> Iter {
         Iter {
             metadata: self.region_metadata.clone(),
             series: self.series.clone(),
+            projection,
             last_key: None,
         }
     }
@@ -147,6 +201,8 @@ impl SeriesSet {
 struct Iter {
     metadata: RegionMetadataRef,
     series: Arc<SeriesRwLockMap>,
+    /// Field column ids to read.
+    projection: HashSet<ColumnId>,
     last_key: Option<Vec<u8>>,
 }
 
@@ -165,7 +221,7 @@ impl Iterator for Iter {
         if let Some((primary_key, series)) = range.next() {
             self.last_key = Some(primary_key.clone());
             let values = series.write().unwrap().compact(&self.metadata);
-            Some(values.and_then(|v| v.to_batch(primary_key, &self.metadata)))
+            Some(values.and_then(|v| v.to_batch(primary_key, &self.metadata, &self.projection)))
         } else {
             None
         }
@@ -341,7 +397,14 @@ struct Values {
 impl Values {
     /// Converts [Values] to `Batch`, sorts the batch according to `timestamp, sequence` desc and
     /// keeps only the latest row for the same timestamp.
-    pub fn to_batch(&self, primary_key: &[u8], metadata: &RegionMetadataRef) -> Result<Batch> {
+    ///
+    /// Only fields in the `projection` are kept in the batch.
+    pub fn to_batch(
+        &self,
+        primary_key: &[u8],
+        metadata: &RegionMetadataRef,
+        projection: &HashSet<ColumnId>,
+    ) -> Result<Batch> {
         let builder = BatchBuilder::with_required_columns(
             primary_key.to_vec(),
             self.timestamp.clone(),
@@ -352,6 +415,7 @@ impl Values {
         let fields = metadata
             .field_columns()
             .zip(self.fields.iter())
+            .filter(|(c, _)| projection.contains(&c.column_id))
             .map(|(c, f)| BatchColumn {
                 column_id: c.column_id,
                 data: f.clone(),
@@ -545,7 +609,8 @@ mod tests {
             fields,
         };
 
-        let batch = values.to_batch(b"test", &schema).unwrap();
+        let projection = schema.field_columns().map(|c| c.column_id).collect();
+        let batch = values.to_batch(b"test", &schema, &projection).unwrap();
         check_value(
             &batch,
             vec![
@@ -703,15 +768,18 @@ mod tests {
         common_telemetry::init_default_ut_logging();
         let schema = schema_for_test();
         let kvs = build_key_values(&schema, 100);
-        let memtable = TimeSeriesMemtable::new(schema, 42).unwrap();
+        let memtable = TimeSeriesMemtable::new(schema, 42);
+        assert!(memtable.is_empty());
         memtable.write(&kvs).unwrap();
+        assert!(!memtable.is_empty());
+        assert!(memtable.stats().estimated_bytes > 0);
 
         let expected_ts = kvs
             .iter()
             .map(|kv| kv.timestamp().as_timestamp().unwrap().unwrap().value())
             .collect::<HashSet<_>>();
 
-        let iter = memtable.iter(ScanRequest::default());
+        let iter = memtable.iter(None);
         let read = iter
             .flat_map(|batch| {
                 batch
@@ -728,4 +796,23 @@ mod tests {
             .collect::<HashSet<_>>();
         assert_eq!(expected_ts, read);
     }
+
+    #[test]
+    fn test_memtable_projection() {
+        common_telemetry::init_default_ut_logging();
+        let schema = schema_for_test();
+        let kvs = build_key_values(&schema, 100);
+        let memtable = TimeSeriesMemtable::new(schema, 42);
+        memtable.write(&kvs).unwrap();
+
+        let iter = memtable.iter(Some(&[3]));
+        let mut num_rows = 0;
+        for batch in iter {
+            let batch = batch.unwrap();
+            assert_eq!(1, batch.fields().len());
+            assert_eq!(3, batch.fields()[0].column_id);
+            num_rows += batch.num_rows();
+        }
+        assert_eq!(100, num_rows);
+    }
 }
@@ -16,14 +16,18 @@
 
 use std::sync::Arc;
 
-use crate::memtable::MemtableRef;
+use crate::memtable::{MemtableId, MemtableRef};
 
 /// A version of current memtables in a region.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct MemtableVersion {
     /// Mutable memtable.
     mutable: MemtableRef,
     /// Immutable memtables.
+    ///
+    /// We only allow one flush job per region but if a flush job failed, then we
+    /// might need to store more than one immutable memtable on the next time we
+    /// flush the region.
     immutables: Vec<MemtableRef>,
 }
 
@@ -42,4 +46,56 @@ impl MemtableVersion {
     pub(crate) fn mutable(&self) -> &MemtableRef {
         &self.mutable
     }
+
+    /// Immutable memtables.
+    pub(crate) fn immutables(&self) -> &[MemtableRef] {
+        &self.immutables
+    }
+
+    /// Lists mutable and immutable memtables.
+    pub(crate) fn list_memtables(&self) -> Vec<MemtableRef> {
+        let mut memtables = Vec::with_capacity(self.immutables.len() + 1);
+        memtables.push(self.mutable.clone());
+        memtables.extend_from_slice(&self.immutables);
+        memtables
+    }
+
+    /// Freezes the mutable memtable and returns a new [MemtableVersion] whose mutable
+    /// memtable is `new_mutable`.
+    ///
+    /// Returns `None` if the mutable memtable is empty.
+    pub(crate) fn freeze_mutable(&self, new_mutable: MemtableRef) -> Option<MemtableVersion> {
+        debug_assert!(new_mutable.is_empty());
+        if self.mutable.is_empty() {
+            return None;
+        }
+
+        // Pushes the mutable memtable to immutable list.
+        let mut immutables = self.immutables.clone();
+        immutables.push(self.mutable.clone());
+
+        Some(MemtableVersion {
+            mutable: new_mutable,
+            immutables,
+        })
+    }
+
+    /// Removes memtables by ids from immutable memtables.
+    pub(crate) fn remove_memtables(&mut self, ids: &[MemtableId]) {
+        self.immutables
+            .retain(|memtable| !ids.contains(&memtable.id()));
+    }
+
+    /// Returns the memory usage of the mutable memtable.
+    pub(crate) fn mutable_usage(&self) -> usize {
+        self.mutable.stats().estimated_bytes
+    }
+
+    /// Returns true if the memtable version is empty.
+    ///
+    /// The version is empty when mutable memtable is empty and there is no
+    /// immutable memtables.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.mutable.is_empty() && self.immutables.is_empty()
+    }
 }
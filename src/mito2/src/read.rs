@@ -15,6 +15,9 @@
 //! Common structs and utilities for reading data.
 
 pub mod merge;
+pub(crate) mod projection;
+pub(crate) mod scan_region;
+pub(crate) mod seq_scan;
 
 use std::sync::Arc;
 
@@ -538,21 +541,6 @@ impl BatchBuilder {
     }
 }
 
-/// Collected [Source] statistics.
-#[derive(Debug, Clone)]
-pub struct SourceStats {
-    /// Number of rows fetched.
-    pub num_rows: usize,
-    /// Min timestamp from fetched batches.
-    ///
-    /// If no rows fetched, the value of the timestamp is i64::MIN.
-    pub min_timestamp: Timestamp,
-    /// Max timestamp from fetched batches.
-    ///
-    /// If no rows fetched, the value of the timestamp is i64::MAX.
-    pub max_timestamp: Timestamp,
-}
-
 /// Async [Batch] reader and iterator wrapper.
 ///
 /// This is the data source for SST writers or internal readers.
@@ -571,12 +559,6 @@ impl Source {
             Source::Iter(iter) => iter.next().transpose(),
         }
     }
-
-    // TODO(yingwen): Remove this method once we support collecting stats in the writer.
-    /// Returns statisics of fetched batches.
-    pub(crate) fn stats(&self) -> SourceStats {
-        unimplemented!()
-    }
 }
 
 /// Async batch reader.
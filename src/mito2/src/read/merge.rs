@@ -68,7 +68,9 @@ impl BatchReader for MergeReader {
 
 impl MergeReader {
     /// Creates a new [MergeReader].
-    pub async fn new(sources: Vec<Source>) -> Result<MergeReader> {
+    ///
+    /// The reader removes deleted rows if `filter_deleted` is true.
+    pub async fn new(sources: Vec<Source>, filter_deleted: bool) -> Result<MergeReader> {
         let mut nodes = BinaryHeap::with_capacity(sources.len());
         for source in sources {
             let node = Node::new(source).await?;
@@ -80,7 +82,7 @@ impl MergeReader {
 
         Ok(MergeReader {
             nodes,
-            batch_merger: BatchMerger::new(),
+            batch_merger: BatchMerger::new(filter_deleted),
         })
     }
 
@@ -102,12 +104,22 @@ impl MergeReader {
 }
 
 /// Builder to build and initialize a [MergeReader].
-#[derive(Default)]
 pub struct MergeReaderBuilder {
     /// Input sources.
     ///
     /// All source must yield batches with the same schema.
     sources: Vec<Source>,
+    /// Whether to remove deleted rows.
+    filter_deleted: bool,
+}
+
+impl Default for MergeReaderBuilder {
+    fn default() -> MergeReaderBuilder {
+        MergeReaderBuilder {
+            sources: Vec::new(),
+            filter_deleted: true,
+        }
+    }
 }
 
 impl MergeReaderBuilder {
@@ -116,6 +128,15 @@ impl MergeReaderBuilder {
         MergeReaderBuilder::default()
     }
 
+    /// Sets whether to remove deleted rows, which is true by default.
+    ///
+    /// Compaction keeps the delete markers if its output may be merged with other files that
+    /// contain rows they delete.
+    pub fn filter_deleted(&mut self, filter_deleted: bool) -> &mut Self {
+        self.filter_deleted = filter_deleted;
+        self
+    }
+
     /// Pushs a batch reader to sources.
     pub fn push_batch_reader(&mut self, reader: BoxedBatchReader) -> &mut Self {
         self.sources.push(Source::Reader(reader));
@@ -131,7 +152,7 @@ impl MergeReaderBuilder {
     /// Builds and initializes the reader, then resets the builder.
     pub async fn build(&mut self) -> Result<MergeReader> {
         let sources = mem::take(&mut self.sources);
-        MergeReader::new(sources).await
+        MergeReader::new(sources, self.filter_deleted).await
     }
 }
 
@@ -141,14 +162,17 @@ struct BatchMerger {
     batches: Vec<Batch>,
     /// Whether the batch buffer is still sorted.
     is_sorted: bool,
+    /// Whether to remove deleted rows.
+    filter_deleted: bool,
 }
 
 impl BatchMerger {
     /// Returns a empty merger.
-    fn new() -> BatchMerger {
+    fn new(filter_deleted: bool) -> BatchMerger {
         BatchMerger {
             batches: Vec::new(),
             is_sorted: true, // An empty merger is always sorted.
+            filter_deleted,
         }
     }
 
@@ -177,22 +201,13 @@ impl BatchMerger {
         // Merger is sorted, checks whether we can still preserve sorted state.
         let last_batch = self.batches.last().unwrap();
         assert_eq!(last_batch.primary_key(), batch.primary_key());
-        match last_batch.last_timestamp().cmp(&batch.first_timestamp()) {
-            Ordering::Less => {
-                // Still sorted.
-                self.batches.push(batch);
-                return;
-            }
-            Ordering::Equal => {
-                // Check sequence.
-                if last_batch.last_sequence() > batch.first_sequence() {
-                    // Still sorted.
-                    self.batches.push(batch);
-                    return;
-                }
-            }
-            Ordering::Greater => (),
+        if last_batch.last_timestamp() < batch.first_timestamp() {
+            // Still sorted.
+            self.batches.push(batch);
+            return;
         }
+        // Batches have the same timestamp or are overlapping. We need to remove
+        // duplicate rows so we treat them as unsorted.
 
         // Merger is no longer sorted.
         self.batches.push(batch);
@@ -221,7 +236,9 @@ impl BatchMerger {
 
         // Filter rows by op type. Currently, the reader only removes deleted rows but doesn't filter
         // rows by sequence for simplicity and performance reason.
-        batch.filter_deleted()?;
+        if self.filter_deleted {
+            batch.filter_deleted()?;
+        }
 
         Ok(Some(batch))
     }
@@ -419,6 +436,42 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_merge_keep_deleted() {
+        let reader1 = VecBatchReader::new(&[new_batch(
+            b"k1",
+            &[1, 2],
+            &[11, 12],
+            &[OpType::Put, OpType::Put],
+            &[21, 22],
+        )]);
+        let reader2 = VecBatchReader::new(&[new_batch(
+            b"k1",
+            &[2, 3],
+            &[13, 14],
+            &[OpType::Delete, OpType::Put],
+            &[23, 24],
+        )]);
+        let mut reader = MergeReaderBuilder::new()
+            .push_batch_reader(Box::new(reader1))
+            .push_batch_reader(Box::new(reader2))
+            .filter_deleted(false)
+            .build()
+            .await
+            .unwrap();
+        check_merge_result(
+            &mut reader,
+            &[new_batch(
+                b"k1",
+                &[1, 2, 3],
+                &[11, 13, 14],
+                &[OpType::Put, OpType::Delete, OpType::Put],
+                &[21, 23, 24],
+            )],
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn test_merge_overlapping() {
         let reader1 = VecBatchReader::new(&[
@@ -489,4 +542,46 @@ mod tests {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn test_merge_same_timestamp() {
+        let reader1 = VecBatchReader::new(&[new_batch(
+            b"k1",
+            &[1, 2],
+            &[11, 12],
+            &[OpType::Put, OpType::Put],
+            &[21, 22],
+        )]);
+        let reader2 = VecBatchReader::new(&[
+            new_batch(
+                b"k1",
+                // This deletes 2.
+                &[2, 3],
+                &[13, 14],
+                &[OpType::Delete, OpType::Put],
+                &[32, 33],
+            ),
+            new_batch(b"k2", &[1], &[15], &[OpType::Put], &[34]),
+        ]);
+        let mut reader = MergeReaderBuilder::new()
+            .push_batch_reader(Box::new(reader1))
+            .push_batch_iter(Box::new(reader2))
+            .build()
+            .await
+            .unwrap();
+        check_merge_result(
+            &mut reader,
+            &[
+                new_batch(
+                    b"k1",
+                    &[1, 3],
+                    &[11, 14],
+                    &[OpType::Put, OpType::Put],
+                    &[21, 33],
+                ),
+                new_batch(b"k2", &[1], &[15], &[OpType::Put], &[34]),
+            ],
+        )
+        .await;
+    }
 }
@@ -0,0 +1,327 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for projection.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use api::v1::SemanticType;
+use common_recordbatch::RecordBatch;
+use datatypes::prelude::{ConcreteDataType, DataType};
+use datatypes::schema::{Schema, SchemaRef};
+use datatypes::value::ValueRef;
+use datatypes::vectors::VectorRef;
+use snafu::{OptionExt, ResultExt};
+use store_api::metadata::RegionMetadata;
+use store_api::storage::ColumnId;
+
+use crate::error::{ComputeVectorSnafu, CreateRecordBatchSnafu, InvalidRequestSnafu, Result};
+use crate::read::Batch;
+use crate::row_converter::{McmpRowCodec, RowCodec, SortField};
+
+/// Handles projection and converts a projected [Batch] to a projected [RecordBatch].
+pub(crate) struct ProjectionMapper {
+    /// Maps column in [RecordBatch] to index in [Batch].
+    batch_indices: Vec<BatchIndex>,
+    /// Decoder for primary key.
+    codec: McmpRowCodec,
+    /// Schema for converted [RecordBatch].
+    output_schema: SchemaRef,
+    /// Id of columns to project.
+    column_ids: Vec<ColumnId>,
+}
+
+impl ProjectionMapper {
+    /// Returns a new mapper with projection.
+    ///
+    /// The `projection` contains indices of columns in the `metadata`.
+    pub(crate) fn new(
+        metadata: &RegionMetadata,
+        projection: impl Iterator<Item = usize>,
+    ) -> Result<ProjectionMapper> {
+        let projection_len = projection.size_hint().0;
+        let mut column_schemas = Vec::with_capacity(projection_len);
+        let mut column_ids = Vec::with_capacity(projection_len);
+        for idx in projection {
+            let column =
+                metadata
+                    .column_metadatas
+                    .get(idx)
+                    .with_context(|| InvalidRequestSnafu {
+                        region_id: metadata.region_id,
+                        reason: format!("projection index {} is out of bound", idx),
+                    })?;
+            column_schemas.push(metadata.schema.column_schemas()[idx].clone());
+            column_ids.push(column.column_id);
+        }
+
+        // Fields in the batch are sorted by their order in the metadata and only
+        // contain projected fields.
+        let field_id_to_index: HashMap<_, _> = metadata
+            .field_columns()
+            .filter(|column| column_ids.contains(&column.column_id))
+            .enumerate()
+            .map(|(index, column)| (column.column_id, index))
+            .collect();
+        // Safety: All columns to project exist in the metadata.
+        let batch_indices = column_ids
+            .iter()
+            .map(|id| {
+                let column = metadata.column_by_id(*id).unwrap();
+                match column.semantic_type {
+                    SemanticType::Tag => {
+                        let index = metadata
+                            .primary_key
+                            .iter()
+                            .position(|pk_id| pk_id == id)
+                            .unwrap();
+                        BatchIndex::Tag(index)
+                    }
+                    SemanticType::Timestamp => BatchIndex::Timestamp,
+                    SemanticType::Field => BatchIndex::Field(field_id_to_index[id]),
+                }
+            })
+            .collect();
+        let codec = McmpRowCodec::new(
+            metadata
+                .primary_key_columns()
+                .map(|c| SortField::new(c.column_schema.data_type.clone()))
+                .collect(),
+        );
+        let output_schema = Arc::new(Schema::new(column_schemas));
+
+        Ok(ProjectionMapper {
+            batch_indices,
+            codec,
+            output_schema,
+            column_ids,
+        })
+    }
+
+    /// Returns a new mapper without projection.
+    pub(crate) fn all(metadata: &RegionMetadata) -> Result<ProjectionMapper> {
+        ProjectionMapper::new(metadata, 0..metadata.column_metadatas.len())
+    }
+
+    /// Returns ids of projected columns.
+    pub(crate) fn column_ids(&self) -> &[ColumnId] {
+        &self.column_ids
+    }
+
+    /// Returns the schema of converted [RecordBatch].
+    pub(crate) fn output_schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+
+    /// Converts a [Batch] to a [RecordBatch].
+    ///
+    /// The batch must match the `projection` used to build the mapper.
+    pub(crate) fn convert(&self, batch: &Batch) -> Result<RecordBatch> {
+        let has_tags = self
+            .batch_indices
+            .iter()
+            .any(|index| matches!(index, BatchIndex::Tag(_)));
+        let pk_values = if has_tags {
+            self.codec.decode(batch.primary_key())?
+        } else {
+            Vec::new()
+        };
+
+        let mut columns = Vec::with_capacity(self.output_schema.num_columns());
+        let num_rows = batch.num_rows();
+        for (index, column_schema) in self
+            .batch_indices
+            .iter()
+            .zip(self.output_schema.column_schemas())
+        {
+            match index {
+                BatchIndex::Tag(idx) => {
+                    let value = pk_values[*idx].as_value_ref();
+                    let vector = new_repeated_vector(&column_schema.data_type, value, num_rows)?;
+                    columns.push(vector);
+                }
+                BatchIndex::Timestamp => {
+                    columns.push(batch.timestamps().clone());
+                }
+                BatchIndex::Field(idx) => {
+                    columns.push(batch.fields()[*idx].data.clone());
+                }
+            }
+        }
+
+        RecordBatch::new(self.output_schema.clone(), columns).context(CreateRecordBatchSnafu)
+    }
+}
+
+/// Index of a vector in a [Batch].
+#[derive(Debug, Clone, Copy)]
+enum BatchIndex {
+    /// Index in primary keys.
+    Tag(usize),
+    /// The time index column.
+    Timestamp,
+    /// Index in fields.
+    Field(usize),
+}
+
+/// Returns a vector with repeated values.
+fn new_repeated_vector(
+    data_type: &ConcreteDataType,
+    value: ValueRef,
+    num_rows: usize,
+) -> Result<VectorRef> {
+    let mut mutable_vector = data_type.create_mutable_vector(num_rows);
+    for _ in 0..num_rows {
+        mutable_vector
+            .try_push_value_ref(value)
+            .context(ComputeVectorSnafu)?;
+    }
+    Ok(mutable_vector.to_vector())
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::OpType;
+    use datatypes::arrow::array::{Int64Array, TimestampMillisecondArray, UInt64Array, UInt8Array};
+    use datatypes::arrow::util::pretty;
+    use datatypes::schema::ColumnSchema;
+    use store_api::metadata::{ColumnMetadata, RegionMetadataBuilder};
+    use store_api::storage::RegionId;
+
+    use super::*;
+    use crate::read::BatchBuilder;
+
+    fn new_metadata() -> RegionMetadata {
+        let mut builder = RegionMetadataBuilder::new(RegionId::new(1, 1));
+        builder
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("k0", ConcreteDataType::int64_datatype(), false),
+                semantic_type: SemanticType::Tag,
+                column_id: 0,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("k1", ConcreteDataType::int64_datatype(), false),
+                semantic_type: SemanticType::Tag,
+                column_id: 1,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 2,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("v0", ConcreteDataType::int64_datatype(), true),
+                semantic_type: SemanticType::Field,
+                column_id: 3,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("v1", ConcreteDataType::int64_datatype(), true),
+                semantic_type: SemanticType::Field,
+                column_id: 4,
+            })
+            .primary_key(vec![0, 1]);
+        builder.build().unwrap()
+    }
+
+    fn new_batch(tags: &[i64], fields: &[(ColumnId, i64)], num_rows: usize) -> Batch {
+        let converter = McmpRowCodec::new(
+            (0..tags.len())
+                .map(|_| SortField::new(ConcreteDataType::int64_datatype()))
+                .collect(),
+        );
+        let primary_key = converter
+            .encode(tags.iter().map(|v| ValueRef::Int64(*v)))
+            .unwrap();
+
+        let mut builder = BatchBuilder::new(primary_key);
+        builder
+            .timestamps_array(Arc::new(TimestampMillisecondArray::from_iter_values(
+                (0..num_rows).map(|i| i as i64 * 1000),
+            )))
+            .unwrap()
+            .sequences_array(Arc::new(UInt64Array::from_iter_values(0..num_rows as u64)))
+            .unwrap()
+            .op_types_array(Arc::new(UInt8Array::from_iter_values(
+                (0..num_rows).map(|_| OpType::Put as u8),
+            )))
+            .unwrap();
+        for (column_id, field) in fields {
+            builder
+                .push_field_array(
+                    *column_id,
+                    Arc::new(Int64Array::from_iter_values(
+                        std::iter::repeat(*field).take(num_rows),
+                    )),
+                )
+                .unwrap();
+        }
+        builder.build().unwrap()
+    }
+
+    fn print_record_batch(record_batch: RecordBatch) -> String {
+        pretty::pretty_format_batches(&[record_batch.into_df_record_batch()])
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_projection_mapper_all() {
+        let metadata = new_metadata();
+        let mapper = ProjectionMapper::all(&metadata).unwrap();
+        assert_eq!([0, 1, 2, 3, 4], mapper.column_ids());
+
+        let batch = new_batch(&[1, 2], &[(3, 3), (4, 4)], 3);
+        let record_batch = mapper.convert(&batch).unwrap();
+        let expect = "\
++----+----+---------------------+----+----+
+| k0 | k1 | ts                  | v0 | v1 |
++----+----+---------------------+----+----+
+| 1  | 2  | 1970-01-01T00:00:00 | 3  | 4  |
+| 1  | 2  | 1970-01-01T00:00:01 | 3  | 4  |
+| 1  | 2  | 1970-01-01T00:00:02 | 3  | 4  |
++----+----+---------------------+----+----+";
+        assert_eq!(expect, print_record_batch(record_batch));
+    }
+
+    #[test]
+    fn test_projection_mapper_with_projection() {
+        let metadata = new_metadata();
+        // Columns v1, k0
+        let mapper = ProjectionMapper::new(&metadata, [4, 0].into_iter()).unwrap();
+        assert_eq!([4, 0], mapper.column_ids());
+
+        let batch = new_batch(&[1, 2], &[(4, 4)], 3);
+        let record_batch = mapper.convert(&batch).unwrap();
+        let expect = "\
++----+----+
+| v1 | k0 |
++----+----+
+| 4  | 1  |
+| 4  | 1  |
+| 4  | 1  |
++----+----+";
+        assert_eq!(expect, print_record_batch(record_batch));
+    }
+
+    #[test]
+    fn test_projection_mapper_invalid_index() {
+        let metadata = new_metadata();
+        assert!(ProjectionMapper::new(&metadata, [5].into_iter()).is_err());
+    }
+}
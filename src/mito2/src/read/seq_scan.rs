@@ -0,0 +1,135 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sequential scan.
+
+use std::sync::Arc;
+
+use async_stream::try_stream;
+use common_error::ext::BoxedError;
+use common_recordbatch::error::ExternalSnafu;
+use common_recordbatch::{RecordBatchStreamAdaptor, SendableRecordBatchStream};
+use common_time::range::TimestampRange;
+use snafu::ResultExt;
+use table::predicate::Predicate;
+
+use crate::access_layer::AccessLayerRef;
+use crate::error::Result;
+use crate::memtable::MemtableRef;
+use crate::read::merge::MergeReaderBuilder;
+use crate::read::projection::ProjectionMapper;
+use crate::read::BatchReader;
+use crate::sst::file::FileHandle;
+
+/// Scans a region and returns rows in a sorted sequence.
+///
+/// The output order is always `order by primary key, time index`.
+pub(crate) struct SeqScan {
+    /// Region SST access layer.
+    access_layer: AccessLayerRef,
+    /// Maps projected Batches to RecordBatches.
+    mapper: Arc<ProjectionMapper>,
+    /// Time range filter for time index.
+    time_range: Option<TimestampRange>,
+    /// Predicate to push down.
+    predicate: Option<Predicate>,
+    /// Memtables to scan.
+    memtables: Vec<MemtableRef>,
+    /// Handles to SST files to scan.
+    files: Vec<FileHandle>,
+}
+
+impl SeqScan {
+    /// Creates a new [SeqScan].
+    pub(crate) fn new(access_layer: AccessLayerRef, mapper: ProjectionMapper) -> SeqScan {
+        SeqScan {
+            access_layer,
+            mapper: Arc::new(mapper),
+            time_range: None,
+            predicate: None,
+            memtables: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Set time range filter for time index.
+    pub(crate) fn with_time_range(mut self, time_range: Option<TimestampRange>) -> Self {
+        self.time_range = time_range;
+        self
+    }
+
+    /// Set predicate to push down.
+    pub(crate) fn with_predicate(mut self, predicate: Option<Predicate>) -> Self {
+        self.predicate = predicate;
+        self
+    }
+
+    /// Set memtables to read.
+    pub(crate) fn with_memtables(mut self, memtables: Vec<MemtableRef>) -> Self {
+        self.memtables = memtables;
+        self
+    }
+
+    /// Set files to read.
+    pub(crate) fn with_files(mut self, files: Vec<FileHandle>) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// Builds a stream for the query.
+    pub(crate) async fn build(&self) -> Result<SendableRecordBatchStream> {
+        // Scans all memtables and SSTs. Builds a merge reader to merge results.
+        let mut builder = MergeReaderBuilder::new();
+        for mem in &self.memtables {
+            let iter = mem.iter(Some(self.mapper.column_ids()));
+            builder.push_batch_iter(iter);
+        }
+        for file in &self.files {
+            let mut reader_builder = self
+                .access_layer
+                .read_sst(file.clone())
+                .projection(self.mapper.column_ids().to_vec());
+            if let Some(predicate) = &self.predicate {
+                reader_builder = reader_builder.predicate(predicate.clone());
+            }
+            if let Some(time_range) = &self.time_range {
+                reader_builder = reader_builder.time_range(*time_range);
+            }
+            let reader = reader_builder.build().await?;
+            builder.push_batch_reader(Box::new(reader));
+        }
+        let mut reader = builder.build().await?;
+        // Creates a stream to poll the batch reader and convert batch into record batch.
+        let mapper = self.mapper.clone();
+        let stream = try_stream! {
+            while let Some(batch) = reader
+                .next_batch()
+                .await
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?
+            {
+                yield mapper.convert(&batch)
+                    .map_err(BoxedError::new)
+                    .context(ExternalSnafu)?;
+            }
+        };
+        let stream = Box::pin(RecordBatchStreamAdaptor {
+            schema: self.mapper.output_schema(),
+            stream: Box::pin(stream),
+            output_ordering: None,
+        });
+
+        Ok(stream)
+    }
+}
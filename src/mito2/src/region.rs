@@ -24,9 +24,11 @@ use common_telemetry::info;
 use store_api::metadata::RegionMetadataRef;
 use store_api::storage::RegionId;
 
+use crate::access_layer::AccessLayerRef;
 use crate::error::Result;
 use crate::manifest::manager::RegionManifestManager;
-use crate::region::version::VersionControlRef;
+use crate::region::version::{VersionControlRef, VersionRef};
+use crate::sst::file_purger::FilePurgerRef;
 
 /// Type to store region version.
 pub type VersionNumber = u32;
@@ -43,7 +45,11 @@ pub(crate) struct MitoRegion {
     /// Version controller for this region.
     pub(crate) version_control: VersionControlRef,
     /// Manager to maintain manifest for this region.
-    manifest_manager: RegionManifestManager,
+    pub(crate) manifest_manager: RegionManifestManager,
+    /// SST files access layer.
+    pub(crate) access_layer: AccessLayerRef,
+    /// Purger to delete files of the region.
+    pub(crate) file_purger: FilePurgerRef,
 }
 
 pub(crate) type MitoRegionRef = Arc<MitoRegion>;
@@ -58,6 +64,12 @@ impl MitoRegion {
         Ok(())
     }
 
+    /// Returns current version of the region.
+    pub(crate) fn version(&self) -> VersionRef {
+        let version_data = self.version_control.current();
+        version_data.version
+    }
+
     /// Returns current metadata of the region.
     pub(crate) fn metadata(&self) -> RegionMetadataRef {
         let version_data = self.version_control.current();
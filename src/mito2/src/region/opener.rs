@@ -16,26 +16,37 @@
 
 use std::sync::Arc;
 
+use common_telemetry::info;
+use futures::StreamExt;
 use object_store::util::join_dir;
 use object_store::ObjectStore;
 use snafu::{ensure, OptionExt};
+use store_api::logstore::LogStore;
 use store_api::metadata::RegionMetadata;
 use store_api::storage::RegionId;
 
+use crate::access_layer::AccessLayer;
 use crate::config::MitoConfig;
 use crate::error::{RegionCorruptedSnafu, RegionNotFoundSnafu, Result};
 use crate::manifest::manager::{RegionManifestManager, RegionManifestOptions};
-use crate::memtable::MemtableBuilderRef;
-use crate::region::version::{VersionBuilder, VersionControl};
+use crate::memtable::{KeyValues, MemtableBuilderRef};
+use crate::region::version::{VersionBuilder, VersionControl, VersionControlRef};
 use crate::region::MitoRegion;
+use crate::request::RegionOptions;
+use crate::schedule::scheduler::SchedulerRef;
+use crate::sst::file_purger::LocalFilePurger;
+use crate::sst::version::SstVersion;
+use crate::wal::{EntryId, Wal};
 
 /// Builder to create a new [MitoRegion] or open an existing one.
 pub(crate) struct RegionOpener {
     region_id: RegionId,
     metadata: Option<RegionMetadata>,
+    options: RegionOptions,
     memtable_builder: MemtableBuilderRef,
     object_store: ObjectStore,
     region_dir: String,
+    scheduler: SchedulerRef,
 }
 
 impl RegionOpener {
@@ -44,13 +55,16 @@ impl RegionOpener {
         region_id: RegionId,
         memtable_builder: MemtableBuilderRef,
         object_store: ObjectStore,
+        scheduler: SchedulerRef,
     ) -> RegionOpener {
         RegionOpener {
             region_id,
             metadata: None,
+            options: RegionOptions::default(),
             memtable_builder,
             object_store,
             region_dir: String::new(),
+            scheduler,
         }
     }
 
@@ -60,6 +74,12 @@ impl RegionOpener {
         self
     }
 
+    /// Sets options of the region.
+    pub(crate) fn options(mut self, options: RegionOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     /// Sets the region dir.
     pub(crate) fn region_dir(mut self, value: &str) -> Self {
         self.region_dir = value.to_string();
@@ -77,7 +97,7 @@ impl RegionOpener {
         // Create a manifest manager for this region.
         let options = RegionManifestOptions {
             manifest_dir: new_manifest_dir(&self.region_dir),
-            object_store: self.object_store,
+            object_store: self.object_store.clone(),
             compress_type: config.manifest_compress_type,
             checkpoint_distance: config.manifest_checkpoint_distance,
         };
@@ -86,23 +106,32 @@ impl RegionOpener {
 
         let mutable = self.memtable_builder.build(&metadata);
 
-        let version = VersionBuilder::new(metadata, mutable).build();
+        let version = VersionBuilder::new(metadata, mutable)
+            .options(self.options)
+            .build();
         let version_control = Arc::new(VersionControl::new(version));
+        let access_layer = Arc::new(AccessLayer::new(self.region_dir, self.object_store));
 
         Ok(MitoRegion {
             region_id,
             version_control,
             manifest_manager,
+            file_purger: Arc::new(LocalFilePurger::new(self.scheduler, access_layer.clone())),
+            access_layer,
         })
     }
 
-    /// Opens an existing region.
+    /// Opens an existing region and replays its WAL.
     ///
     /// Returns error if the region doesn't exist.
-    pub(crate) async fn open(self, config: &MitoConfig) -> Result<MitoRegion> {
+    pub(crate) async fn open<S: LogStore>(
+        self,
+        config: &MitoConfig,
+        wal: &Wal<S>,
+    ) -> Result<MitoRegion> {
         let options = RegionManifestOptions {
             manifest_dir: new_manifest_dir(&self.region_dir),
-            object_store: self.object_store,
+            object_store: self.object_store.clone(),
             compress_type: config.manifest_compress_type,
             checkpoint_distance: config.manifest_checkpoint_distance,
         };
@@ -124,20 +153,77 @@ impl RegionOpener {
             }
         );
 
+        let access_layer = Arc::new(AccessLayer::new(self.region_dir, self.object_store));
+        let file_purger = Arc::new(LocalFilePurger::new(self.scheduler, access_layer.clone()));
+        let mut ssts = SstVersion::new();
+        ssts.add_files(file_purger.clone(), manifest.files.values().cloned());
+
         let mutable = self.memtable_builder.build(&metadata);
-        let version = VersionBuilder::new(metadata, mutable).build();
+        let version = VersionBuilder::new(metadata, mutable)
+            .ssts(ssts)
+            .flushed_entry_id(manifest.flushed_entry_id)
+            .flushed_sequence(manifest.flushed_sequence)
+            .options(self.options)
+            .build();
+        let flushed_entry_id = version.flushed_entry_id;
         let version_control = Arc::new(VersionControl::new(version));
-
-        // TODO(yingwen): Replay.
+        replay_memtable(wal, self.region_id, flushed_entry_id, &version_control).await?;
 
         Ok(MitoRegion {
             region_id: self.region_id,
             version_control,
             manifest_manager,
+            access_layer,
+            file_purger,
         })
     }
 }
 
+/// Replays the WAL entries after `flushed_entry_id` to the mutable memtable.
+async fn replay_memtable<S: LogStore>(
+    wal: &Wal<S>,
+    region_id: RegionId,
+    flushed_entry_id: EntryId,
+    version_control: &VersionControlRef,
+) -> Result<()> {
+    let mut rows_replayed = 0;
+    // Last entry id should start from flushed entry id since there might be no
+    // data in the WAL.
+    let mut last_entry_id = flushed_entry_id;
+    let mut committed_sequence = version_control.current().committed_sequence;
+
+    let version = version_control.current().version;
+    let mutable = version.memtables.mutable();
+    let mut wal_stream = wal.scan(region_id, flushed_entry_id + 1)?;
+    while let Some(res) = wal_stream.next().await {
+        let (entry_id, entry) = res?;
+        last_entry_id = last_entry_id.max(entry_id);
+        for mutation in entry.mutations {
+            let num_rows = mutation
+                .rows
+                .as_ref()
+                .map(|rows| rows.rows.len())
+                .unwrap_or(0);
+            if num_rows == 0 {
+                continue;
+            }
+            // Sequence of the last row in this mutation.
+            committed_sequence = committed_sequence.max(mutation.sequence + num_rows as u64 - 1);
+            let Some(kvs) = KeyValues::new(&version.metadata, mutation) else {
+                continue;
+            };
+            mutable.write(&kvs)?;
+            rows_replayed += num_rows;
+        }
+    }
+    version_control.set_sequence_and_entry_id(committed_sequence, last_entry_id);
+
+    info!(
+        "Replay WAL for region: {}, rows recovered: {}, last entry id: {}",
+        region_id, rows_replayed, last_entry_id
+    );
+    Ok(())
+}
 /// Returns the directory to the manifest files.
 fn new_manifest_dir(region_dir: &str) -> String {
     join_dir(region_dir, "manifest")
@@ -26,10 +26,13 @@
 use std::sync::{Arc, RwLock};
 
 use store_api::metadata::RegionMetadataRef;
-use store_api::storage::SequenceNumber;
+use store_api::storage::{ColumnId, SequenceNumber};
 
+use crate::manifest::action::RegionEdit;
 use crate::memtable::version::{MemtableVersion, MemtableVersionRef};
-use crate::memtable::MemtableRef;
+use crate::memtable::{MemtableBuilderRef, MemtableId, MemtableRef};
+use crate::request::RegionOptions;
+use crate::sst::file_purger::FilePurgerRef;
 use crate::sst::version::{SstVersion, SstVersionRef};
 use crate::wal::EntryId;
 
@@ -45,11 +48,14 @@ pub(crate) struct VersionControl {
 impl VersionControl {
     /// Returns a new [VersionControl] with specific `version`.
     pub(crate) fn new(version: Version) -> VersionControl {
+        // Initialize sequence and entry id from flushed sequence and entry id.
+        let (flushed_sequence, flushed_entry_id) =
+            (version.flushed_sequence, version.flushed_entry_id);
         VersionControl {
             data: RwLock::new(VersionControlData {
                 version: Arc::new(version),
-                committed_sequence: 0,
-                last_entry_id: 0,
+                committed_sequence: flushed_sequence,
+                last_entry_id: flushed_entry_id,
             }),
         }
     }
@@ -58,6 +64,52 @@ impl VersionControl {
     pub(crate) fn current(&self) -> VersionControlData {
         self.data.read().unwrap().clone()
     }
+
+    /// Updates committed sequence and entry id.
+    pub(crate) fn set_sequence_and_entry_id(&self, seq: SequenceNumber, entry_id: EntryId) {
+        let mut data = self.data.write().unwrap();
+        data.committed_sequence = seq;
+        data.last_entry_id = entry_id;
+    }
+
+    /// Freezes the mutable memtable if it is not empty.
+    pub(crate) fn freeze_mutable(&self, builder: &MemtableBuilderRef) {
+        let version = self.current().version;
+        if version.memtables.mutable().is_empty() {
+            return;
+        }
+        let new_mutable = builder.build(&version.metadata);
+        // Safety: The mutable memtable is not empty.
+        let new_memtables = version.memtables.freeze_mutable(new_mutable).unwrap();
+        // Create a new version with memtable switched.
+        let new_version = Arc::new(
+            VersionBuilder::from_version(version)
+                .memtables(new_memtables)
+                .build(),
+        );
+
+        let mut version_data = self.data.write().unwrap();
+        version_data.version = new_version;
+    }
+
+    /// Apply edit to current version.
+    pub(crate) fn apply_edit(
+        &self,
+        edit: RegionEdit,
+        memtables_to_remove: &[MemtableId],
+        purger: FilePurgerRef,
+    ) {
+        let version = self.current().version;
+        let new_version = Arc::new(
+            VersionBuilder::from_version(version)
+                .apply_edit(edit, purger)
+                .remove_memtables(memtables_to_remove)
+                .build(),
+        );
+
+        let mut version_data = self.data.write().unwrap();
+        version_data.version = new_version;
+    }
 }
 
 pub(crate) type VersionControlRef = Arc<VersionControl>;
@@ -89,7 +141,23 @@ pub(crate) struct Version {
     pub(crate) ssts: SstVersionRef,
     /// Inclusive max sequence of flushed data.
     pub(crate) flushed_sequence: SequenceNumber,
-    // TODO(yingwen): RegionOptions.
+    /// Latest entry id during flushing.
+    pub(crate) flushed_entry_id: EntryId,
+    /// Options of the region.
+    pub(crate) options: RegionOptions,
+}
+
+impl Version {
+    /// Returns ids of columns to build bloom filter indexes, ignoring columns not in the
+    /// region, e.g. dropped columns.
+    pub(crate) fn bloom_filter_columns(&self) -> Vec<ColumnId> {
+        self.options
+            .bloom_filter_columns
+            .iter()
+            .filter_map(|name| self.metadata.column_by_name(name))
+            .map(|column| column.column_id)
+            .collect()
+    }
 }
 
 pub(crate) type VersionRef = Arc<Version>;
@@ -97,23 +165,105 @@ pub(crate) type VersionRef = Arc<Version>;
 /// Version builder.
 pub(crate) struct VersionBuilder {
     metadata: RegionMetadataRef,
-    /// Mutable memtable.
-    mutable: MemtableRef,
+    memtables: MemtableVersionRef,
+    ssts: SstVersionRef,
+    flushed_sequence: SequenceNumber,
+    flushed_entry_id: EntryId,
+    options: RegionOptions,
 }
 
 impl VersionBuilder {
     /// Returns a new builder.
     pub(crate) fn new(metadata: RegionMetadataRef, mutable: MemtableRef) -> VersionBuilder {
-        VersionBuilder { metadata, mutable }
+        VersionBuilder {
+            metadata,
+            memtables: Arc::new(MemtableVersion::new(mutable)),
+            ssts: Arc::new(SstVersion::new()),
+            flushed_sequence: 0,
+            flushed_entry_id: 0,
+            options: RegionOptions::default(),
+        }
+    }
+
+    /// Returns a new builder from an existing version.
+    pub(crate) fn from_version(version: VersionRef) -> VersionBuilder {
+        VersionBuilder {
+            metadata: version.metadata.clone(),
+            memtables: version.memtables.clone(),
+            ssts: version.ssts.clone(),
+            flushed_sequence: version.flushed_sequence,
+            flushed_entry_id: version.flushed_entry_id,
+            options: version.options.clone(),
+        }
+    }
+
+    /// Sets memtables.
+    pub(crate) fn memtables(mut self, memtables: MemtableVersion) -> Self {
+        self.memtables = Arc::new(memtables);
+        self
+    }
+
+    /// Sets SSTs.
+    pub(crate) fn ssts(mut self, ssts: SstVersion) -> Self {
+        self.ssts = Arc::new(ssts);
+        self
+    }
+
+    /// Sets flushed sequence.
+    pub(crate) fn flushed_sequence(mut self, sequence: SequenceNumber) -> Self {
+        self.flushed_sequence = sequence;
+        self
+    }
+
+    /// Sets region options.
+    pub(crate) fn options(mut self, options: RegionOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets flushed entry id.
+    pub(crate) fn flushed_entry_id(mut self, entry_id: EntryId) -> Self {
+        self.flushed_entry_id = entry_id;
+        self
+    }
+
+    /// Apply edit to the builder.
+    pub(crate) fn apply_edit(mut self, edit: RegionEdit, file_purger: FilePurgerRef) -> Self {
+        if let Some(entry_id) = edit.flushed_entry_id {
+            self.flushed_entry_id = self.flushed_entry_id.max(entry_id);
+        }
+        if let Some(sequence) = edit.flushed_sequence {
+            self.flushed_sequence = self.flushed_sequence.max(sequence);
+        }
+        if !edit.files_to_add.is_empty() || !edit.files_to_remove.is_empty() {
+            let mut ssts = (*self.ssts).clone();
+            ssts.add_files(file_purger, edit.files_to_add.into_iter());
+            ssts.remove_files(edit.files_to_remove.into_iter());
+            self.ssts = Arc::new(ssts);
+        }
+
+        self
+    }
+
+    /// Remove memtables from the builder.
+    pub(crate) fn remove_memtables(mut self, ids: &[MemtableId]) -> Self {
+        if !ids.is_empty() {
+            let mut memtables = (*self.memtables).clone();
+            memtables.remove_memtables(ids);
+            self.memtables = Arc::new(memtables);
+        }
+        self
     }
 
     /// Builds a new [Version] from the builder.
     pub(crate) fn build(self) -> Version {
         Version {
             metadata: self.metadata,
-            memtables: Arc::new(MemtableVersion::new(self.mutable)),
-            ssts: Arc::new(SstVersion::new()),
-            flushed_sequence: 0,
+            memtables: self.memtables,
+            ssts: self.ssts,
+            flushed_sequence: self.flushed_sequence,
+            flushed_entry_id: self.flushed_entry_id,
+            options: self.options,
         }
     }
 }
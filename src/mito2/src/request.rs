@@ -15,6 +15,7 @@
 //! Worker requests.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use api::helper::{
@@ -29,16 +30,20 @@ use store_api::region_request::{
     RegionAlterRequest, RegionCloseRequest, RegionCompactRequest, RegionCreateRequest,
     RegionDropRequest, RegionFlushRequest, RegionOpenRequest, RegionRequest,
 };
-use store_api::storage::{CompactionStrategy, RegionId};
+use store_api::storage::{CompactionStrategy, RegionId, SequenceNumber};
+use table::requests::BLOOM_FILTER_COLUMNS_KEY;
 use tokio::sync::oneshot::{self, Receiver, Sender};
 
 use crate::config::DEFAULT_WRITE_BUFFER_SIZE;
-use crate::error::{CreateDefaultSnafu, FillDefaultSnafu, InvalidRequestSnafu, Result};
+use crate::error::{CreateDefaultSnafu, Error, FillDefaultSnafu, InvalidRequestSnafu, Result};
+use crate::memtable::MemtableId;
+use crate::sst::file::FileMeta;
+use crate::wal::EntryId;
 
 /// Options that affect the entire region.
 ///
 /// Users need to specify the options while creating/opening a region.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RegionOptions {
     /// Region memtable max size in bytes.
     pub write_buffer_size: Option<ReadableSize>,
@@ -46,6 +51,8 @@ pub struct RegionOptions {
     pub ttl: Option<Duration>,
     /// Compaction strategy.
     pub compaction_strategy: CompactionStrategy,
+    /// Names of columns to build bloom filter indexes in SSTs.
+    pub bloom_filter_columns: Vec<String>,
 }
 
 impl Default for RegionOptions {
@@ -54,10 +61,49 @@ impl Default for RegionOptions {
             write_buffer_size: Some(DEFAULT_WRITE_BUFFER_SIZE),
             ttl: None,
             compaction_strategy: CompactionStrategy::LeveledTimeWindow,
+            bloom_filter_columns: Vec::new(),
         }
     }
 }
 
+impl RegionOptions {
+    /// Parses options from the options of create/open requests.
+    ///
+    /// Now only parses `bloom_filter_columns`, other options keep their default values.
+    pub fn from_request_options(options: &HashMap<String, String>) -> RegionOptions {
+        let bloom_filter_columns = options
+            .get(BLOOM_FILTER_COLUMNS_KEY)
+            .map(|columns| {
+                columns
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|column| !column.is_empty())
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        RegionOptions {
+            bloom_filter_columns,
+            ..Default::default()
+        }
+    }
+
+    /// Validates options against the `metadata` of the region.
+    pub(crate) fn validate(&self, metadata: &RegionMetadata) -> Result<()> {
+        for column in &self.bloom_filter_columns {
+            ensure!(
+                metadata.column_by_name(column).is_some(),
+                InvalidRequestSnafu {
+                    region_id: metadata.region_id,
+                    reason: format!("bloom filter column {column} not found"),
+                }
+            );
+        }
+        Ok(())
+    }
+}
+
 /// Request to write a region.
 #[derive(Debug)]
 pub struct WriteRequest {
@@ -322,10 +368,89 @@ pub(crate) enum WorkerRequest {
     /// Region request.
     Region(RegionTask),
 
+    /// Notify a worker that a background job of a region is finished.
+    Background {
+        /// Id of the region to send.
+        region_id: RegionId,
+        /// Internal notification.
+        notify: BackgroundNotify,
+    },
+
     /// Notify a worker to stop.
     Stop,
 }
 
+/// Notification from a background job.
+#[derive(Debug)]
+pub(crate) enum BackgroundNotify {
+    /// Flush has finished.
+    FlushFinished(FlushFinished),
+    /// Flush has failed.
+    FlushFailed(FlushFailed),
+    /// Compaction has finished.
+    CompactionFinished(CompactionFinished),
+    /// Compaction has failed.
+    CompactionFailed(CompactionFailed),
+}
+
+/// Notifies a flush job is finished.
+#[derive(Debug)]
+pub(crate) struct FlushFinished {
+    /// Meta of the flushed SSTs.
+    pub(crate) file_metas: Vec<FileMeta>,
+    /// Entry id of flushed data.
+    pub(crate) flushed_entry_id: EntryId,
+    /// Sequence of flushed data.
+    pub(crate) flushed_sequence: SequenceNumber,
+    /// Id of memtables to remove.
+    pub(crate) memtables_to_remove: Vec<MemtableId>,
+    /// Flush result senders.
+    pub(crate) senders: Vec<Sender<Result<()>>>,
+}
+
+impl FlushFinished {
+    /// Sends the result to all waiters.
+    pub(crate) fn on_success(self) {
+        for sender in self.senders {
+            let _ = sender.send(Ok(()));
+        }
+    }
+}
+
+/// Notifies a flush job is failed.
+#[derive(Debug)]
+pub(crate) struct FlushFailed {
+    /// The reason of a failed flush job.
+    pub(crate) err: Arc<Error>,
+}
+
+/// Notifies a compaction job is finished.
+#[derive(Debug)]
+pub(crate) struct CompactionFinished {
+    /// Compaction output files that are to be added to region version.
+    pub(crate) compaction_outputs: Vec<FileMeta>,
+    /// Compacted files that are to be removed from region version.
+    pub(crate) compacted_files: Vec<FileMeta>,
+    /// Compaction result senders.
+    pub(crate) senders: Vec<Sender<Result<()>>>,
+}
+
+impl CompactionFinished {
+    /// Sends the result to all waiters.
+    pub(crate) fn on_success(self) {
+        for sender in self.senders {
+            let _ = sender.send(Ok(()));
+        }
+    }
+}
+
+/// Notifies a compaction job is failed.
+#[derive(Debug)]
+pub(crate) struct CompactionFailed {
+    /// The reason of a failed compaction job.
+    pub(crate) err: Arc<Error>,
+}
+
 /// Request to modify a region.
 #[derive(Debug)]
 pub(crate) struct RegionTask {
@@ -44,6 +44,8 @@ pub trait Scheduler {
     async fn stop(&self, await_termination: bool) -> Result<()>;
 }
 
+pub type SchedulerRef = Arc<dyn Scheduler + Send + Sync>;
+
 /// Request scheduler based on local state.
 pub struct LocalScheduler {
     /// Sends jobs to flume bounded channel
@@ -16,6 +16,7 @@
 
 pub mod file;
 pub mod file_purger;
+pub(crate) mod index;
 pub mod parquet;
 mod stream_writer;
 pub(crate) mod version;
@@ -19,6 +19,7 @@ use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use arc_swap::ArcSwapOption;
 use common_time::Timestamp;
 use object_store::util::join_path;
 use serde::{Deserialize, Serialize};
@@ -27,6 +28,7 @@ use store_api::storage::RegionId;
 use uuid::Uuid;
 
 use crate::sst::file_purger::{FilePurgerRef, PurgeRequest};
+use crate::sst::index::SstIndex;
 
 /// Type to store SST level.
 pub type Level = u8;
@@ -57,6 +59,11 @@ impl FileId {
     pub fn as_parquet(&self) -> String {
         format!("{}{}", self, ".parquet")
     }
+
+    /// Append `.index` to file id to make the name of the index file.
+    pub fn as_index(&self) -> String {
+        format!("{}{}", self, ".index")
+    }
 }
 
 impl fmt::Display for FileId {
@@ -90,6 +97,8 @@ pub struct FileMeta {
     pub level: Level,
     /// Size of the file.
     pub file_size: u64,
+    /// Size of the index file, 0 if the file has no index.
+    pub index_file_size: u64,
 }
 
 /// Handle to a SST file.
@@ -113,6 +122,12 @@ impl fmt::Debug for FileHandle {
 }
 
 impl FileHandle {
+    pub fn new(meta: FileMeta, file_purger: FilePurgerRef) -> FileHandle {
+        FileHandle {
+            inner: Arc::new(FileHandleInner::new(meta, file_purger)),
+        }
+    }
+
     /// Returns the file id.
     pub fn file_id(&self) -> FileId {
         self.inner.meta.file_id
@@ -122,6 +137,59 @@ impl FileHandle {
     pub fn file_path(&self, file_dir: &str) -> String {
         join_path(file_dir, &self.file_id().as_parquet())
     }
+
+    /// Returns the path of the index file, `None` if the file has no index.
+    pub fn index_file_path(&self, file_dir: &str) -> Option<String> {
+        (self.inner.meta.index_file_size > 0)
+            .then(|| join_path(file_dir, &self.file_id().as_index()))
+    }
+
+    /// Returns the time range of the file.
+    pub fn time_range(&self) -> FileTimeRange {
+        self.inner.meta.time_range
+    }
+
+    /// Returns the level of the file.
+    pub fn level(&self) -> Level {
+        self.inner.meta.level
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn size(&self) -> u64 {
+        self.inner.meta.file_size
+    }
+
+    /// Returns a copy of the file meta.
+    pub fn meta(&self) -> FileMeta {
+        self.inner.meta.clone()
+    }
+
+    /// Returns true if the file is under compaction.
+    pub fn compacting(&self) -> bool {
+        self.inner.compacting.load(Ordering::Relaxed)
+    }
+
+    /// Sets whether the file is under compaction.
+    pub fn set_compacting(&self, compacting: bool) {
+        self.inner.compacting.store(compacting, Ordering::Relaxed);
+    }
+
+    /// Marks the file as deleted and it will be purged once all handles
+    /// of the file are dropped.
+    pub fn mark_deleted(&self) {
+        self.inner.deleted.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the decoded index of the file if it has been loaded.
+    pub(crate) fn index(&self) -> Option<Arc<SstIndex>> {
+        self.inner.index.load_full()
+    }
+
+    /// Caches the decoded index of the file, so readers of the file don't need to fetch
+    /// and decode the index file again.
+    pub(crate) fn set_index(&self, index: Arc<SstIndex>) {
+        self.inner.index.store(Some(index));
+    }
 }
 
 /// Inner data of [FileHandle].
@@ -131,9 +199,24 @@ struct FileHandleInner {
     meta: FileMeta,
     compacting: AtomicBool,
     deleted: AtomicBool,
+    /// The decoded index of the file, loaded by the first reader that needs it. SST files are
+    /// immutable so the index never changes.
+    index: ArcSwapOption<SstIndex>,
     file_purger: FilePurgerRef,
 }
 
+impl FileHandleInner {
+    fn new(meta: FileMeta, file_purger: FilePurgerRef) -> FileHandleInner {
+        FileHandleInner {
+            meta,
+            compacting: AtomicBool::new(false),
+            deleted: AtomicBool::new(false),
+            index: ArcSwapOption::empty(),
+            file_purger,
+        }
+    }
+}
+
 impl Drop for FileHandleInner {
     fn drop(&mut self) {
         if self.deleted.load(Ordering::Relaxed) {
@@ -148,6 +231,7 @@ impl Drop for FileHandleInner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compaction::test_util::new_file_handle;
 
     #[test]
     fn test_file_id() {
@@ -187,6 +271,7 @@ mod tests {
             time_range: FileTimeRange::default(),
             level,
             file_size: 0,
+            index_file_size: 0,
         }
     }
 
@@ -209,4 +294,16 @@ mod tests {
         let deserialized_file_meta: FileMeta = serde_json::from_str(json_file_meta).unwrap();
         assert_eq!(file_meta, deserialized_file_meta);
     }
+
+    #[test]
+    fn test_file_handle_index() {
+        let handle = new_file_handle(FileId::random(), 0, 0, 0);
+        assert!(handle.index().is_none());
+
+        let index = Arc::new(SstIndex::default());
+        handle.set_index(index.clone());
+        // Clones of the handle share the cached index.
+        let cloned = handle.clone();
+        assert!(Arc::ptr_eq(&index, &cloned.index().unwrap()));
+    }
 }
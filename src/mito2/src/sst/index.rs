@@ -0,0 +1,519 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secondary indexes of SSTs.
+//!
+//! Indexes of a SST are stored in a side file `{file_id}.index` next to the parquet file. The
+//! file starts with a magic number, followed by sections of indexes:
+//! ```text
+//! magic | kind 0 | len 0 | payload 0 | kind 1 | len 1 | payload 1 | ...
+//! ```
+//! `kind` is a u8 and `len` is the length of the payload in u32. All integers are little endian.
+//! Readers skip sections of unknown kinds.
+
+pub(crate) mod bloom_filter;
+pub(crate) mod inverted_index;
+
+use std::sync::Arc;
+
+use bytes::{Buf, BufMut};
+use datafusion::logical_expr::Operator;
+use datafusion::physical_expr::expressions::{BinaryExpr, Column, InListExpr, Literal};
+use datafusion::physical_expr::PhysicalExpr;
+use datafusion_common::ScalarValue;
+use datatypes::data_type::ConcreteDataType;
+use datatypes::value::Value;
+use snafu::ensure;
+use store_api::metadata::{RegionMetadata, RegionMetadataRef};
+use table::predicate::Predicate;
+
+use crate::error::{InvalidSstIndexSnafu, Result};
+use crate::read::Batch;
+use crate::row_converter::{McmpRowCodec, RowCodec, SortField};
+use crate::sst::index::bloom_filter::{BloomFilterIndex, BloomFilterIndexBuilder};
+use crate::sst::index::inverted_index::{InvertedIndex, InvertedIndexBuilder};
+use crate::sst::parquet::WriteOptions;
+
+/// Magic number of the index file.
+const INDEX_MAGIC: &[u8; 4] = b"GTIX";
+/// Section kind of the inverted index.
+const INVERTED_INDEX_KIND: u8 = 1;
+/// Section kind of the bloom filter index.
+const BLOOM_FILTER_INDEX_KIND: u8 = 2;
+
+/// Indexes of a SST file.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct SstIndex {
+    /// Inverted index of tag columns.
+    pub(crate) inverted_index: Option<InvertedIndex>,
+    /// Bloom filter index of columns specified by the region options.
+    pub(crate) bloom_filter_index: Option<BloomFilterIndex>,
+}
+
+impl SstIndex {
+    /// Returns true if there is no index.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.inverted_index.is_none() && self.bloom_filter_index.is_none()
+    }
+
+    /// Encodes the index into bytes of the index file.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut buf = INDEX_MAGIC.to_vec();
+        if let Some(inverted_index) = &self.inverted_index {
+            let mut payload = Vec::new();
+            inverted_index.encode(&mut payload);
+            put_section(&mut buf, INVERTED_INDEX_KIND, &payload);
+        }
+        if let Some(bloom_filter_index) = &self.bloom_filter_index {
+            let mut payload = Vec::new();
+            bloom_filter_index.encode(&mut payload);
+            put_section(&mut buf, BLOOM_FILTER_INDEX_KIND, &payload);
+        }
+        buf
+    }
+
+    /// Decodes the index from bytes of the index file.
+    pub(crate) fn decode(mut buf: &[u8]) -> Result<SstIndex> {
+        ensure!(
+            buf.starts_with(INDEX_MAGIC),
+            InvalidSstIndexSnafu {
+                reason: "magic number mismatch",
+            }
+        );
+        buf.advance(INDEX_MAGIC.len());
+
+        let mut index = SstIndex::default();
+        while buf.has_remaining() {
+            let kind = get_u8(&mut buf)?;
+            let len = get_u32(&mut buf)? as usize;
+            ensure!(
+                buf.remaining() >= len,
+                InvalidSstIndexSnafu {
+                    reason: format!("section {kind} is truncated"),
+                }
+            );
+            let (payload, remaining) = buf.split_at(len);
+            match kind {
+                INVERTED_INDEX_KIND => {
+                    index.inverted_index = Some(InvertedIndex::decode(payload)?);
+                }
+                BLOOM_FILTER_INDEX_KIND => {
+                    index.bloom_filter_index = Some(BloomFilterIndex::decode(payload)?);
+                }
+                _ => (),
+            }
+            buf = remaining;
+        }
+        Ok(index)
+    }
+
+    /// Returns the row groups to read according to the `predicate`, `None` if all row groups
+    /// need to be read.
+    ///
+    /// The `metadata` must be the metadata of the SST.
+    pub(crate) fn prune(
+        &self,
+        predicate: &Predicate,
+        metadata: &RegionMetadata,
+    ) -> Option<RowGroupBitmap> {
+        let lookup = |column: &str, value: &ScalarValue| self.lookup(column, value, metadata);
+        // Expressions of the predicate are conjunctive.
+        predicate
+            .exprs()
+            .iter()
+            .filter_map(|expr| prune_expr(expr, &lookup))
+            .reduce(|mut row_groups, other| {
+                row_groups.intersect(&other);
+                row_groups
+            })
+    }
+
+    /// Returns the row groups that may contain the `value` of the `column`, `None` if the
+    /// column isn't indexed.
+    fn lookup(
+        &self,
+        column: &str,
+        value: &ScalarValue,
+        metadata: &RegionMetadata,
+    ) -> Option<RowGroupBitmap> {
+        let column = metadata.column_by_name(column)?;
+        let data_type = &column.column_schema.data_type;
+        let value = Value::try_from(value.clone()).ok()?;
+        // The type of the value may differ from the type in the SST after altering the column.
+        if value.is_null() || value.data_type() != *data_type {
+            return None;
+        }
+        let key = encode_value(data_type, &value).ok()?;
+
+        let inverted = self
+            .inverted_index
+            .as_ref()
+            .and_then(|index| index.lookup(column.column_id, &key));
+        let bloom_filter = self
+            .bloom_filter_index
+            .as_ref()
+            .and_then(|index| index.lookup(column.column_id, &key));
+        intersect(inverted, bloom_filter)
+    }
+}
+
+/// Returns the row groups that may match the `expr`, `None` if the `expr` can't be evaluated
+/// by the index.
+///
+/// Supports `column = literal`, `column IN (literals)` and their combinations by `AND` and `OR`.
+fn prune_expr(
+    expr: &Arc<dyn PhysicalExpr>,
+    lookup: &impl Fn(&str, &ScalarValue) -> Option<RowGroupBitmap>,
+) -> Option<RowGroupBitmap> {
+    if let Some(binary) = expr.as_any().downcast_ref::<BinaryExpr>() {
+        return match binary.op() {
+            Operator::And => intersect(
+                prune_expr(binary.left(), lookup),
+                prune_expr(binary.right(), lookup),
+            ),
+            Operator::Or => {
+                let mut left = prune_expr(binary.left(), lookup)?;
+                left.union(&prune_expr(binary.right(), lookup)?);
+                Some(left)
+            }
+            Operator::Eq => {
+                let (column, literal) = match (as_column(binary.left()), as_literal(binary.right()))
+                {
+                    (Some(column), Some(literal)) => (column, literal),
+                    _ => (as_column(binary.right())?, as_literal(binary.left())?),
+                };
+                lookup(column, literal)
+            }
+            _ => None,
+        };
+    }
+
+    if let Some(in_list) = expr.as_any().downcast_ref::<InListExpr>() {
+        if in_list.negated() {
+            return None;
+        }
+        let column = as_column(in_list.expr())?;
+        let mut row_groups = RowGroupBitmap::default();
+        for item in in_list.list() {
+            row_groups.union(&lookup(column, as_literal(item)?)?);
+        }
+        return Some(row_groups);
+    }
+
+    None
+}
+
+fn as_column(expr: &Arc<dyn PhysicalExpr>) -> Option<&str> {
+    expr.as_any()
+        .downcast_ref::<Column>()
+        .map(|column| column.name())
+}
+
+fn as_literal(expr: &Arc<dyn PhysicalExpr>) -> Option<&ScalarValue> {
+    expr.as_any()
+        .downcast_ref::<Literal>()
+        .map(|literal| literal.value())
+}
+
+/// Intersects row groups of conjunctive conditions, `None` means all row groups.
+fn intersect(
+    left: Option<RowGroupBitmap>,
+    right: Option<RowGroupBitmap>,
+) -> Option<RowGroupBitmap> {
+    match (left, right) {
+        (Some(mut left), Some(right)) => {
+            left.intersect(&right);
+            Some(left)
+        }
+        (left, right) => left.or(right),
+    }
+}
+
+/// Encodes a column value in memcomparable format, the same as the primary key.
+fn encode_value(data_type: &ConcreteDataType, value: &Value) -> Result<Vec<u8>> {
+    McmpRowCodec::new(vec![SortField::new(data_type.clone())])
+        .encode(std::iter::once(value.as_value_ref()))
+}
+
+/// Builds indexes of a SST while writing it.
+pub(crate) struct SstIndexBuilder {
+    inverted_index: InvertedIndexBuilder,
+    bloom_filter_index: BloomFilterIndexBuilder,
+}
+
+impl SstIndexBuilder {
+    /// Returns a new builder to index SSTs of the region.
+    pub(crate) fn new(metadata: &RegionMetadataRef, opts: &WriteOptions) -> SstIndexBuilder {
+        SstIndexBuilder {
+            inverted_index: InvertedIndexBuilder::new(metadata),
+            bloom_filter_index: BloomFilterIndexBuilder::new(
+                metadata,
+                &opts.bloom_filter_columns,
+                opts.row_group_size,
+            ),
+        }
+    }
+
+    /// Indexes the `batch`, which are rows following the rows indexed before.
+    pub(crate) fn update(&mut self, batch: &Batch) -> Result<()> {
+        self.inverted_index.update(batch)?;
+        self.bloom_filter_index.update(batch)
+    }
+
+    /// Finishes the index, `row_group_rows` are the numbers of rows in each row group of the SST.
+    pub(crate) fn finish(self, row_group_rows: &[usize]) -> SstIndex {
+        SstIndex {
+            inverted_index: self.inverted_index.finish(row_group_rows),
+            bloom_filter_index: self.bloom_filter_index.finish(row_group_rows),
+        }
+    }
+}
+
+/// A bitmap of row groups.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RowGroupBitmap {
+    words: Vec<u64>,
+}
+
+impl RowGroupBitmap {
+    /// Adds the row group to the bitmap.
+    pub(crate) fn insert(&mut self, row_group: usize) {
+        let (word, bit) = (row_group / 64, row_group % 64);
+        if self.words.len() <= word {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+
+    /// Returns true if the row group is in the bitmap.
+    pub(crate) fn contains(&self, row_group: usize) -> bool {
+        self.words
+            .get(row_group / 64)
+            .map_or(false, |word| word & (1 << (row_group % 64)) != 0)
+    }
+
+    /// Keeps row groups both in `self` and `other`.
+    pub(crate) fn intersect(&mut self, other: &RowGroupBitmap) {
+        self.words.truncate(other.words.len());
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= other;
+        }
+    }
+
+    /// Adds row groups in `other` to `self`.
+    pub(crate) fn union(&mut self, other: &RowGroupBitmap) {
+        if self.words.len() < other.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.put_u32_le(self.words.len() as u32);
+        for word in &self.words {
+            buf.put_u64_le(*word);
+        }
+    }
+
+    fn decode(buf: &mut &[u8]) -> Result<RowGroupBitmap> {
+        let len = get_u32(buf)? as usize;
+        ensure_remaining(buf, len * 8)?;
+        let words = (0..len).map(|_| buf.get_u64_le()).collect();
+        Ok(RowGroupBitmap { words })
+    }
+}
+
+fn put_section(buf: &mut Vec<u8>, kind: u8, payload: &[u8]) {
+    buf.put_u8(kind);
+    buf.put_u32_le(payload.len() as u32);
+    buf.put_slice(payload);
+}
+
+fn ensure_remaining(buf: &&[u8], len: usize) -> Result<()> {
+    ensure!(
+        buf.remaining() >= len,
+        InvalidSstIndexSnafu {
+            reason: format!("expect {} more bytes, remaining {}", len, buf.remaining()),
+        }
+    );
+    Ok(())
+}
+
+fn get_u8(buf: &mut &[u8]) -> Result<u8> {
+    ensure_remaining(buf, 1)?;
+    Ok(buf.get_u8())
+}
+
+fn get_u32(buf: &mut &[u8]) -> Result<u32> {
+    ensure_remaining(buf, 4)?;
+    Ok(buf.get_u32_le())
+}
+
+fn get_bytes<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let len = get_u32(buf)? as usize;
+    ensure_remaining(buf, len)?;
+    let (bytes, remaining) = buf.split_at(len);
+    *buf = remaining;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use api::v1::{OpType, SemanticType};
+    use datafusion::logical_expr::Expr;
+    use datatypes::arrow::array::{
+        StringArray, TimestampMillisecondArray, UInt64Array, UInt8Array,
+    };
+    use datatypes::schema::ColumnSchema;
+    use datatypes::value::ValueRef;
+    use store_api::metadata::{ColumnMetadata, RegionMetadataBuilder};
+    use store_api::storage::RegionId;
+
+    use super::*;
+    use crate::read::BatchBuilder;
+
+    /// Creates a region: `host (tag), cpu (tag), trace_id (field), ts`.
+    pub(crate) fn new_region_metadata() -> RegionMetadataRef {
+        let mut builder = RegionMetadataBuilder::new(RegionId::new(1, 1));
+        builder
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+                semantic_type: SemanticType::Tag,
+                column_id: 1,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new("cpu", ConcreteDataType::int64_datatype(), true),
+                semantic_type: SemanticType::Tag,
+                column_id: 2,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "trace_id",
+                    ConcreteDataType::string_datatype(),
+                    true,
+                ),
+                semantic_type: SemanticType::Field,
+                column_id: 3,
+            })
+            .push_column_metadata(ColumnMetadata {
+                column_schema: ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_millisecond_datatype(),
+                    false,
+                ),
+                semantic_type: SemanticType::Timestamp,
+                column_id: 4,
+            })
+            .primary_key(vec![1, 2]);
+        Arc::new(builder.build().unwrap())
+    }
+
+    /// Creates a batch of `num_rows` rows starting from the row `start` of the SST, trace ids of
+    /// the rows are `t{row}`.
+    pub(crate) fn new_test_batch(host: &str, cpu: i64, start: usize, num_rows: usize) -> Batch {
+        let codec = McmpRowCodec::new(vec![
+            SortField::new(ConcreteDataType::string_datatype()),
+            SortField::new(ConcreteDataType::int64_datatype()),
+        ]);
+        let primary_key = codec
+            .encode([ValueRef::String(host), ValueRef::Int64(cpu)].into_iter())
+            .unwrap();
+        let rows = start..start + num_rows;
+
+        let mut builder = BatchBuilder::new(primary_key);
+        builder
+            .timestamps_array(Arc::new(TimestampMillisecondArray::from_iter_values(
+                rows.clone().map(|row| row as i64),
+            )))
+            .unwrap()
+            .sequences_array(Arc::new(UInt64Array::from_iter_values(
+                rows.clone().map(|_| 1),
+            )))
+            .unwrap()
+            .op_types_array(Arc::new(UInt8Array::from_iter_values(
+                rows.clone().map(|_| OpType::Put as u8),
+            )))
+            .unwrap()
+            .push_field_array(
+                3,
+                Arc::new(StringArray::from_iter_values(
+                    rows.map(|row| format!("t{row}")),
+                )),
+            )
+            .unwrap();
+        builder.build().unwrap()
+    }
+
+    /// Returns row groups to read by the `index`, assuming the SST has `num_row_groups`.
+    pub(crate) fn prune(
+        index: &SstIndex,
+        metadata: &RegionMetadataRef,
+        exprs: Vec<Expr>,
+        num_row_groups: usize,
+    ) -> Option<Vec<usize>> {
+        let predicate = Predicate::try_new(
+            exprs.into_iter().map(Into::into).collect(),
+            metadata.schema.clone(),
+        )
+        .unwrap();
+        index.prune(&predicate, metadata).map(|row_groups| {
+            (0..num_row_groups)
+                .filter(|i| row_groups.contains(*i))
+                .collect()
+        })
+    }
+
+    fn new_bitmap(row_groups: &[usize]) -> RowGroupBitmap {
+        let mut bitmap = RowGroupBitmap::default();
+        for row_group in row_groups {
+            bitmap.insert(*row_group);
+        }
+        bitmap
+    }
+
+    #[test]
+    fn test_row_group_bitmap() {
+        let mut bitmap = new_bitmap(&[0, 3, 70]);
+        assert!(bitmap.contains(0));
+        assert!(bitmap.contains(70));
+        assert!(!bitmap.contains(1));
+        assert!(!bitmap.contains(200));
+
+        bitmap.union(&new_bitmap(&[1, 130]));
+        assert_eq!(new_bitmap(&[0, 1, 3, 70, 130]), bitmap);
+
+        bitmap.intersect(&new_bitmap(&[1, 3, 4]));
+        assert_eq!(new_bitmap(&[1, 3]), bitmap);
+
+        let mut buf = Vec::new();
+        bitmap.encode(&mut buf);
+        assert_eq!(bitmap, RowGroupBitmap::decode(&mut buf.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_decode_invalid_index() {
+        assert!(SstIndex::decode(b"ABCD").is_err());
+
+        let mut buf = SstIndex::default().encode();
+        // Unknown sections are skipped.
+        put_section(&mut buf, 100, b"unknown");
+        assert_eq!(SstIndex::default(), SstIndex::decode(&buf).unwrap());
+
+        // Truncated section.
+        buf.truncate(buf.len() - 1);
+        assert!(SstIndex::decode(&buf).is_err());
+    }
+}
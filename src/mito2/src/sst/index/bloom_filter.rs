@@ -0,0 +1,463 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bloom filter index of columns.
+//!
+//! Min/max statistics can't prune row groups by high-cardinality columns like `trace_id`, so we
+//! build a bloom filter of column values for each row group, which tells whether the row group
+//! may contain a value. Users specify columns to index by the region option
+//! `bloom_filter_columns`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::f64::consts::LN_2;
+
+use bytes::{Buf, BufMut};
+use common_telemetry::warn;
+use datatypes::data_type::ConcreteDataType;
+use datatypes::value::Value;
+use snafu::ensure;
+use store_api::metadata::RegionMetadataRef;
+use store_api::storage::ColumnId;
+
+use crate::error::{InvalidSstIndexSnafu, Result};
+use crate::read::Batch;
+use crate::row_converter::{McmpRowCodec, RowCodec, SortField};
+use crate::sst::index::{encode_value, ensure_remaining, get_u32, RowGroupBitmap};
+
+/// Expected false positive rate of bloom filters.
+const FALSE_POSITIVE_RATE: f64 = 0.01;
+/// Max number of hash functions of a bloom filter.
+const MAX_NUM_HASHES: u32 = 16;
+
+/// Bloom filter index of columns in a SST.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct BloomFilterIndex {
+    /// Column id -> bloom filter of each row group.
+    columns: BTreeMap<ColumnId, Vec<BloomFilter>>,
+}
+
+impl BloomFilterIndex {
+    /// Returns the row groups that may contain the encoded `value` of the column, `None` if the
+    /// column isn't indexed.
+    pub(crate) fn lookup(&self, column_id: ColumnId, value: &[u8]) -> Option<RowGroupBitmap> {
+        let filters = self.columns.get(&column_id)?;
+        let hash = hash_value(value);
+        let mut row_groups = RowGroupBitmap::default();
+        for (row_group, filter) in filters.iter().enumerate() {
+            if filter.may_contain(hash) {
+                row_groups.insert(row_group);
+            }
+        }
+        Some(row_groups)
+    }
+
+    pub(crate) fn encode(&self, buf: &mut Vec<u8>) {
+        buf.put_u32_le(self.columns.len() as u32);
+        for (column_id, filters) in &self.columns {
+            buf.put_u32_le(*column_id);
+            buf.put_u32_le(filters.len() as u32);
+            for filter in filters {
+                filter.encode(buf);
+            }
+        }
+    }
+
+    pub(crate) fn decode(mut buf: &[u8]) -> Result<BloomFilterIndex> {
+        let buf = &mut buf;
+        let mut columns = BTreeMap::new();
+        for _ in 0..get_u32(buf)? {
+            let column_id = get_u32(buf)?;
+            let filters = (0..get_u32(buf)?)
+                .map(|_| BloomFilter::decode(buf))
+                .collect::<Result<_>>()?;
+            let _ = columns.insert(column_id, filters);
+        }
+        Ok(BloomFilterIndex { columns })
+    }
+}
+
+/// A bloom filter of value hashes.
+#[derive(Debug, Clone, PartialEq)]
+struct BloomFilter {
+    /// Number of hash functions.
+    num_hashes: u32,
+    bits: Vec<u64>,
+}
+
+impl BloomFilter {
+    /// Returns a filter containing the `hashes`, its size is chosen by the number of hashes and
+    /// the [FALSE_POSITIVE_RATE].
+    fn with_hashes(hashes: &HashSet<u64>) -> BloomFilter {
+        let num_items = hashes.len().max(1) as f64;
+        let num_bits = (-num_items * FALSE_POSITIVE_RATE.ln() / (LN_2 * LN_2)).ceil() as usize;
+        let num_words = (num_bits + 63) / 64;
+        let num_hashes = ((num_words * 64) as f64 / num_items * LN_2).round() as u32;
+
+        let mut filter = BloomFilter {
+            num_hashes: num_hashes.clamp(1, MAX_NUM_HASHES),
+            bits: vec![0; num_words],
+        };
+        for hash in hashes {
+            filter.insert(*hash);
+        }
+        filter
+    }
+
+    fn insert(&mut self, hash: u64) {
+        for bit in self.bit_positions(hash) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    fn may_contain(&self, hash: u64) -> bool {
+        self.bit_positions(hash)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns positions of bits to set for the `hash`, using double hashing to simulate
+    /// multiple hash functions.
+    fn bit_positions(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let num_bits = self.bits.len() as u64 * 64;
+        let delta = mix(hash) | 1;
+        (0..self.num_hashes as u64)
+            .map(move |i| (hash.wrapping_add(i.wrapping_mul(delta)) % num_bits) as usize)
+    }
+
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.put_u32_le(self.num_hashes);
+        buf.put_u32_le(self.bits.len() as u32);
+        for word in &self.bits {
+            buf.put_u64_le(*word);
+        }
+    }
+
+    fn decode(buf: &mut &[u8]) -> Result<BloomFilter> {
+        let num_hashes = get_u32(buf)?;
+        let len = get_u32(buf)? as usize;
+        ensure_remaining(buf, len * 8)?;
+        ensure!(
+            len > 0,
+            InvalidSstIndexSnafu {
+                reason: "empty bloom filter",
+            }
+        );
+        let bits = (0..len).map(|_| buf.get_u64_le()).collect();
+        Ok(BloomFilter { num_hashes, bits })
+    }
+}
+
+/// Hashes the encoded value by FNV-1a.
+///
+/// The hash is persisted in bloom filters so it must be stable, which `std` hashers don't
+/// guarantee.
+fn hash_value(value: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = value.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(PRIME)
+    });
+    mix(hash)
+}
+
+/// Finalizer of splitmix64 to spread bits of the hash.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Position of an indexed column in batches.
+enum ColumnPosition {
+    /// Index of the tag in the primary key.
+    Tag(usize),
+    Field,
+}
+
+struct IndexedColumn {
+    column_id: ColumnId,
+    data_type: ConcreteDataType,
+    position: ColumnPosition,
+}
+
+/// Builds the [BloomFilterIndex] from batches to write.
+///
+/// The parquet writer splits row groups by the row group size, so the builder builds a bloom
+/// filter for every `row_group_size` rows.
+pub(crate) struct BloomFilterIndexBuilder {
+    /// Codec to decode primary keys.
+    codec: McmpRowCodec,
+    columns: Vec<IndexedColumn>,
+    row_group_size: usize,
+    /// Primary key of the last batch and its tag values.
+    last_key: Option<(Vec<u8>, Vec<Value>)>,
+    /// Hashes of values in the current row group, in the same order as `columns`.
+    hashes: Vec<HashSet<u64>>,
+    /// Bloom filters of finished row groups, in the same order as `columns`.
+    filters: Vec<Vec<BloomFilter>>,
+    /// Number of rows indexed.
+    num_rows: usize,
+}
+
+impl BloomFilterIndexBuilder {
+    /// Returns a new builder to index `column_ids` of the region, the time index and columns
+    /// not in the region are ignored.
+    pub(crate) fn new(
+        metadata: &RegionMetadataRef,
+        column_ids: &[ColumnId],
+        row_group_size: usize,
+    ) -> BloomFilterIndexBuilder {
+        let columns: Vec<_> = column_ids
+            .iter()
+            .filter_map(|column_id| {
+                let column = metadata.column_by_id(*column_id)?;
+                let position = match metadata.primary_key.iter().position(|id| id == column_id) {
+                    Some(index) => ColumnPosition::Tag(index),
+                    None if metadata.time_index_column().column_id == *column_id => return None,
+                    None => ColumnPosition::Field,
+                };
+                Some(IndexedColumn {
+                    column_id: *column_id,
+                    data_type: column.column_schema.data_type.clone(),
+                    position,
+                })
+            })
+            .collect();
+        let codec = McmpRowCodec::new(
+            metadata
+                .primary_key_columns()
+                .map(|c| SortField::new(c.column_schema.data_type.clone()))
+                .collect(),
+        );
+
+        BloomFilterIndexBuilder {
+            codec,
+            hashes: vec![HashSet::new(); columns.len()],
+            filters: vec![Vec::new(); columns.len()],
+            columns,
+            row_group_size: row_group_size.max(1),
+            last_key: None,
+            num_rows: 0,
+        }
+    }
+
+    /// Indexes the `batch`, which are rows following the rows indexed before.
+    pub(crate) fn update(&mut self, batch: &Batch) -> Result<()> {
+        if self.columns.is_empty() || batch.is_empty() {
+            return Ok(());
+        }
+
+        let has_tag = self
+            .columns
+            .iter()
+            .any(|c| matches!(c.position, ColumnPosition::Tag(_)));
+        if has_tag && !matches!(&self.last_key, Some((key, _)) if key == batch.primary_key()) {
+            let values = self.codec.decode(batch.primary_key())?;
+            self.last_key = Some((batch.primary_key().to_vec(), values));
+        }
+
+        // Splits the batch by row groups.
+        let mut start = 0;
+        while start < batch.num_rows() {
+            let rows_in_group = self.num_rows % self.row_group_size;
+            let end = batch
+                .num_rows()
+                .min(start + self.row_group_size - rows_in_group);
+            for (column, hashes) in self.columns.iter().zip(&mut self.hashes) {
+                match column.position {
+                    ColumnPosition::Tag(index) => {
+                        // Safety: the last key is set if there is any tag column.
+                        let value = &self.last_key.as_ref().unwrap().1[index];
+                        insert_hash(hashes, &column.data_type, value)?;
+                    }
+                    ColumnPosition::Field => {
+                        let Some(field) = batch
+                            .fields()
+                            .iter()
+                            .find(|field| field.column_id == column.column_id)
+                        else {
+                            continue;
+                        };
+                        for row in start..end {
+                            insert_hash(hashes, &column.data_type, &field.data.get(row))?;
+                        }
+                    }
+                }
+            }
+
+            self.num_rows += end - start;
+            start = end;
+            if self.num_rows % self.row_group_size == 0 {
+                finish_row_group(&mut self.hashes, &mut self.filters);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finishes the index, `row_group_rows` are the numbers of rows in each row group.
+    ///
+    /// Returns `None` if no column is indexed.
+    pub(crate) fn finish(mut self, row_group_rows: &[usize]) -> Option<BloomFilterIndex> {
+        if self.columns.is_empty() {
+            return None;
+        }
+        if self.num_rows % self.row_group_size != 0 {
+            finish_row_group(&mut self.hashes, &mut self.filters);
+        }
+
+        // Checks the SST is split by the row group size as we expect.
+        let num_row_groups = (self.num_rows + self.row_group_size - 1) / self.row_group_size;
+        let split_as_expected = row_group_rows.len() == num_row_groups
+            && row_group_rows
+                .iter()
+                .rev()
+                .skip(1)
+                .all(|rows| *rows == self.row_group_size);
+        if !split_as_expected {
+            warn!(
+                "Skip building bloom filters, row group size: {}, rows of row groups: {:?}",
+                self.row_group_size, row_group_rows
+            );
+            return None;
+        }
+
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| column.column_id)
+            .zip(self.filters)
+            .collect();
+        Some(BloomFilterIndex { columns })
+    }
+}
+
+/// Inserts the hash of a non-null `value` into `hashes`.
+fn insert_hash(
+    hashes: &mut HashSet<u64>,
+    data_type: &ConcreteDataType,
+    value: &Value,
+) -> Result<()> {
+    // A null value never equals to a literal.
+    if !value.is_null() {
+        let _ = hashes.insert(hash_value(&encode_value(data_type, value)?));
+    }
+    Ok(())
+}
+
+fn finish_row_group(hashes: &mut [HashSet<u64>], filters: &mut [Vec<BloomFilter>]) {
+    for (hashes, filters) in hashes.iter_mut().zip(filters) {
+        filters.push(BloomFilter::with_hashes(hashes));
+        hashes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::logical_expr::{col, lit};
+
+    use super::*;
+    use crate::sst::index::tests::{new_region_metadata, new_test_batch, prune};
+    use crate::sst::index::SstIndex;
+
+    #[test]
+    fn test_bloom_filter() {
+        let hashes: HashSet<_> = (0..1000)
+            .map(|i| hash_value(format!("v{i}").as_bytes()))
+            .collect();
+        let filter = BloomFilter::with_hashes(&hashes);
+        assert!(hashes.iter().all(|hash| filter.may_contain(*hash)));
+
+        let false_positives = (1000..11000)
+            .filter(|i| filter.may_contain(hash_value(format!("v{i}").as_bytes())))
+            .count();
+        assert!(false_positives < 300, "false positives: {false_positives}");
+
+        let mut buf = Vec::new();
+        filter.encode(&mut buf);
+        assert_eq!(filter, BloomFilter::decode(&mut buf.as_slice()).unwrap());
+    }
+
+    /// Builds an index of `host` and `trace_id` for 3 row groups with 10, 10, 5 rows:
+    /// - row group 0: (a, 0) x 10
+    /// - row group 1: (a, 0) x 5, (b, 0) x 5
+    /// - row group 2: (b, 0) x 5
+    fn build_test_index(metadata: &RegionMetadataRef) -> BloomFilterIndex {
+        let mut builder = BloomFilterIndexBuilder::new(metadata, &[1, 3, 4], 10);
+        for batch in [
+            new_test_batch("a", 0, 0, 15),
+            new_test_batch("b", 0, 15, 10),
+        ] {
+            builder.update(&batch).unwrap();
+        }
+        builder.finish(&[10, 10, 5]).unwrap()
+    }
+
+    #[test]
+    fn test_build_bloom_filter_index() {
+        let metadata = new_region_metadata();
+        let index = build_test_index(&metadata);
+        // The time index is ignored.
+        assert_eq!(
+            vec![1, 3],
+            index.columns.keys().copied().collect::<Vec<_>>()
+        );
+        assert!(index.columns.values().all(|filters| filters.len() == 3));
+
+        let mut buf = Vec::new();
+        index.encode(&mut buf);
+        assert_eq!(index, BloomFilterIndex::decode(&buf).unwrap());
+
+        // Row groups are not split by the row group size.
+        let mut builder = BloomFilterIndexBuilder::new(&metadata, &[1], 10);
+        builder.update(&new_test_batch("a", 0, 0, 15)).unwrap();
+        assert!(builder.finish(&[5, 10]).is_none());
+    }
+
+    #[test]
+    fn test_prune_by_bloom_filter_index() {
+        let metadata = new_region_metadata();
+        let index = SstIndex {
+            bloom_filter_index: Some(build_test_index(&metadata)),
+            ..Default::default()
+        };
+
+        let cases = [
+            (vec![col("trace_id").eq(lit("t3"))], vec![0]),
+            (vec![col("trace_id").eq(lit("t12"))], vec![1]),
+            (vec![col("trace_id").eq(lit("t24"))], vec![2]),
+            (
+                vec![col("trace_id").in_list(vec![lit("t0"), lit("t20")], false)],
+                vec![0, 2],
+            ),
+            (vec![col("host").eq(lit("b"))], vec![1, 2]),
+        ];
+        for (exprs, expected) in cases {
+            // Bloom filters may have false positives.
+            let row_groups = prune(&index, &metadata, exprs.clone(), 3).unwrap();
+            assert!(
+                expected.iter().all(|i| row_groups.contains(i)),
+                "exprs: {exprs:?}, row groups: {row_groups:?}"
+            );
+        }
+
+        // Most row groups are pruned for absent values.
+        let num_row_groups: usize = (0..100)
+            .map(|i| {
+                let exprs = vec![col("trace_id").eq(lit(format!("x{i}")))];
+                prune(&index, &metadata, exprs, 3).unwrap().len()
+            })
+            .sum();
+        assert!(num_row_groups < 30, "row groups to read: {num_row_groups}");
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inverted index of tag columns.
+//!
+//! The index maps each value of a tag column to the row groups containing the value, so the
+//! reader can skip row groups for predicates like `host = 'a'` or `host IN ('a', 'b')`, which
+//! min/max statistics can't prune since tags are encoded into the primary key.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+use bytes::BufMut;
+use datatypes::data_type::ConcreteDataType;
+use store_api::metadata::RegionMetadataRef;
+use store_api::storage::ColumnId;
+
+use crate::error::Result;
+use crate::read::Batch;
+use crate::row_converter::{McmpRowCodec, RowCodec, SortField};
+use crate::sst::index::{encode_value, get_bytes, get_u32, RowGroupBitmap};
+
+/// Inverted index of tag columns in a SST.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct InvertedIndex {
+    /// Tag column id -> encoded tag value -> row groups containing the value.
+    columns: BTreeMap<ColumnId, BTreeMap<Vec<u8>, RowGroupBitmap>>,
+}
+
+impl InvertedIndex {
+    /// Returns the row groups containing the encoded `value` of the column, `None` if the
+    /// column isn't indexed.
+    pub(crate) fn lookup(&self, column_id: ColumnId, value: &[u8]) -> Option<RowGroupBitmap> {
+        let values = self.columns.get(&column_id)?;
+        Some(values.get(value).cloned().unwrap_or_default())
+    }
+
+    pub(crate) fn encode(&self, buf: &mut Vec<u8>) {
+        buf.put_u32_le(self.columns.len() as u32);
+        for (column_id, values) in &self.columns {
+            buf.put_u32_le(*column_id);
+            buf.put_u32_le(values.len() as u32);
+            for (value, row_groups) in values {
+                buf.put_u32_le(value.len() as u32);
+                buf.put_slice(value);
+                row_groups.encode(buf);
+            }
+        }
+    }
+
+    pub(crate) fn decode(mut buf: &[u8]) -> Result<InvertedIndex> {
+        let buf = &mut buf;
+        let mut columns = BTreeMap::new();
+        for _ in 0..get_u32(buf)? {
+            let column_id = get_u32(buf)?;
+            let mut values = BTreeMap::new();
+            for _ in 0..get_u32(buf)? {
+                let value = get_bytes(buf)?.to_vec();
+                let row_groups = RowGroupBitmap::decode(buf)?;
+                let _ = values.insert(value, row_groups);
+            }
+            let _ = columns.insert(column_id, values);
+        }
+        Ok(InvertedIndex { columns })
+    }
+}
+
+/// Builds the [InvertedIndex] from batches to write.
+pub(crate) struct InvertedIndexBuilder {
+    /// Codec to decode primary keys.
+    codec: McmpRowCodec,
+    /// Ids and data types of tag columns.
+    tags: Vec<(ColumnId, ConcreteDataType)>,
+    /// Primary key of the last batch and its encoded tag values.
+    last_key: Option<(Vec<u8>, Vec<Vec<u8>>)>,
+    /// Row ranges of each encoded tag value, in the same order as `tags`.
+    postings: Vec<HashMap<Vec<u8>, Vec<Range<usize>>>>,
+    /// Number of rows indexed.
+    num_rows: usize,
+}
+
+impl InvertedIndexBuilder {
+    /// Returns a new builder to index tag columns of the region.
+    pub(crate) fn new(metadata: &RegionMetadataRef) -> InvertedIndexBuilder {
+        let tags: Vec<_> = metadata
+            .primary_key_columns()
+            .map(|c| (c.column_id, c.column_schema.data_type.clone()))
+            .collect();
+        let codec = McmpRowCodec::new(
+            tags.iter()
+                .map(|(_, data_type)| SortField::new(data_type.clone()))
+                .collect(),
+        );
+
+        InvertedIndexBuilder {
+            codec,
+            postings: vec![HashMap::new(); tags.len()],
+            tags,
+            last_key: None,
+            num_rows: 0,
+        }
+    }
+
+    /// Indexes the `batch`, which are rows following the rows indexed before.
+    pub(crate) fn update(&mut self, batch: &Batch) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let rows = self.num_rows..self.num_rows + batch.num_rows();
+        self.num_rows = rows.end;
+        if self.tags.is_empty() {
+            return Ok(());
+        }
+
+        // Batches are sorted by primary key, so we only decode the key when it changes.
+        if !matches!(&self.last_key, Some((key, _)) if key == batch.primary_key()) {
+            let values = self.codec.decode(batch.primary_key())?;
+            let encoded = self
+                .tags
+                .iter()
+                .zip(&values)
+                .map(|((_, data_type), value)| encode_value(data_type, value))
+                .collect::<Result<Vec<_>>>()?;
+            self.last_key = Some((batch.primary_key().to_vec(), encoded));
+        }
+        // Safety: the last key is set above.
+        let (_, encoded) = self.last_key.as_ref().unwrap();
+
+        for (postings, value) in self.postings.iter_mut().zip(encoded) {
+            let Some(ranges) = postings.get_mut(value) else {
+                let _ = postings.insert(value.clone(), vec![rows.clone()]);
+                continue;
+            };
+            // Safety: ranges are never empty.
+            let last = ranges.last_mut().unwrap();
+            if last.end == rows.start {
+                last.end = rows.end;
+            } else {
+                ranges.push(rows.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Finishes the index, `row_group_rows` are the numbers of rows in each row group.
+    ///
+    /// Returns `None` if the region has no tag column.
+    pub(crate) fn finish(self, row_group_rows: &[usize]) -> Option<InvertedIndex> {
+        if self.tags.is_empty() {
+            return None;
+        }
+
+        // The first row of each row group.
+        let row_group_starts: Vec<_> = row_group_rows
+            .iter()
+            .scan(0, |start, rows| {
+                let row_group_start = *start;
+                *start += rows;
+                Some(row_group_start)
+            })
+            .collect();
+        let columns = self
+            .tags
+            .iter()
+            .zip(self.postings)
+            .map(|((column_id, _), postings)| {
+                let values = postings
+                    .into_iter()
+                    .map(|(value, ranges)| {
+                        let mut row_groups = RowGroupBitmap::default();
+                        for range in ranges {
+                            let first = row_group_starts
+                                .partition_point(|start| *start <= range.start)
+                                .saturating_sub(1);
+                            let last = row_group_starts
+                                .partition_point(|start| *start < range.end)
+                                .saturating_sub(1);
+                            (first..=last).for_each(|row_group| row_groups.insert(row_group));
+                        }
+                        (value, row_groups)
+                    })
+                    .collect();
+                (*column_id, values)
+            })
+            .collect();
+
+        Some(InvertedIndex { columns })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::logical_expr::{col, lit};
+    use datatypes::value::Value;
+
+    use super::*;
+    use crate::sst::index::tests::{new_region_metadata, new_test_batch, prune};
+    use crate::sst::index::SstIndex;
+
+    /// Builds an index of 4 row groups with 10 rows each:
+    /// - row group 0: (a, 0) x 10
+    /// - row group 1: (a, 0) x 5, (a, 1) x 5
+    /// - row group 2: (b, 0) x 10
+    /// - row group 3: (b, 0) x 5, (c, 1) x 5
+    fn build_test_index(metadata: &RegionMetadataRef) -> InvertedIndex {
+        let mut builder = InvertedIndexBuilder::new(metadata);
+        for batch in [
+            new_test_batch("a", 0, 0, 8),
+            new_test_batch("a", 0, 8, 7),
+            new_test_batch("a", 1, 15, 5),
+            new_test_batch("b", 0, 20, 15),
+            new_test_batch("c", 1, 35, 5),
+        ] {
+            builder.update(&batch).unwrap();
+        }
+        builder.finish(&[10, 10, 10, 10]).unwrap()
+    }
+
+    #[test]
+    fn test_build_inverted_index() {
+        let metadata = new_region_metadata();
+        let index = build_test_index(&metadata);
+
+        let host_values = &index.columns[&1];
+        let row_groups = |value: Value| {
+            let key = encode_value(&ConcreteDataType::string_datatype(), &value).unwrap();
+            (0..4)
+                .filter(|i| host_values[&key].contains(*i))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![0, 1], row_groups(Value::from("a")));
+        assert_eq!(vec![2, 3], row_groups(Value::from("b")));
+        assert_eq!(vec![3], row_groups(Value::from("c")));
+        assert_eq!(2, index.columns[&2].len());
+
+        let mut buf = Vec::new();
+        index.encode(&mut buf);
+        assert_eq!(index, InvertedIndex::decode(&buf).unwrap());
+    }
+
+    #[test]
+    fn test_prune_by_inverted_index() {
+        let metadata = new_region_metadata();
+        let index = SstIndex {
+            inverted_index: Some(build_test_index(&metadata)),
+            ..Default::default()
+        };
+
+        let cases = [
+            (vec![col("host").eq(lit("a"))], Some(vec![0, 1])),
+            (vec![lit("c").eq(col("host"))], Some(vec![3])),
+            (vec![col("host").eq(lit("d"))], Some(vec![])),
+            (vec![col("cpu").eq(lit(1i64))], Some(vec![1, 3])),
+            (
+                vec![col("host").eq(lit("b")), col("cpu").eq(lit(1i64))],
+                Some(vec![3]),
+            ),
+            (
+                vec![col("host").eq(lit("a")).and(col("trace_id").gt(lit("t1")))],
+                Some(vec![0, 1]),
+            ),
+            (
+                vec![col("host").eq(lit("a")).or(col("host").eq(lit("c")))],
+                Some(vec![0, 1, 3]),
+            ),
+            (
+                vec![col("host").in_list(vec![lit("b"), lit("c")], false)],
+                Some(vec![2, 3]),
+            ),
+            // Not supported.
+            (vec![col("host").in_list(vec![lit("b")], true)], None),
+            (vec![col("host").not_eq(lit("a"))], None),
+            (
+                vec![col("host").eq(lit("a")).or(col("trace_id").gt(lit("t1")))],
+                None,
+            ),
+            // Not indexed.
+            (vec![col("trace_id").eq(lit("t1"))], None),
+        ];
+        for (exprs, expected) in cases {
+            assert_eq!(
+                expected,
+                prune(&index, &metadata, exprs.clone(), 4),
+                "exprs: {exprs:?}"
+            );
+        }
+    }
+}
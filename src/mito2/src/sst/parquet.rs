@@ -15,16 +15,20 @@
 //! SST in parquet format.
 
 mod format;
-mod reader;
-mod writer;
+pub mod reader;
+pub mod writer;
 
 use common_base::readable_size::ReadableSize;
+use store_api::storage::ColumnId;
 
 use crate::sst::file::FileTimeRange;
 
 /// Key of metadata in parquet SST.
 pub const PARQUET_METADATA_KEY: &str = "greptime:metadata";
 
+/// Default row group size for parquet files.
+const DEFAULT_ROW_GROUP_SIZE: usize = 100 * 1024;
+
 /// Parquet write options.
 #[derive(Debug)]
 pub struct WriteOptions {
@@ -32,6 +36,18 @@ pub struct WriteOptions {
     pub write_buffer_size: ReadableSize,
     /// Row group size.
     pub row_group_size: usize,
+    /// Columns to build bloom filter indexes.
+    pub bloom_filter_columns: Vec<ColumnId>,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            write_buffer_size: ReadableSize::mb(8),
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+            bloom_filter_columns: Vec::new(),
+        }
+    }
 }
 
 /// Parquet SST info returned by the writer.
@@ -42,4 +58,6 @@ pub struct SstInfo {
     pub file_size: u64,
     /// Number of rows.
     pub num_rows: usize,
+    /// Size of the index file in bytes, 0 if no index is written.
+    pub index_file_size: u64,
 }
@@ -18,6 +18,7 @@ use std::sync::Arc;
 
 use async_compat::CompatExt;
 use async_trait::async_trait;
+use common_telemetry::warn;
 use common_time::range::TimestampRange;
 use datatypes::arrow::record_batch::RecordBatch;
 use futures::stream::BoxStream;
@@ -37,6 +38,7 @@ use crate::error::{
 };
 use crate::read::{Batch, BatchReader};
 use crate::sst::file::FileHandle;
+use crate::sst::index::{RowGroupBitmap, SstIndex};
 use crate::sst::parquet::format::ReadFormat;
 use crate::sst::parquet::PARQUET_METADATA_KEY;
 
@@ -125,15 +127,25 @@ impl ParquetReaderBuilder {
         let key_value_meta = builder.metadata().file_metadata().key_value_metadata();
         let region_meta = self.get_region_metadata(file_path, key_value_meta)?;
 
-        // Prune row groups by metadata.
+        // Prune row groups by metadata and the index.
         if let Some(predicate) = &self.predicate {
-            // TODO(yingwen): Now we encode tags into the full primary key so we need some approach
-            // to implement pruning.
+            // Tags are encoded into the full primary key so min/max statistics can't prune them,
+            // we rely on the inverted index instead.
+            let index_row_groups = self.prune_by_index(predicate, &region_meta).await;
             let pruned_row_groups = predicate
                 .prune_row_groups(builder.metadata().row_groups())
                 .into_iter()
                 .enumerate()
-                .filter_map(|(idx, valid)| if valid { Some(idx) } else { None })
+                .filter_map(|(idx, valid)| {
+                    let in_index = index_row_groups
+                        .as_ref()
+                        .map_or(true, |row_groups| row_groups.contains(idx));
+                    if valid && in_index {
+                        Some(idx)
+                    } else {
+                        None
+                    }
+                })
                 .collect::<Vec<_>>();
             builder = builder.with_row_groups(pruned_row_groups);
         }
@@ -167,6 +179,52 @@ impl ParquetReaderBuilder {
         Ok((Box::pin(stream), read_format))
     }
 
+    /// Returns the row groups to read according to the index of the SST, `None` if the SST has
+    /// no index or the index can't be applied to the predicate.
+    async fn prune_by_index(
+        &self,
+        predicate: &Predicate,
+        region_meta: &RegionMetadata,
+    ) -> Option<RowGroupBitmap> {
+        if predicate.exprs().is_empty() {
+            return None;
+        }
+
+        let index = self.load_index().await?;
+        index.prune(predicate, region_meta)
+    }
+
+    /// Returns the index of the SST, `None` if the SST has no index or the index is unavailable.
+    ///
+    /// The index is fetched and decoded once, then cached in the file handle.
+    async fn load_index(&self) -> Option<Arc<SstIndex>> {
+        let index_path = self.file_handle.index_file_path(&self.file_dir)?;
+        if let Some(index) = self.file_handle.index() {
+            return Some(index);
+        }
+
+        // The index only helps to skip row groups, so we read all row groups if it is unavailable.
+        let bytes = match self.object_store.read(&index_path).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                // The error may be transient, we will try to read the index again next time.
+                warn!("Failed to read index file {}, error: {}", index_path, e);
+                return None;
+            }
+        };
+        let index = match SstIndex::decode(&bytes) {
+            Ok(index) => index,
+            Err(e) => {
+                // The index file is immutable, caches an empty index to avoid decoding it again.
+                warn!(e; "Failed to decode index file {}", index_path);
+                SstIndex::default()
+            }
+        };
+        let index = Arc::new(index);
+        self.file_handle.set_index(index.clone());
+        Some(index)
+    }
+
     /// Decode region metadata from key value.
     fn get_region_metadata(
         &self,
@@ -15,6 +15,7 @@
 //! Parquet writer.
 
 use common_telemetry::debug;
+use common_time::Timestamp;
 use object_store::ObjectStore;
 use parquet::basic::{Compression, Encoding, ZstdLevel};
 use parquet::file::metadata::KeyValue;
@@ -24,16 +25,19 @@ use snafu::ResultExt;
 use store_api::metadata::RegionMetadataRef;
 use store_api::storage::consts::SEQUENCE_COLUMN_NAME;
 
-use crate::error::{InvalidMetadataSnafu, Result};
-use crate::read::Source;
+use crate::error::{InvalidMetadataSnafu, OpenDalSnafu, Result};
+use crate::read::{Batch, Source};
+use crate::sst::index::{SstIndex, SstIndexBuilder};
 use crate::sst::parquet::format::WriteFormat;
 use crate::sst::parquet::{SstInfo, WriteOptions, PARQUET_METADATA_KEY};
 use crate::sst::stream_writer::BufferedWriter;
 
 /// Parquet SST writer.
-pub struct ParquetWriter<'a> {
+pub struct ParquetWriter {
     /// SST output file path.
-    file_path: &'a str,
+    file_path: String,
+    /// Output path of the index file.
+    index_file_path: String,
     /// Input data source.
     source: Source,
     /// Region metadata of the source and the target SST.
@@ -41,16 +45,18 @@ pub struct ParquetWriter<'a> {
     object_store: ObjectStore,
 }
 
-impl<'a> ParquetWriter<'a> {
+impl ParquetWriter {
     /// Creates a new parquet SST writer.
     pub fn new(
-        file_path: &'a str,
+        file_path: String,
+        index_file_path: String,
         metadata: RegionMetadataRef,
         source: Source,
         object_store: ObjectStore,
     ) -> ParquetWriter {
         ParquetWriter {
             file_path,
+            index_file_path,
             source,
             metadata,
             object_store,
@@ -87,7 +93,7 @@ impl<'a> ParquetWriter<'a> {
 
         let write_format = WriteFormat::new(self.metadata.clone());
         let mut buffered_writer = BufferedWriter::try_new(
-            self.file_path.to_string(),
+            self.file_path.clone(),
             self.object_store.clone(),
             write_format.arrow_schema(),
             Some(writer_props),
@@ -95,13 +101,15 @@ impl<'a> ParquetWriter<'a> {
         )
         .await?;
 
+        let mut stats = SourceStats::default();
+        let mut index_builder = SstIndexBuilder::new(&self.metadata, opts);
         while let Some(batch) = self.source.next_batch().await? {
+            stats.update(&batch);
+            index_builder.update(&batch)?;
             let arrow_batch = write_format.convert_batch(&batch)?;
 
             buffered_writer.write(&arrow_batch).await?;
         }
-        // Get stats from the source.
-        let stats = self.source.stats();
 
         if stats.num_rows == 0 {
             debug!(
@@ -113,16 +121,71 @@ impl<'a> ParquetWriter<'a> {
             return Ok(None);
         }
 
-        let (_file_meta, file_size) = buffered_writer.close().await?;
-        let time_range = (stats.min_timestamp, stats.max_timestamp);
+        let (file_meta, file_size) = buffered_writer.close().await?;
+        // Safety: num rows > 0 so we must have min/max.
+        let time_range = stats.time_range.unwrap();
+
+        let row_group_rows: Vec<_> = file_meta
+            .row_groups
+            .iter()
+            .map(|row_group| row_group.num_rows as usize)
+            .collect();
+        let index_file_size = self
+            .write_index(index_builder.finish(&row_group_rows))
+            .await?;
 
         // object_store.write will make sure all bytes are written or an error is raised.
         Ok(Some(SstInfo {
             time_range,
             file_size,
             num_rows: stats.num_rows,
+            index_file_size,
         }))
     }
+
+    /// Writes the index file next to the SST and returns the size of the index file.
+    async fn write_index(&self, index: SstIndex) -> Result<u64> {
+        if index.is_empty() {
+            return Ok(0);
+        }
+
+        let bytes = index.encode();
+        let index_file_size = bytes.len() as u64;
+        self.object_store
+            .write(&self.index_file_path, bytes)
+            .await
+            .context(OpenDalSnafu)?;
+        Ok(index_file_size)
+    }
+}
+
+#[derive(Default)]
+struct SourceStats {
+    /// Number of rows fetched.
+    num_rows: usize,
+    /// Time range of fetched batches.
+    time_range: Option<(Timestamp, Timestamp)>,
+}
+
+impl SourceStats {
+    fn update(&mut self, batch: &Batch) {
+        if batch.is_empty() {
+            return;
+        }
+
+        self.num_rows += batch.num_rows();
+        // Safety: batch is not empty.
+        let (min_in_batch, max_in_batch) = (
+            batch.first_timestamp().unwrap(),
+            batch.last_timestamp().unwrap(),
+        );
+        if let Some(time_range) = &mut self.time_range {
+            time_range.0 = time_range.0.min(min_in_batch);
+            time_range.1 = time_range.1.max(max_in_batch);
+        } else {
+            self.time_range = Some((min_in_batch, max_in_batch));
+        }
+    }
 }
 
 // TODO(yingwen): Port tests.
@@ -17,10 +17,13 @@ use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 
-use crate::sst::file::{FileHandle, FileId, Level, MAX_LEVEL};
+use common_time::Timestamp;
+
+use crate::sst::file::{FileHandle, FileId, FileMeta, Level, MAX_LEVEL};
+use crate::sst::file_purger::FilePurgerRef;
 
 /// A version of all SSTs in a region.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct SstVersion {
     /// SST metadata organized by levels.
     levels: LevelMetaArray,
@@ -35,6 +38,47 @@ impl SstVersion {
             levels: new_level_meta_vec(),
         }
     }
+
+    /// Returns a slice to metadatas of all levels.
+    pub(crate) fn levels(&self) -> &[LevelMeta] {
+        &self.levels
+    }
+
+    /// Add files to the version.
+    ///
+    /// # Panics
+    /// Panics if level of [FileMeta] is greater than [MAX_LEVEL].
+    pub(crate) fn add_files(
+        &mut self,
+        file_purger: FilePurgerRef,
+        files_to_add: impl Iterator<Item = FileMeta>,
+    ) {
+        for file in files_to_add {
+            let level = file.level;
+            let handle = FileHandle::new(file, file_purger.clone());
+            let file_id = handle.file_id();
+            let old = self.levels[level as usize].files.insert(file_id, handle);
+            assert!(old.is_none(), "Adds an existing file: {file_id}");
+        }
+    }
+
+    /// Remove files from the version.
+    ///
+    /// # Panics
+    /// Panics if level of [FileMeta] is greater than [MAX_LEVEL].
+    pub(crate) fn remove_files(&mut self, files_to_remove: impl Iterator<Item = FileMeta>) {
+        for file in files_to_remove {
+            let level = file.level;
+            if let Some(handle) = self.levels[level as usize].files.remove(&file.file_id) {
+                handle.mark_deleted();
+            }
+        }
+    }
+
+    /// Returns the number of files in all levels.
+    pub(crate) fn num_files(&self) -> usize {
+        self.levels.iter().map(|level| level.files.len()).sum()
+    }
 }
 
 // We only has fixed number of level, so we use array to hold elements. This implementation
@@ -42,6 +86,7 @@ impl SstVersion {
 type LevelMetaArray = [LevelMeta; MAX_LEVEL as usize];
 
 /// Metadata of files in the same SST level.
+#[derive(Clone)]
 pub struct LevelMeta {
     /// Level number.
     level: Level,
@@ -57,6 +102,28 @@ impl LevelMeta {
             files: HashMap::new(),
         }
     }
+
+    /// Returns the level number.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns files in this level.
+    pub fn files(&self) -> impl Iterator<Item = &FileHandle> {
+        self.files.values()
+    }
+
+    /// Returns expired SSTs from current level.
+    pub fn get_expired_files(&self, expire_time: &Timestamp) -> Vec<FileHandle> {
+        self.files
+            .values()
+            .filter(|v| {
+                let (_, end) = v.time_range();
+                &end < expire_time
+            })
+            .cloned()
+            .collect()
+    }
 }
 
 impl fmt::Debug for LevelMeta {
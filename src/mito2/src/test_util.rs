@@ -245,6 +245,55 @@ pub(crate) fn ts_ms_value(data: i64) -> v1::Value {
     }
 }
 
+/// Creates value for string.
+#[cfg(test)]
+pub(crate) fn string_value(data: &str) -> v1::Value {
+    v1::Value {
+        value_data: Some(ValueData::StringValue(data.to_string())),
+    }
+}
+
+/// Creates value for f64.
+#[cfg(test)]
+pub(crate) fn f64_value(data: f64) -> v1::Value {
+    v1::Value {
+        value_data: Some(ValueData::F64Value(data)),
+    }
+}
+
+/// Returns the column schemas for rows of the region created by `request`.
+#[cfg(test)]
+pub(crate) fn rows_schema(request: &RegionCreateRequest) -> Vec<v1::ColumnSchema> {
+    request
+        .column_metadatas
+        .iter()
+        .map(|c| v1::ColumnSchema {
+            column_name: c.column_schema.name.clone(),
+            datatype: api::helper::ColumnDataTypeWrapper::try_from(
+                c.column_schema.data_type.clone(),
+            )
+            .unwrap()
+            .datatype() as i32,
+            semantic_type: c.semantic_type as i32,
+        })
+        .collect()
+}
+
+/// Builds rows for the region created by [CreateRequestBuilder] with default
+/// options. Timestamps of rows are `[start, end)` in seconds.
+#[cfg(test)]
+pub(crate) fn build_rows(start: usize, end: usize) -> Vec<v1::Row> {
+    (start..end)
+        .map(|i| v1::Row {
+            values: vec![
+                string_value(&i.to_string()),
+                f64_value(i as f64),
+                ts_ms_value(i as i64 * 1000),
+            ],
+        })
+        .collect()
+}
+
 /// A reader for test that pop [Batch] from a vector.
 pub struct VecBatchReader {
     batches: Vec<Batch>,
@@ -15,7 +15,9 @@
 //! Structs and utilities for writing regions.
 
 mod handle_close;
+mod handle_compaction;
 mod handle_create;
+mod handle_flush;
 mod handle_open;
 mod handle_write;
 
@@ -34,11 +36,17 @@ use store_api::storage::RegionId;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::sync::{mpsc, Mutex};
 
+use crate::compaction::CompactionScheduler;
 use crate::config::MitoConfig;
 use crate::error::{JoinSnafu, Result, WorkerStoppedSnafu};
-use crate::memtable::{DefaultMemtableBuilder, MemtableBuilderRef};
+use crate::flush::FlushScheduler;
+use crate::memtable::time_series::TimeSeriesMemtableBuilder;
+use crate::memtable::MemtableBuilderRef;
 use crate::region::{MitoRegionRef, RegionMap, RegionMapRef};
-use crate::request::{RegionTask, RequestBody, SenderWriteRequest, WorkerRequest};
+use crate::request::{
+    BackgroundNotify, RegionTask, RequestBody, SenderWriteRequest, WorkerRequest,
+};
+use crate::schedule::scheduler::{LocalScheduler, Scheduler, SchedulerRef};
 use crate::wal::Wal;
 
 /// Identifier for a worker.
@@ -81,9 +89,18 @@ pub(crate) type WorkerId = u32;
 /// Chan0 --> Buffer0
 /// Chan1 --> WorkerThread1
 /// ```
-#[derive(Debug)]
 pub(crate) struct WorkerGroup {
     workers: Vec<RegionWorker>,
+    /// Scheduler for background jobs shared by all workers.
+    scheduler: SchedulerRef,
+}
+
+impl std::fmt::Debug for WorkerGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerGroup")
+            .field("workers", &self.workers)
+            .finish()
+    }
 }
 
 impl WorkerGroup {
@@ -97,6 +114,7 @@ impl WorkerGroup {
     ) -> WorkerGroup {
         assert!(config.num_workers.is_power_of_two());
         let config = Arc::new(config);
+        let scheduler: SchedulerRef = Arc::new(LocalScheduler::new(config.max_background_jobs));
 
         let workers = (0..config.num_workers)
             .map(|id| {
@@ -105,17 +123,21 @@ impl WorkerGroup {
                     config.clone(),
                     log_store.clone(),
                     object_store.clone(),
+                    scheduler.clone(),
                 )
             })
             .collect();
 
-        WorkerGroup { workers }
+        WorkerGroup { workers, scheduler }
     }
 
     /// Stop the worker group.
     pub(crate) async fn stop(&self) -> Result<()> {
         info!("Stop region worker group");
 
+        // Stops the scheduler gracefully.
+        self.scheduler.stop(true).await?;
+
         try_join_all(self.workers.iter().map(|worker| worker.stop())).await?;
 
         Ok(())
@@ -175,6 +197,7 @@ impl RegionWorker {
         config: Arc<MitoConfig>,
         log_store: Arc<S>,
         object_store: ObjectStore,
+        scheduler: SchedulerRef,
     ) -> RegionWorker {
         let regions = Arc::new(RegionMap::default());
         let (sender, receiver) = mpsc::channel(config.worker_channel_size);
@@ -184,11 +207,15 @@ impl RegionWorker {
             id,
             config,
             regions: regions.clone(),
+            sender: sender.clone(),
             receiver,
             wal: Wal::new(log_store),
             object_store,
             running: running.clone(),
-            memtable_builder: Arc::new(DefaultMemtableBuilder::default()),
+            memtable_builder: Arc::new(TimeSeriesMemtableBuilder::default()),
+            scheduler: scheduler.clone(),
+            flush_scheduler: FlushScheduler::new(scheduler.clone()),
+            compaction_scheduler: CompactionScheduler::new(scheduler, sender.clone()),
         };
         let handle = common_runtime::spawn_write(async move {
             worker_thread.run().await;
@@ -283,6 +310,10 @@ struct RegionWorkerLoop<S> {
     config: Arc<MitoConfig>,
     /// Regions bound to the worker.
     regions: RegionMapRef,
+    /// Request sender.
+    ///
+    /// Background jobs use it to notify the worker.
+    sender: Sender<WorkerRequest>,
     /// Request receiver.
     receiver: Receiver<WorkerRequest>,
     /// WAL of the engine.
@@ -293,6 +324,12 @@ struct RegionWorkerLoop<S> {
     running: Arc<AtomicBool>,
     /// Memtable builder for each region.
     memtable_builder: MemtableBuilderRef,
+    /// Background job scheduler.
+    scheduler: SchedulerRef,
+    /// Scheduler for flush tasks.
+    flush_scheduler: FlushScheduler,
+    /// Scheduler for compaction tasks.
+    compaction_scheduler: CompactionScheduler,
 }
 
 impl<S: LogStore> RegionWorkerLoop<S> {
@@ -354,6 +391,10 @@ impl<S: LogStore> RegionWorkerLoop<S> {
                 WorkerRequest::Stop => {
                     debug_assert!(!self.running.load(Ordering::Relaxed));
                 }
+                WorkerRequest::Background { region_id, notify } => {
+                    // For background notify, we handle it directly.
+                    self.handle_background_notify(region_id, notify).await;
+                }
             }
         }
 
@@ -363,9 +404,7 @@ impl<S: LogStore> RegionWorkerLoop<S> {
 
         self.handle_ddl_requests(ddl_requests).await;
     }
-}
 
-impl<S> RegionWorkerLoop<S> {
     /// Takes and handles all ddl requests.
     async fn handle_ddl_requests(&mut self, ddl_tasks: Vec<RegionTask>) {
         if ddl_tasks.is_empty() {
@@ -377,11 +416,17 @@ impl<S> RegionWorkerLoop<S> {
                 RequestBody::Create(req) => self.handle_create_request(task.region_id, req).await,
                 RequestBody::Open(req) => self.handle_open_request(task.region_id, req).await,
                 RequestBody::Close(_) => self.handle_close_request(task.region_id).await,
-                RequestBody::Write(_)
-                | RequestBody::Drop(_)
-                | RequestBody::Alter(_)
-                | RequestBody::Flush(_)
-                | RequestBody::Compact(_) => unreachable!(),
+                RequestBody::Flush(_) => {
+                    self.handle_flush_request(task.region_id, task.sender);
+                    continue;
+                }
+                RequestBody::Compact(_) => {
+                    self.handle_compaction_request(task.region_id, task.sender);
+                    continue;
+                }
+                RequestBody::Write(_) | RequestBody::Drop(_) | RequestBody::Alter(_) => {
+                    unreachable!()
+                }
             };
 
             if let Some(sender) = task.sender {
@@ -391,6 +436,24 @@ impl<S> RegionWorkerLoop<S> {
         }
     }
 
+    /// Handles region background request
+    async fn handle_background_notify(&mut self, region_id: RegionId, notify: BackgroundNotify) {
+        match notify {
+            BackgroundNotify::FlushFinished(req) => {
+                self.handle_flush_finished(region_id, req).await
+            }
+            BackgroundNotify::FlushFailed(req) => self.handle_flush_failed(region_id, req),
+            BackgroundNotify::CompactionFinished(req) => {
+                self.handle_compaction_finished(region_id, req)
+            }
+            BackgroundNotify::CompactionFailed(req) => {
+                self.handle_compaction_failure(region_id, req)
+            }
+        }
+    }
+}
+
+impl<S> RegionWorkerLoop<S> {
     // Clean up the worker.
     async fn clean(&self) {
         // Closes remaining regions.
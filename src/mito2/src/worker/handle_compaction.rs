@@ -0,0 +1,94 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Handling compaction related requests.
+
+use common_telemetry::{error, info, warn};
+use store_api::storage::RegionId;
+use tokio::sync::oneshot::Sender;
+
+use crate::error::{RegionNotFoundSnafu, Result};
+use crate::manifest::action::RegionEdit;
+use crate::request::{CompactionFailed, CompactionFinished};
+use crate::worker::RegionWorkerLoop;
+
+impl<S> RegionWorkerLoop<S> {
+    /// Handles compaction request submitted to region worker.
+    pub(crate) fn handle_compaction_request(
+        &mut self,
+        region_id: RegionId,
+        sender: Option<Sender<Result<()>>>,
+    ) {
+        let Some(region) = self.regions.get_region(region_id) else {
+            if let Some(sender) = sender {
+                let _ = sender.send(RegionNotFoundSnafu { region_id }.fail());
+            }
+            return;
+        };
+
+        if let Err(e) = self
+            .compaction_scheduler
+            .schedule_compaction(&region, sender)
+        {
+            error!(e; "Failed to schedule compaction task for region: {}", region_id);
+        } else {
+            info!(
+                "Successfully scheduled compaction task for region: {}",
+                region_id
+            );
+        }
+    }
+
+    /// Handles compaction finished, update region version and manifest, deleted compacted files.
+    pub(crate) fn handle_compaction_finished(
+        &mut self,
+        region_id: RegionId,
+        request: CompactionFinished,
+    ) {
+        let Some(region) = self.regions.get_region(region_id) else {
+            warn!(
+                "Unable to find region {} to apply compaction result",
+                region_id
+            );
+            request.on_success();
+            return;
+        };
+
+        // Apply edit to region's version.
+        let edit = RegionEdit {
+            files_to_add: request.compaction_outputs.clone(),
+            files_to_remove: request.compacted_files.clone(),
+            compaction_time_window: None,
+            flushed_entry_id: None,
+            flushed_sequence: None,
+        };
+        region
+            .version_control
+            .apply_edit(edit, &[], region.file_purger.clone());
+        request.on_success();
+
+        // Schedule next compaction if necessary.
+        self.compaction_scheduler.on_compaction_finished(region_id);
+    }
+
+    /// When compaction fails, we simply log the error.
+    pub(crate) fn handle_compaction_failure(
+        &mut self,
+        region_id: RegionId,
+        request: CompactionFailed,
+    ) {
+        self.compaction_scheduler
+            .on_compaction_failed(region_id, request.err);
+    }
+}
@@ -24,6 +24,7 @@ use store_api::storage::RegionId;
 
 use crate::error::{InvalidMetadataSnafu, RegionExistsSnafu, Result};
 use crate::region::opener::RegionOpener;
+use crate::request::RegionOptions;
 use crate::worker::RegionWorkerLoop;
 
 impl<S> RegionWorkerLoop<S> {
@@ -50,14 +51,18 @@ impl<S> RegionWorkerLoop<S> {
         }
         builder.primary_key(request.primary_key);
         let metadata = builder.build().context(InvalidMetadataSnafu)?;
+        let options = RegionOptions::from_request_options(&request.options);
+        options.validate(&metadata)?;
 
         // Create a MitoRegion from the RegionMetadata.
         let region = RegionOpener::new(
             region_id,
             self.memtable_builder.clone(),
             self.object_store.clone(),
+            self.scheduler.clone(),
         )
         .metadata(metadata)
+        .options(options)
         .region_dir(&request.region_dir)
         .create(&self.config)
         .await?;
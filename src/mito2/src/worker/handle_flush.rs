@@ -0,0 +1,140 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Handling flush related requests.
+
+use common_telemetry::{error, info};
+use store_api::logstore::LogStore;
+use store_api::storage::RegionId;
+use tokio::sync::oneshot::Sender;
+
+use crate::error::{RegionNotFoundSnafu, Result};
+use crate::flush::{FlushReason, RegionFlushTask};
+use crate::manifest::action::RegionEdit;
+use crate::region::MitoRegionRef;
+use crate::request::{FlushFailed, FlushFinished};
+use crate::worker::RegionWorkerLoop;
+
+impl<S> RegionWorkerLoop<S> {
+    /// Handles manual flush request.
+    pub(crate) fn handle_flush_request(
+        &mut self,
+        region_id: RegionId,
+        sender: Option<Sender<Result<()>>>,
+    ) {
+        let Some(region) = self.regions.get_region(region_id) else {
+            if let Some(sender) = sender {
+                let _ = sender.send(RegionNotFoundSnafu { region_id }.fail());
+            }
+            return;
+        };
+
+        let mut task = self.new_flush_task(&region, FlushReason::Manual);
+        task.senders.extend(sender);
+        if let Err(e) = self.flush_scheduler.schedule_flush(&region, task) {
+            error!(e; "Failed to schedule flush task for region {}", region_id);
+        }
+    }
+
+    /// Flushes the region if its mutable memtable exceeds the write buffer size.
+    pub(crate) fn maybe_flush_region(&mut self, region: &MitoRegionRef) {
+        if self.flush_scheduler.is_flush_requested(region.region_id) {
+            // Already flushing.
+            return;
+        }
+
+        let version = region.version_control.current().version;
+        if version.memtables.mutable_usage()
+            < self.config.region_write_buffer_size.as_bytes() as usize
+        {
+            return;
+        }
+
+        let task = self.new_flush_task(region, FlushReason::MemtableFull);
+        if let Err(e) = self.flush_scheduler.schedule_flush(region, task) {
+            error!(e; "Failed to schedule flush task for region {}", region.region_id);
+        }
+    }
+
+    /// Handles flush failure.
+    pub(crate) fn handle_flush_failed(&mut self, region_id: RegionId, request: FlushFailed) {
+        self.flush_scheduler.on_flush_failed(region_id, request.err);
+    }
+
+    /// Creates a flush task with specific `reason` for the `region`.
+    fn new_flush_task(&self, region: &MitoRegionRef, reason: FlushReason) -> RegionFlushTask {
+        RegionFlushTask {
+            region_id: region.region_id,
+            reason,
+            senders: Vec::new(),
+            request_sender: self.sender.clone(),
+            access_layer: region.access_layer.clone(),
+            memtable_builder: self.memtable_builder.clone(),
+        }
+    }
+}
+
+impl<S: LogStore> RegionWorkerLoop<S> {
+    /// On region flush job finished.
+    pub(crate) async fn handle_flush_finished(
+        &mut self,
+        region_id: RegionId,
+        request: FlushFinished,
+    ) {
+        let Some(region) = self.regions.get_region(region_id) else {
+            // We may dropped or closed the region.
+            request.on_success();
+            return;
+        };
+
+        // Apply edit to region's version.
+        let edit = RegionEdit {
+            files_to_add: request.file_metas.clone(),
+            files_to_remove: Vec::new(),
+            compaction_time_window: None,
+            flushed_entry_id: Some(request.flushed_entry_id),
+            flushed_sequence: Some(request.flushed_sequence),
+        };
+        region.version_control.apply_edit(
+            edit,
+            &request.memtables_to_remove,
+            region.file_purger.clone(),
+        );
+
+        // Delete wal.
+        info!(
+            "Region {} flush finished, tries to delete wal entries up to {}",
+            region_id, request.flushed_entry_id
+        );
+        if let Err(e) = self.wal.obsolete(region_id, request.flushed_entry_id).await {
+            // Data is already persisted to SSTs, we can still remove WAL entries later.
+            error!(e; "Failed to delete wal entries for region {}", region_id);
+        }
+
+        // Notifies waiters.
+        request.on_success();
+
+        // Handle pending requests of the region.
+        if let Some(task) = self.flush_scheduler.on_flush_success(region_id) {
+            if let Err(e) = self.flush_scheduler.schedule_flush(&region, task) {
+                error!(e; "Failed to schedule pending flush task for region {}", region_id);
+            }
+        }
+
+        // Schedules compaction.
+        if let Err(e) = self.compaction_scheduler.schedule_compaction(&region, None) {
+            error!(e; "Failed to schedule compaction after flush, region: {}", region_id);
+        }
+    }
+}
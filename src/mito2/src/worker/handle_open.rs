@@ -17,14 +17,16 @@
 use std::sync::Arc;
 
 use common_telemetry::info;
+use store_api::logstore::LogStore;
 use store_api::region_request::RegionOpenRequest;
 use store_api::storage::RegionId;
 
 use crate::error::Result;
 use crate::region::opener::RegionOpener;
+use crate::request::RegionOptions;
 use crate::worker::RegionWorkerLoop;
 
-impl<S> RegionWorkerLoop<S> {
+impl<S: LogStore> RegionWorkerLoop<S> {
     pub(crate) async fn handle_open_request(
         &mut self,
         region_id: RegionId,
@@ -41,9 +43,11 @@ impl<S> RegionWorkerLoop<S> {
             region_id,
             self.memtable_builder.clone(),
             self.object_store.clone(),
+            self.scheduler.clone(),
         )
+        .options(RegionOptions::from_request_options(&request.options))
         .region_dir(&request.region_dir)
-        .open(&self.config)
+        .open(&self.config, &self.wal)
         .await?;
 
         info!("Region {} is opened", region_id);
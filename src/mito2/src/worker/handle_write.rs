@@ -60,6 +60,9 @@ impl<S: LogStore> RegionWorkerLoop<S> {
         // Write to memtables.
         for mut region_ctx in region_ctxs.into_values() {
             region_ctx.write_memtable();
+
+            // Flush the region if its mutable memtable is full.
+            self.maybe_flush_region(&region_ctx.region);
         }
     }
 }
@@ -251,5 +254,10 @@ impl RegionWriteCtx {
                 notify.err = Some(Arc::new(e));
             }
         }
+
+        // Updates the committed sequence and entry id of the region.
+        self.region
+            .version_control
+            .set_sequence_and_entry_id(self.next_sequence - 1, self.next_entry_id);
     }
 }
@@ -0,0 +1,210 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+
+use datafusion_expr::Operator;
+use datatypes::value::Value;
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+use store_api::storage::RegionNumber;
+
+use crate::error::{self, Error};
+use crate::partition::{PartitionExpr, PartitionRule};
+
+/// [HashPartitionRule] distributes rows to partitions by the hash of some column's value. It's
+/// generated from create table request, using MySQL's syntax:
+///
+/// ```SQL
+/// CREATE TABLE table_name (
+///     columns definition
+/// )
+/// PARTITION BY HASH (column_name) PARTITIONS num
+/// ```
+///
+/// Please refer to MySQL's ["HASH Partitioning"](https://dev.mysql.com/doc/refman/8.0/en/partitioning-hash.html)
+/// document for more details.
+///
+/// Rows are spread evenly among regions when the column has high cardinality, like `host` or
+/// `trace_id`, which would be skewed onto a few regions by range partitioning. The price is that
+/// only equality predicates on the column can be used to prune regions.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HashPartitionRule {
+    column_name: String,
+    /// The i-th region holds rows whose hash of partition value modulo the number of regions
+    /// is i.
+    regions: Vec<RegionNumber>,
+}
+
+impl HashPartitionRule {
+    pub fn new(column_name: impl Into<String>, regions: Vec<RegionNumber>) -> Self {
+        Self {
+            column_name: column_name.into(),
+            regions,
+        }
+    }
+
+    pub fn column_name(&self) -> &String {
+        &self.column_name
+    }
+
+    pub fn all_regions(&self) -> &Vec<RegionNumber> {
+        &self.regions
+    }
+
+    fn region_of(&self, value: &Value) -> RegionNumber {
+        let index = hash_value(value) as usize % self.regions.len();
+        self.regions[index]
+    }
+}
+
+/// Hashes the partition value.
+///
+/// The hash must be stable across processes and versions since it decides where the rows are
+/// stored, so we hash a canonical byte representation of the value with CRC32 instead of using
+/// [std::hash::Hash]. Integers are widened before hashing, so a value hashes the same no matter
+/// which integer type it comes with, e.g. a literal in the filter.
+pub fn hash_value(value: &Value) -> u32 {
+    match value {
+        Value::Null => crc32fast::hash(&[]),
+        Value::Boolean(v) => crc32fast::hash(&[*v as u8]),
+        Value::UInt8(v) => crc32fast::hash(&(*v as u64).to_le_bytes()),
+        Value::UInt16(v) => crc32fast::hash(&(*v as u64).to_le_bytes()),
+        Value::UInt32(v) => crc32fast::hash(&(*v as u64).to_le_bytes()),
+        Value::UInt64(v) => crc32fast::hash(&v.to_le_bytes()),
+        Value::Int8(v) => crc32fast::hash(&(*v as i64).to_le_bytes()),
+        Value::Int16(v) => crc32fast::hash(&(*v as i64).to_le_bytes()),
+        Value::Int32(v) => crc32fast::hash(&(*v as i64).to_le_bytes()),
+        Value::Int64(v) => crc32fast::hash(&v.to_le_bytes()),
+        Value::Float32(v) => crc32fast::hash(&(v.0 as f64).to_le_bytes()),
+        Value::Float64(v) => crc32fast::hash(&v.0.to_le_bytes()),
+        Value::String(v) => crc32fast::hash(v.as_utf8().as_bytes()),
+        Value::Binary(v) => crc32fast::hash(v),
+        Value::Date(v) => crc32fast::hash(&(v.val() as i64).to_le_bytes()),
+        Value::DateTime(v) => crc32fast::hash(&v.val().to_le_bytes()),
+        Value::Timestamp(v) => crc32fast::hash(&v.value().to_le_bytes()),
+        Value::Time(_) | Value::Interval(_) | Value::List(_) => {
+            crc32fast::hash(value.to_string().as_bytes())
+        }
+    }
+}
+
+impl PartitionRule for HashPartitionRule {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn partition_columns(&self) -> Vec<String> {
+        vec![self.column_name().to_string()]
+    }
+
+    fn find_region(&self, values: &[Value]) -> Result<RegionNumber, Error> {
+        debug_assert_eq!(
+            values.len(),
+            1,
+            "HashPartitionRule can only handle one partition value, actual {}",
+            values.len()
+        );
+        let value = values.first().context(error::FindRegionSnafu {
+            reason: "no partition value is provided",
+        })?;
+
+        Ok(self.region_of(value))
+    }
+
+    fn find_regions_by_exprs(&self, exprs: &[PartitionExpr]) -> Result<Vec<RegionNumber>, Error> {
+        if exprs.is_empty() {
+            return Ok(self.regions.clone());
+        }
+        debug_assert_eq!(
+            exprs.len(),
+            1,
+            "HashPartitionRule can only handle one partition expr, actual {}",
+            exprs.len()
+        );
+
+        let PartitionExpr { column, op, value } =
+            exprs.first().context(error::FindRegionSnafu {
+                reason: "no partition expr is provided",
+            })?;
+        // Hash doesn't preserve the order of values, so only the equality predicate could
+        // prune regions.
+        let regions = if column == self.column_name() && *op == Operator::Eq {
+            vec![self.region_of(value)]
+        } else {
+            self.all_regions().clone()
+        };
+        Ok(regions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_region() {
+        let rule = HashPartitionRule::new("host", vec![1, 2, 3, 4]);
+
+        // The same value is always put into the same region.
+        let region = rule.find_region(&["host1".into()]).unwrap();
+        for _ in 0..10 {
+            assert_eq!(region, rule.find_region(&["host1".into()]).unwrap());
+        }
+        let expected = rule.regions[hash_value(&"host1".into()) as usize % 4];
+        assert_eq!(expected, region);
+
+        // Integers of different types are in the same region.
+        assert_eq!(
+            rule.find_region(&[Value::Int32(42)]).unwrap(),
+            rule.find_region(&[Value::Int64(42)]).unwrap()
+        );
+
+        // Values are spread among all regions.
+        let mut regions = (0..100)
+            .map(|i| rule.find_region(&[format!("host{i}").into()]).unwrap())
+            .collect::<Vec<_>>();
+        regions.sort();
+        regions.dedup();
+        assert_eq!(vec![1, 2, 3, 4], regions);
+    }
+
+    #[test]
+    fn test_hash_value_is_stable() {
+        // The hash decides where the rows are stored, it must never change.
+        assert_eq!(0, hash_value(&Value::Null));
+        assert_eq!(0x3610a686, hash_value(&"hello".into()));
+    }
+
+    #[test]
+    fn test_find_regions_by_exprs() {
+        let rule = HashPartitionRule::new("host", vec![1, 2, 3, 4]);
+        let region = rule.find_region(&["host1".into()]).unwrap();
+
+        let test =
+            |column: &str, op: Operator, value: &str, expected_regions: Vec<RegionNumber>| {
+                let expr = PartitionExpr::new(column, op, value.into());
+                let regions = rule.find_regions_by_exprs(&[expr]).unwrap();
+                assert_eq!(expected_regions, regions);
+            };
+
+        test("host", Operator::Eq, "host1", vec![region]);
+        test("host", Operator::NotEq, "host1", vec![1, 2, 3, 4]);
+        test("host", Operator::Lt, "host1", vec![1, 2, 3, 4]);
+        test("host", Operator::GtEq, "host1", vec![1, 2, 3, 4]);
+        test("idc", Operator::Eq, "hz", vec![1, 2, 3, 4]);
+
+        assert_eq!(vec![1, 2, 3, 4], rule.find_regions_by_exprs(&[]).unwrap());
+    }
+}
@@ -19,6 +19,7 @@ use api::v1::RowInsertRequest;
 use common_meta::peer::Peer;
 use common_meta::rpc::router::TableRoute;
 use common_query::prelude::Expr;
+use datafusion_expr::expr::InList;
 use datafusion_expr::{BinaryExpr, Expr as DfExpr, Operator};
 use datatypes::prelude::Value;
 use datatypes::schema::Schema;
@@ -29,6 +30,7 @@ use table::requests::{DeleteRequest, InsertRequest};
 
 use crate::columns::RangeColumnsPartitionRule;
 use crate::error::{FindLeaderSnafu, Result};
+use crate::hash::HashPartitionRule;
 use crate::partition::{PartitionBound, PartitionDef, PartitionExpr};
 use crate::range::RangePartitionRule;
 use crate::route::TableRoutes;
@@ -172,6 +174,25 @@ impl PartitionRuleManager {
             .collect::<Vec<RegionNumber>>();
 
         // TODO(LFC): Serializing and deserializing partition rule is ugly, must find a much more elegant way.
+        if let Some(modulus) = partitions[0].partition.hash_modulus() {
+            // Partitions are sorted by their remainders, so the i-th region holds the remainder i.
+            ensure!(
+                partitions.len() == modulus as usize,
+                error::InvalidTableRouteDataSnafu {
+                    table_id,
+                    err_msg: format!(
+                        "expect {} hash partitions, actual {}",
+                        modulus,
+                        partitions.len()
+                    ),
+                }
+            );
+            return Ok(Arc::new(HashPartitionRule::new(
+                partition_columns[0].clone(),
+                regions,
+            )));
+        }
+
         let partition_rule: PartitionRuleRef = match partition_columns.len() {
             1 => {
                 // Omit the last "MAXVALUE".
@@ -179,7 +200,7 @@ impl PartitionRuleManager {
                     .iter()
                     .filter_map(|info| match &info.partition.partition_bounds()[0] {
                         PartitionBound::Value(v) => Some(v.clone()),
-                        PartitionBound::MaxValue => None,
+                        PartitionBound::MaxValue | PartitionBound::Hash { .. } => None,
                     })
                     .collect::<Vec<Value>>();
                 Arc::new(RangePartitionRule::new(
@@ -293,6 +314,32 @@ fn find_regions0(partition_rule: PartitionRuleRef, filter: &Expr) -> Result<Hash
                     .collect::<HashSet<RegionNumber>>());
             }
         }
+        // `column IN (v1, v2, ...)` is the union of regions of `column = v1`, `column = v2`, ...
+        DfExpr::InList(InList {
+            expr,
+            list,
+            negated: false,
+        }) => {
+            if let DfExpr::Column(c) = expr.as_ref() {
+                if list.iter().all(|e| matches!(e, DfExpr::Literal(_))) {
+                    let mut regions = HashSet::new();
+                    for e in list {
+                        let DfExpr::Literal(scalar) = e else {
+                            unreachable!()
+                        };
+                        let value = Value::try_from(scalar.clone()).with_context(|_| {
+                            error::ConvertScalarValueSnafu {
+                                value: scalar.clone(),
+                            }
+                        })?;
+                        regions.extend(partition_rule.find_regions_by_exprs(&[
+                            PartitionExpr::new(&c.name, Operator::Eq, value),
+                        ])?);
+                    }
+                    return Ok(regions);
+                }
+            }
+        }
         DfExpr::BinaryExpr(BinaryExpr { left, op, right })
             if matches!(op, Operator::And | Operator::Or) =>
         {
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
 use std::sync::Arc;
 
 use common_meta::rpc::router::Partition as MetaPartition;
@@ -43,11 +43,28 @@ pub trait PartitionRule: Sync + Send {
     fn find_regions_by_exprs(&self, exprs: &[PartitionExpr]) -> Result<Vec<RegionNumber>>;
 }
 
-/// The right bound(exclusive) of partition range.
+/// The right bound(exclusive) of partition range, or the remainder of a hash partition.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum PartitionBound {
     Value(Value),
     MaxValue,
+    /// The partition holds rows whose hash of partition value modulo `modulus` is `remainder`.
+    Hash {
+        remainder: u32,
+        modulus: u32,
+    },
+}
+
+impl Display for PartitionBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(v) => write!(f, "{}", v),
+            Self::MaxValue => write!(f, "MAXVALUE"),
+            Self::Hash { remainder, modulus } => {
+                write!(f, "MODULUS {}, REMAINDER {}", modulus, remainder)
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -71,6 +88,14 @@ impl PartitionDef {
     pub fn partition_bounds(&self) -> &Vec<PartitionBound> {
         &self.partition_bounds
     }
+
+    /// Returns the number of hash partitions if this is a partition of `PARTITION BY HASH`.
+    pub fn hash_modulus(&self) -> Option<u32> {
+        match self.partition_bounds.first() {
+            Some(PartitionBound::Hash { modulus, .. }) => Some(*modulus),
+            _ => None,
+        }
+    }
 }
 
 impl TryFrom<MetaPartition> for PartitionDef {
@@ -197,5 +222,45 @@ mod tests {
         let b3 = PartitionBound::MaxValue;
         assert!(b1 < b2);
         assert!(b2 < b3);
+
+        let h0 = PartitionBound::Hash {
+            remainder: 0,
+            modulus: 4,
+        };
+        let h1 = PartitionBound::Hash {
+            remainder: 1,
+            modulus: 4,
+        };
+        assert!(h0 < h1);
+        assert_eq!("MODULUS 4, REMAINDER 1", h1.to_string());
+    }
+
+    #[test]
+    fn test_hash_partition_def() {
+        let def = PartitionDef::new(
+            vec!["host".to_string()],
+            vec![PartitionBound::Hash {
+                remainder: 1,
+                modulus: 4,
+            }],
+        );
+        assert_eq!(Some(4), def.hash_modulus());
+
+        let partition: MetaPartition = def.try_into().unwrap();
+        assert_eq!(
+            r#"{"column_list":["host"],"value_list":["{\"Hash\":{\"remainder\":1,\"modulus\":4}}"]}"#,
+            serde_json::to_string(&partition).unwrap(),
+        );
+        let def: PartitionDef = partition.try_into().unwrap();
+        assert_eq!(
+            def.partition_bounds,
+            vec![PartitionBound::Hash {
+                remainder: 1,
+                modulus: 4,
+            }]
+        );
+
+        let def = PartitionDef::new(vec!["a".to_string()], vec![PartitionBound::MaxValue]);
+        assert_eq!(None, def.hash_modulus());
     }
 }
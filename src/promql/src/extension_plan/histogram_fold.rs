@@ -0,0 +1,691 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use datafusion::arrow::array::{Array, ArrayRef, Float64Array, StringArray, UInt32Array};
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::row::{RowConverter, SortField};
+use datafusion::common::{DFSchema, DFSchemaRef};
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::TaskContext;
+use datafusion::logical_expr::{Expr, LogicalPlan, UserDefinedLogicalNodeCore};
+use datafusion::physical_expr::{PhysicalSortExpr, PhysicalSortRequirement};
+use datafusion::physical_plan::expressions::Column as ColumnExpr;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use datatypes::arrow::compute;
+use futures::{ready, Stream, StreamExt};
+
+/// Folds the buckets of classic Prometheus histograms into one quantile value, which is
+/// what `histogram_quantile()` does.
+///
+/// Rows that have the same tag columns (except `le`) and timestamp are buckets of the
+/// same histogram. Each of them is folded into one row whose field column is the
+/// estimated quantile. The `le` column is removed from the output.
+#[derive(Debug)]
+pub struct HistogramFold {
+    le_column: String,
+    tag_columns: Vec<String>,
+    ts_column: String,
+    field_column: String,
+    quantile: f64,
+    input: LogicalPlan,
+    output_schema: DFSchemaRef,
+}
+
+impl PartialEq for HistogramFold {
+    fn eq(&self, other: &Self) -> bool {
+        self.le_column == other.le_column
+            && self.tag_columns == other.tag_columns
+            && self.ts_column == other.ts_column
+            && self.field_column == other.field_column
+            && self.quantile.to_bits() == other.quantile.to_bits()
+            && self.input == other.input
+    }
+}
+
+impl Eq for HistogramFold {}
+
+impl Hash for HistogramFold {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.le_column.hash(state);
+        self.tag_columns.hash(state);
+        self.ts_column.hash(state);
+        self.field_column.hash(state);
+        self.quantile.to_bits().hash(state);
+        self.input.hash(state);
+    }
+}
+
+impl UserDefinedLogicalNodeCore for HistogramFold {
+    fn name(&self) -> &str {
+        Self::name()
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.output_schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "PromHistogramFold: le=[{}], field=[{}], quantile=[{}]",
+            self.le_column, self.field_column, self.quantile
+        )
+    }
+
+    fn from_template(&self, _exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        assert!(!inputs.is_empty());
+
+        let output_schema = Self::build_output_schema(&inputs[0], &self.le_column)
+            .unwrap_or_else(|_| self.output_schema.clone());
+        Self {
+            le_column: self.le_column.clone(),
+            tag_columns: self.tag_columns.clone(),
+            ts_column: self.ts_column.clone(),
+            field_column: self.field_column.clone(),
+            quantile: self.quantile,
+            input: inputs[0].clone(),
+            output_schema,
+        }
+    }
+}
+
+impl HistogramFold {
+    /// `tag_columns` are the tags that identify one histogram, not including `le_column`.
+    pub fn new(
+        le_column: String,
+        tag_columns: Vec<String>,
+        ts_column: String,
+        field_column: String,
+        quantile: f64,
+        input: LogicalPlan,
+    ) -> DataFusionResult<Self> {
+        if !input
+            .schema()
+            .fields()
+            .iter()
+            .any(|f| f.name() == &field_column)
+        {
+            return Err(DataFusionError::Plan(format!(
+                "field column {field_column} not found in the input of histogram fold"
+            )));
+        }
+        let output_schema = Self::build_output_schema(&input, &le_column)?;
+
+        Ok(Self {
+            le_column,
+            tag_columns,
+            ts_column,
+            field_column,
+            quantile,
+            input,
+            output_schema,
+        })
+    }
+
+    pub const fn name() -> &'static str {
+        "HistogramFold"
+    }
+
+    pub fn to_execution_plan(&self, exec_input: Arc<dyn ExecutionPlan>) -> Arc<dyn ExecutionPlan> {
+        Arc::new(HistogramFoldExec {
+            le_column: self.le_column.clone(),
+            tag_columns: self.tag_columns.clone(),
+            ts_column: self.ts_column.clone(),
+            field_column: self.field_column.clone(),
+            quantile: self.quantile,
+            output_schema: Arc::new(self.output_schema.as_ref().into()),
+            input: exec_input,
+            metric: ExecutionPlanMetricsSet::new(),
+        })
+    }
+
+    fn build_output_schema(input: &LogicalPlan, le_column: &str) -> DataFusionResult<DFSchemaRef> {
+        let input_schema = input.schema();
+        if !input_schema.fields().iter().any(|f| f.name() == le_column) {
+            return Err(DataFusionError::Plan(format!(
+                "le column {le_column} not found in the input of histogram fold"
+            )));
+        }
+        let fields = input_schema
+            .fields()
+            .iter()
+            .filter(|f| f.name() != le_column)
+            .cloned()
+            .collect();
+        Ok(Arc::new(DFSchema::new_with_metadata(
+            fields,
+            input_schema.metadata().clone(),
+        )?))
+    }
+}
+
+#[derive(Debug)]
+pub struct HistogramFoldExec {
+    le_column: String,
+    tag_columns: Vec<String>,
+    ts_column: String,
+    field_column: String,
+    quantile: f64,
+    output_schema: SchemaRef,
+    input: Arc<dyn ExecutionPlan>,
+    metric: ExecutionPlanMetricsSet,
+}
+
+impl ExecutionPlan for HistogramFoldExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<Vec<PhysicalSortRequirement>>> {
+        let input_schema = self.input.schema();
+        let exprs = self
+            .tag_columns
+            .iter()
+            .chain(std::iter::once(&self.ts_column))
+            .map(|col| PhysicalSortRequirement {
+                // Safety: the column names are verified in the planning phase
+                expr: Arc::new(ColumnExpr::new_with_schema(col, &input_schema).unwrap()),
+                options: None,
+            })
+            .collect();
+        vec![Some(exprs)]
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![false; self.children().len()]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        assert!(!children.is_empty());
+        Ok(Arc::new(Self {
+            le_column: self.le_column.clone(),
+            tag_columns: self.tag_columns.clone(),
+            ts_column: self.ts_column.clone(),
+            field_column: self.field_column.clone(),
+            quantile: self.quantile,
+            output_schema: self.output_schema.clone(),
+            input: children[0].clone(),
+            metric: self.metric.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let baseline_metric = BaselineMetrics::new(&self.metric, partition);
+
+        let input = self.input.execute(partition, context)?;
+        let input_schema = input.schema();
+        let key_indices = self
+            .tag_columns
+            .iter()
+            .chain(std::iter::once(&self.ts_column))
+            .map(|col| input_schema.index_of(col))
+            .collect::<Result<Vec<_>, _>>()?;
+        let row_converter = RowConverter::new(
+            key_indices
+                .iter()
+                .map(|i| SortField::new(input_schema.field(*i).data_type().clone()))
+                .collect(),
+        )?;
+
+        Ok(Box::pin(HistogramFoldStream {
+            key_indices,
+            le_index: input_schema.index_of(&self.le_column)?,
+            field_index: input_schema.index_of(&self.field_column)?,
+            quantile: self.quantile,
+            row_converter,
+            buffer: None,
+            output_schema: self.output_schema.clone(),
+            input,
+            metric: baseline_metric,
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics {
+            num_rows: None,
+            total_byte_size: None,
+            column_statistics: None,
+            is_exact: false,
+        }
+    }
+}
+
+impl DisplayAs for HistogramFoldExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "PromHistogramFoldExec: le=[{}], field=[{}], quantile=[{}]",
+                    self.le_column, self.field_column, self.quantile
+                )
+            }
+        }
+    }
+}
+
+/// Assume the input stream is ordered on the tag columns and time index. The last
+/// histogram of each batch is kept in the buffer until the next batch arrives, as
+/// its buckets may continue in the next batch.
+pub struct HistogramFoldStream {
+    key_indices: Vec<usize>,
+    le_index: usize,
+    field_index: usize,
+    quantile: f64,
+    row_converter: RowConverter,
+    buffer: Option<RecordBatch>,
+    output_schema: SchemaRef,
+    input: SendableRecordBatchStream,
+    metric: BaselineMetrics,
+}
+
+impl RecordBatchStream for HistogramFoldStream {
+    fn schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+}
+
+impl Stream for HistogramFoldStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let poll = match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let elapsed_compute = self.metric.elapsed_compute().clone();
+                    let _timer = elapsed_compute.timer();
+                    let batch = match self.buffer.take() {
+                        Some(buffer) => compute::concat_batches(&batch.schema(), &[buffer, batch])?,
+                        None => batch,
+                    };
+                    match self.fold(batch, false)? {
+                        Some(result) => Poll::Ready(Some(Ok(result))),
+                        // all rows belong to the last histogram, wait for more input
+                        None => continue,
+                    }
+                }
+                None => match self.buffer.take() {
+                    Some(buffer) => {
+                        let elapsed_compute = self.metric.elapsed_compute().clone();
+                        let _timer = elapsed_compute.timer();
+                        Poll::Ready(self.fold(buffer, true)?.map(Ok))
+                    }
+                    None => Poll::Ready(None),
+                },
+                Some(Err(e)) => Poll::Ready(Some(Err(e))),
+            };
+            return self.metric.record_poll(poll);
+        }
+    }
+}
+
+impl HistogramFoldStream {
+    /// Fold histograms in the given batch. If `is_last` is false, the last histogram is
+    /// put back into the buffer instead of being folded.
+    fn fold(&mut self, batch: RecordBatch, is_last: bool) -> DataFusionResult<Option<RecordBatch>> {
+        let num_rows = batch.num_rows();
+        if num_rows == 0 {
+            return Ok(None);
+        }
+
+        let keys = self.row_converter.convert_columns(
+            &self
+                .key_indices
+                .iter()
+                .map(|i| batch.column(*i).clone())
+                .collect::<Vec<_>>(),
+        )?;
+        let mut group_starts = (0..num_rows)
+            .filter(|i| *i == 0 || keys.row(*i) != keys.row(*i - 1))
+            .collect::<Vec<_>>();
+        let mut end = num_rows;
+        if !is_last {
+            // Safety: the batch is not empty so there is at least one group
+            let last_start = group_starts.pop().unwrap();
+            self.buffer = Some(batch.slice(last_start, num_rows - last_start));
+            end = last_start;
+        }
+        if group_starts.is_empty() {
+            return Ok(None);
+        }
+
+        let le_array = batch
+            .column(self.le_index)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| {
+                DataFusionError::Execution("expect string array as le column".to_string())
+            })?;
+        let field_array = batch
+            .column(self.field_index)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| {
+                DataFusionError::Execution("expect float64 array as field column".to_string())
+            })?;
+
+        let mut quantiles = Vec::with_capacity(group_starts.len());
+        let mut buckets = Vec::new();
+        for (i, start) in group_starts.iter().enumerate() {
+            let group_end = group_starts.get(i + 1).copied().unwrap_or(end);
+            buckets.clear();
+            for row in *start..group_end {
+                if le_array.is_null(row) || field_array.is_null(row) {
+                    continue;
+                }
+                if let Some(upper_bound) = parse_le(le_array.value(row)) {
+                    buckets.push((upper_bound, field_array.value(row)));
+                }
+            }
+            if buckets.is_empty() {
+                quantiles.push(None);
+            } else {
+                quantiles.push(Some(bucket_quantile(self.quantile, &mut buckets)));
+            }
+        }
+
+        let take_indices =
+            UInt32Array::from(group_starts.iter().map(|i| *i as u32).collect::<Vec<_>>());
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(batch.num_columns() - 1);
+        for (index, column) in batch.columns().iter().enumerate() {
+            if index == self.le_index {
+                continue;
+            }
+            if index == self.field_index {
+                columns.push(Arc::new(Float64Array::from(quantiles.clone())));
+            } else {
+                columns.push(compute::take(column, &take_indices, None)?);
+            }
+        }
+
+        Ok(Some(RecordBatch::try_new(
+            self.output_schema.clone(),
+            columns,
+        )?))
+    }
+}
+
+/// Parse the upper bound in `le` label. Both "+Inf" and "Inf" are accepted.
+fn parse_le(le: &str) -> Option<f64> {
+    let le = le.trim();
+    if le.eq_ignore_ascii_case("+inf") || le.eq_ignore_ascii_case("inf") {
+        return Some(f64::INFINITY);
+    }
+    le.parse().ok()
+}
+
+/// Estimate the quantile from buckets of (upper bound, cumulative count), assuming
+/// a linear distribution within each bucket.
+///
+/// Prometheus's implementation:
+/// https://github.com/prometheus/prometheus/blob/v2.45.0/promql/quantile.go#L71-L125
+fn bucket_quantile(quantile: f64, buckets: &mut Vec<(f64, f64)>) -> f64 {
+    if quantile.is_nan() {
+        return f64::NAN;
+    }
+    if quantile < 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if quantile > 1.0 {
+        return f64::INFINITY;
+    }
+
+    buckets.sort_by(|a, b| a.0.total_cmp(&b.0));
+    if buckets.last().map(|b| b.0) != Some(f64::INFINITY) {
+        return f64::NAN;
+    }
+
+    // merge buckets with the same upper bound
+    buckets.dedup_by(|next, prev| {
+        if next.0 == prev.0 {
+            prev.1 += next.1;
+            true
+        } else {
+            false
+        }
+    });
+    // the cumulative counts may be non-monotonic because of precision loss or scraping
+    // at different times, fix them up
+    for i in 1..buckets.len() {
+        if buckets[i].1 < buckets[i - 1].1 {
+            buckets[i].1 = buckets[i - 1].1;
+        }
+    }
+
+    if buckets.len() < 2 {
+        return f64::NAN;
+    }
+    let observations = buckets[buckets.len() - 1].1;
+    if observations == 0.0 {
+        return f64::NAN;
+    }
+
+    let mut rank = quantile * observations;
+    let b = buckets
+        .iter()
+        .position(|(_, count)| *count >= rank)
+        .unwrap_or(buckets.len() - 1);
+    if b == buckets.len() - 1 {
+        return buckets[buckets.len() - 2].0;
+    }
+    if b == 0 && buckets[0].0 <= 0.0 {
+        return buckets[0].0;
+    }
+
+    let mut bucket_start = 0.0;
+    let bucket_end = buckets[b].0;
+    let mut count = buckets[b].1;
+    if b > 0 {
+        bucket_start = buckets[b - 1].0;
+        count -= buckets[b - 1].1;
+        rank -= buckets[b - 1].1;
+    }
+    bucket_start + (bucket_end - bucket_start) * (rank / count)
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::arrow::array::TimestampMillisecondArray;
+    use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::physical_plan::memory::MemoryExec;
+    use datafusion::prelude::SessionContext;
+
+    use super::*;
+
+    #[test]
+    fn test_bucket_quantile() {
+        let buckets = vec![
+            (1.0, 10.0),
+            (2.0, 50.0),
+            (4.0, 90.0),
+            (f64::INFINITY, 100.0),
+        ];
+
+        assert_eq!(0.5, bucket_quantile(0.05, &mut buckets.clone()));
+        assert_eq!(2.0, bucket_quantile(0.5, &mut buckets.clone()));
+        assert_eq!(3.0, bucket_quantile(0.7, &mut buckets.clone()));
+        // falls into the +Inf bucket
+        assert_eq!(4.0, bucket_quantile(0.99, &mut buckets.clone()));
+        assert_eq!(
+            f64::NEG_INFINITY,
+            bucket_quantile(-1.0, &mut buckets.clone())
+        );
+        assert_eq!(f64::INFINITY, bucket_quantile(2.0, &mut buckets.clone()));
+
+        // unordered and non-monotonic buckets
+        let mut unordered = vec![
+            (f64::INFINITY, 100.0),
+            (2.0, 50.0),
+            (4.0, 40.0),
+            (1.0, 10.0),
+        ];
+        assert_eq!(2.0, bucket_quantile(0.5, &mut unordered));
+
+        // no +Inf bucket
+        assert!(bucket_quantile(0.5, &mut vec![(0.1, 10.0), (0.5, 50.0)]).is_nan());
+        // no observations
+        assert!(bucket_quantile(0.5, &mut vec![(0.1, 0.0), (f64::INFINITY, 0.0)]).is_nan());
+    }
+
+    #[test]
+    fn test_parse_le() {
+        assert_eq!(Some(0.5), parse_le("0.5"));
+        assert_eq!(Some(f64::INFINITY), parse_le("+Inf"));
+        assert_eq!(None, parse_le("foo"));
+    }
+
+    fn prepare_test_data() -> MemoryExec {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("le", DataType::Utf8, true),
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new("value", DataType::Float64, true),
+        ]));
+
+        let batch_1 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "a", "a", "a", "a", "a"])) as _,
+                Arc::new(StringArray::from(vec!["1", "2", "+Inf", "1", "2", "+Inf"])) as _,
+                Arc::new(TimestampMillisecondArray::from(vec![
+                    0, 0, 0, 1000, 1000, 1000,
+                ])) as _,
+                Arc::new(Float64Array::from(vec![
+                    20.0, 60.0, 100.0, 0.0, 100.0, 100.0,
+                ])) as _,
+            ],
+        )
+        .unwrap();
+        // buckets of the last histogram in `batch_1` continue in `batch_2`
+        let batch_2 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["b", "b", "b"])) as _,
+                Arc::new(StringArray::from(vec!["1", "2", "+Inf"])) as _,
+                Arc::new(TimestampMillisecondArray::from(vec![0, 0, 0])) as _,
+                Arc::new(Float64Array::from(vec![0.0, 0.0, 0.0])) as _,
+            ],
+        )
+        .unwrap();
+        let batch_3 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["b", "b"])) as _,
+                Arc::new(StringArray::from(vec!["1", "+Inf"])) as _,
+                Arc::new(TimestampMillisecondArray::from(vec![1000, 1000])) as _,
+                Arc::new(Float64Array::from(vec![4.0, 4.0])) as _,
+            ],
+        )
+        .unwrap();
+
+        MemoryExec::try_new(&[vec![batch_1, batch_2, batch_3]], schema, None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn fold_histograms() {
+        let memory_exec = Arc::new(prepare_test_data());
+        let output_schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new("value", DataType::Float64, true),
+        ]));
+        let fold_exec = Arc::new(HistogramFoldExec {
+            le_column: "le".to_string(),
+            tag_columns: vec!["host".to_string()],
+            ts_column: "timestamp".to_string(),
+            field_column: "value".to_string(),
+            quantile: 0.5,
+            output_schema,
+            input: memory_exec,
+            metric: ExecutionPlanMetricsSet::new(),
+        });
+        let session_context = SessionContext::default();
+        let result = datafusion::physical_plan::collect(fold_exec, session_context.task_ctx())
+            .await
+            .unwrap();
+        let result_literal = datatypes::arrow::util::pretty::pretty_format_batches(&result)
+            .unwrap()
+            .to_string();
+
+        let expected = String::from(
+            "+------+---------------------+-------+\
+            \n| host | timestamp           | value |\
+            \n+------+---------------------+-------+\
+            \n| a    | 1970-01-01T00:00:00 | 1.75  |\
+            \n| a    | 1970-01-01T00:00:01 | 1.5   |\
+            \n| b    | 1970-01-01T00:00:00 | NaN   |\
+            \n| b    | 1970-01-01T00:00:01 | 0.5   |\
+            \n+------+---------------------+-------+",
+        );
+        assert_eq!(result_literal, expected);
+    }
+}
@@ -14,6 +14,7 @@
 
 mod aggr_over_time;
 mod changes;
+mod count_values;
 mod deriv;
 mod extrapolate_rate;
 mod holt_winters;
@@ -23,12 +24,14 @@ mod quantile;
 mod resets;
 #[cfg(test)]
 mod test_util;
+mod unique_match_check;
 
 pub use aggr_over_time::{
     AbsentOverTime, AvgOverTime, CountOverTime, LastOverTime, MaxOverTime, MinOverTime,
     PresentOverTime, StddevOverTime, StdvarOverTime, SumOverTime,
 };
 pub use changes::Changes;
+pub use count_values::CountValuesLabel;
 use datafusion::arrow::array::{ArrayRef, Float64Array, TimestampMillisecondArray};
 use datafusion::error::DataFusionError;
 use datafusion::physical_plan::ColumnarValue;
@@ -37,8 +40,9 @@ pub use extrapolate_rate::{Delta, Increase, Rate};
 pub use holt_winters::HoltWinters;
 pub use idelta::IDelta;
 pub use predict_linear::PredictLinear;
-pub use quantile::QuantileOverTime;
+pub use quantile::{Quantile, QuantileOverTime};
 pub use resets::Resets;
+pub use unique_match_check::UniqueMatchCheck;
 
 pub(crate) fn extract_array(columnar_value: &ColumnarValue) -> Result<ArrayRef, DataFusionError> {
     if let ColumnarValue::Array(array) = columnar_value {
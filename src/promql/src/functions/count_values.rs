@@ -0,0 +1,123 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Float64Array, StringArray};
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datatypes::arrow::array::Array;
+use datatypes::arrow::datatypes::DataType;
+
+use crate::functions::extract_array;
+
+/// Formats sample values into label values for the `count_values` aggregation operator
+/// in PromQL, in the same way as `strconv.FormatFloat(v, 'f', -1, 64)` in Go.
+#[derive(Debug)]
+pub struct CountValuesLabel {}
+
+impl CountValuesLabel {
+    pub const fn name() -> &'static str {
+        "prom_count_values_label"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        ScalarUDF {
+            name: Self::name().to_string(),
+            signature: Signature::new(
+                TypeSignature::Exact(Self::input_type()),
+                Volatility::Immutable,
+            ),
+            return_type: Arc::new(|_| Ok(Arc::new(Self::return_type()))),
+            fun: Arc::new(Self::calc),
+        }
+    }
+
+    fn input_type() -> Vec<DataType> {
+        vec![DataType::Float64]
+    }
+
+    fn return_type() -> DataType {
+        DataType::Utf8
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        assert_eq!(input.len(), 1);
+        let array = extract_array(&input[0])?;
+        let values = array
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect Float64 as input array's type, found {}",
+                    Self::name(),
+                    array.data_type()
+                ))
+            })?;
+
+        let result = values
+            .iter()
+            .map(|value| value.map(format_value))
+            .collect::<StringArray>();
+        Ok(ColumnarValue::Array(Arc::new(result)))
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_label_values() {
+        let values = Float64Array::from(vec![
+            Some(1.0),
+            Some(0.25),
+            Some(-3.5),
+            Some(1e21),
+            Some(f64::INFINITY),
+            Some(f64::NEG_INFINITY),
+            Some(f64::NAN),
+            None,
+        ]);
+        let input = vec![ColumnarValue::Array(Arc::new(values))];
+        let ColumnarValue::Array(result) = CountValuesLabel::calc(&input).unwrap() else {
+            unreachable!()
+        };
+        let result = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![
+                Some("1"),
+                Some("0.25"),
+                Some("-3.5"),
+                Some("1000000000000000000000"),
+                Some("+Inf"),
+                Some("-Inf"),
+                Some("NaN"),
+                None
+            ]
+        );
+    }
+}
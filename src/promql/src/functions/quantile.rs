@@ -14,8 +14,8 @@
 
 use std::sync::Arc;
 
-use datafusion::arrow::array::Float64Array;
-use datafusion::arrow::datatypes::TimeUnit;
+use datafusion::arrow::array::{Float64Array, ListArray};
+use datafusion::arrow::datatypes::{Field, TimeUnit};
 use datafusion::common::DataFusionError;
 use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
 use datafusion::physical_plan::ColumnarValue;
@@ -128,6 +128,91 @@ impl QuantileOverTime {
     }
 }
 
+/// The `quantile` aggregation operator in PromQL. It takes the values of each group
+/// collected by `ARRAY_AGG` and calculates their φ-quantile.
+pub struct Quantile {
+    quantile: f64,
+}
+
+impl Quantile {
+    fn new(quantile: f64) -> Self {
+        Self { quantile }
+    }
+
+    pub const fn name() -> &'static str {
+        "prom_quantile"
+    }
+
+    pub fn scalar_udf(quantile: f64) -> ScalarUDF {
+        ScalarUDF {
+            name: Self::name().to_string(),
+            signature: Signature::new(
+                TypeSignature::Exact(Self::input_type()),
+                Volatility::Immutable,
+            ),
+            return_type: Arc::new(|_| Ok(Arc::new(Self::return_type()))),
+            fun: Arc::new(move |input| Self::new(quantile).calc(input)),
+        }
+    }
+
+    // list of values in a group
+    fn input_type() -> Vec<DataType> {
+        vec![DataType::List(Arc::new(Field::new(
+            "item",
+            DataType::Float64,
+            true,
+        )))]
+    }
+
+    fn return_type() -> DataType {
+        DataType::Float64
+    }
+
+    fn calc(&self, input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        assert_eq!(input.len(), 1);
+        let array = extract_array(&input[0])?;
+        let list_array = array.as_any().downcast_ref::<ListArray>().ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "{}: expect List as input array's type, found {}",
+                Self::name(),
+                array.data_type()
+            ))
+        })?;
+        error::ensure(
+            list_array.value_type() == DataType::Float64,
+            DataFusionError::Execution(format!(
+                "{}: expect Float64 as value array's type, found {}",
+                Self::name(),
+                list_array.value_type()
+            )),
+        )?;
+
+        let mut result_array = Vec::with_capacity(list_array.len());
+        for index in 0..list_array.len() {
+            if list_array.is_null(index) {
+                result_array.push(None);
+                continue;
+            }
+            let values = list_array.value(index);
+            let values = values
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .unwrap()
+                .iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            if values.is_empty() {
+                result_array.push(None);
+            } else {
+                result_array.push(quantile_impl(&values, self.quantile));
+            }
+        }
+
+        let result = ColumnarValue::Array(Arc::new(Float64Array::from_iter(result_array)));
+        Ok(result)
+    }
+}
+
 /// Refer to https://github.com/prometheus/prometheus/blob/6e2905a4d4ff9b47b1f6d201333f5bd53633f921/promql/quantile.go#L357-L386
 fn quantile_impl(values: &[f64], quantile: f64) -> Option<f64> {
     if quantile.is_nan() || values.is_empty() {
@@ -156,8 +241,29 @@ fn quantile_impl(values: &[f64], quantile: f64) -> Option<f64> {
 
 #[cfg(test)]
 mod tests {
+    use datafusion::arrow::datatypes::Float64Type;
+
     use super::*;
 
+    #[test]
+    fn calculate_quantile_of_groups() {
+        let list_array = ListArray::from_iter_primitive::<Float64Type, _, _>(vec![
+            Some(vec![Some(1.0), Some(3.0), Some(2.0), Some(4.0)]),
+            Some(vec![Some(5.0), None]),
+            Some(vec![None]),
+            None,
+        ]);
+        let input = vec![ColumnarValue::Array(Arc::new(list_array))];
+        let ColumnarValue::Array(result) = Quantile::new(0.5).calc(&input).unwrap() else {
+            unreachable!()
+        };
+        let result = result.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(2.5), Some(5.0), None, None]
+        );
+    }
+
     #[test]
     fn test_quantile_impl_empty() {
         let values = &[];
@@ -0,0 +1,103 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{BooleanArray, Int64Array};
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datatypes::arrow::array::Array;
+use datatypes::arrow::datatypes::DataType;
+
+use crate::functions::extract_array;
+
+/// Checks the cardinality of one side in vector matching. The input is the number of
+/// samples in each match group, and an error with the given message is raised if any
+/// group has more than one sample. Otherwise it returns `true` for every row.
+#[derive(Debug)]
+pub struct UniqueMatchCheck {
+    message: String,
+}
+
+impl UniqueMatchCheck {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+
+    pub const fn name() -> &'static str {
+        "prom_unique_match_check"
+    }
+
+    pub fn scalar_udf(message: String) -> ScalarUDF {
+        ScalarUDF {
+            name: Self::name().to_string(),
+            signature: Signature::new(
+                TypeSignature::Exact(Self::input_type()),
+                Volatility::Immutable,
+            ),
+            return_type: Arc::new(|_| Ok(Arc::new(Self::return_type()))),
+            fun: Arc::new(move |input| Self::new(message.clone()).calc(input)),
+        }
+    }
+
+    // the number of samples in match group
+    fn input_type() -> Vec<DataType> {
+        vec![DataType::Int64]
+    }
+
+    fn return_type() -> DataType {
+        DataType::Boolean
+    }
+
+    fn calc(&self, input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        assert_eq!(input.len(), 1);
+        let array = extract_array(&input[0])?;
+        let counts = array.as_any().downcast_ref::<Int64Array>().ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "{}: expect Int64 as input array's type, found {}",
+                Self::name(),
+                array.data_type()
+            ))
+        })?;
+
+        if counts.iter().flatten().any(|count| count > 1) {
+            return Err(DataFusionError::Execution(self.message.clone()));
+        }
+
+        let result = BooleanArray::from(vec![true; counts.len()]);
+        Ok(ColumnarValue::Array(Arc::new(result)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_unique_match() {
+        let check = UniqueMatchCheck::new("duplicated".to_string());
+
+        let input = vec![ColumnarValue::Array(Arc::new(Int64Array::from(vec![1, 1])))];
+        let ColumnarValue::Array(result) = check.calc(&input).unwrap() else {
+            unreachable!()
+        };
+        let result = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(result, &BooleanArray::from(vec![true, true]));
+
+        let input = vec![ColumnarValue::Array(Arc::new(Int64Array::from(vec![1, 2])))];
+        let err = check.calc(&input).unwrap_err();
+        assert!(err.to_string().contains("duplicated"), "{err}");
+    }
+}
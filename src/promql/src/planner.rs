@@ -19,13 +19,18 @@ use std::time::UNIX_EPOCH;
 
 use async_recursion::async_recursion;
 use catalog::table_source::DfTableSourceProvider;
+use common_error::ext::ErrorExt;
+use common_error::status_code::StatusCode;
 use datafusion::common::{DFSchemaRef, OwnedTableReference, Result as DfResult};
 use datafusion::datasource::DefaultTableSource;
-use datafusion::logical_expr::expr::{AggregateFunction, Alias, ScalarFunction, ScalarUDF};
+use datafusion::logical_expr::expr::{
+    AggregateFunction, Alias, ScalarFunction, ScalarUDF, WindowFunction,
+};
 use datafusion::logical_expr::expr_rewriter::normalize_cols;
 use datafusion::logical_expr::{
-    AggregateFunction as AggregateFunctionEnum, BinaryExpr, BuiltinScalarFunction, Cast, Extension,
-    LogicalPlan, LogicalPlanBuilder, Operator, ScalarUDF as ScalarUdfDef,
+    AggregateFunction as AggregateFunctionEnum, BinaryExpr, BuiltInWindowFunction,
+    BuiltinScalarFunction, Cast, Extension, LogicalPlan, LogicalPlanBuilder, Operator,
+    ScalarUDF as ScalarUdfDef, WindowFrame, WindowFunction as WindowFunctionEnum,
 };
 use datafusion::optimizer::utils;
 use datafusion::prelude as df_prelude;
@@ -35,9 +40,9 @@ use datafusion::sql::TableReference;
 use datatypes::arrow::datatypes::DataType as ArrowDataType;
 use promql_parser::label::{MatchOp, Matcher, Matchers, METRIC_NAME};
 use promql_parser::parser::{
-    token, AggregateExpr, BinaryExpr as PromBinaryExpr, Call, EvalStmt, Expr as PromExpr, Function,
-    LabelModifier, MatrixSelector, NumberLiteral, Offset, ParenExpr, StringLiteral, SubqueryExpr,
-    TokenType, UnaryExpr, VectorSelector,
+    token, AggregateExpr, AtModifier, BinModifier, BinaryExpr as PromBinaryExpr, Call, EvalStmt,
+    Expr as PromExpr, Function, LabelModifier, MatrixSelector, NumberLiteral, Offset, ParenExpr,
+    StringLiteral, SubqueryExpr, TokenType, UnaryExpr, VectorMatchCardinality, VectorSelector,
 };
 use snafu::{ensure, OptionExt, ResultExt};
 use table::table::adapter::DfTableProviderAdapter;
@@ -50,13 +55,14 @@ use crate::error::{
     ValueNotFoundSnafu, ZeroRangeSelectorSnafu,
 };
 use crate::extension_plan::{
-    build_special_time_expr, EmptyMetric, InstantManipulate, Millisecond, RangeManipulate,
-    SeriesDivide, SeriesNormalize,
+    build_special_time_expr, EmptyMetric, HistogramFold, InstantManipulate, Millisecond,
+    RangeManipulate, SeriesDivide, SeriesNormalize,
 };
 use crate::functions::{
-    AbsentOverTime, AvgOverTime, Changes, CountOverTime, Delta, Deriv, HoltWinters, IDelta,
-    Increase, LastOverTime, MaxOverTime, MinOverTime, PredictLinear, PresentOverTime,
-    QuantileOverTime, Rate, Resets, StddevOverTime, StdvarOverTime, SumOverTime,
+    AbsentOverTime, AvgOverTime, Changes, CountOverTime, CountValuesLabel, Delta, Deriv,
+    HoltWinters, IDelta, Increase, LastOverTime, MaxOverTime, MinOverTime, PredictLinear,
+    PresentOverTime, Quantile, QuantileOverTime, Rate, Resets, StddevOverTime, StdvarOverTime,
+    SumOverTime, UniqueMatchCheck,
 };
 
 /// `time()` function in PromQL.
@@ -70,6 +76,34 @@ const DEFAULT_FIELD_COLUMN: &str = "value";
 /// Special modifier to project field columns under multi-field mode
 const FIELD_COLUMN_MATCHER: &str = "__field__";
 
+/// The tag that holds the upper bound of buckets in Prometheus histograms.
+const LE_COLUMN_NAME: &str = "le";
+
+/// Qualifiers of two sides in binary operations with vector matching.
+const LEFT_SIDE_ALIAS: &str = "lhs";
+const RIGHT_SIDE_ALIAS: &str = "rhs";
+
+/// Prefix of columns holding values of matching labels.
+const MATCH_KEY_PREFIX: &str = "__match_key_";
+
+const ONE_TO_ONE_MATCH_ERROR: &str =
+    "multiple matches for labels: many-to-one matching must be explicit (group_left/group_right)";
+const LEFT_DUPLICATE_MATCH_ERROR: &str =
+    "found duplicate series for the match group on the left hand-side of the operation; \
+    many-to-many matching not allowed: matching labels must be unique on one side";
+const RIGHT_DUPLICATE_MATCH_ERROR: &str =
+    "found duplicate series for the match group on the right hand-side of the operation; \
+    many-to-many matching not allowed: matching labels must be unique on one side";
+
+/// Columns of the steps that results pinned by `@` modifier are broadcast to.
+const PINNED_STEP_TIME: &str = "__pinned_step_time";
+const PINNED_STEP_VALUE: &str = "__pinned_step_value";
+
+/// Intermediate columns of `absent()` and `scalar()`
+const SAMPLE_TIME: &str = "sample_time";
+const SAMPLE_COUNT: &str = "sample_count";
+const SAMPLE_VALUE: &str = "sample_value";
+
 #[derive(Default, Debug, Clone)]
 struct PromPlannerContext {
     // query parameters
@@ -86,15 +120,22 @@ struct PromPlannerContext {
     field_column_matcher: Option<Vec<Matcher>>,
     /// The range in millisecond of range selector. None if there is no range selector.
     range: Option<Millisecond>,
+    /// The time range of the whole query, which `@ start()` and `@ end()` refer to.
+    query_start: Millisecond,
+    query_end: Millisecond,
 }
 
 impl PromPlannerContext {
     fn from_eval_stmt(stmt: &EvalStmt) -> Self {
+        let start = stmt.start.duration_since(UNIX_EPOCH).unwrap().as_millis() as _;
+        let end = stmt.end.duration_since(UNIX_EPOCH).unwrap().as_millis() as _;
         Self {
-            start: stmt.start.duration_since(UNIX_EPOCH).unwrap().as_millis() as _,
-            end: stmt.end.duration_since(UNIX_EPOCH).unwrap().as_millis() as _,
+            start,
+            end,
             interval: stmt.interval.as_millis() as _,
             lookback_delta: stmt.lookback_delta.as_millis() as _,
+            query_start: start,
+            query_end: end,
             ..Default::default()
         }
     }
@@ -120,39 +161,55 @@ impl PromPlanner {
 
     #[async_recursion]
     pub async fn prom_expr_to_plan(&mut self, prom_expr: PromExpr) -> Result<LogicalPlan> {
+        // evaluate the expr pinned by `@` modifier only once, then broadcast the result
+        if let Some((unpinned_expr, pinned_time)) = self.split_at_modifier(&prom_expr) {
+            let (start, end) = (self.ctx.start, self.ctx.end);
+            self.ctx.start = pinned_time;
+            self.ctx.end = pinned_time;
+            let plan = self.prom_expr_to_plan(unpinned_expr).await;
+            self.ctx.start = start;
+            self.ctx.end = end;
+            return self.create_pinned_broadcast_plan(plan?);
+        }
+
         let res = match &prom_expr {
             PromExpr::Aggregate(AggregateExpr {
                 op,
                 expr,
-                // TODO(ruihang): support param
-                param: _param,
+                param,
                 modifier,
             }) => {
                 let input = self.prom_expr_to_plan(*expr.clone()).await?;
 
-                // calculate columns to group by
-                // Need to append time index column into group by columns
-                let group_exprs = modifier
-                    .as_ref()
-                    .map_or(Ok(vec![self.create_time_index_column_expr()?]), |m| {
-                        self.agg_modifier_to_col(input.schema(), m)
-                    })?;
-
-                // convert op and value columns to aggregate exprs
-                let aggr_exprs = self.create_aggregate_exprs(*op, &input)?;
-
-                // create plan
-                let group_sort_expr = group_exprs
-                    .clone()
-                    .into_iter()
-                    .map(|expr| expr.sort(true, false));
-                LogicalPlanBuilder::from(input)
-                    .aggregate(group_exprs, aggr_exprs)
-                    .context(DataFusionPlanningSnafu)?
-                    .sort(group_sort_expr)
-                    .context(DataFusionPlanningSnafu)?
-                    .build()
-                    .context(DataFusionPlanningSnafu)?
+                match op.id() {
+                    token::T_TOPK | token::T_BOTTOMK => {
+                        self.create_topk_plan(input, *op, param, modifier)?
+                    }
+                    token::T_QUANTILE => self.create_quantile_plan(input, *op, param, modifier)?,
+                    token::T_COUNT_VALUES => {
+                        self.create_count_values_plan(input, param, modifier)?
+                    }
+                    _ => {
+                        // calculate columns to group by
+                        let group_exprs = self.create_group_exprs(&input, modifier)?;
+
+                        // convert op and value columns to aggregate exprs
+                        let aggr_exprs = self.create_aggregate_exprs(*op, &input)?;
+
+                        // create plan
+                        let group_sort_expr = group_exprs
+                            .clone()
+                            .into_iter()
+                            .map(|expr| expr.sort(true, false));
+                        LogicalPlanBuilder::from(input)
+                            .aggregate(group_exprs, aggr_exprs)
+                            .context(DataFusionPlanningSnafu)?
+                            .sort(group_sort_expr)
+                            .context(DataFusionPlanningSnafu)?
+                            .build()
+                            .context(DataFusionPlanningSnafu)?
+                    }
+                }
             }
             PromExpr::Unary(UnaryExpr { expr }) => {
                 // Unary Expr in PromQL implys the `-` operator
@@ -250,13 +307,35 @@ impl PromPlanner {
                     // both are columns. join them on time index
                     (None, None) => {
                         let left_input = self.prom_expr_to_plan(*lhs.clone()).await?;
+                        let left_ctx = self.ctx.clone();
                         let left_field_columns = self.ctx.field_columns.clone();
                         let left_schema = left_input.schema().clone();
 
                         let right_input = self.prom_expr_to_plan(*rhs.clone()).await?;
+                        let right_ctx = self.ctx.clone();
                         let right_field_columns = self.ctx.field_columns.clone();
                         let right_schema = right_input.schema().clone();
 
+                        if Self::is_token_a_set_op(*op) {
+                            return self.create_set_op_plan(
+                                (left_input, left_ctx),
+                                (right_input, right_ctx),
+                                *op,
+                                modifier,
+                            );
+                        }
+                        if let Some(modifier) = modifier
+                            .as_ref()
+                            .filter(|m| Self::has_explicit_vector_matching(m))
+                        {
+                            return self.create_vector_matching_plan(
+                                (left_input, left_ctx),
+                                (right_input, right_ctx),
+                                *op,
+                                modifier,
+                            );
+                        }
+
                         let mut field_columns =
                             left_field_columns.iter().zip(right_field_columns.iter());
                         // the new ctx.field_columns for the generated join plan
@@ -294,10 +373,45 @@ impl PromPlanner {
                 }
             }
             PromExpr::Paren(ParenExpr { expr }) => self.prom_expr_to_plan(*expr.clone()).await?,
-            PromExpr::Subquery(SubqueryExpr { .. }) => UnsupportedExprSnafu {
-                name: "Prom Subquery",
+            PromExpr::Subquery(SubqueryExpr {
+                expr,
+                offset,
+                range,
+                step,
+                ..
+            }) => {
+                ensure!(!range.is_zero(), ZeroRangeSelectorSnafu);
+                let range_ms = range.as_millis() as Millisecond;
+                let offset_ms = Self::offset_to_millis(offset);
+                // the step defaults to the interval of outer query
+                let step_ms = step
+                    .filter(|step| !step.is_zero())
+                    .map_or(self.ctx.interval, |step| step.as_millis() as _);
+
+                // evaluate the inner expr at its own steps, which are aligned to the
+                // multiples of step and cover all ranges of the outer query
+                let outer_ctx = self.ctx.clone();
+                let mut inner_start = outer_ctx.start - offset_ms - range_ms;
+                let misalignment = inner_start.rem_euclid(step_ms);
+                if misalignment != 0 {
+                    inner_start += step_ms - misalignment;
+                }
+                self.ctx = PromPlannerContext {
+                    start: inner_start,
+                    end: outer_ctx.end - offset_ms,
+                    interval: step_ms,
+                    lookback_delta: outer_ctx.lookback_delta,
+                    query_start: outer_ctx.query_start,
+                    query_end: outer_ctx.query_end,
+                    ..Default::default()
+                };
+                let input = self.prom_expr_to_plan(*expr.clone()).await?;
+                self.ctx.start = outer_ctx.start;
+                self.ctx.end = outer_ctx.end;
+                self.ctx.interval = outer_ctx.interval;
+
+                self.create_subquery_plan(input, offset_ms, range_ms)?
             }
-            .fail()?,
             PromExpr::NumberLiteral(NumberLiteral { val }) => {
                 self.ctx.time_index_column = Some(DEFAULT_TIME_INDEX_COLUMN.to_string());
                 self.ctx.field_columns = vec![DEFAULT_FIELD_COLUMN.to_string()];
@@ -424,23 +538,71 @@ impl PromPlanner {
                     }));
                 }
 
+                let absent_labels = Self::absent_labels(&args.args);
                 let args = self.create_function_args(&args.args)?;
-                let input = self
-                    .prom_expr_to_plan(args.input.with_context(|| ExpectExprSnafu {
+                let input = match args.input {
+                    Some(input) if func.name == "absent" => {
+                        match self.prom_expr_to_plan(input).await {
+                            Ok(input) => input,
+                            // a metric that doesn't exist at all is absent at any time
+                            Err(e) if e.status_code() == StatusCode::TableNotFound => {
+                                return self.create_absent_plan(None, absent_labels);
+                            }
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    Some(input) => self.prom_expr_to_plan(input).await?,
+                    // `vector()` also accepts a number literal
+                    None if func.name == "vector" && !args.literals.is_empty() => {
+                        return self.create_empty_metric_plan(args.literals[0].clone());
+                    }
+                    None => ExpectExprSnafu {
                         expr: prom_expr.clone(),
-                    })?)
-                    .await?;
-                let mut func_exprs = self.create_function_expr(func, args.literals)?;
-                func_exprs.insert(0, self.create_time_index_column_expr()?);
-                func_exprs.extend_from_slice(&self.create_tag_column_exprs()?);
+                    }
+                    .fail()?,
+                };
 
-                LogicalPlanBuilder::from(input)
-                    .project(func_exprs)
-                    .context(DataFusionPlanningSnafu)?
-                    .filter(self.create_empty_values_filter_expr()?)
-                    .context(DataFusionPlanningSnafu)?
-                    .build()
-                    .context(DataFusionPlanningSnafu)?
+                match func.name {
+                    "histogram_quantile" => {
+                        self.create_histogram_quantile_plan(input, args.literals)?
+                    }
+                    "label_replace" => self.create_label_replace_plan(input, args.literals)?,
+                    "label_join" => self.create_label_join_plan(input, args.literals)?,
+                    "sort" => self.create_sort_plan(input, true)?,
+                    "sort_desc" => self.create_sort_plan(input, false)?,
+                    "clamp" | "clamp_min" | "clamp_max" => {
+                        self.create_clamp_plan(input, func.name, args.literals)?
+                    }
+                    "timestamp" => {
+                        let time_index = self.create_time_index_column_expr()?;
+                        self.projection_for_each_field_column(input, |_| {
+                            Ok(DfExpr::Cast(Cast {
+                                expr: Box::new(DfExpr::Cast(Cast {
+                                    expr: Box::new(time_index.clone()),
+                                    data_type: ArrowDataType::Int64,
+                                })),
+                                data_type: ArrowDataType::Float64,
+                            }) / df_prelude::lit(1000.0))
+                        })?
+                    }
+                    "absent" => self.create_absent_plan(Some(input), absent_labels)?,
+                    "scalar" => self.create_scalar_plan(input)?,
+                    // the input is already a vector without labels
+                    "vector" => input,
+                    _ => {
+                        let mut func_exprs = self.create_function_expr(func, args.literals)?;
+                        func_exprs.insert(0, self.create_time_index_column_expr()?);
+                        func_exprs.extend_from_slice(&self.create_tag_column_exprs()?);
+
+                        LogicalPlanBuilder::from(input)
+                            .project(func_exprs)
+                            .context(DataFusionPlanningSnafu)?
+                            .filter(self.create_empty_values_filter_expr()?)
+                            .context(DataFusionPlanningSnafu)?
+                            .build()
+                            .context(DataFusionPlanningSnafu)?
+                    }
+                }
             }
             PromExpr::Extension(promql_parser::parser::ast::Extension { expr }) => {
                 let children = expr.children();
@@ -513,6 +675,165 @@ impl PromPlanner {
         Ok(Matchers { matchers })
     }
 
+    /// Resolve the evaluation time pinned by `@` modifier.
+    fn pinned_time(&self, at: &Option<AtModifier>) -> Option<Millisecond> {
+        match at.as_ref()? {
+            AtModifier::Start => Some(self.ctx.query_start),
+            AtModifier::End => Some(self.ctx.query_end),
+            AtModifier::At(time) => Some(match time.duration_since(UNIX_EPOCH) {
+                Ok(duration) => duration.as_millis() as Millisecond,
+                // `@` accepts timestamps before epoch
+                Err(e) => -(e.duration().as_millis() as Millisecond),
+            }),
+        }
+    }
+
+    /// Split the `@` modifier from a vector selector, or from the range vector argument of a
+    /// function call. Returns the expr without `@` modifier and the pinned evaluation time.
+    fn split_at_modifier(&self, expr: &PromExpr) -> Option<(PromExpr, Millisecond)> {
+        match expr {
+            PromExpr::VectorSelector(selector) => {
+                let pinned_time = self.pinned_time(&selector.at)?;
+                let mut selector = selector.clone();
+                selector.at = None;
+                Some((PromExpr::VectorSelector(selector), pinned_time))
+            }
+            PromExpr::Call(call) => {
+                let mut call = call.clone();
+                let pinned_time = call
+                    .args
+                    .args
+                    .iter_mut()
+                    .find_map(|arg| match arg.as_mut() {
+                        PromExpr::MatrixSelector(MatrixSelector { vs, .. }) => {
+                            let pinned_time = self.pinned_time(&vs.at)?;
+                            vs.at = None;
+                            Some(pinned_time)
+                        }
+                        PromExpr::Subquery(subquery) => {
+                            let pinned_time = self.pinned_time(&subquery.at)?;
+                            subquery.at = None;
+                            Some(pinned_time)
+                        }
+                        _ => None,
+                    })?;
+                Some((PromExpr::Call(call), pinned_time))
+            }
+            _ => None,
+        }
+    }
+
+    /// Broadcast the result evaluated at a pinned time to every step of the query, by
+    /// replacing its time index with the steps.
+    ///
+    /// # Side effect
+    ///
+    /// This method may reset the table name in context.
+    fn create_pinned_broadcast_plan(&mut self, input: LogicalPlan) -> Result<LogicalPlan> {
+        let time_index = self
+            .ctx
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "unknown" })?;
+        let steps = LogicalPlan::Extension(Extension {
+            node: Arc::new(
+                EmptyMetric::new(
+                    self.ctx.start,
+                    self.ctx.end,
+                    self.ctx.interval,
+                    PINNED_STEP_TIME.to_string(),
+                    PINNED_STEP_VALUE.to_string(),
+                    df_prelude::lit(true),
+                )
+                .context(DataFusionPlanningSnafu)?,
+            ),
+        });
+
+        let exprs = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| {
+                if field.name() == &time_index {
+                    DfExpr::Column(Column::from_name(PINNED_STEP_TIME)).alias(&time_index)
+                } else {
+                    DfExpr::Column(field.qualified_column())
+                }
+            })
+            .collect::<Vec<_>>();
+        let builder = LogicalPlanBuilder::from(input)
+            .cross_join(steps)
+            .context(DataFusionPlanningSnafu)?
+            .project(exprs)
+            .context(DataFusionPlanningSnafu)?;
+        self.qualify_by_table_name(builder)
+    }
+
+    fn offset_to_millis(offset: &Option<Offset>) -> Millisecond {
+        match offset {
+            Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
+            Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
+            None => 0,
+        }
+    }
+
+    /// Fold the result of a subquery's inner expr into ranges of the outer query, like
+    /// what is done to a matrix selector. The inner result is divided into series and
+    /// biased by `offset` first.
+    ///
+    /// # Side effect
+    ///
+    /// This method will set the range in context.
+    fn create_subquery_plan(
+        &mut self,
+        input: LogicalPlan,
+        offset: Millisecond,
+        range: Millisecond,
+    ) -> Result<LogicalPlan> {
+        let time_index = self
+            .ctx
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "unknown" })?;
+        let tag_columns = self.existing_tag_columns(input.schema());
+        let sort_exprs = tag_columns
+            .iter()
+            .chain(Some(&time_index))
+            .map(|col| DfExpr::Column(Column::from_name(col)).sort(false, false));
+        let sort_plan = LogicalPlanBuilder::from(input)
+            .sort(sort_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        let divide_plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(SeriesDivide::new(tag_columns, sort_plan)),
+        });
+        let normalize_plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(SeriesNormalize::new(
+                offset,
+                time_index.clone(),
+                false,
+                divide_plan,
+            )),
+        });
+        let manipulate = RangeManipulate::new(
+            self.ctx.start,
+            self.ctx.end,
+            self.ctx.interval,
+            range,
+            time_index,
+            self.ctx.field_columns.clone(),
+            normalize_plan,
+        )
+        .context(DataFusionPlanningSnafu)?;
+        self.ctx.range = Some(range);
+
+        Ok(LogicalPlan::Extension(Extension {
+            node: Arc::new(manipulate),
+        }))
+    }
+
     async fn selector_to_series_normalize_plan(
         &mut self,
         offset: &Option<Offset>,
@@ -522,11 +843,7 @@ impl PromPlanner {
         let table_name = self.ctx.table_name.clone().unwrap();
 
         // make filter exprs
-        let offset_duration = match offset {
-            Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
-            Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
-            None => 0,
-        };
+        let offset_duration = Self::offset_to_millis(offset);
         let range_ms = self.ctx.range.unwrap_or_default();
         let mut scan_filters = self.matchers_to_expr(label_matchers.clone())?;
         scan_filters.push(self.create_time_index_column_expr()?.gt_eq(DfExpr::Literal(
@@ -1065,12 +1382,12 @@ impl PromPlanner {
             token::T_GROUP => AggregateFunctionEnum::Grouping,
             token::T_STDDEV => AggregateFunctionEnum::StddevPop,
             token::T_STDVAR => AggregateFunctionEnum::VariancePop,
-            token::T_TOPK | token::T_BOTTOMK | token::T_COUNT_VALUES | token::T_QUANTILE => {
-                UnsupportedExprSnafu {
-                    name: format!("{op:?}"),
-                }
-                .fail()?
+            // values are collected and then calculated by `prom_quantile`
+            token::T_QUANTILE => AggregateFunctionEnum::ArrayAgg,
+            token::T_TOPK | token::T_BOTTOMK | token::T_COUNT_VALUES => UnsupportedExprSnafu {
+                name: format!("{op:?}"),
             }
+            .fail()?,
             _ => UnexpectedTokenSnafu { token: op }.fail()?,
         };
 
@@ -1102,129 +1419,1338 @@ impl PromPlanner {
         Ok(exprs)
     }
 
-    /// Try to build a DataFusion Literal Expression from PromQL Expr, return
-    /// `None` if the input is not a literal expression.
-    fn try_build_literal_expr(expr: &PromExpr) -> Option<DfExpr> {
-        match expr {
-            PromExpr::NumberLiteral(NumberLiteral { val }) => {
-                let scalar_value = ScalarValue::Float64(Some(*val));
-                Some(DfExpr::Literal(scalar_value))
-            }
-            PromExpr::StringLiteral(StringLiteral { val }) => {
-                let scalar_value = ScalarValue::Utf8(Some(val.to_string()));
-                Some(DfExpr::Literal(scalar_value))
-            }
-            PromExpr::VectorSelector(_)
-            | PromExpr::MatrixSelector(_)
-            | PromExpr::Call(_)
-            | PromExpr::Extension(_)
-            | PromExpr::Aggregate(_)
-            | PromExpr::Subquery(_) => None,
-            PromExpr::Paren(ParenExpr { expr }) => Self::try_build_literal_expr(expr),
-            // TODO(ruihang): support Unary operator
-            PromExpr::Unary(UnaryExpr { expr, .. }) => Self::try_build_literal_expr(expr),
-            PromExpr::Binary(PromBinaryExpr {
-                lhs,
-                rhs,
-                op,
-                modifier,
-            }) => {
-                let lhs = Self::try_build_literal_expr(lhs)?;
-                let rhs = Self::try_build_literal_expr(rhs)?;
-                let is_comparison_op = Self::is_token_a_comparison_op(*op);
-                let expr_builder = Self::prom_token_to_binary_expr_builder(*op).ok()?;
-                let expr = expr_builder(lhs, rhs).ok()?;
-
-                let should_return_bool = if let Some(m) = modifier {
-                    m.return_bool
-                } else {
-                    false
-                };
-                if is_comparison_op && should_return_bool {
-                    Some(DfExpr::Cast(Cast {
-                        expr: Box::new(expr),
-                        data_type: ArrowDataType::Float64,
-                    }))
-                } else {
-                    Some(expr)
-                }
+    /// Create the group by exprs of an aggregation. Time index column is always included.
+    ///
+    /// # Side effect
+    ///
+    /// This method will change the tag columns in ctx to the grouped ones.
+    fn create_group_exprs(
+        &mut self,
+        input: &LogicalPlan,
+        modifier: &Option<LabelModifier>,
+    ) -> Result<Vec<DfExpr>> {
+        match modifier {
+            Some(modifier) => self.agg_modifier_to_col(input.schema(), modifier),
+            None => {
+                self.ctx.tag_columns.clear();
+                Ok(vec![self.create_time_index_column_expr()?])
             }
         }
     }
 
-    /// Return a lambda to build binary expression from token.
-    /// Because some binary operator are function in DataFusion like `atan2` or `^`.
-    #[allow(clippy::type_complexity)]
-    fn prom_token_to_binary_expr_builder(
-        token: TokenType,
-    ) -> Result<Box<dyn Fn(DfExpr, DfExpr) -> Result<DfExpr>>> {
-        match token.id() {
-            token::T_ADD => Ok(Box::new(|lhs, rhs| Ok(lhs + rhs))),
-            token::T_SUB => Ok(Box::new(|lhs, rhs| Ok(lhs - rhs))),
-            token::T_MUL => Ok(Box::new(|lhs, rhs| Ok(lhs * rhs))),
-            token::T_DIV => Ok(Box::new(|lhs, rhs| Ok(lhs / rhs))),
-            token::T_MOD => Ok(Box::new(|lhs: DfExpr, rhs| Ok(lhs % rhs))),
-            token::T_EQLC => Ok(Box::new(|lhs, rhs| Ok(lhs.eq(rhs)))),
-            token::T_NEQ => Ok(Box::new(|lhs, rhs| Ok(lhs.not_eq(rhs)))),
-            token::T_GTR => Ok(Box::new(|lhs, rhs| Ok(lhs.gt(rhs)))),
-            token::T_LSS => Ok(Box::new(|lhs, rhs| Ok(lhs.lt(rhs)))),
-            token::T_GTE => Ok(Box::new(|lhs, rhs| Ok(lhs.gt_eq(rhs)))),
-            token::T_LTE => Ok(Box::new(|lhs, rhs| Ok(lhs.lt_eq(rhs)))),
-            token::T_POW => Ok(Box::new(|lhs, rhs| {
-                Ok(DfExpr::ScalarFunction(ScalarFunction {
-                    fun: BuiltinScalarFunction::Power,
-                    args: vec![lhs, rhs],
-                }))
-            })),
-            token::T_ATAN2 => Ok(Box::new(|lhs, rhs| {
-                Ok(DfExpr::ScalarFunction(ScalarFunction {
-                    fun: BuiltinScalarFunction::Atan2,
-                    args: vec![lhs, rhs],
-                }))
-            })),
-            _ => UnexpectedTokenSnafu { token }.fail(),
+    /// Get the numeric parameter of aggregate operators like `topk(k, v)`
+    /// and `quantile(φ, v)`.
+    fn aggregate_param_f64(op: TokenType, param: &Option<Box<PromExpr>>) -> Result<f64> {
+        match param.as_deref().and_then(Self::try_build_literal_expr) {
+            Some(DfExpr::Literal(ScalarValue::Float64(Some(val)))) => Ok(val),
+            other => UnexpectedPlanExprSnafu {
+                desc: format!("expect f64 literal as parameter of {op:?}, but found {other:?}"),
+            }
+            .fail(),
         }
     }
 
-    /// Check if the given op is a [comparison operator](https://prometheus.io/docs/prometheus/latest/querying/operators/#comparison-binary-operators).
-    fn is_token_a_comparison_op(token: TokenType) -> bool {
-        matches!(
-            token.id(),
-            token::T_EQLC
-                | token::T_NEQ
-                | token::T_GTR
-                | token::T_LSS
-                | token::T_GTE
-                | token::T_LTE
-        )
-    }
-
-    /// Build a inner join on time index column and tag columns to concat two logical plans.
-    fn join_on_non_field_columns(
-        &self,
-        left: LogicalPlan,
-        right: LogicalPlan,
+    /// Create a plan for `topk(k, v)` and `bottomk(k, v)`, which keep at most `k` samples
+    /// with the largest (smallest) values at each timestamp of every group. Unlike other
+    /// aggregations, the selected samples keep all their labels. NaN values are ranked last.
+    fn create_topk_plan(
+        &mut self,
+        input: LogicalPlan,
+        op: TokenType,
+        param: &Option<Box<PromExpr>>,
+        modifier: &Option<LabelModifier>,
     ) -> Result<LogicalPlan> {
-        let mut tag_columns = self
-            .ctx
-            .tag_columns
+        let k = Self::aggregate_param_f64(op, param)?;
+        ensure!(
+            self.ctx.field_columns.len() == 1,
+            UnsupportedExprSnafu {
+                name: format!("{op:?} on multi-value input"),
+            }
+        );
+
+        // the output series are the same as the input's, so keep tag columns unchanged
+        let tag_columns = self.ctx.tag_columns.clone();
+        let partition_exprs = self.create_group_exprs(&input, modifier)?;
+        self.ctx.tag_columns = tag_columns;
+
+        let output_exprs = input
+            .schema()
+            .fields()
             .iter()
-            .map(Column::from_name)
+            .map(|field| DfExpr::Column(field.qualified_column()))
             .collect::<Vec<_>>();
+        let field = DfExpr::Column(Column::from_name(&self.ctx.field_columns[0]));
+        let is_nan = DfExpr::ScalarFunction(ScalarFunction {
+            fun: BuiltinScalarFunction::Isnan,
+            args: vec![field.clone()],
+        });
+        let asc = op.id() == token::T_BOTTOMK;
+        let rank = DfExpr::WindowFunction(WindowFunction {
+            fun: WindowFunctionEnum::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            args: vec![],
+            partition_by: partition_exprs,
+            order_by: vec![is_nan.sort(true, false), field.sort(asc, false)],
+            window_frame: WindowFrame::new(true),
+        });
+        let rank_column = rank.display_name().context(DataFusionPlanningSnafu)?;
 
-        // push time index column if it exist
-        if let Some(time_index_column) = &self.ctx.time_index_column {
-            tag_columns.push(Column::from_name(time_index_column));
-        }
+        // `k` is truncated like Prometheus, and nothing is selected if it's less than 1
+        let k = k as i64;
+        let predicate = if k < 1 {
+            df_prelude::lit(false)
+        } else {
+            DfExpr::Column(Column::from_name(rank_column)).lt_eq(df_prelude::lit(k as u64))
+        };
 
-        // Inner Join on time index column to concat two operator
-        LogicalPlanBuilder::from(left)
-            .join(
-                right,
-                JoinType::Inner,
-                (tag_columns.clone(), tag_columns),
-                None,
-            )
+        let plan = LogicalPlanBuilder::from(input)
+            .window(vec![rank])
+            .context(DataFusionPlanningSnafu)?
+            .filter(predicate)
+            .context(DataFusionPlanningSnafu)?
+            .project(output_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        self.create_sort_plan(plan, asc)
+    }
+
+    /// Create a plan for `quantile(φ, v)`. Values of each group are collected into a list
+    /// and the φ-quantile is calculated by [Quantile].
+    ///
+    /// # Side effect
+    ///
+    /// This method will update tag columns and value columns in context.
+    fn create_quantile_plan(
+        &mut self,
+        input: LogicalPlan,
+        op: TokenType,
+        param: &Option<Box<PromExpr>>,
+        modifier: &Option<LabelModifier>,
+    ) -> Result<LogicalPlan> {
+        let quantile = Self::aggregate_param_f64(op, param)?;
+        let group_exprs = self.create_group_exprs(&input, modifier)?;
+        let aggr_exprs = self.create_aggregate_exprs(op, &input)?;
+        let group_sort_expr = group_exprs
+            .clone()
+            .into_iter()
+            .map(|expr| expr.sort(true, false));
+
+        let aggregated = LogicalPlanBuilder::from(input)
+            .aggregate(group_exprs, aggr_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        let quantile_udf = Arc::new(Quantile::scalar_udf(quantile));
+        let mut plan = self.projection_for_each_field_column(aggregated, |col| {
+            Ok(DfExpr::ScalarUDF(ScalarUDF {
+                fun: quantile_udf.clone(),
+                args: vec![DfExpr::Column(Column::from_name(col))],
+            }))
+        })?;
+        plan = LogicalPlanBuilder::from(plan)
+            .sort(group_sort_expr)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        Ok(plan)
+    }
+
+    /// Create a plan for `count_values("label", v)`, which counts the samples with the
+    /// same value at each timestamp of every group. The value is exposed as the `label`
+    /// tag of output series.
+    ///
+    /// # Side effect
+    ///
+    /// This method will update tag columns and value columns in context.
+    fn create_count_values_plan(
+        &mut self,
+        input: LogicalPlan,
+        param: &Option<Box<PromExpr>>,
+        modifier: &Option<LabelModifier>,
+    ) -> Result<LogicalPlan> {
+        let label = match param.as_deref().and_then(Self::try_build_literal_expr) {
+            Some(DfExpr::Literal(ScalarValue::Utf8(Some(label)))) => label,
+            other => UnexpectedPlanExprSnafu {
+                desc: format!(
+                    "expect string literal as parameter of count_values, but found {other:?}"
+                ),
+            }
+            .fail()?,
+        };
+        ensure!(
+            self.ctx.field_columns.len() == 1,
+            UnsupportedExprSnafu {
+                name: "count_values on multi-value input"
+            }
+        );
+        let field = DfExpr::Column(Column::from_name(&self.ctx.field_columns[0]));
+
+        // the value label overrides the tag with the same name
+        let mut group_exprs = self.create_group_exprs(&input, modifier)?;
+        group_exprs.retain(|expr| !matches!(expr, DfExpr::Column(col) if col.name == label));
+        self.ctx.tag_columns.retain(|tag| tag != &label);
+        self.ctx.tag_columns.push(label.clone());
+        let label_expr = DfExpr::ScalarUDF(ScalarUDF {
+            fun: Arc::new(CountValuesLabel::scalar_udf()),
+            args: vec![field.clone()],
+        })
+        .alias(&label);
+        // insert before the time index column
+        group_exprs.insert(group_exprs.len() - 1, label_expr);
+
+        let count = DfExpr::AggregateFunction(AggregateFunction {
+            fun: AggregateFunctionEnum::Count,
+            args: vec![field.clone()],
+            distinct: false,
+            filter: None,
+            order_by: None,
+        });
+        let count_column = normalize_cols(vec![count.clone()], &input)
+            .context(DataFusionPlanningSnafu)?
+            .remove(0)
+            .display_name()
+            .context(DataFusionPlanningSnafu)?;
+
+        let aggregated = LogicalPlanBuilder::from(input)
+            .filter(field.is_not_null())
+            .context(DataFusionPlanningSnafu)?
+            .aggregate(group_exprs, vec![count])
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        // output columns are tags, value label and time index, followed by the count
+        let mut project_exprs = Vec::with_capacity(aggregated.schema().fields().len());
+        let mut sort_exprs = Vec::with_capacity(aggregated.schema().fields().len());
+        for df_field in aggregated.schema().fields() {
+            let column = DfExpr::Column(df_field.qualified_column());
+            if df_field.name() == &count_column {
+                project_exprs.push(
+                    DfExpr::Cast(Cast {
+                        expr: Box::new(column),
+                        data_type: ArrowDataType::Float64,
+                    })
+                    .alias(&count_column),
+                );
+            } else {
+                sort_exprs.push(column.clone().sort(true, false));
+                project_exprs.push(column);
+            }
+        }
+
+        self.ctx.field_columns = vec![count_column];
+
+        LogicalPlanBuilder::from(aggregated)
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .sort(sort_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Build an [EmptyMetric] plan whose value column is computed by `field_expr`.
+    ///
+    /// # Side effect
+    ///
+    /// This method will reset the context to the schema of the generated plan.
+    fn create_empty_metric_plan(&mut self, field_expr: DfExpr) -> Result<LogicalPlan> {
+        self.ctx.time_index_column = Some(DEFAULT_TIME_INDEX_COLUMN.to_string());
+        self.ctx.field_columns = vec![DEFAULT_FIELD_COLUMN.to_string()];
+        self.ctx.tag_columns = vec![];
+        self.ctx.table_name = Some(String::new());
+
+        Ok(LogicalPlan::Extension(Extension {
+            node: Arc::new(
+                EmptyMetric::new(
+                    self.ctx.start,
+                    self.ctx.end,
+                    self.ctx.interval,
+                    SPECIAL_TIME_FUNCTION.to_string(),
+                    DEFAULT_FIELD_COLUMN.to_string(),
+                    field_expr,
+                )
+                .context(DataFusionPlanningSnafu)?,
+            ),
+        }))
+    }
+
+    /// Tag columns in context that exist in the given schema.
+    fn existing_tag_columns(&self, schema: &DFSchemaRef) -> Vec<String> {
+        self.ctx
+            .tag_columns
+            .iter()
+            .filter(|tag| schema.field_with_unqualified_name(tag).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    /// Create a [HistogramFold] plan for `histogram_quantile(φ, buckets)`. Buckets are
+    /// identified by the `le` tag, and they are folded along other tags and time index.
+    ///
+    /// # Side effect
+    ///
+    /// This method will remove the `le` tag from context.
+    fn create_histogram_quantile_plan(
+        &mut self,
+        input: LogicalPlan,
+        literals: Vec<DfExpr>,
+    ) -> Result<LogicalPlan> {
+        let quantile = match literals.first() {
+            Some(DfExpr::Literal(ScalarValue::Float64(Some(quantile)))) => *quantile,
+            other => UnexpectedPlanExprSnafu {
+                desc: format!("expect f64 literal as quantile, but found {:?}", other),
+            }
+            .fail()?,
+        };
+        ensure!(
+            input
+                .schema()
+                .field_with_unqualified_name(LE_COLUMN_NAME)
+                .is_ok(),
+            ColumnNotFoundSnafu {
+                col: LE_COLUMN_NAME
+            }
+        );
+        ensure!(
+            self.ctx.field_columns.len() == 1,
+            UnsupportedExprSnafu {
+                name: "histogram_quantile on multi-value input"
+            }
+        );
+
+        self.ctx.tag_columns.retain(|tag| tag != LE_COLUMN_NAME);
+        let tag_columns = self.existing_tag_columns(input.schema());
+        let time_index = self
+            .ctx
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "unknown" })?;
+        let sort_exprs = tag_columns
+            .iter()
+            .chain(Some(&time_index))
+            .map(|col| DfExpr::Column(Column::from_name(col)).sort(true, false));
+        let sorted_input = LogicalPlanBuilder::from(input)
+            .sort(sort_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        let fold = HistogramFold::new(
+            LE_COLUMN_NAME.to_string(),
+            tag_columns,
+            time_index,
+            self.ctx.field_columns[0].clone(),
+            quantile,
+            sorted_input,
+        )
+        .context(DataFusionPlanningSnafu)?;
+        Ok(LogicalPlan::Extension(Extension {
+            node: Arc::new(fold),
+        }))
+    }
+
+    /// Create a plan for `label_replace(v, dst, replacement, src, regex)`. If `regex` matches
+    /// the whole value of `src`, `dst` is set to `replacement` with `$1`-style references to
+    /// the capture groups expanded. Otherwise `dst` is kept unchanged.
+    ///
+    /// # Side effect
+    ///
+    /// This method may add the `dst` tag into context.
+    fn create_label_replace_plan(
+        &mut self,
+        input: LogicalPlan,
+        literals: Vec<DfExpr>,
+    ) -> Result<LogicalPlan> {
+        let args = Self::string_literal_args("label_replace", literals)?;
+        let [dst, replacement, src, regex] = args.as_slice() else {
+            return UnexpectedPlanExprSnafu {
+                desc: format!(
+                    "label_replace expects 4 string arguments, but found {}",
+                    args.len()
+                ),
+            }
+            .fail();
+        };
+
+        let src_expr = self.label_value_expr(input.schema(), src);
+        // the regex in `label_replace` is fully anchored
+        let regex = df_prelude::lit(format!("^(?:{regex})$"));
+        let is_matched = DfExpr::BinaryExpr(BinaryExpr {
+            left: Box::new(src_expr.clone()),
+            op: Operator::RegexMatch,
+            right: Box::new(regex.clone()),
+        });
+        let replaced = DfExpr::ScalarFunction(ScalarFunction {
+            fun: BuiltinScalarFunction::RegexpReplace,
+            args: vec![src_expr, regex, df_prelude::lit(replacement.clone())],
+        });
+        let dst_expr = df_prelude::when(is_matched, replaced)
+            .otherwise(self.label_value_expr(input.schema(), dst))
+            .context(DataFusionPlanningSnafu)?;
+
+        self.projection_with_label(input, dst.clone(), dst_expr)
+    }
+
+    /// Create a plan for `label_join(v, dst, separator, src_1, src_2, ...)`, which sets
+    /// `dst` to the values of all `src` joined by `separator`.
+    ///
+    /// # Side effect
+    ///
+    /// This method may add the `dst` tag into context.
+    fn create_label_join_plan(
+        &mut self,
+        input: LogicalPlan,
+        literals: Vec<DfExpr>,
+    ) -> Result<LogicalPlan> {
+        let args = Self::string_literal_args("label_join", literals)?;
+        let [dst, separator, srcs @ ..] = args.as_slice() else {
+            return UnexpectedPlanExprSnafu {
+                desc: format!(
+                    "label_join expects at least 2 string arguments, but found {}",
+                    args.len()
+                ),
+            }
+            .fail();
+        };
+
+        let dst_expr = if srcs.is_empty() {
+            df_prelude::lit("")
+        } else {
+            let mut concat_args = vec![df_prelude::lit(separator.clone())];
+            concat_args.extend(
+                srcs.iter()
+                    .map(|src| self.label_value_expr(input.schema(), src)),
+            );
+            DfExpr::ScalarFunction(ScalarFunction {
+                fun: BuiltinScalarFunction::ConcatWithSeparator,
+                args: concat_args,
+            })
+        };
+
+        self.projection_with_label(input, dst.clone(), dst_expr)
+    }
+
+    fn string_literal_args(func_name: &str, literals: Vec<DfExpr>) -> Result<Vec<String>> {
+        literals
+            .into_iter()
+            .map(|expr| match expr {
+                DfExpr::Literal(ScalarValue::Utf8(Some(value))) => Ok(value),
+                other => UnexpectedPlanExprSnafu {
+                    desc: format!(
+                        "expect string literal as argument of {func_name}, but found {:?}",
+                        other
+                    ),
+                }
+                .fail(),
+            })
+            .collect()
+    }
+
+    /// Expression of a label's value. Missing labels are treated as empty strings, like
+    /// Prometheus does.
+    fn label_value_expr(&self, schema: &DFSchemaRef, label: &str) -> DfExpr {
+        if label == METRIC_NAME {
+            return df_prelude::lit(self.ctx.table_name.clone().unwrap_or_default());
+        }
+        if schema.field_with_unqualified_name(label).is_err() {
+            return df_prelude::lit("");
+        }
+        DfExpr::ScalarFunction(ScalarFunction {
+            fun: BuiltinScalarFunction::Coalesce,
+            args: vec![
+                DfExpr::Column(Column::from_name(label)),
+                df_prelude::lit(""),
+            ],
+        })
+    }
+
+    /// Build a projection that sets (or adds) tag `label` to the value of `expr`. An empty
+    /// value is turned into NULL, as empty labels are the same as missing ones.
+    ///
+    /// # Side effect
+    ///
+    /// This method will add the `label` tag into context if it doesn't exist.
+    fn projection_with_label(
+        &mut self,
+        input: LogicalPlan,
+        label: String,
+        expr: DfExpr,
+    ) -> Result<LogicalPlan> {
+        let label_expr = DfExpr::ScalarFunction(ScalarFunction {
+            fun: BuiltinScalarFunction::NullIf,
+            args: vec![expr, df_prelude::lit("")],
+        })
+        .alias(&label);
+
+        let mut tag_columns = self.existing_tag_columns(input.schema());
+        tag_columns.retain(|tag| *tag != label);
+        let mut exprs = vec![self.create_time_index_column_expr()?];
+        exprs.extend(
+            tag_columns
+                .iter()
+                .map(|tag| DfExpr::Column(Column::from_name(tag))),
+        );
+        exprs.push(label_expr);
+        exprs.extend(
+            self.ctx
+                .field_columns
+                .iter()
+                .map(|field| DfExpr::Column(Column::from_name(field))),
+        );
+
+        tag_columns.push(label);
+        self.ctx.tag_columns = tag_columns;
+
+        LogicalPlanBuilder::from(input)
+            .project(exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Create a plan for `sort()` and `sort_desc()`, which orders samples at each timestamp
+    /// by their values. NaN and NULL values are always placed last.
+    fn create_sort_plan(&self, input: LogicalPlan, asc: bool) -> Result<LogicalPlan> {
+        let mut sort_exprs = vec![self.create_time_index_column_expr()?.sort(true, false)];
+        sort_exprs.extend(
+            self.ctx
+                .field_columns
+                .iter()
+                .map(|field| DfExpr::Column(Column::from_name(field)).sort(asc, false)),
+        );
+
+        LogicalPlanBuilder::from(input)
+            .sort(sort_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Create a plan for `clamp(v, min, max)`, `clamp_min(v, min)` and `clamp_max(v, max)`.
+    fn create_clamp_plan(
+        &mut self,
+        input: LogicalPlan,
+        func_name: &str,
+        literals: Vec<DfExpr>,
+    ) -> Result<LogicalPlan> {
+        let mut bounds = literals
+            .into_iter()
+            .map(|expr| match expr {
+                DfExpr::Literal(ScalarValue::Float64(Some(bound))) => Ok(bound),
+                other => UnexpectedPlanExprSnafu {
+                    desc: format!("expect f64 literal as bound, but found {:?}", other),
+                }
+                .fail(),
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter();
+        let (min, max) = match func_name {
+            "clamp" => (bounds.next(), bounds.next()),
+            "clamp_min" => (bounds.next(), None),
+            _ => (None, bounds.next()),
+        };
+
+        // Prometheus returns an empty vector if min is greater than max
+        if matches!((min, max), (Some(min), Some(max)) if min > max) {
+            return LogicalPlanBuilder::from(input)
+                .filter(df_prelude::lit(false))
+                .context(DataFusionPlanningSnafu)?
+                .build()
+                .context(DataFusionPlanningSnafu);
+        }
+
+        self.projection_for_each_field_column(input, |col| {
+            let col = DfExpr::Column(col.into());
+            let clamped = match (min, max) {
+                (Some(min), Some(max)) => {
+                    df_prelude::when(col.clone().lt(df_prelude::lit(min)), df_prelude::lit(min))
+                        .when(col.clone().gt(df_prelude::lit(max)), df_prelude::lit(max))
+                        .otherwise(col)
+                }
+                (Some(min), None) => {
+                    df_prelude::when(col.clone().lt(df_prelude::lit(min)), df_prelude::lit(min))
+                        .otherwise(col)
+                }
+                (None, Some(max)) => {
+                    df_prelude::when(col.clone().gt(df_prelude::lit(max)), df_prelude::lit(max))
+                        .otherwise(col)
+                }
+                (None, None) => return Ok(col),
+            };
+            clamped.context(DataFusionPlanningSnafu)
+        })
+    }
+
+    /// Collect the labels that `absent()` attaches to its output, which are the equality
+    /// matchers in the selector of its argument. Labels that are matched more than once
+    /// are dropped.
+    fn absent_labels(args: &[Box<PromExpr>]) -> Vec<(String, String)> {
+        let Some(matchers) = args.iter().find_map(|arg| match arg.as_ref() {
+            PromExpr::VectorSelector(vs) => Some(&vs.matchers),
+            PromExpr::MatrixSelector(MatrixSelector { vs, .. }) => Some(&vs.matchers),
+            _ => None,
+        }) else {
+            return vec![];
+        };
+
+        let mut labels: Vec<(String, String)> = Vec::new();
+        let mut duplicated = HashSet::new();
+        for matcher in &matchers.matchers {
+            if matcher.name == METRIC_NAME || matcher.name == FIELD_COLUMN_MATCHER {
+                continue;
+            }
+            if labels.iter().any(|(name, _)| *name == matcher.name) {
+                let _ = duplicated.insert(matcher.name.clone());
+            } else if matches!(matcher.op, MatchOp::Equal) {
+                labels.push((matcher.name.clone(), matcher.value.clone()));
+            }
+        }
+        labels.retain(|(name, _)| !duplicated.contains(name));
+        labels
+    }
+
+    /// Create a plan for `absent()`. It generates value 1 at the timestamps where the input
+    /// has no sample, with the given labels. `None` input means the metric doesn't exist.
+    ///
+    /// # Side effect
+    ///
+    /// This method will reset the context to the schema of the generated plan.
+    fn create_absent_plan(
+        &mut self,
+        input: Option<LogicalPlan>,
+        labels: Vec<(String, String)>,
+    ) -> Result<LogicalPlan> {
+        let present_timestamps = match input {
+            Some(input) => Some(
+                LogicalPlanBuilder::from(input)
+                    .project(vec![self
+                        .create_time_index_column_expr()?
+                        .alias(SAMPLE_TIME)])
+                    .context(DataFusionPlanningSnafu)?
+                    .distinct()
+                    .context(DataFusionPlanningSnafu)?
+                    .build()
+                    .context(DataFusionPlanningSnafu)?,
+            ),
+            None => None,
+        };
+
+        let empty_metric = self.create_empty_metric_plan(df_prelude::lit(1.0))?;
+        let time_index = Column::new(Some(String::new()), DEFAULT_TIME_INDEX_COLUMN);
+        let mut exprs = vec![
+            DfExpr::Column(time_index.clone()),
+            DfExpr::Column(Column::new(Some(String::new()), DEFAULT_FIELD_COLUMN)),
+        ];
+        exprs.extend(
+            labels
+                .iter()
+                .map(|(name, value)| df_prelude::lit(value.clone()).alias(name)),
+        );
+        self.ctx.tag_columns = labels.into_iter().map(|(name, _)| name).collect();
+
+        let mut builder = LogicalPlanBuilder::from(empty_metric);
+        if let Some(present_timestamps) = present_timestamps {
+            builder = builder
+                .join(
+                    present_timestamps,
+                    JoinType::LeftAnti,
+                    (vec![time_index], vec![Column::from_name(SAMPLE_TIME)]),
+                    None,
+                )
+                .context(DataFusionPlanningSnafu)?;
+        }
+        builder
+            .project(exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Create a plan for `scalar()`. At each timestamp, the result is the sample value if
+    /// the input has exactly one sample, or NaN otherwise.
+    ///
+    /// # Side effect
+    ///
+    /// This method will reset the context to the schema of the generated plan.
+    fn create_scalar_plan(&mut self, input: LogicalPlan) -> Result<LogicalPlan> {
+        ensure!(
+            self.ctx.field_columns.len() == 1,
+            UnsupportedExprSnafu {
+                name: "scalar on multi-value input"
+            }
+        );
+        let input_time_index = self.create_time_index_column_expr()?;
+        let field = DfExpr::Column(Column::from_name(&self.ctx.field_columns[0]));
+        let aggr_exprs = [AggregateFunctionEnum::Count, AggregateFunctionEnum::Max]
+            .into_iter()
+            .zip([SAMPLE_COUNT, SAMPLE_VALUE])
+            .map(|(fun, name)| {
+                DfExpr::AggregateFunction(AggregateFunction {
+                    fun,
+                    args: vec![field.clone()],
+                    distinct: false,
+                    filter: None,
+                    order_by: None,
+                })
+                .alias(name)
+            })
+            .collect::<Vec<_>>();
+        let samples = LogicalPlanBuilder::from(input)
+            .aggregate(vec![input_time_index.alias(SAMPLE_TIME)], aggr_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        // timestamps without any sample are kept by the left join and yield NaN
+        let empty_metric = self.create_empty_metric_plan(df_prelude::lit(f64::NAN))?;
+        let time_index = Column::new(Some(String::new()), DEFAULT_TIME_INDEX_COLUMN);
+        let value = df_prelude::when(
+            DfExpr::Column(Column::from_name(SAMPLE_COUNT)).eq(df_prelude::lit(1_i64)),
+            DfExpr::Column(Column::from_name(SAMPLE_VALUE)),
+        )
+        .otherwise(DfExpr::Column(Column::new(
+            Some(String::new()),
+            DEFAULT_FIELD_COLUMN,
+        )))
+        .context(DataFusionPlanningSnafu)?
+        .alias(DEFAULT_FIELD_COLUMN);
+
+        LogicalPlanBuilder::from(empty_metric)
+            .join(
+                samples,
+                JoinType::Left,
+                (
+                    vec![time_index.clone()],
+                    vec![Column::from_name(SAMPLE_TIME)],
+                ),
+                None,
+            )
+            .context(DataFusionPlanningSnafu)?
+            .project(vec![DfExpr::Column(time_index), value])
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Try to build a DataFusion Literal Expression from PromQL Expr, return
+    /// `None` if the input is not a literal expression.
+    fn try_build_literal_expr(expr: &PromExpr) -> Option<DfExpr> {
+        match expr {
+            PromExpr::NumberLiteral(NumberLiteral { val }) => {
+                let scalar_value = ScalarValue::Float64(Some(*val));
+                Some(DfExpr::Literal(scalar_value))
+            }
+            PromExpr::StringLiteral(StringLiteral { val }) => {
+                let scalar_value = ScalarValue::Utf8(Some(val.to_string()));
+                Some(DfExpr::Literal(scalar_value))
+            }
+            PromExpr::VectorSelector(_)
+            | PromExpr::MatrixSelector(_)
+            | PromExpr::Call(_)
+            | PromExpr::Extension(_)
+            | PromExpr::Aggregate(_)
+            | PromExpr::Subquery(_) => None,
+            PromExpr::Paren(ParenExpr { expr }) => Self::try_build_literal_expr(expr),
+            // TODO(ruihang): support Unary operator
+            PromExpr::Unary(UnaryExpr { expr, .. }) => Self::try_build_literal_expr(expr),
+            PromExpr::Binary(PromBinaryExpr {
+                lhs,
+                rhs,
+                op,
+                modifier,
+            }) => {
+                let lhs = Self::try_build_literal_expr(lhs)?;
+                let rhs = Self::try_build_literal_expr(rhs)?;
+                let is_comparison_op = Self::is_token_a_comparison_op(*op);
+                let expr_builder = Self::prom_token_to_binary_expr_builder(*op).ok()?;
+                let expr = expr_builder(lhs, rhs).ok()?;
+
+                let should_return_bool = if let Some(m) = modifier {
+                    m.return_bool
+                } else {
+                    false
+                };
+                if is_comparison_op && should_return_bool {
+                    Some(DfExpr::Cast(Cast {
+                        expr: Box::new(expr),
+                        data_type: ArrowDataType::Float64,
+                    }))
+                } else {
+                    Some(expr)
+                }
+            }
+        }
+    }
+
+    /// Return a lambda to build binary expression from token.
+    /// Because some binary operator are function in DataFusion like `atan2` or `^`.
+    #[allow(clippy::type_complexity)]
+    fn prom_token_to_binary_expr_builder(
+        token: TokenType,
+    ) -> Result<Box<dyn Fn(DfExpr, DfExpr) -> Result<DfExpr>>> {
+        match token.id() {
+            token::T_ADD => Ok(Box::new(|lhs, rhs| Ok(lhs + rhs))),
+            token::T_SUB => Ok(Box::new(|lhs, rhs| Ok(lhs - rhs))),
+            token::T_MUL => Ok(Box::new(|lhs, rhs| Ok(lhs * rhs))),
+            token::T_DIV => Ok(Box::new(|lhs, rhs| Ok(lhs / rhs))),
+            token::T_MOD => Ok(Box::new(|lhs: DfExpr, rhs| Ok(lhs % rhs))),
+            token::T_EQLC => Ok(Box::new(|lhs, rhs| Ok(lhs.eq(rhs)))),
+            token::T_NEQ => Ok(Box::new(|lhs, rhs| Ok(lhs.not_eq(rhs)))),
+            token::T_GTR => Ok(Box::new(|lhs, rhs| Ok(lhs.gt(rhs)))),
+            token::T_LSS => Ok(Box::new(|lhs, rhs| Ok(lhs.lt(rhs)))),
+            token::T_GTE => Ok(Box::new(|lhs, rhs| Ok(lhs.gt_eq(rhs)))),
+            token::T_LTE => Ok(Box::new(|lhs, rhs| Ok(lhs.lt_eq(rhs)))),
+            token::T_POW => Ok(Box::new(|lhs, rhs| {
+                Ok(DfExpr::ScalarFunction(ScalarFunction {
+                    fun: BuiltinScalarFunction::Power,
+                    args: vec![lhs, rhs],
+                }))
+            })),
+            token::T_ATAN2 => Ok(Box::new(|lhs, rhs| {
+                Ok(DfExpr::ScalarFunction(ScalarFunction {
+                    fun: BuiltinScalarFunction::Atan2,
+                    args: vec![lhs, rhs],
+                }))
+            })),
+            _ => UnexpectedTokenSnafu { token }.fail(),
+        }
+    }
+
+    /// Check if the given op is a [comparison operator](https://prometheus.io/docs/prometheus/latest/querying/operators/#comparison-binary-operators).
+    fn is_token_a_comparison_op(token: TokenType) -> bool {
+        matches!(
+            token.id(),
+            token::T_EQLC
+                | token::T_NEQ
+                | token::T_GTR
+                | token::T_LSS
+                | token::T_GTE
+                | token::T_LTE
+        )
+    }
+
+    /// Check if the given op is a [logical/set operator](https://prometheus.io/docs/prometheus/latest/querying/operators/#logical-set-binary-operators).
+    fn is_token_a_set_op(token: TokenType) -> bool {
+        matches!(token.id(), token::T_LAND | token::T_LOR | token::T_LUNLESS)
+    }
+
+    /// Check if the binary operation specifies matching labels by `on`/`ignoring`, or
+    /// cardinality by `group_left`/`group_right`.
+    fn has_explicit_vector_matching(modifier: &BinModifier) -> bool {
+        modifier.matching.is_some() || !matches!(modifier.card, VectorMatchCardinality::OneToOne)
+    }
+
+    /// Tag columns in the given context that exist in the plan's schema.
+    fn side_tag_columns(input: &LogicalPlan, ctx: &PromPlannerContext) -> Vec<String> {
+        ctx.tag_columns
+            .iter()
+            .filter(|tag| input.schema().field_with_unqualified_name(tag).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    /// Labels that samples of both sides are matched on. All labels are used by default,
+    /// and `on`/`ignoring` selects or excludes some of them.
+    fn match_labels(
+        left_tags: &[String],
+        right_tags: &[String],
+        matching: Option<&LabelModifier>,
+    ) -> Vec<String> {
+        match matching {
+            Some(LabelModifier::Include(on)) => {
+                let mut labels = Vec::with_capacity(on.labels.len());
+                for label in &on.labels {
+                    if !labels.contains(label) {
+                        labels.push(label.clone());
+                    }
+                }
+                labels
+            }
+            Some(LabelModifier::Exclude(ignoring)) => left_tags
+                .iter()
+                .chain(right_tags)
+                .filter(|tag| !ignoring.labels.contains(*tag))
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            None => left_tags
+                .iter()
+                .chain(right_tags)
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn match_key_column(index: usize) -> String {
+        format!("{MATCH_KEY_PREFIX}{index}")
+    }
+
+    fn side_column(side: &str, name: &str) -> DfExpr {
+        DfExpr::Column(Column::new(Some(side.to_string()), name))
+    }
+
+    /// Join key columns of one side prepared by [Self::prepare_matching_side].
+    fn match_join_keys(side: &str, match_label_num: usize, time_index: &str) -> Vec<Column> {
+        (0..match_label_num)
+            .map(|index| Column::new(Some(side.to_string()), Self::match_key_column(index)))
+            .chain(Some(Column::new(Some(side.to_string()), time_index)))
+            .collect()
+    }
+
+    /// Prepare one side of a binary operation between two vectors. Values of matching labels
+    /// are projected into key columns to join on, where a missing label has an empty value.
+    /// If `duplicate_error` is given, an error will be raised when a match group at some
+    /// timestamp contains more than one sample. The output is qualified by `side`.
+    fn prepare_matching_side(
+        input: LogicalPlan,
+        ctx: &PromPlannerContext,
+        match_labels: &[String],
+        side: &str,
+        duplicate_error: Option<&str>,
+    ) -> Result<LogicalPlan> {
+        let time_index = ctx
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "unknown" })?;
+        let schema = input.schema().clone();
+
+        let mut exprs = schema
+            .fields()
+            .iter()
+            .map(|field| DfExpr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+        let mut partition_exprs = Vec::with_capacity(match_labels.len() + 1);
+        for (index, label) in match_labels.iter().enumerate() {
+            let value = match schema.field_with_unqualified_name(label) {
+                Ok(field) => DfExpr::ScalarFunction(ScalarFunction {
+                    fun: BuiltinScalarFunction::Coalesce,
+                    args: vec![
+                        DfExpr::Column(field.qualified_column()),
+                        df_prelude::lit(""),
+                    ],
+                }),
+                Err(_) => df_prelude::lit(""),
+            };
+            let key = Self::match_key_column(index);
+            exprs.push(value.alias(&key));
+            partition_exprs.push(DfExpr::Column(Column::from_name(key)));
+        }
+        partition_exprs.push(DfExpr::Column(Column::from_name(time_index)));
+
+        let mut builder = LogicalPlanBuilder::from(input)
+            .project(exprs)
+            .context(DataFusionPlanningSnafu)?;
+        if let Some(message) = duplicate_error {
+            let count = DfExpr::WindowFunction(WindowFunction {
+                fun: WindowFunctionEnum::AggregateFunction(AggregateFunctionEnum::Count),
+                args: vec![df_prelude::lit(1_u8)],
+                partition_by: partition_exprs,
+                order_by: vec![],
+                window_frame: WindowFrame::new(false),
+            });
+            let count_column = count.display_name().context(DataFusionPlanningSnafu)?;
+            let check = DfExpr::ScalarUDF(ScalarUDF {
+                fun: Arc::new(UniqueMatchCheck::scalar_udf(message.to_string())),
+                args: vec![DfExpr::Column(Column::from_name(count_column))],
+            });
+            builder = builder
+                .window(vec![count])
+                .context(DataFusionPlanningSnafu)?
+                .filter(check)
+                .context(DataFusionPlanningSnafu)?;
+        }
+
+        builder
+            .alias(OwnedTableReference::bare(side.to_string()))
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Qualify the output of a binary operation by the table name in context, which
+    /// following plans refer columns with.
+    fn qualify_by_table_name(&mut self, builder: LogicalPlanBuilder) -> Result<LogicalPlan> {
+        let builder = match &self.ctx.table_name {
+            Some(table_name) if !table_name.is_empty() => builder
+                .alias(OwnedTableReference::bare(table_name.clone()))
+                .context(DataFusionPlanningSnafu)?,
+            _ => {
+                self.ctx.table_name = None;
+                builder
+            }
+        };
+        builder.build().context(DataFusionPlanningSnafu)
+    }
+
+    /// Create a plan for arithmetic and comparison operations between two vectors with
+    /// `on`/`ignoring` or `group_left`/`group_right` modifiers. Samples are joined on
+    /// values of the matching labels and time index, and the "one" side of the matching
+    /// must have at most one sample in each match group.
+    ///
+    /// # Side effect
+    ///
+    /// This method will reset the context to the generated plan.
+    fn create_vector_matching_plan(
+        &mut self,
+        (left_input, left_ctx): (LogicalPlan, PromPlannerContext),
+        (right_input, right_ctx): (LogicalPlan, PromPlannerContext),
+        op: TokenType,
+        modifier: &BinModifier,
+    ) -> Result<LogicalPlan> {
+        ensure!(
+            left_ctx.field_columns.len() == right_ctx.field_columns.len(),
+            UnsupportedExprSnafu {
+                name: "binary operation between vectors with different number of values"
+            }
+        );
+        let left_tags = Self::side_tag_columns(&left_input, &left_ctx);
+        let right_tags = Self::side_tag_columns(&right_input, &right_ctx);
+        let match_labels = Self::match_labels(&left_tags, &right_tags, modifier.matching.as_ref());
+
+        let (left_check, right_check) = match &modifier.card {
+            VectorMatchCardinality::OneToOne => (
+                Some(ONE_TO_ONE_MATCH_ERROR),
+                Some(RIGHT_DUPLICATE_MATCH_ERROR),
+            ),
+            VectorMatchCardinality::ManyToOne(_) => (None, Some(RIGHT_DUPLICATE_MATCH_ERROR)),
+            VectorMatchCardinality::OneToMany(_) => (Some(LEFT_DUPLICATE_MATCH_ERROR), None),
+            VectorMatchCardinality::ManyToMany => UnsupportedExprSnafu {
+                name: "many-to-many matching for arithmetic or comparison operator",
+            }
+            .fail()?,
+        };
+        let left_time_index = left_ctx
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "unknown" })?;
+        let right_time_index = right_ctx
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "unknown" })?;
+        let left_plan = Self::prepare_matching_side(
+            left_input,
+            &left_ctx,
+            &match_labels,
+            LEFT_SIDE_ALIAS,
+            left_check,
+        )?;
+        let right_plan = Self::prepare_matching_side(
+            right_input,
+            &right_ctx,
+            &match_labels,
+            RIGHT_SIDE_ALIAS,
+            right_check,
+        )?;
+        let join_keys = (
+            Self::match_join_keys(LEFT_SIDE_ALIAS, match_labels.len(), &left_time_index),
+            Self::match_join_keys(RIGHT_SIDE_ALIAS, match_labels.len(), &right_time_index),
+        );
+        let join_plan = LogicalPlanBuilder::from(left_plan)
+            .join(right_plan, JoinType::Inner, join_keys, None)
+            .context(DataFusionPlanningSnafu)?;
+
+        // labels of result series
+        let mut tag_columns = vec![];
+        let mut tag_exprs = vec![];
+        match (&modifier.card, &modifier.matching) {
+            (VectorMatchCardinality::OneToOne, Some(LabelModifier::Include(_))) => {
+                for (index, label) in match_labels.iter().enumerate() {
+                    let key = Self::side_column(LEFT_SIDE_ALIAS, &Self::match_key_column(index));
+                    tag_exprs.push(
+                        DfExpr::ScalarFunction(ScalarFunction {
+                            fun: BuiltinScalarFunction::NullIf,
+                            args: vec![key, df_prelude::lit("")],
+                        })
+                        .alias(label),
+                    );
+                    tag_columns.push(label.clone());
+                }
+            }
+            (VectorMatchCardinality::OneToOne, matching) => {
+                let ignoring = match matching {
+                    Some(LabelModifier::Exclude(ignoring)) => ignoring.labels.as_slice(),
+                    _ => &[],
+                };
+                for tag in left_tags.iter().filter(|tag| !ignoring.contains(*tag)) {
+                    tag_exprs.push(Self::side_column(LEFT_SIDE_ALIAS, tag).alias(tag));
+                    tag_columns.push(tag.clone());
+                }
+            }
+            (VectorMatchCardinality::ManyToOne(include), _)
+            | (VectorMatchCardinality::OneToMany(include), _) => {
+                let (many_side, many_tags, one_side, one_tags) =
+                    if matches!(modifier.card, VectorMatchCardinality::ManyToOne(_)) {
+                        (LEFT_SIDE_ALIAS, &left_tags, RIGHT_SIDE_ALIAS, &right_tags)
+                    } else {
+                        (RIGHT_SIDE_ALIAS, &right_tags, LEFT_SIDE_ALIAS, &left_tags)
+                    };
+                for tag in many_tags
+                    .iter()
+                    .filter(|tag| !include.labels.contains(*tag))
+                {
+                    tag_exprs.push(Self::side_column(many_side, tag).alias(tag));
+                    tag_columns.push(tag.clone());
+                }
+                // labels listed in `group_x` are copied from the "one" side
+                for label in &include.labels {
+                    if one_tags.contains(label) && !tag_columns.contains(label) {
+                        tag_exprs.push(Self::side_column(one_side, label).alias(label));
+                        tag_columns.push(label.clone());
+                    }
+                }
+            }
+            (VectorMatchCardinality::ManyToMany, _) => unreachable!(),
+        }
+
+        let (result_ctx, time_index_expr) =
+            if matches!(modifier.card, VectorMatchCardinality::OneToMany(_)) {
+                let expr =
+                    Self::side_column(RIGHT_SIDE_ALIAS, &right_time_index).alias(&right_time_index);
+                (right_ctx, expr)
+            } else {
+                let expr =
+                    Self::side_column(LEFT_SIDE_ALIAS, &left_time_index).alias(&left_time_index);
+                (left_ctx, expr)
+            };
+
+        // comparison operators without `bool` filter samples and keep values of lhs
+        let is_comparison_op = Self::is_token_a_comparison_op(op);
+        let is_filter = is_comparison_op && !modifier.return_bool;
+        let binary_expr_builder = Self::prom_token_to_binary_expr_builder(op)?;
+        let mut predicates = vec![];
+        let mut field_columns = vec![];
+        let mut field_exprs = vec![];
+        for (left_field, right_field) in left_ctx
+            .field_columns
+            .iter()
+            .zip(right_ctx.field_columns.iter())
+        {
+            let left_col = Self::side_column(LEFT_SIDE_ALIAS, left_field);
+            let right_col = Self::side_column(RIGHT_SIDE_ALIAS, right_field);
+            let mut binary_expr = binary_expr_builder(left_col.clone(), right_col)?;
+            if is_filter {
+                predicates.push(binary_expr);
+                field_exprs.push(left_col.alias(left_field));
+                field_columns.push(left_field.clone());
+            } else {
+                if is_comparison_op {
+                    binary_expr = DfExpr::Cast(Cast {
+                        expr: Box::new(binary_expr),
+                        data_type: ArrowDataType::Float64,
+                    });
+                }
+                let name = binary_expr
+                    .display_name()
+                    .context(DataFusionPlanningSnafu)?;
+                field_exprs.push(binary_expr.alias(&name));
+                field_columns.push(name);
+            }
+        }
+
+        let mut builder = join_plan;
+        if let Some(predicate) = utils::conjunction(predicates) {
+            builder = builder.filter(predicate).context(DataFusionPlanningSnafu)?;
+        }
+        let project_exprs = tag_exprs
+            .into_iter()
+            .chain(Some(time_index_expr))
+            .chain(field_exprs)
+            .collect::<Vec<_>>();
+        builder = builder
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?;
+
+        self.ctx = result_ctx;
+        self.ctx.tag_columns = tag_columns;
+        self.ctx.field_columns = field_columns;
+        self.qualify_by_table_name(builder)
+    }
+
+    /// Create a plan for set operators `and`, `or` and `unless` between two vectors.
+    /// Samples are matched on values of the matching labels and time index, and
+    /// many-to-many matching is allowed.
+    ///
+    /// - `and` keeps samples of lhs that have matches in rhs.
+    /// - `unless` keeps samples of lhs that have no match in rhs.
+    /// - `or` keeps all samples of lhs, plus samples of rhs that have no match in lhs.
+    ///
+    /// # Side effect
+    ///
+    /// This method will reset the context to the generated plan.
+    fn create_set_op_plan(
+        &mut self,
+        (left_input, left_ctx): (LogicalPlan, PromPlannerContext),
+        (right_input, right_ctx): (LogicalPlan, PromPlannerContext),
+        op: TokenType,
+        modifier: &Option<BinModifier>,
+    ) -> Result<LogicalPlan> {
+        ensure!(
+            left_ctx.field_columns.len() == right_ctx.field_columns.len(),
+            UnsupportedExprSnafu {
+                name: "set operation between vectors with different number of values"
+            }
+        );
+        let left_tags = Self::side_tag_columns(&left_input, &left_ctx);
+        let right_tags = Self::side_tag_columns(&right_input, &right_ctx);
+        let matching = modifier.as_ref().and_then(|m| m.matching.as_ref());
+        let match_labels = Self::match_labels(&left_tags, &right_tags, matching);
+        let left_time_index = left_ctx
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "unknown" })?;
+        let right_time_index = right_ctx
+            .time_index_column
+            .clone()
+            .with_context(|| TimeIndexNotFoundSnafu { table: "unknown" })?;
+
+        let left_plan = Self::prepare_matching_side(
+            left_input,
+            &left_ctx,
+            &match_labels,
+            LEFT_SIDE_ALIAS,
+            None,
+        )?;
+        let right_plan = Self::prepare_matching_side(
+            right_input,
+            &right_ctx,
+            &match_labels,
+            RIGHT_SIDE_ALIAS,
+            None,
+        )?;
+        let left_keys =
+            Self::match_join_keys(LEFT_SIDE_ALIAS, match_labels.len(), &left_time_index);
+        let right_keys =
+            Self::match_join_keys(RIGHT_SIDE_ALIAS, match_labels.len(), &right_time_index);
+
+        // project one side into the output layout
+        let project_side = |builder: LogicalPlanBuilder,
+                            side: &str,
+                            output_tags: &[String],
+                            side_tags: &[String],
+                            side_ctx: &PromPlannerContext,
+                            time_index: &str| {
+            let exprs = output_tags
+                .iter()
+                .map(|tag| {
+                    if side_tags.contains(tag) {
+                        Self::side_column(side, tag).alias(tag)
+                    } else {
+                        DfExpr::Literal(ScalarValue::Utf8(None)).alias(tag)
+                    }
+                })
+                .chain(Some(
+                    Self::side_column(side, time_index).alias(&left_time_index),
+                ))
+                .chain(
+                    side_ctx
+                        .field_columns
+                        .iter()
+                        .zip(left_ctx.field_columns.iter())
+                        .map(|(field, name)| Self::side_column(side, field).alias(name)),
+                )
+                .collect::<Vec<_>>();
+            builder.project(exprs).context(DataFusionPlanningSnafu)
+        };
+
+        let (builder, tag_columns) = match op.id() {
+            token::T_LAND | token::T_LUNLESS => {
+                let join_type = if op.id() == token::T_LAND {
+                    JoinType::LeftSemi
+                } else {
+                    JoinType::LeftAnti
+                };
+                let builder = LogicalPlanBuilder::from(left_plan)
+                    .join(right_plan, join_type, (left_keys, right_keys), None)
+                    .context(DataFusionPlanningSnafu)?;
+                let builder = project_side(
+                    builder,
+                    LEFT_SIDE_ALIAS,
+                    &left_tags,
+                    &left_tags,
+                    &left_ctx,
+                    &left_time_index,
+                )?;
+                (builder, left_tags)
+            }
+            token::T_LOR => {
+                let mut output_tags = left_tags.clone();
+                output_tags.extend(
+                    right_tags
+                        .iter()
+                        .filter(|tag| !left_tags.contains(*tag))
+                        .cloned(),
+                );
+
+                // samples of rhs that have no match in lhs
+                let right_only = LogicalPlanBuilder::from(right_plan)
+                    .join(
+                        left_plan.clone(),
+                        JoinType::LeftAnti,
+                        (right_keys, left_keys),
+                        None,
+                    )
+                    .context(DataFusionPlanningSnafu)?;
+                let right_only = project_side(
+                    right_only,
+                    RIGHT_SIDE_ALIAS,
+                    &output_tags,
+                    &right_tags,
+                    &right_ctx,
+                    &right_time_index,
+                )?
+                .build()
+                .context(DataFusionPlanningSnafu)?;
+                let builder = project_side(
+                    LogicalPlanBuilder::from(left_plan),
+                    LEFT_SIDE_ALIAS,
+                    &output_tags,
+                    &left_tags,
+                    &left_ctx,
+                    &left_time_index,
+                )?
+                .union(right_only)
+                .context(DataFusionPlanningSnafu)?;
+                (builder, output_tags)
+            }
+            _ => UnexpectedTokenSnafu { token: op }.fail()?,
+        };
+
+        self.ctx = left_ctx;
+        self.ctx.tag_columns = tag_columns;
+        self.qualify_by_table_name(builder)
+    }
+
+    /// Build a inner join on time index column and tag columns to concat two logical plans.
+    fn join_on_non_field_columns(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        let mut tag_columns = self
+            .ctx
+            .tag_columns
+            .iter()
+            .map(Column::from_name)
+            .collect::<Vec<_>>();
+
+        // push time index column if it exist
+        if let Some(time_index_column) = &self.ctx.time_index_column {
+            tag_columns.push(Column::from_name(time_index_column));
+        }
+
+        // Inner Join on time index column to concat two operator
+        LogicalPlanBuilder::from(left)
+            .join(
+                right,
+                JoinType::Inner,
+                (tag_columns.clone(), tag_columns),
+                None,
+            )
             .context(DataFusionPlanningSnafu)?
             .build()
             .context(DataFusionPlanningSnafu)
@@ -1352,10 +2878,20 @@ mod test {
         num_tag: usize,
         num_field: usize,
     ) -> DfTableSourceProvider {
+        let tags = (0..num_tag).map(|i| format!("tag_{i}")).collect::<Vec<_>>();
+        build_test_table_provider_with_tags(table_name, &tags, num_field).await
+    }
+
+    async fn build_test_table_provider_with_tags(
+        table_name: String,
+        tags: &[String],
+        num_field: usize,
+    ) -> DfTableSourceProvider {
+        let num_tag = tags.len();
         let mut columns = vec![];
-        for i in 0..num_tag {
+        for tag in tags {
             columns.push(ColumnSchema::new(
-                format!("tag_{i}"),
+                tag.clone(),
                 ConcreteDataType::string_datatype(),
                 false,
             ));
@@ -1455,12 +2991,6 @@ mod test {
         do_single_instant_function_call("abs", "abs").await;
     }
 
-    #[tokio::test]
-    #[should_panic]
-    async fn single_absent() {
-        do_single_instant_function_call("absent", "").await;
-    }
-
     #[tokio::test]
     async fn single_ceil() {
         do_single_instant_function_call("ceil", "ceil").await;
@@ -1486,41 +3016,17 @@ mod test {
         do_single_instant_function_call("log10", "log10").await;
     }
 
-    #[tokio::test]
-    #[should_panic]
-    async fn single_scalar() {
-        do_single_instant_function_call("scalar", "").await;
-    }
-
     #[tokio::test]
     #[should_panic]
     async fn single_sgn() {
         do_single_instant_function_call("sgn", "").await;
     }
 
-    #[tokio::test]
-    #[should_panic]
-    async fn single_sort() {
-        do_single_instant_function_call("sort", "").await;
-    }
-
-    #[tokio::test]
-    #[should_panic]
-    async fn single_sort_desc() {
-        do_single_instant_function_call("sort_desc", "").await;
-    }
-
     #[tokio::test]
     async fn single_sqrt() {
         do_single_instant_function_call("sqrt", "sqrt").await;
     }
 
-    #[tokio::test]
-    #[should_panic]
-    async fn single_timestamp() {
-        do_single_instant_function_call("timestamp", "").await;
-    }
-
     #[tokio::test]
     async fn single_acos() {
         do_single_instant_function_call("acos", "acos").await;
@@ -1721,27 +3227,94 @@ mod test {
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_top_k() {
-        do_aggregate_expr_plan("topk", "").await;
+        for query in [
+            "topk(2, some_metric) by (tag_1)",
+            "bottomk(2, some_metric) without (tag_0)",
+        ] {
+            let plan = indie_query_plan(query, &["tag_0", "tag_1"]).await.unwrap();
+            assert!(matches!(plan, LogicalPlan::Sort(_)), "query: {query}");
+            assert_eq!(
+                unqualified_field_names(&plan),
+                vec!["tag_0", "tag_1", "timestamp", "field_0"]
+            );
+            let plan_str = plan.display_indent().to_string();
+            assert!(plan_str.contains("ROW_NUMBER()"), "{plan_str}");
+            assert!(plan_str.contains("<= UInt64(2)"), "{plan_str}");
+        }
+
+        // k less than 1 selects nothing
+        let plan = indie_query_plan("topk(0.5, some_metric)", &["tag_0"])
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent().to_string();
+        assert!(plan_str.contains("Filter: Boolean(false)"), "{plan_str}");
+
+        assert!(
+            indie_query_plan("topk(some_metric, some_metric)", &["tag_0"])
+                .await
+                .is_err()
+        );
     }
 
     #[tokio::test]
-    #[should_panic]
-    async fn aggregate_bottom_k() {
-        do_aggregate_expr_plan("bottomk", "").await;
+    async fn aggregate_quantile() {
+        let plan = indie_query_plan("quantile(0.9, some_metric) by (tag_1)", &["tag_0", "tag_1"])
+            .await
+            .unwrap();
+        assert!(matches!(plan, LogicalPlan::Sort(_)));
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec![
+                "tag_1",
+                "timestamp",
+                "prom_quantile(ARRAYAGG(some_metric.field_0))"
+            ]
+        );
+
+        let plan = indie_query_plan("quantile(0.5, some_metric)", &["tag_0"])
+            .await
+            .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["timestamp", "prom_quantile(ARRAYAGG(some_metric.field_0))"]
+        );
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_count_values() {
-        do_aggregate_expr_plan("count_values", "").await;
-    }
+        let plan = indie_query_plan(
+            "count_values(\"version\", some_metric) by (tag_1)",
+            &["tag_0", "tag_1"],
+        )
+        .await
+        .unwrap();
+        assert!(matches!(plan, LogicalPlan::Sort(_)));
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec![
+                "tag_1",
+                "version",
+                "timestamp",
+                "COUNT(some_metric.field_0)"
+            ]
+        );
+        assert_eq!(
+            plan.schema()
+                .field_with_unqualified_name("COUNT(some_metric.field_0)")
+                .unwrap()
+                .data_type(),
+            &ArrowDataType::Float64
+        );
 
-    #[tokio::test]
-    #[should_panic]
-    async fn aggregate_quantile() {
-        do_aggregate_expr_plan("quantile", "").await;
+        // the value label overrides the existing tag
+        let plan = indie_query_plan("count_values(\"tag_1\", some_metric)", &["tag_0", "tag_1"])
+            .await
+            .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["tag_1", "timestamp", "COUNT(some_metric.field_0)"]
+        );
     }
 
     // TODO(ruihang): add range fn tests once exprs are ready.
@@ -1806,6 +3379,77 @@ mod test {
         assert_eq!(plan.display_indent_schema().to_string(), expected);
     }
 
+    #[tokio::test]
+    async fn binary_op_vector_matching() {
+        let tags = ["tag_0", "tag_1"];
+
+        // one-to-one matching keeps only labels in `on`
+        let plan = indie_query_plan("some_metric + on(tag_0) some_metric", &tags)
+            .await
+            .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["tag_0", "timestamp", "lhs.field_0 + rhs.field_0"]
+        );
+        let plan_str = plan.display_indent().to_string();
+        assert!(
+            plan_str.contains(
+                "Inner Join: lhs.__match_key_0 = rhs.__match_key_0, lhs.timestamp = rhs.timestamp"
+            ),
+            "{plan_str}"
+        );
+        // both sides are checked to be unique
+        assert_eq!(plan_str.matches("prom_unique_match_check").count(), 2);
+
+        // many-to-one matching copies labels in `group_left` from rhs
+        let plan = indie_query_plan(
+            "some_metric * ignoring(tag_1) group_left(tag_1) some_metric",
+            &tags,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["tag_0", "tag_1", "timestamp", "lhs.field_0 * rhs.field_0"]
+        );
+        let plan_str = plan.display_indent().to_string();
+        assert_eq!(plan_str.matches("prom_unique_match_check").count(), 1);
+
+        // one-to-many comparison filters samples and keeps values of lhs
+        let plan = indie_query_plan("some_metric > on(tag_0) group_right some_metric", &tags)
+            .await
+            .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["tag_0", "tag_1", "timestamp", "field_0"]
+        );
+        let plan_str = plan.display_indent().to_string();
+        assert!(
+            plan_str.contains("Filter: lhs.field_0 > rhs.field_0"),
+            "{plan_str}"
+        );
+    }
+
+    #[tokio::test]
+    async fn binary_op_set_operators() {
+        let tags = ["tag_0", "tag_1"];
+        for (query, plan_node) in [
+            ("some_metric and on(tag_0) some_metric", "LeftSemi Join:"),
+            ("some_metric unless some_metric", "LeftAnti Join:"),
+            ("some_metric or ignoring(tag_1) some_metric", "Union"),
+        ] {
+            let plan = indie_query_plan(query, &tags).await.unwrap();
+            assert_eq!(
+                unqualified_field_names(&plan),
+                vec!["tag_0", "tag_1", "timestamp", "field_0"],
+                "query: {query}"
+            );
+            let plan_str = plan.display_indent().to_string();
+            assert!(plan_str.contains(plan_node), "{plan_str}");
+            assert!(!plan_str.contains("prom_unique_match_check"), "{plan_str}");
+        }
+    }
+
     async fn indie_query_plan_compare(query: &str, expected: String) {
         let prom_expr = parser::parse(query).unwrap();
         let eval_stmt = EvalStmt {
@@ -1937,6 +3581,303 @@ mod test {
         indie_query_plan_compare(query, expected).await;
     }
 
+    async fn indie_query_plan(query: &str, tags: &[&str]) -> Result<LogicalPlan> {
+        let prom_expr = parser::parse(query).unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        let tags = tags.iter().map(|tag| tag.to_string()).collect::<Vec<_>>();
+        let table_provider =
+            build_test_table_provider_with_tags("some_metric".to_string(), &tags, 1).await;
+        PromPlanner::stmt_to_plan(table_provider, eval_stmt).await
+    }
+
+    fn unqualified_field_names(plan: &LogicalPlan) -> Vec<String> {
+        plan.schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn subquery() {
+        let plan = indie_query_plan("max_over_time(rate(some_metric[5m])[1h:1m])", &["tag_0"])
+            .await
+            .unwrap();
+        assert_eq!(unqualified_field_names(&plan)[..2], ["tag_0", "timestamp"]);
+        let plan_str = plan.display_indent().to_string();
+        // the outer query folds inner results into ranges
+        assert!(
+            plan_str.contains(
+                "PromRangeManipulate: req range=[0..100000000], interval=[5000], \
+                eval range=[3600000], time index=[timestamp]"
+            ),
+            "{plan_str}"
+        );
+        // the inner query is evaluated at its own steps
+        assert!(
+            plan_str.contains(
+                "PromRangeManipulate: req range=[-3600000..100000000], interval=[60000], \
+                eval range=[300000], time index=[timestamp]"
+            ),
+            "{plan_str}"
+        );
+
+        // inner steps are aligned, and biased by offset
+        let plan = indie_query_plan("max_over_time(some_metric[90s:1m] offset 5m)", &["tag_0"])
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent().to_string();
+        assert!(
+            plan_str.contains(
+                "PromInstantManipulate: range=[-360000..99700000], lookback=[1000], \
+                interval=[60000], time index=[timestamp]"
+            ),
+            "{plan_str}"
+        );
+        assert!(
+            plan_str.contains(
+                "PromSeriesNormalize: offset=[300000], time index=[timestamp], filter NaN: [false]"
+            ),
+            "{plan_str}"
+        );
+
+        // step defaults to the interval of outer query
+        let plan = indie_query_plan("min_over_time(some_metric[10m:])", &["tag_0"])
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent().to_string();
+        assert!(
+            plan_str.contains(
+                "PromInstantManipulate: range=[-600000..100000000], lookback=[1000], \
+                interval=[5000], time index=[timestamp]"
+            ),
+            "{plan_str}"
+        );
+    }
+
+    #[tokio::test]
+    async fn at_modifier() {
+        // evaluated at the pinned time, and broadcast to all steps
+        let plan = indie_query_plan("some_metric @ 1000", &["tag_0"])
+            .await
+            .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["tag_0", "timestamp", "field_0"]
+        );
+        let plan_str = plan.display_indent().to_string();
+        for expected in [
+            "PromInstantManipulate: range=[1000000..1000000], lookback=[1000]",
+            "timestamp >= TimestampMillisecond(999000, None)",
+            "timestamp <= TimestampMillisecond(1001000, None)",
+            "CrossJoin:",
+            "EmptyMetric: range=[0..100000000], interval=[5000]",
+        ] {
+            assert!(plan_str.contains(expected), "{plan_str}");
+        }
+
+        let plan = indie_query_plan("some_metric @ end() offset 5m", &["tag_0"])
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent().to_string();
+        for expected in [
+            "PromInstantManipulate: range=[100000000..100000000], lookback=[1000]",
+            "PromSeriesNormalize: offset=[300000]",
+            "timestamp <= TimestampMillisecond(99701000, None)",
+        ] {
+            assert!(plan_str.contains(expected), "{plan_str}");
+        }
+
+        // range functions are evaluated at the pinned time
+        let plan = indie_query_plan("rate(some_metric[5m] @ start())", &["tag_0"])
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent().to_string();
+        for expected in [
+            "PromRangeManipulate: req range=[0..0], interval=[5000], eval range=[300000]",
+            "EmptyMetric: range=[0..100000000], interval=[5000]",
+        ] {
+            assert!(plan_str.contains(expected), "{plan_str}");
+        }
+    }
+
+    #[tokio::test]
+    async fn negative_offset() {
+        let plan = indie_query_plan("some_metric offset -5m", &["tag_0"])
+            .await
+            .unwrap();
+        let plan_str = plan.display_indent().to_string();
+        for expected in [
+            "PromSeriesNormalize: offset=[-300000]",
+            "timestamp >= TimestampMillisecond(299000, None)",
+            "timestamp <= TimestampMillisecond(100301000, None)",
+        ] {
+            assert!(plan_str.contains(expected), "{plan_str}");
+        }
+    }
+
+    #[tokio::test]
+    async fn sort_functions() {
+        for query in ["sort(some_metric)", "sort_desc(some_metric)"] {
+            let plan = indie_query_plan(query, &["tag_0"]).await.unwrap();
+            assert!(matches!(plan, LogicalPlan::Sort(_)), "query: {query}");
+            assert_eq!(
+                unqualified_field_names(&plan),
+                vec!["tag_0", "timestamp", "field_0"]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn timestamp_function() {
+        let plan = indie_query_plan("timestamp(some_metric)", &["tag_0"])
+            .await
+            .unwrap();
+        let fields = unqualified_field_names(&plan);
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[..2], ["tag_0", "timestamp"]);
+    }
+
+    #[tokio::test]
+    async fn clamp_functions() {
+        for query in [
+            "clamp(some_metric, 0, 1)",
+            "clamp_min(some_metric, 0)",
+            "clamp_max(some_metric, 1)",
+        ] {
+            let plan = indie_query_plan(query, &["tag_0"]).await.unwrap();
+            assert!(matches!(plan, LogicalPlan::Projection(_)), "query: {query}");
+            assert_eq!(unqualified_field_names(&plan).len(), 3, "query: {query}");
+        }
+
+        // min is greater than max
+        let plan = indie_query_plan("clamp(some_metric, 1, 0)", &["tag_0"])
+            .await
+            .unwrap();
+        assert!(matches!(plan, LogicalPlan::Filter(_)));
+    }
+
+    #[tokio::test]
+    async fn absent_function() {
+        let plan = indie_query_plan(
+            r#"absent(some_metric{tag_0="foo", tag_1!="bar", tag_2="a", tag_2="b"})"#,
+            &["tag_0", "tag_1", "tag_2"],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["time", "value", "tag_0"]
+        );
+
+        let plan = indie_query_plan("absent(some_metric)", &["tag_0"])
+            .await
+            .unwrap();
+        assert_eq!(unqualified_field_names(&plan), vec!["time", "value"]);
+
+        // nonexistent metric
+        let plan = indie_query_plan(r#"absent(nonexistent{tag_0="foo"})"#, &["tag_0"])
+            .await
+            .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["time", "value", "tag_0"]
+        );
+    }
+
+    #[tokio::test]
+    async fn scalar_and_vector_functions() {
+        for query in ["scalar(some_metric)", "vector(1)", "vector(time())"] {
+            let plan = indie_query_plan(query, &["tag_0"]).await.unwrap();
+            assert_eq!(
+                unqualified_field_names(&plan),
+                vec!["time", "value"],
+                "query: {query}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn label_replace_function() {
+        let plan = indie_query_plan(
+            r#"label_replace(some_metric, "foo", "$1", "tag_0", "(.*)-.*")"#,
+            &["tag_0"],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["timestamp", "tag_0", "foo", "field_0"]
+        );
+
+        // replace an existing label
+        let plan = indie_query_plan(
+            r#"label_replace(some_metric, "tag_0", "$1", "tag_1", "(.*)")"#,
+            &["tag_0", "tag_1"],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["timestamp", "tag_1", "tag_0", "field_0"]
+        );
+    }
+
+    #[tokio::test]
+    async fn label_join_function() {
+        let plan = indie_query_plan(
+            r#"label_join(some_metric, "foo", ",", "tag_0", "tag_1", "nonexistent")"#,
+            &["tag_0", "tag_1"],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["timestamp", "tag_0", "tag_1", "foo", "field_0"]
+        );
+    }
+
+    #[tokio::test]
+    async fn histogram_quantile_function() {
+        let plan = indie_query_plan("histogram_quantile(0.9, some_metric)", &["host", "le"])
+            .await
+            .unwrap();
+        assert_eq!(
+            unqualified_field_names(&plan),
+            vec!["host", "timestamp", "field_0"]
+        );
+        let LogicalPlan::Extension(Extension { node }) = &plan else {
+            panic!("expect an extension plan, found {plan:?}");
+        };
+        assert_eq!(node.name(), HistogramFold::name());
+
+        let plan = indie_query_plan(
+            "histogram_quantile(0.9, sum by (le) (rate(some_metric[5m])))",
+            &["host", "le"],
+        )
+        .await
+        .unwrap();
+        let fields = unqualified_field_names(&plan);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0], "timestamp");
+
+        // `le` tag is required
+        assert!(
+            indie_query_plan("histogram_quantile(0.9, some_metric)", &["host"])
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn value_matcher() {
         // template
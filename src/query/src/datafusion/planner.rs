@@ -18,8 +18,11 @@ use std::sync::Arc;
 
 use arrow_schema::DataType;
 use catalog::table_source::DfTableSourceProvider;
+use common_catalog::format_full_table_name;
 use common_query::logical_plan::create_aggregate_function;
 use datafusion::catalog::TableReference;
+use datafusion::datasource::view::ViewTable;
+use datafusion::datasource::{provider_as_source, DefaultTableSource};
 use datafusion::error::Result as DfResult;
 use datafusion::execution::context::SessionState;
 use datafusion::physical_plan::udaf::AggregateUDF;
@@ -30,10 +33,14 @@ use datafusion_common::{DataFusionError, OwnedTableReference};
 use datafusion_expr::{TableSource, WindowUDF};
 use datafusion_physical_expr::var_provider::{is_system_variables, VarType};
 use datafusion_sql::parser::Statement as DfStatement;
-use session::context::QueryContextRef;
-use snafu::ResultExt;
-
-use crate::error::{CatalogSnafu, DataFusionSnafu, Result};
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{ensure, OptionExt, ResultExt};
+use table::metadata::TableType;
+use table::table::adapter::DfTableProviderAdapter;
+
+use crate::error::{CatalogSnafu, DataFusionSnafu, InvalidViewSnafu, Result};
+use crate::parser::{QueryLanguageParser, QueryStatement};
+use crate::planner::plan_sql;
 use crate::query_engine::QueryEngineState;
 
 pub struct DfContextProviderAdapter {
@@ -49,6 +56,7 @@ impl DfContextProviderAdapter {
         session_state: SessionState,
         df_stmt: &DfStatement,
         query_ctx: QueryContextRef,
+        views: Vec<String>,
     ) -> Result<Self> {
         let table_names = session_state
             .resolve_table_references(df_stmt)
@@ -58,9 +66,17 @@ impl DfContextProviderAdapter {
             engine_state.catalog_manager().clone(),
             engine_state.disallow_cross_schema_query(),
             query_ctx.as_ref(),
-        );
-
-        let tables = resolve_tables(table_names, &mut table_provider).await?;
+        )
+        .with_information_schema_filter(engine_state.information_schema_filter(&query_ctx));
+
+        let mut tables = resolve_tables(table_names, &mut table_provider).await?;
+        for table in tables.values_mut() {
+            if let Some(view) =
+                expand_view(&engine_state, &session_state, &query_ctx, table, &views).await?
+            {
+                *table = view;
+            }
+        }
 
         Ok(Self {
             engine_state,
@@ -94,6 +110,84 @@ async fn resolve_tables(
     Ok(tables)
 }
 
+/// Plans the definition of the view behind `source`, returning `None` if `source` is not a view.
+///
+/// The user of `query_ctx` must be able to read the tables the definition reads, which is
+/// checked before the view is expanded.
+async fn expand_view(
+    engine_state: &Arc<QueryEngineState>,
+    session_state: &SessionState,
+    query_ctx: &QueryContextRef,
+    source: &Arc<dyn TableSource>,
+    views: &[String],
+) -> Result<Option<Arc<dyn TableSource>>> {
+    let Some(table) = source
+        .as_any()
+        .downcast_ref::<DefaultTableSource>()
+        .and_then(|source| {
+            source
+                .table_provider
+                .as_any()
+                .downcast_ref::<DfTableProviderAdapter>()
+        })
+        .map(|adapter| adapter.table())
+    else {
+        return Ok(None);
+    };
+    if table.table_type() != TableType::View {
+        return Ok(None);
+    }
+
+    let table_info = table.table_info();
+    let view_name = format_full_table_name(
+        &table_info.catalog_name,
+        &table_info.schema_name,
+        &table_info.name,
+    );
+    ensure!(
+        !views.contains(&view_name),
+        InvalidViewSnafu {
+            view: &view_name,
+            reason: "the view references itself",
+        }
+    );
+    let definition =
+        table_info
+            .meta
+            .view_definition
+            .as_ref()
+            .with_context(|| InvalidViewSnafu {
+                view: &view_name,
+                reason: "missing view definition",
+            })?;
+    let QueryStatement::Sql(stmt) = QueryLanguageParser::parse_sql(definition)? else {
+        unreachable!("parse_sql always returns a SQL statement");
+    };
+
+    // The definition is resolved against the schema the view is created in.
+    engine_state.check_view_permission(
+        query_ctx,
+        &table_info.catalog_name,
+        &table_info.schema_name,
+        &stmt,
+    )?;
+    let view_ctx = QueryContext::with(&table_info.catalog_name, &table_info.schema_name);
+    view_ctx.set_current_user(query_ctx.current_user());
+    let mut expanding = views.to_vec();
+    expanding.push(view_name);
+    let plan = plan_sql(
+        engine_state.clone(),
+        session_state.clone(),
+        stmt,
+        view_ctx,
+        expanding,
+    )
+    .await?;
+
+    let view = ViewTable::try_new(plan, Some(definition.clone())).context(DataFusionSnafu)?;
+    Ok(Some(provider_as_source(Arc::new(view))))
+}
+
 impl ContextProvider for DfContextProviderAdapter {
     fn get_table_provider(&self, name: TableReference) -> DfResult<Arc<dyn TableSource>> {
         let table_ref = self.table_provider.resolve_table_ref(name)?;
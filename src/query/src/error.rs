@@ -236,6 +236,19 @@ pub enum Error {
     ))]
     TimeIndexNotFound { table: String, location: Location },
 
+    #[snafu(display("Failed to pass permission check, source: {}", source))]
+    Permission {
+        source: auth::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Invalid view {}: {}", view, reason))]
+    InvalidView {
+        view: String,
+        reason: String,
+        location: Location,
+    },
+
     #[snafu(display("Failed to add duration '{:?}' to SystemTime, overflowed", duration))]
     AddSystemTimeOverflow {
         duration: Duration,
@@ -261,6 +274,7 @@ impl ErrorExt for Error {
             | MissingRequiredField { .. }
             | BuildRegex { .. }
             | ConvertSchema { .. }
+            | InvalidView { .. }
             | AddSystemTimeOverflow { .. } => StatusCode::InvalidArguments,
 
             BuildBackend { .. } | ListObjects { .. } => StatusCode::StorageUnavailable,
@@ -270,6 +284,7 @@ impl ErrorExt for Error {
 
             QueryAccessDenied { .. } => StatusCode::AccessDenied,
             Catalog { source, .. } => source.status_code(),
+            Permission { source, .. } => source.status_code(),
             VectorComputation { source, .. } | ConvertDatafusionSchema { source, .. } => {
                 source.status_code()
             }
@@ -36,6 +36,7 @@ pub use crate::datafusion::DfContextProviderAdapter;
 pub use crate::query_engine::{
     QueryEngine, QueryEngineContext, QueryEngineFactory, QueryEngineRef,
 };
+pub use crate::range_select::plan::RangeSelect;
 
 #[cfg(test)]
 mod tests;
@@ -18,14 +18,18 @@ use async_trait::async_trait;
 use catalog::table_source::DfTableSourceProvider;
 use common_error::ext::BoxedError;
 use datafusion::execution::context::SessionState;
+use datafusion_expr::LogicalPlan as DfLogicalPlan;
+use datafusion_optimizer::analyzer::inline_table_scan::InlineTableScan;
+use datafusion_optimizer::analyzer::AnalyzerRule;
 use datafusion_sql::planner::{ParserOptions, SqlToRel};
+use futures::future::BoxFuture;
 use promql::planner::PromPlanner;
 use promql_parser::parser::EvalStmt;
 use session::context::QueryContextRef;
 use snafu::ResultExt;
 use sql::statements::statement::Statement;
 
-use crate::error::{PlanSqlSnafu, QueryPlanSnafu, Result, SqlSnafu};
+use crate::error::{DataFusionSnafu, PlanSqlSnafu, QueryPlanSnafu, Result, SqlSnafu};
 use crate::parser::QueryStatement;
 use crate::plan::LogicalPlan;
 use crate::query_engine::QueryEngineState;
@@ -52,23 +56,73 @@ impl DfLogicalPlanner {
     }
 
     async fn plan_sql(&self, stmt: Statement, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
-        let df_stmt = (&stmt).try_into().context(SqlSnafu)?;
+        plan_sql(
+            self.engine_state.clone(),
+            self.session_state.clone(),
+            stmt,
+            query_ctx,
+            Vec::new(),
+        )
+        .await
+        .map(LogicalPlan::DfPlan)
+    }
 
+    async fn plan_pql(&self, stmt: EvalStmt, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
         let table_provider = DfTableSourceProvider::new(
             self.engine_state.catalog_manager().clone(),
             self.engine_state.disallow_cross_schema_query(),
             query_ctx.as_ref(),
-        );
+        )
+        .with_information_schema_filter(self.engine_state.information_schema_filter(&query_ctx));
+        PromPlanner::stmt_to_plan(table_provider, stmt)
+            .await
+            .map(LogicalPlan::DfPlan)
+            .map_err(BoxedError::new)
+            .context(QueryPlanSnafu)
+    }
+}
+
+#[async_trait]
+impl LogicalPlanner for DfLogicalPlanner {
+    async fn plan(&self, stmt: QueryStatement, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
+        match stmt {
+            QueryStatement::Sql(stmt) => self.plan_sql(stmt, query_ctx).await,
+            QueryStatement::Promql(stmt) => self.plan_pql(stmt, query_ctx).await,
+        }
+    }
+}
+
+/// Plans a SQL statement into a DataFusion plan, with all the views it references expanded.
+///
+/// `views` are the full names of the views whose definitions are being planned, from the
+/// outermost one. They are used to reject views that reference themselves.
+pub(crate) fn plan_sql(
+    engine_state: Arc<QueryEngineState>,
+    session_state: SessionState,
+    stmt: Statement,
+    query_ctx: QueryContextRef,
+    views: Vec<String>,
+) -> BoxFuture<'static, Result<DfLogicalPlan>> {
+    Box::pin(async move {
+        let df_stmt = (&stmt).try_into().context(SqlSnafu)?;
+
+        let table_provider = DfTableSourceProvider::new(
+            engine_state.catalog_manager().clone(),
+            engine_state.disallow_cross_schema_query(),
+            query_ctx.as_ref(),
+        )
+        .with_information_schema_filter(engine_state.information_schema_filter(&query_ctx));
 
         let context_provider = DfContextProviderAdapter::try_new(
-            self.engine_state.clone(),
-            self.session_state.clone(),
+            engine_state,
+            session_state.clone(),
             &df_stmt,
             query_ctx,
+            views,
         )
         .await?;
 
-        let config_options = self.session_state.config().options();
+        let config_options = session_state.config().options();
         let parser_options = ParserOptions {
             enable_ident_normalization: config_options.sql_parser.enable_ident_normalization,
             parse_float_as_decimal: config_options.sql_parser.parse_float_as_decimal,
@@ -84,32 +138,13 @@ impl DfLogicalPlanner {
             };
             PlanSqlSnafu { sql }
         })?;
-        let plan = RangePlanRewriter::new(table_provider, context_provider)
+        // Replaces the scans on views with the plans of their definitions, so the rest of the
+        // planning only sees base tables.
+        let result = InlineTableScan::new()
+            .analyze(result, config_options)
+            .context(DataFusionSnafu)?;
+        RangePlanRewriter::new(table_provider, context_provider)
             .rewrite(result)
-            .await?;
-        Ok(LogicalPlan::DfPlan(plan))
-    }
-
-    async fn plan_pql(&self, stmt: EvalStmt, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
-        let table_provider = DfTableSourceProvider::new(
-            self.engine_state.catalog_manager().clone(),
-            self.engine_state.disallow_cross_schema_query(),
-            query_ctx.as_ref(),
-        );
-        PromPlanner::stmt_to_plan(table_provider, stmt)
             .await
-            .map(LogicalPlan::DfPlan)
-            .map_err(BoxedError::new)
-            .context(QueryPlanSnafu)
-    }
-}
-
-#[async_trait]
-impl LogicalPlanner for DfLogicalPlanner {
-    async fn plan(&self, stmt: QueryStatement, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
-        match stmt {
-            QueryStatement::Sql(stmt) => self.plan_sql(stmt, query_ctx).await,
-            QueryStatement::Promql(stmt) => self.plan_pql(stmt, query_ctx).await,
-        }
-    }
+    })
 }
@@ -17,6 +17,8 @@ use std::fmt;
 use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
+use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq, PermissionResp};
+use catalog::information_schema::MetadataFilterRef;
 use catalog::CatalogManagerRef;
 use client::client_manager::DatanodeClients;
 use common_base::Plugins;
@@ -39,11 +41,15 @@ use datafusion_optimizer::analyzer::Analyzer;
 use datafusion_optimizer::optimizer::Optimizer;
 use partition::manager::PartitionRuleManager;
 use promql::extension_plan::PromExtensionPlanner;
+use session::context::QueryContext;
+use snafu::ResultExt;
+use sql::statements::statement::Statement;
 use substrait::extension_serializer::ExtensionSerializer;
 use table::table::adapter::DfTableProviderAdapter;
 use table::TableRef;
 
 use crate::dist_plan::{DistExtensionPlanner, DistPlannerAnalyzer};
+use crate::error::{PermissionSnafu, Result};
 use crate::optimizer::order_hint::OrderHintRule;
 use crate::optimizer::string_normalization::StringNormalizationRule;
 use crate::optimizer::type_conversion::TypeConversionRule;
@@ -193,6 +199,54 @@ impl QueryEngineState {
             .unwrap_or(false)
     }
 
+    /// Returns the filter of schemas and tables that the current user can see in information
+    /// schema tables. Everything is visible if there is no permission checker or user.
+    pub(crate) fn information_schema_filter(
+        &self,
+        query_ctx: &QueryContext,
+    ) -> Option<MetadataFilterRef> {
+        let checker = self.plugins.get::<PermissionCheckerRef>()?;
+        let user_info = query_ctx.current_user()?;
+        Some(Arc::new(
+            move |catalog: &str, schema: &str, table: Option<&str>| {
+                matches!(
+                    checker.check_permission(
+                        Some(user_info.clone()),
+                        PermissionReq::ReadMetadata {
+                            catalog,
+                            schema,
+                            table,
+                        },
+                    ),
+                    Ok(PermissionResp::Allow)
+                )
+            },
+        ))
+    }
+
+    /// Checks that the current user can read everything the definition `stmt` of a view in
+    /// `catalog.schema` reads. Views run with the privileges of the users querying them, so
+    /// a view never grants access to tables its users can't read themselves.
+    pub(crate) fn check_view_permission(
+        &self,
+        query_ctx: &QueryContext,
+        catalog: &str,
+        schema: &str,
+        stmt: &Statement,
+    ) -> Result<()> {
+        let checker = self.plugins.get::<PermissionCheckerRef>();
+        let _ = checker
+            .as_ref()
+            .check_database_permission(
+                query_ctx.current_user(),
+                catalog,
+                schema,
+                PermissionReq::SqlStatement(stmt),
+            )
+            .context(PermissionSnafu)?;
+        Ok(())
+    }
+
     pub(crate) fn session_state(&self) -> SessionState {
         self.df_context.state()
     }
@@ -54,11 +54,130 @@ use crate::error::{DataFusionSnafu, Result};
 
 type Millisecond = <TimestampMillisecondType as ArrowPrimitiveType>::Native;
 
+/// The strategy used to fill the value of an align slot which has no data
+/// (or whose aggregated result is null) in a RangeSelect query.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum Fill {
+    /// Leave the slot as null
+    Null,
+    /// Use the value of the previous non-null slot in the same time series
+    Prev,
+    /// Linear interpolate between the nearest non-null slots on both sides
+    Linear,
+    /// Use a constant value
+    Const(ScalarValue),
+}
+
+impl Display for Fill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fill::Null => write!(f, "NULL"),
+            Fill::Prev => write!(f, "PREV"),
+            Fill::Linear => write!(f, "LINEAR"),
+            Fill::Const(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl Fill {
+    /// Parse the fill strategy from the `FILL` keyword argument.
+    /// An empty string means no fill strategy is given.
+    pub fn try_from_str(value: &str, datatype: &DataType) -> DfResult<Option<Self>> {
+        let s = value.to_uppercase();
+        match s.as_str() {
+            "" => Ok(None),
+            "NULL" => Ok(Some(Self::Null)),
+            "PREV" => Ok(Some(Self::Prev)),
+            "LINEAR" => {
+                if datatype.is_numeric() {
+                    Ok(Some(Self::Linear))
+                } else {
+                    Err(DataFusionError::Plan(format!(
+                        "Use FILL LINEAR on Non-numeric DataType {}",
+                        datatype
+                    )))
+                }
+            }
+            _ => ScalarValue::try_from_string(value.to_string(), datatype)
+                .map_err(|err| {
+                    DataFusionError::Plan(format!(
+                        "{} is not a valid fill option, fail to convert to a const value. {{ {} }}",
+                        value, err
+                    ))
+                })
+                .map(|x| Some(Fill::Const(x))),
+        }
+    }
+
+    /// Fill the null values in `data` according to the strategy.
+    /// `ts` are the align timestamps of `data`, sorted in ascending order.
+    pub fn apply_fill_strategy(
+        &self,
+        ts: &[Millisecond],
+        data: &mut [ScalarValue],
+    ) -> DfResult<()> {
+        debug_assert_eq!(ts.len(), data.len());
+        match self {
+            Fill::Null => {}
+            Fill::Prev => {
+                for i in 1..data.len() {
+                    if data[i].is_null() {
+                        data[i] = data[i - 1].clone();
+                    }
+                }
+            }
+            Fill::Linear => {
+                // index of the last non-null value
+                let mut prev = None;
+                let mut i = 0;
+                while i < data.len() {
+                    if !data[i].is_null() {
+                        prev = Some(i);
+                        i += 1;
+                        continue;
+                    }
+                    let Some(next) = (i + 1..data.len()).find(|j| !data[*j].is_null()) else {
+                        break;
+                    };
+                    if let Some(prev) = prev {
+                        let (y0, y1) = (scalar_to_f64(&data[prev])?, scalar_to_f64(&data[next])?);
+                        let (x0, x1) = (ts[prev] as f64, ts[next] as f64);
+                        let datatype = data[prev].get_datatype();
+                        for j in i..next {
+                            let y = y0 + (y1 - y0) * (ts[j] as f64 - x0) / (x1 - x0);
+                            data[j] = ScalarValue::Float64(Some(y)).cast_to(&datatype)?;
+                        }
+                    }
+                    i = next;
+                }
+            }
+            Fill::Const(value) => {
+                for x in data.iter_mut() {
+                    if x.is_null() {
+                        *x = value.clone();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn scalar_to_f64(value: &ScalarValue) -> DfResult<f64> {
+    match value.cast_to(&DataType::Float64)? {
+        ScalarValue::Float64(Some(x)) => Ok(x),
+        _ => Err(DataFusionError::Execution(format!(
+            "Fail to convert {} to f64 when applying FILL LINEAR",
+            value
+        ))),
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct RangeFn {
     pub expr: Expr,
     pub range: Duration,
-    pub fill: String,
+    pub fill: Option<Fill>,
 }
 
 impl Display for RangeFn {
@@ -68,7 +187,10 @@ impl Display for RangeFn {
             "RangeFn {{ expr:{} range:{}s fill:{} }}",
             self.expr.display_name().unwrap_or("?".into()),
             self.range.as_secs(),
-            self.fill,
+            self.fill
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
         )
     }
 }
@@ -100,9 +222,8 @@ impl RangeSelect {
                 Ok(DFField::new_unqualified(
                     &expr.display_name()?,
                     expr.get_type(input.schema())?,
-                    // TODO(Taylor-lagrange): We have not implemented fill currently,
-                    // it is possible that some columns may not be able to aggregate data,
-                    // so we temporarily set that all data is nullable
+                    // Even with a fill strategy, some align slots may still have no value
+                    // (e.g. the leading slots of `FILL PREV`), so all data is nullable
                     true,
                 ))
             })
@@ -267,6 +388,7 @@ impl RangeSelect {
                     expr,
                     args,
                     range: range_fn.range.as_millis() as Millisecond,
+                    fill: range_fn.fill.clone(),
                 })
             })
             .collect::<DfResult<Vec<_>>>()?;
@@ -293,6 +415,7 @@ struct RangeFnExec {
     pub expr: Arc<dyn AggregateExpr>,
     pub args: Vec<Arc<dyn PhysicalExpr>>,
     pub range: Millisecond,
+    pub fill: Option<Fill>,
 }
 
 #[derive(Debug)]
@@ -315,7 +438,17 @@ impl DisplayAs for RangeSelectExec {
                 let range_expr_strs: Vec<String> = self
                     .range_exec
                     .iter()
-                    .map(|e| format!("RangeFnExec{{ {}, range: {:?}}}", e.expr.name(), e.range))
+                    .map(|e| match &e.fill {
+                        Some(fill) => format!(
+                            "RangeFnExec{{ {}, range: {:?}, fill: {}}}",
+                            e.expr.name(),
+                            e.range,
+                            fill
+                        ),
+                        None => {
+                            format!("RangeFnExec{{ {}, range: {:?}}}", e.expr.name(), e.range)
+                        }
+                    })
                     .collect();
                 let by: Vec<String> = self.by.iter().map(|e| e.to_string()).collect();
                 write!(
@@ -581,23 +714,79 @@ impl RangeSelectStream {
         if self.series_map.is_empty() {
             return Ok(RecordBatch::new_empty(self.schema.clone()));
         }
+        // If any range fn has a fill strategy, every time series outputs all align slots
+        // between the first and the last align_ts appeared in the whole query result.
+        let dense_align_ts = if self.range_exec.iter().any(|e| e.fill.is_some()) {
+            let all_align_ts = self
+                .series_map
+                .values()
+                .flat_map(|state| state.align_ts_accumulator.keys());
+            match (all_align_ts.clone().min(), all_align_ts.max()) {
+                (Some(start), Some(end)) => Some(
+                    (*start..=*end)
+                        .step_by(self.align as usize)
+                        .collect::<Vec<_>>(),
+                ),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        let output_num_rows = match &dense_align_ts {
+            Some(align_ts) => align_ts.len() * self.series_map.len(),
+            None => self.output_num_rows,
+        };
+        // the null value used for align slots that have no data
+        let null_values = self
+            .schema
+            .fields()
+            .iter()
+            .take(self.range_exec.len())
+            .map(|field| ScalarValue::try_from(field.data_type()))
+            .collect::<DfResult<Vec<_>>>()?;
         // 1 for time index column
         let mut columns: Vec<Arc<dyn Array>> =
             Vec::with_capacity(1 + self.range_exec.len() + self.by.len());
-        let mut ts_builder = TimestampMillisecondBuilder::with_capacity(self.output_num_rows);
-        let mut all_scalar = vec![vec![]; self.range_exec.len()];
-        let mut by_rows = Vec::with_capacity(self.output_num_rows);
+        let mut ts_builder = TimestampMillisecondBuilder::with_capacity(output_num_rows);
+        let mut all_scalar = vec![Vec::with_capacity(output_num_rows); self.range_exec.len()];
+        let mut by_rows = Vec::with_capacity(output_num_rows);
         for SeriesState {
             row,
             align_ts_accumulator,
         } in self.series_map.values()
         {
-            for (ts, accumulators) in align_ts_accumulator {
-                for (i, accumulator) in accumulators.iter().enumerate() {
-                    all_scalar[i].push(accumulator.evaluate()?);
+            let Some(dense_align_ts) = &dense_align_ts else {
+                for (ts, accumulators) in align_ts_accumulator {
+                    for (i, accumulator) in accumulators.iter().enumerate() {
+                        all_scalar[i].push(accumulator.evaluate()?);
+                    }
+                    by_rows.push(row.row());
+                    ts_builder.append_value(*ts);
+                }
+                continue;
+            };
+            let start = by_rows.len();
+            for ts in dense_align_ts {
+                match align_ts_accumulator.get(ts) {
+                    Some(accumulators) => {
+                        for (i, accumulator) in accumulators.iter().enumerate() {
+                            all_scalar[i].push(accumulator.evaluate()?);
+                        }
+                    }
+                    None => {
+                        for (i, null_value) in null_values.iter().enumerate() {
+                            all_scalar[i].push(null_value.clone());
+                        }
+                    }
                 }
                 by_rows.push(row.row());
-                ts_builder.append_value(*ts);
+            }
+            ts_builder.append_slice(dense_align_ts);
+            // apply fill strategy on this time series
+            for (i, range_exec) in self.range_exec.iter().enumerate() {
+                if let Some(fill) = &range_exec.fill {
+                    fill.apply_fill_strategy(dense_align_ts, &mut all_scalar[i][start..])?;
+                }
             }
         }
         for column_scalar in all_scalar {
@@ -682,24 +871,42 @@ mod test {
 
     const TIME_INDEX_COLUMN: &str = "timestamp";
 
-    fn prepare_test_data() -> MemoryExec {
+    fn prepare_test_data(is_gap: bool) -> MemoryExec {
         let schema = Arc::new(Schema::new(vec![
             Field::new(TIME_INDEX_COLUMN, TimestampMillisecondType::DATA_TYPE, true),
             Field::new("value", DataType::Int64, true),
             Field::new("host", DataType::Utf8, true),
         ]));
-        let timestamp_column = Arc::new(TimestampMillisecondArray::from(vec![
-            // host 1 every 5s
-            0, 5_000, 10_000, 15_000, 20_000, 25_000, 30_000, 35_000, 40_000,
-            // host 2 every 5s
-            0, 5_000, 10_000, 15_000, 20_000, 25_000, 30_000, 35_000, 40_000,
-        ])) as _;
-        let values = vec![
-            0, 1, 2, 3, 4, 5, 6, 7, 8, // data for host 1
-            9, 10, 11, 12, 13, 14, 15, 16, 17, // data for host 2
-        ];
-        let mut host = vec!["host1"; 9];
-        host.extend(vec!["host2"; 9]);
+        let (timestamp, values, host) = if is_gap {
+            (
+                vec![
+                    // host 1 every 5s, missing data on 5s, 10s, 30s
+                    0, 15_000, 20_000, 25_000, 35_000, 40_000,
+                    // host 2 every 5s, missing data on 5s, 10s, 30s
+                    0, 15_000, 20_000, 25_000, 35_000, 40_000,
+                ],
+                vec![
+                    0, 3, 4, 5, 7, 8, // data for host 1
+                    9, 12, 13, 14, 16, 17, // data for host 2
+                ],
+                [vec!["host1"; 6], vec!["host2"; 6]].concat(),
+            )
+        } else {
+            (
+                vec![
+                    // host 1 every 5s
+                    0, 5_000, 10_000, 15_000, 20_000, 25_000, 30_000, 35_000, 40_000,
+                    // host 2 every 5s
+                    0, 5_000, 10_000, 15_000, 20_000, 25_000, 30_000, 35_000, 40_000,
+                ],
+                vec![
+                    0, 1, 2, 3, 4, 5, 6, 7, 8, // data for host 1
+                    9, 10, 11, 12, 13, 14, 15, 16, 17, // data for host 2
+                ],
+                [vec!["host1"; 9], vec!["host2"; 9]].concat(),
+            )
+        };
+        let timestamp_column = Arc::new(TimestampMillisecondArray::from(timestamp)) as _;
         let value_column = Arc::new(Int64Array::from(values)) as _;
         let host_column = Arc::new(StringArray::from(host)) as _;
         let data = RecordBatch::try_new(
@@ -715,9 +922,11 @@ mod test {
         range1: Millisecond,
         range2: Millisecond,
         align: Millisecond,
+        fill: Option<Fill>,
+        is_gap: bool,
         expected: String,
     ) {
-        let memory_exec = Arc::new(prepare_test_data());
+        let memory_exec = Arc::new(prepare_test_data(is_gap));
         let range_select_exec = Arc::new(RangeSelectExec {
             input: memory_exec,
             range_exec: vec![
@@ -729,6 +938,7 @@ mod test {
                     )),
                     args: vec![Arc::new(Column::new("value", 1))],
                     range: range1,
+                    fill: fill.clone(),
                 },
                 RangeFnExec {
                     expr: Arc::new(expressions::Max::new(
@@ -738,6 +948,7 @@ mod test {
                     )),
                     args: vec![Arc::new(Column::new("value", 1))],
                     range: range2,
+                    fill,
                 },
             ],
             align,
@@ -812,7 +1023,7 @@ mod test {
             \n| 17         | 17         | 1970-01-01T00:00:45 | host2 |\
             \n+------------+------------+---------------------+-------+",
         );
-        do_range_select_test(10_000, 10_000, 5_000, expected).await;
+        do_range_select_test(10_000, 10_000, 5_000, None, false, expected).await;
     }
 
     #[tokio::test]
@@ -825,7 +1036,7 @@ mod test {
             \n| 9          | 9          | 1970-01-01T00:00:00 | host2 |\
             \n+------------+------------+---------------------+-------+",
         );
-        do_range_select_test(10_000, 10_000, 1_000_000, expected).await;
+        do_range_select_test(10_000, 10_000, 1_000_000, None, false, expected).await;
     }
 
     #[tokio::test]
@@ -856,6 +1067,192 @@ mod test {
             \n| 17         |            | 1970-01-01T00:00:45 | host2 |\
             \n+------------+------------+---------------------+-------+",
         );
-        do_range_select_test(10_000, 5_000, 5_000, expected).await;
+        do_range_select_test(10_000, 5_000, 5_000, None, false, expected).await;
+    }
+
+    #[tokio::test]
+    async fn fill_test_null() {
+        let expected = String::from(
+            "+------------+------------+---------------------+-------+\
+            \n| MIN(value) | MAX(value) | timestamp           | host  |\
+            \n+------------+------------+---------------------+-------+\
+            \n| 0          | 0          | 1970-01-01T00:00:00 | host1 |\
+            \n|            |            | 1970-01-01T00:00:05 | host1 |\
+            \n|            |            | 1970-01-01T00:00:10 | host1 |\
+            \n| 3          | 3          | 1970-01-01T00:00:15 | host1 |\
+            \n| 4          | 4          | 1970-01-01T00:00:20 | host1 |\
+            \n| 5          | 5          | 1970-01-01T00:00:25 | host1 |\
+            \n|            |            | 1970-01-01T00:00:30 | host1 |\
+            \n| 7          | 7          | 1970-01-01T00:00:35 | host1 |\
+            \n| 8          | 8          | 1970-01-01T00:00:40 | host1 |\
+            \n| 9          | 9          | 1970-01-01T00:00:00 | host2 |\
+            \n|            |            | 1970-01-01T00:00:05 | host2 |\
+            \n|            |            | 1970-01-01T00:00:10 | host2 |\
+            \n| 12         | 12         | 1970-01-01T00:00:15 | host2 |\
+            \n| 13         | 13         | 1970-01-01T00:00:20 | host2 |\
+            \n| 14         | 14         | 1970-01-01T00:00:25 | host2 |\
+            \n|            |            | 1970-01-01T00:00:30 | host2 |\
+            \n| 16         | 16         | 1970-01-01T00:00:35 | host2 |\
+            \n| 17         | 17         | 1970-01-01T00:00:40 | host2 |\
+            \n+------------+------------+---------------------+-------+",
+        );
+        do_range_select_test(5_000, 5_000, 5_000, Some(Fill::Null), true, expected).await;
+    }
+
+    #[tokio::test]
+    async fn fill_test_prev() {
+        let expected = String::from(
+            "+------------+------------+---------------------+-------+\
+            \n| MIN(value) | MAX(value) | timestamp           | host  |\
+            \n+------------+------------+---------------------+-------+\
+            \n| 0          | 0          | 1970-01-01T00:00:00 | host1 |\
+            \n| 0          | 0          | 1970-01-01T00:00:05 | host1 |\
+            \n| 0          | 0          | 1970-01-01T00:00:10 | host1 |\
+            \n| 3          | 3          | 1970-01-01T00:00:15 | host1 |\
+            \n| 4          | 4          | 1970-01-01T00:00:20 | host1 |\
+            \n| 5          | 5          | 1970-01-01T00:00:25 | host1 |\
+            \n| 5          | 5          | 1970-01-01T00:00:30 | host1 |\
+            \n| 7          | 7          | 1970-01-01T00:00:35 | host1 |\
+            \n| 8          | 8          | 1970-01-01T00:00:40 | host1 |\
+            \n| 9          | 9          | 1970-01-01T00:00:00 | host2 |\
+            \n| 9          | 9          | 1970-01-01T00:00:05 | host2 |\
+            \n| 9          | 9          | 1970-01-01T00:00:10 | host2 |\
+            \n| 12         | 12         | 1970-01-01T00:00:15 | host2 |\
+            \n| 13         | 13         | 1970-01-01T00:00:20 | host2 |\
+            \n| 14         | 14         | 1970-01-01T00:00:25 | host2 |\
+            \n| 14         | 14         | 1970-01-01T00:00:30 | host2 |\
+            \n| 16         | 16         | 1970-01-01T00:00:35 | host2 |\
+            \n| 17         | 17         | 1970-01-01T00:00:40 | host2 |\
+            \n+------------+------------+---------------------+-------+",
+        );
+        do_range_select_test(5_000, 5_000, 5_000, Some(Fill::Prev), true, expected).await;
+    }
+
+    #[tokio::test]
+    async fn fill_test_linear() {
+        let expected = String::from(
+            "+------------+------------+---------------------+-------+\
+            \n| MIN(value) | MAX(value) | timestamp           | host  |\
+            \n+------------+------------+---------------------+-------+\
+            \n| 0          | 0          | 1970-01-01T00:00:00 | host1 |\
+            \n| 1          | 1          | 1970-01-01T00:00:05 | host1 |\
+            \n| 2          | 2          | 1970-01-01T00:00:10 | host1 |\
+            \n| 3          | 3          | 1970-01-01T00:00:15 | host1 |\
+            \n| 4          | 4          | 1970-01-01T00:00:20 | host1 |\
+            \n| 5          | 5          | 1970-01-01T00:00:25 | host1 |\
+            \n| 6          | 6          | 1970-01-01T00:00:30 | host1 |\
+            \n| 7          | 7          | 1970-01-01T00:00:35 | host1 |\
+            \n| 8          | 8          | 1970-01-01T00:00:40 | host1 |\
+            \n| 9          | 9          | 1970-01-01T00:00:00 | host2 |\
+            \n| 10         | 10         | 1970-01-01T00:00:05 | host2 |\
+            \n| 11         | 11         | 1970-01-01T00:00:10 | host2 |\
+            \n| 12         | 12         | 1970-01-01T00:00:15 | host2 |\
+            \n| 13         | 13         | 1970-01-01T00:00:20 | host2 |\
+            \n| 14         | 14         | 1970-01-01T00:00:25 | host2 |\
+            \n| 15         | 15         | 1970-01-01T00:00:30 | host2 |\
+            \n| 16         | 16         | 1970-01-01T00:00:35 | host2 |\
+            \n| 17         | 17         | 1970-01-01T00:00:40 | host2 |\
+            \n+------------+------------+---------------------+-------+",
+        );
+        do_range_select_test(5_000, 5_000, 5_000, Some(Fill::Linear), true, expected).await;
+    }
+
+    #[tokio::test]
+    async fn fill_test_const() {
+        let expected = String::from(
+            "+------------+------------+---------------------+-------+\
+            \n| MIN(value) | MAX(value) | timestamp           | host  |\
+            \n+------------+------------+---------------------+-------+\
+            \n| 0          | 0          | 1970-01-01T00:00:00 | host1 |\
+            \n| 6          | 6          | 1970-01-01T00:00:05 | host1 |\
+            \n| 6          | 6          | 1970-01-01T00:00:10 | host1 |\
+            \n| 3          | 3          | 1970-01-01T00:00:15 | host1 |\
+            \n| 4          | 4          | 1970-01-01T00:00:20 | host1 |\
+            \n| 5          | 5          | 1970-01-01T00:00:25 | host1 |\
+            \n| 6          | 6          | 1970-01-01T00:00:30 | host1 |\
+            \n| 7          | 7          | 1970-01-01T00:00:35 | host1 |\
+            \n| 8          | 8          | 1970-01-01T00:00:40 | host1 |\
+            \n| 9          | 9          | 1970-01-01T00:00:00 | host2 |\
+            \n| 6          | 6          | 1970-01-01T00:00:05 | host2 |\
+            \n| 6          | 6          | 1970-01-01T00:00:10 | host2 |\
+            \n| 12         | 12         | 1970-01-01T00:00:15 | host2 |\
+            \n| 13         | 13         | 1970-01-01T00:00:20 | host2 |\
+            \n| 14         | 14         | 1970-01-01T00:00:25 | host2 |\
+            \n| 6          | 6          | 1970-01-01T00:00:30 | host2 |\
+            \n| 16         | 16         | 1970-01-01T00:00:35 | host2 |\
+            \n| 17         | 17         | 1970-01-01T00:00:40 | host2 |\
+            \n+------------+------------+---------------------+-------+",
+        );
+        do_range_select_test(
+            5_000,
+            5_000,
+            5_000,
+            Some(Fill::Const(ScalarValue::Int64(Some(6)))),
+            true,
+            expected,
+        )
+        .await;
+    }
+
+    #[test]
+    fn fill_test() {
+        assert!(Fill::try_from_str("Linear", &DataType::UInt8).unwrap() == Some(Fill::Linear));
+        assert_eq!(
+            Fill::try_from_str("Linear", &DataType::Boolean)
+                .unwrap_err()
+                .to_string(),
+            "Error during planning: Use FILL LINEAR on Non-numeric DataType Boolean"
+        );
+        assert!(Fill::try_from_str("WHAT", &DataType::UInt8).is_err());
+        assert!(Fill::try_from_str("8.0", &DataType::UInt8).is_err());
+        assert!(
+            Fill::try_from_str("8", &DataType::UInt8).unwrap()
+                == Some(Fill::Const(ScalarValue::UInt8(Some(8))))
+        );
+        assert!(Fill::try_from_str("", &DataType::UInt8).unwrap().is_none());
+        let mut test1 = vec![
+            ScalarValue::UInt8(Some(8)),
+            ScalarValue::UInt8(None),
+            ScalarValue::UInt8(Some(9)),
+            ScalarValue::UInt8(None),
+            ScalarValue::UInt8(None),
+            ScalarValue::UInt8(Some(12)),
+            ScalarValue::UInt8(None),
+        ];
+        Fill::Linear
+            .apply_fill_strategy(&[1, 2, 3, 4, 5, 6, 7], &mut test1)
+            .unwrap();
+        assert_eq!(
+            test1,
+            vec![
+                ScalarValue::UInt8(Some(8)),
+                ScalarValue::UInt8(Some(8)),
+                ScalarValue::UInt8(Some(9)),
+                ScalarValue::UInt8(Some(10)),
+                ScalarValue::UInt8(Some(11)),
+                ScalarValue::UInt8(Some(12)),
+                ScalarValue::UInt8(None),
+            ]
+        );
+        let mut test2 = vec![
+            ScalarValue::Float64(None),
+            ScalarValue::Float64(Some(1.0)),
+            ScalarValue::Float64(None),
+            ScalarValue::Float64(None),
+            ScalarValue::Float64(Some(4.0)),
+        ];
+        Fill::Prev
+            .apply_fill_strategy(&[0, 5, 10, 15, 20], &mut test2)
+            .unwrap();
+        assert_eq!(
+            test2,
+            vec![
+                ScalarValue::Float64(None),
+                ScalarValue::Float64(Some(1.0)),
+                ScalarValue::Float64(Some(1.0)),
+                ScalarValue::Float64(Some(1.0)),
+                ScalarValue::Float64(Some(4.0)),
+            ]
+        );
     }
 }
@@ -25,7 +25,8 @@ use datafusion_common::tree_node::{TreeNode, TreeNodeRewriter, VisitRecursion};
 use datafusion_common::{DFSchema, DataFusionError, Result as DFResult};
 use datafusion_expr::expr::{AggregateFunction, AggregateUDF, ScalarUDF};
 use datafusion_expr::{
-    AggregateFunction as AggregateFn, Expr, Extension, LogicalPlan, LogicalPlanBuilder, Projection,
+    AggregateFunction as AggregateFn, Expr, ExprSchemable, Extension, LogicalPlan,
+    LogicalPlanBuilder, Projection,
 };
 use datafusion_sql::planner::ContextProvider;
 use datatypes::prelude::ConcreteDataType;
@@ -36,13 +37,14 @@ use table::table::adapter::DfTableProviderAdapter;
 use crate::error::{
     CatalogSnafu, DataFusionSnafu, Result, TimeIndexNotFoundSnafu, UnknownTableSnafu,
 };
-use crate::range_select::plan::{RangeFn, RangeSelect};
+use crate::range_select::plan::{Fill, RangeFn, RangeSelect};
 use crate::DfContextProviderAdapter;
 
 /// `RangeExprRewriter` will recursively search certain `Expr`, find all `range_fn` scalar udf contained in `Expr`,
 /// and collect the information required by the RangeSelect query,
 /// and finally modify the `range_fn` scalar udf to an ordinary column field.
 pub struct RangeExprRewriter<'a> {
+    input_plan: &'a Arc<LogicalPlan>,
     align: Duration,
     by: Vec<Expr>,
     range_fn: Vec<RangeFn>,
@@ -111,12 +113,9 @@ impl<'a> TreeNodeRewriter for RangeExprRewriter<'a> {
                     .map_err(|e| DataFusionError::Plan(e.to_string()))?;
                 let byc = str::parse::<usize>(parse_str_expr(&func.args, argc + 4)?)
                     .map_err(|e| DataFusionError::Plan(e.to_string()))?;
-                let mut range_fn = RangeFn {
-                    expr: Expr::Wildcard,
-                    range: parse_duration(parse_str_expr(&func.args, argc + 2)?)
-                        .map_err(DataFusionError::Plan)?,
-                    fill: parse_str_expr(&func.args, argc + 3)?.to_string(),
-                };
+                let range = parse_duration(parse_str_expr(&func.args, argc + 2)?)
+                    .map_err(DataFusionError::Plan)?;
+                let fill = parse_str_expr(&func.args, argc + 3)?;
                 let args = parse_expr_list(&func.args, 2, argc)?;
                 let by = parse_expr_list(&func.args, argc + 5, byc)?;
                 let align = parse_duration(parse_str_expr(&func.args, argc + byc + 5)?)
@@ -135,9 +134,15 @@ impl<'a> TreeNodeRewriter for RangeExprRewriter<'a> {
                 } else {
                     self.align = align;
                 }
-                range_fn.expr = self.gen_range_expr(func_name, args)?;
-                let alias = Expr::Column(Column::from_name(range_fn.expr.display_name()?));
-                self.range_fn.push(range_fn);
+                let range_expr = self.gen_range_expr(func_name, args)?;
+                let fill =
+                    Fill::try_from_str(fill, &range_expr.get_type(self.input_plan.schema())?)?;
+                let alias = Expr::Column(Column::from_name(range_expr.display_name()?));
+                self.range_fn.push(RangeFn {
+                    expr: range_expr,
+                    range,
+                    fill,
+                });
                 return Ok(alias);
             }
         }
@@ -192,6 +197,7 @@ impl RangePlanRewriter {
                 };
                 let (time_index, default_by) = self.get_index_by(input.schema().clone()).await?;
                 let mut range_rewriter = RangeExprRewriter {
+                    input_plan: &input,
                     align: Duration::default(),
                     by: vec![],
                     range_fn: vec![],
@@ -39,6 +39,7 @@ use sql::ast::ColumnDef;
 use sql::statements::column_def_to_schema;
 use sql::statements::create::Partitions;
 use sql::statements::show::{ShowDatabases, ShowKind, ShowTables};
+use table::metadata::TableType;
 use table::requests::{IMMUTABLE_TABLE_LOCATION_KEY, IMMUTABLE_TABLE_PATTERN_KEY};
 use table::TableRef;
 
@@ -47,6 +48,7 @@ use crate::error::{self, Result};
 
 const SCHEMAS_COLUMN: &str = "Schemas";
 const TABLES_COLUMN: &str = "Tables";
+const TABLE_TYPE_COLUMN: &str = "Table_type";
 const COLUMN_NAME_COLUMN: &str = "Field";
 const COLUMN_TYPE_COLUMN: &str = "Type";
 const COLUMN_NULLABLE_COLUMN: &str = "Null";
@@ -93,6 +95,13 @@ static SHOW_CREATE_TABLE_OUTPUT_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
     ]))
 });
 
+static SHOW_CREATE_VIEW_OUTPUT_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![
+        ColumnSchema::new("View", ConcreteDataType::string_datatype(), false),
+        ColumnSchema::new("Create View", ConcreteDataType::string_datatype(), false),
+    ]))
+});
+
 pub async fn show_databases(
     stmt: ShowDatabases,
     catalog_manager: CatalogManagerRef,
@@ -153,20 +162,59 @@ pub async fn show_tables(
 
     // TODO(dennis): Specify the order of the results in schema provider API
     tables.sort();
-    let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+
+    // `SHOW FULL TABLES` also outputs the type of each table, like MySQL does.
+    let table_types = if stmt.full {
+        let mut table_types = HashMap::with_capacity(tables.len());
+        for table_name in &tables {
+            let table_type = catalog_manager
+                .table(query_ctx.current_catalog(), &schema, table_name)
+                .await
+                .context(error::CatalogSnafu)?
+                .map_or(TableType::Base, |table| table.table_type());
+            let _ = table_types.insert(table_name.clone(), table_type_name(table_type));
+        }
+        Some(table_types)
+    } else {
+        None
+    };
+    let table_types_column = |tables: &[String]| -> Option<VectorRef> {
+        table_types.as_ref().map(|table_types| {
+            Arc::new(StringVector::from_iterator(
+                tables.iter().map(|table| table_types[table]),
+            )) as _
+        })
+    };
+
+    let mut column_schemas = vec![ColumnSchema::new(
         TABLES_COLUMN,
         ConcreteDataType::string_datatype(),
         false,
-    )]));
+    )];
+    if stmt.full {
+        column_schemas.push(ColumnSchema::new(
+            TABLE_TYPE_COLUMN,
+            ConcreteDataType::string_datatype(),
+            false,
+        ));
+    }
+    let schema = Arc::new(Schema::new(column_schemas));
     match stmt.kind {
         ShowKind::All => {
+            let types = table_types_column(&tables);
             let tables = Arc::new(StringVector::from(tables)) as _;
-            let records = RecordBatches::try_from_columns(schema, vec![tables])
-                .context(error::CreateRecordBatchSnafu)?;
+            let records = RecordBatches::try_from_columns(
+                schema,
+                std::iter::once(tables).chain(types).collect(),
+            )
+            .context(error::CreateRecordBatchSnafu)?;
             Ok(Output::RecordBatches(records))
         }
         ShowKind::Where(filter) => {
-            let columns = vec![Arc::new(StringVector::from(tables)) as _];
+            let types = table_types_column(&tables);
+            let columns = std::iter::once(Arc::new(StringVector::from(tables)) as _)
+                .chain(types)
+                .collect();
             let record_batch =
                 RecordBatch::new(schema, columns).context(error::CreateRecordBatchSnafu)?;
             let result = execute_show_with_filter(record_batch, Some(filter)).await?;
@@ -175,13 +223,32 @@ pub async fn show_tables(
         ShowKind::Like(ident) => {
             let tables =
                 Helper::like_utf8(tables, &ident.value).context(error::VectorComputationSnafu)?;
-            let records = RecordBatches::try_from_columns(schema, vec![tables])
-                .context(error::CreateRecordBatchSnafu)?;
+            let types = table_types_column(
+                &(0..tables.len())
+                    .filter_map(|i| {
+                        let table = tables.get_ref(i);
+                        table.as_string().ok().flatten().map(str::to_string)
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            let records = RecordBatches::try_from_columns(
+                schema,
+                std::iter::once(tables).chain(types).collect(),
+            )
+            .context(error::CreateRecordBatchSnafu)?;
             Ok(Output::RecordBatches(records))
         }
     }
 }
 
+fn table_type_name(table_type: TableType) -> &'static str {
+    match table_type {
+        TableType::Base => "BASE TABLE",
+        TableType::View => "VIEW",
+        TableType::Temporary => "LOCAL TEMPORARY",
+    }
+}
+
 pub fn show_create_table(table: TableRef, partitions: Option<Partitions>) -> Result<Output> {
     let table_info = table.table_info();
     let table_name = &table_info.name;
@@ -198,6 +265,28 @@ pub fn show_create_table(table: TableRef, partitions: Option<Partitions>) -> Res
     Ok(Output::RecordBatches(records))
 }
 
+pub fn show_create_view(view: TableRef) -> Result<Output> {
+    let table_info = view.table_info();
+    let view_name = &table_info.name;
+    let definition = table_info
+        .meta
+        .view_definition
+        .as_ref()
+        .context(error::InvalidViewSnafu {
+            view: view_name,
+            reason: "missing view definition",
+        })?;
+    let sql = format!("CREATE VIEW {view_name} AS {definition}");
+    let columns = vec![
+        Arc::new(StringVector::from(vec![view_name.clone()])) as _,
+        Arc::new(StringVector::from(vec![sql])) as _,
+    ];
+    let records = RecordBatches::try_from_columns(SHOW_CREATE_VIEW_OUTPUT_SCHEMA.clone(), columns)
+        .context(error::CreateRecordBatchSnafu)?;
+
+    Ok(Output::RecordBatches(records))
+}
+
 pub fn describe_table(table: TableRef) -> Result<Output> {
     let table_info = table.table_info();
     let columns_schemas = table_info.meta.schema.column_schemas();
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use auth::{PermissionChecker, PermissionCheckerRef, PermissionReq, PermissionResp, UserInfoRef};
 use catalog::local::MemoryCatalogManager;
 use catalog::RegisterTableRequest;
 use common_base::Plugins;
@@ -33,7 +34,7 @@ use table::table::adapter::DfTableProviderAdapter;
 use table::table::numbers::{NumbersTable, NUMBERS_TABLE_NAME};
 use table::test_util::MemTable;
 
-use crate::error::{QueryExecutionSnafu, Result};
+use crate::error::{Error, QueryExecutionSnafu, Result};
 use crate::parser::QueryLanguageParser;
 use crate::plan::LogicalPlan;
 use crate::query_engine::options::QueryOptions;
@@ -148,6 +149,70 @@ async fn test_query_validate() -> Result<()> {
     Ok(())
 }
 
+/// Rejects every statement of the user "guest".
+struct GuestRejecter;
+
+impl PermissionChecker for GuestRejecter {
+    fn check_permission(
+        &self,
+        user_info: Option<UserInfoRef>,
+        _req: PermissionReq,
+    ) -> auth::error::Result<PermissionResp> {
+        if user_info.is_some_and(|user| user.username() == "guest") {
+            Ok(PermissionResp::Reject)
+        } else {
+            Ok(PermissionResp::Allow)
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_view_permission() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let catalog_list = catalog_manager()?;
+    let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+        "number",
+        ConcreteDataType::uint32_datatype(),
+        false,
+    )]));
+    let columns: Vec<VectorRef> = vec![Arc::new(UInt32Vector::from_slice(Vec::<u32>::new()))];
+    let view = MemTable::new_with_catalog(
+        "numbers_view",
+        RecordBatch::new(schema, columns).unwrap(),
+        NUMBERS_TABLE_ID + 1,
+        DEFAULT_CATALOG_NAME.to_string(),
+        DEFAULT_SCHEMA_NAME.to_string(),
+        vec![],
+    )
+    .into_view("select number from numbers");
+    let req = RegisterTableRequest {
+        catalog: DEFAULT_CATALOG_NAME.to_string(),
+        schema: DEFAULT_SCHEMA_NAME.to_string(),
+        table_name: "numbers_view".to_string(),
+        table_id: NUMBERS_TABLE_ID + 1,
+        table: Arc::new(view),
+    };
+    let _ = catalog_list.register_table_sync(req).unwrap();
+
+    let plugins = Plugins::new();
+    plugins.insert::<PermissionCheckerRef>(Arc::new(GuestRejecter));
+    let factory =
+        QueryEngineFactory::new_with_plugins(catalog_list, false, None, None, Arc::new(plugins));
+    let engine = factory.query_engine();
+
+    let plan = |user: &str| {
+        let stmt = QueryLanguageParser::parse_sql("select * from numbers_view limit 10").unwrap();
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_current_user(Some(auth::userinfo_by_name(Some(user.to_string()))));
+        let planner = engine.planner();
+        async move { planner.plan(stmt, query_ctx).await }
+    };
+    // Views are expanded with the privileges of the user querying them.
+    assert!(plan("admin").await.is_ok());
+    assert!(matches!(plan("guest").await, Err(Error::Permission { .. })));
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_udf() -> Result<()> {
     common_telemetry::init_default_ut_logging();
@@ -140,6 +140,18 @@ pub enum Error {
         source: common_grpc::error::Error,
     },
 
+    #[snafu(display("Failed to write OTLP traces, source: {}", source))]
+    OtlpTracesWrite {
+        location: Location,
+        source: common_grpc::error::Error,
+    },
+
+    #[snafu(display("Failed to write OTLP logs, source: {}", source))]
+    OtlpLogsWrite {
+        location: Location,
+        source: common_grpc::error::Error,
+    },
+
     #[snafu(display("Failed to convert time precision, name: {}", name))]
     TimePrecision { name: String, location: Location },
 
@@ -391,7 +403,9 @@ impl ErrorExt for Error {
 
             InfluxdbLinesWrite { source, .. }
             | PromSeriesWrite { source, .. }
-            | OtlpMetricsWrite { source, .. } => source.status_code(),
+            | OtlpMetricsWrite { source, .. }
+            | OtlpTracesWrite { source, .. }
+            | OtlpLogsWrite { source, .. } => source.status_code(),
 
             Hyper { .. } => StatusCode::Unknown,
             TlsRequired { .. } => StatusCode::Unknown,
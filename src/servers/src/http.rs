@@ -75,7 +75,8 @@ use crate::metrics::{
 };
 use crate::metrics_handler::MetricsHandler;
 use crate::prometheus::{
-    instant_query, label_values_query, labels_query, range_query, series_query,
+    alerts_query, build_info_query, exemplars_query, format_query, instant_query,
+    label_values_query, labels_query, metadata_query, range_query, rules_query, series_query,
     PrometheusHandlerRef,
 };
 use crate::query_handler::grpc::ServerGrpcQueryHandlerRef;
@@ -676,6 +677,18 @@ impl HttpServer {
                 "/label/:label_name/values",
                 routing::get(label_values_query),
             )
+            .route("/metadata", routing::get(metadata_query))
+            .route("/status/buildinfo", routing::get(build_info_query))
+            .route("/rules", routing::get(rules_query))
+            .route("/alerts", routing::get(alerts_query))
+            .route(
+                "/query_exemplars",
+                routing::post(exemplars_query).get(exemplars_query),
+            )
+            .route(
+                "/format_query",
+                routing::post(format_query).get(format_query),
+            )
             .with_state(prometheus_handler)
     }
 
@@ -704,6 +717,8 @@ impl HttpServer {
     fn route_otlp<S>(&self, otlp_handler: OpenTelemetryProtocolHandlerRef) -> Router<S> {
         Router::new()
             .route("/v1/metrics", routing::post(otlp::metrics))
+            .route("/v1/traces", routing::post(otlp::traces))
+            .route("/v1/logs", routing::post(otlp::logs))
             .with_state(otlp_handler)
     }
 
@@ -50,3 +50,81 @@ impl GreptimeDbName {
         self.0.as_ref()
     }
 }
+
+pub static GREPTIME_TRACE_TABLE_NAME_HEADER_NAME: HeaderName =
+    HeaderName::from_static("x-greptime-trace-table-name");
+
+/// The table to write OTLP traces into.
+pub struct GreptimeTraceTableName(Option<String>);
+
+impl Header for GreptimeTraceTableName {
+    fn name() -> &'static HeaderName {
+        &GREPTIME_TRACE_TABLE_NAME_HEADER_NAME
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, headers::Error>
+    where
+        Self: Sized,
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        if let Some(value) = values.next() {
+            let str_value = value.to_str().map_err(|_| headers::Error::invalid())?;
+            Ok(Self(Some(str_value.to_owned())))
+        } else {
+            Ok(Self(None))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        if let Some(name) = &self.0 {
+            if let Ok(value) = HeaderValue::from_str(name) {
+                values.extend(std::iter::once(value));
+            }
+        }
+    }
+}
+
+impl GreptimeTraceTableName {
+    pub fn value(&self) -> Option<&String> {
+        self.0.as_ref()
+    }
+}
+
+pub static GREPTIME_LOG_TABLE_NAME_HEADER_NAME: HeaderName =
+    HeaderName::from_static("x-greptime-log-table-name");
+
+/// The table to write OTLP logs into.
+pub struct GreptimeLogTableName(Option<String>);
+
+impl Header for GreptimeLogTableName {
+    fn name() -> &'static HeaderName {
+        &GREPTIME_LOG_TABLE_NAME_HEADER_NAME
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, headers::Error>
+    where
+        Self: Sized,
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        if let Some(value) = values.next() {
+            let str_value = value.to_str().map_err(|_| headers::Error::invalid())?;
+            Ok(Self(Some(str_value.to_owned())))
+        } else {
+            Ok(Self(None))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        if let Some(name) = &self.0 {
+            if let Ok(value) = HeaderValue::from_str(name) {
+                values.extend(std::iter::once(value));
+            }
+        }
+    }
+}
+
+impl GreptimeLogTableName {
+    pub fn value(&self) -> Option<&String> {
+        self.0.as_ref()
+    }
+}
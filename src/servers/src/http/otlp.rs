@@ -19,15 +19,23 @@ use axum::response::IntoResponse;
 use axum::{Extension, TypedHeader};
 use common_telemetry::timer;
 use hyper::Body;
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse,
+};
 use opentelemetry_proto::tonic::collector::metrics::v1::{
     ExportMetricsServiceRequest, ExportMetricsServiceResponse,
 };
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
 use prost::Message;
 use session::context::QueryContext;
 use snafu::prelude::*;
 
 use crate::error::{self, Result};
-use crate::http::header::GreptimeDbName;
+use crate::http::header::{GreptimeDbName, GreptimeLogTableName, GreptimeTraceTableName};
+use crate::otlp::logs::LOG_TABLE_NAME;
+use crate::otlp::trace::TRACE_TABLE_NAME;
 use crate::query_handler::OpenTelemetryProtocolHandlerRef;
 
 #[axum_macros::debug_handler]
@@ -36,29 +44,75 @@ pub async fn metrics(
     TypedHeader(db): TypedHeader<GreptimeDbName>,
     user_info: Extension<UserInfoRef>,
     RawBody(body): RawBody,
-) -> Result<OtlpResponse> {
+) -> Result<OtlpResponse<ExportMetricsServiceResponse>> {
     let ctx = QueryContext::with_db_name(db.value());
     ctx.set_current_user(Some(user_info.0));
     let _timer = timer!(
         crate::metrics::METRIC_HTTP_OPENTELEMETRY_ELAPSED,
         &[(crate::metrics::METRIC_DB_LABEL, ctx.get_db_string())]
     );
-    let request = parse_body(body).await?;
+    let request = parse_body::<ExportMetricsServiceRequest>(body).await?;
     handler.metrics(request, ctx).await.map(OtlpResponse)
 }
 
-async fn parse_body(body: Body) -> Result<ExportMetricsServiceRequest> {
+#[axum_macros::debug_handler]
+pub async fn traces(
+    State(handler): State<OpenTelemetryProtocolHandlerRef>,
+    TypedHeader(db): TypedHeader<GreptimeDbName>,
+    table_name: Option<TypedHeader<GreptimeTraceTableName>>,
+    user_info: Extension<UserInfoRef>,
+    RawBody(body): RawBody,
+) -> Result<OtlpResponse<ExportTraceServiceResponse>> {
+    let ctx = QueryContext::with_db_name(db.value());
+    ctx.set_current_user(Some(user_info.0));
+    let _timer = timer!(
+        crate::metrics::METRIC_HTTP_OPENTELEMETRY_TRACES_ELAPSED,
+        &[(crate::metrics::METRIC_DB_LABEL, ctx.get_db_string())]
+    );
+    let table_name = table_name
+        .and_then(|TypedHeader(name)| name.value().cloned())
+        .unwrap_or_else(|| TRACE_TABLE_NAME.to_string());
+    let request = parse_body::<ExportTraceServiceRequest>(body).await?;
+    handler
+        .traces(request, table_name, ctx)
+        .await
+        .map(OtlpResponse)
+}
+
+#[axum_macros::debug_handler]
+pub async fn logs(
+    State(handler): State<OpenTelemetryProtocolHandlerRef>,
+    TypedHeader(db): TypedHeader<GreptimeDbName>,
+    table_name: Option<TypedHeader<GreptimeLogTableName>>,
+    user_info: Extension<UserInfoRef>,
+    RawBody(body): RawBody,
+) -> Result<OtlpResponse<ExportLogsServiceResponse>> {
+    let ctx = QueryContext::with_db_name(db.value());
+    ctx.set_current_user(Some(user_info.0));
+    let _timer = timer!(
+        crate::metrics::METRIC_HTTP_OPENTELEMETRY_LOGS_ELAPSED,
+        &[(crate::metrics::METRIC_DB_LABEL, ctx.get_db_string())]
+    );
+    let table_name = table_name
+        .and_then(|TypedHeader(name)| name.value().cloned())
+        .unwrap_or_else(|| LOG_TABLE_NAME.to_string());
+    let request = parse_body::<ExportLogsServiceRequest>(body).await?;
+    handler
+        .logs(request, table_name, ctx)
+        .await
+        .map(OtlpResponse)
+}
+
+async fn parse_body<T: Message + Default>(body: Body) -> Result<T> {
     hyper::body::to_bytes(body)
         .await
         .context(error::HyperSnafu)
-        .and_then(|buf| {
-            ExportMetricsServiceRequest::decode(&buf[..]).context(error::DecodeOtlpRequestSnafu)
-        })
+        .and_then(|buf| T::decode(&buf[..]).context(error::DecodeOtlpRequestSnafu))
 }
 
-pub struct OtlpResponse(ExportMetricsServiceResponse);
+pub struct OtlpResponse<T>(T);
 
-impl IntoResponse for OtlpResponse {
+impl<T: Message> IntoResponse for OtlpResponse<T> {
     fn into_response(self) -> axum::response::Response {
         (
             [(header::CONTENT_TYPE, "application/x-protobuf")],
@@ -38,6 +38,9 @@ pub(crate) const METRIC_HTTP_PROM_STORE_WRITE_ELAPSED: &str =
     "servers.http_prometheus_write_elapsed";
 pub(crate) const METRIC_HTTP_PROM_STORE_READ_ELAPSED: &str = "servers.http_prometheus_read_elapsed";
 pub(crate) const METRIC_HTTP_OPENTELEMETRY_ELAPSED: &str = "servers.http_otlp_elapsed";
+pub(crate) const METRIC_HTTP_OPENTELEMETRY_TRACES_ELAPSED: &str =
+    "servers.http_otlp_traces_elapsed";
+pub(crate) const METRIC_HTTP_OPENTELEMETRY_LOGS_ELAPSED: &str = "servers.http_otlp_logs_elapsed";
 pub(crate) const METRIC_TCP_OPENTSDB_LINE_WRITE_ELAPSED: &str =
     "servers.opentsdb_line_write_elapsed";
 pub(crate) const METRIC_HTTP_PROMQL_INSTANT_QUERY_ELAPSED: &str =
@@ -50,6 +53,8 @@ pub(crate) const METRIC_HTTP_PROMQL_SERIES_QUERY_ELAPSED: &str =
     "servers.http_promql_series_query_elapsed";
 pub(crate) const METRIC_HTTP_PROMQL_LABEL_VALUE_QUERY_ELAPSED: &str =
     "servers.http_promql_label_value_query_elapsed";
+pub(crate) const METRIC_HTTP_PROMQL_METADATA_QUERY_ELAPSED: &str =
+    "servers.http_promql_metadata_query_elapsed";
 
 pub(crate) const METRIC_MYSQL_CONNECTIONS: &str = "servers.mysql_connection_count";
 pub(crate) const METRIC_MYSQL_QUERY_TIMER: &str = "servers.mysql_query_elapsed";
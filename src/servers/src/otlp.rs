@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod logs;
+pub mod trace;
+mod utils;
+
 use api::v1::{InsertRequest, InsertRequests};
 use common_grpc::writer::{LinesWriter, Precision};
 use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
@@ -23,6 +27,13 @@ use crate::error::{self, Result};
 
 const GREPTIME_TIMESTAMP: &str = "greptime_timestamp";
 const GREPTIME_VALUE: &str = "greptime_value";
+/// The tag of the upper bound of histogram buckets, as Prometheus does.
+const LE_TAG: &str = "le";
+/// The tag of the quantile of summaries, as Prometheus does.
+const QUANTILE_TAG: &str = "quantile";
+const BUCKET_TABLE_SUFFIX: &str = "_bucket";
+const SUM_TABLE_SUFFIX: &str = "_sum";
+const COUNT_TABLE_SUFFIX: &str = "_count";
 
 /// Normalize otlp instrumentation, metric and attribute names
 ///
@@ -52,7 +63,7 @@ pub fn to_grpc_insert_requests(
         for scope in resource.scope_metrics {
             let scope_attrs = scope.scope.map(|s| s.attributes);
             for metric in scope.metrics {
-                if let Some(insert) =
+                for insert in
                     encode_metrics(&metric, resource_attrs.as_ref(), scope_attrs.as_ref())?
                 {
                     rows += insert.row_count;
@@ -73,25 +84,30 @@ fn encode_metrics(
     metric: &Metric,
     resource_attrs: Option<&Vec<KeyValue>>,
     scope_attrs: Option<&Vec<KeyValue>>,
-) -> Result<Option<InsertRequest>> {
+) -> Result<Vec<InsertRequest>> {
     let name = &metric.name;
     // note that we don't store description or unit, we might want to deal with
     // these fields in the future.
     if let Some(data) = &metric.data {
         match data {
             metric::Data::Gauge(gauge) => {
-                encode_gauge(name, gauge, resource_attrs, scope_attrs).map(Some)
+                encode_gauge(name, gauge, resource_attrs, scope_attrs).map(|r| vec![r])
+            }
+            metric::Data::Sum(sum) => {
+                encode_sum(name, sum, resource_attrs, scope_attrs).map(|r| vec![r])
             }
-            metric::Data::Sum(sum) => encode_sum(name, sum, resource_attrs, scope_attrs).map(Some),
             metric::Data::Summary(summary) => {
-                encode_summary(name, summary, resource_attrs, scope_attrs).map(Some)
+                encode_summary(name, summary, resource_attrs, scope_attrs)
+            }
+            metric::Data::Histogram(hist) => {
+                encode_histogram(name, hist, resource_attrs, scope_attrs)
+            }
+            metric::Data::ExponentialHistogram(hist) => {
+                encode_exponential_histogram(name, hist, resource_attrs, scope_attrs)
             }
-            // TODO(sunng87) leave histogram for next release
-            metric::Data::Histogram(_hist) => Ok(None),
-            metric::Data::ExponentialHistogram(_hist) => Ok(None),
         }
     } else {
-        Ok(None)
+        Ok(vec![])
     }
 }
 
@@ -214,147 +230,227 @@ fn encode_sum(
     })
 }
 
-// TODO(sunng87): we may need better implementation for histogram
-#[allow(dead_code)]
-fn encode_histogram(name: &str, hist: &Histogram) -> Result<InsertRequest> {
-    let mut lines = LinesWriter::with_lines(hist.data_points.len());
+/// The rows of a histogram or summary metric, they are written into multiple tables like
+/// Prometheus does, so that functions like `histogram_quantile` work on them:
+/// - `<name>_bucket` (for histograms) or `<name>` (for summaries): one row per bucket or
+/// quantile, with the `le` or `quantile` tag
+/// - `<name>_sum`: the sum of observations, if present
+/// - `<name>_count`: the count of observations
+struct DistributionLines {
+    values: LinesWriter,
+    sum: LinesWriter,
+    count: LinesWriter,
+}
 
-    for data_point in &hist.data_points {
-        for attr in &data_point.attributes {
-            write_attribute(&mut lines, attr)?;
+impl DistributionLines {
+    fn with_lines(lines: usize) -> Self {
+        Self {
+            values: LinesWriter::with_lines(lines),
+            sum: LinesWriter::with_lines(lines),
+            count: LinesWriter::with_lines(lines),
         }
+    }
 
-        write_timestamp(&mut lines, data_point.time_unix_nano as i64)?;
+    fn finish(self, name: &str, values_table_suffix: &str) -> Vec<InsertRequest> {
+        let name = normalize_otlp_name(name);
+        [
+            (values_table_suffix, self.values),
+            (SUM_TABLE_SUFFIX, self.sum),
+            (COUNT_TABLE_SUFFIX, self.count),
+        ]
+        .into_iter()
+        .filter_map(|(suffix, lines)| {
+            let (columns, row_count) = lines.finish();
+            (row_count > 0).then(|| InsertRequest {
+                table_name: format!("{name}{suffix}"),
+                region_number: 0,
+                columns,
+                row_count,
+            })
+        })
+        .collect()
+    }
+}
 
-        for (idx, count) in data_point.bucket_counts.iter().enumerate() {
-            // here we don't store bucket boundary
-            lines
-                .write_u64(&format!("bucket_{}", idx), *count)
-                .context(error::OtlpMetricsWriteSnafu)?;
-        }
+/// Attributes and the timestamp shared by all rows of a data point.
+struct DataPointContext<'a> {
+    resource_attrs: Option<&'a Vec<KeyValue>>,
+    scope_attrs: Option<&'a Vec<KeyValue>>,
+    attrs: &'a Vec<KeyValue>,
+    time_nano: i64,
+}
 
-        if let Some(min) = data_point.min {
+impl DataPointContext<'_> {
+    /// Writes a row with `value` and an optional extra tag, like `le` of buckets.
+    fn write_row(
+        &self,
+        lines: &mut LinesWriter,
+        tag: Option<(&str, &str)>,
+        value: f64,
+    ) -> Result<()> {
+        write_attributes(lines, self.resource_attrs)?;
+        write_attributes(lines, self.scope_attrs)?;
+        write_attributes(lines, Some(self.attrs))?;
+        if let Some((name, tag_value)) = tag {
             lines
-                .write_f64("min", min)
+                .write_tag(name, tag_value)
                 .context(error::OtlpMetricsWriteSnafu)?;
         }
+        write_timestamp(lines, self.time_nano)?;
+        lines
+            .write_f64(GREPTIME_VALUE, value)
+            .context(error::OtlpMetricsWriteSnafu)?;
+        lines.commit();
+        Ok(())
+    }
 
-        if let Some(max) = data_point.max {
-            lines
-                .write_f64("max", max)
-                .context(error::OtlpMetricsWriteSnafu)?;
+    /// Writes cumulative bucket counts with their upper bounds, then the sum and the count.
+    fn write_buckets(
+        &self,
+        lines: &mut DistributionLines,
+        buckets: impl Iterator<Item = (f64, u64)>,
+        sum: Option<f64>,
+        count: u64,
+    ) -> Result<()> {
+        let mut cumulative = 0;
+        for (upper_bound, bucket_count) in buckets {
+            cumulative += bucket_count;
+            self.write_row(
+                &mut lines.values,
+                Some((LE_TAG, &upper_bound.to_string())),
+                cumulative as f64,
+            )?;
         }
+        // the `+Inf` bucket contains all observations
+        self.write_row(&mut lines.values, Some((LE_TAG, "+Inf")), count as f64)?;
 
-        lines.commit();
+        if let Some(sum) = sum {
+            self.write_row(&mut lines.sum, None, sum)?;
+        }
+        self.write_row(&mut lines.count, None, count as f64)
     }
-
-    let (columns, row_count) = lines.finish();
-    Ok(InsertRequest {
-        table_name: normalize_otlp_name(name),
-        region_number: 0,
-        columns,
-        row_count,
-    })
 }
 
-#[allow(dead_code)]
-fn encode_exponential_histogram(name: &str, hist: &ExponentialHistogram) -> Result<InsertRequest> {
-    let mut lines = LinesWriter::with_lines(hist.data_points.len());
+/// encode this histogram metric
+///
+/// Buckets are stored as cumulative counts with the `le` tag, which is the explicit upper bound
+/// of the bucket, the same as Prometheus histograms. `aggregation_temporality` is ignored.
+fn encode_histogram(
+    name: &str,
+    hist: &Histogram,
+    resource_attrs: Option<&Vec<KeyValue>>,
+    scope_attrs: Option<&Vec<KeyValue>>,
+) -> Result<Vec<InsertRequest>> {
+    let mut lines = DistributionLines::with_lines(hist.data_points.len());
 
     for data_point in &hist.data_points {
-        for attr in &data_point.attributes {
-            write_attribute(&mut lines, attr)?;
-        }
+        let ctx = DataPointContext {
+            resource_attrs,
+            scope_attrs,
+            attrs: &data_point.attributes,
+            time_nano: data_point.time_unix_nano as i64,
+        };
+        // the last bucket is the overflow bucket without an explicit bound, it's covered by
+        // the `+Inf` bucket.
+        let buckets = data_point
+            .explicit_bounds
+            .iter()
+            .copied()
+            .zip(data_point.bucket_counts.iter().copied());
+        ctx.write_buckets(&mut lines, buckets, data_point.sum, data_point.count)?;
+    }
 
-        write_timestamp(&mut lines, data_point.time_unix_nano as i64)?;
+    Ok(lines.finish(name, BUCKET_TABLE_SUFFIX))
+}
 
-        // TODO(sunng87): confirm if this working
-        if let Some(positive_buckets) = &data_point.positive {
-            for (idx, count) in positive_buckets.bucket_counts.iter().enumerate() {
-                // here we don't store bucket boundary
-                lines
-                    .write_u64(
-                        &format!("bucket_{}", idx + positive_buckets.offset as usize),
-                        *count,
-                    )
-                    .context(error::OtlpMetricsWriteSnafu)?;
-            }
-        }
+/// encode this exponential histogram metric
+///
+/// Exponential buckets are expanded into explicit ones, so they are queried the same as
+/// histograms. With `base = 2^(2^-scale)`, the positive bucket at index `i` holds values in
+/// `(base^i, base^(i+1)]`, and the negative one holds values in `[-base^(i+1), -base^i)`.
+/// Values in the zero bucket are counted in the bucket with `le="0"`.
+fn encode_exponential_histogram(
+    name: &str,
+    hist: &ExponentialHistogram,
+    resource_attrs: Option<&Vec<KeyValue>>,
+    scope_attrs: Option<&Vec<KeyValue>>,
+) -> Result<Vec<InsertRequest>> {
+    let mut lines = DistributionLines::with_lines(hist.data_points.len());
 
-        if let Some(negative_buckets) = &data_point.negative {
-            for (idx, count) in negative_buckets.bucket_counts.iter().enumerate() {
-                lines
-                    .write_u64(
-                        &format!("bucket_{}", idx + negative_buckets.offset as usize),
-                        *count,
-                    )
-                    .context(error::OtlpMetricsWriteSnafu)?;
-            }
-        }
+    for data_point in &hist.data_points {
+        let ctx = DataPointContext {
+            resource_attrs,
+            scope_attrs,
+            attrs: &data_point.attributes,
+            time_nano: data_point.time_unix_nano as i64,
+        };
+        let buckets = exponential_buckets(data_point);
+        ctx.write_buckets(
+            &mut lines,
+            buckets.into_iter(),
+            data_point.sum,
+            data_point.count,
+        )?;
+    }
 
-        if let Some(min) = data_point.min {
-            lines
-                .write_f64("min", min)
-                .context(error::OtlpMetricsWriteSnafu)?;
-        }
+    Ok(lines.finish(name, BUCKET_TABLE_SUFFIX))
+}
 
-        if let Some(max) = data_point.max {
-            lines
-                .write_f64("max", max)
-                .context(error::OtlpMetricsWriteSnafu)?;
+/// Returns the upper bounds and counts of buckets in ascending order.
+fn exponential_buckets(data_point: &ExponentialHistogramDataPoint) -> Vec<(f64, u64)> {
+    // base^index = 2^(index * 2^-scale)
+    let exponent_factor = 2f64.powi(-data_point.scale);
+    let bound = |index: i64| 2f64.powf(index as f64 * exponent_factor);
+
+    let mut buckets = Vec::new();
+    if let Some(negative) = &data_point.negative {
+        // the larger the index, the smaller the bucket
+        for (idx, count) in negative.bucket_counts.iter().enumerate().rev() {
+            let index = negative.offset as i64 + idx as i64;
+            buckets.push((-bound(index), *count));
         }
-
-        lines.commit();
     }
-
-    let (columns, row_count) = lines.finish();
-    Ok(InsertRequest {
-        table_name: normalize_otlp_name(name),
-        region_number: 0,
-        columns,
-        row_count,
-    })
+    buckets.push((0f64, data_point.zero_count));
+    if let Some(positive) = &data_point.positive {
+        for (idx, count) in positive.bucket_counts.iter().enumerate() {
+            let index = positive.offset as i64 + idx as i64;
+            buckets.push((bound(index + 1), *count));
+        }
+    }
+    buckets
 }
 
+/// encode this summary metric
+///
+/// Quantiles are stored in the `<name>` table with the `quantile` tag, the same as Prometheus
+/// summaries.
 fn encode_summary(
     name: &str,
     summary: &Summary,
     resource_attrs: Option<&Vec<KeyValue>>,
     scope_attrs: Option<&Vec<KeyValue>>,
-) -> Result<InsertRequest> {
-    let mut lines = LinesWriter::with_lines(summary.data_points.len());
+) -> Result<Vec<InsertRequest>> {
+    let mut lines = DistributionLines::with_lines(summary.data_points.len());
 
     for data_point in &summary.data_points {
-        write_attributes(&mut lines, resource_attrs)?;
-        write_attributes(&mut lines, scope_attrs)?;
-        write_attributes(&mut lines, Some(data_point.attributes.as_ref()))?;
-
-        write_timestamp(&mut lines, data_point.time_unix_nano as i64)?;
-
+        let ctx = DataPointContext {
+            resource_attrs,
+            scope_attrs,
+            attrs: &data_point.attributes,
+            time_nano: data_point.time_unix_nano as i64,
+        };
         for quantile in &data_point.quantile_values {
-            // here we don't store bucket boundary
-            lines
-                .write_f64(
-                    &format!("greptime_p{:02}", quantile.quantile * 100f64),
-                    quantile.value,
-                )
-                .context(error::OtlpMetricsWriteSnafu)?;
+            ctx.write_row(
+                &mut lines.values,
+                Some((QUANTILE_TAG, &quantile.quantile.to_string())),
+                quantile.value,
+            )?;
         }
-
-        lines
-            .write_u64("greptime_count", data_point.count)
-            .context(error::OtlpMetricsWriteSnafu)?;
-
-        lines.commit();
+        ctx.write_row(&mut lines.sum, None, data_point.sum)?;
+        ctx.write_row(&mut lines.count, None, data_point.count as f64)?;
     }
 
-    let (columns, row_count) = lines.finish();
-    Ok(InsertRequest {
-        table_name: normalize_otlp_name(name),
-        region_number: 0,
-        columns,
-        row_count,
-    })
+    Ok(lines.finish(name, ""))
 }
 
 #[cfg(test)]
@@ -504,11 +600,15 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(inserts.table_name, "datamon");
-        assert_eq!(inserts.row_count, 1);
-        assert_eq!(inserts.columns.len(), 7);
         assert_eq!(
             inserts
+                .iter()
+                .map(|i| (i.table_name.as_str(), i.row_count))
+                .collect::<Vec<_>>(),
+            vec![("datamon", 2), ("datamon_sum", 1), ("datamon_count", 1)]
+        );
+        assert_eq!(
+            inserts[0]
                 .columns
                 .iter()
                 .map(|c| &c.column_name)
@@ -517,10 +617,141 @@ mod tests {
                 "resource",
                 "scope",
                 "host",
+                "quantile",
                 "greptime_timestamp",
-                "greptime_p90",
-                "greptime_p95",
-                "greptime_count"
+                "greptime_value"
+            ]
+        );
+        let values = inserts[0].columns[3].values.as_ref().unwrap();
+        assert_eq!(values.string_values, vec!["0.9", "0.95"]);
+        let values = inserts[0].columns[5].values.as_ref().unwrap();
+        assert_eq!(values.f64_values, vec![1000.0, 3030.0]);
+        let values = inserts[1].columns[4].values.as_ref().unwrap();
+        assert_eq!(values.f64_values, vec![5400.0]);
+        let values = inserts[2].columns[4].values.as_ref().unwrap();
+        assert_eq!(values.f64_values, vec![25.0]);
+    }
+
+    fn bucket_values(insert: &InsertRequest) -> Vec<(String, f64)> {
+        let column = |name: &str| {
+            insert
+                .columns
+                .iter()
+                .find(|c| c.column_name == name)
+                .unwrap()
+                .values
+                .clone()
+                .unwrap()
+        };
+        column(LE_TAG)
+            .string_values
+            .into_iter()
+            .zip(column(GREPTIME_VALUE).f64_values)
+            .collect()
+    }
+
+    #[test]
+    fn test_encode_histogram() {
+        let data_points = vec![HistogramDataPoint {
+            attributes: vec![keyvalue("host", "testserver")],
+            time_unix_nano: 100,
+            count: 10,
+            sum: Some(120.0),
+            bucket_counts: vec![2, 3, 4, 1],
+            explicit_bounds: vec![1.0, 5.0, 25.0],
+            ..Default::default()
+        }];
+        let histogram = Histogram {
+            data_points,
+            ..Default::default()
+        };
+        let inserts = encode_histogram(
+            "http.latency",
+            &histogram,
+            Some(&vec![keyvalue("resource", "app")]),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            inserts
+                .iter()
+                .map(|i| (i.table_name.as_str(), i.row_count))
+                .collect::<Vec<_>>(),
+            vec![
+                ("http_latency_bucket", 4),
+                ("http_latency_sum", 1),
+                ("http_latency_count", 1)
+            ]
+        );
+        assert_eq!(
+            inserts[0]
+                .columns
+                .iter()
+                .map(|c| &c.column_name)
+                .collect::<Vec<&String>>(),
+            vec![
+                "resource",
+                "host",
+                "le",
+                "greptime_timestamp",
+                "greptime_value"
+            ]
+        );
+        assert_eq!(
+            bucket_values(&inserts[0]),
+            vec![
+                ("1".to_string(), 2.0),
+                ("5".to_string(), 5.0),
+                ("25".to_string(), 9.0),
+                ("+Inf".to_string(), 10.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_exponential_histogram() {
+        let data_points = vec![ExponentialHistogramDataPoint {
+            attributes: vec![keyvalue("host", "testserver")],
+            time_unix_nano: 100,
+            count: 10,
+            sum: Some(20.0),
+            // base = 2
+            scale: 0,
+            zero_count: 1,
+            positive: Some(exponential_histogram_data_point::Buckets {
+                offset: 1,
+                bucket_counts: vec![3, 4],
+            }),
+            negative: Some(exponential_histogram_data_point::Buckets {
+                offset: 0,
+                bucket_counts: vec![1, 1],
+            }),
+            ..Default::default()
+        }];
+        let histogram = ExponentialHistogram {
+            data_points,
+            ..Default::default()
+        };
+        let inserts = encode_exponential_histogram("latency", &histogram, None, None).unwrap();
+
+        assert_eq!(
+            inserts
+                .iter()
+                .map(|i| i.table_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["latency_bucket", "latency_sum", "latency_count"]
+        );
+        // negative buckets: [-2, -1), [-4, -2); positive buckets: (2, 4], (4, 8]
+        assert_eq!(
+            bucket_values(&inserts[0]),
+            vec![
+                ("-2".to_string(), 1.0),
+                ("-1".to_string(), 2.0),
+                ("0".to_string(), 3.0),
+                ("4".to_string(), 6.0),
+                ("8".to_string(), 10.0),
+                ("+Inf".to_string(), 10.0)
             ]
         );
     }
@@ -0,0 +1,344 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api::v1::{InsertRequest, InsertRequests};
+use common_grpc::writer::LinesWriter;
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue};
+use opentelemetry_proto::tonic::logs::v1::LogRecord;
+use snafu::ResultExt;
+
+use super::utils::{any_value_to_json, attributes_to_json, scope_info, service_name, ScopeInfo};
+use crate::error::{self, Result};
+
+/// The table that log records are written into if not specified.
+pub const LOG_TABLE_NAME: &str = "opentelemetry_logs";
+
+// tags
+const SERVICE_NAME: &str = "service_name";
+const TRACE_ID: &str = "trace_id";
+const SPAN_ID: &str = "span_id";
+// time index
+const GREPTIME_TIMESTAMP: &str = "greptime_timestamp";
+// fields
+const OBSERVED_TIME_UNIX_NANO: &str = "observed_time_unix_nano";
+const SEVERITY_TEXT: &str = "severity_text";
+const SEVERITY_NUMBER: &str = "severity_number";
+const BODY: &str = "body";
+const SCOPE_NAME: &str = "scope_name";
+const SCOPE_VERSION: &str = "scope_version";
+const RESOURCE_ATTRIBUTES: &str = "resource_attributes";
+const SCOPE_ATTRIBUTES: &str = "scope_attributes";
+const LOG_ATTRIBUTES: &str = "log_attributes";
+
+/// Convert OpenTelemetry logs to a GreptimeDB insert request, one row per log record.
+///
+/// See
+/// https://github.com/open-telemetry/opentelemetry-proto/blob/main/opentelemetry/proto/logs/v1/logs.proto
+/// for data structure of OTLP logs.
+///
+/// The layout of the table:
+/// - tags: `service_name` (the `service.name` resource attribute), `trace_id` and `span_id`
+/// - time index: `greptime_timestamp` in nanoseconds, the time when the event occurred, or the
+/// observed time if it's unknown
+/// - fields: `observed_time_unix_nano`, `severity_text`, `severity_number`, `body`,
+/// `scope_name`, `scope_version`, and the JSON encoded `resource_attributes`,
+/// `scope_attributes` and `log_attributes`
+///
+/// Rows with the same tags and time index overwrite each other, so the time index keeps the
+/// full precision of the records, and records of different spans never collide.
+///
+/// All the textual fields are plain strings so they can be searched with `LIKE` or regex
+/// matching. A string body is stored as is, other kinds of bodies are JSON encoded.
+///
+/// Returns `InsertRequests` and total number of rows to ingest
+pub fn to_grpc_insert_requests(
+    request: ExportLogsServiceRequest,
+    table_name: &str,
+) -> Result<(InsertRequests, usize)> {
+    let record_count = request
+        .resource_logs
+        .iter()
+        .flat_map(|r| &r.scope_logs)
+        .map(|s| s.log_records.len())
+        .sum();
+    let mut lines = LinesWriter::with_lines(record_count);
+
+    for resource in request.resource_logs {
+        let resource_attrs = resource.resource.map(|r| r.attributes).unwrap_or_default();
+        let service_name = service_name(&resource_attrs);
+        let resource_attrs = attributes_to_json(&resource_attrs);
+
+        for scope_logs in resource.scope_logs {
+            let scope = scope_info(scope_logs.scope);
+            for record in scope_logs.log_records {
+                write_log_record(
+                    &mut lines,
+                    &record,
+                    service_name.as_deref(),
+                    &resource_attrs,
+                    &scope,
+                )?;
+            }
+        }
+    }
+
+    let mut inserts = Vec::with_capacity(1);
+    if record_count > 0 {
+        let (columns, row_count) = lines.finish();
+        inserts.push(InsertRequest {
+            table_name: table_name.to_string(),
+            region_number: 0,
+            columns,
+            row_count,
+        });
+    }
+    Ok((InsertRequests { inserts }, record_count))
+}
+
+fn write_log_record(
+    lines: &mut LinesWriter,
+    record: &LogRecord,
+    service_name: Option<&str>,
+    resource_attrs: &str,
+    scope: &ScopeInfo,
+) -> Result<()> {
+    if let Some(service_name) = service_name {
+        lines
+            .write_tag(SERVICE_NAME, service_name)
+            .context(error::OtlpLogsWriteSnafu)?;
+    }
+    for (column, id) in [(TRACE_ID, &record.trace_id), (SPAN_ID, &record.span_id)] {
+        if !id.is_empty() {
+            lines
+                .write_tag(column, &hex::encode(id))
+                .context(error::OtlpLogsWriteSnafu)?;
+        }
+    }
+    let time_unix_nano = if record.time_unix_nano > 0 {
+        record.time_unix_nano
+    } else {
+        record.observed_time_unix_nano
+    };
+    lines
+        .write_ns_ts(GREPTIME_TIMESTAMP, time_unix_nano as i64)
+        .context(error::OtlpLogsWriteSnafu)?;
+    lines
+        .write_u64(OBSERVED_TIME_UNIX_NANO, record.observed_time_unix_nano)
+        .context(error::OtlpLogsWriteSnafu)?;
+    lines
+        .write_i64(SEVERITY_NUMBER, record.severity_number as i64)
+        .context(error::OtlpLogsWriteSnafu)?;
+
+    let mut strings = vec![
+        (RESOURCE_ATTRIBUTES, resource_attrs.to_string()),
+        (SCOPE_ATTRIBUTES, scope.attributes.clone()),
+        (LOG_ATTRIBUTES, attributes_to_json(&record.attributes)),
+    ];
+    if let Some(body) = body_to_string(record.body.as_ref()) {
+        strings.push((BODY, body));
+    }
+    for (column, value) in [
+        (SEVERITY_TEXT, &record.severity_text),
+        (SCOPE_NAME, &scope.name),
+        (SCOPE_VERSION, &scope.version),
+    ] {
+        if !value.is_empty() {
+            strings.push((column, value.clone()));
+        }
+    }
+    for (column, value) in strings {
+        lines
+            .write_string(column, &value)
+            .context(error::OtlpLogsWriteSnafu)?;
+    }
+
+    lines.commit();
+    Ok(())
+}
+
+fn body_to_string(body: Option<&AnyValue>) -> Option<String> {
+    match body.and_then(|b| b.value.as_ref())? {
+        any_value::Value::StringValue(s) => Some(s.clone()),
+        _ => Some(any_value_to_json(body).to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::column::Values;
+    use opentelemetry_proto::tonic::common::v1::{InstrumentationScope, KeyValue, KeyValueList};
+    use opentelemetry_proto::tonic::logs::v1::{ResourceLogs, ScopeLogs, SeverityNumber};
+    use opentelemetry_proto::tonic::resource::v1::Resource;
+
+    use super::*;
+
+    fn keyvalue(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(string_value(value)),
+        }
+    }
+
+    fn string_value(s: &str) -> AnyValue {
+        AnyValue {
+            value: Some(any_value::Value::StringValue(s.to_string())),
+        }
+    }
+
+    fn column_values<'a>(inserts: &'a InsertRequests, name: &str) -> &'a Values {
+        inserts.inserts[0]
+            .columns
+            .iter()
+            .find(|c| c.column_name == name)
+            .unwrap()
+            .values
+            .as_ref()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_body_to_string() {
+        assert_eq!(None, body_to_string(None));
+        assert_eq!(
+            Some("hello".to_string()),
+            body_to_string(Some(&string_value("hello")))
+        );
+        let kvlist = AnyValue {
+            value: Some(any_value::Value::KvlistValue(KeyValueList {
+                values: vec![keyvalue("msg", "hello")],
+            })),
+        };
+        assert_eq!(
+            Some(r#"{"msg":"hello"}"#.to_string()),
+            body_to_string(Some(&kvlist))
+        );
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests() {
+        let records = vec![
+            LogRecord {
+                time_unix_nano: 1_000_000_000,
+                observed_time_unix_nano: 1_000_500_000,
+                severity_number: SeverityNumber::Info as i32,
+                severity_text: "INFO".to_string(),
+                body: Some(string_value("server started")),
+                attributes: vec![keyvalue("port", "4000")],
+                ..Default::default()
+            },
+            LogRecord {
+                observed_time_unix_nano: 2_000_000_000,
+                severity_number: SeverityNumber::Error as i32,
+                severity_text: "ERROR".to_string(),
+                body: Some(string_value("connection refused")),
+                trace_id: vec![1; 16],
+                span_id: vec![2; 8],
+                ..Default::default()
+            },
+        ];
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: Some(Resource {
+                    attributes: vec![keyvalue("service.name", "frontend")],
+                    ..Default::default()
+                }),
+                scope_logs: vec![ScopeLogs {
+                    scope: Some(InstrumentationScope {
+                        name: "logger".to_string(),
+                        ..Default::default()
+                    }),
+                    log_records: records,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let (inserts, rows) = to_grpc_insert_requests(request, LOG_TABLE_NAME).unwrap();
+        assert_eq!(2, rows);
+        assert_eq!(1, inserts.inserts.len());
+        assert_eq!(LOG_TABLE_NAME, inserts.inserts[0].table_name);
+        assert_eq!(2, inserts.inserts[0].row_count);
+
+        assert_eq!(
+            vec!["frontend", "frontend"],
+            column_values(&inserts, SERVICE_NAME).string_values
+        );
+        // falls back to the observed time
+        assert_eq!(
+            vec![1_000_000_000, 2_000_000_000],
+            column_values(&inserts, GREPTIME_TIMESTAMP).ts_nanosecond_values
+        );
+        assert_eq!(
+            vec![9, 17],
+            column_values(&inserts, SEVERITY_NUMBER).i64_values
+        );
+        assert_eq!(
+            vec!["INFO", "ERROR"],
+            column_values(&inserts, SEVERITY_TEXT).string_values
+        );
+        assert_eq!(
+            vec!["server started", "connection refused"],
+            column_values(&inserts, BODY).string_values
+        );
+        assert_eq!(
+            vec!["01".repeat(16)],
+            column_values(&inserts, TRACE_ID).string_values
+        );
+        assert_eq!(
+            vec![r#"{"port":"4000"}"#, "{}"],
+            column_values(&inserts, LOG_ATTRIBUTES).string_values
+        );
+        assert_eq!(
+            vec![r#"{"service.name":"frontend"}"#; 2],
+            column_values(&inserts, RESOURCE_ATTRIBUTES).string_values
+        );
+    }
+
+    #[test]
+    fn test_records_in_same_millisecond() {
+        let records = (0..2)
+            .map(|i| LogRecord {
+                time_unix_nano: 1_000_000_000 + i,
+                body: Some(string_value("retrying")),
+                ..Default::default()
+            })
+            .collect();
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                scope_logs: vec![ScopeLogs {
+                    log_records: records,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let (inserts, rows) = to_grpc_insert_requests(request, LOG_TABLE_NAME).unwrap();
+        assert_eq!(2, rows);
+        assert_eq!(
+            vec![1_000_000_000, 1_000_000_001],
+            column_values(&inserts, GREPTIME_TIMESTAMP).ts_nanosecond_values
+        );
+    }
+
+    #[test]
+    fn test_empty_request() {
+        let (inserts, rows) =
+            to_grpc_insert_requests(ExportLogsServiceRequest::default(), LOG_TABLE_NAME).unwrap();
+        assert_eq!(0, rows);
+        assert!(inserts.inserts.is_empty());
+    }
+}
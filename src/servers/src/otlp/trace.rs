@@ -0,0 +1,395 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api::v1::{InsertRequest, InsertRequests};
+use common_grpc::writer::{LinesWriter, Precision};
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::trace::v1::span::{Event, Link, SpanKind};
+use opentelemetry_proto::tonic::trace::v1::status::StatusCode;
+use opentelemetry_proto::tonic::trace::v1::Span;
+use serde_json::Value;
+use snafu::ResultExt;
+
+use super::utils::{
+    attributes_to_json, attributes_to_json_value, scope_info, service_name, ScopeInfo,
+};
+use crate::error::{self, Result};
+
+/// The table that spans are written into if not specified.
+pub const TRACE_TABLE_NAME: &str = "opentelemetry_traces";
+
+// tags
+const SERVICE_NAME: &str = "service_name";
+const TRACE_ID: &str = "trace_id";
+const SPAN_ID: &str = "span_id";
+// time index, the start time of the span
+const GREPTIME_TIMESTAMP: &str = "greptime_timestamp";
+// fields
+const PARENT_SPAN_ID: &str = "parent_span_id";
+const TRACE_STATE: &str = "trace_state";
+const SPAN_NAME: &str = "span_name";
+const SPAN_KIND: &str = "span_kind";
+const SPAN_STATUS_CODE: &str = "span_status_code";
+const SPAN_STATUS_MESSAGE: &str = "span_status_message";
+const START_TIME_UNIX_NANO: &str = "start_time_unix_nano";
+const END_TIME_UNIX_NANO: &str = "end_time_unix_nano";
+const DURATION_NANO: &str = "duration_nano";
+const SCOPE_NAME: &str = "scope_name";
+const SCOPE_VERSION: &str = "scope_version";
+const RESOURCE_ATTRIBUTES: &str = "resource_attributes";
+const SCOPE_ATTRIBUTES: &str = "scope_attributes";
+const SPAN_ATTRIBUTES: &str = "span_attributes";
+const SPAN_EVENTS: &str = "span_events";
+const SPAN_LINKS: &str = "span_links";
+
+/// Convert OpenTelemetry traces to a GreptimeDB insert request, one row per span.
+///
+/// See
+/// https://github.com/open-telemetry/opentelemetry-proto/blob/main/opentelemetry/proto/trace/v1/trace.proto
+/// for data structure of OTLP traces.
+///
+/// The layout of the table:
+/// - tags: `service_name` (the `service.name` resource attribute), `trace_id` and `span_id`
+/// - time index: `greptime_timestamp`, the start time of the span in milliseconds
+/// - fields: `parent_span_id`, `trace_state`, `span_name`, `span_kind`, `span_status_code`,
+/// `span_status_message`, `start_time_unix_nano`, `end_time_unix_nano`, `duration_nano`,
+/// `scope_name`, `scope_version`, and the JSON encoded `resource_attributes`,
+/// `scope_attributes`, `span_attributes`, `span_events` and `span_links`
+///
+/// Ids are hex encoded, and absent values (e.g. the parent of a root span) are null.
+///
+/// Returns `InsertRequests` and total number of rows to ingest
+pub fn to_grpc_insert_requests(
+    request: ExportTraceServiceRequest,
+    table_name: &str,
+) -> Result<(InsertRequests, usize)> {
+    let span_count = request
+        .resource_spans
+        .iter()
+        .flat_map(|r| &r.scope_spans)
+        .map(|s| s.spans.len())
+        .sum();
+    let mut lines = LinesWriter::with_lines(span_count);
+
+    for resource in request.resource_spans {
+        let resource_attrs = resource.resource.map(|r| r.attributes).unwrap_or_default();
+        let service_name = service_name(&resource_attrs);
+        let resource_attrs = attributes_to_json(&resource_attrs);
+
+        for scope_spans in resource.scope_spans {
+            let scope = scope_info(scope_spans.scope);
+            for span in scope_spans.spans {
+                write_span(
+                    &mut lines,
+                    &span,
+                    service_name.as_deref(),
+                    &resource_attrs,
+                    &scope,
+                )?;
+            }
+        }
+    }
+
+    let mut inserts = Vec::with_capacity(1);
+    if span_count > 0 {
+        let (columns, row_count) = lines.finish();
+        inserts.push(InsertRequest {
+            table_name: table_name.to_string(),
+            region_number: 0,
+            columns,
+            row_count,
+        });
+    }
+    Ok((InsertRequests { inserts }, span_count))
+}
+
+fn write_span(
+    lines: &mut LinesWriter,
+    span: &Span,
+    service_name: Option<&str>,
+    resource_attrs: &str,
+    scope: &ScopeInfo,
+) -> Result<()> {
+    if let Some(service_name) = service_name {
+        lines
+            .write_tag(SERVICE_NAME, service_name)
+            .context(error::OtlpTracesWriteSnafu)?;
+    }
+    lines
+        .write_tag(TRACE_ID, &hex::encode(&span.trace_id))
+        .context(error::OtlpTracesWriteSnafu)?;
+    lines
+        .write_tag(SPAN_ID, &hex::encode(&span.span_id))
+        .context(error::OtlpTracesWriteSnafu)?;
+    lines
+        .write_ts(
+            GREPTIME_TIMESTAMP,
+            (span.start_time_unix_nano as i64, Precision::Nanosecond),
+        )
+        .context(error::OtlpTracesWriteSnafu)?;
+
+    let mut strings = vec![
+        (SPAN_NAME, span.name.clone()),
+        (SPAN_KIND, span_kind(span.kind)),
+        (
+            SPAN_STATUS_CODE,
+            status_code(span.status.as_ref().map(|s| s.code).unwrap_or_default()),
+        ),
+        (RESOURCE_ATTRIBUTES, resource_attrs.to_string()),
+        (SCOPE_ATTRIBUTES, scope.attributes.clone()),
+        (SPAN_ATTRIBUTES, attributes_to_json(&span.attributes)),
+        (SPAN_EVENTS, events_to_json(&span.events)),
+        (SPAN_LINKS, links_to_json(&span.links)),
+    ];
+    if !span.parent_span_id.is_empty() {
+        strings.push((PARENT_SPAN_ID, hex::encode(&span.parent_span_id)));
+    }
+    for (column, value) in [
+        (TRACE_STATE, &span.trace_state),
+        (SCOPE_NAME, &scope.name),
+        (SCOPE_VERSION, &scope.version),
+    ] {
+        if !value.is_empty() {
+            strings.push((column, value.clone()));
+        }
+    }
+    if let Some(status) = span.status.as_ref().filter(|s| !s.message.is_empty()) {
+        strings.push((SPAN_STATUS_MESSAGE, status.message.clone()));
+    }
+    for (column, value) in strings {
+        lines
+            .write_string(column, &value)
+            .context(error::OtlpTracesWriteSnafu)?;
+    }
+
+    for (column, value) in [
+        (START_TIME_UNIX_NANO, span.start_time_unix_nano),
+        (END_TIME_UNIX_NANO, span.end_time_unix_nano),
+        (
+            DURATION_NANO,
+            span.end_time_unix_nano
+                .saturating_sub(span.start_time_unix_nano),
+        ),
+    ] {
+        lines
+            .write_u64(column, value)
+            .context(error::OtlpTracesWriteSnafu)?;
+    }
+
+    lines.commit();
+    Ok(())
+}
+
+fn span_kind(kind: i32) -> String {
+    SpanKind::from_i32(kind)
+        .unwrap_or(SpanKind::Unspecified)
+        .as_str_name()
+        .to_string()
+}
+
+fn status_code(code: i32) -> String {
+    StatusCode::from_i32(code)
+        .unwrap_or(StatusCode::Unset)
+        .as_str_name()
+        .to_string()
+}
+
+fn events_to_json(events: &[Event]) -> String {
+    Value::Array(
+        events
+            .iter()
+            .map(|event| {
+                serde_json::json!({
+                    "name": event.name,
+                    "time_unix_nano": event.time_unix_nano,
+                    "attributes": attributes_to_json_value(&event.attributes),
+                })
+            })
+            .collect(),
+    )
+    .to_string()
+}
+
+fn links_to_json(links: &[Link]) -> String {
+    Value::Array(
+        links
+            .iter()
+            .map(|link| {
+                serde_json::json!({
+                    "trace_id": hex::encode(&link.trace_id),
+                    "span_id": hex::encode(&link.span_id),
+                    "trace_state": link.trace_state,
+                    "attributes": attributes_to_json_value(&link.attributes),
+                })
+            })
+            .collect(),
+    )
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::column::Values;
+    use opentelemetry_proto::tonic::common::v1::{
+        any_value, AnyValue, InstrumentationScope, KeyValue,
+    };
+    use opentelemetry_proto::tonic::resource::v1::Resource;
+    use opentelemetry_proto::tonic::trace::v1::{ResourceSpans, ScopeSpans, Status};
+
+    use super::*;
+
+    fn keyvalue(key: &str, value: any_value::Value) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue { value: Some(value) }),
+        }
+    }
+
+    fn string_value(s: &str) -> any_value::Value {
+        any_value::Value::StringValue(s.to_string())
+    }
+
+    fn column_values<'a>(inserts: &'a InsertRequests, name: &str) -> &'a Values {
+        inserts.inserts[0]
+            .columns
+            .iter()
+            .find(|c| c.column_name == name)
+            .unwrap()
+            .values
+            .as_ref()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_empty_request() {
+        let (inserts, rows) =
+            to_grpc_insert_requests(ExportTraceServiceRequest::default(), TRACE_TABLE_NAME)
+                .unwrap();
+        assert_eq!(0, rows);
+        assert!(inserts.inserts.is_empty());
+    }
+
+    #[test]
+    fn test_to_grpc_insert_requests() {
+        let root = Span {
+            trace_id: vec![1; 16],
+            span_id: vec![2; 8],
+            name: "GET /metrics".to_string(),
+            kind: SpanKind::Server as i32,
+            start_time_unix_nano: 1_000_000_000,
+            end_time_unix_nano: 1_500_000_000,
+            attributes: vec![keyvalue("http.method", string_value("GET"))],
+            events: vec![Event {
+                time_unix_nano: 1_100_000_000,
+                name: "cache miss".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let child = Span {
+            trace_id: vec![1; 16],
+            span_id: vec![3; 8],
+            parent_span_id: vec![2; 8],
+            name: "query".to_string(),
+            kind: SpanKind::Internal as i32,
+            start_time_unix_nano: 1_200_000_000,
+            end_time_unix_nano: 1_300_000_000,
+            status: Some(Status {
+                message: "timeout".to_string(),
+                code: StatusCode::Error as i32,
+            }),
+            ..Default::default()
+        };
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(Resource {
+                    attributes: vec![keyvalue("service.name", string_value("frontend"))],
+                    ..Default::default()
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: Some(InstrumentationScope {
+                        name: "tracer".to_string(),
+                        ..Default::default()
+                    }),
+                    spans: vec![root, child],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let (inserts, rows) = to_grpc_insert_requests(request, TRACE_TABLE_NAME).unwrap();
+        assert_eq!(2, rows);
+        assert_eq!(1, inserts.inserts.len());
+        let insert = &inserts.inserts[0];
+        assert_eq!(TRACE_TABLE_NAME, insert.table_name);
+        assert_eq!(2, insert.row_count);
+
+        assert_eq!(
+            vec!["frontend", "frontend"],
+            column_values(&inserts, SERVICE_NAME).string_values
+        );
+        assert_eq!(
+            vec!["01".repeat(16), "01".repeat(16)],
+            column_values(&inserts, TRACE_ID).string_values
+        );
+        assert_eq!(
+            vec!["02".repeat(8), "03".repeat(8)],
+            column_values(&inserts, SPAN_ID).string_values
+        );
+        assert_eq!(
+            vec![1000, 1200],
+            column_values(&inserts, GREPTIME_TIMESTAMP).ts_millisecond_values
+        );
+        // the parent of the root span is null
+        assert_eq!(
+            vec!["02".repeat(8)],
+            column_values(&inserts, PARENT_SPAN_ID).string_values
+        );
+        assert_eq!(
+            vec!["SPAN_KIND_SERVER", "SPAN_KIND_INTERNAL"],
+            column_values(&inserts, SPAN_KIND).string_values
+        );
+        assert_eq!(
+            vec!["STATUS_CODE_UNSET", "STATUS_CODE_ERROR"],
+            column_values(&inserts, SPAN_STATUS_CODE).string_values
+        );
+        assert_eq!(
+            vec!["timeout"],
+            column_values(&inserts, SPAN_STATUS_MESSAGE).string_values
+        );
+        assert_eq!(
+            vec![500_000_000, 100_000_000],
+            column_values(&inserts, DURATION_NANO).u64_values
+        );
+        assert_eq!(
+            vec![r#"{"http.method":"GET"}"#, "{}"],
+            column_values(&inserts, SPAN_ATTRIBUTES).string_values
+        );
+        let events = &column_values(&inserts, SPAN_EVENTS).string_values;
+        assert_eq!(
+            serde_json::json!([{
+                "name": "cache miss",
+                "time_unix_nano": 1_100_000_000u64,
+                "attributes": {},
+            }]),
+            serde_json::from_str::<Value>(&events[0]).unwrap()
+        );
+        assert_eq!("[]", events[1]);
+        assert_eq!(
+            vec!["tracer", "tracer"],
+            column_values(&inserts, SCOPE_NAME).string_values
+        );
+    }
+}
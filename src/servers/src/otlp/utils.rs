@@ -0,0 +1,144 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use serde_json::{Map, Value};
+
+const SERVICE_NAME_KEY: &str = "service.name";
+
+/// Returns the `service.name` resource attribute.
+pub(crate) fn service_name(resource_attrs: &[KeyValue]) -> Option<String> {
+    resource_attrs
+        .iter()
+        .find(|attr| attr.key == SERVICE_NAME_KEY)
+        .and_then(|attr| attr.value.as_ref())
+        .and_then(|v| match &v.value {
+            Some(any_value::Value::StringValue(s)) => Some(s.clone()),
+            _ => None,
+        })
+}
+
+/// The instrumentation scope, with attributes encoded as JSON.
+pub(crate) struct ScopeInfo {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) attributes: String,
+}
+
+pub(crate) fn scope_info(scope: Option<InstrumentationScope>) -> ScopeInfo {
+    let scope = scope.unwrap_or_default();
+    ScopeInfo {
+        name: scope.name,
+        version: scope.version,
+        attributes: attributes_to_json(&scope.attributes),
+    }
+}
+
+/// Encodes attributes as a JSON object, the string of which is stored in a single column.
+pub(crate) fn attributes_to_json(attrs: &[KeyValue]) -> String {
+    attributes_to_json_value(attrs).to_string()
+}
+
+pub(crate) fn attributes_to_json_value(attrs: &[KeyValue]) -> Value {
+    Value::Object(
+        attrs
+            .iter()
+            .map(|attr| (attr.key.clone(), any_value_to_json(attr.value.as_ref())))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+pub(crate) fn any_value_to_json(value: Option<&AnyValue>) -> Value {
+    let Some(value) = value.and_then(|v| v.value.as_ref()) else {
+        return Value::Null;
+    };
+    match value {
+        any_value::Value::StringValue(s) => Value::String(s.clone()),
+        any_value::Value::BoolValue(b) => Value::Bool(*b),
+        any_value::Value::IntValue(i) => Value::from(*i),
+        any_value::Value::DoubleValue(d) => Value::from(*d),
+        any_value::Value::ArrayValue(array) => Value::Array(
+            array
+                .values
+                .iter()
+                .map(|v| any_value_to_json(Some(v)))
+                .collect(),
+        ),
+        any_value::Value::KvlistValue(kvs) => attributes_to_json_value(&kvs.values),
+        any_value::Value::BytesValue(bytes) => Value::String(hex::encode(bytes)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::tonic::common::v1::{ArrayValue, KeyValueList};
+
+    use super::*;
+
+    fn keyvalue(key: &str, value: any_value::Value) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue { value: Some(value) }),
+        }
+    }
+
+    fn string_value(s: &str) -> any_value::Value {
+        any_value::Value::StringValue(s.to_string())
+    }
+
+    #[test]
+    fn test_service_name() {
+        let attrs = vec![
+            keyvalue("host", string_value("localhost")),
+            keyvalue(SERVICE_NAME_KEY, string_value("frontend")),
+        ];
+        assert_eq!(Some("frontend".to_string()), service_name(&attrs));
+        assert_eq!(None, service_name(&attrs[..1]));
+    }
+
+    #[test]
+    fn test_attributes_to_json() {
+        let attrs = vec![
+            keyvalue("str", string_value("a")),
+            keyvalue("int", any_value::Value::IntValue(1)),
+            keyvalue("bool", any_value::Value::BoolValue(true)),
+            keyvalue(
+                "array",
+                any_value::Value::ArrayValue(ArrayValue {
+                    values: vec![AnyValue {
+                        value: Some(any_value::Value::DoubleValue(1.5)),
+                    }],
+                }),
+            ),
+            keyvalue(
+                "kvs",
+                any_value::Value::KvlistValue(KeyValueList {
+                    values: vec![keyvalue("k", string_value("v"))],
+                }),
+            ),
+            keyvalue("bytes", any_value::Value::BytesValue(vec![0xab, 0xcd])),
+        ];
+        assert_eq!(
+            serde_json::json!({
+                "str": "a",
+                "int": 1,
+                "bool": true,
+                "array": [1.5],
+                "kvs": {"k": "v"},
+                "bytes": "abcd",
+            }),
+            serde_json::from_str::<Value>(&attributes_to_json(&attrs)).unwrap()
+        );
+    }
+}
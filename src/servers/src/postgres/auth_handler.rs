@@ -15,12 +15,17 @@
 use std::fmt::Debug;
 use std::sync::Exclusive;
 
-use ::auth::{userinfo_by_name, Identity, Password, UserInfoRef, UserProviderRef};
+use ::auth::{
+    userinfo_by_name, Identity, Password, PgAuthMethod, ScramSha256Verifier, UserInfoRef,
+    UserProviderRef, SCRAM_SHA_256,
+};
 use async_trait::async_trait;
+use bytes::Bytes;
 use common_catalog::parse_catalog_and_schema_from_db_string;
 use common_error::ext::ErrorExt;
 use futures::{Sink, SinkExt};
 use metrics::increment_counter;
+use parking_lot::Mutex;
 use pgwire::api::auth::StartupHandler;
 use pgwire::api::{auth, ClientInfo, PgWireConnectionState};
 use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
@@ -36,14 +41,40 @@ use crate::query_handler::sql::ServerSqlQueryHandlerRef;
 
 pub(crate) struct PgLoginVerifier {
     user_provider: Option<UserProviderRef>,
+    state: Mutex<AuthState>,
 }
 
 impl PgLoginVerifier {
     pub(crate) fn new(user_provider: Option<UserProviderRef>) -> Self {
-        Self { user_provider }
+        Self {
+            user_provider,
+            state: Mutex::new(AuthState::CleartextPassword),
+        }
     }
 }
 
+/// Where the password authentication of a connection is, it decides how to read the next
+/// password message from the client.
+#[derive(Clone)]
+enum AuthState {
+    CleartextPassword,
+    Md5Password {
+        salt: [u8; 4],
+    },
+    /// Waits for the SASLInitialResponse.
+    /// ref: <https://www.postgresql.org/docs/current/sasl-authentication.html>
+    ScramStart {
+        verifier: ScramSha256Verifier,
+    },
+    /// Waits for the SASLResponse with the client proof.
+    ScramContinue {
+        verifier: ScramSha256Verifier,
+        client_first_bare: String,
+        server_first: String,
+        nonce: String,
+    },
+}
+
 #[allow(dead_code)]
 struct LoginInfo {
     user: Option<String>,
@@ -73,7 +104,38 @@ impl LoginInfo {
 }
 
 impl PgLoginVerifier {
-    async fn auth(&self, login: &LoginInfo, password: &str) -> Result<Option<UserInfoRef>> {
+    /// Decides the authentication method by how the password of the user is saved, and returns
+    /// the message requesting the password from the client.
+    async fn start_authentication(&self, login: &LoginInfo) -> Authentication {
+        let method = match (&self.user_provider, &login.user) {
+            (Some(provider), Some(user)) => provider
+                .pg_auth_method(user)
+                .await
+                .unwrap_or(PgAuthMethod::CleartextPassword),
+            _ => PgAuthMethod::CleartextPassword,
+        };
+        let (state, message) = match method {
+            PgAuthMethod::CleartextPassword => (
+                AuthState::CleartextPassword,
+                Authentication::CleartextPassword,
+            ),
+            PgAuthMethod::Md5Password => {
+                let salt = rand::random::<[u8; 4]>();
+                (
+                    AuthState::Md5Password { salt },
+                    Authentication::MD5Password(salt.to_vec()),
+                )
+            }
+            PgAuthMethod::ScramSha256(verifier) => (
+                AuthState::ScramStart { verifier },
+                Authentication::SASL(vec![SCRAM_SHA_256.to_string()]),
+            ),
+        };
+        *self.state.lock() = state;
+        message
+    }
+
+    async fn auth(&self, login: &LoginInfo, password: Password<'_>) -> Result<Option<UserInfoRef>> {
         let user_provider = match &self.user_provider {
             Some(provider) => provider,
             None => return Ok(None),
@@ -93,12 +155,7 @@ impl PgLoginVerifier {
         };
 
         match user_provider
-            .auth(
-                Identity::UserId(user_name, None),
-                Password::PlainText(password.to_string().into()),
-                catalog,
-                schema,
-            )
+            .auth(Identity::UserId(user_name, None), password, catalog, schema)
             .await
         {
             Err(e) => {
@@ -166,10 +223,10 @@ impl StartupHandler for PostgresServerHandler {
 
                 if self.login_verifier.user_provider.is_some() {
                     client.set_state(PgWireConnectionState::AuthenticationInProgress);
+                    let login_info = LoginInfo::from_client_info(client);
+                    let message = self.login_verifier.start_authentication(&login_info).await;
                     client
-                        .send(PgWireBackendMessage::Authentication(
-                            Authentication::CleartextPassword,
-                        ))
+                        .send(PgWireBackendMessage::Authentication(message))
                         .await?;
                 } else {
                     self.session.set_user_info(userinfo_by_name(
@@ -180,17 +237,100 @@ impl StartupHandler for PostgresServerHandler {
                 }
             }
             PgWireFrontendMessage::PasswordMessageFamily(pwd) => {
+                let login_info = LoginInfo::from_client_info(client);
                 // the newer version of pgwire has a few variant password
                 // message like cleartext/md5 password, saslresponse, etc. Here
-                // we must manually coerce it into password
-                let pwd = pwd.into_password()?;
+                // we must manually coerce it by the state of the authentication
+                let state = self.login_verifier.state.lock().clone();
+                let (auth_result, server_final) = match state {
+                    AuthState::CleartextPassword => {
+                        let pwd = pwd.into_password()?;
+                        let password = Password::PlainText(pwd.password().to_string().into());
+                        (self.login_verifier.auth(&login_info, password).await, None)
+                    }
+                    AuthState::Md5Password { salt } => {
+                        let pwd = pwd.into_password()?;
+                        let password = Password::PgMD5(pwd.password().as_bytes(), &salt);
+                        (self.login_verifier.auth(&login_info, password).await, None)
+                    }
+                    AuthState::ScramStart { verifier } => {
+                        let response = pwd.into_sasl_initial_response()?;
+                        let client_first = response
+                            .data()
+                            .as_ref()
+                            .and_then(|data| std::str::from_utf8(data).ok())
+                            .filter(|_| response.auth_method() == SCRAM_SHA_256)
+                            .and_then(parse_client_first);
+                        let Some((client_first_bare, client_nonce)) = client_first else {
+                            return send_error(
+                                client,
+                                "FATAL",
+                                "08P01",
+                                "malformed SCRAM message".to_owned(),
+                            )
+                            .await;
+                        };
 
-                let login_info = LoginInfo::from_client_info(client);
+                        let server_nonce = base64::encode(rand::random::<[u8; 18]>());
+                        let nonce = format!("{client_nonce}{server_nonce}");
+                        let server_first = format!(
+                            "r={nonce},s={},i={}",
+                            base64::encode(&verifier.salt),
+                            verifier.iterations
+                        );
+                        client
+                            .send(PgWireBackendMessage::Authentication(
+                                Authentication::SASLContinue(Bytes::from(server_first.clone())),
+                            ))
+                            .await?;
+                        *self.login_verifier.state.lock() = AuthState::ScramContinue {
+                            verifier,
+                            client_first_bare,
+                            server_first,
+                            nonce,
+                        };
+                        return Ok(());
+                    }
+                    AuthState::ScramContinue {
+                        verifier,
+                        client_first_bare,
+                        server_first,
+                        nonce,
+                    } => {
+                        let response = pwd.into_sasl_response()?;
+                        let client_final = std::str::from_utf8(response.data())
+                            .ok()
+                            .and_then(|data| parse_client_final(data, &nonce));
+                        let Some((without_proof, client_proof)) = client_final else {
+                            return send_error(
+                                client,
+                                "FATAL",
+                                "08P01",
+                                "malformed SCRAM message".to_owned(),
+                            )
+                            .await;
+                        };
 
-                // do authenticate
-                let auth_result = self.login_verifier.auth(&login_info, pwd.password()).await;
+                        let auth_message =
+                            format!("{client_first_bare},{server_first},{without_proof}");
+                        let password =
+                            Password::PgScramSha256(&client_proof, auth_message.as_bytes());
+                        let server_signature = verifier.server_signature(auth_message.as_bytes());
+                        (
+                            self.login_verifier.auth(&login_info, password).await,
+                            Some(format!("v={}", base64::encode(server_signature))),
+                        )
+                    }
+                };
 
                 if let Ok(Some(user_info)) = auth_result {
+                    if let Some(server_final) = server_final {
+                        client
+                            .send(PgWireBackendMessage::Authentication(
+                                Authentication::SASLFinal(Bytes::from(server_final)),
+                            ))
+                            .await?;
+                    }
                     self.session.set_user_info(user_info);
                     set_client_info(client, &self.session);
                     auth::finish_authentication(client, self.param_provider.as_ref()).await;
@@ -210,6 +350,33 @@ impl StartupHandler for PostgresServerHandler {
     }
 }
 
+/// Parses the `client-first-message` of SCRAM, e.g. `n,,n=user,r=fyko+d2lbbFgONRv9qkxdawL`,
+/// returns the `client-first-message-bare` and the client nonce. Channel binding is not
+/// supported. The username is ignored as PostgreSQL does, it's sent in the startup message.
+fn parse_client_first(message: &str) -> Option<(String, String)> {
+    let bare = message
+        .strip_prefix("n,,")
+        .or_else(|| message.strip_prefix("y,,"))?;
+    let nonce = bare
+        .split(',')
+        .find_map(|attr| attr.strip_prefix("r="))
+        .filter(|nonce| !nonce.is_empty())?;
+    Some((bare.to_string(), nonce.to_string()))
+}
+
+/// Parses the `client-final-message` of SCRAM, e.g. `c=biws,r=<nonce>,p=<proof>`, returns the
+/// `client-final-message-without-proof` and the decoded client proof.
+fn parse_client_final(message: &str, nonce: &str) -> Option<(String, Vec<u8>)> {
+    let (without_proof, proof) = message.rsplit_once(",p=")?;
+    let client_nonce = without_proof
+        .split(',')
+        .find_map(|attr| attr.strip_prefix("r="))?;
+    if client_nonce != nonce {
+        return None;
+    }
+    Some((without_proof.to_string(), base64::decode(proof).ok()?))
+}
+
 async fn send_error<C>(client: &mut C, level: &str, code: &str, message: String) -> PgWireResult<()>
 where
     C: ClientInfo + Sink<PgWireBackendMessage> + Unpin + Send,
@@ -42,6 +42,7 @@ use serde::de::{self, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
 use session::context::{QueryContext, QueryContextRef};
 use snafu::{Location, OptionExt, ResultExt};
+use table::metadata::TableInfo;
 
 use crate::error::{
     CollectRecordbatchSnafu, Error, InternalSnafu, InvalidQuerySnafu, Result, UnexpectedResultSnafu,
@@ -57,6 +58,16 @@ pub trait PrometheusHandler {
     async fn do_query(&self, query: &PromQuery, query_ctx: QueryContextRef) -> Result<Output>;
 
     fn catalog_manager(&self) -> CatalogManagerRef;
+
+    /// Rule groups evaluated by this server, served by `/api/v1/rules`.
+    fn rule_groups(&self) -> Vec<RuleGroup> {
+        vec![]
+    }
+
+    /// Pending and firing alerts, served by `/api/v1/alerts`.
+    fn alerts(&self) -> Vec<Alert> {
+        vec![]
+    }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
@@ -76,6 +87,122 @@ pub struct PromData {
     pub result: Vec<PromSeries>,
 }
 
+/// Metadata of a metric, as returned by `/api/v1/metadata`.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct MetricMetadata {
+    #[serde(rename = "type")]
+    pub metric_type: String,
+    pub help: String,
+    pub unit: String,
+}
+
+/// Build information, as returned by `/api/v1/status/buildinfo`.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    pub version: String,
+    pub revision: String,
+    pub branch: String,
+    pub build_user: String,
+    pub build_date: String,
+    pub go_version: String,
+}
+
+/// Exemplars of one series, as returned by `/api/v1/query_exemplars`.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct PromExemplars {
+    #[serde(rename = "seriesLabels")]
+    pub series_labels: HashMap<String, String>,
+    pub exemplars: Vec<PromExemplar>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct PromExemplar {
+    pub labels: HashMap<String, String>,
+    pub value: String,
+    pub timestamp: f64,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleDiscovery {
+    pub groups: Vec<RuleGroup>,
+}
+
+/// A rule group and the status of its rules.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleGroup {
+    pub name: String,
+    pub file: String,
+    pub rules: Vec<RuleStatus>,
+    /// Evaluation interval in seconds.
+    pub interval: f64,
+    /// Time spent on the last evaluation in seconds.
+    pub evaluation_time: f64,
+    /// RFC 3339 time of the last evaluation.
+    pub last_evaluation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RuleStatus {
+    Alerting(AlertingRuleStatus),
+    Recording(RecordingRuleStatus),
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertingRuleStatus {
+    /// One of `inactive`, `pending` and `firing`.
+    pub state: String,
+    pub name: String,
+    pub query: String,
+    /// The `for` duration in seconds.
+    pub duration: f64,
+    pub labels: HashMap<String, String>,
+    pub annotations: HashMap<String, String>,
+    pub alerts: Vec<Alert>,
+    /// One of `ok`, `err` and `unknown`.
+    pub health: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub evaluation_time: f64,
+    pub last_evaluation: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingRuleStatus {
+    pub name: String,
+    pub query: String,
+    pub labels: HashMap<String, String>,
+    pub health: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub evaluation_time: f64,
+    pub last_evaluation: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertDiscovery {
+    pub alerts: Vec<Alert>,
+}
+
+/// An active alert of an alerting rule.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+    pub labels: HashMap<String, String>,
+    pub annotations: HashMap<String, String>,
+    /// Either `pending` or `firing`.
+    pub state: String,
+    /// RFC 3339 time when the alert became active.
+    pub active_at: String,
+    pub value: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(untagged)]
 pub enum PrometheusResponse {
@@ -83,6 +210,12 @@ pub enum PrometheusResponse {
     Labels(Vec<String>),
     Series(Vec<HashMap<String, String>>),
     LabelValues(Vec<String>),
+    BuildInfo(BuildInfo),
+    Rules(RuleDiscovery),
+    Alerts(AlertDiscovery),
+    Metadata(HashMap<String, Vec<MetricMetadata>>),
+    Exemplars(Vec<PromExemplars>),
+    FormatQuery(String),
 }
 
 impl Default for PrometheusResponse {
@@ -181,7 +314,7 @@ impl PrometheusJsonResponse {
     }
 
     /// Convert [RecordBatches] to [PromData]
-    fn record_batches_to_data(
+    pub fn record_batches_to_data(
         batches: RecordBatches,
         metric_name: String,
         result_type: ValueType,
@@ -857,3 +990,202 @@ pub async fn series_query(
     }
     PrometheusJsonResponse::success(PrometheusResponse::Series(series))
 }
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct MetadataQuery {
+    metric: Option<String>,
+    limit: Option<usize>,
+    db: Option<String>,
+}
+
+/// Metric metadata backed by the tables in the catalog. GreptimeDB doesn't record
+/// metric types, so every metric is reported as `unknown` and the table comment is
+/// used as the help text. Tables that don't look like metrics are skipped.
+#[axum_macros::debug_handler]
+pub async fn metadata_query(
+    State(handler): State<PrometheusHandlerRef>,
+    Query(params): Query<MetadataQuery>,
+) -> Json<PrometheusJsonResponse> {
+    let _timer = timer!(crate::metrics::METRIC_HTTP_PROMQL_METADATA_QUERY_ELAPSED);
+
+    let db = &params.db.unwrap_or(DEFAULT_SCHEMA_NAME.to_string());
+    let (catalog, schema) = parse_catalog_and_schema_from_db_string(db);
+
+    match retrieve_metric_metadata(
+        catalog,
+        schema,
+        params.metric.as_deref(),
+        params.limit,
+        &handler.catalog_manager(),
+    )
+    .await
+    {
+        Ok(metadata) => PrometheusJsonResponse::success(PrometheusResponse::Metadata(metadata)),
+        Err(e) => PrometheusJsonResponse::error(e.status_code().to_string(), e.to_string()),
+    }
+}
+
+async fn retrieve_metric_metadata(
+    catalog: &str,
+    schema: &str,
+    metric: Option<&str>,
+    limit: Option<usize>,
+    manager: &CatalogManagerRef,
+) -> std::result::Result<HashMap<String, Vec<MetricMetadata>>, catalog::error::Error> {
+    let mut table_names = match metric {
+        Some(metric) => vec![metric.to_string()],
+        None => manager.table_names(catalog, schema).await?,
+    };
+    table_names.sort_unstable();
+    // A non-positive limit means no limit, as in Prometheus.
+    let limit = limit.filter(|limit| *limit > 0).unwrap_or(usize::MAX);
+
+    let mut metadata = HashMap::new();
+    for table_name in table_names {
+        // Stops looking up tables once there are enough metrics.
+        if metadata.len() >= limit {
+            break;
+        }
+        let Some(table) = manager.table(catalog, schema, &table_name).await? else {
+            continue;
+        };
+        let table_info = table.table_info();
+        if !is_metric_table(&table_info) {
+            continue;
+        }
+        let help = table_info.desc.clone().unwrap_or_default();
+        let _ = metadata.insert(
+            table_name,
+            vec![MetricMetadata {
+                metric_type: "unknown".to_string(),
+                help,
+                unit: String::new(),
+            }],
+        );
+    }
+
+    Ok(metadata)
+}
+
+/// Returns whether the table holds Prometheus metrics, i.e. it has a time index and
+/// all its fields, which are the sample values, are numeric.
+fn is_metric_table(table_info: &TableInfo) -> bool {
+    let schema = &table_info.meta.schema;
+    if schema.timestamp_column().is_none() {
+        return false;
+    }
+    let mut fields = table_info.meta.field_column_names().peekable();
+    fields.peek().is_some()
+        && fields.all(|name| {
+            schema.column_schema_by_name(name).is_some_and(|column| {
+                matches!(
+                    column.data_type,
+                    ConcreteDataType::Int8(_)
+                        | ConcreteDataType::Int16(_)
+                        | ConcreteDataType::Int32(_)
+                        | ConcreteDataType::Int64(_)
+                        | ConcreteDataType::UInt8(_)
+                        | ConcreteDataType::UInt16(_)
+                        | ConcreteDataType::UInt32(_)
+                        | ConcreteDataType::UInt64(_)
+                        | ConcreteDataType::Float32(_)
+                        | ConcreteDataType::Float64(_)
+                )
+            })
+        })
+}
+
+#[axum_macros::debug_handler]
+pub async fn build_info_query() -> Json<PrometheusJsonResponse> {
+    PrometheusJsonResponse::success(PrometheusResponse::BuildInfo(BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        revision: env!("GIT_COMMIT").to_string(),
+        branch: env!("GIT_BRANCH").to_string(),
+        build_user: String::new(),
+        build_date: env!("SOURCE_TIMESTAMP").to_string(),
+        go_version: env!("RUSTC_VERSION").to_string(),
+    }))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ExemplarsQuery {
+    query: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    db: Option<String>,
+}
+
+/// GreptimeDB doesn't store exemplars yet. The query is still validated so that
+/// malformed expressions are reported, and an empty result is returned otherwise.
+#[axum_macros::debug_handler]
+pub async fn exemplars_query(
+    Query(params): Query<ExemplarsQuery>,
+    Form(form_params): Form<ExemplarsQuery>,
+) -> Json<PrometheusJsonResponse> {
+    let query = params.query.or(form_params.query).unwrap_or_default();
+    if let Err(reason) = promql_parser::parser::parse(&query) {
+        return PrometheusJsonResponse::error("bad_data", reason);
+    }
+    PrometheusJsonResponse::success(PrometheusResponse::Exemplars(vec![]))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FormatQuery {
+    query: Option<String>,
+}
+
+/// Validates the query and returns it back. The parser doesn't provide a pretty
+/// printer, so the expression is only trimmed rather than reformatted.
+#[axum_macros::debug_handler]
+pub async fn format_query(
+    Query(params): Query<FormatQuery>,
+    Form(form_params): Form<FormatQuery>,
+) -> Json<PrometheusJsonResponse> {
+    let query = params.query.or(form_params.query).unwrap_or_default();
+    if let Err(reason) = promql_parser::parser::parse(&query) {
+        return PrometheusJsonResponse::error("bad_data", reason);
+    }
+    PrometheusJsonResponse::success(PrometheusResponse::FormatQuery(query.trim().to_string()))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RulesQuery {
+    #[serde(rename = "type")]
+    rule_type: Option<String>,
+}
+
+#[axum_macros::debug_handler]
+pub async fn rules_query(
+    State(handler): State<PrometheusHandlerRef>,
+    Query(params): Query<RulesQuery>,
+) -> Json<PrometheusJsonResponse> {
+    let mut groups = handler.rule_groups();
+    match params.rule_type.as_deref() {
+        None => {}
+        Some("alert") => groups.iter_mut().for_each(|group| {
+            group
+                .rules
+                .retain(|rule| matches!(rule, RuleStatus::Alerting(_)))
+        }),
+        Some("record") => groups.iter_mut().for_each(|group| {
+            group
+                .rules
+                .retain(|rule| matches!(rule, RuleStatus::Recording(_)))
+        }),
+        Some(other) => {
+            return PrometheusJsonResponse::error(
+                "bad_data",
+                format!("unsupported rule type {other}, expect alert or record"),
+            )
+        }
+    }
+    PrometheusJsonResponse::success(PrometheusResponse::Rules(RuleDiscovery { groups }))
+}
+
+#[axum_macros::debug_handler]
+pub async fn alerts_query(
+    State(handler): State<PrometheusHandlerRef>,
+) -> Json<PrometheusJsonResponse> {
+    let alerts = handler.alerts();
+    PrometheusJsonResponse::success(PrometheusResponse::Alerts(AlertDiscovery { alerts }))
+}
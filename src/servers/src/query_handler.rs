@@ -31,9 +31,15 @@ use std::sync::Arc;
 use api::prom_store::remote::{ReadRequest, WriteRequest};
 use async_trait::async_trait;
 use common_query::Output;
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse,
+};
 use opentelemetry_proto::tonic::collector::metrics::v1::{
     ExportMetricsServiceRequest, ExportMetricsServiceResponse,
 };
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
 use session::context::QueryContextRef;
 
 use crate::error::Result;
@@ -96,4 +102,20 @@ pub trait OpenTelemetryProtocolHandler {
         request: ExportMetricsServiceRequest,
         ctx: QueryContextRef,
     ) -> Result<ExportMetricsServiceResponse>;
+
+    /// Handling opentelemetry traces request, spans are written into the table `table_name`
+    async fn traces(
+        &self,
+        request: ExportTraceServiceRequest,
+        table_name: String,
+        ctx: QueryContextRef,
+    ) -> Result<ExportTraceServiceResponse>;
+
+    /// Handling opentelemetry logs request, log records are written into the table `table_name`
+    async fn logs(
+        &self,
+        request: ExportLogsServiceRequest,
+        table_name: String,
+        ctx: QueryContextRef,
+    ) -> Result<ExportLogsServiceResponse>;
 }
@@ -20,6 +20,7 @@ use auth::tests::{DatabaseAuthInfo, MockUserProvider};
 use auth::UserProviderRef;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_runtime::Builder as RuntimeBuilder;
+use common_test_util::temp_dir::create_temp_dir;
 use pgwire::api::Type;
 use rand::rngs::StdRng;
 use rand::Rng;
@@ -127,6 +128,59 @@ async fn test_schema_validating() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_hashed_password_auth() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let dir = create_temp_dir("test_hashed_password_auth");
+    let path = dir.path().join("htpasswd");
+    let scram = auth::ScramSha256Verifier::new("scram_pwd", b"0123456789".to_vec(), 4096);
+    let md5 = format!("{:x}", md5::compute("md5_pwdmd5_user"));
+    std::fs::write(&path, format!("scram_user:{scram}\nmd5_user:md5{md5}\n")).unwrap();
+    let user_provider =
+        auth::user_provider_from_option(&format!("htpasswd_user_provider:{}", path.display()))
+            .unwrap();
+
+    let instance = Arc::new(create_testing_instance(MemTable::default_numbers_table()));
+    let io_runtime = Arc::new(
+        RuntimeBuilder::default()
+            .worker_threads(4)
+            .thread_name("postgres-io-handlers")
+            .build()
+            .unwrap(),
+    );
+    let pg_server = PostgresServer::new(
+        instance,
+        Default::default(),
+        io_runtime,
+        Some(user_provider),
+    );
+    let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let server_port = pg_server.start(listening).await.unwrap().port();
+
+    for (user, password, ok) in [
+        ("scram_user", "scram_pwd", true),
+        ("scram_user", "wrong", false),
+        ("md5_user", "md5_pwd", true),
+        ("md5_user", "wrong", false),
+    ] {
+        let url = format!(
+            "host=127.0.0.1 port={server_port} user={user} password={password} connect_timeout=2 dbname={DEFAULT_SCHEMA_NAME}",
+        );
+        let result = tokio_postgres::connect(&url, NoTls).await;
+        assert_eq!(ok, result.is_ok(), "user: {user}, password: {password}");
+        if let Ok((client, conn)) = result {
+            let _handle = tokio::spawn(conn);
+            let rows = client
+                .simple_query("SELECT uint32s FROM numbers LIMIT 1")
+                .await;
+            assert!(rows.is_ok());
+        }
+    }
+
+    pg_server.shutdown().await.unwrap();
+    Ok(())
+}
+
 // #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_shutdown_pg_server(with_pwd: bool) -> Result<()> {
     common_telemetry::init_default_ut_logging();
@@ -111,6 +111,10 @@ impl<'a> ParserContext<'a> {
 
                     Keyword::TRUNCATE => self.parse_truncate(),
 
+                    Keyword::GRANT => self.parse_grant(),
+
+                    Keyword::REVOKE => self.parse_revoke(),
+
                     Keyword::NoKeyword
                         if w.value.to_uppercase() == tql_parser::TQL && w.quote_style.is_none() =>
                     {
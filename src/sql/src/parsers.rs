@@ -20,6 +20,7 @@ pub(crate) mod describe_parser;
 pub(crate) mod drop_parser;
 pub(crate) mod explain_parser;
 pub(crate) mod insert_parser;
+pub(crate) mod privilege_parser;
 pub(crate) mod query_parser;
 pub(crate) mod show_parser;
 pub(crate) mod tql_parser;
@@ -14,8 +14,9 @@
 
 use common_query::AddColumnLocation;
 use snafu::ResultExt;
+use sqlparser::ast::{Ident, SqlOption};
 use sqlparser::keywords::Keyword;
-use sqlparser::parser::ParserError;
+use sqlparser::parser::{Parser, ParserError};
 use sqlparser::tokenizer::Token;
 
 use crate::error::{self, Result};
@@ -73,6 +74,22 @@ impl<'a> ParserContext<'a> {
                     parser.peek_token()
                 )));
             }
+        } else if Self::parse_modify_keyword(parser) {
+            let _ = parser.parse_keyword(Keyword::COLUMN);
+            // `SET` can't be a data type, so it must start a `SET DEFAULT` clause.
+            let is_set_default = matches!(
+                parser.peek_nth_token(1).token,
+                Token::Word(word) if word.keyword == Keyword::SET
+            );
+            if is_set_default {
+                let name = parser.parse_identifier()?;
+                parser.expect_keywords(&[Keyword::SET, Keyword::DEFAULT])?;
+                let default = parser.parse_expr()?;
+                AlterTableOperation::SetColumnDefault { name, default }
+            } else {
+                let column_def = parser.parse_column_def()?;
+                AlterTableOperation::ModifyColumn { column_def }
+            }
         } else if parser.parse_keyword(Keyword::RENAME) {
             let new_table_name_obj = parser.parse_object_name()?;
             let new_table_name = match &new_table_name_obj.0[..] {
@@ -84,21 +101,53 @@ impl<'a> ParserContext<'a> {
                 }
             };
             AlterTableOperation::RenameTable { new_table_name }
+        } else if parser.parse_keyword(Keyword::SET) {
+            let options = parser.parse_comma_separated(Self::parse_table_option)?;
+            AlterTableOperation::SetTableOptions { options }
         } else {
             return Err(ParserError::ParserError(format!(
-                "expect keyword ADD or DROP or RENAME after ALTER TABLE, found {}",
+                "expect keyword ADD or DROP or MODIFY or RENAME or SET after ALTER TABLE, found {}",
                 parser.peek_token()
             )));
         };
         Ok(AlterTable::new(table_name, alter_operation))
     }
+
+    /// Consumes the `MODIFY` keyword if it's the next token.
+    fn parse_modify_keyword(parser: &mut Parser) -> bool {
+        if let Token::Word(word) = parser.peek_token().token {
+            if word.value.to_ascii_uppercase() == "MODIFY" {
+                let _ = parser.next_token();
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Parses a table option like `ttl = '7d'`. The name may contain dots, e.g.
+    /// `compaction.twcs.time_window_seconds = 3600`.
+    fn parse_table_option(parser: &mut Parser) -> std::result::Result<SqlOption, ParserError> {
+        let name = parser.parse_object_name()?;
+        parser.expect_token(&Token::Eq)?;
+        let value = parser.parse_value()?;
+        let name = name
+            .0
+            .iter()
+            .map(|ident| ident.value.as_str())
+            .collect::<Vec<_>>()
+            .join(".");
+        Ok(SqlOption {
+            name: Ident::new(name),
+            value,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
 
-    use sqlparser::ast::{ColumnOption, DataType};
+    use sqlparser::ast::{ColumnOption, DataType, Expr, Value};
 
     use super::*;
     use crate::dialect::GreptimeDbDialect;
@@ -241,13 +290,103 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_alter_modify_column() {
+        let sql = "ALTER TABLE my_metric_1 MODIFY COLUMN a BIGINT NULL";
+        let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        assert_matches!(statement, Statement::Alter { .. });
+        match statement {
+            Statement::Alter(alter_table) => {
+                assert_eq!("my_metric_1", alter_table.table_name().0[0].value);
+
+                let alter_operation = alter_table.alter_operation();
+                assert_matches!(alter_operation, AlterTableOperation::ModifyColumn { .. });
+                match alter_operation {
+                    AlterTableOperation::ModifyColumn { column_def } => {
+                        assert_eq!("a", column_def.name.value);
+                        assert_eq!(DataType::BigInt(None), column_def.data_type);
+                        assert!(column_def
+                            .options
+                            .iter()
+                            .any(|o| matches!(o.option, ColumnOption::Null)));
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        // The COLUMN keyword is optional.
+        let sql = "ALTER TABLE my_metric_1 MODIFY a DOUBLE";
+        let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        match result.remove(0) {
+            Statement::Alter(alter_table) => match alter_table.alter_operation() {
+                AlterTableOperation::ModifyColumn { column_def } => {
+                    assert_eq!("a", column_def.name.value);
+                    assert_eq!(DataType::Double, column_def.data_type);
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_alter_set_column_default() {
+        let sql = "ALTER TABLE my_metric_1 MODIFY COLUMN a SET DEFAULT 1";
+        let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        assert_matches!(statement, Statement::Alter { .. });
+        match statement {
+            Statement::Alter(alter_table) => {
+                let alter_operation = alter_table.alter_operation();
+                assert_matches!(
+                    alter_operation,
+                    AlterTableOperation::SetColumnDefault { .. }
+                );
+                match alter_operation {
+                    AlterTableOperation::SetColumnDefault { name, default } => {
+                        assert_eq!("a", name.value);
+                        assert_eq!(&Expr::Value(Value::Number("1".to_string(), false)), default);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 modify b set default 'x'";
+        let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        match result.remove(0) {
+            Statement::Alter(alter_table) => match alter_table.alter_operation() {
+                AlterTableOperation::SetColumnDefault { name, default } => {
+                    assert_eq!("b", name.value);
+                    assert_eq!(
+                        &Expr::Value(Value::SingleQuotedString("x".to_string())),
+                        default
+                    );
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 MODIFY a SET 1";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
+
     #[test]
     fn test_parse_alter_rename_table() {
         let sql = "ALTER TABLE test_table table_t";
         let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap_err();
         assert!(result
             .to_string()
-            .contains("expect keyword ADD or DROP or RENAME after ALTER TABLE"));
+            .contains("expect keyword ADD or DROP or MODIFY or RENAME or SET after ALTER TABLE"));
 
         let sql = "ALTER TABLE test_table RENAME table_t";
         let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
@@ -271,4 +410,45 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_parse_alter_set_table_options() {
+        let sql = "ALTER TABLE test_table SET";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+
+        let sql =
+            "ALTER TABLE test_table SET ttl = '7d', compaction.twcs.time_window_seconds = 3600";
+        let mut result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        assert_matches!(statement, Statement::Alter { .. });
+        match statement {
+            Statement::Alter(alter_table) => {
+                assert_eq!("test_table", alter_table.table_name().0[0].value);
+
+                let alter_operation = alter_table.alter_operation();
+                assert_matches!(alter_operation, AlterTableOperation::SetTableOptions { .. });
+                match alter_operation {
+                    AlterTableOperation::SetTableOptions { options } => {
+                        assert_eq!(
+                            &vec![
+                                SqlOption {
+                                    name: Ident::new("ttl"),
+                                    value: Value::SingleQuotedString("7d".to_string()),
+                                },
+                                SqlOption {
+                                    name: Ident::new("compaction.twcs.time_window_seconds"),
+                                    value: Value::Number("3600".to_string(), false),
+                                },
+                            ],
+                            options
+                        );
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
 }
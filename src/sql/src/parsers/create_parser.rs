@@ -26,19 +26,24 @@ use sqlparser::tokenizer::{Token, TokenWithLocation, Word};
 
 use crate::ast::{ColumnDef, Ident, TableConstraint, Value as SqlValue};
 use crate::error::{
-    self, InvalidColumnOptionSnafu, InvalidTimeIndexSnafu, MissingTimeIndexSnafu, Result,
-    SyntaxSnafu,
+    self, InvalidColumnOptionSnafu, InvalidTableNameSnafu, InvalidTimeIndexSnafu,
+    MissingTimeIndexSnafu, Result, SyntaxSnafu,
 };
 use crate::parser::ParserContext;
+use crate::parsers::privilege_parser::{ROLE, USER};
 use crate::statements::create::{
-    CreateDatabase, CreateExternalTable, CreateTable, PartitionEntry, Partitions, TIME_INDEX,
+    CreateDatabase, CreateExternalTable, CreateMaterializedView, CreateTable, CreateView,
+    PartitionEntry, Partitions, TIME_INDEX,
 };
+use crate::statements::query::Query;
 use crate::statements::statement::Statement;
 use crate::statements::{sql_data_type_to_concrete_data_type, sql_value_to_value};
 use crate::util::parse_option_string;
 
 const ENGINE: &str = "ENGINE";
 const MAXVALUE: &str = "MAXVALUE";
+const HASH: &str = "HASH";
+const PARTITIONS: &str = "PARTITIONS";
 
 static LESS: Lazy<Token> = Lazy::new(|| Token::make_keyword("LESS"));
 static THAN: Lazy<Token> = Lazy::new(|| Token::make_keyword("THAN"));
@@ -54,6 +59,18 @@ impl<'a> ParserContext<'a> {
 
                 Keyword::EXTERNAL => self.parse_create_external_table(),
 
+                Keyword::MATERIALIZED => self.parse_create_materialized_view(),
+
+                Keyword::VIEW => self.parse_create_view(),
+
+                _ if w.quote_style.is_none() && w.value.eq_ignore_ascii_case(USER) => {
+                    self.parse_create_user()
+                }
+
+                _ if w.quote_style.is_none() && w.value.eq_ignore_ascii_case(ROLE) => {
+                    self.parse_create_role()
+                }
+
                 _ => self.unsupported(w.to_string()),
             },
             unexpected => self.unsupported(unexpected.to_string()),
@@ -102,6 +119,72 @@ impl<'a> ParserContext<'a> {
         }))
     }
 
+    fn parse_create_materialized_view(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        self.parser
+            .expect_keyword(Keyword::VIEW)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let view_name = self
+            .parser
+            .parse_object_name()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a view name",
+                actual: self.peek_token_as_string(),
+            })?;
+        self.parser
+            .expect_keyword(Keyword::AS)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let query = self
+            .parser
+            .parse_query()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        Ok(Statement::CreateMaterializedView(CreateMaterializedView {
+            name: view_name,
+            if_not_exists,
+            query: Box::new(Query::try_from(query)?),
+        }))
+    }
+
+    fn parse_create_view(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let view_name = self
+            .parser
+            .parse_object_name()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a view name",
+                actual: self.peek_token_as_string(),
+            })?;
+        ensure!(
+            !view_name.0.is_empty(),
+            InvalidTableNameSnafu {
+                name: view_name.to_string(),
+            }
+        );
+        self.parser
+            .expect_keyword(Keyword::AS)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let query = self
+            .parser
+            .parse_query()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        Ok(Statement::CreateView(CreateView {
+            name: view_name,
+            if_not_exists,
+            query: Box::new(Query::try_from(query)?),
+            columns: vec![],
+        }))
+    }
+
     fn parse_create_database(&mut self) -> Result<Statement> {
         let _ = self.parser.next_token();
 
@@ -166,15 +249,28 @@ impl<'a> ParserContext<'a> {
 
     // "PARTITION BY ..." syntax:
     // https://dev.mysql.com/doc/refman/8.0/en/partitioning-columns-range.html
+    // https://dev.mysql.com/doc/refman/8.0/en/partitioning-hash.html
     fn parse_partitions(&mut self) -> Result<Option<Partitions>> {
         if !self.parser.parse_keyword(Keyword::PARTITION) {
             return Ok(None);
         }
         self.parser
-            .expect_keywords(&[Keyword::BY, Keyword::RANGE, Keyword::COLUMNS])
+            .expect_keyword(Keyword::BY)
             .context(error::UnexpectedSnafu {
                 sql: self.sql,
-                expected: "BY, RANGE, COLUMNS",
+                expected: "BY",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        if self.consume_token(HASH) {
+            return self.parse_hash_partitions().map(Some);
+        }
+
+        self.parser
+            .expect_keywords(&[Keyword::RANGE, Keyword::COLUMNS])
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "HASH or RANGE, COLUMNS",
                 actual: self.peek_token_as_string(),
             })?;
 
@@ -188,9 +284,36 @@ impl<'a> ParserContext<'a> {
         Ok(Some(Partitions {
             column_list,
             entries,
+            hash_partitions: None,
         }))
     }
 
+    // "HASH (column) PARTITIONS n", the part after "PARTITION BY".
+    fn parse_hash_partitions(&mut self) -> Result<Partitions> {
+        let column_list = self
+            .parser
+            .parse_parenthesized_column_list(Mandatory, false)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        if !self.consume_token(PARTITIONS) {
+            return self.expected(PARTITIONS, self.parser.peek_token());
+        }
+        let token = self.parser.next_token();
+        let num_partitions = match &token.token {
+            Token::Number(n, _) => n.parse::<u32>().ok(),
+            _ => None,
+        };
+        let Some(num_partitions) = num_partitions else {
+            return self.expected("the number of partitions", token);
+        };
+
+        Ok(Partitions {
+            column_list,
+            entries: vec![],
+            hash_partitions: Some(num_partitions),
+        })
+    }
+
     fn parse_partition_entry(&mut self) -> Result<PartitionEntry> {
         self.parser
             .expect_keyword(Keyword::PARTITION)
@@ -632,6 +755,10 @@ fn validate_time_index(create_table: &CreateTable) -> Result<()> {
 fn validate_partitions(columns: &[ColumnDef], partitions: &Partitions) -> Result<()> {
     let partition_columns = ensure_partition_columns_defined(columns, partitions)?;
 
+    if let Some(num_partitions) = partitions.hash_partitions {
+        return validate_hash_partitions(&partition_columns, num_partitions);
+    }
+
     ensure_partition_names_no_duplicate(partitions)?;
 
     ensure_value_list_len_matches_columns(partitions, &partition_columns)?;
@@ -643,6 +770,23 @@ fn validate_partitions(columns: &[ColumnDef], partitions: &Partitions) -> Result
     Ok(())
 }
 
+/// Ensure that hash partitioning is on exactly one column and creates at least one partition.
+fn validate_hash_partitions(partition_columns: &[&ColumnDef], num_partitions: u32) -> Result<()> {
+    ensure!(
+        partition_columns.len() == 1,
+        error::InvalidSqlSnafu {
+            msg: "PARTITION BY HASH only supports one partition column.",
+        }
+    );
+    ensure!(
+        num_partitions > 0,
+        error::InvalidSqlSnafu {
+            msg: "The number of hash partitions must be greater than 0.",
+        }
+    );
+    Ok(())
+}
+
 /// Ensure that partition ranges fully cover all values.
 // Simply check the last partition is bounded by "MAXVALUE"s.
 // MySQL does not have this restriction. However, I think we'd better have it because:
@@ -920,6 +1064,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_create_materialized_view() {
+        let sql = "CREATE MATERIALIZED VIEW IF NOT EXISTS cpu_5m AS \
+                   SELECT ts, host, avg(cpu) RANGE '5m' AS cpu FROM monitor ALIGN '1m' BY (host)";
+        let stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match &stmts[0] {
+            Statement::CreateMaterializedView(c) => {
+                assert_eq!(c.name.to_string(), "cpu_5m");
+                assert!(c.if_not_exists);
+                assert!(c.query.to_string().contains("FROM monitor"));
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "CREATE MATERIALIZED VIEW cpu_5m SELECT * FROM monitor";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+
+        let sql = "CREATE MATERIALIZED VIEW AS SELECT * FROM monitor";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
+
+    #[test]
+    fn test_parse_create_view() {
+        let sql = "CREATE VIEW IF NOT EXISTS my_schema.busy_hosts AS \
+                   SELECT host, cpu FROM monitor WHERE cpu > 0.8";
+        let stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match &stmts[0] {
+            Statement::CreateView(c) => {
+                assert_eq!(c.name.to_string(), "my_schema.busy_hosts");
+                assert!(c.if_not_exists);
+                assert!(c.columns.is_empty());
+                assert_eq!(
+                    c.query.to_string(),
+                    "SELECT host, cpu FROM monitor WHERE cpu > 0.8"
+                );
+                assert_eq!(
+                    c.to_string(),
+                    "CREATE VIEW IF NOT EXISTS my_schema.busy_hosts AS \
+                     SELECT host, cpu FROM monitor WHERE cpu > 0.8"
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "CREATE VIEW busy_hosts SELECT * FROM monitor";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+
+        let sql = "CREATE VIEW AS SELECT * FROM monitor";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
+
     #[test]
     fn test_validate_create() {
         let sql = r"
@@ -1375,6 +1572,82 @@ ENGINE=mito";
             .contains("Expected a concrete value, found: MAXVALU"));
     }
 
+    #[test]
+    fn test_parse_create_table_with_hash_partitions() {
+        let sql = r"
+CREATE TABLE monitor (
+  host       STRING,
+  ts         TIMESTAMP,
+  cpu        DOUBLE DEFAULT 0,
+  TIME INDEX (ts),
+  PRIMARY KEY (host),
+)
+PARTITION BY HASH (host) PARTITIONS 4
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(result.len(), 1);
+        match &result[0] {
+            Statement::CreateTable(c) => {
+                let partitions = c.partitions.as_ref().unwrap();
+                assert_eq!(partitions.column_list, vec![Ident::new("host")]);
+                assert!(partitions.entries.is_empty());
+                assert_eq!(partitions.hash_partitions, Some(4));
+                assert_eq!(c.engine, "mito");
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (b, a) PARTITIONS 4
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("PARTITION BY HASH only supports one partition column"));
+
+        let sql = r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (x) PARTITIONS 4
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Partition column \"x\" not defined!"));
+
+        let sql = r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (b) PARTITIONS 0
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("The number of hash partitions must be greater than 0"));
+
+        let sql = r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (b) 4
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Expected PARTITIONS, found: 4"));
+
+        let sql = r"
+CREATE TABLE rcx ( a INT, b STRING, c TIMESTAMP, TIME INDEX (c) )
+PARTITION BY HASH (b) PARTITIONS many
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Expected the number of partitions, found: many"));
+    }
+
     fn assert_column_def(column: &ColumnDef, name: &str, data_type: &str) {
         assert_eq!(column.name.to_string(), name);
         assert_eq!(column.data_type.to_string(), data_type);
@@ -17,13 +17,17 @@ use sqlparser::keywords::Keyword;
 
 use crate::error::{self, InvalidTableNameSnafu, Result};
 use crate::parser::ParserContext;
-use crate::statements::drop::DropTable;
+use crate::statements::drop::{DropTable, DropView};
 use crate::statements::statement::Statement;
 
 /// DROP statement parser implementation
 impl<'a> ParserContext<'a> {
     pub(crate) fn parse_drop(&mut self) -> Result<Statement> {
         let _ = self.parser.next_token();
+        if self.matches_keyword(Keyword::VIEW) {
+            let _ = self.parser.next_token();
+            return self.parse_drop_view();
+        }
         if !self.matches_keyword(Keyword::TABLE) {
             return self.unsupported(self.peek_token_as_string());
         }
@@ -46,6 +50,26 @@ impl<'a> ParserContext<'a> {
 
         Ok(Statement::DropTable(DropTable::new(table_ident)))
     }
+
+    fn parse_drop_view(&mut self) -> Result<Statement> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let view_ident =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a view name",
+                    actual: self.peek_token_as_string(),
+                })?;
+        ensure!(
+            !view_ident.0.is_empty(),
+            InvalidTableNameSnafu {
+                name: view_ident.to_string()
+            }
+        );
+
+        Ok(Statement::DropView(DropView::new(view_ident, if_exists)))
+    }
 }
 
 #[cfg(test)]
@@ -88,4 +112,27 @@ mod tests {
             ])))
         )
     }
+
+    #[test]
+    pub fn test_drop_view() {
+        let sql = "DROP VIEW my_schema.foo";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropView(DropView::new(
+                ObjectName(vec![Ident::new("my_schema"), Ident::new("foo")]),
+                false
+            ))
+        );
+
+        let sql = "DROP VIEW IF EXISTS foo";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropView(DropView::new(ObjectName(vec![Ident::new("foo")]), true))
+        );
+
+        let sql = "DROP VIEW";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
 }
@@ -0,0 +1,369 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::ResultExt;
+use sqlparser::ast::{Ident, ObjectName};
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::privilege::{
+    CreateRole, CreateUser, Grant, GrantObject, Grantable, Privilege, Revoke,
+};
+use crate::statements::statement::Statement;
+
+pub(crate) const USER: &str = "USER";
+pub(crate) const ROLE: &str = "ROLE";
+const IDENTIFIED: &str = "IDENTIFIED";
+const GRANT_OBJECT_SYNTAX: &str =
+    "*.*, <catalog>.*.*, [<catalog>.]<schema>.* or [[<catalog>.]<schema>.]<table>";
+
+/// Parses `CREATE USER`, `CREATE ROLE`, `GRANT` and `REVOKE` statements:
+///
+/// ```sql
+/// CREATE USER [IF NOT EXISTS] user IDENTIFIED BY 'password';
+/// CREATE ROLE [IF NOT EXISTS] role;
+/// GRANT privilege [, privilege ...] ON object TO user_or_role;
+/// GRANT role TO user;
+/// REVOKE privilege [, privilege ...] ON object FROM user_or_role;
+/// REVOKE role FROM user;
+/// ```
+///
+/// `privilege` is one of `READ` (or `SELECT`), `WRITE` (or `INSERT`) and `ALL [PRIVILEGES]`.
+/// `object` is one of `*.*`, `catalog.*.*`, `[catalog.]schema.*` and `[[catalog.]schema.]table`.
+impl<'a> ParserContext<'a> {
+    /// Parses `CREATE USER`. The `CREATE` keyword should have been consumed.
+    pub(crate) fn parse_create_user(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parse_principal("a user name")?;
+
+        if !self.consume_token(IDENTIFIED) {
+            return self.expected(IDENTIFIED, self.parser.peek_token());
+        }
+        self.parser
+            .expect_keyword(Keyword::BY)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let password = match self.parser.next_token() {
+            TokenWithLocation {
+                token: Token::SingleQuotedString(s) | Token::DoubleQuotedString(s),
+                ..
+            } => s,
+            unexpected => return self.expected("a quoted password", unexpected),
+        };
+
+        Ok(Statement::CreateUser(CreateUser {
+            name,
+            password,
+            if_not_exists,
+        }))
+    }
+
+    /// Parses `CREATE ROLE`. The `CREATE` keyword should have been consumed.
+    pub(crate) fn parse_create_role(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parse_principal("a role name")?;
+
+        Ok(Statement::CreateRole(CreateRole {
+            name,
+            if_not_exists,
+        }))
+    }
+
+    pub(crate) fn parse_grant(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let grantable = self.parse_grantable()?;
+        self.parser
+            .expect_keyword(Keyword::TO)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let grantee = self.parse_principal("a user or role name")?;
+
+        Ok(Statement::Grant(Grant { grantable, grantee }))
+    }
+
+    pub(crate) fn parse_revoke(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let grantable = self.parse_grantable()?;
+        self.parser
+            .expect_keyword(Keyword::FROM)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let grantee = self.parse_principal("a user or role name")?;
+
+        Ok(Statement::Revoke(Revoke { grantable, grantee }))
+    }
+
+    fn parse_grantable(&mut self) -> Result<Grantable> {
+        if self.peek_privilege().is_none() {
+            let role = self.parse_principal("a privilege or role name")?;
+            return Ok(Grantable::Role(role));
+        }
+
+        let mut privileges = Vec::new();
+        loop {
+            let token = self.parser.next_token();
+            let Some(privilege) = token_to_privilege(&token.token) else {
+                return self.expected("a privilege", token);
+            };
+            if privilege == Privilege::All {
+                let _ = self.parser.parse_keyword(Keyword::PRIVILEGES);
+            }
+            privileges.push(privilege);
+
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+
+        self.parser
+            .expect_keyword(Keyword::ON)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let object = self.parse_grant_object()?;
+
+        Ok(Grantable::Privileges { privileges, object })
+    }
+
+    fn peek_privilege(&self) -> Option<Privilege> {
+        token_to_privilege(&self.parser.peek_token().token)
+    }
+
+    /// Parses `*.*`, `catalog.*.*`, `[catalog.]schema.*` or `[[catalog.]schema.]table`.
+    fn parse_grant_object(&mut self) -> Result<GrantObject> {
+        // `None` stands for the wildcard `*`.
+        let mut parts: Vec<Option<Ident>> = Vec::new();
+        loop {
+            match self.parser.next_token() {
+                TokenWithLocation {
+                    token: Token::Mul, ..
+                } => parts.push(None),
+                TokenWithLocation {
+                    token: Token::Word(w),
+                    ..
+                } => parts.push(Some(w.to_ident())),
+                unexpected => return self.expected("an identifier or '*'", unexpected),
+            }
+            if !self.parser.consume_token(&Token::Period) {
+                break;
+            }
+        }
+
+        let object = match &parts[..] {
+            [None, None] => GrantObject::All,
+            [Some(catalog), None, None] => GrantObject::Catalog(catalog.clone()),
+            [Some(schema), None] => GrantObject::Schema(ObjectName(vec![schema.clone()])),
+            [Some(catalog), Some(schema), None] => {
+                GrantObject::Schema(ObjectName(vec![catalog.clone(), schema.clone()]))
+            }
+            [Some(_)] | [Some(_), Some(_)] | [Some(_), Some(_), Some(_)] => {
+                GrantObject::Table(ObjectName(parts.iter().flatten().cloned().collect()))
+            }
+            _ => {
+                let actual = parts
+                    .iter()
+                    .map(|part| match part {
+                        Some(ident) => ident.to_string(),
+                        None => "*".to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".");
+                return error::InvalidSqlSnafu {
+                    msg: format!(
+                        "expect privilege object to be {GRANT_OBJECT_SYNTAX}, actual: {actual}"
+                    ),
+                }
+                .fail();
+            }
+        };
+        Ok(object)
+    }
+
+    /// Parses a user or role name, which can be an identifier or a quoted string.
+    fn parse_principal(&mut self, expected: &str) -> Result<Ident> {
+        match self.parser.next_token() {
+            TokenWithLocation {
+                token: Token::Word(w),
+                ..
+            } => Ok(w.to_ident()),
+            TokenWithLocation {
+                token: Token::SingleQuotedString(s),
+                ..
+            } => Ok(Ident::with_quote('\'', s)),
+            unexpected => self.expected(expected, unexpected),
+        }
+    }
+}
+
+fn token_to_privilege(token: &Token) -> Option<Privilege> {
+    let Token::Word(w) = token else {
+        return None;
+    };
+    if w.quote_style.is_some() {
+        return None;
+    }
+    match w.keyword {
+        Keyword::READ | Keyword::SELECT => Some(Privilege::Read),
+        Keyword::WRITE | Keyword::INSERT => Some(Privilege::Write),
+        Keyword::ALL => Some(Privilege::All),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::GreptimeDbDialect;
+
+    fn parse(sql: &str) -> Statement {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        stmts.pop().unwrap()
+    }
+
+    #[test]
+    fn test_parse_create_user() {
+        let Statement::CreateUser(stmt) = parse("CREATE USER alice IDENTIFIED BY 'secret'") else {
+            unreachable!()
+        };
+        assert_eq!("alice", stmt.name.value);
+        assert_eq!("secret", stmt.password);
+        assert!(!stmt.if_not_exists);
+        assert!(!format!("{stmt:?}").contains("secret"));
+
+        let Statement::CreateUser(stmt) =
+            parse("CREATE USER IF NOT EXISTS 'bob' IDENTIFIED BY \"pwd\"")
+        else {
+            unreachable!()
+        };
+        assert_eq!("bob", stmt.name.value);
+        assert_eq!("pwd", stmt.password);
+        assert!(stmt.if_not_exists);
+
+        let result = ParserContext::create_with_dialect("CREATE USER alice", &GreptimeDbDialect {});
+        assert!(result.is_err());
+        let result = ParserContext::create_with_dialect(
+            "CREATE USER alice IDENTIFIED BY secret",
+            &GreptimeDbDialect {},
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_create_role() {
+        assert_eq!(
+            Statement::CreateRole(CreateRole {
+                name: Ident::new("reader"),
+                if_not_exists: true,
+            }),
+            parse("CREATE ROLE IF NOT EXISTS reader")
+        );
+    }
+
+    #[test]
+    fn test_parse_grant() {
+        assert_eq!(
+            Statement::Grant(Grant {
+                grantable: Grantable::Privileges {
+                    privileges: vec![Privilege::Read, Privilege::Write],
+                    object: GrantObject::All,
+                },
+                grantee: Ident::new("alice"),
+            }),
+            parse("GRANT SELECT, WRITE ON *.* TO alice")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                grantable: Grantable::Privileges {
+                    privileges: vec![Privilege::All],
+                    object: GrantObject::Catalog(Ident::new("greptime")),
+                },
+                grantee: Ident::new("alice"),
+            }),
+            parse("GRANT ALL PRIVILEGES ON greptime.*.* TO alice")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                grantable: Grantable::Privileges {
+                    privileges: vec![Privilege::Read],
+                    object: GrantObject::Schema(ObjectName(vec![
+                        Ident::new("greptime"),
+                        Ident::new("public")
+                    ])),
+                },
+                grantee: Ident::new("reader"),
+            }),
+            parse("GRANT READ ON greptime.public.* TO reader")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                grantable: Grantable::Privileges {
+                    privileges: vec![Privilege::Write],
+                    object: GrantObject::Table(ObjectName(vec![
+                        Ident::new("public"),
+                        Ident::new("foo")
+                    ])),
+                },
+                grantee: Ident::new("writer"),
+            }),
+            parse("GRANT INSERT ON public.foo TO writer")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                grantable: Grantable::Role(Ident::new("reader")),
+                grantee: Ident::new("alice"),
+            }),
+            parse("GRANT reader TO alice")
+        );
+    }
+
+    #[test]
+    fn test_parse_revoke() {
+        assert_eq!(
+            Statement::Revoke(Revoke {
+                grantable: Grantable::Privileges {
+                    privileges: vec![Privilege::Write],
+                    object: GrantObject::Schema(ObjectName(vec![Ident::new("public")])),
+                },
+                grantee: Ident::new("alice"),
+            }),
+            parse("REVOKE WRITE ON public.* FROM alice")
+        );
+        assert_eq!(
+            Statement::Revoke(Revoke {
+                grantable: Grantable::Role(Ident::new("reader")),
+                grantee: Ident::new("alice"),
+            }),
+            parse("REVOKE reader FROM alice")
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_grant() {
+        for sql in [
+            "GRANT READ ON * TO alice",
+            "GRANT READ ON *.foo TO alice",
+            "GRANT READ ON a.b.c.d TO alice",
+            "GRANT READ TO alice",
+            "GRANT READ ON *.* FROM alice",
+            "REVOKE READ ON *.* TO alice",
+        ] {
+            let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+            assert!(result.is_err(), "sql: {sql}, result: {result:?}");
+        }
+    }
+}
@@ -18,7 +18,9 @@ use sqlparser::tokenizer::Token;
 
 use crate::error::{self, InvalidDatabaseNameSnafu, InvalidTableNameSnafu, Result};
 use crate::parser::ParserContext;
-use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowKind, ShowTables};
+use crate::statements::show::{
+    ShowCreateTable, ShowCreateView, ShowDatabases, ShowKind, ShowMaterializedViews, ShowTables,
+};
 use crate::statements::statement::Statement;
 
 /// SHOW statement parser implementation
@@ -30,10 +32,24 @@ impl<'a> ParserContext<'a> {
             self.parse_show_databases()
         } else if self.matches_keyword(Keyword::TABLES) {
             let _ = self.parser.next_token();
-            self.parse_show_tables()
+            self.parse_show_tables(false)
+        } else if self.consume_token("FULL") {
+            if self.consume_token("TABLES") {
+                self.parse_show_tables(true)
+            } else {
+                self.unsupported(self.peek_token_as_string())
+            }
+        } else if self.consume_token("MATERIALIZED") {
+            if self.consume_token("VIEWS") {
+                self.parse_show_materialized_views()
+            } else {
+                self.unsupported(self.peek_token_as_string())
+            }
         } else if self.consume_token("CREATE") {
             if self.consume_token("TABLE") {
                 self.parse_show_create_table()
+            } else if self.consume_token("VIEW") {
+                self.parse_show_create_view()
             } else {
                 self.unsupported(self.peek_token_as_string())
             }
@@ -61,12 +77,59 @@ impl<'a> ParserContext<'a> {
         Ok(Statement::ShowCreateTable(ShowCreateTable { table_name }))
     }
 
-    fn parse_show_tables(&mut self) -> Result<Statement> {
+    /// Parse SHOW CREATE VIEW statement
+    fn parse_show_create_view(&mut self) -> Result<Statement> {
+        let view_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a view name",
+                    actual: self.peek_token_as_string(),
+                })?;
+        ensure!(
+            !view_name.0.is_empty(),
+            InvalidTableNameSnafu {
+                name: view_name.to_string(),
+            }
+        );
+        Ok(Statement::ShowCreateView(ShowCreateView { view_name }))
+    }
+
+    /// Parse SHOW MATERIALIZED VIEWS [IN | FROM database] statement
+    fn parse_show_materialized_views(&mut self) -> Result<Statement> {
+        let database =
+            if self.parser.parse_keyword(Keyword::IN) || self.parser.parse_keyword(Keyword::FROM) {
+                let db_name =
+                    self.parser
+                        .parse_object_name()
+                        .with_context(|_| error::UnexpectedSnafu {
+                            sql: self.sql,
+                            expected: "a database name",
+                            actual: self.peek_token_as_string(),
+                        })?;
+                ensure!(
+                    db_name.0.len() == 1,
+                    InvalidDatabaseNameSnafu {
+                        name: db_name.to_string(),
+                    }
+                );
+                Some(db_name.to_string())
+            } else {
+                None
+            };
+        Ok(Statement::ShowMaterializedViews(ShowMaterializedViews {
+            database,
+        }))
+    }
+
+    fn parse_show_tables(&mut self, full: bool) -> Result<Statement> {
         let database = match self.parser.peek_token().token {
             Token::EOF | Token::SemiColon => {
                 return Ok(Statement::ShowTables(ShowTables {
                     kind: ShowKind::All,
                     database: None,
+                    full,
                 }));
             }
 
@@ -126,7 +189,11 @@ impl<'a> ParserContext<'a> {
             _ => return self.unsupported(self.peek_token_as_string()),
         };
 
-        Ok(Statement::ShowTables(ShowTables { kind, database }))
+        Ok(Statement::ShowTables(ShowTables {
+            kind,
+            database,
+            full,
+        }))
     }
 
     /// Parses `SHOW DATABASES` statement.
@@ -234,6 +301,7 @@ mod tests {
             Statement::ShowTables(ShowTables {
                 kind: ShowKind::All,
                 database: None,
+                full: false,
             })
         );
     }
@@ -253,6 +321,7 @@ mod tests {
                     quote_style: None,
                 }),
                 database: None,
+                full: false,
             })
         );
 
@@ -269,6 +338,7 @@ mod tests {
                     quote_style: None,
                 }),
                 database: Some(_),
+                full: false,
             })
         );
     }
@@ -285,6 +355,7 @@ mod tests {
             Statement::ShowTables(ShowTables {
                 kind: ShowKind::Where(sqlparser::ast::Expr::Like { .. }),
                 database: None,
+                full: false,
             })
         );
 
@@ -298,7 +369,69 @@ mod tests {
             Statement::ShowTables(ShowTables {
                 kind: ShowKind::Where(sqlparser::ast::Expr::Like { .. }),
                 database: Some(_),
+                full: false,
             })
         );
     }
+
+    #[test]
+    pub fn test_show_full_tables() {
+        let sql = "SHOW FULL TABLES in test_db LIKE test_table";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        let stmts = result.unwrap();
+        assert_eq!(1, stmts.len());
+
+        assert_matches!(
+            &stmts[0],
+            Statement::ShowTables(ShowTables {
+                kind: ShowKind::Like(_),
+                database: Some(_),
+                full: true,
+            })
+        );
+
+        let sql = "SHOW FULL DATABASES";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
+
+    #[test]
+    pub fn test_show_create_view() {
+        let sql = "SHOW CREATE VIEW my_schema.busy_hosts";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {});
+        let stmts = result.unwrap();
+        assert_eq!(1, stmts.len());
+
+        match &stmts[0] {
+            Statement::ShowCreateView(show) => {
+                assert_eq!(show.view_name.to_string(), "my_schema.busy_hosts");
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "SHOW CREATE VIEW";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
+
+    #[test]
+    pub fn test_show_materialized_views() {
+        let sql = "SHOW MATERIALIZED VIEWS";
+        let stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        assert_matches!(
+            &stmts[0],
+            Statement::ShowMaterializedViews(ShowMaterializedViews { database: None })
+        );
+
+        let sql = "SHOW MATERIALIZED VIEWS IN test_db";
+        let stmts = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(
+            stmts[0],
+            Statement::ShowMaterializedViews(ShowMaterializedViews {
+                database: Some("test_db".to_string())
+            })
+        );
+
+        let sql = "SHOW MATERIALIZED TABLES";
+        assert!(ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).is_err());
+    }
 }
@@ -20,6 +20,7 @@ pub mod describe;
 pub mod drop;
 pub mod explain;
 pub mod insert;
+pub mod privilege;
 pub mod query;
 pub mod show;
 pub mod statement;
@@ -242,31 +243,36 @@ fn parse_column_default_constraint(
     data_type: &ConcreteDataType,
     opts: &[ColumnOptionDef],
 ) -> Result<Option<ColumnDefaultConstraint>> {
-    if let Some(opt) = opts
-        .iter()
-        .find(|o| matches!(o.option, ColumnOption::Default(_)))
-    {
-        let default_constraint = match &opt.option {
-            ColumnOption::Default(Expr::Value(v)) => {
-                ColumnDefaultConstraint::Value(sql_value_to_value(column_name, data_type, v)?)
-            }
-            ColumnOption::Default(Expr::Function(func)) => {
-                // Always use lowercase for function expression
-                ColumnDefaultConstraint::Function(format!("{func}").to_lowercase())
-            }
-            ColumnOption::Default(expr) => {
-                return UnsupportedDefaultValueSnafu {
-                    column_name,
-                    expr: expr.clone(),
-                }
-                .fail();
-            }
-            _ => unreachable!(),
-        };
+    opts.iter()
+        .find_map(|o| match &o.option {
+            ColumnOption::Default(expr) => Some(expr),
+            _ => None,
+        })
+        .map(|expr| sql_default_to_constraint(column_name, data_type, expr))
+        .transpose()
+}
 
-        Ok(Some(default_constraint))
-    } else {
-        Ok(None)
+/// Converts the default value `expr` of the column `column_name` to a [ColumnDefaultConstraint].
+pub fn sql_default_to_constraint(
+    column_name: &str,
+    data_type: &ConcreteDataType,
+    expr: &Expr,
+) -> Result<ColumnDefaultConstraint> {
+    match expr {
+        Expr::Value(v) => Ok(ColumnDefaultConstraint::Value(sql_value_to_value(
+            column_name,
+            data_type,
+            v,
+        )?)),
+        // Always use lowercase for function expression
+        Expr::Function(func) => Ok(ColumnDefaultConstraint::Function(
+            format!("{func}").to_lowercase(),
+        )),
+        expr => UnsupportedDefaultValueSnafu {
+            column_name,
+            expr: expr.clone(),
+        }
+        .fail(),
     }
 }
 
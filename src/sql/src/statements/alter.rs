@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use common_query::AddColumnLocation;
-use sqlparser::ast::{ColumnDef, Ident, ObjectName, TableConstraint};
+use sqlparser::ast::{ColumnDef, Expr, Ident, ObjectName, SqlOption, TableConstraint};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AlterTable {
@@ -49,6 +49,12 @@ pub enum AlterTableOperation {
     },
     /// `DROP COLUMN <name>`
     DropColumn { name: Ident },
+    /// `MODIFY [ COLUMN ] <column_def>`
+    ModifyColumn { column_def: ColumnDef },
+    /// `MODIFY [ COLUMN ] <name> SET DEFAULT <expr>`
+    SetColumnDefault { name: Ident, default: Expr },
     /// `RENAME <new_table_name>`
     RenameTable { new_table_name: String },
+    /// `SET <option_name> = <value> [, ...]`
+    SetTableOptions { options: Vec<SqlOption> },
 }
@@ -19,6 +19,7 @@ use common_catalog::consts::IMMUTABLE_FILE_ENGINE;
 use itertools::Itertools;
 
 use crate::ast::{ColumnDef, Ident, ObjectName, SqlOption, TableConstraint, Value as SqlValue};
+use crate::statements::query::Query;
 
 const LINE_SEP: &str = ",\n";
 const COMMA_SEP: &str = ", ";
@@ -128,6 +129,9 @@ impl CreateTable {
 pub struct Partitions {
     pub column_list: Vec<Ident>,
     pub entries: Vec<PartitionEntry>,
+    /// Number of partitions of `PARTITION BY HASH (column) PARTITIONS n`. The `entries` are
+    /// always empty for hash partitions.
+    pub hash_partitions: Option<u32>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -149,7 +153,14 @@ impl Display for PartitionEntry {
 
 impl Display for Partitions {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if !self.column_list.is_empty() {
+        if let Some(num_partitions) = self.hash_partitions {
+            write!(
+                f,
+                "PARTITION BY HASH ({}) PARTITIONS {}",
+                format_list_comma!(self.column_list),
+                num_partitions,
+            )
+        } else if !self.column_list.is_empty() {
             write!(
                 f,
                 r#"PARTITION BY RANGE COLUMNS ({}) (
@@ -190,6 +201,46 @@ impl Display for CreateTable {
     }
 }
 
+/// `CREATE MATERIALIZED VIEW`, a table kept up to date with the result of a query.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CreateMaterializedView {
+    pub name: ObjectName,
+    /// Create if not exists
+    pub if_not_exists: bool,
+    pub query: Box<Query>,
+}
+
+impl Display for CreateMaterializedView {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE MATERIALIZED VIEW ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{} AS {}", self.name, self.query)
+    }
+}
+
+/// `CREATE VIEW`, a named query expanded in place wherever the view is referenced.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CreateView {
+    pub name: ObjectName,
+    /// Create if not exists
+    pub if_not_exists: bool,
+    pub query: Box<Query>,
+    /// Output columns of the query, filled in after the query is planned.
+    pub columns: Vec<ColumnDef>,
+}
+
+impl Display for CreateView {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE VIEW ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{} AS {}", self.name, self.query)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CreateDatabase {
     pub name: ObjectName,
@@ -271,6 +322,47 @@ WITH(
         }
     }
 
+    #[test]
+    fn test_display_hash_partitions() {
+        let sql = r"create table if not exists demo(
+                             host string,
+                             ts bigint,
+                             cpu double default 0,
+                             TIME INDEX (ts),
+                             PRIMARY KEY(host)
+                       )
+                       PARTITION BY HASH (host) PARTITIONS 8
+                       engine=mito;
+         ";
+        let result = ParserContext::create_with_dialect(sql, &GreptimeDbDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        match &result[0] {
+            Statement::CreateTable(c) => {
+                let new_sql = format!("\n{}", c);
+                assert_eq!(
+                    r#"
+CREATE TABLE IF NOT EXISTS demo (
+  host STRING,
+  ts BIGINT,
+  cpu DOUBLE DEFAULT 0,
+  TIME INDEX (ts),
+  PRIMARY KEY (host)
+)
+PARTITION BY HASH (host) PARTITIONS 8
+ENGINE=mito
+"#,
+                    &new_sql
+                );
+
+                let new_result =
+                    ParserContext::create_with_dialect(&new_sql, &GreptimeDbDialect {}).unwrap();
+                assert_eq!(result, new_result);
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn test_display_empty_partition_column() {
         let sql = r"create table if not exists demo(
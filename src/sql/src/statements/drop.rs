@@ -30,3 +30,28 @@ impl DropTable {
         &self.table_name
     }
 }
+
+/// DROP VIEW statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropView {
+    view_name: ObjectName,
+    drop_if_exists: bool,
+}
+
+impl DropView {
+    /// Creates a statement for `DROP VIEW`
+    pub fn new(view_name: ObjectName, drop_if_exists: bool) -> Self {
+        Self {
+            view_name,
+            drop_if_exists,
+        }
+    }
+
+    pub fn view_name(&self) -> &ObjectName {
+        &self.view_name
+    }
+
+    pub fn drop_if_exists(&self) -> bool {
+        self.drop_if_exists
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Debug, Formatter};
+
+use sqlparser::ast::{Ident, ObjectName};
+
+/// A privilege in `GRANT` and `REVOKE` statements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Privilege {
+    /// `READ` or `SELECT`
+    Read,
+    /// `WRITE` or `INSERT`
+    Write,
+    /// `ALL [PRIVILEGES]`
+    All,
+}
+
+/// The object that privileges are granted on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrantObject {
+    /// `*.*`
+    All,
+    /// `catalog.*.*`
+    Catalog(Ident),
+    /// `schema.*` or `catalog.schema.*`
+    Schema(ObjectName),
+    /// `table`, `schema.table` or `catalog.schema.table`
+    Table(ObjectName),
+}
+
+/// What to grant or revoke.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grantable {
+    Privileges {
+        privileges: Vec<Privilege>,
+        object: GrantObject,
+    },
+    Role(Ident),
+}
+
+/// CREATE USER statement.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CreateUser {
+    pub name: Ident,
+    pub password: String,
+    pub if_not_exists: bool,
+}
+
+// Hides the password from logs and error messages.
+impl Debug for CreateUser {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CreateUser")
+            .field("name", &self.name)
+            .field("password", &"******")
+            .field("if_not_exists", &self.if_not_exists)
+            .finish()
+    }
+}
+
+/// CREATE ROLE statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateRole {
+    pub name: Ident,
+    pub if_not_exists: bool,
+}
+
+/// GRANT statement, grants privileges or a role to a user or role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grant {
+    pub grantable: Grantable,
+    pub grantee: Ident,
+}
+
+/// REVOKE statement, revokes privileges or a role from a user or role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revoke {
+    pub grantable: Grantable,
+    pub grantee: Ident,
+}
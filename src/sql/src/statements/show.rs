@@ -52,6 +52,8 @@ impl ShowDatabases {
 pub struct ShowTables {
     pub kind: ShowKind,
     pub database: Option<String>,
+    /// `SHOW FULL TABLES` also outputs the type of each table.
+    pub full: bool,
 }
 
 /// SQL structure for `SHOW CREATE TABLE`.
@@ -60,6 +62,18 @@ pub struct ShowCreateTable {
     pub table_name: ObjectName,
 }
 
+/// SQL structure for `SHOW CREATE VIEW`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShowCreateView {
+    pub view_name: ObjectName,
+}
+
+/// SQL structure for `SHOW MATERIALIZED VIEWS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShowMaterializedViews {
+    pub database: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
@@ -17,14 +17,19 @@ use sqlparser::ast::Statement as SpStatement;
 
 use crate::error::{ConvertToDfStatementSnafu, Error};
 use crate::statements::alter::AlterTable;
-use crate::statements::create::{CreateDatabase, CreateExternalTable, CreateTable};
+use crate::statements::create::{
+    CreateDatabase, CreateExternalTable, CreateMaterializedView, CreateTable, CreateView,
+};
 use crate::statements::delete::Delete;
 use crate::statements::describe::DescribeTable;
-use crate::statements::drop::DropTable;
+use crate::statements::drop::{DropTable, DropView};
 use crate::statements::explain::Explain;
 use crate::statements::insert::Insert;
+use crate::statements::privilege::{CreateRole, CreateUser, Grant, Revoke};
 use crate::statements::query::Query;
-use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowTables};
+use crate::statements::show::{
+    ShowCreateTable, ShowCreateView, ShowDatabases, ShowMaterializedViews, ShowTables,
+};
 use crate::statements::tql::Tql;
 use crate::statements::truncate::TruncateTable;
 
@@ -42,8 +47,14 @@ pub enum Statement {
     CreateTable(CreateTable),
     // CREATE EXTERNAL TABLE
     CreateExternalTable(CreateExternalTable),
+    // CREATE MATERIALIZED VIEW
+    CreateMaterializedView(CreateMaterializedView),
+    // CREATE VIEW
+    CreateView(CreateView),
     // DROP TABLE
     DropTable(DropTable),
+    // DROP VIEW
+    DropView(DropView),
     // CREATE DATABASE
     CreateDatabase(CreateDatabase),
     /// ALTER TABLE
@@ -54,6 +65,10 @@ pub enum Statement {
     ShowTables(ShowTables),
     // SHOW CREATE TABLE
     ShowCreateTable(ShowCreateTable),
+    // SHOW CREATE VIEW
+    ShowCreateView(ShowCreateView),
+    // SHOW MATERIALIZED VIEWS
+    ShowMaterializedViews(ShowMaterializedViews),
     // DESCRIBE TABLE
     DescribeTable(DescribeTable),
     // EXPLAIN QUERY
@@ -63,6 +78,14 @@ pub enum Statement {
     Tql(Tql),
     // TRUNCATE TABLE
     TruncateTable(TruncateTable),
+    // CREATE USER
+    CreateUser(CreateUser),
+    // CREATE ROLE
+    CreateRole(CreateRole),
+    // GRANT
+    Grant(Grant),
+    // REVOKE
+    Revoke(Revoke),
 }
 
 /// Comment hints from SQL.
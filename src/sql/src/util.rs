@@ -22,6 +22,7 @@ static SQL_SECRET_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
     vec![
         Regex::new(r#"(?i)access_key_id=["'](\w*)["'].*"#).unwrap(),
         Regex::new(r#"(?i)secret_access_key=["'](\w*)["'].*"#).unwrap(),
+        Regex::new(r#"(?i)identified\s+by\s+["']([^"']+)["'].*"#).unwrap(),
     ]
 });
 
@@ -71,5 +72,9 @@ mod test {
             ),
             r#"COPY 'my_table' FROM '/test.orc' WITH (FORMAT = 'orc') CONNECTION(ENDPOINT = 's3.storage.site', REGION = 'hz', ACCESS_KEY_ID='******', SECRET_ACCESS_KEY="******");"#
         );
+        assert_eq!(
+            redact_sql_secrets("CREATE USER alice IDENTIFIED BY 'p@ss word';"),
+            "CREATE USER alice IDENTIFIED BY '******';"
+        );
     }
 }
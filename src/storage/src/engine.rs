@@ -540,8 +540,8 @@ mod tests {
     use log_store::test_util::log_store_util;
     use object_store::services::Fs;
     use store_api::storage::{
-        ChunkReader, FlushContext, ReadContext, Region, ScanRequest, Snapshot, WriteContext,
-        WriteRequest,
+        AlterOptions, ChunkReader, FlushContext, ReadContext, Region, ScanRequest, Snapshot,
+        TwcsOptions, WriteContext, WriteRequest,
     };
 
     use super::*;
@@ -639,6 +639,42 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_alter_region_options() {
+        let dir = create_temp_dir("test_alter_options");
+        let log_file_dir = create_temp_dir("test_alter_options_wal");
+
+        let region_name = "region-0";
+        let region_id = 123456;
+        let mut config = EngineConfig::default();
+        config.global_ttl = Some(Duration::from_secs(3600));
+
+        let (_engine, region) =
+            create_engine_and_region(&dir, &log_file_dir, region_name, region_id, config.clone())
+                .await;
+        assert_eq!(Some(Duration::from_secs(3600)), region.ttl().await);
+
+        let options = AlterOptions {
+            write_buffer_size: Some(1024),
+            ttl: Some(Duration::from_secs(60)),
+            compaction_strategy: CompactionStrategy::Twcs(TwcsOptions::default()),
+        };
+        region.alter_options(&options).await.unwrap();
+        assert_eq!(1024, region.write_buffer_size().await);
+        assert_eq!(Some(Duration::from_secs(60)), region.ttl().await);
+
+        // Falls back to the defaults of the engine.
+        region
+            .alter_options(&AlterOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            config.region_write_buffer_size.as_bytes() as usize,
+            region.write_buffer_size().await
+        );
+        assert_eq!(Some(Duration::from_secs(3600)), region.ttl().await);
+    }
+
     #[tokio::test]
     async fn test_drop_region() {
         common_telemetry::init_default_ut_logging();
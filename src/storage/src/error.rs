@@ -280,6 +280,9 @@ pub enum Error {
     #[snafu(display("Try to write the closed region"))]
     ClosedRegion { location: Location },
 
+    #[snafu(display("Try to write the read-only region"))]
+    ReadOnlyRegion { location: Location },
+
     #[snafu(display("Invalid projection, source: {}", source))]
     InvalidProjection {
         location: Location,
@@ -361,6 +364,21 @@ pub enum Error {
     #[snafu(display("Failed to read column {}, no proper default value for it", column))]
     NoDefaultToRead { column: String, location: Location },
 
+    #[snafu(display(
+        "Failed to cast column {} from {:?} to {:?}, source: {}",
+        column,
+        from,
+        to,
+        source
+    ))]
+    CastColumn {
+        column: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+        location: Location,
+        source: datatypes::error::Error,
+    },
+
     #[snafu(display(
         "Failed to convert arrow chunk to batch, name: {}, source: {}",
         name,
@@ -589,6 +607,7 @@ impl ErrorExt for Error {
             | CompatRead { .. }
             | CreateDefaultToRead { .. }
             | NoDefaultToRead { .. }
+            | CastColumn { .. }
             | NewRecordBatch { .. }
             | BatchCorrupted { .. }
             | DecodeArrow { .. }
@@ -629,6 +648,8 @@ impl ErrorExt for Error {
             }
             DeleteSst { .. } => StatusCode::StorageUnavailable,
 
+            ReadOnlyRegion { .. } => StatusCode::StorageUnavailable,
+
             StartManifestGcTask { .. }
             | StopManifestGcTask { .. }
             | IllegalSchedulerState { .. }
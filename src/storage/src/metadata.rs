@@ -84,6 +84,9 @@ pub enum Error {
     #[snafu(display("Failed to drop column {} as it is an internal column", name))]
     DropInternalColumn { name: String },
 
+    #[snafu(display("Failed to modify column {}, reason: {}", name, reason))]
+    ModifyColumn { name: String, reason: String },
+
     // End of variants for validating `AlterRequest`.
     #[snafu(display("Failed to convert to column schema, source: {}", source))]
     ToColumnSchema {
@@ -244,6 +247,11 @@ impl RegionMetadata {
                     self.validate_drop_column(name)?;
                 }
             }
+            AlterOperation::ModifyColumns { columns } => {
+                for col in columns {
+                    self.validate_modify_column(col)?;
+                }
+            }
         }
 
         Ok(())
@@ -309,6 +317,44 @@ impl RegionMetadata {
         Ok(())
     }
 
+    fn validate_modify_column(&self, desc: &ColumnDescriptor) -> Result<()> {
+        let name = &desc.name;
+        // Only field columns are allowed to modify.
+        let Some(column) = self
+            .columns
+            .iter_field_columns()
+            .find(|column| column.desc.name == *name)
+        else {
+            return ModifyColumnSnafu {
+                name,
+                reason: "no such field column",
+            }
+            .fail();
+        };
+        let old_desc = &column.desc;
+
+        ensure!(
+            old_desc.data_type == desc.data_type
+                || old_desc.data_type.can_widen_to(&desc.data_type),
+            ModifyColumnSnafu {
+                name,
+                reason: format!(
+                    "can't change data type from {:?} to {:?}",
+                    old_desc.data_type, desc.data_type
+                ),
+            }
+        );
+        ensure!(
+            !old_desc.is_nullable() || desc.is_nullable(),
+            ModifyColumnSnafu {
+                name,
+                reason: "can't change a nullable column to NOT NULL",
+            }
+        );
+
+        Ok(())
+    }
+
     fn to_descriptor(&self) -> RegionDescriptor {
         let row_key = self.columns.to_row_key_descriptor();
         let mut builder = RegionDescriptorBuilder::default()
@@ -1292,6 +1338,79 @@ mod tests {
         metadata.validate_alter(&req).unwrap();
     }
 
+    #[test]
+    fn test_validate_modify_column_request() {
+        let builder = RegionDescBuilder::new("region-alter")
+            .timestamp(("ts", LogicalTypeId::TimestampMillisecond, false))
+            .push_key_column(("k0", LogicalTypeId::Int32, false))
+            .push_field_column(("v0", LogicalTypeId::Float32, false));
+        let last_column_id = builder.last_column_id();
+        let metadata: RegionMetadata = builder.build().try_into().unwrap();
+
+        let modify = |id, name: &str, data_type, is_nullable| AlterRequest {
+            operation: AlterOperation::ModifyColumns {
+                columns: vec![ColumnDescriptorBuilder::new(id, name, data_type)
+                    .is_nullable(is_nullable)
+                    .build()
+                    .unwrap()],
+            },
+            version: 0,
+        };
+
+        // Modify key column.
+        let req = modify(
+            last_column_id - 1,
+            "k0",
+            ConcreteDataType::int64_datatype(),
+            false,
+        );
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyColumn { .. }
+        ));
+
+        // Narrow the data type.
+        let req = modify(
+            last_column_id,
+            "v0",
+            ConcreteDataType::int32_datatype(),
+            false,
+        );
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyColumn { .. }
+        ));
+
+        // Valid request
+        let req = modify(
+            last_column_id,
+            "v0",
+            ConcreteDataType::float64_datatype(),
+            true,
+        );
+        metadata.validate_alter(&req).unwrap();
+
+        let metadata = metadata.alter(&req).unwrap();
+        assert_eq!(1, metadata.version());
+        let column = metadata.schema().user_schema().column_schema_by_name("v0");
+        let column = column.unwrap();
+        assert_eq!(ConcreteDataType::float64_datatype(), column.data_type);
+        assert!(column.is_nullable());
+
+        // Nullable column can't be changed to NOT NULL.
+        let mut req = modify(
+            last_column_id,
+            "v0",
+            ConcreteDataType::float64_datatype(),
+            false,
+        );
+        req.version = 1;
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyColumn { .. }
+        ));
+    }
+
     #[test]
     fn test_column_metadata_conversion() {
         let desc = ColumnDescriptorBuilder::new(123, "test", ConcreteDataType::int32_datatype())
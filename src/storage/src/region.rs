@@ -32,13 +32,12 @@ use store_api::manifest::{
     self, Manifest, ManifestLogStorage, ManifestVersion, MetaActionIterator,
 };
 use store_api::storage::{
-    AlterRequest, CloseContext, CompactContext, CompactionStrategy, FlushContext, FlushReason,
-    OpenOptions, ReadContext, Region, RegionId, SequenceNumber, WriteContext, WriteResponse,
+    AlterOptions, AlterRequest, CloseContext, CompactContext, CompactionStrategy, FlushContext,
+    FlushReason, OpenOptions, ReadContext, Region, RegionId, SequenceNumber, WriteContext,
+    WriteResponse,
 };
 
-use crate::compaction::{
-    compaction_strategy_to_picker, CompactionPickerRef, CompactionSchedulerRef,
-};
+use crate::compaction::{compaction_strategy_to_picker, CompactionSchedulerRef};
 use crate::config::EngineConfig;
 use crate::error::{self, Error, Result};
 use crate::file_purger::FilePurgerRef;
@@ -132,6 +131,10 @@ impl<S: LogStore> Region for RegionImpl<S> {
         self.inner.alter(request).await
     }
 
+    async fn alter_options(&self, options: &AlterOptions) -> Result<()> {
+        self.inner.alter_options(options).await
+    }
+
     async fn drop_region(&self) -> Result<()> {
         decrement_gauge!(crate::metrics::REGION_COUNT, 1.0);
         self.inner.drop_region().await
@@ -158,6 +161,17 @@ impl<S: LogStore> Region for RegionImpl<S> {
     async fn truncate(&self) -> Result<()> {
         self.inner.truncate().await
     }
+
+    async fn set_writable(&self, writable: bool) {
+        logging::info!(
+            "Set region {}, name: {} writable: {}",
+            self.inner.shared.id,
+            self.inner.shared.name,
+            writable
+        );
+
+        self.inner.writer.set_writable(writable).await
+    }
 }
 
 /// Storage related config for region.
@@ -245,13 +259,12 @@ impl<S: LogStore> RegionImpl<S> {
                 store_config.ttl,
                 store_config.write_buffer_size,
                 store_config.compaction_scheduler.clone(),
-                compaction_picker.clone(),
+                compaction_picker,
             )),
             wal,
             flush_strategy: store_config.flush_strategy,
             flush_scheduler: store_config.flush_scheduler,
             compaction_scheduler: store_config.compaction_scheduler,
-            compaction_picker,
             sst_layer: store_config.sst_layer,
             manifest: store_config.manifest,
         });
@@ -349,7 +362,7 @@ impl<S: LogStore> RegionImpl<S> {
             wal: &wal,
             writer: &writer,
             manifest: &store_config.manifest,
-            compaction_picker: compaction_picker.clone(),
+            compaction_picker,
         };
         // Replay all unflushed data.
         writer
@@ -363,7 +376,6 @@ impl<S: LogStore> RegionImpl<S> {
             flush_strategy: store_config.flush_strategy,
             flush_scheduler: store_config.flush_scheduler,
             compaction_scheduler: store_config.compaction_scheduler,
-            compaction_picker,
             sst_layer: store_config.sst_layer,
             manifest: store_config.manifest,
         });
@@ -607,7 +619,7 @@ impl<S: LogStore> RegionImpl<S> {
             wal: &inner.wal,
             writer: &inner.writer,
             manifest: &inner.manifest,
-            compaction_picker: inner.compaction_picker.clone(),
+            compaction_picker: inner.writer.compaction_picker(),
         };
 
         inner.writer.replay(recovered_metadata, writer_ctx).await
@@ -616,6 +628,10 @@ impl<S: LogStore> RegionImpl<S> {
     pub(crate) async fn write_buffer_size(&self) -> usize {
         self.inner.writer.write_buffer_size().await
     }
+
+    pub(crate) async fn ttl(&self) -> Option<Duration> {
+        self.inner.writer.ttl().await
+    }
 }
 
 /// Shared data of region.
@@ -664,7 +680,6 @@ struct RegionInner<S: LogStore> {
     flush_strategy: FlushStrategyRef,
     flush_scheduler: FlushSchedulerRef<S>,
     compaction_scheduler: CompactionSchedulerRef<S>,
-    compaction_picker: CompactionPickerRef<S>,
     sst_layer: AccessLayerRef,
     manifest: RegionManifest,
 }
@@ -708,7 +723,7 @@ impl<S: LogStore> RegionInner<S> {
             wal: &self.wal,
             writer: &self.writer,
             manifest: &self.manifest,
-            compaction_picker: self.compaction_picker.clone(),
+            compaction_picker: self.writer.compaction_picker(),
         };
         // The writer would also try to compat the schema of write batch if it finds out the
         // schema version of request is less than current schema version.
@@ -732,6 +747,17 @@ impl<S: LogStore> RegionInner<S> {
         self.writer.alter(alter_ctx, request).await
     }
 
+    async fn alter_options(&self, options: &AlterOptions) -> Result<()> {
+        logging::info!(
+            "Alter options of region {}, name: {}, options: {:?}",
+            self.shared.id,
+            self.shared.name,
+            options
+        );
+
+        self.writer.alter_options(options).await
+    }
+
     async fn close(&self, ctx: &CloseContext) -> Result<()> {
         self.writer.close().await?;
         if ctx.flush {
@@ -770,7 +796,7 @@ impl<S: LogStore> RegionInner<S> {
             wal: &self.wal,
             writer: &self.writer,
             manifest: &self.manifest,
-            compaction_picker: self.compaction_picker.clone(),
+            compaction_picker: self.writer.compaction_picker(),
         };
         self.writer.flush(writer_ctx, ctx).await
     }
@@ -617,7 +617,7 @@ impl WindowedReaderTester {
 
             assert!(self
                 .region
-                .write(&WriteContext {}, write_batch)
+                .write(&WriteContext::default(), write_batch)
                 .await
                 .is_ok());
 
@@ -19,7 +19,8 @@ use std::sync::Arc;
 use common_test_util::temp_dir::create_temp_dir;
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use store_api::storage::{
-    AlterOperation, AlterRequest, CloseContext, Region, RegionMeta, WriteResponse,
+    AlterOperation, AlterRequest, CloseContext, Region, RegionMeta, WriteContext, WriteRequest,
+    WriteResponse,
 };
 
 use crate::config::EngineConfig;
@@ -166,3 +167,37 @@ async fn test_close_wait_flush_done() {
 
     assert!(!has_parquet_file(&sst_dir));
 }
+
+#[tokio::test]
+async fn test_set_region_read_only() {
+    common_telemetry::init_default_ut_logging();
+    let dir = create_temp_dir("set-read-only");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let tester = CloseTester::new(store_dir, flush_switch).await;
+
+    let data = [(1000, Some(100))];
+
+    tester.base().region.set_writable(false).await;
+    assert_eq!(
+        tester.try_put(&data).await.unwrap_err().to_string(),
+        "Try to write the read-only region"
+    );
+
+    // Internal writes still go to the read-only region.
+    let mut batch = tests::new_write_batch_for_test(false);
+    batch
+        .put(tests::new_put_data(&[(
+            2000.into(),
+            Some("200".to_string()),
+        )]))
+        .unwrap();
+    let ctx = WriteContext {
+        ignore_read_only: true,
+    };
+    assert!(tester.base().region.write(&ctx, batch).await.is_ok());
+
+    tester.base().region.set_writable(true).await;
+    assert!(tester.try_put(&data).await.is_ok());
+}
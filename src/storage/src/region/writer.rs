@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use common_base::readable_size::ReadableSize;
@@ -23,11 +23,15 @@ use snafu::{ensure, ResultExt};
 use store_api::logstore::LogStore;
 use store_api::manifest::{Manifest, ManifestLogStorage, ManifestVersion, MetaAction};
 use store_api::storage::{
-    AlterRequest, FlushContext, FlushReason, SequenceNumber, WriteContext, WriteResponse,
+    AlterOptions, AlterRequest, FlushContext, FlushReason, SequenceNumber, WriteContext,
+    WriteResponse,
 };
 use tokio::sync::{oneshot, Mutex};
 
-use crate::compaction::{CompactionPickerRef, CompactionRequestImpl, CompactionSchedulerRef};
+use crate::compaction::{
+    compaction_strategy_to_picker, CompactionPickerRef, CompactionRequestImpl,
+    CompactionSchedulerRef,
+};
 use crate::config::EngineConfig;
 use crate::error::{self, Result};
 use crate::flush::{
@@ -67,7 +71,8 @@ pub struct RegionWriter<S: LogStore> {
     version_mutex: Mutex<()>,
 
     compaction_scheduler: CompactionSchedulerRef<S>,
-    compaction_picker: CompactionPickerRef<S>,
+    /// Picker of compaction, which could be replaced by altering options of the region.
+    compaction_picker: RwLock<CompactionPickerRef<S>>,
 }
 
 impl<S> RegionWriter<S>
@@ -91,10 +96,15 @@ where
             )),
             version_mutex: Mutex::new(()),
             compaction_scheduler,
-            compaction_picker,
+            compaction_picker: RwLock::new(compaction_picker),
         }
     }
 
+    /// Returns the current compaction picker of the region.
+    pub(crate) fn compaction_picker(&self) -> CompactionPickerRef<S> {
+        self.compaction_picker.read().unwrap().clone()
+    }
+
     /// Write to region in the write lock.
     pub async fn write(
         &self,
@@ -105,12 +115,25 @@ where
         let mut inner = self.inner.lock().await;
 
         ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+        ensure!(
+            inner.writable || ctx.ignore_read_only,
+            error::ReadOnlyRegionSnafu
+        );
 
         inner
             .write(&self.version_mutex, ctx, request, writer_ctx)
             .await
     }
 
+    /// Sets whether the region accepts writes.
+    ///
+    /// Acquires the write lock, so no write is in progress once a region is set to
+    /// read-only.
+    pub async fn set_writable(&self, writable: bool) {
+        let mut inner = self.inner.lock().await;
+        inner.writable = writable;
+    }
+
     /// Replay data to memtables.
     pub async fn replay(
         &self,
@@ -242,6 +265,28 @@ where
             .await
     }
 
+    /// Alters options of the region.
+    ///
+    /// The new options take effect on the next flush and compaction. They are not persisted
+    /// to the manifest of the region, the caller should pass them to the region again when
+    /// the region is reopened.
+    pub async fn alter_options(&self, options: &AlterOptions) -> Result<()> {
+        // Acquires the write lock so that options don't change during a write.
+        let mut inner = self.inner.lock().await;
+
+        ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+        // Falls back to the defaults of the engine, like opening the region does.
+        inner.ttl = options.ttl.or(inner.engine_config.global_ttl);
+        inner.write_buffer_size = options
+            .write_buffer_size
+            .unwrap_or(inner.engine_config.region_write_buffer_size.as_bytes() as usize);
+        *self.compaction_picker.write().unwrap() =
+            compaction_strategy_to_picker(&options.compaction_strategy);
+
+        Ok(())
+    }
+
     /// Allocate a sequence and persist the manifest version using that sequence to the wal.
     ///
     /// This method should be protected by the `version_mutex`.
@@ -380,7 +425,7 @@ where
         inner
             .manual_compact(
                 request,
-                self.compaction_picker.clone(),
+                self.compaction_picker(),
                 self.compaction_scheduler.clone(),
                 sst_write_buffer_size,
             )
@@ -457,6 +502,10 @@ where
     pub(crate) async fn write_buffer_size(&self) -> usize {
         self.inner.lock().await.write_buffer_size
     }
+
+    pub(crate) async fn ttl(&self) -> Option<Duration> {
+        self.inner.lock().await.ttl
+    }
 }
 
 /// Structs needed by triggering a compaction.
@@ -540,6 +589,9 @@ struct WriterInner {
     ///
     /// It should protected by upper mutex
     closed: bool,
+    /// `WriterInner` rejects writing if the region is not writable, e.g. the region is
+    /// being migrated to another node. Unlike `closed`, the region can still be flushed.
+    writable: bool,
     engine_config: Arc<EngineConfig>,
     ttl: Option<Duration>,
     /// Size in bytes to freeze the mutable memtable.
@@ -558,6 +610,7 @@ impl WriterInner {
             flush_handle: None,
             engine_config,
             closed: false,
+            writable: true,
             ttl,
             write_buffer_size,
         }
@@ -71,7 +71,19 @@ impl CompatWrite for Mutation {
         let mut columns = Vec::with_capacity(dest_schema.num_columns());
         for column_schema in dest_schema.column_schemas() {
             if let Some(vector) = self.record_batch.column_by_name(&column_schema.name) {
-                columns.push(vector.clone());
+                if vector.data_type() == column_schema.data_type {
+                    columns.push(vector.clone());
+                } else {
+                    // The column has been modified to a wider type.
+                    let vector = vector.cast(&column_schema.data_type).with_context(|_| {
+                        error::CastColumnSnafu {
+                            column: &column_schema.name,
+                            from: vector.data_type(),
+                            to: column_schema.data_type.clone(),
+                        }
+                    })?;
+                    columns.push(vector);
+                }
             } else {
                 // We need to fill the column by null or its default value.
                 let vector = write_batch::new_column_with_default_value(column_schema, num_rows)?;
@@ -104,7 +116,7 @@ mod tests {
 
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::{ColumnDefaultConstraint, SchemaBuilder};
-    use datatypes::vectors::{Int32Vector, TimestampMillisecondVector, VectorRef};
+    use datatypes::vectors::{Int32Vector, Int64Vector, TimestampMillisecondVector, VectorRef};
     use store_api::storage::WriteRequest;
 
     use super::*;
@@ -186,6 +198,32 @@ mod tests {
         let _ = mutation.record_batch.column_by_name("v0").unwrap();
     }
 
+    #[test]
+    fn test_mutation_compat_write_modified_column() {
+        let mut put_data = new_put_data();
+        let v0 = Arc::new(Int32Vector::from_slice([4, 5, 6])) as VectorRef;
+        let _ = put_data.insert("v0".to_string(), v0);
+        let schema_old = new_test_schema(Some(None));
+        // Modify v0 from int32 to int64.
+        let mut column_schemas = schema_old.column_schemas().to_vec();
+        column_schemas[2] = ColumnSchema::new("v0", ConcreteDataType::int64_datatype(), true);
+        let schema = Arc::new(
+            SchemaBuilder::try_from(column_schemas)
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+        let mut batch = WriteBatch::new(schema_old, TEST_ROW_KEY_END);
+        batch.put(put_data).unwrap();
+
+        let mutation = &mut batch.payload.mutations[0];
+        mutation.compat_write(&schema).unwrap();
+
+        let v0 = mutation.record_batch.column_by_name("v0").unwrap();
+        let expect = Arc::new(Int64Vector::from_slice([4, 5, 6])) as VectorRef;
+        assert_eq!(&expect, v0);
+    }
+
     #[test]
     fn test_write_batch_compat_to_old() {
         let schema_old = new_test_schema(None);
@@ -33,8 +33,8 @@ pub use datatypes::schema::{
 pub use self::chunk::{Chunk, ChunkReader};
 pub use self::descriptors::*;
 pub use self::engine::{
-    CloseOptions, CompactionStrategy, CreateOptions, EngineContext, OpenOptions, StorageEngine,
-    TwcsOptions,
+    AlterOptions, CloseOptions, CompactionStrategy, CreateOptions, EngineContext, OpenOptions,
+    StorageEngine, TwcsOptions,
 };
 pub use self::metadata::RegionMeta;
 pub use self::region::{
@@ -117,6 +117,17 @@ pub struct OpenOptions {
     pub compaction_strategy: CompactionStrategy,
 }
 
+/// Options of a region that can be altered after the region is opened.
+#[derive(Debug, Clone, Default)]
+pub struct AlterOptions {
+    /// Region memtable max size in bytes
+    pub write_buffer_size: Option<usize>,
+    /// Region SST files TTL
+    pub ttl: Option<Duration>,
+    /// Compaction strategy
+    pub compaction_strategy: CompactionStrategy,
+}
+
 /// Options to close a region.
 #[derive(Debug, Clone, Default)]
 pub struct CloseOptions {
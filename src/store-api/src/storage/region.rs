@@ -35,7 +35,7 @@
 use async_trait::async_trait;
 use common_error::ext::ErrorExt;
 
-use crate::storage::engine::OpenOptions;
+use crate::storage::engine::{AlterOptions, OpenOptions};
 use crate::storage::metadata::RegionMeta;
 use crate::storage::requests::{AlterRequest, WriteRequest};
 use crate::storage::responses::WriteResponse;
@@ -73,6 +73,10 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
 
     async fn alter(&self, request: AlterRequest) -> Result<(), Self::Error>;
 
+    /// Alters options of the region. Options that are not set fall back to the
+    /// defaults of the engine.
+    async fn alter_options(&self, options: &AlterOptions) -> Result<(), Self::Error>;
+
     async fn drop_region(&self) -> Result<(), Self::Error>;
 
     fn disk_usage_bytes(&self) -> u64;
@@ -90,6 +94,10 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
     async fn compact(&self, ctx: &CompactContext) -> Result<(), Self::Error>;
 
     async fn truncate(&self) -> Result<(), Self::Error>;
+
+    /// Sets whether the region accepts writes. A region that isn't writable rejects writes,
+    /// but it can still be read and flushed.
+    async fn set_writable(&self, writable: bool);
 }
 
 #[derive(Default, Debug)]
@@ -100,7 +108,11 @@ pub struct RegionStat {
 
 /// Context for write operations.
 #[derive(Debug, Clone, Default)]
-pub struct WriteContext {}
+pub struct WriteContext {
+    /// Writes even if the region is read-only. Only internal writes that keep the data of the
+    /// region consistent set it, e.g. moving rows between regions of a table.
+    pub ignore_read_only: bool,
+}
 
 impl From<&OpenOptions> for WriteContext {
     fn from(_opts: &OpenOptions) -> WriteContext {
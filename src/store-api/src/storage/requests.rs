@@ -84,6 +84,13 @@ pub enum AlterOperation {
         /// Name of columns to drop.
         names: Vec<String>,
     },
+    /// Modify data type or nullability of value columns in the region.
+    ModifyColumns {
+        /// New descriptors of columns to modify, columns are matched by their names.
+        ///
+        /// Ids in these descriptors are ignored, modified columns always keep their ids.
+        columns: Vec<ColumnDescriptor>,
+    },
 }
 
 impl AlterOperation {
@@ -96,6 +103,9 @@ impl AlterOperation {
             AlterOperation::DropColumns { names } => {
                 Self::apply_drop(names, descriptor);
             }
+            AlterOperation::ModifyColumns { columns } => {
+                Self::apply_modify(columns, descriptor);
+            }
         }
     }
 
@@ -127,6 +137,22 @@ impl AlterOperation {
             cf.columns.retain(|col| !name_set.contains(&col.name));
         }
     }
+
+    /// Replace value columns in the [RegionDescriptor] by `columns` with the same names.
+    ///
+    /// Non-value columns in `columns` would be ignored.
+    fn apply_modify(columns: &[ColumnDescriptor], descriptor: &mut RegionDescriptor) {
+        let cfs = std::iter::once(&mut descriptor.default_cf).chain(&mut descriptor.extra_cfs);
+        for cf in cfs {
+            for col in &mut cf.columns {
+                if let Some(new_col) = columns.iter().find(|c| c.name == col.name) {
+                    let id = col.id;
+                    *col = new_col.clone();
+                    col.id = id;
+                }
+            }
+        }
+    }
 }
 
 /// Alter region request.
@@ -213,5 +239,20 @@ mod tests {
         op.apply(&mut desc);
         assert_eq!(1, desc.row_key.columns.len());
         assert_eq!(1, desc.default_cf.columns.len());
+
+        // The column keeps its id.
+        let new_col = ColumnDescriptorBuilder::new(0, "4", ConcreteDataType::float64_datatype())
+            .is_nullable(true)
+            .build()
+            .unwrap();
+        let op = AlterOperation::ModifyColumns {
+            columns: vec![new_col],
+        };
+        op.apply(&mut desc);
+        assert_eq!(1, desc.default_cf.columns.len());
+        let col = &desc.default_cf.columns[0];
+        assert_eq!(4, col.id);
+        assert_eq!(ConcreteDataType::float64_datatype(), col.data_type);
+        assert!(col.is_nullable());
     }
 }
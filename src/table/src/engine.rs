@@ -24,7 +24,7 @@ use crate::error::{self, Result};
 use crate::metadata::TableId;
 use crate::requests::{
     AlterTableRequest, CloseTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest,
-    TruncateTableRequest,
+    TableRegionsRequest, TruncateTableRequest,
 };
 use crate::TableRef;
 pub mod manager;
@@ -138,6 +138,32 @@ pub trait TableEngine: Send + Sync {
         .fail()?
     }
 
+    /// Creates regions of an existing table and loads them into the table, e.g. when a region
+    /// of the table is split. Regions that exist already are loaded only.
+    async fn create_regions(
+        &self,
+        _ctx: &EngineContext,
+        _request: TableRegionsRequest,
+    ) -> Result<TableRef> {
+        error::UnsupportedSnafu {
+            operation: "create_regions",
+        }
+        .fail()?
+    }
+
+    /// Removes regions from a table and deletes their data, e.g. when regions of the table are
+    /// merged. Regions that don't exist are ignored.
+    async fn drop_regions(
+        &self,
+        _ctx: &EngineContext,
+        _request: TableRegionsRequest,
+    ) -> Result<()> {
+        error::UnsupportedSnafu {
+            operation: "drop_regions",
+        }
+        .fail()?
+    }
+
     /// Close the engine.
     async fn close(&self) -> Result<()>;
 
@@ -122,6 +122,26 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display(
+        "Failed to modify column {} in table {}, reason: {}",
+        column_name,
+        table_name,
+        reason
+    ))]
+    ModifyColumn {
+        column_name: String,
+        table_name: String,
+        reason: String,
+        location: Location,
+    },
+
+    #[snafu(display("Option {} of table {} can not be altered", key, table_name))]
+    UnalterableTableOption {
+        key: String,
+        table_name: String,
+        location: Location,
+    },
+
     #[snafu(display("Invalid table state: {}", table_id))]
     InvalidTable {
         table_id: TableId,
@@ -154,6 +174,8 @@ impl ErrorExt for Error {
             Error::RegionSchemaMismatch { .. } => StatusCode::StorageUnavailable,
             Error::Unsupported { .. } => StatusCode::Unsupported,
             Error::ParseTableOption { .. }
+            | Error::UnalterableTableOption { .. }
+            | Error::ModifyColumn { .. }
             | Error::EngineNotFound { .. }
             | Error::EngineExist { .. } => StatusCode::InvalidArguments,
 
@@ -20,14 +20,20 @@ use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_query::AddColumnLocation;
 use datafusion_expr::TableProviderFilterPushDown;
 pub use datatypes::error::{Error as ConvertError, Result as ConvertResult};
-use datatypes::schema::{ColumnSchema, RawSchema, Schema, SchemaBuilder, SchemaRef};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{
+    ColumnDefaultConstraint, ColumnSchema, RawSchema, Schema, SchemaBuilder, SchemaRef,
+};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use snafu::{ensure, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::{ColumnDescriptor, ColumnDescriptorBuilder, ColumnId};
 
 use crate::error::{self, Result};
-use crate::requests::{AddColumnRequest, AlterKind, TableOptions};
+use crate::requests::{
+    is_alterable_table_option, AddColumnRequest, AlterKind, ModifyColumnRequest, TableOptions,
+    TTL_KEY, WRITE_BUFFER_SIZE_KEY,
+};
 
 pub type TableId = u32;
 pub type TableVersion = u64;
@@ -117,6 +123,9 @@ pub struct TableMeta {
     pub created_on: DateTime<Utc>,
     #[builder(default = "Vec::new()")]
     pub partition_key_indices: Vec<usize>,
+    /// The SQL definition of the table if it is a view.
+    #[builder(default, setter(into))]
+    pub view_definition: Option<String>,
 }
 
 impl TableMetaBuilder {
@@ -185,6 +194,8 @@ impl TableMeta {
         match alter_kind {
             AlterKind::AddColumns { columns } => self.add_columns(table_name, columns),
             AlterKind::DropColumns { names } => self.remove_columns(table_name, names),
+            AlterKind::ModifyColumns { columns } => self.modify_columns(table_name, columns),
+            AlterKind::SetTableOptions { options } => self.set_table_options(table_name, options),
             // No need to rebuild table meta when renaming tables.
             AlterKind::RenameTable { .. } => {
                 let mut meta_builder = TableMetaBuilder::default();
@@ -233,7 +244,8 @@ impl TableMeta {
             .options(self.options.clone())
             .created_on(self.created_on)
             .region_numbers(self.region_numbers.clone())
-            .next_column_id(self.next_column_id);
+            .next_column_id(self.next_column_id)
+            .view_definition(self.view_definition.clone());
 
         builder
     }
@@ -389,6 +401,146 @@ impl TableMeta {
         Ok(meta_builder)
     }
 
+    fn modify_columns(
+        &self,
+        table_name: &str,
+        requests: &[ModifyColumnRequest],
+    ) -> Result<TableMetaBuilder> {
+        let table_schema = &self.schema;
+        let mut meta_builder = self.new_meta_builder();
+        let mut columns = table_schema.column_schemas().to_vec();
+
+        for request in requests {
+            let column_name = &request.column_name;
+            let index = table_schema
+                .column_index_by_name(column_name)
+                .with_context(|| error::ColumnNotExistsSnafu {
+                    column_name,
+                    table_name,
+                })?;
+            let old_column = &columns[index];
+            let data_type = request
+                .data_type
+                .clone()
+                .unwrap_or_else(|| old_column.data_type.clone());
+            let is_nullable = request.is_nullable.unwrap_or(old_column.is_nullable());
+
+            let reason = if self.primary_key_indices.contains(&index) {
+                Some("can't modify a primary key column".to_string())
+            } else if table_schema.timestamp_index() == Some(index) {
+                Some("can't modify the time index column".to_string())
+            } else if old_column.data_type != data_type
+                && !old_column.data_type.can_widen_to(&data_type)
+            {
+                Some(format!(
+                    "can't change data type from {:?} to {:?}",
+                    old_column.data_type, data_type
+                ))
+            } else if old_column.is_nullable() && !is_nullable {
+                Some("can't change a nullable column to NOT NULL".to_string())
+            } else {
+                None
+            };
+            let modify_column_error = |reason| {
+                error::ModifyColumnSnafu {
+                    column_name,
+                    table_name,
+                    reason,
+                }
+                .build()
+            };
+            if let Some(reason) = reason {
+                return Err(modify_column_error(reason));
+            }
+
+            // Keeps the current default value unless a new one is given, the value is
+            // converted if the column is widened.
+            let default_constraint = match &request.default_constraint {
+                Some(constraint) => Some(constraint.clone()),
+                None => old_column
+                    .default_constraint()
+                    .map(|constraint| widen_default_constraint(constraint, old_column, &data_type))
+                    .transpose()
+                    .map_err(|e| {
+                        modify_column_error(format!("can't convert the default value: {e}"))
+                    })?,
+            };
+            // Keeps the metadata of the original column.
+            columns[index] = ColumnSchema::new(column_name, data_type, is_nullable)
+                .with_default_constraint(default_constraint)
+                .map_err(|e| modify_column_error(format!("invalid default value: {e}")))?
+                .with_metadata(old_column.metadata().clone());
+        }
+
+        let mut builder = SchemaBuilder::try_from_columns(columns)
+            .with_context(|_| error::SchemaBuildSnafu {
+                msg: format!("Failed to convert column schemas into schema for table {table_name}"),
+            })?
+            // Also bump the schema version.
+            .version(table_schema.version() + 1);
+        for (k, v) in table_schema.metadata().iter() {
+            builder = builder.add_metadata(k, v);
+        }
+        let new_schema = builder.build().with_context(|_| error::SchemaBuildSnafu {
+            msg: format!("Table {table_name} cannot modify columns"),
+        })?;
+
+        // Columns are modified in place so the primary key indices are unchanged.
+        let _ = meta_builder
+            .schema(Arc::new(new_schema))
+            .primary_key_indices(self.primary_key_indices.clone());
+
+        Ok(meta_builder)
+    }
+
+    fn set_table_options(
+        &self,
+        table_name: &str,
+        options: &HashMap<String, String>,
+    ) -> Result<TableMetaBuilder> {
+        let mut new_options = self.options.clone();
+        let mut options_to_set = HashMap::with_capacity(options.len());
+        for (key, value) in options {
+            ensure!(
+                is_alterable_table_option(key),
+                error::UnalterableTableOptionSnafu { key, table_name }
+            );
+
+            if !value.is_empty() {
+                let _ = options_to_set.insert(key.clone(), value.clone());
+                continue;
+            }
+            // Resets the option.
+            match key.as_str() {
+                WRITE_BUFFER_SIZE_KEY => new_options.write_buffer_size = None,
+                TTL_KEY => new_options.ttl = None,
+                _ => {
+                    let _ = new_options.extra_options.remove(key);
+                }
+            }
+        }
+
+        // Only parses options to set, so the other options are kept as they are.
+        let options_to_set = TableOptions::try_from(&options_to_set)?;
+        if options_to_set.write_buffer_size.is_some() {
+            new_options.write_buffer_size = options_to_set.write_buffer_size;
+        }
+        if options_to_set.ttl.is_some() {
+            new_options.ttl = options_to_set.ttl;
+        }
+        new_options
+            .extra_options
+            .extend(options_to_set.extra_options);
+
+        let mut meta_builder = self.new_meta_builder();
+        let _ = meta_builder
+            .schema(self.schema.clone())
+            .primary_key_indices(self.primary_key_indices.clone())
+            .options(new_options);
+
+        Ok(meta_builder)
+    }
+
     /// Split requests into different groups using column location info.
     fn split_requests_by_column_location<'a>(
         &self,
@@ -508,6 +660,25 @@ impl From<TableId> for TableIdent {
     }
 }
 
+/// Converts the default value of `column` to the type `data_type` the column is widened to.
+fn widen_default_constraint(
+    constraint: &ColumnDefaultConstraint,
+    column: &ColumnSchema,
+    data_type: &ConcreteDataType,
+) -> ConvertResult<ColumnDefaultConstraint> {
+    match constraint {
+        ColumnDefaultConstraint::Value(value)
+            if !value.is_null() && column.data_type != *data_type =>
+        {
+            let vector = constraint
+                .create_default_vector(&column.data_type, column.is_nullable(), 1)?
+                .cast(data_type)?;
+            Ok(ColumnDefaultConstraint::Value(vector.get(0)))
+        }
+        _ => Ok(constraint.clone()),
+    }
+}
+
 /// Struct used to serialize and deserialize [`TableMeta`].
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct RawTableMeta {
@@ -522,6 +693,8 @@ pub struct RawTableMeta {
     pub created_on: DateTime<Utc>,
     #[serde(default)]
     pub partition_key_indices: Vec<usize>,
+    #[serde(default)]
+    pub view_definition: Option<String>,
 }
 
 impl From<TableMeta> for RawTableMeta {
@@ -537,6 +710,7 @@ impl From<TableMeta> for RawTableMeta {
             options: meta.options,
             created_on: meta.created_on,
             partition_key_indices: meta.partition_key_indices,
+            view_definition: meta.view_definition,
         }
     }
 }
@@ -556,6 +730,7 @@ impl TryFrom<RawTableMeta> for TableMeta {
             options: raw.options,
             created_on: raw.created_on,
             partition_key_indices: raw.partition_key_indices,
+            view_definition: raw.view_definition,
         })
     }
 }
@@ -604,11 +779,14 @@ impl TryFrom<RawTableInfo> for TableInfo {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
 
+    use common_base::readable_size::ReadableSize;
     use common_error::ext::ErrorExt;
     use common_error::status_code::StatusCode;
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::{ColumnSchema, Schema, SchemaBuilder};
+    use datatypes::value::Value;
 
     use super::*;
 
@@ -907,6 +1085,273 @@ mod tests {
         assert_eq!(StatusCode::InvalidArguments, err.status_code());
     }
 
+    #[test]
+    fn test_modify_columns() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let modify = |column_schema: ColumnSchema| AlterKind::ModifyColumns {
+            columns: vec![ModifyColumnRequest {
+                column_name: column_schema.name.clone(),
+                data_type: Some(column_schema.data_type.clone()),
+                is_nullable: Some(column_schema.is_nullable()),
+                default_constraint: None,
+            }],
+        };
+
+        let alter_kind = modify(ColumnSchema::new(
+            "col2",
+            ConcreteDataType::int64_datatype(),
+            true,
+        ));
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        let new_schema = &new_meta.schema;
+        assert_eq!(124, new_schema.version());
+        assert_eq!(
+            ConcreteDataType::int64_datatype(),
+            new_schema.column_schema_by_name("col2").unwrap().data_type
+        );
+        assert_eq!(meta.primary_key_indices, new_meta.primary_key_indices);
+        assert_eq!(meta.value_indices, new_meta.value_indices);
+        assert_eq!(meta.next_column_id, new_meta.next_column_id);
+        assert_eq!(Some(1), new_schema.timestamp_index());
+
+        // Narrowing the type.
+        let alter_kind = modify(ColumnSchema::new(
+            "col2",
+            ConcreteDataType::int16_datatype(),
+            true,
+        ));
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        // Changing to NOT NULL.
+        let alter_kind = modify(ColumnSchema::new(
+            "col2",
+            ConcreteDataType::int32_datatype(),
+            false,
+        ));
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        // Primary key column.
+        let alter_kind = modify(ColumnSchema::new(
+            "col1",
+            ConcreteDataType::int64_datatype(),
+            true,
+        ));
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        // Time index column.
+        let alter_kind = modify(ColumnSchema::new(
+            "ts",
+            ConcreteDataType::timestamp_millisecond_datatype(),
+            true,
+        ));
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        let alter_kind = modify(ColumnSchema::new(
+            "unknown",
+            ConcreteDataType::int64_datatype(),
+            true,
+        ));
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::TableColumnNotFound, err.status_code());
+    }
+
+    #[test]
+    fn test_modify_columns_keep_attributes() {
+        let column_schemas = vec![
+            ColumnSchema::new(
+                "ts",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            )
+            .with_time_index(true),
+            ColumnSchema::new("col", ConcreteDataType::int32_datatype(), false)
+                .with_default_constraint(Some(ColumnDefaultConstraint::Value(Value::Int32(1))))
+                .unwrap(),
+        ];
+        let schema = Arc::new(
+            SchemaBuilder::try_from(column_schemas)
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![])
+            .engine("engine")
+            .next_column_id(2)
+            .build()
+            .unwrap();
+
+        // Only widens the type, the default value and nullability are kept.
+        let alter_kind = AlterKind::ModifyColumns {
+            columns: vec![ModifyColumnRequest {
+                column_name: "col".to_string(),
+                data_type: Some(ConcreteDataType::int64_datatype()),
+                is_nullable: None,
+                default_constraint: None,
+            }],
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        let column = new_meta.schema.column_schema_by_name("col").unwrap();
+        assert_eq!(ConcreteDataType::int64_datatype(), column.data_type);
+        assert!(!column.is_nullable());
+        assert_eq!(
+            Some(&ColumnDefaultConstraint::Value(Value::Int64(1))),
+            column.default_constraint()
+        );
+
+        // Only sets the default value.
+        let alter_kind = AlterKind::ModifyColumns {
+            columns: vec![ModifyColumnRequest {
+                column_name: "col".to_string(),
+                data_type: None,
+                is_nullable: None,
+                default_constraint: Some(ColumnDefaultConstraint::Value(Value::Int64(2))),
+            }],
+        };
+        let new_meta = new_meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        let column = new_meta.schema.column_schema_by_name("col").unwrap();
+        assert_eq!(ConcreteDataType::int64_datatype(), column.data_type);
+        assert!(!column.is_nullable());
+        assert_eq!(
+            Some(&ColumnDefaultConstraint::Value(Value::Int64(2))),
+            column.default_constraint()
+        );
+
+        // The default value must match the type of the column.
+        let alter_kind = AlterKind::ModifyColumns {
+            columns: vec![ModifyColumnRequest {
+                column_name: "col".to_string(),
+                data_type: None,
+                is_nullable: None,
+                default_constraint: Some(ColumnDefaultConstraint::Value(Value::from("a"))),
+            }],
+        };
+        let err = new_meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    }
+
+    #[test]
+    fn test_set_table_options() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .options(TableOptions {
+                ttl: Some(Duration::from_secs(3600)),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([
+                ("ttl".to_string(), "7d".to_string()),
+                ("write_buffer_size".to_string(), "1MB".to_string()),
+                (
+                    "compaction.twcs.time_window_seconds".to_string(),
+                    "3600".to_string(),
+                ),
+            ]),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(meta.schema, new_meta.schema);
+        assert_eq!(meta.primary_key_indices, new_meta.primary_key_indices);
+        assert_eq!(
+            Some(Duration::from_secs(7 * 24 * 3600)),
+            new_meta.options.ttl
+        );
+        assert_eq!(
+            Some(ReadableSize::mb(1)),
+            new_meta.options.write_buffer_size
+        );
+        assert_eq!(
+            "3600",
+            new_meta.options.extra_options["compaction.twcs.time_window_seconds"]
+        );
+
+        // Empty value resets the option.
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("ttl".to_string(), String::new())]),
+        };
+        let new_meta = new_meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(None, new_meta.options.ttl);
+        assert_eq!(
+            Some(ReadableSize::mb(1)),
+            new_meta.options.write_buffer_size
+        );
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("ttl".to_string(), "abc".to_string())]),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("regions".to_string(), "2".to_string())]),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    }
+
     #[test]
     fn test_alloc_new_column() {
         let schema = Arc::new(new_test_schema());
@@ -21,8 +21,9 @@ use std::time::Duration;
 use common_base::readable_size::ReadableSize;
 use common_query::AddColumnLocation;
 use common_time::range::TimestampRange;
-use datatypes::prelude::VectorRef;
-use datatypes::schema::{ColumnSchema, RawSchema};
+use datatypes::prelude::{ConcreteDataType, VectorRef};
+use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, RawSchema};
+use datatypes::value::Value;
 use serde::{Deserialize, Serialize};
 use store_api::storage::RegionNumber;
 
@@ -35,6 +36,10 @@ pub const IMMUTABLE_TABLE_META_KEY: &str = "__private.immutable_table_meta";
 pub const IMMUTABLE_TABLE_LOCATION_KEY: &str = "location";
 pub const IMMUTABLE_TABLE_PATTERN_KEY: &str = "pattern";
 pub const IMMUTABLE_TABLE_FORMAT_KEY: &str = "format";
+/// The create option carrying the SQL definition of a view, which is moved into
+/// [`TableMeta::view_definition`](crate::metadata::TableMeta::view_definition) when the view
+/// is created.
+pub const VIEW_DEFINITION_KEY: &str = "__private.view_definition";
 
 #[derive(Debug, Clone)]
 pub struct CreateDatabaseRequest {
@@ -83,6 +88,32 @@ pub struct TableOptions {
 pub const WRITE_BUFFER_SIZE_KEY: &str = "write_buffer_size";
 pub const TTL_KEY: &str = "ttl";
 pub const REGIONS_KEY: &str = "regions";
+/// The option selecting the compaction strategy, options of the strategy have the prefix
+/// [COMPACTION_KEY_PREFIX].
+pub const COMPACTION_KEY: &str = "compaction";
+pub const COMPACTION_KEY_PREFIX: &str = "compaction.";
+/// The prefix of table options reserved for internal use, users can't set them.
+pub const PRIVATE_TABLE_OPTION_PREFIX: &str = "__private.";
+/// The option holding the query of a materialized view, whose results are stored in the table.
+pub const MATERIALIZED_VIEW_QUERY_KEY: &str = "__private.materialized_view_query";
+/// The option holding the user who created the materialized view, whose privileges the view
+/// is refreshed with.
+pub const MATERIALIZED_VIEW_OWNER_KEY: &str = "__private.materialized_view_owner";
+/// The option listing columns to build bloom filter indexes, separated by commas.
+pub const BLOOM_FILTER_COLUMNS_KEY: &str = "bloom_filter_columns";
+
+/// Returns true if the table option `key` is reserved for internal use.
+pub fn is_private_table_option(key: &str) -> bool {
+    key.starts_with(PRIVATE_TABLE_OPTION_PREFIX)
+}
+
+/// Returns true if the table option `key` could be changed by altering the table.
+pub fn is_alterable_table_option(key: &str) -> bool {
+    key == WRITE_BUFFER_SIZE_KEY
+        || key == TTL_KEY
+        || key == COMPACTION_KEY
+        || key.starts_with(COMPACTION_KEY_PREFIX)
+}
 
 impl TryFrom<&HashMap<String, String>> for TableOptions {
     type Error = error::Error;
@@ -190,11 +221,37 @@ pub struct AddColumnRequest {
     pub location: Option<AddColumnLocation>,
 }
 
+/// Modify column request, attributes that are not given are kept as they are.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifyColumnRequest {
+    pub column_name: String,
+    /// The new data type of the column.
+    pub data_type: Option<ConcreteDataType>,
+    /// Whether the column becomes nullable.
+    pub is_nullable: Option<bool>,
+    /// The new default value of the column.
+    pub default_constraint: Option<ColumnDefaultConstraint>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AlterKind {
-    AddColumns { columns: Vec<AddColumnRequest> },
-    DropColumns { names: Vec<String> },
-    RenameTable { new_table_name: String },
+    AddColumns {
+        columns: Vec<AddColumnRequest>,
+    },
+    DropColumns {
+        names: Vec<String>,
+    },
+    /// Modifies the data type, nullability or default value of field columns.
+    ModifyColumns {
+        columns: Vec<ModifyColumnRequest>,
+    },
+    RenameTable {
+        new_table_name: String,
+    },
+    /// Sets table options, an option with empty value is reset to its default.
+    SetTableOptions {
+        options: HashMap<String, String>,
+    },
 }
 
 /// Drop table request
@@ -239,6 +296,38 @@ impl CloseTableRequest {
     }
 }
 
+/// Creates or drops regions of an existing table, e.g. when regions of the table are split or
+/// merged.
+#[derive(Debug, Clone)]
+pub struct TableRegionsRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub table_id: TableId,
+    pub region_numbers: Vec<RegionNumber>,
+}
+
+impl TableRegionsRequest {
+    pub fn table_ref(&self) -> TableReference {
+        TableReference {
+            catalog: &self.catalog_name,
+            schema: &self.schema_name,
+            table: &self.table_name,
+        }
+    }
+}
+
+/// Moves rows from one region of a table to another.
+#[derive(Debug, Clone)]
+pub struct MoveRegionRowsRequest {
+    pub from: RegionNumber,
+    pub to: RegionNumber,
+    pub partition_columns: Vec<String>,
+    /// Only moves the rows whose values of the partition columns are not less than the bound,
+    /// compared in lexicographic order. Moves all rows if it's `None`.
+    pub lower_bound: Option<Vec<Value>>,
+}
+
 #[derive(Debug)]
 pub struct InsertRequest {
     pub catalog_name: String,
@@ -23,11 +23,12 @@ use async_trait::async_trait;
 use common_query::logical_plan::Expr;
 use common_recordbatch::SendableRecordBatchStream;
 use datatypes::schema::SchemaRef;
+use datatypes::value::Value;
 use store_api::storage::{RegionNumber, ScanRequest};
 
 use crate::error::{Result, UnsupportedSnafu};
 use crate::metadata::{FilterPushDownType, TableId, TableInfoRef, TableType};
-use crate::requests::{AlterTableRequest, DeleteRequest, InsertRequest};
+use crate::requests::{AlterTableRequest, DeleteRequest, InsertRequest, MoveRegionRowsRequest};
 use crate::stats::TableStatistics;
 use crate::RegionStat;
 
@@ -135,6 +136,42 @@ pub trait Table: Send + Sync {
         }
         .fail()?
     }
+
+    /// Sets whether the region accepts writes. The region can still be read and flushed if
+    /// it's not writable.
+    async fn set_region_writable(&self, region_number: RegionNumber, writable: bool) -> Result<()> {
+        let _ = (region_number, writable);
+        UnsupportedSnafu {
+            operation: "SET_REGION_WRITABLE",
+        }
+        .fail()?
+    }
+
+    /// Returns the values of `partition_columns` that split the rows of the region into two
+    /// halves of similar sizes, or `None` if the rows can't be split, e.g. the region is empty.
+    async fn region_split_key(
+        &self,
+        region_number: RegionNumber,
+        partition_columns: &[String],
+    ) -> Result<Option<Vec<Value>>> {
+        let _ = (region_number, partition_columns);
+        UnsupportedSnafu {
+            operation: "REGION_SPLIT_KEY",
+        }
+        .fail()?
+    }
+
+    /// Moves rows from one region to another, even if the regions are read-only. Returns the
+    /// number of rows moved.
+    ///
+    /// Moving the same rows again is harmless, so a failed request can simply be retried.
+    async fn move_region_rows(&self, request: MoveRegionRowsRequest) -> Result<usize> {
+        let _ = request;
+        UnsupportedSnafu {
+            operation: "MOVE_REGION_ROWS",
+        }
+        .fail()?
+    }
 }
 
 pub type TableRef = Arc<dyn Table>;
@@ -101,6 +101,15 @@ impl MemTable {
         Self { info, recordbatch }
     }
 
+    /// Turns the table into a view defined by the SQL `definition`.
+    pub fn into_view(mut self, definition: impl Into<String>) -> Self {
+        let mut info = (*self.info).clone();
+        info.table_type = TableType::View;
+        info.meta.view_definition = Some(definition.into());
+        self.info = Arc::new(info);
+        self
+    }
+
     pub fn table_name(&self) -> &str {
         &self.info.name
     }
@@ -20,12 +20,16 @@ mod test {
     use common_query::Output;
     use common_recordbatch::RecordBatches;
     use frontend::instance::Instance;
+    use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
     use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+    use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
     use opentelemetry_proto::tonic::common::v1::any_value::Value as Val;
     use opentelemetry_proto::tonic::common::v1::{AnyValue, InstrumentationScope, KeyValue};
+    use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
     use opentelemetry_proto::tonic::metrics::v1::number_data_point::Value;
     use opentelemetry_proto::tonic::metrics::v1::{metric, NumberDataPoint, *};
     use opentelemetry_proto::tonic::resource::v1::Resource;
+    use opentelemetry_proto::tonic::trace::v1::{ResourceSpans, ScopeSpans, Span};
     use servers::query_handler::sql::SqlQueryHandler;
     use servers::query_handler::OpenTelemetryProtocolHandler;
     use session::context::QueryContext;
@@ -38,6 +42,8 @@ mod test {
         let instance = &standalone.instance;
 
         test_otlp(instance).await;
+        test_otlp_traces(instance).await;
+        test_otlp_logs(instance).await;
     }
 
     #[tokio::test(flavor = "multi_thread")]
@@ -45,6 +51,8 @@ mod test {
         let instance = tests::create_distributed_instance("test_standalone_otlp").await;
 
         test_otlp(&instance.frontend()).await;
+        test_otlp_traces(&instance.frontend()).await;
+        test_otlp_logs(&instance.frontend()).await;
     }
 
     async fn test_otlp(instance: &Arc<Instance>) {
@@ -88,6 +96,174 @@ mod test {
         );
     }
 
+    async fn test_otlp_traces(instance: &Arc<Instance>) {
+        let db = "otlp_traces";
+        let ctx = QueryContext::with(DEFAULT_CATALOG_NAME, db);
+
+        assert!(SqlQueryHandler::do_query(
+            instance.as_ref(),
+            &format!("CREATE DATABASE IF NOT EXISTS {db}"),
+            ctx.clone(),
+        )
+        .await
+        .get(0)
+        .unwrap()
+        .is_ok());
+
+        let resp = instance
+            .traces(build_traces_request(), "my_traces".to_string(), ctx.clone())
+            .await
+            .unwrap();
+        assert!(resp.partial_success.is_none());
+
+        let mut output = instance
+            .do_query(
+                "SELECT service_name, span_id, parent_span_id, span_name, duration_nano \
+                FROM my_traces ORDER BY greptime_timestamp",
+                ctx.clone(),
+            )
+            .await;
+        let output = output.remove(0).unwrap();
+        let Output::Stream(stream) = output else {
+            unreachable!()
+        };
+        let recordbatches = RecordBatches::try_collect(stream).await.unwrap();
+        assert_eq!(
+            recordbatches.pretty_print().unwrap(),
+            "\
++--------------+------------------+------------------+-----------+---------------+
+| service_name | span_id          | parent_span_id   | span_name | duration_nano |
++--------------+------------------+------------------+-----------+---------------+
+| greptimedb   | 0202020202020202 |                  | root      | 300           |
+| greptimedb   | 0303030303030303 | 0202020202020202 | child     | 100           |
++--------------+------------------+------------------+-----------+---------------+",
+        );
+    }
+
+    fn build_traces_request() -> ExportTraceServiceRequest {
+        let spans = vec![
+            Span {
+                trace_id: vec![1; 16],
+                span_id: vec![2; 8],
+                name: "root".into(),
+                start_time_unix_nano: 1_000_000,
+                end_time_unix_nano: 1_000_300,
+                ..Default::default()
+            },
+            Span {
+                trace_id: vec![1; 16],
+                span_id: vec![3; 8],
+                parent_span_id: vec![2; 8],
+                name: "child".into(),
+                start_time_unix_nano: 2_000_000,
+                end_time_unix_nano: 2_000_100,
+                ..Default::default()
+            },
+        ];
+
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(Resource {
+                    attributes: vec![keyvalue("service.name", "greptimedb")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_spans: vec![ScopeSpans {
+                    spans,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    async fn test_otlp_logs(instance: &Arc<Instance>) {
+        let db = "otlp_logs";
+        let ctx = QueryContext::with(DEFAULT_CATALOG_NAME, db);
+
+        assert!(SqlQueryHandler::do_query(
+            instance.as_ref(),
+            &format!("CREATE DATABASE IF NOT EXISTS {db}"),
+            ctx.clone(),
+        )
+        .await
+        .get(0)
+        .unwrap()
+        .is_ok());
+
+        let resp = instance
+            .logs(build_logs_request(), "my_logs".to_string(), ctx.clone())
+            .await
+            .unwrap();
+        assert!(resp.partial_success.is_none());
+
+        let mut output = instance
+            .do_query(
+                "SELECT service_name, severity_text, body FROM my_logs \
+                WHERE body LIKE '%refused%' ORDER BY greptime_timestamp",
+                ctx.clone(),
+            )
+            .await;
+        let output = output.remove(0).unwrap();
+        let Output::Stream(stream) = output else {
+            unreachable!()
+        };
+        let recordbatches = RecordBatches::try_collect(stream).await.unwrap();
+        assert_eq!(
+            recordbatches.pretty_print().unwrap(),
+            "\
++--------------+---------------+--------------------------+
+| service_name | severity_text | body                     |
++--------------+---------------+--------------------------+
+| greptimedb   | ERROR         | connection refused       |
+| greptimedb   | ERROR         | connection refused again |
++--------------+---------------+--------------------------+",
+        );
+    }
+
+    fn build_logs_request() -> ExportLogsServiceRequest {
+        let log_records = vec![
+            LogRecord {
+                time_unix_nano: 1_000_000,
+                severity_text: "INFO".into(),
+                body: Some(AnyValue {
+                    value: Some(Val::StringValue("server started".into())),
+                }),
+                ..Default::default()
+            },
+            LogRecord {
+                time_unix_nano: 2_000_000,
+                severity_text: "ERROR".into(),
+                body: Some(AnyValue {
+                    value: Some(Val::StringValue("connection refused".into())),
+                }),
+                ..Default::default()
+            },
+            // in the same millisecond as the previous one
+            LogRecord {
+                time_unix_nano: 2_000_500,
+                severity_text: "ERROR".into(),
+                body: Some(AnyValue {
+                    value: Some(Val::StringValue("connection refused again".into())),
+                }),
+                ..Default::default()
+            },
+        ];
+
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: Some(Resource {
+                    attributes: vec![keyvalue("service.name", "greptimedb")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_logs: vec![ScopeLogs {
+                    log_records,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
     fn build_request() -> ExportMetricsServiceRequest {
         let data_points = vec![
             NumberDataPoint {
@@ -14,6 +14,7 @@
 
 use std::env;
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_catalog::consts::DEFAULT_CATALOG_NAME;
 use common_query::Output;
@@ -333,6 +334,235 @@ async fn test_execute_insert_by_select(instance: Arc<dyn MockInstance>) {
     check_output_stream(output, expected).await;
 }
 
+#[apply(both_instances_cases)]
+async fn test_materialized_view(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let output = execute_sql(
+        &instance,
+        "create table host(ts timestamp(3) time index, host string primary key, val double)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "insert into host values (0, 'host1', 0.0), (5000, 'host1', 1.0), (10000, 'host1', 2.0)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(3)));
+
+    assert!(matches!(
+        try_execute_sql(
+            &instance,
+            "create materialized view bad as select * from host"
+        )
+        .await
+        .unwrap_err(),
+        Error::InvalidMaterializedView { .. }
+    ));
+
+    // The view is backfilled with the existing data.
+    let output = execute_sql(
+        &instance,
+        "create materialized view host_max as \
+         select ts, host, max(val) range '10s' as max_val from host align '5s'",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(4)));
+
+    let output = execute_sql(
+        &instance,
+        "select ts, host, max_val from host_max order by ts",
+    )
+    .await;
+    let expected = "\
++---------------------+-------+---------+
+| ts                  | host  | max_val |
++---------------------+-------+---------+
+| 1970-01-01T00:00:00 | host1 | 0.0     |
+| 1970-01-01T00:00:05 | host1 | 1.0     |
+| 1970-01-01T00:00:10 | host1 | 2.0     |
+| 1970-01-01T00:00:15 | host1 | 2.0     |
++---------------------+-------+---------+";
+    check_output_stream(output, expected).await;
+
+    // New rows, including late ones, refresh the align slots they fall into.
+    let output = execute_sql(
+        &instance,
+        "insert into host values (20000, 'host1', 10.0), (12000, 'host1', 7.0)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    // Views are refreshed in the background.
+    let expected = "\
++---------------------+-------+---------+
+| ts                  | host  | max_val |
++---------------------+-------+---------+
+| 1970-01-01T00:00:00 | host1 | 0.0     |
+| 1970-01-01T00:00:05 | host1 | 1.0     |
+| 1970-01-01T00:00:10 | host1 | 2.0     |
+| 1970-01-01T00:00:15 | host1 | 7.0     |
+| 1970-01-01T00:00:20 | host1 | 10.0    |
+| 1970-01-01T00:00:25 | host1 | 10.0    |
++---------------------+-------+---------+";
+    wait_for_output(
+        &instance,
+        "select ts, host, max_val from host_max order by ts",
+        expected,
+    )
+    .await;
+
+    // Align slots without rows left are removed.
+    let output = execute_sql(&instance, "delete from host where ts = 20000").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let expected = "\
++---------------------+-------+---------+
+| ts                  | host  | max_val |
++---------------------+-------+---------+
+| 1970-01-01T00:00:00 | host1 | 0.0     |
+| 1970-01-01T00:00:05 | host1 | 1.0     |
+| 1970-01-01T00:00:10 | host1 | 2.0     |
+| 1970-01-01T00:00:15 | host1 | 7.0     |
+| 1970-01-01T00:00:20 | host1 | 7.0     |
++---------------------+-------+---------+";
+    wait_for_output(
+        &instance,
+        "select ts, host, max_val from host_max order by ts",
+        expected,
+    )
+    .await;
+
+    let output = execute_sql(&instance, "show materialized views").await;
+    let expected = "\
++--------------------+--------------+--------+-------+
+| Materialized_views | Source_table | State  | Error |
++--------------------+--------------+--------+-------+
+| host_max           | host         | active |       |
++--------------------+--------------+--------+-------+";
+    check_output_stream(output, expected).await;
+}
+
+/// Waits for the output of `sql` to become `expected`, for results maintained in the
+/// background.
+async fn wait_for_output(instance: &Arc<Instance>, sql: &str, expected: &str) {
+    for _ in 0..50 {
+        let recordbatches = match execute_sql(instance, sql).await {
+            Output::Stream(stream) => util::collect_batches(stream).await.unwrap(),
+            Output::RecordBatches(recordbatches) => recordbatches,
+            _ => unreachable!(),
+        };
+        if recordbatches.pretty_print().unwrap() == expected {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    check_output_stream(execute_sql(instance, sql).await, expected).await;
+}
+
+#[apply(both_instances_cases)]
+async fn test_view(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let output = execute_sql(
+        &instance,
+        "create table host(ts timestamp(3) time index, host string primary key, val double)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "insert into host values (0, 'host1', 0.5), (5000, 'host2', 1.0), (10000, 'host1', 2.0)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(3)));
+
+    let output = execute_sql(
+        &instance,
+        "create view busy_host as select ts, host, val from host where val > 0.8",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    // Views can be built on other views.
+    let output = execute_sql(
+        &instance,
+        "create view busy_host_count as select host, count(*) as c from busy_host group by host",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    assert!(
+        try_execute_sql(&instance, "create view bad as select * from not_exist")
+            .await
+            .is_err()
+    );
+
+    let output = execute_sql(&instance, "select * from busy_host order by ts").await;
+    let expected = "\
++---------------------+-------+-----+
+| ts                  | host  | val |
++---------------------+-------+-----+
+| 1970-01-01T00:00:05 | host2 | 1.0 |
+| 1970-01-01T00:00:10 | host1 | 2.0 |
++---------------------+-------+-----+";
+    check_output_stream(output, expected).await;
+
+    let output = execute_sql(
+        &instance,
+        "select v.host, v.c, h.val from busy_host_count v join host h on v.host = h.host \
+         where h.ts = 0",
+    )
+    .await;
+    let expected = "\
++-------+---+-----+
+| host  | c | val |
++-------+---+-----+
+| host1 | 1 | 0.5 |
++-------+---+-----+";
+    check_output_stream(output, expected).await;
+
+    // Views read the latest data of their tables.
+    let output = execute_sql(&instance, "insert into host values (15000, 'host2', 3.0)").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(&instance, "select * from busy_host_count order by host").await;
+    let expected = "\
++-------+---+
+| host  | c |
++-------+---+
+| host1 | 1 |
+| host2 | 2 |
++-------+---+";
+    check_output_stream(output, expected).await;
+
+    let output = execute_sql(&instance, "show full tables like '%host%'").await;
+    let expected = "\
++-----------------+------------+
+| Tables          | Table_type |
++-----------------+------------+
+| busy_host       | VIEW       |
+| busy_host_count | VIEW       |
+| host            | BASE TABLE |
++-----------------+------------+";
+    check_output_stream(output, expected).await;
+
+    let output = execute_sql(&instance, "show create view busy_host").await;
+    let expected = "\
++-----------+-------------------------------------------------------------------------+
+| View      | Create View                                                             |
++-----------+-------------------------------------------------------------------------+
+| busy_host | CREATE VIEW busy_host AS SELECT ts, host, val FROM host WHERE val > 0.8 |
++-----------+-------------------------------------------------------------------------+";
+    check_output_stream(output, expected).await;
+
+    assert!(try_execute_sql(&instance, "drop view host").await.is_err());
+    let output = execute_sql(&instance, "drop view busy_host_count").await;
+    assert!(matches!(output, Output::AffectedRows(_)));
+    let output = execute_sql(&instance, "drop view if exists busy_host_count").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    assert!(try_execute_sql(&instance, "select * from busy_host_count")
+        .await
+        .is_err());
+}
+
 #[apply(both_instances_cases)]
 async fn test_execute_insert_query_with_i64_timestamp(instance: Arc<dyn MockInstance>) {
     let instance = instance.frontend();
@@ -1036,6 +1266,139 @@ async fn test_alter_table(instance: Arc<dyn MockInstance>) {
     check_output_stream(output, expected).await;
 }
 
+#[apply(standalone_instance_case)]
+async fn test_alter_table_options(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let sql = r#"create table demo(
+    host STRING,
+    cpu DOUBLE,
+    ts bigint,
+    TIME INDEX(ts)
+) with(ttl='7d')"#;
+    let output = execute_sql(&instance, sql).await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "alter table demo set ttl = '30d', write_buffer_size = '1MB'",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    // Options that are not related to the storage can't be altered.
+    assert!(
+        try_execute_sql(&instance, "alter table demo set regions = 2")
+            .await
+            .is_err()
+    );
+    assert!(
+        try_execute_sql(&instance, "alter table demo set ttl = 'abc'")
+            .await
+            .is_err()
+    );
+
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.1, 1000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "show create table demo").await;
+    let expected = "\
++-------+-----------------------------------+
+| Table | Create Table                      |
++-------+-----------------------------------+
+| demo  | CREATE TABLE IF NOT EXISTS demo ( |
+|       |   host STRING NULL,               |
+|       |   cpu DOUBLE NULL,                |
+|       |   ts BIGINT NOT NULL,             |
+|       |   TIME INDEX (ts)                 |
+|       | )                                 |
+|       | ENGINE=mito                       |
+|       | WITH(                             |
+|       |   regions = 1,                    |
+|       |   write_buffer_size = '1.0MiB',   |
+|       |   ttl = '30days'                  |
+|       | )                                 |
++-------+-----------------------------------+";
+    check_output_stream(output, expected).await;
+}
+
+#[apply(standalone_instance_case)]
+async fn test_alter_table_modify_column(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let sql = r#"create table demo(
+    host STRING,
+    cpu INT DEFAULT 7,
+    ts bigint,
+    TIME INDEX(ts),
+    PRIMARY KEY(host)
+)"#;
+    let output = execute_sql(&instance, sql).await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1, 1000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    // Key columns and narrowing types are not allowed.
+    assert!(
+        try_execute_sql(&instance, "alter table demo modify column host INT")
+            .await
+            .is_err()
+    );
+    assert!(
+        try_execute_sql(&instance, "alter table demo modify column cpu SMALLINT")
+            .await
+            .is_err()
+    );
+
+    let output = execute_sql(&instance, "alter table demo modify column cpu BIGINT").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host2', 3000000000, 2000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    // The default value is kept after modifying the type.
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, ts) values ('host3', 3000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "alter table demo modify cpu set default 8").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, ts) values ('host4', 4000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "select * from demo order by ts").await;
+    let expected = "\
++-------+------------+------+
+| host  | cpu        | ts   |
++-------+------------+------+
+| host1 | 1          | 1000 |
+| host2 | 3000000000 | 2000 |
+| host3 | 7          | 3000 |
+| host4 | 8          | 4000 |
++-------+------------+------+";
+    check_output_stream(output, expected).await;
+}
+
 async fn test_insert_with_default_value_for_type(instance: Arc<Instance>, type_name: &str) {
     let table_name = format!("test_table_with_{type_name}");
     let create_sql = format!(
@@ -1443,25 +1806,35 @@ async fn test_information_schema_dot_tables(instance: Arc<dyn MockInstance>) {
     let expected = match is_distributed_mode {
         true => {
             "\
-+---------------+--------------------+------------+-----------------+----------+-------------+
-| table_catalog | table_schema       | table_name | table_type      | table_id | engine      |
-+---------------+--------------------+------------+-----------------+----------+-------------+
-| greptime      | information_schema | columns    | LOCAL TEMPORARY | 4        |             |
-| greptime      | public             | numbers    | LOCAL TEMPORARY | 2        | test_engine |
-| greptime      | public             | scripts    | BASE TABLE      | 1024     | mito        |
-| greptime      | information_schema | tables     | LOCAL TEMPORARY | 3        |             |
-+---------------+--------------------+------------+-----------------+----------+-------------+"
++---------------+--------------------+------------------+-----------------+----------+-------------+
+| table_catalog | table_schema       | table_name       | table_type      | table_id | engine      |
++---------------+--------------------+------------------+-----------------+----------+-------------+
+| greptime      | information_schema | columns          | LOCAL TEMPORARY | 4        |             |
+| greptime      | information_schema | engines          | LOCAL TEMPORARY | 6        |             |
+| greptime      | information_schema | key_column_usage | LOCAL TEMPORARY | 7        |             |
+| greptime      | public             | numbers          | LOCAL TEMPORARY | 2        | test_engine |
+| greptime      | information_schema | partitions       | LOCAL TEMPORARY | 8        |             |
+| greptime      | information_schema | region_peers     | LOCAL TEMPORARY | 9        |             |
+| greptime      | information_schema | schemata         | LOCAL TEMPORARY | 5        |             |
+| greptime      | public             | scripts          | BASE TABLE      | 1024     | mito        |
+| greptime      | information_schema | tables           | LOCAL TEMPORARY | 3        |             |
++---------------+--------------------+------------------+-----------------+----------+-------------+"
         }
         false => {
             "\
-+---------------+--------------------+------------+-----------------+----------+-------------+
-| table_catalog | table_schema       | table_name | table_type      | table_id | engine      |
-+---------------+--------------------+------------+-----------------+----------+-------------+
-| greptime      | information_schema | columns    | LOCAL TEMPORARY | 4        |             |
-| greptime      | public             | numbers    | LOCAL TEMPORARY | 2        | test_engine |
-| greptime      | public             | scripts    | BASE TABLE      | 1        | mito        |
-| greptime      | information_schema | tables     | LOCAL TEMPORARY | 3        |             |
-+---------------+--------------------+------------+-----------------+----------+-------------+"
++---------------+--------------------+------------------+-----------------+----------+-------------+
+| table_catalog | table_schema       | table_name       | table_type      | table_id | engine      |
++---------------+--------------------+------------------+-----------------+----------+-------------+
+| greptime      | information_schema | columns          | LOCAL TEMPORARY | 4        |             |
+| greptime      | information_schema | engines          | LOCAL TEMPORARY | 6        |             |
+| greptime      | information_schema | key_column_usage | LOCAL TEMPORARY | 7        |             |
+| greptime      | public             | numbers          | LOCAL TEMPORARY | 2        | test_engine |
+| greptime      | information_schema | partitions       | LOCAL TEMPORARY | 8        |             |
+| greptime      | information_schema | region_peers     | LOCAL TEMPORARY | 9        |             |
+| greptime      | information_schema | schemata         | LOCAL TEMPORARY | 5        |             |
+| greptime      | public             | scripts          | BASE TABLE      | 1        | mito        |
+| greptime      | information_schema | tables           | LOCAL TEMPORARY | 3        |             |
++---------------+--------------------+------------------+-----------------+----------+-------------+"
         }
     };
 
@@ -1471,23 +1844,33 @@ async fn test_information_schema_dot_tables(instance: Arc<dyn MockInstance>) {
     let expected = match is_distributed_mode {
         true => {
             "\
-+-----------------+--------------------+---------------+-----------------+----------+--------+
-| table_catalog   | table_schema       | table_name    | table_type      | table_id | engine |
-+-----------------+--------------------+---------------+-----------------+----------+--------+
-| another_catalog | another_schema     | another_table | BASE TABLE      | 1025     | mito   |
-| another_catalog | information_schema | columns       | LOCAL TEMPORARY | 4        |        |
-| another_catalog | information_schema | tables        | LOCAL TEMPORARY | 3        |        |
-+-----------------+--------------------+---------------+-----------------+----------+--------+"
++-----------------+--------------------+------------------+-----------------+----------+--------+
+| table_catalog   | table_schema       | table_name       | table_type      | table_id | engine |
++-----------------+--------------------+------------------+-----------------+----------+--------+
+| another_catalog | another_schema     | another_table    | BASE TABLE      | 1025     | mito   |
+| another_catalog | information_schema | columns          | LOCAL TEMPORARY | 4        |        |
+| another_catalog | information_schema | engines          | LOCAL TEMPORARY | 6        |        |
+| another_catalog | information_schema | key_column_usage | LOCAL TEMPORARY | 7        |        |
+| another_catalog | information_schema | partitions       | LOCAL TEMPORARY | 8        |        |
+| another_catalog | information_schema | region_peers     | LOCAL TEMPORARY | 9        |        |
+| another_catalog | information_schema | schemata         | LOCAL TEMPORARY | 5        |        |
+| another_catalog | information_schema | tables           | LOCAL TEMPORARY | 3        |        |
++-----------------+--------------------+------------------+-----------------+----------+--------+"
         }
         false => {
             "\
-+-----------------+--------------------+---------------+-----------------+----------+--------+
-| table_catalog   | table_schema       | table_name    | table_type      | table_id | engine |
-+-----------------+--------------------+---------------+-----------------+----------+--------+
-| another_catalog | another_schema     | another_table | BASE TABLE      | 1024     | mito   |
-| another_catalog | information_schema | columns       | LOCAL TEMPORARY | 4        |        |
-| another_catalog | information_schema | tables        | LOCAL TEMPORARY | 3        |        |
-+-----------------+--------------------+---------------+-----------------+----------+--------+"
++-----------------+--------------------+------------------+-----------------+----------+--------+
+| table_catalog   | table_schema       | table_name       | table_type      | table_id | engine |
++-----------------+--------------------+------------------+-----------------+----------+--------+
+| another_catalog | another_schema     | another_table    | BASE TABLE      | 1024     | mito   |
+| another_catalog | information_schema | columns          | LOCAL TEMPORARY | 4        |        |
+| another_catalog | information_schema | engines          | LOCAL TEMPORARY | 6        |        |
+| another_catalog | information_schema | key_column_usage | LOCAL TEMPORARY | 7        |        |
+| another_catalog | information_schema | partitions       | LOCAL TEMPORARY | 8        |        |
+| another_catalog | information_schema | region_peers     | LOCAL TEMPORARY | 9        |        |
+| another_catalog | information_schema | schemata         | LOCAL TEMPORARY | 5        |        |
+| another_catalog | information_schema | tables           | LOCAL TEMPORARY | 3        |        |
++-----------------+--------------------+------------------+-----------------+----------+--------+"
         }
     };
     check_output_stream(output, expected).await;
@@ -1508,52 +1891,132 @@ async fn test_information_schema_dot_columns(instance: Arc<dyn MockInstance>) {
 
     let output = execute_sql(&instance, sql).await;
     let expected = "\
-+---------------+--------------------+------------+---------------+----------------------+---------------+
-| table_catalog | table_schema       | table_name | column_name   | data_type            | semantic_type |
-+---------------+--------------------+------------+---------------+----------------------+---------------+
-| greptime      | information_schema | columns    | table_catalog | String               | FIELD         |
-| greptime      | information_schema | columns    | table_schema  | String               | FIELD         |
-| greptime      | information_schema | columns    | table_name    | String               | FIELD         |
-| greptime      | information_schema | columns    | column_name   | String               | FIELD         |
-| greptime      | information_schema | columns    | data_type     | String               | FIELD         |
-| greptime      | information_schema | columns    | semantic_type | String               | FIELD         |
-| greptime      | public             | numbers    | number        | UInt32               | PRIMARY KEY   |
-| greptime      | public             | scripts    | schema        | String               | PRIMARY KEY   |
-| greptime      | public             | scripts    | name          | String               | PRIMARY KEY   |
-| greptime      | public             | scripts    | script        | String               | FIELD         |
-| greptime      | public             | scripts    | engine        | String               | FIELD         |
-| greptime      | public             | scripts    | timestamp     | TimestampMillisecond | TIME INDEX    |
-| greptime      | public             | scripts    | gmt_created   | TimestampMillisecond | FIELD         |
-| greptime      | public             | scripts    | gmt_modified  | TimestampMillisecond | FIELD         |
-| greptime      | information_schema | tables     | table_catalog | String               | FIELD         |
-| greptime      | information_schema | tables     | table_schema  | String               | FIELD         |
-| greptime      | information_schema | tables     | table_name    | String               | FIELD         |
-| greptime      | information_schema | tables     | table_type    | String               | FIELD         |
-| greptime      | information_schema | tables     | table_id      | UInt32               | FIELD         |
-| greptime      | information_schema | tables     | engine        | String               | FIELD         |
-+---------------+--------------------+------------+---------------+----------------------+---------------+";
++---------------+--------------------+------------------+-------------------------------+----------------------+---------------+
+| table_catalog | table_schema       | table_name       | column_name                   | data_type            | semantic_type |
++---------------+--------------------+------------------+-------------------------------+----------------------+---------------+
+| greptime      | information_schema | columns          | table_catalog                 | String               | FIELD         |
+| greptime      | information_schema | columns          | table_schema                  | String               | FIELD         |
+| greptime      | information_schema | columns          | table_name                    | String               | FIELD         |
+| greptime      | information_schema | columns          | column_name                   | String               | FIELD         |
+| greptime      | information_schema | columns          | data_type                     | String               | FIELD         |
+| greptime      | information_schema | columns          | semantic_type                 | String               | FIELD         |
+| greptime      | information_schema | engines          | engine                        | String               | FIELD         |
+| greptime      | information_schema | engines          | support                       | String               | FIELD         |
+| greptime      | information_schema | engines          | comment                       | String               | FIELD         |
+| greptime      | information_schema | engines          | transactions                  | String               | FIELD         |
+| greptime      | information_schema | engines          | xa                            | String               | FIELD         |
+| greptime      | information_schema | engines          | savepoints                    | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | constraint_catalog            | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | constraint_schema             | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | constraint_name               | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | table_catalog                 | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | table_schema                  | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | table_name                    | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | column_name                   | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | ordinal_position              | UInt32               | FIELD         |
+| greptime      | information_schema | key_column_usage | position_in_unique_constraint | UInt32               | FIELD         |
+| greptime      | information_schema | key_column_usage | referenced_table_schema       | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | referenced_table_name         | String               | FIELD         |
+| greptime      | information_schema | key_column_usage | referenced_column_name        | String               | FIELD         |
+| greptime      | public             | numbers          | number                        | UInt32               | PRIMARY KEY   |
+| greptime      | information_schema | partitions       | table_catalog                 | String               | FIELD         |
+| greptime      | information_schema | partitions       | table_schema                  | String               | FIELD         |
+| greptime      | information_schema | partitions       | table_name                    | String               | FIELD         |
+| greptime      | information_schema | partitions       | partition_name                | String               | FIELD         |
+| greptime      | information_schema | partitions       | partition_ordinal_position    | Int64                | FIELD         |
+| greptime      | information_schema | partitions       | partition_method              | String               | FIELD         |
+| greptime      | information_schema | partitions       | partition_expression          | String               | FIELD         |
+| greptime      | information_schema | partitions       | partition_description         | String               | FIELD         |
+| greptime      | information_schema | partitions       | greptime_partition_id         | UInt64               | FIELD         |
+| greptime      | information_schema | region_peers     | table_catalog                 | String               | FIELD         |
+| greptime      | information_schema | region_peers     | table_schema                  | String               | FIELD         |
+| greptime      | information_schema | region_peers     | table_name                    | String               | FIELD         |
+| greptime      | information_schema | region_peers     | region_id                     | UInt64               | FIELD         |
+| greptime      | information_schema | region_peers     | region_number                 | UInt32               | FIELD         |
+| greptime      | information_schema | region_peers     | peer_id                       | UInt64               | FIELD         |
+| greptime      | information_schema | region_peers     | peer_addr                     | String               | FIELD         |
+| greptime      | information_schema | region_peers     | is_leader                     | String               | FIELD         |
+| greptime      | information_schema | schemata         | catalog_name                  | String               | FIELD         |
+| greptime      | information_schema | schemata         | schema_name                   | String               | FIELD         |
+| greptime      | information_schema | schemata         | default_character_set_name    | String               | FIELD         |
+| greptime      | information_schema | schemata         | default_collation_name        | String               | FIELD         |
+| greptime      | information_schema | schemata         | sql_path                      | String               | FIELD         |
+| greptime      | public             | scripts          | schema                        | String               | PRIMARY KEY   |
+| greptime      | public             | scripts          | name                          | String               | PRIMARY KEY   |
+| greptime      | public             | scripts          | script                        | String               | FIELD         |
+| greptime      | public             | scripts          | engine                        | String               | FIELD         |
+| greptime      | public             | scripts          | timestamp                     | TimestampMillisecond | TIME INDEX    |
+| greptime      | public             | scripts          | gmt_created                   | TimestampMillisecond | FIELD         |
+| greptime      | public             | scripts          | gmt_modified                  | TimestampMillisecond | FIELD         |
+| greptime      | information_schema | tables           | table_catalog                 | String               | FIELD         |
+| greptime      | information_schema | tables           | table_schema                  | String               | FIELD         |
+| greptime      | information_schema | tables           | table_name                    | String               | FIELD         |
+| greptime      | information_schema | tables           | table_type                    | String               | FIELD         |
+| greptime      | information_schema | tables           | table_id                      | UInt32               | FIELD         |
+| greptime      | information_schema | tables           | engine                        | String               | FIELD         |
++---------------+--------------------+------------------+-------------------------------+----------------------+---------------+";
 
     check_output_stream(output, expected).await;
 
     let output = execute_sql_with(&instance, sql, query_ctx).await;
     let expected = "\
-+-----------------+--------------------+---------------+---------------+-----------+---------------+
-| table_catalog   | table_schema       | table_name    | column_name   | data_type | semantic_type |
-+-----------------+--------------------+---------------+---------------+-----------+---------------+
-| another_catalog | another_schema     | another_table | i             | Int64     | TIME INDEX    |
-| another_catalog | information_schema | columns       | table_catalog | String    | FIELD         |
-| another_catalog | information_schema | columns       | table_schema  | String    | FIELD         |
-| another_catalog | information_schema | columns       | table_name    | String    | FIELD         |
-| another_catalog | information_schema | columns       | column_name   | String    | FIELD         |
-| another_catalog | information_schema | columns       | data_type     | String    | FIELD         |
-| another_catalog | information_schema | columns       | semantic_type | String    | FIELD         |
-| another_catalog | information_schema | tables        | table_catalog | String    | FIELD         |
-| another_catalog | information_schema | tables        | table_schema  | String    | FIELD         |
-| another_catalog | information_schema | tables        | table_name    | String    | FIELD         |
-| another_catalog | information_schema | tables        | table_type    | String    | FIELD         |
-| another_catalog | information_schema | tables        | table_id      | UInt32    | FIELD         |
-| another_catalog | information_schema | tables        | engine        | String    | FIELD         |
-+-----------------+--------------------+---------------+---------------+-----------+---------------+";
++-----------------+--------------------+------------------+-------------------------------+-----------+---------------+
+| table_catalog   | table_schema       | table_name       | column_name                   | data_type | semantic_type |
++-----------------+--------------------+------------------+-------------------------------+-----------+---------------+
+| another_catalog | another_schema     | another_table    | i                             | Int64     | TIME INDEX    |
+| another_catalog | information_schema | columns          | table_catalog                 | String    | FIELD         |
+| another_catalog | information_schema | columns          | table_schema                  | String    | FIELD         |
+| another_catalog | information_schema | columns          | table_name                    | String    | FIELD         |
+| another_catalog | information_schema | columns          | column_name                   | String    | FIELD         |
+| another_catalog | information_schema | columns          | data_type                     | String    | FIELD         |
+| another_catalog | information_schema | columns          | semantic_type                 | String    | FIELD         |
+| another_catalog | information_schema | engines          | engine                        | String    | FIELD         |
+| another_catalog | information_schema | engines          | support                       | String    | FIELD         |
+| another_catalog | information_schema | engines          | comment                       | String    | FIELD         |
+| another_catalog | information_schema | engines          | transactions                  | String    | FIELD         |
+| another_catalog | information_schema | engines          | xa                            | String    | FIELD         |
+| another_catalog | information_schema | engines          | savepoints                    | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | constraint_catalog            | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | constraint_schema             | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | constraint_name               | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | table_catalog                 | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | table_schema                  | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | table_name                    | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | column_name                   | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | ordinal_position              | UInt32    | FIELD         |
+| another_catalog | information_schema | key_column_usage | position_in_unique_constraint | UInt32    | FIELD         |
+| another_catalog | information_schema | key_column_usage | referenced_table_schema       | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | referenced_table_name         | String    | FIELD         |
+| another_catalog | information_schema | key_column_usage | referenced_column_name        | String    | FIELD         |
+| another_catalog | information_schema | partitions       | table_catalog                 | String    | FIELD         |
+| another_catalog | information_schema | partitions       | table_schema                  | String    | FIELD         |
+| another_catalog | information_schema | partitions       | table_name                    | String    | FIELD         |
+| another_catalog | information_schema | partitions       | partition_name                | String    | FIELD         |
+| another_catalog | information_schema | partitions       | partition_ordinal_position    | Int64     | FIELD         |
+| another_catalog | information_schema | partitions       | partition_method              | String    | FIELD         |
+| another_catalog | information_schema | partitions       | partition_expression          | String    | FIELD         |
+| another_catalog | information_schema | partitions       | partition_description         | String    | FIELD         |
+| another_catalog | information_schema | partitions       | greptime_partition_id         | UInt64    | FIELD         |
+| another_catalog | information_schema | region_peers     | table_catalog                 | String    | FIELD         |
+| another_catalog | information_schema | region_peers     | table_schema                  | String    | FIELD         |
+| another_catalog | information_schema | region_peers     | table_name                    | String    | FIELD         |
+| another_catalog | information_schema | region_peers     | region_id                     | UInt64    | FIELD         |
+| another_catalog | information_schema | region_peers     | region_number                 | UInt32    | FIELD         |
+| another_catalog | information_schema | region_peers     | peer_id                       | UInt64    | FIELD         |
+| another_catalog | information_schema | region_peers     | peer_addr                     | String    | FIELD         |
+| another_catalog | information_schema | region_peers     | is_leader                     | String    | FIELD         |
+| another_catalog | information_schema | schemata         | catalog_name                  | String    | FIELD         |
+| another_catalog | information_schema | schemata         | schema_name                   | String    | FIELD         |
+| another_catalog | information_schema | schemata         | default_character_set_name    | String    | FIELD         |
+| another_catalog | information_schema | schemata         | default_collation_name        | String    | FIELD         |
+| another_catalog | information_schema | schemata         | sql_path                      | String    | FIELD         |
+| another_catalog | information_schema | tables           | table_catalog                 | String    | FIELD         |
+| another_catalog | information_schema | tables           | table_schema                  | String    | FIELD         |
+| another_catalog | information_schema | tables           | table_name                    | String    | FIELD         |
+| another_catalog | information_schema | tables           | table_type                    | String    | FIELD         |
+| another_catalog | information_schema | tables           | table_id                      | UInt32    | FIELD         |
+| another_catalog | information_schema | tables           | engine                        | String    | FIELD         |
++-----------------+--------------------+------------------+-------------------------------+-----------+---------------+";
 
     check_output_stream(output, expected).await;
 }
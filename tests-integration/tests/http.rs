@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use auth::user_provider_from_option;
 use axum::http::StatusCode;
@@ -491,6 +491,107 @@ pub async fn test_prom_http_api(store_type: StorageType) {
     assert!(prom_resp.error.is_none());
     assert!(prom_resp.error_type.is_none());
 
+    // metadata
+    let res = client
+        .get("/v1/sql?sql=create table logs(message string, ts timestamp time index)")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let res = client.get("/v1/prometheus/api/v1/metadata").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    let PrometheusResponse::Metadata(metadata) = body.data else {
+        unreachable!()
+    };
+    assert_eq!(metadata["demo"][0].metric_type, "unknown");
+    // tables that don't hold metrics are skipped
+    assert!(!metadata.contains_key("logs"));
+    assert!(!metadata.contains_key("numbers"));
+    let res = client
+        .get("/v1/prometheus/api/v1/metadata?limit=1")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    let PrometheusResponse::Metadata(metadata) = body.data else {
+        unreachable!()
+    };
+    assert_eq!(metadata.len(), 1);
+    let res = client
+        .get("/v1/prometheus/api/v1/metadata?metric=not_exist")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    assert_eq!(body.data, PrometheusResponse::Metadata(HashMap::new()));
+
+    // build info
+    let res = client
+        .get("/v1/prometheus/api/v1/status/buildinfo")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    assert!(matches!(body.data, PrometheusResponse::BuildInfo(_)));
+
+    // rules and alerts, no rule file is configured
+    let res = client.get("/v1/prometheus/api/v1/rules").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    assert_eq!(
+        body.data,
+        serde_json::from_value::<PrometheusResponse>(json!({ "groups": [] })).unwrap()
+    );
+    let res = client
+        .get("/v1/prometheus/api/v1/rules?type=unknown")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let prom_resp = res.json::<PrometheusJsonResponse>().await;
+    assert_eq!(prom_resp.status, "error");
+    let res = client.get("/v1/prometheus/api/v1/alerts").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    assert_eq!(
+        body.data,
+        serde_json::from_value::<PrometheusResponse>(json!({ "alerts": [] })).unwrap()
+    );
+
+    // exemplars
+    let res = client
+        .get("/v1/prometheus/api/v1/query_exemplars?query=demo&start=0&end=100")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    let res = client
+        .get("/v1/prometheus/api/v1/query_exemplars?query=demo%7B")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let prom_resp = res.json::<PrometheusJsonResponse>().await;
+    assert_eq!(prom_resp.status, "error");
+
+    // format query
+    let res = client
+        .post("/v1/prometheus/api/v1/format_query?query=%20sum(demo)%20")
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<PrometheusJsonResponse>(&res.text().await).unwrap();
+    assert_eq!(body.status, "success");
+    assert_eq!(
+        body.data,
+        PrometheusResponse::FormatQuery("sum(demo)".to_string())
+    );
+
     guard.remove_all().await;
 }
 
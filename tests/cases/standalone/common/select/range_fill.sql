@@ -0,0 +1,31 @@
+CREATE TABLE host (
+  ts timestamp(3) time index,
+  host STRING PRIMARY KEY,
+  val DOUBLE,
+);
+
+INSERT INTO TABLE host VALUES
+    (0,     'host1', 0.0),
+    (15000, 'host1', 3.0),
+    (20000, 'host1', 4.0),
+    (25000, 'host1', 5.0),
+    (35000, 'host1', 7.0),
+    (40000, 'host1', 8.0),
+    (0,     'host2', 9.0),
+    (15000, 'host2', 12.0),
+    (20000, 'host2', 13.0),
+    (25000, 'host2', 14.0),
+    (35000, 'host2', 16.0),
+    (40000, 'host2', 17.0);
+
+SELECT min(val) RANGE '5s' FILL NULL, ts, host FROM host ALIGN '5s' ORDER BY host, ts;
+
+SELECT min(val) RANGE '5s' FILL PREV, ts, host FROM host ALIGN '5s' ORDER BY host, ts;
+
+SELECT min(val) RANGE '5s' FILL LINEAR, ts, host FROM host ALIGN '5s' ORDER BY host, ts;
+
+SELECT min(val) RANGE '5s' FILL 6.0, ts, host FROM host ALIGN '5s' ORDER BY host, ts;
+
+SELECT min(val) RANGE '5s' FILL NULL, max(val) RANGE '5s' FILL PREV, ts, host FROM host ALIGN '5s' ORDER BY host, ts;
+
+DROP TABLE host;
@@ -35,6 +35,19 @@ where table_catalog = 'greptime'
   and table_schema != 'information_schema'
 order by table_schema, table_name;
 
+select engine, support, transactions, xa, savepoints
+from information_schema.engines
+order by engine;
+
+select catalog_name, schema_name, default_character_set_name, default_collation_name
+from information_schema.schemata
+where schema_name = 'my_db';
+
+select constraint_name, table_schema, table_name, column_name, ordinal_position
+from information_schema.key_column_usage
+where table_schema = 'my_db'
+order by table_name, constraint_name, ordinal_position;
+
 use public;
 
 drop schema my_db;